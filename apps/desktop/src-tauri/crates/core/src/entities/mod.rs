@@ -2,5 +2,6 @@
 
 pub mod app_setting;
 pub mod exchange_rate;
+pub mod exchange_rate_history;
 pub mod prelude;
 pub mod verified_session;
@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExchangeRateHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ExchangeRateHistory::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ExchangeRateHistory::FromCurrency)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExchangeRateHistory::ToCurrency)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExchangeRateHistory::Rate)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ExchangeRateHistory::Source)
+                            .text()
+                            .not_null()
+                            .default("api"),
+                    )
+                    .col(
+                        ColumnDef::new(ExchangeRateHistory::RecordedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Seed with the current rates so existing pairs have a starting point
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO exchange_rate_history (from_currency, to_currency, rate, source, recorded_at)
+                 SELECT from_currency, to_currency, rate, source, fetched_at FROM exchange_rates",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_exchange_rate_history_pair_recorded_at")
+                    .table(ExchangeRateHistory::Table)
+                    .col(ExchangeRateHistory::FromCurrency)
+                    .col(ExchangeRateHistory::ToCurrency)
+                    .col(ExchangeRateHistory::RecordedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ExchangeRateHistory::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ExchangeRateHistory {
+    #[iden = "exchange_rate_history"]
+    Table,
+    Id,
+    FromCurrency,
+    ToCurrency,
+    Rate,
+    Source,
+    RecordedAt,
+}
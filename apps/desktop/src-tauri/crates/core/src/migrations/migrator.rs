@@ -9,6 +9,7 @@ use super::m20250208_000001_drop_old_settings_table;
 use super::m20250214_000001_create_verified_sessions;
 use super::m20260216_000001_create_exchange_rates_table;
 use super::m20260216_000002_rename_tables_to_plural;
+use super::m20260226_000001_create_exchange_rate_history_table;
 
 pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
     vec![
@@ -21,5 +22,6 @@ pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         Box::new(m20250214_000001_create_verified_sessions::Migration),
         Box::new(m20260216_000001_create_exchange_rates_table::Migration),
         Box::new(m20260216_000002_rename_tables_to_plural::Migration),
+        Box::new(m20260226_000001_create_exchange_rate_history_table::Migration),
     ]
 }
@@ -1,8 +1,11 @@
+use chrono::{DateTime, Utc};
 use sea_orm::{
-    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Statement,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set, Statement,
 };
 
 use crate::entities::exchange_rate::{self, Entity as ExchangeRate};
+use crate::entities::exchange_rate_history::{self, Entity as ExchangeRateHistory};
 use crate::error::AppError;
 
 pub struct ExchangeRateRepository;
@@ -37,7 +40,11 @@ impl ExchangeRateRepository {
         Ok(result)
     }
 
-    /// Upsert an exchange rate (insert or update on conflict)
+    /// Upsert an exchange rate (insert or update on conflict).
+    ///
+    /// An `"api"` rate never replaces an existing `"manual"` rate for the same pair,
+    /// so user-pinned rates survive background refreshes. A manual rate always wins.
+    /// Every rate that is stored is also appended to the rate history.
     pub async fn upsert_rate(
         conn: &DatabaseConnection,
         from: &str,
@@ -45,25 +52,32 @@ impl ExchangeRateRepository {
         rate: f64,
         source: &str,
     ) -> Result<exchange_rate::Model, AppError> {
-        let now = chrono::Utc::now().to_rfc3339();
+        let recorded_at = chrono::Utc::now();
+        let now = recorded_at.to_rfc3339();
 
-        conn.execute(Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::Sqlite,
-            r#"INSERT INTO exchange_rates (from_currency, to_currency, rate, source, fetched_at)
+        let result = conn
+            .execute(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                r#"INSERT INTO exchange_rates (from_currency, to_currency, rate, source, fetched_at)
                VALUES ($1, $2, $3, $4, $5)
                ON CONFLICT(from_currency, to_currency) DO UPDATE SET
                    rate = excluded.rate,
                    source = excluded.source,
-                   fetched_at = excluded.fetched_at"#,
-            [
-                from.into(),
-                to.into(),
-                rate.into(),
-                source.into(),
-                now.into(),
-            ],
-        ))
-        .await?;
+                   fetched_at = excluded.fetched_at
+               WHERE excluded.source = 'manual' OR exchange_rates.source != 'manual'"#,
+                [
+                    from.into(),
+                    to.into(),
+                    rate.into(),
+                    source.into(),
+                    now.into(),
+                ],
+            ))
+            .await?;
+
+        if result.rows_affected() > 0 {
+            Self::record_history(conn, from, to, rate, source, recorded_at).await?;
+        }
 
         // Return the upserted row
         Self::find_rate(conn, from, to)
@@ -71,6 +85,41 @@ impl ExchangeRateRepository {
             .ok_or_else(|| AppError::Internal("Failed to retrieve upserted exchange rate".into()))
     }
 
+    /// Append a rate to the history of a currency pair
+    pub async fn record_history(
+        conn: &DatabaseConnection,
+        from: &str,
+        to: &str,
+        rate: f64,
+        source: &str,
+        recorded_at: DateTime<Utc>,
+    ) -> Result<exchange_rate_history::Model, AppError> {
+        let active_model = exchange_rate_history::ActiveModel {
+            from_currency: Set(from.to_string()),
+            to_currency: Set(to.to_string()),
+            rate: Set(rate),
+            source: Set(source.to_string()),
+            recorded_at: Set(recorded_at),
+            ..Default::default()
+        };
+        Ok(active_model.insert(conn).await?)
+    }
+
+    /// Find every recorded rate for a currency pair, oldest first
+    pub async fn find_history(
+        conn: &DatabaseConnection,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<exchange_rate_history::Model>, AppError> {
+        let history = ExchangeRateHistory::find()
+            .filter(exchange_rate_history::Column::FromCurrency.eq(from))
+            .filter(exchange_rate_history::Column::ToCurrency.eq(to))
+            .order_by_asc(exchange_rate_history::Column::RecordedAt)
+            .all(conn)
+            .await?;
+        Ok(history)
+    }
+
     /// Find all exchange rates
     pub async fn find_all(
         conn: &DatabaseConnection,
@@ -162,6 +211,27 @@ mod tests {
         assert!((manual.unwrap().rate - 1.6).abs() < 0.001);
     }
 
+    #[tokio::test]
+    async fn test_api_upsert_does_not_replace_manual_rate() {
+        let conn = setup_app_settings_db().await;
+
+        ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.6, "manual")
+            .await
+            .unwrap();
+        let rate = ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+            .await
+            .unwrap();
+
+        assert_eq!(rate.source, "manual");
+        assert!((rate.rate - 1.6).abs() < 0.001);
+
+        // A new manual rate still replaces the old one
+        let rate = ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.7, "manual")
+            .await
+            .unwrap();
+        assert!((rate.rate - 1.7).abs() < 0.001);
+    }
+
     #[tokio::test]
     async fn test_find_rate_not_found() {
         let conn = setup_app_settings_db().await;
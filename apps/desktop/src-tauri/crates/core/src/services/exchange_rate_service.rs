@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use sea_orm::DatabaseConnection;
 use serde::Deserialize;
 
@@ -147,6 +148,45 @@ impl ExchangeRateService {
         )))
     }
 
+    /// Get every recorded rate for a currency pair as `(recorded_at, rate)`, oldest first.
+    ///
+    /// Identity (same currency) returns an empty history; callers use 1.0 for it.
+    /// Currency codes are normalized to uppercase before DB lookups.
+    pub async fn get_rate_history(
+        conn: &DatabaseConnection,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<(DateTime<Utc>, f64)>, AppError> {
+        let from = from.to_ascii_uppercase();
+        let to = to.to_ascii_uppercase();
+        if from == to {
+            return Ok(Vec::new());
+        }
+
+        let history = ExchangeRateRepository::find_history(conn, &from, &to).await?;
+        Ok(history
+            .into_iter()
+            .map(|entry| (entry.recorded_at, entry.rate))
+            .collect())
+    }
+
+    /// Pick the rate recorded nearest to `at` from a history sorted oldest first.
+    pub fn nearest_rate(history: &[(DateTime<Utc>, f64)], at: DateTime<Utc>) -> Option<f64> {
+        let after = history.partition_point(|(recorded_at, _)| *recorded_at < at);
+        let before = after.checked_sub(1).map(|i| history[i]);
+        match (before, history.get(after)) {
+            (Some((before_at, before_rate)), Some(&(after_at, after_rate))) => {
+                if at - before_at <= after_at - at {
+                    Some(before_rate)
+                } else {
+                    Some(after_rate)
+                }
+            }
+            (Some((_, rate)), None) | (None, Some(&(_, rate))) => Some(rate),
+            (None, None) => None,
+        }
+    }
+
     /// Pure conversion function: convert minor units from one currency to another.
     /// Handles different currency exponents (e.g., JPY has 0 decimals, USD has 2).
     pub fn convert_minor_units(amount: i64, rate: f64, from_exp: u32, to_exp: u32) -> i64 {
@@ -155,7 +195,10 @@ impl ExchangeRateService {
         (converted_major * 10_f64.powi(to_exp as i32)).round() as i64
     }
 
-    /// Set a manual exchange rate override
+    /// Set a manual exchange rate override.
+    ///
+    /// Manual rates are pinned: later API refreshes leave them untouched until
+    /// the rate is deleted.
     pub async fn set_manual_rate(
         conn: &DatabaseConnection,
         from: &str,
@@ -230,6 +273,38 @@ mod tests {
         let result = ExchangeRateService::convert_minor_units(999, 1.5, 2, 2);
         assert_eq!(result, 1499);
     }
+
+    #[test]
+    fn test_nearest_rate() {
+        use chrono::TimeZone;
+
+        let day = |d| Utc.with_ymd_and_hms(2026, 1, d, 0, 0, 0).unwrap();
+        let history = vec![(day(1), 1.5), (day(10), 1.6)];
+
+        assert_eq!(ExchangeRateService::nearest_rate(&[], day(5)), None);
+        // Before the first and after the last recorded rate
+        assert_eq!(
+            ExchangeRateService::nearest_rate(&history, day(1)),
+            Some(1.5)
+        );
+        assert_eq!(
+            ExchangeRateService::nearest_rate(&history, day(20)),
+            Some(1.6)
+        );
+        // Between two rates, the closer one wins (ties go to the earlier)
+        assert_eq!(
+            ExchangeRateService::nearest_rate(&history, day(4)),
+            Some(1.5)
+        );
+        assert_eq!(
+            ExchangeRateService::nearest_rate(&history, day(8)),
+            Some(1.6)
+        );
+        assert_eq!(
+            ExchangeRateService::nearest_rate(&history, day(5) + chrono::Duration::hours(12)),
+            Some(1.5)
+        );
+    }
 }
 
 #[cfg(test)]
@@ -309,6 +384,25 @@ mod integration_tests {
         assert!((rate - 1.6).abs() < 0.001);
     }
 
+    #[tokio::test]
+    async fn test_manual_rate_survives_api_refresh() {
+        let conn = setup_app_settings_db().await;
+
+        ExchangeRateService::set_manual_rate(&conn, "usd", "aud", 1.6)
+            .await
+            .unwrap();
+
+        // Simulate a refresh storing a fresh API rate for the same pair
+        ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+            .await
+            .unwrap();
+
+        let rate = ExchangeRateService::get_rate(&conn, "USD", "AUD")
+            .await
+            .unwrap();
+        assert!((rate - 1.6).abs() < 0.001);
+    }
+
     #[tokio::test]
     async fn test_set_manual_rate_rejects_zero() {
         let conn = setup_app_settings_db().await;
@@ -338,6 +432,33 @@ mod integration_tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_get_rate_history_records_each_stored_rate() {
+        let conn = setup_app_settings_db().await;
+        ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+            .await
+            .unwrap();
+        ExchangeRateService::set_manual_rate(&conn, "usd", "aud", 1.6)
+            .await
+            .unwrap();
+        // Ignored: a pinned manual rate is never replaced by an API refresh
+        ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.4, "api")
+            .await
+            .unwrap();
+
+        let history = ExchangeRateService::get_rate_history(&conn, "usd", "aud")
+            .await
+            .unwrap();
+        let rates: Vec<f64> = history.iter().map(|(_, rate)| *rate).collect();
+        assert_eq!(rates, vec![1.5, 1.6]);
+        assert!(history[0].0 <= history[1].0);
+
+        let identity = ExchangeRateService::get_rate_history(&conn, "AUD", "AUD")
+            .await
+            .unwrap();
+        assert!(identity.is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_all() {
         let conn = setup_app_settings_db().await;
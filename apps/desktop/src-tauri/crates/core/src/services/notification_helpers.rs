@@ -3,6 +3,8 @@
 //! This module provides Tauri-agnostic notification data structures
 //! that can be used by the domain layer for building notification content.
 
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
 use serde::Serialize;
 
 /// Data needed to display a notification (Tauri-agnostic)
@@ -10,6 +12,12 @@ use serde::Serialize;
 pub struct NotificationData {
     pub title: String,
     pub body: String,
+    /// Product the notification is about; `None` for multi-product summaries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    /// In-app route to open when the notification is clicked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action_url: Option<String>,
 }
 
 impl NotificationData {
@@ -18,19 +26,109 @@ impl NotificationData {
         Self {
             title: title.into(),
             body: body.into(),
+            product_id: None,
+            action_url: None,
         }
     }
+
+    /// Link the notification to a product, so clicking it opens the product's page
+    pub fn for_product(mut self, product_id: impl ToString) -> Self {
+        let product_id = product_id.to_string();
+        self.action_url = Some(format!("/products/{}", product_id));
+        self.product_id = Some(product_id);
+        self
+    }
+}
+
+/// Daily window during which notifications are suppressed.
+///
+/// `start` is inclusive and `end` exclusive. A window whose end is before its
+/// start crosses midnight (e.g. 22:00-07:00).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Format of quiet hours settings (24-hour "HH:MM")
+    const TIME_FORMAT: &'static str = "%H:%M";
+
+    /// Parse a "HH:MM" time of day, as stored in the quiet hours settings
+    pub fn parse_time(value: &str) -> Option<NaiveTime> {
+        if value.len() != 5 {
+            return None;
+        }
+        NaiveTime::parse_from_str(value, Self::TIME_FORMAT).ok()
+    }
+
+    /// Build quiet hours from "HH:MM" settings.
+    ///
+    /// Returns `None` (quiet hours disabled) if either value is empty or invalid,
+    /// or if start and end are equal.
+    pub fn from_settings(start: &str, end: &str) -> Option<Self> {
+        let start = Self::parse_time(start)?;
+        let end = Self::parse_time(end)?;
+        (start != end).then_some(Self { start, end })
+    }
+
+    /// Whether the given local time of day falls inside the window
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// Whether `now` falls inside the window in the given display timezone.
+    ///
+    /// `"auto"` (or an unrecognised timezone) uses the system's local time.
+    pub fn is_active_at(&self, now: DateTime<Utc>, timezone: &str) -> bool {
+        self.contains(to_display_time(now, timezone).time())
+    }
+}
+
+/// Convert `now` to wall-clock time in the given display timezone.
+///
+/// `"auto"` (or an unrecognised timezone) uses the system's local time.
+pub fn to_display_time(now: DateTime<Utc>, timezone: &str) -> NaiveDateTime {
+    match timezone.parse::<Tz>() {
+        Ok(tz) => now.with_timezone(&tz).naive_local(),
+        Err(_) => now.with_timezone(&Local).naive_local(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+
+    fn time(value: &str) -> NaiveTime {
+        QuietHours::parse_time(value).unwrap()
+    }
 
     #[test]
     fn test_notification_data_new() {
         let notification = NotificationData::new("Test Title", "Test Body");
         assert_eq!(notification.title, "Test Title");
         assert_eq!(notification.body, "Test Body");
+        assert_eq!(notification.product_id, None);
+        assert_eq!(notification.action_url, None);
+    }
+
+    #[test]
+    fn test_notification_data_for_product() {
+        let notification = NotificationData::new("Title", "Body").for_product("abc-123");
+        assert_eq!(notification.product_id.as_deref(), Some("abc-123"));
+        assert_eq!(
+            notification.action_url.as_deref(),
+            Some("/products/abc-123")
+        );
+
+        let json = serde_json::to_string(&notification).unwrap();
+        assert!(json.contains("\"product_id\":\"abc-123\""));
+        assert!(json.contains("\"action_url\":\"/products/abc-123\""));
     }
 
     #[test]
@@ -48,4 +146,66 @@ mod tests {
         assert_eq!(cloned.title, "Original");
         assert_eq!(cloned.body, "Content");
     }
+
+    #[test]
+    fn test_quiet_hours_parse_time() {
+        assert_eq!(
+            QuietHours::parse_time("07:30"),
+            NaiveTime::from_hms_opt(7, 30, 0)
+        );
+        assert!(QuietHours::parse_time("").is_none());
+        assert!(QuietHours::parse_time("7:30").is_none());
+        assert!(QuietHours::parse_time("24:00").is_none());
+        assert!(QuietHours::parse_time("12:60").is_none());
+        assert!(QuietHours::parse_time("noon").is_none());
+    }
+
+    #[test]
+    fn test_quiet_hours_from_settings_disabled() {
+        assert!(QuietHours::from_settings("", "").is_none());
+        assert!(QuietHours::from_settings("22:00", "").is_none());
+        assert!(QuietHours::from_settings("22:00", "22:00").is_none());
+    }
+
+    #[test]
+    fn test_quiet_hours_same_day_window() {
+        let quiet = QuietHours::from_settings("13:00", "15:30").unwrap();
+        assert!(quiet.contains(time("13:00")));
+        assert!(quiet.contains(time("15:29")));
+        assert!(!quiet.contains(time("15:30")));
+        assert!(!quiet.contains(time("12:59")));
+        assert!(!quiet.contains(time("03:00")));
+    }
+
+    #[test]
+    fn test_quiet_hours_crossing_midnight() {
+        let quiet = QuietHours::from_settings("22:00", "07:00").unwrap();
+        assert!(quiet.contains(time("22:00")));
+        assert!(quiet.contains(time("23:59")));
+        assert!(quiet.contains(time("00:00")));
+        assert!(quiet.contains(time("03:00")));
+        assert!(quiet.contains(time("06:59")));
+        assert!(!quiet.contains(time("07:00")));
+        assert!(!quiet.contains(time("12:00")));
+        assert!(!quiet.contains(time("21:59")));
+    }
+
+    #[test]
+    fn test_quiet_hours_use_display_timezone() {
+        let quiet = QuietHours::from_settings("22:00", "07:00").unwrap();
+        // 16:00 UTC is 03:00 the next day in Sydney (AEDT, UTC+11)
+        let now = Utc.with_ymd_and_hms(2026, 1, 15, 16, 0, 0).unwrap();
+        assert!(quiet.is_active_at(now, "Australia/Sydney"));
+        // ...and 11:00 in New York (EST, UTC-5)
+        assert!(!quiet.is_active_at(now, "America/New_York"));
+    }
+
+    #[test]
+    fn test_to_display_time_moves_date_with_timezone() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 15, 16, 0, 0).unwrap();
+        let sydney = to_display_time(now, "Australia/Sydney");
+        assert_eq!(sydney.to_string(), "2026-01-16 03:00:00");
+        let new_york = to_display_time(now, "America/New_York");
+        assert_eq!(new_york.to_string(), "2026-01-15 11:00:00");
+    }
 }
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::entities::app_setting::SettingScope;
 use crate::error::AppError;
 use crate::repositories::{ScopedSettingsReader, SettingsHelpers};
+use crate::services::notification_helpers::QuietHours;
 
 /// Setting keys for global settings
 pub mod keys {
@@ -19,6 +20,11 @@ pub mod keys {
     pub const DISPLAY_TIMEZONE: &str = "display_timezone";
     pub const DATE_FORMAT: &str = "date_format";
     pub const PREFERRED_CURRENCY: &str = "preferred_currency";
+    pub const NOTIFICATION_WEBHOOK_URL: &str = "notification_webhook_url";
+    pub const QUIET_HOURS_START: &str = "quiet_hours_start";
+    pub const QUIET_HOURS_END: &str = "quiet_hours_end";
+    pub const NOTIFICATION_MODE: &str = "notification_mode";
+    pub const DIGEST_INTERVAL: &str = "digest_interval";
 }
 
 /// Default values for settings
@@ -34,6 +40,13 @@ pub mod defaults {
     pub const DISPLAY_TIMEZONE: &str = "auto";
     pub const DATE_FORMAT: &str = "system";
     pub const PREFERRED_CURRENCY: &str = "AUD";
+    /// Empty = webhook notifications disabled
+    pub const NOTIFICATION_WEBHOOK_URL: &str = "";
+    /// Empty = quiet hours disabled
+    pub const QUIET_HOURS_START: &str = "";
+    pub const QUIET_HOURS_END: &str = "";
+    pub const NOTIFICATION_MODE: &str = "per_event";
+    pub const DIGEST_INTERVAL: &str = "daily";
 }
 
 /// Settings model returned by the service
@@ -53,9 +66,45 @@ pub struct Settings {
     pub display_timezone: String,
     pub date_format: String,
     pub preferred_currency: String,
+    pub notification_webhook_url: String,
+    /// Start of the daily quiet hours ("HH:MM" in `display_timezone`, empty = disabled)
+    pub quiet_hours_start: String,
+    /// End of the daily quiet hours ("HH:MM", exclusive; may be before the start)
+    pub quiet_hours_end: String,
+    /// "per_event" notifies on each check, "digest" sends one summary per `digest_interval`
+    pub notification_mode: String,
+    /// How often the digest is sent in digest mode ("daily" or "weekly")
+    pub digest_interval: String,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Settings {
+    /// Configured quiet hours, if enabled
+    pub fn quiet_hours(&self) -> Option<QuietHours> {
+        QuietHours::from_settings(&self.quiet_hours_start, &self.quiet_hours_end)
+    }
+
+    /// Whether notifications should be suppressed at `now` because of quiet hours
+    pub fn is_quiet_time(&self, now: DateTime<Utc>) -> bool {
+        self.quiet_hours()
+            .is_some_and(|quiet| quiet.is_active_at(now, &self.display_timezone))
+    }
+
+    /// Whether background check changes are collected into a periodic digest
+    /// instead of notifying on each check
+    pub fn is_digest_mode(&self) -> bool {
+        self.notification_mode == "digest"
+    }
+
+    /// Time between digest notifications
+    pub fn digest_period(&self) -> chrono::Duration {
+        match self.digest_interval.as_str() {
+            "weekly" => chrono::Duration::weeks(1),
+            _ => chrono::Duration::days(1),
+        }
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
@@ -70,6 +119,11 @@ impl Default for Settings {
             display_timezone: defaults::DISPLAY_TIMEZONE.to_string(),
             date_format: defaults::DATE_FORMAT.to_string(),
             preferred_currency: defaults::PREFERRED_CURRENCY.to_string(),
+            notification_webhook_url: defaults::NOTIFICATION_WEBHOOK_URL.to_string(),
+            quiet_hours_start: defaults::QUIET_HOURS_START.to_string(),
+            quiet_hours_end: defaults::QUIET_HOURS_END.to_string(),
+            notification_mode: defaults::NOTIFICATION_MODE.to_string(),
+            digest_interval: defaults::DIGEST_INTERVAL.to_string(),
             updated_at: Utc::now(),
         }
     }
@@ -77,6 +131,7 @@ impl Default for Settings {
 
 /// Parameters for updating settings (all fields optional for partial updates)
 #[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct UpdateSettingsParams {
     pub theme: Option<String>,
     pub show_in_tray: Option<bool>,
@@ -89,6 +144,11 @@ pub struct UpdateSettingsParams {
     pub display_timezone: Option<String>,
     pub date_format: Option<String>,
     pub preferred_currency: Option<String>,
+    pub notification_webhook_url: Option<String>,
+    pub quiet_hours_start: Option<String>,
+    pub quiet_hours_end: Option<String>,
+    pub notification_mode: Option<String>,
+    pub digest_interval: Option<String>,
 }
 
 /// Cached settings for bulk operations.
@@ -156,6 +216,16 @@ impl SettingsCache {
         &self.settings.preferred_currency
     }
 
+    /// Get the notification webhook URL (empty when not configured)
+    pub fn notification_webhook_url(&self) -> &str {
+        &self.settings.notification_webhook_url
+    }
+
+    /// Check if notifications are suppressed at `now` by quiet hours
+    pub fn is_quiet_time(&self, now: DateTime<Utc>) -> bool {
+        self.settings.is_quiet_time(now)
+    }
+
     /// Get when these settings were loaded
     pub fn loaded_at(&self) -> DateTime<Utc> {
         self.loaded_at
@@ -204,6 +274,24 @@ impl SettingService {
             preferred_currency: r
                 .string(keys::PREFERRED_CURRENCY, defaults::PREFERRED_CURRENCY)
                 .await?,
+            notification_webhook_url: r
+                .string(
+                    keys::NOTIFICATION_WEBHOOK_URL,
+                    defaults::NOTIFICATION_WEBHOOK_URL,
+                )
+                .await?,
+            quiet_hours_start: r
+                .string(keys::QUIET_HOURS_START, defaults::QUIET_HOURS_START)
+                .await?,
+            quiet_hours_end: r
+                .string(keys::QUIET_HOURS_END, defaults::QUIET_HOURS_END)
+                .await?,
+            notification_mode: r
+                .string(keys::NOTIFICATION_MODE, defaults::NOTIFICATION_MODE)
+                .await?,
+            digest_interval: r
+                .string(keys::DIGEST_INTERVAL, defaults::DIGEST_INTERVAL)
+                .await?,
             updated_at: Utc::now(),
         })
     }
@@ -235,6 +323,21 @@ impl SettingService {
         if let Some(ref currency) = params.preferred_currency {
             Self::validate_preferred_currency(currency)?;
         }
+        if let Some(ref url) = params.notification_webhook_url {
+            Self::validate_webhook_url(url)?;
+        }
+        if let Some(ref time) = params.quiet_hours_start {
+            Self::validate_quiet_hours_time(time)?;
+        }
+        if let Some(ref time) = params.quiet_hours_end {
+            Self::validate_quiet_hours_time(time)?;
+        }
+        if let Some(ref mode) = params.notification_mode {
+            Self::validate_notification_mode(mode)?;
+        }
+        if let Some(ref interval) = params.digest_interval {
+            Self::validate_digest_interval(interval)?;
+        }
 
         let scope = SettingScope::Global;
 
@@ -268,6 +371,33 @@ impl SettingService {
             params.enable_notifications,
         )
         .await?;
+        Self::persist_optional_string(
+            conn,
+            &scope,
+            keys::NOTIFICATION_WEBHOOK_URL,
+            params
+                .notification_webhook_url
+                .map(|url| url.trim().to_string()),
+        )
+        .await?;
+        Self::persist_optional_string(
+            conn,
+            &scope,
+            keys::QUIET_HOURS_START,
+            params.quiet_hours_start,
+        )
+        .await?;
+        Self::persist_optional_string(conn, &scope, keys::QUIET_HOURS_END, params.quiet_hours_end)
+            .await?;
+        Self::persist_optional_string(
+            conn,
+            &scope,
+            keys::NOTIFICATION_MODE,
+            params.notification_mode,
+        )
+        .await?;
+        Self::persist_optional_string(conn, &scope, keys::DIGEST_INTERVAL, params.digest_interval)
+            .await?;
 
         // Display
         Self::persist_optional_string(
@@ -291,6 +421,31 @@ impl SettingService {
         Self::get(conn).await
     }
 
+    /// Export the current settings as pretty-printed JSON, for restoring after a reinstall
+    pub async fn export(conn: &DatabaseConnection) -> Result<String, AppError> {
+        let settings = Self::get(conn).await?;
+        serde_json::to_string_pretty(&settings)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {}", e)))
+    }
+
+    /// Apply settings previously produced by [`Self::export`]
+    ///
+    /// Missing keys keep their current value; unknown keys are rejected so a
+    /// typo or a file from another app isn't silently ignored. Values are
+    /// validated like any other update, so nothing is written if one is invalid.
+    pub async fn import(conn: &DatabaseConnection, json: &str) -> Result<Settings, AppError> {
+        let mut value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| AppError::Validation(format!("Invalid settings JSON: {}", e)))?;
+        // `updated_at` is exported for reference but isn't a setting
+        if let Some(map) = value.as_object_mut() {
+            map.remove("updated_at");
+        }
+        let params = UpdateSettingsParams::deserialize(value)
+            .map_err(|e| AppError::Validation(format!("Invalid settings: {}", e)))?;
+
+        Self::update(conn, params).await
+    }
+
     /// Persist an optional string setting (no-op if `None`)
     async fn persist_optional_string(
         conn: &DatabaseConnection,
@@ -400,6 +555,52 @@ impl SettingService {
         }
     }
 
+    /// An empty URL disables webhooks; otherwise it must be an absolute http(s) URL
+    fn validate_webhook_url(url: &str) -> Result<(), AppError> {
+        let url = url.trim();
+        if url.is_empty() {
+            return Ok(());
+        }
+        match reqwest::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+            _ => Err(AppError::Validation(format!(
+                "Invalid webhook URL: {}. Must be an http or https URL",
+                url
+            ))),
+        }
+    }
+
+    /// Quiet hours times are "HH:MM" (24-hour); empty disables quiet hours
+    fn validate_quiet_hours_time(time: &str) -> Result<(), AppError> {
+        if time.is_empty() || QuietHours::parse_time(time).is_some() {
+            return Ok(());
+        }
+        Err(AppError::Validation(format!(
+            "Invalid quiet hours time: {}. Must be HH:MM (24-hour)",
+            time
+        )))
+    }
+
+    fn validate_notification_mode(mode: &str) -> Result<(), AppError> {
+        match mode {
+            "per_event" | "digest" => Ok(()),
+            _ => Err(AppError::Validation(format!(
+                "Invalid notification mode: {}. Must be 'per_event' or 'digest'",
+                mode
+            ))),
+        }
+    }
+
+    fn validate_digest_interval(interval: &str) -> Result<(), AppError> {
+        match interval {
+            "daily" | "weekly" => Ok(()),
+            _ => Err(AppError::Validation(format!(
+                "Invalid digest interval: {}. Must be 'daily' or 'weekly'",
+                interval
+            ))),
+        }
+    }
+
     fn validate_date_format(format: &str) -> Result<(), AppError> {
         match format {
             "system" | "MM/DD/YYYY" | "DD/MM/YYYY" | "YYYY-MM-DD" => Ok(()),
@@ -465,6 +666,13 @@ mod tests {
         assert_eq!(settings.display_timezone, "auto");
         assert_eq!(settings.date_format, "system");
         assert_eq!(settings.preferred_currency, "AUD");
+        assert_eq!(settings.notification_webhook_url, "");
+        assert_eq!(settings.quiet_hours_start, "");
+        assert_eq!(settings.quiet_hours_end, "");
+        assert!(settings.quiet_hours().is_none());
+        assert_eq!(settings.notification_mode, "per_event");
+        assert_eq!(settings.digest_interval, "daily");
+        assert!(!settings.is_digest_mode());
     }
 
     #[test]
@@ -608,6 +816,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_webhook_url_accepts_empty_and_http() {
+        assert!(SettingService::validate_webhook_url("").is_ok());
+        assert!(SettingService::validate_webhook_url("  ").is_ok());
+        assert!(
+            SettingService::validate_webhook_url("https://discord.com/api/webhooks/1/abc").is_ok()
+        );
+        assert!(SettingService::validate_webhook_url("http://localhost:8080/hook").is_ok());
+    }
+
+    #[test]
+    fn test_validate_webhook_url_rejects_invalid() {
+        assert!(SettingService::validate_webhook_url("not a url").is_err());
+        assert!(SettingService::validate_webhook_url("ftp://example.com/hook").is_err());
+    }
+
     #[test]
     fn test_validate_date_format_accepts_system() {
         assert!(SettingService::validate_date_format("system").is_ok());
@@ -631,6 +855,7 @@ mod tests {
 mod integration_tests {
     use super::*;
     use crate::test_utils::setup_app_settings_db;
+    use chrono::TimeZone;
 
     #[tokio::test]
     async fn test_get_returns_defaults() {
@@ -687,6 +912,81 @@ mod integration_tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let conn = setup_app_settings_db().await;
+        SettingService::update(
+            &conn,
+            UpdateSettingsParams {
+                theme: Some("dark".to_string()),
+                log_level: Some("debug".to_string()),
+                launch_at_login: Some(true),
+                preferred_currency: Some("USD".to_string()),
+                quiet_hours_start: Some("22:00".to_string()),
+                quiet_hours_end: Some("07:00".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let exported = SettingService::export(&conn).await.unwrap();
+
+        let fresh = setup_app_settings_db().await;
+        let imported = SettingService::import(&fresh, &exported).await.unwrap();
+
+        let original = SettingService::get(&conn).await.unwrap();
+        assert_eq!(
+            Settings {
+                updated_at: original.updated_at,
+                ..imported
+            },
+            original
+        );
+        assert_eq!(SettingService::get(&fresh).await.unwrap().theme, "dark");
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_invalid_log_level() {
+        let conn = setup_app_settings_db().await;
+
+        let result =
+            SettingService::import(&conn, r#"{"theme":"dark","log_level":"verbose"}"#).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        // Nothing is written when any value is invalid
+        assert_eq!(SettingService::get(&conn).await.unwrap().theme, "system");
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_unknown_keys() {
+        let conn = setup_app_settings_db().await;
+
+        let result = SettingService::import(&conn, r#"{"theme":"dark","colour":"red"}"#).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_malformed_json() {
+        let conn = setup_app_settings_db().await;
+
+        let result = SettingService::import(&conn, "not json").await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_import_partial_keeps_other_settings() {
+        let conn = setup_app_settings_db().await;
+
+        let settings = SettingService::import(&conn, r#"{"show_in_tray":false}"#)
+            .await
+            .unwrap();
+
+        assert!(!settings.show_in_tray);
+        assert_eq!(settings.theme, "system");
+    }
+
     #[tokio::test]
     async fn test_update_theme_success() {
         let conn = setup_app_settings_db().await;
@@ -740,6 +1040,11 @@ mod integration_tests {
             color_palette: Some("ocean".to_string()),
             display_timezone: Some("Asia/Tokyo".to_string()),
             date_format: Some("YYYY-MM-DD".to_string()),
+            notification_webhook_url: None,
+            quiet_hours_start: None,
+            quiet_hours_end: None,
+            notification_mode: None,
+            digest_interval: None,
             preferred_currency: Some("USD".to_string()),
         };
 
@@ -990,4 +1295,113 @@ mod integration_tests {
         let cache = SettingsCache::load(&conn).await.unwrap();
         assert_eq!(cache.preferred_currency(), "GBP");
     }
+
+    #[tokio::test]
+    async fn test_notification_webhook_url_set_and_clear() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateSettingsParams {
+            notification_webhook_url: Some(" https://hooks.slack.com/services/T/B/X ".to_string()),
+            ..Default::default()
+        };
+        SettingService::update(&conn, params).await.unwrap();
+
+        let cache = SettingsCache::load(&conn).await.unwrap();
+        assert_eq!(
+            cache.notification_webhook_url(),
+            "https://hooks.slack.com/services/T/B/X"
+        );
+
+        let params = UpdateSettingsParams {
+            notification_webhook_url: Some(String::new()),
+            ..Default::default()
+        };
+        let settings = SettingService::update(&conn, params).await.unwrap();
+        assert_eq!(settings.notification_webhook_url, "");
+    }
+
+    #[tokio::test]
+    async fn test_update_validates_notification_webhook_url() {
+        let conn = setup_app_settings_db().await;
+        let params = UpdateSettingsParams {
+            notification_webhook_url: Some("webhook".to_string()),
+            ..Default::default()
+        };
+
+        let result = SettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_quiet_hours_set_and_validate() {
+        let conn = setup_app_settings_db().await;
+        let params = UpdateSettingsParams {
+            quiet_hours_start: Some("22:00".to_string()),
+            quiet_hours_end: Some("07:00".to_string()),
+            display_timezone: Some("Europe/London".to_string()),
+            ..Default::default()
+        };
+
+        let settings = SettingService::update(&conn, params).await.unwrap();
+        assert_eq!(settings.quiet_hours_start, "22:00");
+        assert_eq!(settings.quiet_hours_end, "07:00");
+
+        let cache = SettingsCache::load(&conn).await.unwrap();
+        let night = Utc.with_ymd_and_hms(2026, 1, 15, 3, 0, 0).unwrap();
+        let afternoon = Utc.with_ymd_and_hms(2026, 1, 15, 15, 0, 0).unwrap();
+        assert!(cache.is_quiet_time(night));
+        assert!(!cache.is_quiet_time(afternoon));
+
+        let params = UpdateSettingsParams {
+            quiet_hours_end: Some("7am".to_string()),
+            ..Default::default()
+        };
+        let result = SettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_notification_mode_and_digest_interval() {
+        let conn = setup_app_settings_db().await;
+        let params = UpdateSettingsParams {
+            notification_mode: Some("digest".to_string()),
+            digest_interval: Some("weekly".to_string()),
+            ..Default::default()
+        };
+
+        let settings = SettingService::update(&conn, params).await.unwrap();
+        assert!(settings.is_digest_mode());
+        assert_eq!(settings.digest_period(), chrono::Duration::weeks(1));
+
+        let params = UpdateSettingsParams {
+            notification_mode: Some("hourly".to_string()),
+            ..Default::default()
+        };
+        let result = SettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let params = UpdateSettingsParams {
+            digest_interval: Some("monthly".to_string()),
+            ..Default::default()
+        };
+        let result = SettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let settings = SettingService::get(&conn).await.unwrap();
+        assert_eq!(settings.digest_interval, "weekly");
+    }
+
+    #[test]
+    fn test_digest_period_defaults_to_daily() {
+        assert_eq!(
+            Settings::default().digest_period(),
+            chrono::Duration::days(1)
+        );
+    }
+
+    #[test]
+    fn test_is_quiet_time_disabled_by_default() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 15, 3, 0, 0).unwrap();
+        assert!(!Settings::default().is_quiet_time(now));
+    }
 }
@@ -6,13 +6,15 @@ use sea_orm::{ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, Sc
 
 use crate::entities::app_setting::Entity as AppSettingEntity;
 use crate::entities::exchange_rate::Entity as ExchangeRateEntity;
+use crate::entities::exchange_rate_history::Entity as ExchangeRateHistoryEntity;
 
 /// Creates a bare in-memory SQLite test database with no tables
 pub async fn setup_in_memory_db() -> DatabaseConnection {
     Database::connect("sqlite::memory:").await.unwrap()
 }
 
-/// Creates an in-memory SQLite test database with core tables (app_settings + exchange_rates)
+/// Creates an in-memory SQLite test database with core tables
+/// (app_settings + exchange_rates + exchange_rate_history)
 pub async fn setup_app_settings_db() -> DatabaseConnection {
     let conn = Database::connect("sqlite::memory:").await.unwrap();
     let schema = Schema::new(DatabaseBackend::Sqlite);
@@ -27,6 +29,11 @@ pub async fn setup_app_settings_db() -> DatabaseConnection {
         .await
         .unwrap();
 
+    let stmt = schema.create_table_from_entity(ExchangeRateHistoryEntity);
+    conn.execute(conn.get_database_backend().build(&stmt))
+        .await
+        .unwrap();
+
     // Create the unique index needed for upsert operations
     conn.execute_unprepared(
         "CREATE UNIQUE INDEX IF NOT EXISTS idx_exchange_rates_currency_pair ON exchange_rates (from_currency, to_currency)",
@@ -1,27 +1,31 @@
+use product_stalker_core::AppError;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// Schema.org availability values that map to InStock status
-const IN_STOCK_INDICATORS: &[&str] = &[
-    "instock",
-    "instoreonly",
-    "onlineonly",
-    "limitedavailability",
-];
+const IN_STOCK_INDICATORS: &[&str] = &["instock", "instoreonly", "onlineonly"];
+
+/// Schema.org availability values that map to LimitedAvailability status
+const LIMITED_AVAILABILITY_INDICATORS: &[&str] = &["limitedavailability"];
 
 /// Schema.org availability values that map to OutOfStock status
 const OUT_OF_STOCK_INDICATORS: &[&str] = &["outofstock", "soldout", "discontinued"];
 
 /// Schema.org availability values that map to BackOrder status
-const BACK_ORDER_INDICATORS: &[&str] = &["backorder", "preorder", "presale"];
+const BACK_ORDER_INDICATORS: &[&str] = &["backorder"];
+
+/// Schema.org availability values that map to PreOrder status
+const PRE_ORDER_INDICATORS: &[&str] = &["preorder", "presale"];
 
 /// Availability status for a product
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AvailabilityStatus {
     InStock,
+    LimitedAvailability,
     OutOfStock,
     BackOrder,
+    PreOrder,
     #[default]
     Unknown,
 }
@@ -37,12 +41,18 @@ impl AvailabilityStatus {
     /// Parse a Schema.org availability value into an AvailabilityStatus
     ///
     /// Handles all 10 official Schema.org ItemAvailability values:
-    /// - InStock, InStoreOnly, OnlineOnly, LimitedAvailability -> InStock
+    /// - InStock, InStoreOnly, OnlineOnly -> InStock
+    /// - LimitedAvailability -> LimitedAvailability
     /// - OutOfStock, SoldOut, Discontinued -> OutOfStock
-    /// - BackOrder, PreOrder, PreSale -> BackOrder
+    /// - BackOrder -> BackOrder
+    /// - PreOrder, PreSale -> PreOrder
     pub fn from_schema_org(value: &str) -> Self {
         let normalized = value.to_lowercase();
 
+        if contains_any_indicator(&normalized, LIMITED_AVAILABILITY_INDICATORS) {
+            return Self::LimitedAvailability;
+        }
+
         if contains_any_indicator(&normalized, IN_STOCK_INDICATORS) {
             return Self::InStock;
         }
@@ -55,6 +65,10 @@ impl AvailabilityStatus {
             return Self::BackOrder;
         }
 
+        if contains_any_indicator(&normalized, PRE_ORDER_INDICATORS) {
+            return Self::PreOrder;
+        }
+
         log::warn!(
             "Unrecognized Schema.org availability value: '{}' - returning Unknown",
             value
@@ -66,11 +80,18 @@ impl AvailabilityStatus {
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::InStock => "in_stock",
+            Self::LimitedAvailability => "limited_availability",
             Self::OutOfStock => "out_of_stock",
             Self::BackOrder => "back_order",
+            Self::PreOrder => "pre_order",
             Self::Unknown => "unknown",
         }
     }
+
+    /// Whether the product can currently be purchased (in stock or limited stock)
+    pub fn is_available(&self) -> bool {
+        matches!(self, Self::InStock | Self::LimitedAvailability)
+    }
 }
 
 impl std::str::FromStr for AvailabilityStatus {
@@ -79,8 +100,10 @@ impl std::str::FromStr for AvailabilityStatus {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "in_stock" => Ok(Self::InStock),
+            "limited_availability" => Ok(Self::LimitedAvailability),
             "out_of_stock" => Ok(Self::OutOfStock),
             "back_order" => Ok(Self::BackOrder),
+            "pre_order" => Ok(Self::PreOrder),
             _ => Ok(Self::Unknown),
         }
     }
@@ -92,6 +115,181 @@ impl std::fmt::Display for AvailabilityStatus {
     }
 }
 
+/// How much to trust a scraped availability status.
+///
+/// Structured data (Schema.org offers, site APIs and hydration data) states
+/// availability outright; heuristics such as add-to-cart buttons and meta tags
+/// only suggest it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    /// Read from structured data the site publishes
+    #[default]
+    High,
+    /// Inferred from page heuristics, so it may be a guess
+    Low,
+}
+
+impl Confidence {
+    /// Convert to database string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Low => "low",
+        }
+    }
+}
+
+impl std::str::FromStr for Confidence {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "high" => Ok(Self::High),
+            "low" => Ok(Self::Low),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Message fragments the fetch pipeline uses when bot protection blocks a page
+const BOT_PROTECTION_INDICATORS: &[&str] = &[
+    "bot protection",
+    "captcha",
+    "manual verification",
+    "headless browser",
+];
+
+/// Prefix of errors for HTTP responses with a non-success status
+pub(crate) const HTTP_STATUS_ERROR_PREFIX: &str = "HTTP ";
+
+/// Prefix of errors for requests that failed before a response arrived
+pub(crate) const NETWORK_ERROR_PREFIX: &str = "Request failed: ";
+
+/// Prefix of errors for hosts that couldn't be reached at all (DNS lookup
+/// failed or the connection was refused)
+pub(crate) const UNREACHABLE_ERROR_PREFIX: &str = "Site unreachable: ";
+
+/// Prefix of errors for requests that got no response within the timeout
+pub(crate) const TIMEOUT_ERROR_PREFIX: &str = "Request timed out: ";
+
+/// Category of a failed availability check.
+///
+/// Lets the frontend tell transient failures (worth retrying) apart from
+/// pages that will keep failing until something changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckErrorKind {
+    /// Blocked by a bot challenge the enabled fallbacks couldn't get past
+    BotProtection,
+    /// The site responded with a non-success HTTP status
+    HttpStatus,
+    /// The request failed before a response arrived, for a reason other than
+    /// the two below (e.g. TLS or a dropped connection)
+    Network,
+    /// The host couldn't be reached: its name didn't resolve or it refused the connection
+    Unreachable,
+    /// The site didn't respond within the HTTP timeout
+    Timeout,
+    /// The page loaded but no availability data could be extracted
+    Scraping,
+    /// The product itself can't be checked (no URL, unsupported scheme, ...)
+    Validation,
+    /// Unexpected failure inside the app (database, task panics, ...)
+    Internal,
+    /// The scraped price was far below the recent average, most likely a
+    /// glitch on the site (e.g. $0.00), so it was ignored
+    ImplausiblePrice,
+}
+
+impl CheckErrorKind {
+    /// Categorize an error returned while checking a product.
+    ///
+    /// Scraper failures all surface as `AppError::External`, so those are
+    /// told apart by the messages the fetch pipeline produces.
+    pub fn from_error(error: &AppError) -> Self {
+        match error {
+            AppError::Validation(_) | AppError::NotFound(_) => Self::Validation,
+            AppError::Database(_) | AppError::Internal(_) => Self::Internal,
+            AppError::External(message) => Self::from_external_message(message),
+        }
+    }
+
+    fn from_external_message(message: &str) -> Self {
+        if message.starts_with(NETWORK_ERROR_PREFIX) {
+            return Self::Network;
+        }
+        if message.starts_with(UNREACHABLE_ERROR_PREFIX) {
+            return Self::Unreachable;
+        }
+        if message.starts_with(TIMEOUT_ERROR_PREFIX) {
+            return Self::Timeout;
+        }
+        if message.starts_with(HTTP_STATUS_ERROR_PREFIX) {
+            return Self::HttpStatus;
+        }
+        let normalized = message.to_lowercase();
+        if contains_any_indicator(&normalized, BOT_PROTECTION_INDICATORS) {
+            return Self::BotProtection;
+        }
+        Self::Scraping
+    }
+
+    /// Convert to database string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BotProtection => "bot_protection",
+            Self::HttpStatus => "http_status",
+            Self::Network => "network",
+            Self::Unreachable => "unreachable",
+            Self::Timeout => "timeout",
+            Self::Scraping => "scraping",
+            Self::Validation => "validation",
+            Self::Internal => "internal",
+            Self::ImplausiblePrice => "implausible_price",
+        }
+    }
+
+    /// Whether the same check may succeed if simply retried
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::HttpStatus | Self::Network | Self::Unreachable | Self::Timeout
+        )
+    }
+}
+
+impl std::str::FromStr for CheckErrorKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bot_protection" => Ok(Self::BotProtection),
+            "http_status" => Ok(Self::HttpStatus),
+            "network" => Ok(Self::Network),
+            "unreachable" => Ok(Self::Unreachable),
+            "timeout" => Ok(Self::Timeout),
+            "scraping" => Ok(Self::Scraping),
+            "validation" => Ok(Self::Validation),
+            "internal" => Ok(Self::Internal),
+            "implausible_price" => Ok(Self::ImplausiblePrice),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for CheckErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Availability check entity
 ///
 /// Represents a single availability check for a product.
@@ -106,7 +304,7 @@ pub struct Model {
     /// Product-retailer link this check was performed against
     pub product_retailer_id: Option<Uuid>,
 
-    /// Status as stored in DB (in_stock, out_of_stock, back_order, unknown)
+    /// Status as stored in DB (in_stock, limited_availability, out_of_stock, back_order, pre_order, unknown)
     pub status: String,
 
     /// Original schema.org availability value
@@ -115,6 +313,9 @@ pub struct Model {
     /// Error message if check failed
     pub error_message: Option<String>,
 
+    /// Category of the failure (see `CheckErrorKind`), None for successful checks
+    pub error_kind: Option<String>,
+
     /// When the check was performed
     pub checked_at: DateTimeUtc,
 
@@ -132,6 +333,22 @@ pub struct Model {
 
     /// Currency code of the normalized price (the user's preferred currency)
     pub normalized_currency: Option<String>,
+
+    /// Strikethrough ("was") price in minor units, same currency as `price_minor_units`
+    pub compare_at_minor_units: Option<i64>,
+
+    /// Schema.org `priceValidUntil` of the scraped offer
+    pub price_valid_until: Option<Date>,
+
+    /// How long fetching the page took, in milliseconds
+    pub fetch_duration_ms: Option<i64>,
+
+    /// Size of the fetched HTML in bytes
+    pub content_bytes: Option<i64>,
+
+    /// How the status was found (see `Confidence`), None for failed checks
+    /// and checks recorded before confidence existed
+    pub confidence: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -171,6 +388,23 @@ impl Model {
         self.status.parse().unwrap_or_default()
     }
 
+    /// Parse the stored error kind into a typed `CheckErrorKind`.
+    pub fn error_kind_enum(&self) -> Option<CheckErrorKind> {
+        self.error_kind.as_deref()?.parse().ok()
+    }
+
+    /// Parse the stored confidence into a typed `Confidence`.
+    pub fn confidence_enum(&self) -> Option<Confidence> {
+        self.confidence.as_deref()?.parse().ok()
+    }
+
+    /// Whether the offer's `priceValidUntil` had already passed when the check ran,
+    /// meaning the scraped price is likely a stale/cached one.
+    pub fn is_offer_expired(&self) -> bool {
+        self.price_valid_until
+            .is_some_and(|valid_until| valid_until < self.checked_at.date_naive())
+    }
+
     /// Get the effective price in minor units, preferring normalized over original.
     pub fn effective_price_minor_units(&self) -> Option<i64> {
         self.normalized_price_minor_units.or(self.price_minor_units)
@@ -223,7 +457,7 @@ mod tests {
             AvailabilityStatus::BackOrder
         );
         assert_eq!(
-            AvailabilityStatus::from_schema_org("http://schema.org/PreOrder"),
+            AvailabilityStatus::from_schema_org("https://schema.org/BackOrder"),
             AvailabilityStatus::BackOrder
         );
     }
@@ -248,6 +482,7 @@ mod tests {
             "out_of_stock"
         );
         assert_eq!(format!("{}", AvailabilityStatus::BackOrder), "back_order");
+        assert_eq!(format!("{}", AvailabilityStatus::PreOrder), "pre_order");
         assert_eq!(format!("{}", AvailabilityStatus::Unknown), "unknown");
     }
 
@@ -277,15 +512,15 @@ mod tests {
     fn test_from_schema_org_preorder() {
         assert_eq!(
             AvailabilityStatus::from_schema_org("http://schema.org/PreOrder"),
-            AvailabilityStatus::BackOrder
+            AvailabilityStatus::PreOrder
         );
         assert_eq!(
             AvailabilityStatus::from_schema_org("PreOrder"),
-            AvailabilityStatus::BackOrder
+            AvailabilityStatus::PreOrder
         );
         assert_eq!(
             AvailabilityStatus::from_schema_org("preorder"),
-            AvailabilityStatus::BackOrder
+            AvailabilityStatus::PreOrder
         );
     }
 
@@ -295,6 +530,33 @@ mod tests {
         assert_eq!(AvailabilityStatus::OutOfStock.as_str(), "out_of_stock");
         assert_eq!(AvailabilityStatus::BackOrder.as_str(), "back_order");
         assert_eq!(AvailabilityStatus::Unknown.as_str(), "unknown");
+        assert_eq!(
+            AvailabilityStatus::LimitedAvailability.as_str(),
+            "limited_availability"
+        );
+    }
+
+    #[test]
+    fn test_is_available() {
+        assert!(AvailabilityStatus::InStock.is_available());
+        assert!(AvailabilityStatus::LimitedAvailability.is_available());
+        assert!(!AvailabilityStatus::OutOfStock.is_available());
+        assert!(!AvailabilityStatus::BackOrder.is_available());
+        assert!(!AvailabilityStatus::PreOrder.is_available());
+        assert!(!AvailabilityStatus::Unknown.is_available());
+    }
+
+    #[test]
+    fn test_limited_availability_round_trip() {
+        let status = AvailabilityStatus::LimitedAvailability;
+
+        let json = serde_json::to_string(&status).unwrap();
+        assert_eq!(json, "\"limited_availability\"");
+        let deserialized: AvailabilityStatus = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, status);
+
+        let parsed: AvailabilityStatus = status.as_str().parse().unwrap();
+        assert_eq!(parsed, status);
     }
 
     #[test]
@@ -333,6 +595,9 @@ mod tests {
         let status: AvailabilityStatus = serde_json::from_str("\"back_order\"").unwrap();
         assert_eq!(status, AvailabilityStatus::BackOrder);
 
+        let status: AvailabilityStatus = serde_json::from_str("\"pre_order\"").unwrap();
+        assert_eq!(status, AvailabilityStatus::PreOrder);
+
         let status: AvailabilityStatus = serde_json::from_str("\"unknown\"").unwrap();
         assert_eq!(status, AvailabilityStatus::Unknown);
     }
@@ -361,12 +626,18 @@ mod tests {
             status: "in_stock".to_string(),
             raw_availability: None,
             error_message: None,
+            error_kind: None,
             checked_at: chrono::Utc::now(),
             price_minor_units: None,
             price_currency: None,
             raw_price: None,
             normalized_price_minor_units: None,
             normalized_currency: None,
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            fetch_duration_ms: None,
+            content_bytes: None,
+            confidence: None,
         };
         assert_eq!(model.status_enum(), AvailabilityStatus::InStock);
 
@@ -382,6 +653,18 @@ mod tests {
         };
         assert_eq!(model.status_enum(), AvailabilityStatus::BackOrder);
 
+        let model = Model {
+            status: "limited_availability".to_string(),
+            ..model
+        };
+        assert_eq!(model.status_enum(), AvailabilityStatus::LimitedAvailability);
+
+        let model = Model {
+            status: "pre_order".to_string(),
+            ..model
+        };
+        assert_eq!(model.status_enum(), AvailabilityStatus::PreOrder);
+
         let model = Model {
             status: "unknown".to_string(),
             ..model
@@ -404,12 +687,18 @@ mod tests {
             status: "in_stock".to_string(),
             raw_availability: None,
             error_message: None,
+            error_kind: None,
             checked_at: chrono::Utc::now(),
             price_minor_units: Some(5000),
             price_currency: Some("USD".to_string()),
             raw_price: None,
             normalized_price_minor_units: Some(7935),
             normalized_currency: Some("AUD".to_string()),
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            fetch_duration_ms: None,
+            content_bytes: None,
+            confidence: None,
         };
         assert_eq!(model.effective_price_minor_units(), Some(7935));
         assert_eq!(model.effective_currency(), Some("AUD"));
@@ -424,17 +713,69 @@ mod tests {
             status: "in_stock".to_string(),
             raw_availability: None,
             error_message: None,
+            error_kind: None,
             checked_at: chrono::Utc::now(),
             price_minor_units: Some(5000),
             price_currency: Some("USD".to_string()),
             raw_price: None,
             normalized_price_minor_units: None,
             normalized_currency: None,
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            fetch_duration_ms: None,
+            content_bytes: None,
+            confidence: None,
         };
         assert_eq!(model.effective_price_minor_units(), Some(5000));
         assert_eq!(model.effective_currency(), Some("USD"));
     }
 
+    #[test]
+    fn test_is_offer_expired() {
+        let checked_at = chrono::Utc::now();
+        let today = checked_at.date_naive();
+        let model = Model {
+            id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            product_retailer_id: None,
+            status: "in_stock".to_string(),
+            raw_availability: None,
+            error_message: None,
+            error_kind: None,
+            checked_at,
+            price_minor_units: Some(5000),
+            price_currency: Some("USD".to_string()),
+            raw_price: None,
+            normalized_price_minor_units: None,
+            normalized_currency: None,
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            fetch_duration_ms: None,
+            content_bytes: None,
+            confidence: None,
+        };
+        assert!(!model.is_offer_expired());
+
+        let model = Model {
+            price_valid_until: today.pred_opt(),
+            ..model
+        };
+        assert!(model.is_offer_expired());
+
+        // Valid through the end of the day
+        let model = Model {
+            price_valid_until: Some(today),
+            ..model
+        };
+        assert!(!model.is_offer_expired());
+
+        let model = Model {
+            price_valid_until: today.succ_opt(),
+            ..model
+        };
+        assert!(!model.is_offer_expired());
+    }
+
     #[test]
     fn test_from_schema_org_with_whitespace() {
         // Whitespace shouldn't break the match since we check contains
@@ -467,7 +808,14 @@ mod tests {
     fn test_from_schema_org_in_stock_variants() {
         assert_schema_org_maps_to("InStoreOnly", AvailabilityStatus::InStock);
         assert_schema_org_maps_to("OnlineOnly", AvailabilityStatus::InStock);
-        assert_schema_org_maps_to("LimitedAvailability", AvailabilityStatus::InStock);
+    }
+
+    #[test]
+    fn test_from_schema_org_limited_availability() {
+        assert_schema_org_maps_to(
+            "LimitedAvailability",
+            AvailabilityStatus::LimitedAvailability,
+        );
     }
 
     #[test]
@@ -477,7 +825,129 @@ mod tests {
     }
 
     #[test]
-    fn test_from_schema_org_back_order_variants() {
-        assert_schema_org_maps_to("PreSale", AvailabilityStatus::BackOrder);
+    fn test_from_schema_org_pre_order_variants() {
+        assert_schema_org_maps_to("PreOrder", AvailabilityStatus::PreOrder);
+        assert_schema_org_maps_to("PreSale", AvailabilityStatus::PreOrder);
+    }
+
+    // Tests for CheckErrorKind
+
+    fn external(message: &str) -> AppError {
+        AppError::External(message.to_string())
+    }
+
+    #[test]
+    fn test_error_kind_from_app_error_variants() {
+        let cases = [
+            (
+                AppError::Validation("Product has no URL set".to_string()),
+                "validation",
+            ),
+            (
+                AppError::NotFound("Product not found".to_string()),
+                "validation",
+            ),
+            (
+                AppError::Internal("Headless task failed".to_string()),
+                "internal",
+            ),
+            (
+                AppError::Database(sea_orm::DbErr::Custom("locked".to_string())),
+                "internal",
+            ),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(CheckErrorKind::from_error(&error).as_str(), expected);
+        }
+    }
+
+    #[test]
+    fn test_error_kind_from_external_messages() {
+        let cases = [
+            (
+                "This site has bot protection. Enable headless browser in settings to check this site.",
+                "bot_protection",
+            ),
+            (
+                "This site requires manual verification (CAPTCHA). Please check the product page directly.",
+                "bot_protection",
+            ),
+            (
+                "Headless browser timed out. The site may require manual verification.",
+                "bot_protection",
+            ),
+            ("HTTP 404 for URL: https://example.com/p", "http_status"),
+            (
+                "Request failed: error sending request for url (https://example.com/p)",
+                "network",
+            ),
+            (
+                "Site unreachable: dns error: failed to lookup address information",
+                "unreachable",
+            ),
+            (
+                "Request timed out: operation timed out",
+                "timeout",
+            ),
+            (
+                "No availability information found in Schema.org data",
+                "scraping",
+            ),
+        ];
+
+        for (message, expected) in cases {
+            assert_eq!(
+                CheckErrorKind::from_error(&external(message)).as_str(),
+                expected,
+                "{}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_kind_round_trips_through_str() {
+        for kind in [
+            CheckErrorKind::BotProtection,
+            CheckErrorKind::HttpStatus,
+            CheckErrorKind::Network,
+            CheckErrorKind::Unreachable,
+            CheckErrorKind::Timeout,
+            CheckErrorKind::Scraping,
+            CheckErrorKind::Validation,
+            CheckErrorKind::Internal,
+            CheckErrorKind::ImplausiblePrice,
+        ] {
+            assert_eq!(kind.as_str().parse::<CheckErrorKind>(), Ok(kind));
+            assert_eq!(
+                serde_json::to_string(&kind).unwrap(),
+                format!("\"{}\"", kind)
+            );
+        }
+        assert!("bogus".parse::<CheckErrorKind>().is_err());
+    }
+
+    #[test]
+    fn test_confidence_round_trips_through_str() {
+        for confidence in [Confidence::High, Confidence::Low] {
+            assert_eq!(confidence.as_str().parse::<Confidence>(), Ok(confidence));
+            assert_eq!(
+                serde_json::to_string(&confidence).unwrap(),
+                format!("\"{}\"", confidence)
+            );
+        }
+        assert!("bogus".parse::<Confidence>().is_err());
+    }
+
+    #[test]
+    fn test_error_kind_is_transient() {
+        assert!(CheckErrorKind::Network.is_transient());
+        assert!(CheckErrorKind::Unreachable.is_transient());
+        assert!(CheckErrorKind::Timeout.is_transient());
+        assert!(CheckErrorKind::HttpStatus.is_transient());
+        assert!(!CheckErrorKind::BotProtection.is_transient());
+        assert!(!CheckErrorKind::Scraping.is_transient());
+        assert!(!CheckErrorKind::Validation.is_transient());
     }
 }
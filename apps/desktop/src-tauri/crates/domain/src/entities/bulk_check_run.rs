@@ -0,0 +1,57 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Bulk check run entity
+///
+/// The summary of one "check all" run, kept so the outcome of the last run
+/// can be shown after the progress events are gone.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "bulk_check_runs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// When the run finished
+    pub ran_at: DateTimeUtc,
+
+    /// Number of products (or retailer links) checked
+    pub total: i32,
+
+    pub successful: i32,
+
+    pub failed: i32,
+
+    /// Products that came back in stock during the run
+    pub back_in_stock_count: i32,
+
+    /// Products whose price dropped during the run
+    pub price_drop_count: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_model_serialize() {
+        let model = Model {
+            id: Uuid::new_v4(),
+            ran_at: Utc::now(),
+            total: 10,
+            successful: 9,
+            failed: 1,
+            back_in_stock_count: 3,
+            price_drop_count: 2,
+        };
+        let json = serde_json::to_string(&model).unwrap();
+        assert!(json.contains("\"back_in_stock_count\":3"));
+        assert!(json.contains("\"price_drop_count\":2"));
+    }
+}
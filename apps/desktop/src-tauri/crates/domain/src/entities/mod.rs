@@ -1,7 +1,10 @@
 //! Domain entities
 
 pub mod availability_check;
+pub mod bulk_check_run;
 pub mod prelude;
 pub mod product;
 pub mod product_retailer;
+pub mod product_tag;
 pub mod retailer;
+pub mod tag;
@@ -11,6 +11,15 @@ pub use super::availability_check::Entity as AvailabilityCheck;
 #[allow(unused_imports)]
 pub use super::availability_check::Model as AvailabilityCheckModel;
 
+#[allow(unused_imports)]
+pub use super::bulk_check_run::ActiveModel as BulkCheckRunActiveModel;
+#[allow(unused_imports)]
+pub use super::bulk_check_run::Column as BulkCheckRunColumn;
+#[allow(unused_imports)]
+pub use super::bulk_check_run::Entity as BulkCheckRun;
+#[allow(unused_imports)]
+pub use super::bulk_check_run::Model as BulkCheckRunModel;
+
 #[allow(unused_imports)]
 pub use super::product::ActiveModel as ProductActiveModel;
 #[allow(unused_imports)]
@@ -29,6 +38,15 @@ pub use super::product_retailer::Entity as ProductRetailer;
 #[allow(unused_imports)]
 pub use super::product_retailer::Model as ProductRetailerModel;
 
+#[allow(unused_imports)]
+pub use super::product_tag::ActiveModel as ProductTagActiveModel;
+#[allow(unused_imports)]
+pub use super::product_tag::Column as ProductTagColumn;
+#[allow(unused_imports)]
+pub use super::product_tag::Entity as ProductTag;
+#[allow(unused_imports)]
+pub use super::product_tag::Model as ProductTagModel;
+
 #[allow(unused_imports)]
 pub use super::retailer::ActiveModel as RetailerActiveModel;
 #[allow(unused_imports)]
@@ -37,3 +55,12 @@ pub use super::retailer::Column as RetailerColumn;
 pub use super::retailer::Entity as Retailer;
 #[allow(unused_imports)]
 pub use super::retailer::Model as RetailerModel;
+
+#[allow(unused_imports)]
+pub use super::tag::ActiveModel as TagActiveModel;
+#[allow(unused_imports)]
+pub use super::tag::Column as TagColumn;
+#[allow(unused_imports)]
+pub use super::tag::Entity as Tag;
+#[allow(unused_imports)]
+pub use super::tag::Model as TagModel;
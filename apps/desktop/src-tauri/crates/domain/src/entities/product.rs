@@ -30,6 +30,37 @@ pub struct Model {
     /// User-defined display order (0 = first)
     pub sort_order: i32,
 
+    /// Background check interval override in minutes (NULL = use the global interval)
+    pub check_interval_minutes: Option<i32>,
+
+    /// Price in minor units at or below which the user wants to be notified
+    pub target_price_minor_units: Option<i64>,
+
+    /// ISO 4217 currency of the target price (NULL = same as the scraped price)
+    pub target_price_currency: Option<String>,
+
+    /// Paused products are skipped by background and bulk checks
+    pub is_paused: bool,
+
+    /// Archived products are hidden from the product list and never checked,
+    /// but keep their history
+    pub is_archived: bool,
+
+    /// Products not tracking price are checked for stock only; scraped prices are dropped
+    pub track_price: bool,
+
+    /// Set when the user picked the currency; auto-detection never changes a locked currency
+    pub currency_locked: bool,
+
+    /// Check that triggered the last back-in-stock notification
+    pub last_notified_check_id: Option<Uuid>,
+
+    /// When the last back-in-stock notification was sent
+    pub last_notified_at: Option<DateTimeUtc>,
+
+    /// Notifications for this product are suppressed until this time (checks still run)
+    pub notifications_snoozed_until: Option<DateTimeUtc>,
+
     /// Creation timestamp
     pub created_at: DateTimeUtc,
 
@@ -44,6 +75,9 @@ pub enum Relation {
 
     #[sea_orm(has_many = "super::product_retailer::Entity")]
     ProductRetailers,
+
+    #[sea_orm(has_many = "super::product_tag::Entity")]
+    ProductTags,
 }
 
 impl Related<super::availability_check::Entity> for Entity {
@@ -58,8 +92,22 @@ impl Related<super::product_retailer::Entity> for Entity {
     }
 }
 
+impl Related<super::product_tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductTags.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
 
+impl Model {
+    /// Whether the product's notifications are snoozed at `now`
+    pub fn is_notifications_snoozed(&self, now: DateTimeUtc) -> bool {
+        self.notifications_snoozed_until
+            .is_some_and(|until| now < until)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +124,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -95,6 +153,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -115,6 +183,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -126,6 +204,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -143,6 +231,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -154,6 +252,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -171,6 +279,16 @@ mod tests {
             notes: Some("notes".to_string()),
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -192,6 +310,16 @@ mod tests {
             notes: Some("Important notes about this product".to_string()),
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -209,6 +337,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -228,10 +366,52 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: created,
             updated_at: updated,
         };
         assert_ne!(model.created_at, model.updated_at);
         assert!(model.updated_at > model.created_at);
     }
+
+    #[test]
+    fn test_is_notifications_snoozed() {
+        let now = Utc::now();
+        let mut model = Model {
+            id: Uuid::new_v4(),
+            name: "Snoozed".to_string(),
+            url: None,
+            description: None,
+            notes: None,
+            currency: None,
+            sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
+            created_at: now,
+            updated_at: now,
+        };
+        assert!(!model.is_notifications_snoozed(now));
+
+        model.notifications_snoozed_until = Some(now + chrono::Duration::hours(1));
+        assert!(model.is_notifications_snoozed(now));
+        assert!(!model.is_notifications_snoozed(now + chrono::Duration::hours(1)));
+        assert!(!model.is_notifications_snoozed(now + chrono::Duration::hours(2)));
+    }
 }
@@ -26,6 +26,14 @@ pub struct Model {
     /// User-defined display order (0 = first)
     pub sort_order: i32,
 
+    /// ISO 4217 currency detected for this link (None = not detected yet)
+    pub currency: Option<String>,
+
+    /// JSON object of variant attributes picking the ProductGroup variant to
+    /// check (e.g. `{"size":"M","color":"black"}`), for stores whose URL doesn't
+    #[sea_orm(column_type = "Text", nullable)]
+    pub variant_selector: Option<String>,
+
     /// Creation timestamp
     pub created_at: DateTimeUtc,
 }
@@ -85,6 +93,8 @@ mod tests {
             url: "https://amazon.com/dp/B123".to_string(),
             label: Some("64GB".to_string()),
             sort_order: 0,
+            currency: None,
+            variant_selector: None,
             created_at: Utc::now(),
         };
         let cloned = model.clone();
@@ -102,6 +112,8 @@ mod tests {
             url: "https://walmart.com/item/456".to_string(),
             label: None,
             sort_order: 0,
+            currency: None,
+            variant_selector: None,
             created_at: Utc::now(),
         };
         let json = serde_json::to_string(&model).unwrap();
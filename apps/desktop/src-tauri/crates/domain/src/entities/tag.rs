@@ -0,0 +1,63 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Tag entity
+///
+/// A user-defined label (e.g., "kitchen", "gpu") used to group products.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "tags")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// Unique tag name, stored trimmed and lowercase
+    #[sea_orm(unique)]
+    pub name: String,
+
+    /// Creation timestamp
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::product_tag::Entity")]
+    ProductTags,
+}
+
+impl Related<super::product_tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductTags.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_model_clone() {
+        let model = Model {
+            id: Uuid::new_v4(),
+            name: "kitchen".to_string(),
+            created_at: Utc::now(),
+        };
+        let cloned = model.clone();
+        assert_eq!(model.id, cloned.id);
+        assert_eq!(model.name, cloned.name);
+    }
+
+    #[test]
+    fn test_model_serialize() {
+        let model = Model {
+            id: Uuid::new_v4(),
+            name: "gpu".to_string(),
+            created_at: Utc::now(),
+        };
+        let json = serde_json::to_string(&model).unwrap();
+        assert!(json.contains("gpu"));
+    }
+}
@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Strikethrough ("was") price, in the same currency as price_minor_units
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .add_column(
+                        ColumnDef::new(AvailabilityChecks::CompareAtMinorUnits)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .drop_column(AvailabilityChecks::CompareAtMinorUnits)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AvailabilityChecks {
+    Table,
+    CompareAtMinorUnits,
+}
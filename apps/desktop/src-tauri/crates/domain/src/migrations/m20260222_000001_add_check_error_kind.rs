@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Category of a failed check (bot_protection, http_status, network, ...)
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .add_column(ColumnDef::new(AvailabilityChecks::ErrorKind).text().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .drop_column(AvailabilityChecks::ErrorKind)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AvailabilityChecks {
+    Table,
+    ErrorKind,
+}
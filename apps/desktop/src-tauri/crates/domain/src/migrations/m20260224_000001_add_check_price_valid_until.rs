@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Schema.org `priceValidUntil` of the scraped offer
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .add_column(
+                        ColumnDef::new(AvailabilityChecks::PriceValidUntil)
+                            .date()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .drop_column(AvailabilityChecks::PriceValidUntil)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AvailabilityChecks {
+    Table,
+    PriceValidUntil,
+}
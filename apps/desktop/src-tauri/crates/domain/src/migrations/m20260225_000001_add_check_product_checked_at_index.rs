@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Latest-check-per-product lookups filter on product_id and sort by checked_at
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_availability_checks_product_id_checked_at")
+                    .table(AvailabilityChecks::Table)
+                    .col(AvailabilityChecks::ProductId)
+                    .col(AvailabilityChecks::CheckedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name("idx_availability_checks_product_id_checked_at")
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AvailabilityChecks {
+    Table,
+    ProductId,
+    CheckedAt,
+}
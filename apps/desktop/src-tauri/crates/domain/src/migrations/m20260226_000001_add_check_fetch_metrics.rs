@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // How long the page fetch took, for spotting slow hosts
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .add_column(
+                        ColumnDef::new(AvailabilityChecks::FetchDurationMs)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Size of the fetched HTML in bytes
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .add_column(
+                        ColumnDef::new(AvailabilityChecks::ContentBytes)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .drop_column(AvailabilityChecks::FetchDurationMs)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .drop_column(AvailabilityChecks::ContentBytes)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AvailabilityChecks {
+    Table,
+    FetchDurationMs,
+    ContentBytes,
+}
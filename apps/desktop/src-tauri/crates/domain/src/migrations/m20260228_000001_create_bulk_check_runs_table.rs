@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // One row per bulk check, so the last run's outcome survives a restart
+        db.execute_unprepared(
+            r#"
+            CREATE TABLE IF NOT EXISTS bulk_check_runs (
+                id TEXT NOT NULL PRIMARY KEY,
+                ran_at TEXT NOT NULL,
+                total INTEGER NOT NULL,
+                successful INTEGER NOT NULL,
+                failed INTEGER NOT NULL,
+                back_in_stock_count INTEGER NOT NULL,
+                price_drop_count INTEGER NOT NULL
+            )
+            "#,
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_bulk_check_runs_ran_at ON bulk_check_runs (ran_at)",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared("DROP TABLE IF EXISTS bulk_check_runs")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Each retailer link can be in its own country, so it detects its own currency
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProductRetailers::Table)
+                    .add_column(ColumnDef::new(ProductRetailers::Currency).string().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProductRetailers::Table)
+                    .drop_column(ProductRetailers::Currency)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProductRetailers {
+    Table,
+    Currency,
+}
@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // JSON object of variant attributes (e.g. size, color) picking the variant to check
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProductRetailers::Table)
+                    .add_column(
+                        ColumnDef::new(ProductRetailers::VariantSelector)
+                            .text()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProductRetailers::Table)
+                    .drop_column(ProductRetailers::VariantSelector)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProductRetailers {
+    Table,
+    VariantSelector,
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // How the status was found (high = structured data, low = page heuristics)
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .add_column(ColumnDef::new(AvailabilityChecks::Confidence).text().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AvailabilityChecks::Table)
+                    .drop_column(AvailabilityChecks::Confidence)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AvailabilityChecks {
+    Table,
+    Confidence,
+}
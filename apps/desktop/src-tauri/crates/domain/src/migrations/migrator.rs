@@ -9,6 +9,24 @@ use super::m20260213_000001_add_multi_retailer;
 use super::m20260214_000001_add_product_sort_order;
 use super::m20260215_000001_add_retailer_sort_order;
 use super::m20260216_000002_add_normalized_price_columns;
+use super::m20260217_000001_add_product_check_interval;
+use super::m20260218_000001_add_product_target_price;
+use super::m20260219_000001_add_product_is_paused;
+use super::m20260220_000001_add_check_compare_at_price;
+use super::m20260221_000001_add_tags;
+use super::m20260222_000001_add_check_error_kind;
+use super::m20260223_000001_add_product_last_notified;
+use super::m20260224_000001_add_check_price_valid_until;
+use super::m20260225_000001_add_check_product_checked_at_index;
+use super::m20260226_000001_add_check_fetch_metrics;
+use super::m20260227_000001_add_product_notifications_snoozed_until;
+use super::m20260228_000001_create_bulk_check_runs_table;
+use super::m20260301_000001_add_product_is_archived;
+use super::m20260302_000001_add_product_track_price;
+use super::m20260303_000001_add_product_retailer_currency;
+use super::m20260304_000001_add_product_retailer_variant_selector;
+use super::m20260305_000001_add_product_currency_locked;
+use super::m20260306_000001_add_check_confidence;
 
 pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
     vec![
@@ -21,5 +39,23 @@ pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         Box::new(m20260214_000001_add_product_sort_order::Migration),
         Box::new(m20260215_000001_add_retailer_sort_order::Migration),
         Box::new(m20260216_000002_add_normalized_price_columns::Migration),
+        Box::new(m20260217_000001_add_product_check_interval::Migration),
+        Box::new(m20260218_000001_add_product_target_price::Migration),
+        Box::new(m20260219_000001_add_product_is_paused::Migration),
+        Box::new(m20260220_000001_add_check_compare_at_price::Migration),
+        Box::new(m20260221_000001_add_tags::Migration),
+        Box::new(m20260222_000001_add_check_error_kind::Migration),
+        Box::new(m20260223_000001_add_product_last_notified::Migration),
+        Box::new(m20260224_000001_add_check_price_valid_until::Migration),
+        Box::new(m20260225_000001_add_check_product_checked_at_index::Migration),
+        Box::new(m20260226_000001_add_check_fetch_metrics::Migration),
+        Box::new(m20260227_000001_add_product_notifications_snoozed_until::Migration),
+        Box::new(m20260228_000001_create_bulk_check_runs_table::Migration),
+        Box::new(m20260301_000001_add_product_is_archived::Migration),
+        Box::new(m20260302_000001_add_product_track_price::Migration),
+        Box::new(m20260303_000001_add_product_retailer_currency::Migration),
+        Box::new(m20260304_000001_add_product_retailer_variant_selector::Migration),
+        Box::new(m20260305_000001_add_product_currency_locked::Migration),
+        Box::new(m20260306_000001_add_check_confidence::Migration),
     ]
 }
@@ -7,6 +7,24 @@ mod m20260213_000001_add_multi_retailer;
 mod m20260214_000001_add_product_sort_order;
 mod m20260215_000001_add_retailer_sort_order;
 mod m20260216_000002_add_normalized_price_columns;
+mod m20260217_000001_add_product_check_interval;
+mod m20260218_000001_add_product_target_price;
+mod m20260219_000001_add_product_is_paused;
+mod m20260220_000001_add_check_compare_at_price;
+mod m20260221_000001_add_tags;
+mod m20260222_000001_add_check_error_kind;
+mod m20260223_000001_add_product_last_notified;
+mod m20260224_000001_add_check_price_valid_until;
+mod m20260225_000001_add_check_product_checked_at_index;
+mod m20260226_000001_add_check_fetch_metrics;
+mod m20260227_000001_add_product_notifications_snoozed_until;
+mod m20260228_000001_create_bulk_check_runs_table;
+mod m20260301_000001_add_product_is_archived;
+mod m20260302_000001_add_product_track_price;
+mod m20260303_000001_add_product_retailer_currency;
+mod m20260304_000001_add_product_retailer_variant_selector;
+mod m20260305_000001_add_product_currency_locked;
+mod m20260306_000001_add_check_confidence;
 mod migrator;
 
 pub use migrator::migrations;
@@ -1,12 +1,17 @@
-use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, NaiveDate, Utc};
 use product_stalker_core::AppError;
+use sea_orm::sea_query::Query;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult,
-    IntoActiveModel, QueryFilter, QueryOrder, Set, Statement,
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbBackend, EntityTrait,
+    FromQueryResult, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, Set, Statement,
+    TransactionTrait,
 };
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::entities::availability_check::AvailabilityStatus;
+use crate::entities::availability_check::{AvailabilityStatus, CheckErrorKind, Confidence};
 use crate::entities::prelude::*;
 
 /// Helper struct for parsing SQLite AVG query results
@@ -28,11 +33,290 @@ pub struct CurrencyAverageResult {
     pub check_count: i64,
 }
 
-/// Result of finding the cheapest current price across retailers
+/// Product ID returned by queries that only select products
+#[derive(Debug, FromQueryResult)]
+struct ProductIdRow {
+    product_id: Uuid,
+}
+
+/// Check ID returned by queries that only select checks
+#[derive(Debug, FromQueryResult)]
+struct CheckIdRow {
+    check_id: Uuid,
+}
+
+/// Latest check of one of a product's current retailer links
 #[derive(Debug, FromQueryResult)]
+struct LatestTargetRow {
+    product_id: Uuid,
+    status: Option<String>,
+    price_minor_units: Option<i64>,
+    price_currency: Option<String>,
+}
+
+/// Latest check of one of a product's current retailer links (or of its legacy
+/// URL), for totals across products
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatestTargetCheck {
+    pub product_id: Uuid,
+    /// `None` when the product has never been checked
+    pub status: Option<AvailabilityStatus>,
+    pub price_minor_units: Option<i64>,
+    pub price_currency: Option<String>,
+}
+
+/// Latest priced check for a single retailer
+#[derive(Debug, FromQueryResult)]
+struct RetailerPriceRow {
+    price_minor_units: i64,
+    price_currency: String,
+}
+
+/// Result of finding the cheapest current price across retailers
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CheapestPriceResult {
+    /// Cheapest price in the retailer's own currency
     pub price_minor_units: i64,
     pub price_currency: String,
+    /// The same price converted to the preferred currency, when the comparison was
+    /// made in that currency
+    pub normalized_price_minor_units: Option<i64>,
+    pub normalized_currency: Option<String>,
+    /// True when a preferred currency was requested but some retailer's price had
+    /// no exchange rate, so native prices were compared instead
+    pub normalization_skipped: bool,
+}
+
+/// A recorded price at a point in time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PricePoint {
+    pub price_minor_units: i64,
+    pub price_currency: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl PricePoint {
+    /// Build from a check, returning `None` if the check has no price.
+    ///
+    /// Uses the normalized price when the check has one, so checks from retailers
+    /// in different currencies can be compared.
+    pub(crate) fn from_check(check: &AvailabilityCheckModel) -> Option<Self> {
+        if let Some(normalized) = check.normalized_price_minor_units {
+            return Some(Self {
+                price_minor_units: normalized,
+                price_currency: check.normalized_currency.clone(),
+                checked_at: check.checked_at,
+            });
+        }
+        Some(Self {
+            price_minor_units: check.price_minor_units?,
+            price_currency: check.price_currency.clone(),
+            checked_at: check.checked_at,
+        })
+    }
+}
+
+/// Lowest and highest prices ever recorded for a product
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PriceExtremes {
+    pub lowest: Option<PricePoint>,
+    pub highest: Option<PricePoint>,
+    /// Timestamp of the product's first check (with or without a price)
+    pub first_checked_at: Option<DateTime<Utc>>,
+}
+
+/// Direction of a product's price over a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    Rising,
+    Falling,
+    Stable,
+}
+
+/// Price trend over the last few days, from a least-squares line through the window's prices
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PriceTrend {
+    pub direction: TrendDirection,
+    /// Change along the fitted line from the first to the last check, as a
+    /// percentage of the fitted first price (e.g. -12.5 for a 12.5% fall)
+    pub change_percent: f64,
+    /// Number of priced checks in the window
+    pub sample_count: usize,
+    /// Window length in days
+    pub days: i64,
+}
+
+impl PriceTrend {
+    /// Changes smaller than this (in either direction) count as stable
+    pub const STABLE_THRESHOLD_PERCENT: f64 = 2.0;
+
+    /// Fit a line through `(checked_at, price)` samples and classify its slope.
+    ///
+    /// Fewer than two samples, or samples all at the same instant, are stable.
+    pub fn from_samples(samples: &[(DateTime<Utc>, i64)], days: i64) -> Self {
+        let stable = |change_percent| Self {
+            direction: TrendDirection::Stable,
+            change_percent,
+            sample_count: samples.len(),
+            days,
+        };
+        let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+            return stable(0.0);
+        };
+
+        // x in days since the first sample, keeping the sums well within f64 precision
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|(at, price)| {
+                let x = (*at - first.0).num_seconds() as f64 / 86_400.0;
+                (x, *price as f64)
+            })
+            .collect();
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        if variance == 0.0 {
+            return stable(0.0);
+        }
+        let covariance: f64 = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let slope = covariance / variance;
+
+        let span = (last.0 - first.0).num_seconds() as f64 / 86_400.0;
+        let fitted_start = mean_y - slope * mean_x;
+        if fitted_start <= 0.0 {
+            return stable(0.0);
+        }
+        let change_percent = slope * span / fitted_start * 100.0;
+
+        let direction = if change_percent >= Self::STABLE_THRESHOLD_PERCENT {
+            TrendDirection::Rising
+        } else if change_percent <= -Self::STABLE_THRESHOLD_PERCENT {
+            TrendDirection::Falling
+        } else {
+            TrendDirection::Stable
+        };
+        Self {
+            direction,
+            ..stable(change_percent)
+        }
+    }
+}
+
+/// Outcome of repairing checks whose product or retailer link no longer exists
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RepairReport {
+    /// Checks moved to a surviving product or retailer link, or detached from a deleted link
+    pub reassigned: u64,
+    /// Checks deleted because neither their product nor their retailer link survives
+    pub deleted: u64,
+}
+
+/// A change in availability status between consecutive checks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusTransition {
+    /// Retailer link the checks belong to (`None` for legacy checks without one)
+    pub product_retailer_id: Option<Uuid>,
+    pub from_status: AvailabilityStatus,
+    pub to_status: AvailabilityStatus,
+    /// When the new status was first observed
+    pub changed_at: DateTime<Utc>,
+    /// How long the previous status was held, from its first check until this change
+    pub previous_duration_secs: i64,
+}
+
+/// Priced check used to compute recent price drops
+#[derive(Debug, FromQueryResult)]
+struct PricedCheckRow {
+    product_id: Uuid,
+    product_retailer_id: Option<Uuid>,
+    price_minor_units: i64,
+    price_currency: String,
+    checked_at: DateTime<Utc>,
+}
+
+/// A product's largest price drop within a time window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PriceDrop {
+    pub product_id: Uuid,
+    /// Retailer link the drop was seen at (`None` for legacy checks without one)
+    pub product_retailer_id: Option<Uuid>,
+    /// Highest price in the window before the latest check
+    pub previous_price_minor_units: i64,
+    /// Price of the latest check in the window
+    pub current_price_minor_units: i64,
+    /// Currency of both prices: the preferred currency, or the retailer's own
+    /// currency when no exchange rate was available
+    pub currency: String,
+    /// Drop from the previous to the current price, in percent
+    pub drop_percent: f64,
+    pub current_checked_at: DateTime<Utc>,
+}
+
+/// A single check's price tagged with the start of its price series bucket
+#[derive(Debug, FromQueryResult)]
+struct BucketedCheckRow {
+    bucket_start: String,
+    price_minor_units: Option<i64>,
+    price_currency: Option<String>,
+    checked_at: DateTime<Utc>,
+}
+
+/// Number of recent checks for one host with the same outcome
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct HostOutcomeCount {
+    /// Host derived from the checked URL (lowercase)
+    pub host: String,
+    pub failed: bool,
+    /// Stored error kind; `None` for successful checks and failures recorded
+    /// before error kinds existed
+    pub error_kind: Option<String>,
+    pub check_count: i64,
+}
+
+/// Fetch time percentiles over recent checks for one host
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct HostFetchTiming {
+    /// Host derived from the checked URL (lowercase)
+    pub host: String,
+    /// Number of checks with a recorded fetch time in the sample
+    pub sample_count: i64,
+    pub p50_fetch_ms: i64,
+    pub p95_fetch_ms: i64,
+}
+
+/// Helper struct for parsing bucketed average price query results
+#[derive(Debug, FromQueryResult)]
+struct PriceBucketResult {
+    bucket_start: String,
+    avg_price: Option<f64>,
+}
+
+/// Time bucket size for aggregating price history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bucket {
+    Hourly,
+    Daily,
+    /// Weeks start on Monday
+    Weekly,
+}
+
+impl Bucket {
+    /// SQLite expression truncating `checked_at` to the start of its bucket (UTC, RFC 3339)
+    fn bucket_start_sql(self) -> &'static str {
+        match self {
+            Self::Hourly => "strftime('%Y-%m-%dT%H:00:00+00:00', checked_at)",
+            Self::Daily => "strftime('%Y-%m-%dT00:00:00+00:00', checked_at)",
+            Self::Weekly => {
+                "strftime('%Y-%m-%dT00:00:00+00:00', checked_at, 'weekday 0', '-6 days')"
+            }
+        }
+    }
 }
 
 /// Repository for availability check data access
@@ -44,12 +328,18 @@ pub struct CreateCheckParams {
     pub status: AvailabilityStatus,
     pub raw_availability: Option<String>,
     pub error_message: Option<String>,
+    pub error_kind: Option<CheckErrorKind>,
     pub price_minor_units: Option<i64>,
     pub price_currency: Option<String>,
     pub raw_price: Option<String>,
     pub product_retailer_id: Option<Uuid>,
     pub normalized_price_minor_units: Option<i64>,
     pub normalized_currency: Option<String>,
+    pub compare_at_minor_units: Option<i64>,
+    pub price_valid_until: Option<NaiveDate>,
+    pub fetch_duration_ms: Option<i64>,
+    pub content_bytes: Option<i64>,
+    pub confidence: Option<Confidence>,
 }
 
 impl AvailabilityCheckRepository {
@@ -69,12 +359,18 @@ impl AvailabilityCheckRepository {
             status: Set(params.status.as_str().to_string()),
             raw_availability: Set(params.raw_availability),
             error_message: Set(params.error_message),
+            error_kind: Set(params.error_kind.map(|kind| kind.as_str().to_string())),
             checked_at: Set(now),
             price_minor_units: Set(params.price_minor_units),
             price_currency: Set(params.price_currency),
             raw_price: Set(params.raw_price),
             normalized_price_minor_units: Set(params.normalized_price_minor_units),
             normalized_currency: Set(params.normalized_currency),
+            compare_at_minor_units: Set(params.compare_at_minor_units),
+            price_valid_until: Set(params.price_valid_until),
+            fetch_duration_ms: Set(params.fetch_duration_ms),
+            content_bytes: Set(params.content_bytes),
+            confidence: Set(params.confidence.map(|c| c.as_str().to_string())),
         };
 
         let check = active_model.insert(conn).await?;
@@ -94,16 +390,203 @@ impl AvailabilityCheckRepository {
         Ok(check)
     }
 
+    /// When the product was last seen out of stock or on back order.
+    ///
+    /// Failed checks don't count since they didn't observe a status.
+    pub async fn find_latest_out_of_stock_at(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        let check = AvailabilityCheck::find()
+            .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
+            .filter(AvailabilityCheckColumn::ErrorMessage.is_null())
+            .filter(AvailabilityCheckColumn::Status.is_in([
+                AvailabilityStatus::OutOfStock.as_str(),
+                AvailabilityStatus::BackOrder.as_str(),
+            ]))
+            .order_by_desc(AvailabilityCheckColumn::CheckedAt)
+            .one(conn)
+            .await?;
+        Ok(check.map(|c| c.checked_at))
+    }
+
+    /// When the product was first seen in stock, across all its retailer links.
+    ///
+    /// Failed checks don't count since they didn't observe a status.
+    pub async fn get_first_in_stock_at(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        let check = AvailabilityCheck::find()
+            .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
+            .filter(AvailabilityCheckColumn::ErrorMessage.is_null())
+            .filter(AvailabilityCheckColumn::Status.eq(AvailabilityStatus::InStock.as_str()))
+            .order_by_asc(AvailabilityCheckColumn::CheckedAt)
+            .one(conn)
+            .await?;
+        Ok(check.map(|c| c.checked_at))
+    }
+
+    /// Delete a product's checks from before `cutoff`, returning how many were removed.
+    ///
+    /// The latest check for the product and for each of its retailer links is always
+    /// kept, since it's the previous status that the next check compares against.
+    pub async fn delete_older_than(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, AppError> {
+        let mut keep = Vec::new();
+        if let Some(latest) = Self::find_latest_for_product(conn, product_id).await? {
+            keep.push(latest.id);
+        }
+
+        let retailer_ids: Vec<Option<Uuid>> = AvailabilityCheck::find()
+            .select_only()
+            .column(AvailabilityCheckColumn::ProductRetailerId)
+            .distinct()
+            .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
+            .filter(AvailabilityCheckColumn::ProductRetailerId.is_not_null())
+            .into_tuple()
+            .all(conn)
+            .await?;
+        for retailer_id in retailer_ids.into_iter().flatten() {
+            if let Some(latest) = Self::find_latest_for_product_retailer(conn, retailer_id).await? {
+                keep.push(latest.id);
+            }
+        }
+
+        let result = AvailabilityCheck::delete_many()
+            .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
+            .filter(AvailabilityCheckColumn::CheckedAt.lt(cutoff))
+            .filter(AvailabilityCheckColumn::Id.is_not_in(keep))
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Fix checks left pointing at a deleted product or retailer link.
+    ///
+    /// - A check whose product is gone moves to the product of its retailer link,
+    ///   if that link (and its product) still exist; otherwise it is deleted.
+    /// - A check whose retailer link is gone moves to its product's only link, or
+    ///   becomes a product-level check (no link) when the product has several or none.
+    ///
+    /// Runs in a transaction, so a failure leaves every check untouched.
+    pub async fn repair_orphaned_checks(
+        conn: &DatabaseConnection,
+    ) -> Result<RepairReport, AppError> {
+        let txn = conn.begin().await?;
+
+        let product_ids: HashSet<Uuid> = Product::find()
+            .select_only()
+            .column(ProductColumn::Id)
+            .into_tuple()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .collect();
+        let link_owners: HashMap<Uuid, Uuid> = ProductRetailer::find()
+            .select_only()
+            .column(ProductRetailerColumn::Id)
+            .column(ProductRetailerColumn::ProductId)
+            .into_tuple::<(Uuid, Uuid)>()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .collect();
+        let mut links_by_product: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (&link_id, &product_id) in &link_owners {
+            links_by_product
+                .entry(product_id)
+                .or_default()
+                .push(link_id);
+        }
+
+        let orphans = AvailabilityCheck::find()
+            .filter(
+                Condition::any()
+                    .add(
+                        AvailabilityCheckColumn::ProductId.not_in_subquery(
+                            Query::select()
+                                .column(ProductColumn::Id)
+                                .from(Product)
+                                .to_owned(),
+                        ),
+                    )
+                    .add(
+                        AvailabilityCheckColumn::ProductRetailerId.not_in_subquery(
+                            Query::select()
+                                .column(ProductRetailerColumn::Id)
+                                .from(ProductRetailer)
+                                .to_owned(),
+                        ),
+                    ),
+            )
+            .all(&txn)
+            .await?;
+
+        let mut report = RepairReport::default();
+        let mut to_delete = Vec::new();
+        for check in orphans {
+            let mut active_model = check.clone().into_active_model();
+            if product_ids.contains(&check.product_id) {
+                let only_link = links_by_product
+                    .get(&check.product_id)
+                    .filter(|links| links.len() == 1)
+                    .map(|links| links[0]);
+                active_model.product_retailer_id = Set(only_link);
+            } else {
+                let owner = check
+                    .product_retailer_id
+                    .and_then(|link_id| link_owners.get(&link_id))
+                    .filter(|owner| product_ids.contains(owner));
+                match owner {
+                    Some(&owner) => active_model.product_id = Set(owner),
+                    None => {
+                        to_delete.push(check.id);
+                        continue;
+                    }
+                }
+            }
+            active_model.update(&txn).await?;
+            report.reassigned += 1;
+        }
+
+        if !to_delete.is_empty() {
+            report.deleted = AvailabilityCheck::delete_many()
+                .filter(AvailabilityCheckColumn::Id.is_in(to_delete))
+                .exec(&txn)
+                .await?
+                .rows_affected;
+        }
+
+        txn.commit().await?;
+        Ok(report)
+    }
+
     /// Find all availability checks for a product, ordered by most recent first
+    ///
+    /// `from` and `to` optionally restrict `checked_at` to [from, to); the limit
+    /// applies within that range, keeping the most recent checks.
     pub async fn find_all_for_product(
         conn: &DatabaseConnection,
         product_id: Uuid,
         limit: Option<u64>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
     ) -> Result<Vec<AvailabilityCheckModel>, AppError> {
         let mut query = AvailabilityCheck::find()
             .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
             .order_by_desc(AvailabilityCheckColumn::CheckedAt);
 
+        if let Some(from) = from {
+            query = query.filter(AvailabilityCheckColumn::CheckedAt.gte(from));
+        }
+        if let Some(to) = to {
+            query = query.filter(AvailabilityCheckColumn::CheckedAt.lt(to));
+        }
+
         if let Some(limit) = limit {
             use sea_orm::QuerySelect;
             query = query.limit(limit);
@@ -218,21 +701,26 @@ impl AvailabilityCheckRepository {
 
     /// Find the cheapest current price across all retailers for a product.
     ///
-    /// Uses a window function to get the latest check per retailer, then picks
-    /// the lowest price. Only considers checks linked to a product_retailer
-    /// that have a non-null price.
+    /// Uses a window function to get the latest priced check per retailer, then
+    /// picks the lowest price. Only considers checks linked to a product_retailer.
+    ///
+    /// With a `preferred_currency`, each retailer's native price is converted with
+    /// today's exchange rate so retailers in different currencies compare fairly.
+    /// If any price can't be converted, native prices are compared instead and
+    /// `normalization_skipped` is set.
     pub async fn find_cheapest_current_price(
         conn: &DatabaseConnection,
         product_id: Uuid,
+        preferred_currency: Option<&str>,
     ) -> Result<Option<CheapestPriceResult>, AppError> {
         use sea_orm::Value;
 
-        let result = CheapestPriceResult::find_by_statement(Statement::from_sql_and_values(
+        let rows = RetailerPriceRow::find_by_statement(Statement::from_sql_and_values(
             DbBackend::Sqlite,
             r#"
                 WITH latest_per_retailer AS (
-                    SELECT COALESCE(normalized_price_minor_units, price_minor_units) as price_minor_units,
-                           COALESCE(normalized_currency, price_currency) as price_currency,
+                    SELECT price_minor_units,
+                           price_currency,
                            ROW_NUMBER() OVER (
                                PARTITION BY product_retailer_id
                                ORDER BY checked_at DESC
@@ -240,22 +728,355 @@ impl AvailabilityCheckRepository {
                     FROM availability_checks
                     WHERE product_id = ?
                       AND product_retailer_id IS NOT NULL
-                      AND COALESCE(normalized_price_minor_units, price_minor_units) IS NOT NULL
+                      AND price_minor_units IS NOT NULL
+                      AND price_currency IS NOT NULL
                 )
                 SELECT price_minor_units, price_currency
                 FROM latest_per_retailer
                 WHERE rn = 1
                 ORDER BY price_minor_units ASC
-                LIMIT 1
             "#,
             [Value::Uuid(Some(Box::new(product_id)))],
         ))
-        .one(conn)
+        .all(conn)
         .await?;
 
+        let normalized = match preferred_currency {
+            Some(preferred) => Self::normalize_retailer_prices(conn, &rows, preferred).await,
+            None => None,
+        };
+
+        let result = match normalized {
+            Some(normalized) => rows
+                .into_iter()
+                .zip(normalized)
+                .min_by_key(|(_, normalized)| *normalized)
+                .map(|(row, normalized)| CheapestPriceResult {
+                    price_minor_units: row.price_minor_units,
+                    price_currency: row.price_currency,
+                    normalized_price_minor_units: Some(normalized),
+                    normalized_currency: preferred_currency.map(str::to_string),
+                    normalization_skipped: false,
+                }),
+            None => rows
+                .into_iter()
+                .min_by_key(|row| row.price_minor_units)
+                .map(|row| CheapestPriceResult {
+                    price_minor_units: row.price_minor_units,
+                    price_currency: row.price_currency,
+                    normalized_price_minor_units: None,
+                    normalized_currency: None,
+                    normalization_skipped: preferred_currency.is_some(),
+                }),
+        };
+
         Ok(result)
     }
 
+    /// Find unpaused, unarchived products whose latest check failed, in product sort order.
+    ///
+    /// The latest check is taken per retailer link, so a product is included when
+    /// any of its current retailers last failed. Products without retailer links
+    /// use their latest check without a retailer (legacy URL).
+    pub async fn find_products_with_failed_latest_check(
+        conn: &DatabaseConnection,
+    ) -> Result<Vec<Uuid>, AppError> {
+        let rows = ProductIdRow::find_by_statement(Statement::from_string(
+            DbBackend::Sqlite,
+            r#"
+                WITH latest_per_target AS (
+                    SELECT product_id,
+                           product_retailer_id,
+                           error_message,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY product_id, product_retailer_id
+                               ORDER BY checked_at DESC
+                           ) as rn
+                    FROM availability_checks
+                )
+                SELECT p.id as product_id
+                FROM products p
+                WHERE p.is_paused = 0
+                  AND p.is_archived = 0
+                  AND EXISTS (
+                      SELECT 1
+                      FROM latest_per_target l
+                      WHERE l.product_id = p.id
+                        AND l.rn = 1
+                        AND l.error_message IS NOT NULL
+                        AND (
+                            l.product_retailer_id IN (
+                                SELECT pr.id FROM product_retailers pr WHERE pr.product_id = p.id
+                            )
+                            OR (
+                                l.product_retailer_id IS NULL
+                                AND NOT EXISTS (
+                                    SELECT 1 FROM product_retailers pr WHERE pr.product_id = p.id
+                                )
+                            )
+                        )
+                  )
+                ORDER BY p.sort_order ASC
+            "#,
+        ))
+        .all(conn)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.product_id).collect())
+    }
+
+    /// Find the latest check per current retailer link of every unarchived product,
+    /// in product sort order.
+    ///
+    /// Products without retailer links use their latest check without a retailer
+    /// (legacy URL). A product that was never checked is returned once, without a
+    /// status.
+    pub async fn find_latest_checks_per_target(
+        conn: &DatabaseConnection,
+    ) -> Result<Vec<LatestTargetCheck>, AppError> {
+        let rows = LatestTargetRow::find_by_statement(Statement::from_string(
+            DbBackend::Sqlite,
+            r#"
+                WITH latest_per_target AS (
+                    SELECT product_id,
+                           product_retailer_id,
+                           status,
+                           price_minor_units,
+                           price_currency,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY product_id, product_retailer_id
+                               ORDER BY checked_at DESC
+                           ) as rn
+                    FROM availability_checks
+                )
+                SELECT p.id as product_id,
+                       l.status,
+                       l.price_minor_units,
+                       l.price_currency
+                FROM products p
+                LEFT JOIN latest_per_target l
+                    ON l.product_id = p.id
+                   AND l.rn = 1
+                   AND (
+                       l.product_retailer_id IN (
+                           SELECT pr.id FROM product_retailers pr WHERE pr.product_id = p.id
+                       )
+                       OR (
+                           l.product_retailer_id IS NULL
+                           AND NOT EXISTS (
+                               SELECT 1 FROM product_retailers pr WHERE pr.product_id = p.id
+                           )
+                       )
+                   )
+                WHERE p.is_archived = 0
+                ORDER BY p.sort_order ASC
+            "#,
+        ))
+        .all(conn)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| LatestTargetCheck {
+                product_id: row.product_id,
+                status: row.status.map(|status| status.parse().unwrap_or_default()),
+                price_minor_units: row.price_minor_units,
+                price_currency: row.price_currency,
+            })
+            .collect())
+    }
+
+    /// Convert each retailer price to the preferred currency, or `None` if any
+    /// currency has no exchange rate.
+    async fn normalize_retailer_prices(
+        conn: &DatabaseConnection,
+        rows: &[RetailerPriceRow],
+        preferred_currency: &str,
+    ) -> Option<Vec<i64>> {
+        use crate::services::currency::currency_exponent;
+        use product_stalker_core::services::ExchangeRateService;
+
+        let to_exp = currency_exponent(preferred_currency);
+        let mut normalized = Vec::with_capacity(rows.len());
+        for row in rows {
+            if row.price_currency.eq_ignore_ascii_case(preferred_currency) {
+                normalized.push(row.price_minor_units);
+                continue;
+            }
+
+            match ExchangeRateService::get_rate(conn, &row.price_currency, preferred_currency).await
+            {
+                Ok(rate) => normalized.push(ExchangeRateService::convert_minor_units(
+                    row.price_minor_units,
+                    rate,
+                    currency_exponent(&row.price_currency),
+                    to_exp,
+                )),
+                Err(e) => {
+                    log::warn!(
+                        "Comparing retailer prices natively, cannot convert {} to {}: {}",
+                        row.price_currency,
+                        preferred_currency,
+                        e
+                    );
+                    return None;
+                }
+            }
+        }
+        Some(normalized)
+    }
+
+    /// Find products whose price dropped within the window starting at `since`,
+    /// largest percentage drop first, at most `limit` products.
+    ///
+    /// Drops are measured per retailer link, from the highest price in the window
+    /// to the latest one, and a product is reported with its largest drop. Prices
+    /// are converted to `preferred_currency` with today's exchange rate; a link is
+    /// compared in its own currency when a rate is missing, and skipped if its
+    /// prices then mix currencies. Archived products are left out.
+    pub async fn find_recent_price_drops(
+        conn: &DatabaseConnection,
+        since: DateTime<Utc>,
+        limit: u64,
+        preferred_currency: &str,
+    ) -> Result<Vec<PriceDrop>, AppError> {
+        let rows = PricedCheckRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            r#"
+                SELECT ac.product_id,
+                       ac.product_retailer_id,
+                       ac.price_minor_units,
+                       ac.price_currency,
+                       ac.checked_at
+                FROM availability_checks ac
+                JOIN products p ON p.id = ac.product_id
+                WHERE ac.checked_at >= ?
+                  AND ac.price_minor_units IS NOT NULL
+                  AND ac.price_currency IS NOT NULL
+                  AND p.is_archived = 0
+                ORDER BY ac.checked_at ASC
+            "#,
+            [since.into()],
+        ))
+        .all(conn)
+        .await?;
+
+        // Group converted prices by retailer link, oldest first
+        let mut rates: HashMap<String, Option<f64>> = HashMap::new();
+        let mut by_target: HashMap<(Uuid, Option<Uuid>), Vec<PricedCheckRow>> = HashMap::new();
+        for mut row in rows {
+            (row.price_minor_units, row.price_currency) = Self::convert_price(
+                conn,
+                &mut rates,
+                row.price_minor_units,
+                &row.price_currency,
+                preferred_currency,
+            )
+            .await;
+            by_target
+                .entry((row.product_id, row.product_retailer_id))
+                .or_default()
+                .push(row);
+        }
+
+        let mut by_product: HashMap<Uuid, PriceDrop> = HashMap::new();
+        for prices in by_target.into_values() {
+            let Some((current, earlier)) = prices.split_last() else {
+                continue;
+            };
+            if earlier
+                .iter()
+                .any(|row| row.price_currency != current.price_currency)
+            {
+                continue;
+            }
+            let Some(previous) = earlier.iter().map(|row| row.price_minor_units).max() else {
+                continue;
+            };
+            if previous <= 0 || current.price_minor_units >= previous {
+                continue;
+            }
+
+            let drop = PriceDrop {
+                product_id: current.product_id,
+                product_retailer_id: current.product_retailer_id,
+                previous_price_minor_units: previous,
+                current_price_minor_units: current.price_minor_units,
+                currency: current.price_currency.clone(),
+                drop_percent: (previous - current.price_minor_units) as f64 / previous as f64
+                    * 100.0,
+                current_checked_at: current.checked_at,
+            };
+            let is_larger = match by_product.get(&drop.product_id) {
+                Some(best) => drop.drop_percent > best.drop_percent,
+                None => true,
+            };
+            if is_larger {
+                by_product.insert(drop.product_id, drop);
+            }
+        }
+
+        let mut drops: Vec<PriceDrop> = by_product.into_values().collect();
+        drops.sort_by(|a, b| {
+            b.drop_percent
+                .total_cmp(&a.drop_percent)
+                .then(b.current_checked_at.cmp(&a.current_checked_at))
+        });
+        drops.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+        Ok(drops)
+    }
+
+    /// Convert a price to the preferred currency with today's rate, caching rates
+    /// per currency. Returns the price unchanged in its own currency when no rate
+    /// is available.
+    async fn convert_price(
+        conn: &DatabaseConnection,
+        rates: &mut HashMap<String, Option<f64>>,
+        price_minor_units: i64,
+        price_currency: &str,
+        preferred_currency: &str,
+    ) -> (i64, String) {
+        use crate::services::currency::currency_exponent;
+        use product_stalker_core::services::ExchangeRateService;
+
+        if price_currency.eq_ignore_ascii_case(preferred_currency) {
+            return (price_minor_units, preferred_currency.to_string());
+        }
+
+        let key = price_currency.to_ascii_uppercase();
+        let rate = match rates.get(&key) {
+            Some(rate) => *rate,
+            None => {
+                let rate = ExchangeRateService::get_rate(conn, price_currency, preferred_currency)
+                    .await
+                    .map_err(|e| {
+                        log::warn!(
+                            "Comparing price drops natively, cannot convert {} to {}: {}",
+                            price_currency,
+                            preferred_currency,
+                            e
+                        );
+                    })
+                    .ok();
+                rates.insert(key, rate);
+                rate
+            }
+        };
+
+        match rate {
+            Some(rate) => (
+                ExchangeRateService::convert_minor_units(
+                    price_minor_units,
+                    rate,
+                    currency_exponent(price_currency),
+                    currency_exponent(preferred_currency),
+                ),
+                preferred_currency.to_string(),
+            ),
+            None => (price_minor_units, price_currency.to_string()),
+        }
+    }
+
     /// Find all availability checks that have price data (both price_minor_units
     /// and price_currency are non-null).
     pub async fn find_all_with_price_data(
@@ -287,40 +1108,521 @@ impl AvailabilityCheckRepository {
         Ok(updated)
     }
 
-    /// Get average original price for a product-retailer within a time period [from, to).
+    /// Get the lowest and highest prices ever recorded for a product.
     ///
-    /// Uses original `price_minor_units` (not normalized) because each retailer
-    /// operates in a single currency, making the percentage change inherently
-    /// rate-stable without re-normalization.
-    pub async fn get_average_price_for_period_by_product_retailer(
+    /// Uses the normalized price when available, falling back to the original price.
+    /// Only prices in the currency of the latest priced check are compared, so a
+    /// retailer in another currency can't skew the range. When the same price was
+    /// seen more than once, the most recent check is returned.
+    pub async fn get_price_extremes(
         conn: &DatabaseConnection,
-        product_retailer_id: Uuid,
+        product_id: Uuid,
+    ) -> Result<PriceExtremes, AppError> {
+        let points: Vec<PricePoint> = AvailabilityCheck::find()
+            .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
+            .filter(AvailabilityCheckColumn::PriceMinorUnits.is_not_null())
+            .order_by_desc(AvailabilityCheckColumn::CheckedAt)
+            .all(conn)
+            .await?
+            .iter()
+            .filter_map(PricePoint::from_check)
+            .collect();
+
+        let currency = points.first().map(|point| point.price_currency.clone());
+        let comparable = || {
+            points
+                .iter()
+                .filter(|point| Some(&point.price_currency) == currency.as_ref())
+        };
+        // Points are newest first, so keeping the earlier point on a tie keeps the most recent
+        let lowest = comparable().reduce(|best, point| {
+            if point.price_minor_units < best.price_minor_units {
+                point
+            } else {
+                best
+            }
+        });
+        let highest = comparable().reduce(|best, point| {
+            if point.price_minor_units > best.price_minor_units {
+                point
+            } else {
+                best
+            }
+        });
+        let first = AvailabilityCheck::find()
+            .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
+            .order_by_asc(AvailabilityCheckColumn::CheckedAt)
+            .one(conn)
+            .await?;
+
+        Ok(PriceExtremes {
+            lowest: lowest.cloned(),
+            highest: highest.cloned(),
+            first_checked_at: first.map(|check| check.checked_at),
+        })
+    }
+
+    /// Classify a product's price over the last `days` as rising, falling or stable.
+    ///
+    /// Uses the normalized price when available, falling back to the original price,
+    /// across all retailers. See [`PriceTrend::from_samples`] for the fit.
+    pub async fn get_price_trend(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        days: i64,
+    ) -> Result<PriceTrend, AppError> {
+        if days <= 0 {
+            return Err(AppError::Validation(format!(
+                "Trend window must be at least one day, got {}",
+                days
+            )));
+        }
+        let since = Utc::now() - chrono::Duration::days(days);
+
+        let samples: Vec<(DateTime<Utc>, i64)> = AvailabilityCheck::find()
+            .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
+            .filter(AvailabilityCheckColumn::CheckedAt.gte(since))
+            .filter(AvailabilityCheckColumn::PriceMinorUnits.is_not_null())
+            .order_by_asc(AvailabilityCheckColumn::CheckedAt)
+            .all(conn)
+            .await?
+            .into_iter()
+            .filter_map(|check| {
+                let price = check
+                    .normalized_price_minor_units
+                    .or(check.price_minor_units)?;
+                Some((check.checked_at, price))
+            })
+            .collect();
+
+        Ok(PriceTrend::from_samples(&samples, days))
+    }
+
+    /// Get the average price per time bucket for a product within [from, to).
+    ///
+    /// Uses the normalized price when available, falling back to the original price,
+    /// and averages across all retailers. As in [`Self::get_price_extremes`], only
+    /// prices in the currency of the range's latest priced check are averaged; other
+    /// checks count as unpriced. Buckets that only contain checks without a
+    /// (comparable) price yield `None`. Buckets with no checks are omitted. Ordered
+    /// oldest first.
+    pub async fn get_price_series(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        bucket: Bucket,
         from: DateTime<Utc>,
         to: DateTime<Utc>,
-    ) -> Result<Option<i64>, AppError> {
+    ) -> Result<Vec<(DateTime<Utc>, Option<i64>)>, AppError> {
         use sea_orm::Value;
 
-        let result = AveragePriceResult::find_by_statement(Statement::from_sql_and_values(
-            DbBackend::Sqlite,
+        let latest = AvailabilityCheck::find()
+            .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
+            .filter(AvailabilityCheckColumn::CheckedAt.gte(from))
+            .filter(AvailabilityCheckColumn::CheckedAt.lt(to))
+            .filter(AvailabilityCheckColumn::PriceMinorUnits.is_not_null())
+            .order_by_desc(AvailabilityCheckColumn::CheckedAt)
+            .one(conn)
+            .await?;
+        let currency = latest
+            .as_ref()
+            .and_then(PricePoint::from_check)
+            .and_then(|point| point.price_currency);
+
+        // `IS` so that legacy checks without a currency still compare equal
+        let sql = format!(
             r#"
-                SELECT AVG(price_minor_units) as avg_price
+                SELECT {} as bucket_start,
+                       AVG(CASE
+                           WHEN (CASE WHEN normalized_price_minor_units IS NOT NULL
+                                      THEN normalized_currency
+                                      ELSE price_currency END) IS ?
+                           THEN COALESCE(normalized_price_minor_units, price_minor_units)
+                       END) as avg_price
                 FROM availability_checks
-                WHERE product_retailer_id = ?
+                WHERE product_id = ?
                   AND checked_at >= ?
                   AND checked_at < ?
-                  AND price_minor_units IS NOT NULL
+                GROUP BY bucket_start
+                ORDER BY bucket_start ASC
             "#,
+            bucket.bucket_start_sql()
+        );
+
+        let rows = PriceBucketResult::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            sql,
             [
-                Value::Uuid(Some(Box::new(product_retailer_id))),
+                currency.into(),
+                Value::Uuid(Some(Box::new(product_id))),
                 from.into(),
                 to.into(),
             ],
         ))
-        .one(conn)
+        .all(conn)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let start = Self::parse_bucket_start(&row.bucket_start)?;
+                Ok((start, row.avg_price.map(|avg| avg.round() as i64)))
+            })
+            .collect()
+    }
+
+    /// Get every check for a product within [from, to) with the start of its time
+    /// bucket, oldest first.
+    ///
+    /// Unlike `get_price_series`, prices are left unaveraged in their original
+    /// currency so callers can convert each one at the rate of its own time.
+    /// Checks without a price yield `None`.
+    pub async fn get_bucketed_prices(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        bucket: Bucket,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, Option<PricePoint>)>, AppError> {
+        use sea_orm::Value;
+
+        let sql = format!(
+            r#"
+                SELECT {} as bucket_start, price_minor_units, price_currency, checked_at
+                FROM availability_checks
+                WHERE product_id = ?
+                  AND checked_at >= ?
+                  AND checked_at < ?
+                ORDER BY checked_at ASC
+            "#,
+            bucket.bucket_start_sql()
+        );
+
+        let rows = BucketedCheckRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            sql,
+            [
+                Value::Uuid(Some(Box::new(product_id))),
+                from.into(),
+                to.into(),
+            ],
+        ))
+        .all(conn)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let start = Self::parse_bucket_start(&row.bucket_start)?;
+                let point = row.price_minor_units.map(|price_minor_units| PricePoint {
+                    price_minor_units,
+                    price_currency: row.price_currency,
+                    checked_at: row.checked_at,
+                });
+                Ok((start, point))
+            })
+            .collect()
+    }
+
+    /// Parse a bucket start produced by `Bucket::bucket_start_sql`
+    fn parse_bucket_start(bucket_start: &str) -> Result<DateTime<Utc>, AppError> {
+        Ok(DateTime::parse_from_rfc3339(bucket_start)
+            .map_err(|e| {
+                AppError::Internal(format!(
+                    "Invalid price bucket timestamp '{}': {}",
+                    bucket_start, e
+                ))
+            })?
+            .with_timezone(&Utc))
+    }
+
+    /// Get average original price for a product-retailer within a time period [from, to).
+    ///
+    /// Uses original `price_minor_units` (not normalized) because each retailer
+    /// operates in a single currency, making the percentage change inherently
+    /// rate-stable without re-normalization.
+    pub async fn get_average_price_for_period_by_product_retailer(
+        conn: &DatabaseConnection,
+        product_retailer_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Option<i64>, AppError> {
+        use sea_orm::Value;
+
+        let result = AveragePriceResult::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            r#"
+                SELECT AVG(price_minor_units) as avg_price
+                FROM availability_checks
+                WHERE product_retailer_id = ?
+                  AND checked_at >= ?
+                  AND checked_at < ?
+                  AND price_minor_units IS NOT NULL
+            "#,
+            [
+                Value::Uuid(Some(Box::new(product_retailer_id))),
+                from.into(),
+                to.into(),
+            ],
+        ))
+        .one(conn)
         .await?;
 
         Ok(result.and_then(|r| r.avg_price.map(|avg| avg.round() as i64)))
     }
+    /// Get the status transitions for a product, oldest first.
+    ///
+    /// Scans the product's checks in time order and emits a transition whenever
+    /// the status differs from the previous check of the same retailer link, so
+    /// interleaved checks of different retailers don't register as changes.
+    /// Failed checks are skipped since they didn't observe a status.
+    pub async fn get_status_transitions(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Vec<StatusTransition>, AppError> {
+        let checks = AvailabilityCheck::find()
+            .filter(AvailabilityCheckColumn::ProductId.eq(product_id))
+            .filter(AvailabilityCheckColumn::ErrorMessage.is_null())
+            .order_by_asc(AvailabilityCheckColumn::CheckedAt)
+            .all(conn)
+            .await?;
+
+        // Current status per retailer link, with when it was first observed
+        let mut current: HashMap<Option<Uuid>, (AvailabilityStatus, DateTime<Utc>)> =
+            HashMap::new();
+        let mut transitions = Vec::new();
+
+        for check in checks {
+            let status = check.status_enum();
+            match current.get(&check.product_retailer_id) {
+                Some((previous, _)) if *previous == status => continue,
+                Some((previous, since)) => transitions.push(StatusTransition {
+                    product_retailer_id: check.product_retailer_id,
+                    from_status: previous.clone(),
+                    to_status: status.clone(),
+                    changed_at: check.checked_at,
+                    previous_duration_secs: (check.checked_at - *since).num_seconds(),
+                }),
+                None => {}
+            }
+            current.insert(check.product_retailer_id, (status, check.checked_at));
+        }
+
+        Ok(transitions)
+    }
+
+    /// Get when a product came back in stock (OutOfStock → InStock) at or after
+    /// `since`, oldest first.
+    pub async fn find_restock_times(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, AppError> {
+        let transitions = Self::get_status_transitions(conn, product_id).await?;
+
+        Ok(transitions
+            .into_iter()
+            .filter(|t| {
+                t.from_status == AvailabilityStatus::OutOfStock
+                    && t.to_status == AvailabilityStatus::InStock
+                    && t.changed_at >= since
+            })
+            .map(|t| t.changed_at)
+            .collect())
+    }
+
+    /// Count how many times a product came back in stock at or after `since`
+    pub async fn count_restock_events(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<u32, AppError> {
+        let restocks = Self::find_restock_times(conn, product_id, since).await?;
+        Ok(restocks.len() as u32)
+    }
+
+    /// Find products whose latest status differs from their status as of `since`,
+    /// with the latest check, newest first.
+    ///
+    /// Statuses are compared per retailer link, so a product can appear once per
+    /// changed retailer. Failed checks are skipped since they didn't observe a
+    /// status, and links with no check at or before `since` are left out.
+    pub async fn find_changed_since(
+        conn: &DatabaseConnection,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(ProductModel, AvailabilityCheckModel)>, AppError> {
+        let rows = CheckIdRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            r#"
+                WITH latest AS (
+                    SELECT id, product_id, product_retailer_id, status,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY product_id, product_retailer_id
+                               ORDER BY checked_at DESC
+                           ) as rn
+                    FROM availability_checks
+                    WHERE error_message IS NULL AND checked_at > ?
+                ),
+                previous AS (
+                    SELECT product_id, product_retailer_id, status,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY product_id, product_retailer_id
+                               ORDER BY checked_at DESC
+                           ) as rn
+                    FROM availability_checks
+                    WHERE error_message IS NULL AND checked_at <= ?
+                )
+                SELECT l.id as check_id
+                FROM latest l
+                JOIN previous p
+                  ON p.product_id = l.product_id
+                 AND p.product_retailer_id IS l.product_retailer_id
+                 AND p.rn = 1
+                WHERE l.rn = 1
+                  AND l.status != p.status
+            "#,
+            [since.into(), since.into()],
+        ))
+        .all(conn)
+        .await?;
+
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let checks = AvailabilityCheck::find()
+            .filter(AvailabilityCheckColumn::Id.is_in(rows.into_iter().map(|row| row.check_id)))
+            .order_by_desc(AvailabilityCheckColumn::CheckedAt)
+            .find_also_related(Product)
+            .all(conn)
+            .await?;
+
+        Ok(checks
+            .into_iter()
+            .filter_map(|(check, product)| product.map(|product| (product, check)))
+            .collect())
+    }
+
+    /// Count outcomes of the latest `limit_per_host` checks for each host.
+    ///
+    /// The host is derived from the retailer link's URL, falling back to the
+    /// product's own URL for legacy checks. Rows are grouped by host, whether
+    /// the check failed, and its error kind.
+    pub async fn count_recent_outcomes_by_host(
+        conn: &DatabaseConnection,
+        limit_per_host: u64,
+    ) -> Result<Vec<HostOutcomeCount>, AppError> {
+        let rows = HostOutcomeCount::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            r#"
+                WITH checked_urls AS (
+                    SELECT checked_at, error_message, error_kind,
+                           substr(url, instr(url, '://') + 3) as rest
+                    FROM (
+                        SELECT ac.checked_at, ac.error_message, ac.error_kind,
+                               COALESCE(pr.url, p.url) as url
+                        FROM availability_checks ac
+                        JOIN products p ON p.id = ac.product_id
+                        LEFT JOIN product_retailers pr ON pr.id = ac.product_retailer_id
+                    )
+                    WHERE instr(url, '://') > 0
+                ),
+                recent AS (
+                    SELECT host, error_message, error_kind,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY host
+                               ORDER BY checked_at DESC
+                           ) as rn
+                    FROM (
+                        SELECT checked_at, error_message, error_kind,
+                               lower(CASE WHEN instr(rest, '/') > 0
+                                          THEN substr(rest, 1, instr(rest, '/') - 1)
+                                          ELSE rest END) as host
+                        FROM checked_urls
+                    )
+                    WHERE host != ''
+                )
+                SELECT host,
+                       error_message IS NOT NULL as failed,
+                       error_kind,
+                       COUNT(*) as check_count
+                FROM recent
+                WHERE rn <= ?
+                GROUP BY host, failed, error_kind
+                ORDER BY host
+            "#,
+            [(limit_per_host as i64).into()],
+        ))
+        .all(conn)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Median and 95th percentile fetch time per host, over each host's most
+    /// recent `limit_per_host` checks that recorded a fetch time.
+    ///
+    /// Hosts are derived the same way as in [`Self::count_recent_outcomes_by_host`].
+    /// Percentiles use the nearest-rank method, so they are always a recorded value.
+    pub async fn fetch_time_percentiles_by_host(
+        conn: &DatabaseConnection,
+        limit_per_host: u64,
+    ) -> Result<Vec<HostFetchTiming>, AppError> {
+        let rows = HostFetchTiming::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            r#"
+                WITH checked_urls AS (
+                    SELECT checked_at, fetch_duration_ms,
+                           substr(url, instr(url, '://') + 3) as rest
+                    FROM (
+                        SELECT ac.checked_at, ac.fetch_duration_ms,
+                               COALESCE(pr.url, p.url) as url
+                        FROM availability_checks ac
+                        JOIN products p ON p.id = ac.product_id
+                        LEFT JOIN product_retailers pr ON pr.id = ac.product_retailer_id
+                        WHERE ac.fetch_duration_ms IS NOT NULL
+                    )
+                    WHERE instr(url, '://') > 0
+                ),
+                recent AS (
+                    SELECT host, fetch_duration_ms,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY host
+                               ORDER BY checked_at DESC
+                           ) as rn
+                    FROM (
+                        SELECT checked_at, fetch_duration_ms,
+                               lower(CASE WHEN instr(rest, '/') > 0
+                                          THEN substr(rest, 1, instr(rest, '/') - 1)
+                                          ELSE rest END) as host
+                        FROM checked_urls
+                    )
+                    WHERE host != ''
+                ),
+                ranked AS (
+                    SELECT host, fetch_duration_ms,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY host
+                               ORDER BY fetch_duration_ms
+                           ) as rank,
+                           COUNT(*) OVER (PARTITION BY host) as n
+                    FROM recent
+                    WHERE rn <= ?
+                )
+                SELECT host,
+                       n as sample_count,
+                       MIN(CASE WHEN rank * 100 >= n * 50 THEN fetch_duration_ms END)
+                           as p50_fetch_ms,
+                       MIN(CASE WHEN rank * 100 >= n * 95 THEN fetch_duration_ms END)
+                           as p95_fetch_ms
+                FROM ranked
+                GROUP BY host
+                ORDER BY host
+            "#,
+            [(limit_per_host as i64).into()],
+        ))
+        .all(conn)
+        .await?;
+
+        Ok(rows)
+    }
 }
 
 #[cfg(test)]
@@ -339,12 +1641,18 @@ impl AvailabilityCheckRepository {
             status: Set("in_stock".to_string()),
             raw_availability: Set(None),
             error_message: Set(None),
+            error_kind: Set(None),
             checked_at: Set(checked_at),
             price_minor_units: Set(price_minor_units),
             price_currency: Set(Some("USD".to_string())),
             raw_price: Set(None),
             normalized_price_minor_units: Set(None),
             normalized_currency: Set(None),
+            compare_at_minor_units: Set(None),
+            price_valid_until: Set(None),
+            fetch_duration_ms: Set(None),
+            content_bytes: Set(None),
+            confidence: Set(None),
         };
         active_model.insert(conn).await.unwrap()
     }
@@ -365,12 +1673,18 @@ impl AvailabilityCheckRepository {
             status: Set("in_stock".to_string()),
             raw_availability: Set(None),
             error_message: Set(None),
+            error_kind: Set(None),
             checked_at: Set(checked_at),
             price_minor_units: Set(price_minor_units),
             price_currency: Set(price_currency.map(|s| s.to_string())),
             raw_price: Set(None),
             normalized_price_minor_units: Set(None),
             normalized_currency: Set(None),
+            compare_at_minor_units: Set(None),
+            price_valid_until: Set(None),
+            fetch_duration_ms: Set(None),
+            content_bytes: Set(None),
+            confidence: Set(None),
         };
         active_model.insert(conn).await.unwrap()
     }
@@ -379,7 +1693,9 @@ impl AvailabilityCheckRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{create_test_product_default, setup_availability_db};
+    use crate::test_utils::{
+        create_test_product, create_test_product_default, setup_availability_db,
+    };
 
     #[tokio::test]
     async fn test_create_availability_check() {
@@ -423,6 +1739,7 @@ mod tests {
             CreateCheckParams {
                 status: AvailabilityStatus::Unknown,
                 error_message: Some("Failed to fetch page".to_string()),
+                error_kind: Some(CheckErrorKind::Network),
                 ..Default::default()
             },
         )
@@ -435,6 +1752,8 @@ mod tests {
             check.error_message,
             Some("Failed to fetch page".to_string())
         );
+        assert_eq!(check.error_kind.as_deref(), Some("network"));
+        assert_eq!(check.error_kind_enum(), Some(CheckErrorKind::Network));
     }
 
     #[tokio::test]
@@ -511,6 +1830,121 @@ mod tests {
         assert!(latest.is_none());
     }
 
+    #[tokio::test]
+    async fn test_find_latest_out_of_stock_at() {
+        let conn = setup_availability_db().await;
+        let product_id = create_test_product_default(&conn).await;
+
+        let none = AvailabilityCheckRepository::find_latest_out_of_stock_at(&conn, product_id)
+            .await
+            .unwrap();
+        assert!(none.is_none());
+
+        let mut back_order_at = None;
+        for params in [
+            CreateCheckParams {
+                status: AvailabilityStatus::OutOfStock,
+                ..Default::default()
+            },
+            CreateCheckParams {
+                status: AvailabilityStatus::BackOrder,
+                ..Default::default()
+            },
+            CreateCheckParams {
+                status: AvailabilityStatus::InStock,
+                ..Default::default()
+            },
+            // Failed checks didn't observe a status
+            CreateCheckParams {
+                status: AvailabilityStatus::OutOfStock,
+                error_message: Some("Network error: timeout".to_string()),
+                ..Default::default()
+            },
+        ] {
+            let is_back_order =
+                params.status == AvailabilityStatus::BackOrder && params.error_message.is_none();
+            let check =
+                AvailabilityCheckRepository::create(&conn, Uuid::new_v4(), product_id, params)
+                    .await
+                    .unwrap();
+            if is_back_order {
+                back_order_at = Some(check.checked_at);
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+
+        let latest = AvailabilityCheckRepository::find_latest_out_of_stock_at(&conn, product_id)
+            .await
+            .unwrap();
+        assert_eq!(latest, back_order_at);
+    }
+
+    #[tokio::test]
+    async fn test_get_first_in_stock_at() {
+        let conn = setup_availability_db().await;
+        let product_id = create_test_product_default(&conn).await;
+        let start = Utc::now() - chrono::Duration::days(10);
+
+        let none = AvailabilityCheckRepository::get_first_in_stock_at(&conn, product_id)
+            .await
+            .unwrap();
+        assert!(none.is_none());
+
+        // Out of stock for three days, a failed check claiming in stock, then a drop
+        for (day, status, error_message) in [
+            (0, AvailabilityStatus::OutOfStock, None),
+            (1, AvailabilityStatus::OutOfStock, None),
+            (
+                2,
+                AvailabilityStatus::InStock,
+                Some("Network error: timeout"),
+            ),
+            (3, AvailabilityStatus::OutOfStock, None),
+            (4, AvailabilityStatus::InStock, None),
+            (5, AvailabilityStatus::OutOfStock, None),
+            (6, AvailabilityStatus::InStock, None),
+        ] {
+            let check = AvailabilityCheckRepository::create_with_timestamp(
+                &conn,
+                product_id,
+                None,
+                start + chrono::Duration::days(day),
+            )
+            .await;
+            let mut active_model = check.into_active_model();
+            active_model.status = Set(status.as_str().to_string());
+            active_model.error_message = Set(error_message.map(str::to_string));
+            active_model.update(&conn).await.unwrap();
+        }
+
+        let first = AvailabilityCheckRepository::get_first_in_stock_at(&conn, product_id)
+            .await
+            .unwrap();
+        assert_eq!(first, Some(start + chrono::Duration::days(4)));
+    }
+
+    #[tokio::test]
+    async fn test_get_first_in_stock_at_never_in_stock() {
+        let conn = setup_availability_db().await;
+        let product_id = create_test_product_default(&conn).await;
+        AvailabilityCheckRepository::create(
+            &conn,
+            Uuid::new_v4(),
+            product_id,
+            CreateCheckParams {
+                status: AvailabilityStatus::OutOfStock,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let first = AvailabilityCheckRepository::get_first_in_stock_at(&conn, product_id)
+            .await
+            .unwrap();
+        assert!(first.is_none());
+    }
+
     #[tokio::test]
     async fn test_find_all_for_product() {
         let conn = setup_availability_db().await;
@@ -532,9 +1966,10 @@ mod tests {
             .unwrap();
         }
 
-        let all = AvailabilityCheckRepository::find_all_for_product(&conn, product_id, None)
-            .await
-            .unwrap();
+        let all =
+            AvailabilityCheckRepository::find_all_for_product(&conn, product_id, None, None, None)
+                .await
+                .unwrap();
 
         assert_eq!(all.len(), 5);
     }
@@ -560,61 +1995,143 @@ mod tests {
             .unwrap();
         }
 
-        let limited = AvailabilityCheckRepository::find_all_for_product(&conn, product_id, Some(3))
-            .await
-            .unwrap();
+        let limited = AvailabilityCheckRepository::find_all_for_product(
+            &conn,
+            product_id,
+            Some(3),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
 
         assert_eq!(limited.len(), 3);
     }
 
-    mod average_price_period_tests {
-        use super::*;
-        use chrono::Duration;
-
-        #[tokio::test]
-        async fn test_no_data_returns_none() {
-            let conn = setup_availability_db().await;
-            let product_id = create_test_product_default(&conn).await;
-            let now = Utc::now();
-            let from = now - Duration::hours(24);
-
-            let result = AvailabilityCheckRepository::get_average_price_for_period(
-                &conn, product_id, from, now,
-            )
-            .await
-            .unwrap();
-
-            assert_eq!(result, None);
-        }
-
-        #[tokio::test]
-        async fn test_single_check_returns_price() {
-            let conn = setup_availability_db().await;
-            let product_id = create_test_product_default(&conn).await;
-            let now = Utc::now();
-            let from = now - Duration::hours(24);
-            let check_time = now - Duration::hours(12);
+    #[tokio::test]
+    async fn test_find_all_for_product_in_range() {
+        let conn = setup_availability_db().await;
+        let product_id = create_test_product_default(&conn).await;
+        let start = Utc::now() - chrono::Duration::days(10);
 
+        // One check per day for 7 days
+        for day in 0..7 {
             AvailabilityCheckRepository::create_with_timestamp(
                 &conn,
                 product_id,
-                Some(10000),
-                check_time,
+                Some(1000 + day),
+                start + chrono::Duration::days(day),
             )
             .await;
-
-            let result = AvailabilityCheckRepository::get_average_price_for_period(
-                &conn, product_id, from, now,
-            )
-            .await
-            .unwrap();
-
-            assert_eq!(result, Some(10000));
         }
 
-        #[tokio::test]
-        async fn test_multiple_checks_returns_average() {
-            let conn = setup_availability_db().await;
+        let from = start + chrono::Duration::days(2);
+        let to = start + chrono::Duration::days(5);
+        let in_range = AvailabilityCheckRepository::find_all_for_product(
+            &conn,
+            product_id,
+            None,
+            Some(from),
+            Some(to),
+        )
+        .await
+        .unwrap();
+
+        // [from, to) covers days 2, 3 and 4, most recent first
+        let prices: Vec<_> = in_range.iter().map(|c| c.price_minor_units).collect();
+        assert_eq!(prices, vec![Some(1004), Some(1003), Some(1002)]);
+
+        let since = AvailabilityCheckRepository::find_all_for_product(
+            &conn,
+            product_id,
+            None,
+            Some(to),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(since.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_find_all_for_product_range_with_limit() {
+        let conn = setup_availability_db().await;
+        let product_id = create_test_product_default(&conn).await;
+        let start = Utc::now() - chrono::Duration::days(10);
+
+        for day in 0..7 {
+            AvailabilityCheckRepository::create_with_timestamp(
+                &conn,
+                product_id,
+                Some(1000 + day),
+                start + chrono::Duration::days(day),
+            )
+            .await;
+        }
+
+        let limited = AvailabilityCheckRepository::find_all_for_product(
+            &conn,
+            product_id,
+            Some(2),
+            Some(start + chrono::Duration::days(1)),
+            Some(start + chrono::Duration::days(5)),
+        )
+        .await
+        .unwrap();
+
+        // The limit keeps the most recent checks within the range
+        let prices: Vec<_> = limited.iter().map(|c| c.price_minor_units).collect();
+        assert_eq!(prices, vec![Some(1004), Some(1003)]);
+    }
+
+    mod average_price_period_tests {
+        use super::*;
+        use chrono::Duration;
+
+        #[tokio::test]
+        async fn test_no_data_returns_none() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let now = Utc::now();
+            let from = now - Duration::hours(24);
+
+            let result = AvailabilityCheckRepository::get_average_price_for_period(
+                &conn, product_id, from, now,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result, None);
+        }
+
+        #[tokio::test]
+        async fn test_single_check_returns_price() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let now = Utc::now();
+            let from = now - Duration::hours(24);
+            let check_time = now - Duration::hours(12);
+
+            AvailabilityCheckRepository::create_with_timestamp(
+                &conn,
+                product_id,
+                Some(10000),
+                check_time,
+            )
+            .await;
+
+            let result = AvailabilityCheckRepository::get_average_price_for_period(
+                &conn, product_id, from, now,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result, Some(10000));
+        }
+
+        #[tokio::test]
+        async fn test_multiple_checks_returns_average() {
+            let conn = setup_availability_db().await;
             let product_id = create_test_product_default(&conn).await;
             let now = Utc::now();
             let from = now - Duration::hours(24);
@@ -757,10 +2274,12 @@ mod tests {
         use crate::repositories::{
             CreateProductRetailerParams, ProductRetailerRepository, RetailerRepository,
         };
+        use crate::test_utils::setup_availability_db_with_exchange_rates;
         use chrono::Duration;
+        use product_stalker_core::repositories::ExchangeRateRepository;
 
         /// Helper to create a product_retailer record and return its ID
-        async fn create_test_product_retailer(
+        pub(super) async fn create_test_product_retailer(
             conn: &DatabaseConnection,
             product_id: Uuid,
             domain: &str,
@@ -790,7 +2309,7 @@ mod tests {
             let product_id = create_test_product_default(&conn).await;
 
             let result =
-                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id)
+                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id, None)
                     .await
                     .unwrap();
 
@@ -815,7 +2334,7 @@ mod tests {
             .await;
 
             let result =
-                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id)
+                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id, None)
                     .await
                     .unwrap();
 
@@ -856,7 +2375,7 @@ mod tests {
             .await;
 
             let result =
-                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id)
+                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id, None)
                     .await
                     .unwrap();
 
@@ -894,7 +2413,7 @@ mod tests {
             .await;
 
             let result =
-                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id)
+                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id, None)
                     .await
                     .unwrap();
 
@@ -914,7 +2433,7 @@ mod tests {
                 .await;
 
             let result =
-                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id)
+                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id, None)
                     .await
                     .unwrap();
 
@@ -941,12 +2460,127 @@ mod tests {
             .await;
 
             let result =
-                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id)
+                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id, None)
                     .await
                     .unwrap();
 
             assert!(result.is_none());
         }
+        /// Retailer A at USD 30.00 and retailer B at AUD 40.00
+        async fn seed_two_currency_retailers(conn: &DatabaseConnection) -> Uuid {
+            let product_id = create_test_product_default(conn).await;
+            let pr_a = create_test_product_retailer(conn, product_id, "shop-us.com").await;
+            let pr_b = create_test_product_retailer(conn, product_id, "shop-au.com").await;
+            let now = Utc::now();
+
+            AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                conn,
+                product_id,
+                pr_a,
+                Some(3000),
+                Some("USD"),
+                now,
+            )
+            .await;
+            AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                conn,
+                product_id,
+                pr_b,
+                Some(4000),
+                Some("AUD"),
+                now,
+            )
+            .await;
+
+            product_id
+        }
+
+        #[tokio::test]
+        async fn test_compares_in_preferred_currency() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product_id = seed_two_currency_retailers(&conn).await;
+            // USD 30.00 is AUD 45.00, so the AUD retailer is cheaper
+            ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+                .await
+                .unwrap();
+
+            let cheapest = AvailabilityCheckRepository::find_cheapest_current_price(
+                &conn,
+                product_id,
+                Some("AUD"),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+            assert_eq!(cheapest.price_minor_units, 4000);
+            assert_eq!(cheapest.price_currency, "AUD");
+            assert_eq!(cheapest.normalized_price_minor_units, Some(4000));
+            assert_eq!(cheapest.normalized_currency, Some("AUD".to_string()));
+            assert!(!cheapest.normalization_skipped);
+        }
+
+        #[tokio::test]
+        async fn test_returns_native_and_converted_price() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product_id = seed_two_currency_retailers(&conn).await;
+            // USD 30.00 is AUD 36.00, so the USD retailer is cheaper
+            ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.2, "api")
+                .await
+                .unwrap();
+
+            let cheapest = AvailabilityCheckRepository::find_cheapest_current_price(
+                &conn,
+                product_id,
+                Some("AUD"),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+            assert_eq!(cheapest.price_minor_units, 3000);
+            assert_eq!(cheapest.price_currency, "USD");
+            assert_eq!(cheapest.normalized_price_minor_units, Some(3600));
+            assert_eq!(cheapest.normalized_currency, Some("AUD".to_string()));
+            assert!(!cheapest.normalization_skipped);
+        }
+
+        #[tokio::test]
+        async fn test_missing_rate_falls_back_to_native_comparison() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product_id = seed_two_currency_retailers(&conn).await;
+
+            let cheapest = AvailabilityCheckRepository::find_cheapest_current_price(
+                &conn,
+                product_id,
+                Some("AUD"),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+            assert_eq!(cheapest.price_minor_units, 3000);
+            assert_eq!(cheapest.price_currency, "USD");
+            assert!(cheapest.normalized_price_minor_units.is_none());
+            assert!(cheapest.normalized_currency.is_none());
+            assert!(cheapest.normalization_skipped);
+        }
+
+        #[tokio::test]
+        async fn test_without_preferred_currency_compares_natively() {
+            let conn = setup_availability_db().await;
+            let product_id = seed_two_currency_retailers(&conn).await;
+
+            let cheapest =
+                AvailabilityCheckRepository::find_cheapest_current_price(&conn, product_id, None)
+                    .await
+                    .unwrap()
+                    .unwrap();
+
+            assert_eq!(cheapest.price_minor_units, 3000);
+            assert!(cheapest.normalized_price_minor_units.is_none());
+            assert!(!cheapest.normalization_skipped);
+        }
     }
 
     mod find_all_with_price_data_tests {
@@ -1009,6 +2643,207 @@ mod tests {
         }
     }
 
+    mod price_drop_tests {
+        use super::cheapest_price_tests::create_test_product_retailer;
+        use super::*;
+        use crate::repositories::{ProductRepository, ProductUpdateInput};
+        use crate::test_utils::setup_availability_db_with_exchange_rates;
+        use chrono::Duration;
+        use product_stalker_core::repositories::ExchangeRateRepository;
+
+        /// Record a USD price history for a new product, oldest first, an hour apart
+        async fn product_with_prices(conn: &DatabaseConnection, prices: &[i64]) -> Uuid {
+            let product_id = create_test_product_default(conn).await;
+            let start = Utc::now() - Duration::hours(prices.len() as i64);
+            for (i, price) in prices.iter().enumerate() {
+                AvailabilityCheckRepository::create_with_timestamp(
+                    conn,
+                    product_id,
+                    Some(*price),
+                    start + Duration::hours(i as i64),
+                )
+                .await;
+            }
+            product_id
+        }
+
+        #[tokio::test]
+        async fn test_orders_by_largest_drop() {
+            let conn = setup_availability_db().await;
+            let small = product_with_prices(&conn, &[10000, 9000]).await;
+            let large = product_with_prices(&conn, &[10000, 5000]).await;
+            let medium = product_with_prices(&conn, &[10000, 8000]).await;
+            product_with_prices(&conn, &[8000, 9000]).await;
+            product_with_prices(&conn, &[7000]).await;
+
+            let drops = AvailabilityCheckRepository::find_recent_price_drops(
+                &conn,
+                Utc::now() - Duration::days(1),
+                10,
+                "USD",
+            )
+            .await
+            .unwrap();
+
+            let ids: Vec<Uuid> = drops.iter().map(|d| d.product_id).collect();
+            assert_eq!(ids, vec![large, medium, small]);
+            assert!((drops[0].drop_percent - 50.0).abs() < 1e-9);
+            assert_eq!(drops[0].previous_price_minor_units, 10000);
+            assert_eq!(drops[0].current_price_minor_units, 5000);
+            assert_eq!(drops[0].currency, "USD");
+        }
+
+        #[tokio::test]
+        async fn test_measures_from_highest_price_in_window() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let now = Utc::now();
+            for (days_ago, price) in [(10, 20000), (3, 10000), (2, 12000), (1, 9000)] {
+                AvailabilityCheckRepository::create_with_timestamp(
+                    &conn,
+                    product_id,
+                    Some(price),
+                    now - Duration::days(days_ago),
+                )
+                .await;
+            }
+
+            let drops = AvailabilityCheckRepository::find_recent_price_drops(
+                &conn,
+                now - Duration::days(7),
+                10,
+                "USD",
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(drops.len(), 1);
+            assert_eq!(drops[0].previous_price_minor_units, 12000);
+            assert_eq!(drops[0].current_price_minor_units, 9000);
+            assert!((drops[0].drop_percent - 25.0).abs() < 1e-9);
+        }
+
+        #[tokio::test]
+        async fn test_respects_limit() {
+            let conn = setup_availability_db().await;
+            product_with_prices(&conn, &[10000, 9000]).await;
+            let large = product_with_prices(&conn, &[10000, 5000]).await;
+
+            let drops = AvailabilityCheckRepository::find_recent_price_drops(
+                &conn,
+                Utc::now() - Duration::days(1),
+                1,
+                "USD",
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(drops.len(), 1);
+            assert_eq!(drops[0].product_id, large);
+        }
+
+        #[tokio::test]
+        async fn test_normalizes_to_preferred_currency() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+                .await
+                .unwrap();
+            let product_id = create_test_product_default(&conn).await;
+            let pr_id = create_test_product_retailer(&conn, product_id, "shop-us.com").await;
+            let now = Utc::now();
+            for (hours_ago, price) in [(2, 3000), (1, 2000)] {
+                AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                    &conn,
+                    product_id,
+                    pr_id,
+                    Some(price),
+                    Some("USD"),
+                    now - Duration::hours(hours_ago),
+                )
+                .await;
+            }
+
+            let drops = AvailabilityCheckRepository::find_recent_price_drops(
+                &conn,
+                now - Duration::days(1),
+                10,
+                "AUD",
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(drops.len(), 1);
+            assert_eq!(drops[0].product_retailer_id, Some(pr_id));
+            assert_eq!(drops[0].previous_price_minor_units, 4500);
+            assert_eq!(drops[0].current_price_minor_units, 3000);
+            assert_eq!(drops[0].currency, "AUD");
+        }
+
+        #[tokio::test]
+        async fn test_missing_rate_compares_in_native_currency() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product_id = create_test_product_default(&conn).await;
+            let pr_id = create_test_product_retailer(&conn, product_id, "shop-eu.com").await;
+            let now = Utc::now();
+            for (hours_ago, price) in [(2, 2000), (1, 1000)] {
+                AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                    &conn,
+                    product_id,
+                    pr_id,
+                    Some(price),
+                    Some("EUR"),
+                    now - Duration::hours(hours_ago),
+                )
+                .await;
+            }
+
+            let drops = AvailabilityCheckRepository::find_recent_price_drops(
+                &conn,
+                now - Duration::days(1),
+                10,
+                "AUD",
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(drops.len(), 1);
+            assert_eq!(drops[0].currency, "EUR");
+            assert_eq!(drops[0].current_price_minor_units, 1000);
+            assert!((drops[0].drop_percent - 50.0).abs() < 1e-9);
+        }
+
+        #[tokio::test]
+        async fn test_skips_archived_products() {
+            let conn = setup_availability_db().await;
+            let product_id = product_with_prices(&conn, &[10000, 5000]).await;
+            let model = ProductRepository::find_by_id(&conn, product_id)
+                .await
+                .unwrap()
+                .unwrap();
+            ProductRepository::update(
+                &conn,
+                model,
+                ProductUpdateInput {
+                    is_archived: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+            let drops = AvailabilityCheckRepository::find_recent_price_drops(
+                &conn,
+                Utc::now() - Duration::days(1),
+                10,
+                "USD",
+            )
+            .await
+            .unwrap();
+
+            assert!(drops.is_empty());
+        }
+    }
+
     mod update_normalized_price_tests {
         use super::*;
 
@@ -1099,12 +2934,18 @@ mod tests {
                 status: Set("in_stock".to_string()),
                 raw_availability: Set(None),
                 error_message: Set(None),
+                error_kind: Set(None),
                 checked_at: Set(checked_at),
                 price_minor_units: Set(price),
                 price_currency: Set(currency.map(|s| s.to_string())),
                 raw_price: Set(None),
                 normalized_price_minor_units: Set(None),
                 normalized_currency: Set(None),
+                compare_at_minor_units: Set(None),
+                price_valid_until: Set(None),
+                fetch_duration_ms: Set(None),
+                content_bytes: Set(None),
+                confidence: Set(None),
             };
             model.insert(conn).await.unwrap();
         }
@@ -1296,4 +3137,1596 @@ mod tests {
             assert!((results[0].avg_price - 10000.0).abs() < 0.01);
         }
     }
+
+    mod price_series_tests {
+        use super::*;
+        use chrono::{Duration, TimeZone};
+
+        fn at(day: u32, hour: u32) -> DateTime<Utc> {
+            // 2024-01-01 is a Monday
+            Utc.with_ymd_and_hms(2024, 1, day, hour, 0, 0).unwrap()
+        }
+
+        async fn seed(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            price: Option<i64>,
+            time: DateTime<Utc>,
+        ) {
+            AvailabilityCheckRepository::create_with_timestamp(conn, product_id, price, time).await;
+        }
+
+        #[tokio::test]
+        async fn test_no_data_returns_empty() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let series = AvailabilityCheckRepository::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Daily,
+                at(1, 0),
+                at(8, 0),
+            )
+            .await
+            .unwrap();
+
+            assert!(series.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_daily_buckets_average_per_day() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            // Day 1: 1000 and 2000 -> 1500
+            seed(&conn, product_id, Some(1000), at(1, 3)).await;
+            seed(&conn, product_id, Some(2000), at(1, 21)).await;
+            // Day 2: 3000, 3001, 3003 -> 3001.33 -> 3001
+            seed(&conn, product_id, Some(3000), at(2, 0)).await;
+            seed(&conn, product_id, Some(3001), at(2, 12)).await;
+            seed(&conn, product_id, Some(3003), at(2, 23)).await;
+            // Day 4: single check, day 3 has none
+            seed(&conn, product_id, Some(500), at(4, 9)).await;
+
+            let series = AvailabilityCheckRepository::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Daily,
+                at(1, 0),
+                at(8, 0),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(
+                series,
+                vec![
+                    (at(1, 0), Some(1500)),
+                    (at(2, 0), Some(3001)),
+                    (at(4, 0), Some(500)),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_hourly_buckets() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            seed(&conn, product_id, Some(1000), at(1, 5)).await;
+            seed(
+                &conn,
+                product_id,
+                Some(2000),
+                at(1, 5) + Duration::minutes(45),
+            )
+            .await;
+            seed(
+                &conn,
+                product_id,
+                Some(4000),
+                at(1, 6) + Duration::minutes(1),
+            )
+            .await;
+
+            let series = AvailabilityCheckRepository::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Hourly,
+                at(1, 0),
+                at(2, 0),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(series, vec![(at(1, 5), Some(1500)), (at(1, 6), Some(4000))]);
+        }
+
+        #[tokio::test]
+        async fn test_weekly_buckets_start_on_monday() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            // Week of Mon 2024-01-01
+            seed(&conn, product_id, Some(1000), at(1, 10)).await;
+            seed(&conn, product_id, Some(3000), at(7, 23)).await; // Sunday
+                                                                  // Week of Mon 2024-01-08
+            seed(&conn, product_id, Some(5000), at(8, 0)).await;
+
+            let series = AvailabilityCheckRepository::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Weekly,
+                at(1, 0),
+                at(15, 0),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(series, vec![(at(1, 0), Some(2000)), (at(8, 0), Some(5000))]);
+        }
+
+        #[tokio::test]
+        async fn test_averages_only_latest_currency() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let pr_us = cheapest_price_tests::create_test_product_retailer(
+                &conn,
+                product_id,
+                "shop-us.com",
+            )
+            .await;
+            let pr_au = cheapest_price_tests::create_test_product_retailer(
+                &conn,
+                product_id,
+                "shop-au.com",
+            )
+            .await;
+
+            let checks = [
+                (pr_us, 1000, "USD", at(1, 10)),
+                (pr_au, 1600, "AUD", at(1, 11)),
+                (pr_au, 1500, "AUD", at(2, 10)),
+                (pr_us, 3000, "USD", at(2, 11)),
+            ];
+            for (pr_id, price, currency, time) in checks {
+                AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                    &conn,
+                    product_id,
+                    pr_id,
+                    Some(price),
+                    Some(currency),
+                    time,
+                )
+                .await;
+            }
+
+            let series = AvailabilityCheckRepository::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Daily,
+                at(1, 0),
+                at(3, 0),
+            )
+            .await
+            .unwrap();
+
+            // The latest check is in USD, so the AUD prices are left out
+            assert_eq!(series, vec![(at(1, 0), Some(1000)), (at(2, 0), Some(3000))]);
+        }
+
+        #[tokio::test]
+        async fn test_averages_normalized_prices_across_currencies() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let pr_us = cheapest_price_tests::create_test_product_retailer(
+                &conn,
+                product_id,
+                "shop-us.com",
+            )
+            .await;
+            let pr_au = cheapest_price_tests::create_test_product_retailer(
+                &conn,
+                product_id,
+                "shop-au.com",
+            )
+            .await;
+
+            // USD 10.00 converted to AUD 15.00, alongside a native AUD 17.00
+            let us = AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                product_id,
+                pr_us,
+                Some(1000),
+                Some("USD"),
+                at(1, 10),
+            )
+            .await;
+            AvailabilityCheckRepository::update_normalized_price(
+                &conn,
+                us.id,
+                Some(1500),
+                Some("AUD".to_string()),
+            )
+            .await
+            .unwrap();
+            AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                product_id,
+                pr_au,
+                Some(1700),
+                Some("AUD"),
+                at(1, 11),
+            )
+            .await;
+
+            let series = AvailabilityCheckRepository::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Daily,
+                at(1, 0),
+                at(2, 0),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(series, vec![(at(1, 0), Some(1600))]);
+        }
+
+        #[tokio::test]
+        async fn test_bucket_without_prices_is_none() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            seed(&conn, product_id, None, at(1, 10)).await;
+            seed(&conn, product_id, Some(1000), at(2, 10)).await;
+            seed(&conn, product_id, None, at(2, 11)).await;
+
+            let series = AvailabilityCheckRepository::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Daily,
+                at(1, 0),
+                at(3, 0),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(series, vec![(at(1, 0), None), (at(2, 0), Some(1000))]);
+        }
+
+        #[tokio::test]
+        async fn test_excludes_outside_range_and_other_products() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let other_id = create_test_product(&conn, "https://other.com").await;
+
+            seed(&conn, product_id, Some(9999), at(1, 23)).await; // before range
+            seed(&conn, product_id, Some(1000), at(2, 0)).await; // from is inclusive
+            seed(&conn, product_id, Some(9999), at(3, 0)).await; // to is exclusive
+            seed(&conn, other_id, Some(9999), at(2, 12)).await;
+
+            let series = AvailabilityCheckRepository::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Daily,
+                at(2, 0),
+                at(3, 0),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(series, vec![(at(2, 0), Some(1000))]);
+        }
+
+        #[test]
+        fn test_bucket_deserializes_snake_case() {
+            let bucket: Bucket = serde_json::from_str(r#""weekly""#).unwrap();
+            assert_eq!(bucket, Bucket::Weekly);
+            assert!(serde_json::from_str::<Bucket>(r#""monthly""#).is_err());
+        }
+    }
+
+    mod price_extremes_tests {
+        use super::cheapest_price_tests::create_test_product_retailer;
+        use super::*;
+        use chrono::Duration;
+
+        #[tokio::test]
+        async fn test_no_checks_returns_empty() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let extremes = AvailabilityCheckRepository::get_price_extremes(&conn, product_id)
+                .await
+                .unwrap();
+
+            assert_eq!(extremes, PriceExtremes::default());
+        }
+
+        #[tokio::test]
+        async fn test_finds_min_and_max_with_timestamps() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let other_id = create_test_product(&conn, "https://other.com").await;
+            let now = Utc::now();
+
+            let series = [
+                (Some(5000), 10),
+                (Some(4200), 8),
+                (None, 7),
+                (Some(3900), 6), // lowest
+                (Some(6100), 4), // highest
+                (Some(4500), 1),
+            ];
+            for (price, days_ago) in series {
+                AvailabilityCheckRepository::create_with_timestamp(
+                    &conn,
+                    product_id,
+                    price,
+                    now - Duration::days(days_ago),
+                )
+                .await;
+            }
+            AvailabilityCheckRepository::create_with_timestamp(&conn, other_id, Some(100), now)
+                .await;
+
+            let extremes = AvailabilityCheckRepository::get_price_extremes(&conn, product_id)
+                .await
+                .unwrap();
+
+            let lowest = extremes.lowest.unwrap();
+            assert_eq!(lowest.price_minor_units, 3900);
+            assert_eq!(lowest.price_currency, Some("USD".to_string()));
+            assert_eq!(lowest.checked_at, now - Duration::days(6));
+
+            let highest = extremes.highest.unwrap();
+            assert_eq!(highest.price_minor_units, 6100);
+            assert_eq!(highest.checked_at, now - Duration::days(4));
+
+            assert_eq!(extremes.first_checked_at, Some(now - Duration::days(10)));
+        }
+
+        #[tokio::test]
+        async fn test_repeated_lowest_returns_most_recent() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let now = Utc::now();
+
+            for days_ago in [5, 2] {
+                AvailabilityCheckRepository::create_with_timestamp(
+                    &conn,
+                    product_id,
+                    Some(1000),
+                    now - Duration::days(days_ago),
+                )
+                .await;
+            }
+
+            let extremes = AvailabilityCheckRepository::get_price_extremes(&conn, product_id)
+                .await
+                .unwrap();
+
+            assert_eq!(extremes.lowest.unwrap().checked_at, now - Duration::days(2));
+        }
+
+        #[tokio::test]
+        async fn test_unpriced_checks_only() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let now = Utc::now();
+
+            AvailabilityCheckRepository::create_with_timestamp(&conn, product_id, None, now).await;
+
+            let extremes = AvailabilityCheckRepository::get_price_extremes(&conn, product_id)
+                .await
+                .unwrap();
+
+            assert!(extremes.lowest.is_none());
+            assert!(extremes.highest.is_none());
+            assert_eq!(extremes.first_checked_at, Some(now));
+        }
+
+        #[tokio::test]
+        async fn test_ignores_prices_in_other_currencies() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let pr_us = create_test_product_retailer(&conn, product_id, "shop-us.com").await;
+            let pr_jp = create_test_product_retailer(&conn, product_id, "shop-jp.com").await;
+            let now = Utc::now();
+
+            let series = [
+                (pr_jp, 450_000, "JPY", 5),
+                (pr_us, 3000, "USD", 3),
+                (pr_jp, 90, "JPY", 2),
+                (pr_us, 5000, "USD", 0),
+            ];
+            for (pr_id, price, currency, days_ago) in series {
+                AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                    &conn,
+                    product_id,
+                    pr_id,
+                    Some(price),
+                    Some(currency),
+                    now - Duration::days(days_ago),
+                )
+                .await;
+            }
+
+            let extremes = AvailabilityCheckRepository::get_price_extremes(&conn, product_id)
+                .await
+                .unwrap();
+
+            let lowest = extremes.lowest.unwrap();
+            assert_eq!(lowest.price_minor_units, 3000);
+            assert_eq!(lowest.price_currency, Some("USD".to_string()));
+            let highest = extremes.highest.unwrap();
+            assert_eq!(highest.price_minor_units, 5000);
+            assert_eq!(highest.price_currency, Some("USD".to_string()));
+            assert_eq!(extremes.first_checked_at, Some(now - Duration::days(5)));
+        }
+
+        #[tokio::test]
+        async fn test_compares_normalized_prices() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let pr_us = create_test_product_retailer(&conn, product_id, "shop-us.com").await;
+            let pr_au = create_test_product_retailer(&conn, product_id, "shop-au.com").await;
+            let now = Utc::now();
+
+            // USD 30.00 converted to AUD 45.00
+            let us = AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                product_id,
+                pr_us,
+                Some(3000),
+                Some("USD"),
+                now - Duration::days(1),
+            )
+            .await;
+            AvailabilityCheckRepository::update_normalized_price(
+                &conn,
+                us.id,
+                Some(4500),
+                Some("AUD".to_string()),
+            )
+            .await
+            .unwrap();
+            AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                product_id,
+                pr_au,
+                Some(4000),
+                Some("AUD"),
+                now,
+            )
+            .await;
+
+            let extremes = AvailabilityCheckRepository::get_price_extremes(&conn, product_id)
+                .await
+                .unwrap();
+
+            let lowest = extremes.lowest.unwrap();
+            assert_eq!(lowest.price_minor_units, 4000);
+            assert_eq!(lowest.price_currency, Some("AUD".to_string()));
+            let highest = extremes.highest.unwrap();
+            assert_eq!(highest.price_minor_units, 4500);
+            assert_eq!(highest.price_currency, Some("AUD".to_string()));
+            assert_eq!(highest.checked_at, now - Duration::days(1));
+        }
+    }
+
+    mod failed_latest_check_tests {
+        use super::*;
+        use crate::repositories::{
+            CreateProductRetailerParams, ProductRepository, ProductRetailerRepository,
+            ProductUpdateInput, RetailerRepository,
+        };
+        use chrono::Duration;
+
+        async fn record_check(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            product_retailer_id: Option<Uuid>,
+            error_message: Option<&str>,
+            minutes_ago: i64,
+        ) {
+            let check = AvailabilityCheckRepository::create_with_timestamp(
+                conn,
+                product_id,
+                None,
+                Utc::now() - Duration::minutes(minutes_ago),
+            )
+            .await;
+            let mut active_model = check.into_active_model();
+            active_model.product_retailer_id = Set(product_retailer_id);
+            active_model.error_message = Set(error_message.map(str::to_string));
+            active_model.update(conn).await.unwrap();
+        }
+
+        async fn add_retailer(conn: &DatabaseConnection, product_id: Uuid, domain: &str) -> Uuid {
+            let retailer = RetailerRepository::find_or_create_by_domain(conn, domain)
+                .await
+                .unwrap();
+            ProductRetailerRepository::create(
+                conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: format!("https://{}/item", domain),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap()
+            .id
+        }
+
+        #[tokio::test]
+        async fn test_only_products_whose_latest_check_failed() {
+            let conn = setup_availability_db().await;
+
+            // Failed earlier, succeeded since
+            let recovered = create_test_product(&conn, "https://a.com/recovered").await;
+            record_check(&conn, recovered, None, Some("HTTP 503"), 60).await;
+            record_check(&conn, recovered, None, None, 10).await;
+
+            // Latest check failed
+            let failed = create_test_product(&conn, "https://a.com/failed").await;
+            record_check(&conn, failed, None, None, 60).await;
+            record_check(&conn, failed, None, Some("Request failed: timeout"), 10).await;
+
+            // Never checked
+            create_test_product(&conn, "https://a.com/new").await;
+
+            let ids = AvailabilityCheckRepository::find_products_with_failed_latest_check(&conn)
+                .await
+                .unwrap();
+            assert_eq!(ids, vec![failed]);
+        }
+
+        #[tokio::test]
+        async fn test_failure_at_any_retailer_counts() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://a.com/item").await;
+            let shop_a = add_retailer(&conn, product_id, "shop-a.com").await;
+            let shop_b = add_retailer(&conn, product_id, "shop-b.com").await;
+
+            // shop-b failed before shop-a's successful check
+            record_check(&conn, product_id, Some(shop_b), Some("HTTP 500"), 20).await;
+            record_check(&conn, product_id, Some(shop_a), None, 10).await;
+
+            let ids = AvailabilityCheckRepository::find_products_with_failed_latest_check(&conn)
+                .await
+                .unwrap();
+            assert_eq!(ids, vec![product_id]);
+        }
+
+        #[tokio::test]
+        async fn test_ignores_legacy_failures_once_retailers_exist() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://a.com/item").await;
+            record_check(&conn, product_id, None, Some("HTTP 404"), 60).await;
+
+            let shop = add_retailer(&conn, product_id, "shop.com").await;
+            record_check(&conn, product_id, Some(shop), None, 10).await;
+
+            let ids = AvailabilityCheckRepository::find_products_with_failed_latest_check(&conn)
+                .await
+                .unwrap();
+            assert!(ids.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_skips_paused_products_and_keeps_sort_order() {
+            let conn = setup_availability_db().await;
+            let first = create_test_product(&conn, "https://a.com/first").await;
+            let paused = create_test_product(&conn, "https://a.com/paused").await;
+            let last = create_test_product(&conn, "https://a.com/last").await;
+            for product_id in [last, paused, first] {
+                record_check(&conn, product_id, None, Some("HTTP 503"), 10).await;
+            }
+
+            let model = ProductRepository::find_by_id(&conn, paused)
+                .await
+                .unwrap()
+                .unwrap();
+            ProductRepository::update(
+                &conn,
+                model,
+                ProductUpdateInput {
+                    is_paused: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+            let ids = AvailabilityCheckRepository::find_products_with_failed_latest_check(&conn)
+                .await
+                .unwrap();
+            assert_eq!(ids, vec![first, last]);
+        }
+
+        #[tokio::test]
+        async fn test_skips_archived_products() {
+            let conn = setup_availability_db().await;
+            let active = create_test_product(&conn, "https://a.com/active").await;
+            let archived = create_test_product(&conn, "https://a.com/archived").await;
+            for product_id in [active, archived] {
+                record_check(&conn, product_id, None, Some("HTTP 503"), 10).await;
+            }
+
+            let model = ProductRepository::find_by_id(&conn, archived)
+                .await
+                .unwrap()
+                .unwrap();
+            ProductRepository::update(
+                &conn,
+                model,
+                ProductUpdateInput {
+                    is_archived: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+            let ids = AvailabilityCheckRepository::find_products_with_failed_latest_check(&conn)
+                .await
+                .unwrap();
+            assert_eq!(ids, vec![active]);
+        }
+    }
+
+    mod status_transition_tests {
+        use super::*;
+        use crate::repositories::{
+            CreateProductRetailerParams, ProductRetailerRepository, RetailerRepository,
+        };
+        use chrono::Duration;
+
+        async fn add_retailer(conn: &DatabaseConnection, product_id: Uuid, domain: &str) -> Uuid {
+            let retailer = RetailerRepository::find_or_create_by_domain(conn, domain)
+                .await
+                .unwrap();
+            ProductRetailerRepository::create(
+                conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: format!("https://{}/item", domain),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap()
+            .id
+        }
+
+        async fn record_status(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            product_retailer_id: Option<Uuid>,
+            status: AvailabilityStatus,
+            error_message: Option<&str>,
+            checked_at: DateTime<Utc>,
+        ) {
+            let check = AvailabilityCheckRepository::create_with_timestamp(
+                conn, product_id, None, checked_at,
+            )
+            .await;
+            let mut active_model = check.into_active_model();
+            active_model.product_retailer_id = Set(product_retailer_id);
+            active_model.status = Set(status.as_str().to_string());
+            active_model.error_message = Set(error_message.map(str::to_string));
+            active_model.update(conn).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_no_checks_returns_empty() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let transitions =
+                AvailabilityCheckRepository::get_status_transitions(&conn, product_id)
+                    .await
+                    .unwrap();
+
+            assert!(transitions.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_collapses_repeated_statuses_into_transitions() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let other_id = create_test_product(&conn, "https://other.com").await;
+            let start = Utc::now() - Duration::days(10);
+
+            let sequence = [
+                (AvailabilityStatus::InStock, 0),
+                (AvailabilityStatus::InStock, 1),
+                (AvailabilityStatus::OutOfStock, 2),
+                (AvailabilityStatus::OutOfStock, 3),
+                (AvailabilityStatus::OutOfStock, 4),
+                (AvailabilityStatus::InStock, 6),
+                (AvailabilityStatus::InStock, 7),
+            ];
+            for (status, day) in sequence {
+                record_status(
+                    &conn,
+                    product_id,
+                    None,
+                    status,
+                    None,
+                    start + Duration::days(day),
+                )
+                .await;
+            }
+            record_status(
+                &conn,
+                other_id,
+                None,
+                AvailabilityStatus::OutOfStock,
+                None,
+                start + Duration::days(5),
+            )
+            .await;
+
+            let transitions =
+                AvailabilityCheckRepository::get_status_transitions(&conn, product_id)
+                    .await
+                    .unwrap();
+
+            assert_eq!(transitions.len(), 2);
+            assert_eq!(transitions[0].from_status, AvailabilityStatus::InStock);
+            assert_eq!(transitions[0].to_status, AvailabilityStatus::OutOfStock);
+            assert_eq!(transitions[0].changed_at, start + Duration::days(2));
+            assert_eq!(
+                transitions[0].previous_duration_secs,
+                Duration::days(2).num_seconds()
+            );
+            assert_eq!(transitions[1].from_status, AvailabilityStatus::OutOfStock);
+            assert_eq!(transitions[1].to_status, AvailabilityStatus::InStock);
+            assert_eq!(transitions[1].changed_at, start + Duration::days(6));
+            assert_eq!(
+                transitions[1].previous_duration_secs,
+                Duration::days(4).num_seconds()
+            );
+        }
+
+        #[tokio::test]
+        async fn test_failed_checks_are_ignored() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let start = Utc::now() - Duration::days(3);
+
+            record_status(
+                &conn,
+                product_id,
+                None,
+                AvailabilityStatus::InStock,
+                None,
+                start,
+            )
+            .await;
+            record_status(
+                &conn,
+                product_id,
+                None,
+                AvailabilityStatus::Unknown,
+                Some("Request failed: timeout"),
+                start + Duration::days(1),
+            )
+            .await;
+            record_status(
+                &conn,
+                product_id,
+                None,
+                AvailabilityStatus::InStock,
+                None,
+                start + Duration::days(2),
+            )
+            .await;
+
+            let transitions =
+                AvailabilityCheckRepository::get_status_transitions(&conn, product_id)
+                    .await
+                    .unwrap();
+
+            assert!(transitions.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_retailer_links_are_tracked_separately() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let retailer_a = Some(add_retailer(&conn, product_id, "a.com").await);
+            let retailer_b = Some(add_retailer(&conn, product_id, "b.com").await);
+            let start = Utc::now() - Duration::hours(10);
+
+            // Interleaved checks: A stays in stock, B sells out at hour 3
+            let sequence = [
+                (retailer_a, AvailabilityStatus::InStock, 0),
+                (retailer_b, AvailabilityStatus::InStock, 1),
+                (retailer_a, AvailabilityStatus::InStock, 2),
+                (retailer_b, AvailabilityStatus::OutOfStock, 3),
+                (retailer_a, AvailabilityStatus::InStock, 4),
+            ];
+            for (retailer, status, hour) in sequence {
+                record_status(
+                    &conn,
+                    product_id,
+                    retailer,
+                    status,
+                    None,
+                    start + Duration::hours(hour),
+                )
+                .await;
+            }
+
+            let transitions =
+                AvailabilityCheckRepository::get_status_transitions(&conn, product_id)
+                    .await
+                    .unwrap();
+
+            assert_eq!(transitions.len(), 1);
+            assert_eq!(transitions[0].product_retailer_id, retailer_b);
+            assert_eq!(transitions[0].to_status, AvailabilityStatus::OutOfStock);
+            assert_eq!(
+                transitions[0].previous_duration_secs,
+                Duration::hours(2).num_seconds()
+            );
+        }
+
+        /// Seed a history that sells out and restocks on days 2/4, 6/9 and 12/13
+        async fn seed_restock_history(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            start: DateTime<Utc>,
+        ) {
+            let sequence = [
+                (AvailabilityStatus::InStock, 0),
+                (AvailabilityStatus::OutOfStock, 2),
+                (AvailabilityStatus::InStock, 4),
+                (AvailabilityStatus::OutOfStock, 6),
+                (AvailabilityStatus::Unknown, 7),
+                (AvailabilityStatus::InStock, 9),
+                (AvailabilityStatus::OutOfStock, 12),
+                (AvailabilityStatus::InStock, 13),
+            ];
+            for (status, day) in sequence {
+                record_status(
+                    conn,
+                    product_id,
+                    None,
+                    status,
+                    None,
+                    start + Duration::days(day),
+                )
+                .await;
+            }
+        }
+
+        #[tokio::test]
+        async fn test_count_restock_events_counts_out_of_stock_to_in_stock() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let start = Utc::now() - Duration::days(20);
+            seed_restock_history(&conn, product_id, start).await;
+
+            let count = AvailabilityCheckRepository::count_restock_events(
+                &conn,
+                product_id,
+                start - Duration::days(1),
+            )
+            .await
+            .unwrap();
+
+            // Day 9 comes from Unknown, not OutOfStock, so it isn't a restock
+            assert_eq!(count, 2);
+        }
+
+        #[tokio::test]
+        async fn test_count_restock_events_respects_window() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let start = Utc::now() - Duration::days(20);
+            seed_restock_history(&conn, product_id, start).await;
+
+            let count = AvailabilityCheckRepository::count_restock_events(
+                &conn,
+                product_id,
+                start + Duration::days(5),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(count, 1);
+        }
+
+        #[tokio::test]
+        async fn test_find_restock_times_returns_oldest_first() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let start = Utc::now() - Duration::days(20);
+            seed_restock_history(&conn, product_id, start).await;
+
+            let times = AvailabilityCheckRepository::find_restock_times(
+                &conn,
+                product_id,
+                start - Duration::days(1),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(
+                times,
+                vec![start + Duration::days(4), start + Duration::days(13)]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_count_restock_events_without_checks_is_zero() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let count = AvailabilityCheckRepository::count_restock_events(
+                &conn,
+                product_id,
+                Utc::now() - Duration::days(90),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(count, 0);
+        }
+
+        #[tokio::test]
+        async fn test_find_changed_since_returns_products_with_new_status() {
+            let conn = setup_availability_db().await;
+            let since = Utc::now() - Duration::days(1);
+            let restocked = create_test_product_default(&conn).await;
+            let unchanged = create_test_product_default(&conn).await;
+            let added_later = create_test_product_default(&conn).await;
+
+            let before = since - Duration::hours(1);
+            record_status(
+                &conn,
+                restocked,
+                None,
+                AvailabilityStatus::OutOfStock,
+                None,
+                before,
+            )
+            .await;
+            record_status(
+                &conn,
+                unchanged,
+                None,
+                AvailabilityStatus::InStock,
+                None,
+                before,
+            )
+            .await;
+
+            let after = since + Duration::hours(1);
+            record_status(
+                &conn,
+                restocked,
+                None,
+                AvailabilityStatus::InStock,
+                None,
+                after,
+            )
+            .await;
+            record_status(
+                &conn,
+                unchanged,
+                None,
+                AvailabilityStatus::InStock,
+                None,
+                after,
+            )
+            .await;
+            record_status(
+                &conn,
+                added_later,
+                None,
+                AvailabilityStatus::InStock,
+                None,
+                after,
+            )
+            .await;
+
+            let changes = AvailabilityCheckRepository::find_changed_since(&conn, since)
+                .await
+                .unwrap();
+
+            assert_eq!(changes.len(), 1);
+            let (product, check) = &changes[0];
+            assert_eq!(product.id, restocked);
+            assert_eq!(check.status_enum(), AvailabilityStatus::InStock);
+            assert_eq!(check.checked_at, after);
+        }
+
+        #[tokio::test]
+        async fn test_find_changed_since_ignores_status_that_changed_back() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let since = Utc::now() - Duration::days(1);
+
+            for (status, hours) in [
+                (AvailabilityStatus::InStock, -1),
+                (AvailabilityStatus::OutOfStock, 1),
+                (AvailabilityStatus::InStock, 2),
+            ] {
+                record_status(
+                    &conn,
+                    product_id,
+                    None,
+                    status,
+                    None,
+                    since + Duration::hours(hours),
+                )
+                .await;
+            }
+
+            let changes = AvailabilityCheckRepository::find_changed_since(&conn, since)
+                .await
+                .unwrap();
+
+            assert!(changes.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_find_changed_since_skips_failed_checks() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let since = Utc::now() - Duration::days(1);
+
+            record_status(
+                &conn,
+                product_id,
+                None,
+                AvailabilityStatus::OutOfStock,
+                None,
+                since - Duration::hours(1),
+            )
+            .await;
+            record_status(
+                &conn,
+                product_id,
+                None,
+                AvailabilityStatus::InStock,
+                None,
+                since + Duration::hours(1),
+            )
+            .await;
+            record_status(
+                &conn,
+                product_id,
+                None,
+                AvailabilityStatus::Unknown,
+                Some("timeout"),
+                since + Duration::hours(2),
+            )
+            .await;
+
+            let changes = AvailabilityCheckRepository::find_changed_since(&conn, since)
+                .await
+                .unwrap();
+
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].1.status_enum(), AvailabilityStatus::InStock);
+        }
+
+        #[tokio::test]
+        async fn test_find_changed_since_compares_per_retailer() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let retailer_a = add_retailer(&conn, product_id, "a.example.com").await;
+            let retailer_b = add_retailer(&conn, product_id, "b.example.com").await;
+            let since = Utc::now() - Duration::days(1);
+
+            let before = since - Duration::hours(2);
+            record_status(
+                &conn,
+                product_id,
+                Some(retailer_a),
+                AvailabilityStatus::InStock,
+                None,
+                before,
+            )
+            .await;
+            record_status(
+                &conn,
+                product_id,
+                Some(retailer_b),
+                AvailabilityStatus::OutOfStock,
+                None,
+                before + Duration::minutes(1),
+            )
+            .await;
+
+            let after = since + Duration::hours(1);
+            record_status(
+                &conn,
+                product_id,
+                Some(retailer_b),
+                AvailabilityStatus::InStock,
+                None,
+                after,
+            )
+            .await;
+            record_status(
+                &conn,
+                product_id,
+                Some(retailer_a),
+                AvailabilityStatus::InStock,
+                None,
+                after + Duration::minutes(1),
+            )
+            .await;
+
+            let changes = AvailabilityCheckRepository::find_changed_since(&conn, since)
+                .await
+                .unwrap();
+
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].0.id, product_id);
+            assert_eq!(changes[0].1.product_retailer_id, Some(retailer_b));
+        }
+
+        #[tokio::test]
+        async fn test_find_changed_since_without_checks_is_empty() {
+            let conn = setup_availability_db().await;
+            create_test_product_default(&conn).await;
+
+            let changes = AvailabilityCheckRepository::find_changed_since(
+                &conn,
+                Utc::now() - Duration::days(7),
+            )
+            .await
+            .unwrap();
+
+            assert!(changes.is_empty());
+        }
+    }
+
+    mod fetch_time_percentiles_tests {
+        use super::*;
+        use chrono::Duration;
+
+        async fn record_fetch(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            fetch_duration_ms: Option<i64>,
+            minutes_ago: i64,
+        ) {
+            let check = AvailabilityCheckRepository::create_with_timestamp(
+                conn,
+                product_id,
+                None,
+                Utc::now() - Duration::minutes(minutes_ago),
+            )
+            .await;
+            let mut active_model = check.into_active_model();
+            active_model.fetch_duration_ms = Set(fetch_duration_ms);
+            active_model.update(conn).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_create_persists_fetch_metrics() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let check = AvailabilityCheckRepository::create(
+                &conn,
+                Uuid::new_v4(),
+                product_id,
+                CreateCheckParams {
+                    status: AvailabilityStatus::InStock,
+                    fetch_duration_ms: Some(420),
+                    content_bytes: Some(183_000),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+            let stored = AvailabilityCheckRepository::find_latest_for_product(&conn, product_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(stored.id, check.id);
+            assert_eq!(stored.fetch_duration_ms, Some(420));
+            assert_eq!(stored.content_bytes, Some(183_000));
+        }
+
+        #[tokio::test]
+        async fn test_percentiles_per_host() {
+            let conn = setup_availability_db().await;
+
+            // 1..=20 seconds, recorded out of order
+            let slow = create_test_product(&conn, "https://Slow.com/item").await;
+            for (i, seconds) in [
+                7, 3, 20, 1, 15, 9, 12, 2, 18, 5, 11, 4, 16, 8, 19, 6, 14, 10, 17, 13,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                record_fetch(&conn, slow, Some(seconds * 1000), i as i64).await;
+            }
+            // Checks without a fetch time (failures, Shopify fast path) are ignored
+            record_fetch(&conn, slow, None, 100).await;
+
+            let fast = create_test_product(&conn, "https://fast.com/item").await;
+            record_fetch(&conn, fast, Some(120), 1).await;
+
+            let timings = AvailabilityCheckRepository::fetch_time_percentiles_by_host(&conn, 50)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                timings,
+                vec![
+                    HostFetchTiming {
+                        host: "fast.com".to_string(),
+                        sample_count: 1,
+                        p50_fetch_ms: 120,
+                        p95_fetch_ms: 120,
+                    },
+                    HostFetchTiming {
+                        host: "slow.com".to_string(),
+                        sample_count: 20,
+                        p50_fetch_ms: 10_000,
+                        p95_fetch_ms: 19_000,
+                    },
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_percentiles_only_use_recent_checks() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://shop.com/item").await;
+
+            record_fetch(&conn, product_id, Some(90_000), 60).await;
+            record_fetch(&conn, product_id, Some(300), 2).await;
+            record_fetch(&conn, product_id, Some(500), 1).await;
+
+            let timings = AvailabilityCheckRepository::fetch_time_percentiles_by_host(&conn, 2)
+                .await
+                .unwrap();
+
+            assert_eq!(timings.len(), 1);
+            assert_eq!(timings[0].sample_count, 2);
+            assert_eq!(timings[0].p50_fetch_ms, 300);
+            assert_eq!(timings[0].p95_fetch_ms, 500);
+        }
+    }
+
+    mod repair_orphaned_checks_tests {
+        use super::cheapest_price_tests::create_test_product_retailer;
+        use super::*;
+        use sea_orm::ConnectionTrait;
+
+        /// Delete rows behind the repository's back, the way a crash or an
+        /// older build without cascades could leave them
+        async fn delete_without_cascade(conn: &DatabaseConnection, sql: &str, id: Uuid) {
+            conn.execute_unprepared("PRAGMA foreign_keys = OFF")
+                .await
+                .unwrap();
+            conn.execute(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                sql,
+                [id.into()],
+            ))
+            .await
+            .unwrap();
+        }
+
+        async fn find_check(conn: &DatabaseConnection, id: Uuid) -> Option<AvailabilityCheckModel> {
+            AvailabilityCheck::find_by_id(id).one(conn).await.unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_no_orphans_reports_nothing() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let pr_id = create_test_product_retailer(&conn, product_id, "a.com").await;
+            AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                product_id,
+                pr_id,
+                Some(1000),
+                Some("USD"),
+                Utc::now(),
+            )
+            .await;
+
+            let report = AvailabilityCheckRepository::repair_orphaned_checks(&conn)
+                .await
+                .unwrap();
+
+            assert_eq!(report, RepairReport::default());
+        }
+
+        #[tokio::test]
+        async fn test_deletes_checks_whose_product_and_link_are_gone() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let pr_id = create_test_product_retailer(&conn, product_id, "a.com").await;
+            let linked = AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                product_id,
+                pr_id,
+                Some(1000),
+                Some("USD"),
+                Utc::now(),
+            )
+            .await;
+            let unlinked = AvailabilityCheckRepository::create_with_timestamp(
+                &conn,
+                product_id,
+                Some(1000),
+                Utc::now(),
+            )
+            .await;
+            delete_without_cascade(&conn, "DELETE FROM product_retailers WHERE id = ?", pr_id)
+                .await;
+            delete_without_cascade(&conn, "DELETE FROM products WHERE id = ?", product_id).await;
+
+            let report = AvailabilityCheckRepository::repair_orphaned_checks(&conn)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                report,
+                RepairReport {
+                    reassigned: 0,
+                    deleted: 2
+                }
+            );
+            assert!(find_check(&conn, linked.id).await.is_none());
+            assert!(find_check(&conn, unlinked.id).await.is_none());
+        }
+
+        #[tokio::test]
+        async fn test_reassigns_to_product_of_surviving_link() {
+            let conn = setup_availability_db().await;
+            let old_product = create_test_product(&conn, "https://a.com/old").await;
+            let new_product = create_test_product(&conn, "https://a.com/new").await;
+            let pr_id = create_test_product_retailer(&conn, new_product, "a.com").await;
+            let check = AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                old_product,
+                pr_id,
+                Some(1000),
+                Some("USD"),
+                Utc::now(),
+            )
+            .await;
+            delete_without_cascade(&conn, "DELETE FROM products WHERE id = ?", old_product).await;
+
+            let report = AvailabilityCheckRepository::repair_orphaned_checks(&conn)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                report,
+                RepairReport {
+                    reassigned: 1,
+                    deleted: 0
+                }
+            );
+            let repaired = find_check(&conn, check.id).await.unwrap();
+            assert_eq!(repaired.product_id, new_product);
+            assert_eq!(repaired.product_retailer_id, Some(pr_id));
+        }
+
+        #[tokio::test]
+        async fn test_moves_check_to_products_only_remaining_link() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let gone = create_test_product_retailer(&conn, product_id, "a.com").await;
+            let remaining = create_test_product_retailer(&conn, product_id, "b.com").await;
+            let check = AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                product_id,
+                gone,
+                Some(1000),
+                Some("USD"),
+                Utc::now(),
+            )
+            .await;
+            delete_without_cascade(&conn, "DELETE FROM product_retailers WHERE id = ?", gone).await;
+
+            let report = AvailabilityCheckRepository::repair_orphaned_checks(&conn)
+                .await
+                .unwrap();
+
+            assert_eq!(report.reassigned, 1);
+            let repaired = find_check(&conn, check.id).await.unwrap();
+            assert_eq!(repaired.product_id, product_id);
+            assert_eq!(repaired.product_retailer_id, Some(remaining));
+        }
+
+        #[tokio::test]
+        async fn test_detaches_check_when_product_has_several_links() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let gone = create_test_product_retailer(&conn, product_id, "a.com").await;
+            create_test_product_retailer(&conn, product_id, "b.com").await;
+            create_test_product_retailer(&conn, product_id, "c.com").await;
+            let check = AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                product_id,
+                gone,
+                Some(1000),
+                Some("USD"),
+                Utc::now(),
+            )
+            .await;
+            delete_without_cascade(&conn, "DELETE FROM product_retailers WHERE id = ?", gone).await;
+
+            let report = AvailabilityCheckRepository::repair_orphaned_checks(&conn)
+                .await
+                .unwrap();
+
+            assert_eq!(report.reassigned, 1);
+            let repaired = find_check(&conn, check.id).await.unwrap();
+            assert_eq!(repaired.product_id, product_id);
+            assert!(repaired.product_retailer_id.is_none());
+        }
+
+        #[tokio::test]
+        async fn test_leaves_healthy_checks_untouched() {
+            let conn = setup_availability_db().await;
+            let kept_product = create_test_product(&conn, "https://a.com/kept").await;
+            let gone_product = create_test_product(&conn, "https://a.com/gone").await;
+            let kept = AvailabilityCheckRepository::create_with_timestamp(
+                &conn,
+                kept_product,
+                Some(1000),
+                Utc::now(),
+            )
+            .await;
+            AvailabilityCheckRepository::create_with_timestamp(
+                &conn,
+                gone_product,
+                Some(1000),
+                Utc::now(),
+            )
+            .await;
+            delete_without_cascade(&conn, "DELETE FROM products WHERE id = ?", gone_product).await;
+
+            let report = AvailabilityCheckRepository::repair_orphaned_checks(&conn)
+                .await
+                .unwrap();
+
+            assert_eq!(report.deleted, 1);
+            assert_eq!(find_check(&conn, kept.id).await.unwrap(), kept);
+        }
+    }
+
+    mod price_trend_tests {
+        use super::*;
+        use chrono::Duration;
+
+        /// Seed one check per day, oldest first, ending today
+        async fn seed_daily_prices(conn: &DatabaseConnection, product_id: Uuid, prices: &[i64]) {
+            let now = Utc::now();
+            for (i, price) in prices.iter().enumerate() {
+                let days_ago = (prices.len() - 1 - i) as i64;
+                AvailabilityCheckRepository::create_with_timestamp(
+                    conn,
+                    product_id,
+                    Some(*price),
+                    now - Duration::days(days_ago),
+                )
+                .await;
+            }
+        }
+
+        #[tokio::test]
+        async fn test_rising_series() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            seed_daily_prices(
+                &conn,
+                product_id,
+                &[1000, 1050, 1100, 1150, 1200, 1250, 1300],
+            )
+            .await;
+
+            let trend = AvailabilityCheckRepository::get_price_trend(&conn, product_id, 7)
+                .await
+                .unwrap();
+
+            assert_eq!(trend.direction, TrendDirection::Rising);
+            assert!((trend.change_percent - 30.0).abs() < 0.01);
+            assert_eq!(trend.sample_count, 7);
+            assert_eq!(trend.days, 7);
+        }
+
+        #[tokio::test]
+        async fn test_falling_series() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            seed_daily_prices(&conn, product_id, &[2000, 1900, 1800, 1700, 1600]).await;
+
+            let trend = AvailabilityCheckRepository::get_price_trend(&conn, product_id, 7)
+                .await
+                .unwrap();
+
+            assert_eq!(trend.direction, TrendDirection::Falling);
+            assert!((trend.change_percent + 20.0).abs() < 0.01);
+        }
+
+        #[tokio::test]
+        async fn test_small_wobble_is_stable() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            seed_daily_prices(&conn, product_id, &[1000, 1005, 995, 1000, 1004]).await;
+
+            let trend = AvailabilityCheckRepository::get_price_trend(&conn, product_id, 7)
+                .await
+                .unwrap();
+
+            assert_eq!(trend.direction, TrendDirection::Stable);
+            assert!(trend.change_percent.abs() < PriceTrend::STABLE_THRESHOLD_PERCENT);
+        }
+
+        #[tokio::test]
+        async fn test_ignores_checks_before_window() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            // Ten days of falling prices, then flat for the last week
+            seed_daily_prices(
+                &conn,
+                product_id,
+                &[
+                    5000, 4500, 4000, 3500, 1000, 1000, 1000, 1000, 1000, 1000, 1000,
+                ],
+            )
+            .await;
+
+            let trend = AvailabilityCheckRepository::get_price_trend(&conn, product_id, 6)
+                .await
+                .unwrap();
+
+            assert_eq!(trend.direction, TrendDirection::Stable);
+            assert_eq!(trend.sample_count, 6);
+        }
+
+        #[tokio::test]
+        async fn test_single_check_is_stable() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            seed_daily_prices(&conn, product_id, &[1000]).await;
+
+            let trend = AvailabilityCheckRepository::get_price_trend(&conn, product_id, 30)
+                .await
+                .unwrap();
+
+            assert_eq!(trend.direction, TrendDirection::Stable);
+            assert_eq!(trend.change_percent, 0.0);
+            assert_eq!(trend.sample_count, 1);
+        }
+
+        #[tokio::test]
+        async fn test_rejects_non_positive_window() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let result = AvailabilityCheckRepository::get_price_trend(&conn, product_id, 0).await;
+
+            assert!(matches!(result, Err(AppError::Validation(_))));
+        }
+    }
 }
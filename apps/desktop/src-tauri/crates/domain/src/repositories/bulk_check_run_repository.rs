@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use product_stalker_core::AppError;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, QueryOrder, QuerySelect, Set};
+use uuid::Uuid;
+
+use crate::entities::prelude::*;
+
+/// Parameters for recording a finished bulk check run
+pub struct CreateBulkCheckRunParams {
+    pub ran_at: DateTime<Utc>,
+    pub total: i32,
+    pub successful: i32,
+    pub failed: i32,
+    pub back_in_stock_count: i32,
+    pub price_drop_count: i32,
+}
+
+/// Repository for bulk check run history
+pub struct BulkCheckRunRepository;
+
+impl BulkCheckRunRepository {
+    /// Record a finished bulk check run
+    pub async fn create(
+        conn: &DatabaseConnection,
+        params: CreateBulkCheckRunParams,
+    ) -> Result<BulkCheckRunModel, AppError> {
+        let active_model = BulkCheckRunActiveModel {
+            id: Set(Uuid::new_v4()),
+            ran_at: Set(params.ran_at),
+            total: Set(params.total),
+            successful: Set(params.successful),
+            failed: Set(params.failed),
+            back_in_stock_count: Set(params.back_in_stock_count),
+            price_drop_count: Set(params.price_drop_count),
+        };
+
+        let run = active_model.insert(conn).await?;
+        Ok(run)
+    }
+
+    /// Find the most recent runs, newest first
+    pub async fn find_recent(
+        conn: &DatabaseConnection,
+        limit: u64,
+    ) -> Result<Vec<BulkCheckRunModel>, AppError> {
+        let runs = BulkCheckRun::find()
+            .order_by_desc(BulkCheckRunColumn::RanAt)
+            .limit(limit)
+            .all(conn)
+            .await?;
+        Ok(runs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::test_utils::setup_bulk_check_runs_db;
+
+    fn params(ran_at: DateTime<Utc>, back_in_stock_count: i32) -> CreateBulkCheckRunParams {
+        CreateBulkCheckRunParams {
+            ran_at,
+            total: 5,
+            successful: 4,
+            failed: 1,
+            back_in_stock_count,
+            price_drop_count: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_stores_counts() {
+        let conn = setup_bulk_check_runs_db().await;
+        let ran_at = Utc::now();
+
+        let run = BulkCheckRunRepository::create(&conn, params(ran_at, 3))
+            .await
+            .unwrap();
+
+        assert_eq!(run.ran_at, ran_at);
+        assert_eq!(run.total, 5);
+        assert_eq!(run.failed, 1);
+        assert_eq!(run.back_in_stock_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_find_recent_newest_first_with_limit() {
+        let conn = setup_bulk_check_runs_db().await;
+        let now = Utc::now();
+        for (hours_ago, restocked) in [(3, 1), (1, 2), (2, 3)] {
+            BulkCheckRunRepository::create(
+                &conn,
+                params(now - Duration::hours(hours_ago), restocked),
+            )
+            .await
+            .unwrap();
+        }
+
+        let runs = BulkCheckRunRepository::find_recent(&conn, 2).await.unwrap();
+
+        let restocked: Vec<i32> = runs.iter().map(|r| r.back_in_stock_count).collect();
+        assert_eq!(restocked, vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_find_recent_empty() {
+        let conn = setup_bulk_check_runs_db().await;
+
+        let runs = BulkCheckRunRepository::find_recent(&conn, 10)
+            .await
+            .unwrap();
+
+        assert!(runs.is_empty());
+    }
+}
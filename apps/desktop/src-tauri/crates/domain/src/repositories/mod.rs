@@ -1,13 +1,21 @@
 //! Domain repositories
 
 mod availability_check_repository;
+mod bulk_check_run_repository;
 mod product_repository;
 mod product_retailer_repository;
 mod retailer_repository;
+mod tag_repository;
 
 pub use availability_check_repository::{
-    AvailabilityCheckRepository, CheapestPriceResult, CreateCheckParams, CurrencyAverageResult,
+    AvailabilityCheckRepository, Bucket, CheapestPriceResult, CreateCheckParams,
+    CurrencyAverageResult, HostFetchTiming, HostOutcomeCount, LatestTargetCheck, PriceDrop,
+    PriceExtremes, PricePoint, PriceTrend, RepairReport, StatusTransition, TrendDirection,
+};
+pub use bulk_check_run_repository::{BulkCheckRunRepository, CreateBulkCheckRunParams};
+pub use product_repository::{
+    CloneProductLink, CreateProductRepoParams, ProductRepository, ProductUpdateInput,
 };
-pub use product_repository::{CreateProductRepoParams, ProductRepository, ProductUpdateInput};
 pub use product_retailer_repository::{CreateProductRetailerParams, ProductRetailerRepository};
-pub use retailer_repository::RetailerRepository;
+pub use retailer_repository::{RetailerCheckCount, RetailerRepository};
+pub use tag_repository::TagRepository;
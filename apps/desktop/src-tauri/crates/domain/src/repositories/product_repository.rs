@@ -1,10 +1,13 @@
+use chrono::{DateTime, Utc};
 use product_stalker_core::AppError;
+use sea_orm::sea_query::LikeExpr;
 use sea_orm::{
-    ActiveModelTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryOrder, Set,
-    TransactionTrait,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbBackend,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set, Statement, TransactionTrait,
 };
 use uuid::Uuid;
 
+use crate::entities::availability_check::AvailabilityStatus;
 use crate::entities::prelude::*;
 
 /// Input for updating a product's fields.
@@ -13,7 +16,8 @@ use crate::entities::prelude::*;
 /// - `None` = keep existing value unchanged
 /// - `Some(value)` = update to new value
 ///
-/// For nullable fields (`description`, `notes`), uses nested Option:
+/// For nullable fields (`description`, `notes`, `check_interval_minutes`, target price), uses
+/// nested Option:
 /// - `None` = keep existing value unchanged
 /// - `Some(None)` = clear the field (set to NULL)
 /// - `Some(Some(value))` = set to the new value
@@ -24,6 +28,13 @@ pub struct ProductUpdateInput {
     pub description: Option<Option<String>>,
     pub notes: Option<Option<String>>,
     pub currency: Option<Option<String>>,
+    pub check_interval_minutes: Option<Option<i32>>,
+    pub target_price_minor_units: Option<Option<i64>>,
+    pub target_price_currency: Option<Option<String>>,
+    pub is_paused: Option<bool>,
+    pub is_archived: Option<bool>,
+    pub track_price: Option<bool>,
+    pub currency_locked: Option<bool>,
 }
 
 /// Parameters for creating a new product at the repository level
@@ -32,6 +43,15 @@ pub struct CreateProductRepoParams {
     pub url: Option<String>,
     pub description: Option<String>,
     pub notes: Option<String>,
+    pub check_interval_minutes: Option<i32>,
+    pub target_price_minor_units: Option<i64>,
+    pub target_price_currency: Option<String>,
+}
+
+/// Retailer link given to a cloned product in place of the source's links
+pub struct CloneProductLink {
+    pub retailer_id: Uuid,
+    pub url: String,
 }
 
 /// Repository for product data access
@@ -41,34 +61,120 @@ pub struct CreateProductRepoParams {
 pub struct ProductRepository;
 
 impl ProductRepository {
-    /// Find all products ordered by sort_order
+    /// Find all unarchived products ordered by sort_order, then name
     pub async fn find_all(conn: &DatabaseConnection) -> Result<Vec<ProductModel>, AppError> {
         let products = Product::find()
+            .filter(ProductColumn::IsArchived.eq(false))
             .order_by_asc(ProductColumn::SortOrder)
+            .order_by_asc(ProductColumn::Name)
             .all(conn)
             .await?;
         Ok(products)
     }
 
-    /// Find a product by ID
-    pub async fn find_by_id(
+    /// Find all products, archived included, ordered by sort_order, then name
+    pub async fn find_all_including_archived(
+        conn: &DatabaseConnection,
+    ) -> Result<Vec<ProductModel>, AppError> {
+        let products = Product::find()
+            .order_by_asc(ProductColumn::SortOrder)
+            .order_by_asc(ProductColumn::Name)
+            .all(conn)
+            .await?;
+        Ok(products)
+    }
+
+    /// Find unarchived products whose name, description or notes contain `query`, ordered
+    /// by sort_order, then name.
+    ///
+    /// Matching is a case-insensitive substring match (SQLite `LIKE`). `%` and `_`
+    /// in the query match literally.
+    pub async fn search(
         conn: &DatabaseConnection,
+        query: &str,
+    ) -> Result<Vec<ProductModel>, AppError> {
+        let escaped = query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let pattern = || LikeExpr::new(format!("%{}%", escaped)).escape('\\');
+
+        let products = Product::find()
+            .filter(
+                Condition::any()
+                    .add(ProductColumn::Name.like(pattern()))
+                    .add(ProductColumn::Description.like(pattern()))
+                    .add(ProductColumn::Notes.like(pattern())),
+            )
+            .filter(ProductColumn::IsArchived.eq(false))
+            .order_by_asc(ProductColumn::SortOrder)
+            .order_by_asc(ProductColumn::Name)
+            .all(conn)
+            .await?;
+        Ok(products)
+    }
+
+    /// Find a product by ID
+    pub async fn find_by_id<C: ConnectionTrait>(
+        conn: &C,
         id: Uuid,
     ) -> Result<Option<ProductModel>, AppError> {
         let product = Product::find_by_id(id).one(conn).await?;
         Ok(product)
     }
 
-    /// Create a new product (appends to end of sort order)
-    pub async fn create(
+    /// Find products whose most recent availability check has the given status,
+    /// ordered by sort_order, then name.
+    ///
+    /// The latest check is taken across all retailers of a product. Products that
+    /// have never been checked are treated as `Unknown`.
+    pub async fn find_by_latest_status(
         conn: &DatabaseConnection,
+        status: AvailabilityStatus,
+    ) -> Result<Vec<ProductModel>, AppError> {
+        let products = Product::find()
+            .from_raw_sql(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                r#"
+                    SELECT p.*
+                    FROM products p
+                    LEFT JOIN availability_checks ac ON ac.id = (
+                        SELECT latest.id
+                        FROM availability_checks latest
+                        WHERE latest.product_id = p.id
+                        ORDER BY latest.checked_at DESC
+                        LIMIT 1
+                    )
+                    WHERE COALESCE(ac.status, ?) = ?
+                    ORDER BY p.sort_order ASC, p.name ASC
+                "#,
+                [
+                    AvailabilityStatus::Unknown.as_str().into(),
+                    status.as_str().into(),
+                ],
+            ))
+            .all(conn)
+            .await?;
+        Ok(products)
+    }
+
+    /// Create a new product (appends to end of sort order)
+    pub async fn create<C: ConnectionTrait>(
+        conn: &C,
         id: Uuid,
         params: CreateProductRepoParams,
     ) -> Result<ProductModel, AppError> {
         let now = chrono::Utc::now();
 
-        // Append to end: sort_order = current product count
-        let count = Product::find().count(conn).await? as i32;
+        // Append to end: one past the highest sort_order, so gaps left by deleted
+        // products never produce a duplicate
+        let max_sort_order: Option<i32> = Product::find()
+            .select_only()
+            .column_as(ProductColumn::SortOrder.max(), "max_sort_order")
+            .into_tuple()
+            .one(conn)
+            .await?
+            .flatten();
 
         let active_model = ProductActiveModel {
             id: Set(id),
@@ -77,7 +183,17 @@ impl ProductRepository {
             description: Set(params.description),
             notes: Set(params.notes),
             currency: Set(None),
-            sort_order: Set(count),
+            sort_order: Set(max_sort_order.map_or(0, |max| max + 1)),
+            check_interval_minutes: Set(params.check_interval_minutes),
+            target_price_minor_units: Set(params.target_price_minor_units),
+            target_price_currency: Set(params.target_price_currency),
+            is_paused: Set(false),
+            is_archived: Set(false),
+            track_price: Set(true),
+            currency_locked: Set(false),
+            last_notified_check_id: Set(None),
+            last_notified_at: Set(None),
+            notifications_snoozed_until: Set(None),
             created_at: Set(now),
             updated_at: Set(now),
         };
@@ -86,12 +202,13 @@ impl ProductRepository {
         Ok(product)
     }
 
-    /// Find all product IDs that have no associated product_retailers
+    /// Find all unpaused, unarchived products that have no associated product_retailers
+    /// (for bulk checks)
     pub async fn find_all_without_retailers(
         conn: &DatabaseConnection,
     ) -> Result<Vec<ProductModel>, AppError> {
         use crate::entities::prelude::ProductRetailerColumn;
-        use sea_orm::{ColumnTrait, JoinType, QueryFilter, QuerySelect, RelationTrait};
+        use sea_orm::{JoinType, QuerySelect, RelationTrait};
 
         let products = Product::find()
             .join(
@@ -99,6 +216,8 @@ impl ProductRepository {
                 crate::entities::product::Relation::ProductRetailers.def(),
             )
             .filter(ProductRetailerColumn::Id.is_null())
+            .filter(ProductColumn::IsPaused.eq(false))
+            .filter(ProductColumn::IsArchived.eq(false))
             .all(conn)
             .await?;
         Ok(products)
@@ -110,8 +229,8 @@ impl ProductRepository {
     ///
     /// * `model` - The existing product model to update
     /// * `input` - The fields to update (see [`ProductUpdateInput`] for details)
-    pub async fn update(
-        conn: &DatabaseConnection,
+    pub async fn update<C: ConnectionTrait>(
+        conn: &C,
         model: ProductModel,
         input: ProductUpdateInput,
     ) -> Result<ProductModel, AppError> {
@@ -132,6 +251,27 @@ impl ProductRepository {
         if let Some(currency) = input.currency {
             active_model.currency = Set(currency);
         }
+        if let Some(check_interval_minutes) = input.check_interval_minutes {
+            active_model.check_interval_minutes = Set(check_interval_minutes);
+        }
+        if let Some(target_price_minor_units) = input.target_price_minor_units {
+            active_model.target_price_minor_units = Set(target_price_minor_units);
+        }
+        if let Some(target_price_currency) = input.target_price_currency {
+            active_model.target_price_currency = Set(target_price_currency);
+        }
+        if let Some(is_paused) = input.is_paused {
+            active_model.is_paused = Set(is_paused);
+        }
+        if let Some(is_archived) = input.is_archived {
+            active_model.is_archived = Set(is_archived);
+        }
+        if let Some(track_price) = input.track_price {
+            active_model.track_price = Set(track_price);
+        }
+        if let Some(currency_locked) = input.currency_locked {
+            active_model.currency_locked = Set(currency_locked);
+        }
         active_model.updated_at = Set(chrono::Utc::now());
 
         let updated = active_model.update(conn).await?;
@@ -158,17 +298,259 @@ impl ProductRepository {
         Ok(())
     }
 
+    /// Record that a back-in-stock notification was sent for `check_id`.
+    ///
+    /// Bookkeeping only, so `updated_at` is left untouched.
+    pub async fn record_restock_notification(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        check_id: Uuid,
+        notified_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let product = Product::find_by_id(product_id).one(conn).await?;
+        let product = product
+            .ok_or_else(|| AppError::NotFound(format!("Product not found: {}", product_id)))?;
+        let mut active_model: ProductActiveModel = product.into();
+        active_model.last_notified_check_id = Set(Some(check_id));
+        active_model.last_notified_at = Set(Some(notified_at));
+        active_model.update(conn).await?;
+        Ok(())
+    }
+
+    /// Set or clear the time until which the product's notifications are snoozed.
+    ///
+    /// Like restock bookkeeping, this leaves `updated_at` untouched.
+    pub async fn set_notifications_snoozed_until(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<ProductModel, AppError> {
+        let product = Product::find_by_id(product_id).one(conn).await?;
+        let product = product
+            .ok_or_else(|| AppError::NotFound(format!("Product not found: {}", product_id)))?;
+        let mut active_model: ProductActiveModel = product.into();
+        active_model.notifications_snoozed_until = Set(until);
+        let updated = active_model.update(conn).await?;
+        Ok(updated)
+    }
+
+    /// Merge `merge_id` into `keep_id` in a transaction, then delete `merge_id`.
+    ///
+    /// The merged product's retailer links, checks and tags move to the kept
+    /// product. A link whose URL the kept product already tracks is dropped in
+    /// favour of the kept link, with its checks moved onto that link.
+    pub async fn merge_into(
+        conn: &DatabaseConnection,
+        keep_id: Uuid,
+        merge_id: Uuid,
+    ) -> Result<(), AppError> {
+        use sea_orm::sea_query::Expr;
+
+        let txn = conn.begin().await?;
+
+        for id in [keep_id, merge_id] {
+            if Product::find_by_id(id).one(&txn).await?.is_none() {
+                return Err(AppError::NotFound(format!("Product not found: {}", id)));
+            }
+        }
+
+        let keep_links = ProductRetailer::find()
+            .filter(ProductRetailerColumn::ProductId.eq(keep_id))
+            .all(&txn)
+            .await?;
+        let merge_links = ProductRetailer::find()
+            .filter(ProductRetailerColumn::ProductId.eq(merge_id))
+            .order_by_asc(ProductRetailerColumn::SortOrder)
+            .all(&txn)
+            .await?;
+        let mut next_sort_order = keep_links
+            .iter()
+            .map(|link| link.sort_order + 1)
+            .max()
+            .unwrap_or(0);
+
+        for link in merge_links {
+            match keep_links.iter().find(|kept| kept.url == link.url) {
+                Some(kept) => {
+                    AvailabilityCheck::update_many()
+                        .col_expr(
+                            AvailabilityCheckColumn::ProductRetailerId,
+                            Expr::value(kept.id),
+                        )
+                        .filter(AvailabilityCheckColumn::ProductRetailerId.eq(link.id))
+                        .exec(&txn)
+                        .await?;
+                    ProductRetailer::delete_by_id(link.id).exec(&txn).await?;
+                }
+                None => {
+                    let mut active_model: ProductRetailerActiveModel = link.into();
+                    active_model.product_id = Set(keep_id);
+                    active_model.sort_order = Set(next_sort_order);
+                    active_model.update(&txn).await?;
+                    next_sort_order += 1;
+                }
+            }
+        }
+
+        AvailabilityCheck::update_many()
+            .col_expr(AvailabilityCheckColumn::ProductId, Expr::value(keep_id))
+            .filter(AvailabilityCheckColumn::ProductId.eq(merge_id))
+            .exec(&txn)
+            .await?;
+
+        let merge_tags = ProductTag::find()
+            .filter(ProductTagColumn::ProductId.eq(merge_id))
+            .all(&txn)
+            .await?;
+        for tag in merge_tags {
+            if ProductTag::find_by_id((keep_id, tag.tag_id))
+                .one(&txn)
+                .await?
+                .is_none()
+            {
+                ProductTagActiveModel {
+                    product_id: Set(keep_id),
+                    tag_id: Set(tag.tag_id),
+                }
+                .insert(&txn)
+                .await?;
+            }
+        }
+
+        Product::delete_by_id(merge_id).exec(&txn).await?;
+
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Copy `source_id` to a new product `new_id` named `name`, in a transaction.
+    ///
+    /// Settings and tags are copied. Retailer links are copied too, unless
+    /// `replacement_link` is given, in which case it becomes the only link and
+    /// the detected currency is left to the first check. Check history,
+    /// notification state and archiving are not copied, and the clone goes to
+    /// the end of the sort order.
+    pub async fn clone_product(
+        conn: &DatabaseConnection,
+        source_id: Uuid,
+        new_id: Uuid,
+        name: String,
+        replacement_link: Option<CloneProductLink>,
+    ) -> Result<ProductModel, AppError> {
+        let txn = conn.begin().await?;
+
+        let source = Product::find_by_id(source_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Product not found: {}", source_id)))?;
+        let max_sort_order: Option<i32> = Product::find()
+            .select_only()
+            .column_as(ProductColumn::SortOrder.max(), "max_sort_order")
+            .into_tuple()
+            .one(&txn)
+            .await?
+            .flatten();
+
+        let now = chrono::Utc::now();
+        let keeps_links = replacement_link.is_none();
+        let product = ProductActiveModel {
+            id: Set(new_id),
+            name: Set(name),
+            url: Set(source.url.filter(|_| keeps_links)),
+            description: Set(source.description),
+            notes: Set(source.notes),
+            currency: Set(source.currency.filter(|_| keeps_links)),
+            sort_order: Set(max_sort_order.map_or(0, |max| max + 1)),
+            check_interval_minutes: Set(source.check_interval_minutes),
+            target_price_minor_units: Set(source.target_price_minor_units),
+            target_price_currency: Set(source.target_price_currency),
+            is_paused: Set(source.is_paused),
+            is_archived: Set(false),
+            track_price: Set(source.track_price),
+            currency_locked: Set(source.currency_locked && keeps_links),
+            last_notified_check_id: Set(None),
+            last_notified_at: Set(None),
+            notifications_snoozed_until: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&txn)
+        .await?;
+
+        match replacement_link {
+            Some(link) => {
+                ProductRetailerActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    product_id: Set(new_id),
+                    retailer_id: Set(link.retailer_id),
+                    url: Set(link.url),
+                    label: Set(None),
+                    sort_order: Set(0),
+                    currency: Set(None),
+                    variant_selector: Set(None),
+                    created_at: Set(now),
+                }
+                .insert(&txn)
+                .await?;
+            }
+            None => {
+                let links = ProductRetailer::find()
+                    .filter(ProductRetailerColumn::ProductId.eq(source_id))
+                    .all(&txn)
+                    .await?;
+                for link in links {
+                    ProductRetailerActiveModel {
+                        id: Set(Uuid::new_v4()),
+                        product_id: Set(new_id),
+                        retailer_id: Set(link.retailer_id),
+                        url: Set(link.url),
+                        label: Set(link.label),
+                        sort_order: Set(link.sort_order),
+                        currency: Set(link.currency),
+                        variant_selector: Set(link.variant_selector),
+                        created_at: Set(now),
+                    }
+                    .insert(&txn)
+                    .await?;
+                }
+            }
+        }
+
+        let tags = ProductTag::find()
+            .filter(ProductTagColumn::ProductId.eq(source_id))
+            .all(&txn)
+            .await?;
+        for tag in tags {
+            ProductTagActiveModel {
+                product_id: Set(new_id),
+                tag_id: Set(tag.tag_id),
+            }
+            .insert(&txn)
+            .await?;
+        }
+
+        txn.commit().await?;
+        Ok(product)
+    }
+
     /// Delete a product by ID
     pub async fn delete_by_id(conn: &DatabaseConnection, id: Uuid) -> Result<u64, AppError> {
         let result = Product::delete_by_id(id).exec(conn).await?;
         Ok(result.rows_affected)
     }
+
+    /// Delete all products (checks and retailer links cascade)
+    pub async fn delete_all<C: ConnectionTrait>(conn: &C) -> Result<u64, AppError> {
+        let result = Product::delete_many().exec(conn).await?;
+        Ok(result.rows_affected)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::setup_products_db;
+    use crate::test_utils::{setup_availability_db, setup_products_db};
+    use chrono::{DateTime, Duration, Utc};
 
     fn params(name: &str, url: &str) -> CreateProductRepoParams {
         CreateProductRepoParams {
@@ -176,6 +558,9 @@ mod tests {
             url: Some(url.to_string()),
             description: None,
             notes: None,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
         }
     }
 
@@ -195,6 +580,82 @@ mod tests {
         assert_eq!(found.unwrap().id, id);
     }
 
+    async fn create_with_text(
+        conn: &DatabaseConnection,
+        name: &str,
+        description: Option<&str>,
+        notes: Option<&str>,
+    ) -> Uuid {
+        let id = Uuid::new_v4();
+        ProductRepository::create(
+            conn,
+            id,
+            CreateProductRepoParams {
+                description: description.map(str::to_string),
+                notes: notes.map(str::to_string),
+                ..params(name, "https://example.com")
+            },
+        )
+        .await
+        .unwrap();
+        id
+    }
+
+    #[tokio::test]
+    async fn test_search_partial_match_across_fields() {
+        let conn = setup_products_db().await;
+        let by_name = create_with_text(&conn, "Graphics Card", None, None).await;
+        let by_description =
+            create_with_text(&conn, "Widget", Some("A graphics tablet"), None).await;
+        let by_notes = create_with_text(&conn, "Gadget", None, Some("for graphic design")).await;
+        create_with_text(&conn, "Kettle", Some("Kitchen"), Some("Birthday gift")).await;
+
+        let results = ProductRepository::search(&conn, "graphic").await.unwrap();
+        let ids: Vec<Uuid> = results.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![by_name, by_description, by_notes]);
+    }
+
+    #[tokio::test]
+    async fn test_search_is_case_insensitive() {
+        let conn = setup_products_db().await;
+        let id = create_with_text(&conn, "Coffee Grinder", None, Some("Burr GRINDER")).await;
+
+        for query in ["coffee", "COFFEE", "cOfFeE", "burr grinder"] {
+            let results = ProductRepository::search(&conn, query).await.unwrap();
+            assert_eq!(results.len(), 1, "query: {}", query);
+            assert_eq!(results[0].id, id);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_handles_null_description_and_notes() {
+        let conn = setup_products_db().await;
+        create_with_text(&conn, "Plain Product", None, None).await;
+
+        let results = ProductRepository::search(&conn, "plain").await.unwrap();
+        assert_eq!(results.len(), 1);
+
+        let results = ProductRepository::search(&conn, "missing").await.unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_treats_wildcards_literally() {
+        let conn = setup_products_db().await;
+        let discounted = create_with_text(&conn, "Headphones", Some("50% off"), None).await;
+        create_with_text(&conn, "Speaker", Some("500 watts"), None).await;
+        create_with_text(&conn, "my_product", None, None).await;
+        create_with_text(&conn, "myXproduct", None, None).await;
+
+        let results = ProductRepository::search(&conn, "50%").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, discounted);
+
+        let results = ProductRepository::search(&conn, "y_p").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "my_product");
+    }
+
     #[tokio::test]
     async fn test_find_all_empty() {
         let conn = setup_products_db().await;
@@ -263,6 +724,24 @@ mod tests {
         assert_eq!(rows, 0);
     }
 
+    #[tokio::test]
+    async fn test_delete_all_products() {
+        let conn = setup_products_db().await;
+        for i in 1..=3 {
+            ProductRepository::create(
+                &conn,
+                Uuid::new_v4(),
+                params(&format!("Product {}", i), "https://test.com"),
+            )
+            .await
+            .unwrap();
+        }
+
+        let rows = ProductRepository::delete_all(&conn).await.unwrap();
+        assert_eq!(rows, 3);
+        assert!(ProductRepository::find_all(&conn).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_find_all_with_multiple_products() {
         let conn = setup_products_db().await;
@@ -295,6 +774,9 @@ mod tests {
                 url: Some("https://full.com".to_string()),
                 description: Some("A description".to_string()),
                 notes: Some("Some notes".to_string()),
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await
@@ -325,6 +807,13 @@ mod tests {
                 description: Some(Some("New description".to_string())),
                 notes: Some(Some("New notes".to_string())),
                 currency: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
+                is_paused: None,
+                is_archived: None,
+                track_price: None,
+                currency_locked: None,
             },
         )
         .await
@@ -349,6 +838,9 @@ mod tests {
                 url: Some("https://product.com".to_string()),
                 description: Some("Has description".to_string()),
                 notes: Some("Has notes".to_string()),
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await
@@ -426,6 +918,65 @@ mod tests {
         assert_eq!(p3.sort_order, 2);
     }
 
+    #[tokio::test]
+    async fn test_create_appends_after_highest_sort_order() {
+        let conn = setup_products_db().await;
+
+        let p1 = ProductRepository::create(&conn, Uuid::new_v4(), params("A", "https://a.com"))
+            .await
+            .unwrap();
+        let p2 = ProductRepository::create(&conn, Uuid::new_v4(), params("B", "https://b.com"))
+            .await
+            .unwrap();
+        ProductRepository::delete_by_id(&conn, p1.id).await.unwrap();
+
+        let p3 = ProductRepository::create(&conn, Uuid::new_v4(), params("C", "https://c.com"))
+            .await
+            .unwrap();
+
+        assert_eq!(p2.sort_order, 1);
+        assert_eq!(p3.sort_order, 2);
+        let names: Vec<String> = ProductRepository::find_all(&conn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["B", "C"]);
+    }
+
+    #[tokio::test]
+    async fn test_find_all_breaks_sort_order_ties_by_name() {
+        let conn = setup_products_db().await;
+
+        let zebra =
+            ProductRepository::create(&conn, Uuid::new_v4(), params("Zebra", "https://z.com"))
+                .await
+                .unwrap();
+        let apple =
+            ProductRepository::create(&conn, Uuid::new_v4(), params("Apple", "https://a.com"))
+                .await
+                .unwrap();
+        let mango =
+            ProductRepository::create(&conn, Uuid::new_v4(), params("Mango", "https://m.com"))
+                .await
+                .unwrap();
+        ProductRepository::update_sort_orders(
+            &conn,
+            vec![(zebra.id, 0), (apple.id, 0), (mango.id, 1)],
+        )
+        .await
+        .unwrap();
+
+        let names: Vec<String> = ProductRepository::find_all(&conn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["Apple", "Zebra", "Mango"]);
+    }
+
     #[tokio::test]
     async fn test_update_sort_orders_invalid_id() {
         let conn = setup_products_db().await;
@@ -433,4 +984,604 @@ mod tests {
         let result = ProductRepository::update_sort_orders(&conn, vec![(Uuid::new_v4(), 0)]).await;
         assert!(matches!(result, Err(AppError::NotFound(_))));
     }
+
+    async fn seed_check(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        status: AvailabilityStatus,
+        checked_at: DateTime<Utc>,
+    ) {
+        AvailabilityCheckActiveModel {
+            id: Set(Uuid::new_v4()),
+            product_id: Set(product_id),
+            product_retailer_id: Set(None),
+            status: Set(status.as_str().to_string()),
+            raw_availability: Set(None),
+            error_message: Set(None),
+            error_kind: Set(None),
+            checked_at: Set(checked_at),
+            price_minor_units: Set(None),
+            price_currency: Set(None),
+            raw_price: Set(None),
+            normalized_price_minor_units: Set(None),
+            normalized_currency: Set(None),
+            compare_at_minor_units: Set(None),
+            price_valid_until: Set(None),
+            fetch_duration_ms: Set(None),
+            content_bytes: Set(None),
+            confidence: Set(None),
+        }
+        .insert(conn)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_find_by_latest_status_uses_most_recent_check() {
+        let conn = setup_availability_db().await;
+        let now = Utc::now();
+
+        // Was out of stock, now in stock
+        let restocked = Uuid::new_v4();
+        ProductRepository::create(&conn, restocked, params("Restocked", "https://a.com"))
+            .await
+            .unwrap();
+        seed_check(
+            &conn,
+            restocked,
+            AvailabilityStatus::OutOfStock,
+            now - Duration::hours(2),
+        )
+        .await;
+        seed_check(&conn, restocked, AvailabilityStatus::InStock, now).await;
+
+        // Was in stock, now sold out
+        let sold_out = Uuid::new_v4();
+        ProductRepository::create(&conn, sold_out, params("Sold Out", "https://b.com"))
+            .await
+            .unwrap();
+        seed_check(
+            &conn,
+            sold_out,
+            AvailabilityStatus::InStock,
+            now - Duration::hours(2),
+        )
+        .await;
+        seed_check(&conn, sold_out, AvailabilityStatus::OutOfStock, now).await;
+
+        // Always in stock
+        let steady = Uuid::new_v4();
+        ProductRepository::create(&conn, steady, params("Steady", "https://c.com"))
+            .await
+            .unwrap();
+        seed_check(
+            &conn,
+            steady,
+            AvailabilityStatus::InStock,
+            now - Duration::hours(1),
+        )
+        .await;
+
+        let in_stock = ProductRepository::find_by_latest_status(&conn, AvailabilityStatus::InStock)
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = in_stock.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![restocked, steady]);
+
+        let out_of_stock =
+            ProductRepository::find_by_latest_status(&conn, AvailabilityStatus::OutOfStock)
+                .await
+                .unwrap();
+        assert_eq!(out_of_stock.len(), 1);
+        assert_eq!(out_of_stock[0].id, sold_out);
+
+        let back_order =
+            ProductRepository::find_by_latest_status(&conn, AvailabilityStatus::BackOrder)
+                .await
+                .unwrap();
+        assert!(back_order.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_by_latest_status_unchecked_products_are_unknown() {
+        let conn = setup_availability_db().await;
+        let now = Utc::now();
+
+        let unchecked = Uuid::new_v4();
+        ProductRepository::create(&conn, unchecked, params("Unchecked", "https://a.com"))
+            .await
+            .unwrap();
+
+        let unknown = Uuid::new_v4();
+        ProductRepository::create(&conn, unknown, params("Unknown", "https://b.com"))
+            .await
+            .unwrap();
+        seed_check(&conn, unknown, AvailabilityStatus::Unknown, now).await;
+
+        let checked = Uuid::new_v4();
+        ProductRepository::create(&conn, checked, params("Checked", "https://c.com"))
+            .await
+            .unwrap();
+        seed_check(&conn, checked, AvailabilityStatus::InStock, now).await;
+
+        let in_stock = ProductRepository::find_by_latest_status(&conn, AvailabilityStatus::InStock)
+            .await
+            .unwrap();
+        assert_eq!(in_stock.len(), 1);
+        assert_eq!(in_stock[0].id, checked);
+
+        let unknowns = ProductRepository::find_by_latest_status(&conn, AvailabilityStatus::Unknown)
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = unknowns.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![unchecked, unknown]);
+    }
+
+    #[tokio::test]
+    async fn test_find_all_without_retailers_excludes_paused() {
+        let conn = setup_availability_db().await;
+
+        let active = Uuid::new_v4();
+        ProductRepository::create(&conn, active, params("Active", "https://a.com"))
+            .await
+            .unwrap();
+
+        let paused =
+            ProductRepository::create(&conn, Uuid::new_v4(), params("Paused", "https://b.com"))
+                .await
+                .unwrap();
+        let paused = ProductRepository::update(
+            &conn,
+            paused,
+            ProductUpdateInput {
+                is_paused: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(paused.is_paused);
+
+        let products = ProductRepository::find_all_without_retailers(&conn)
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = products.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![active]);
+    }
+
+    #[tokio::test]
+    async fn test_archived_products_excluded_from_find_all_and_search() {
+        let conn = setup_availability_db().await;
+
+        let active = Uuid::new_v4();
+        ProductRepository::create(&conn, active, params("Widget A", "https://a.com"))
+            .await
+            .unwrap();
+        let archived =
+            ProductRepository::create(&conn, Uuid::new_v4(), params("Widget B", "https://b.com"))
+                .await
+                .unwrap();
+        let archived_id = archived.id;
+        ProductRepository::update(
+            &conn,
+            archived,
+            ProductUpdateInput {
+                is_archived: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let ids = |products: Vec<ProductModel>| products.iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(ProductRepository::find_all(&conn).await.unwrap()),
+            vec![active]
+        );
+        assert_eq!(
+            ids(ProductRepository::search(&conn, "widget").await.unwrap()),
+            vec![active]
+        );
+        assert_eq!(
+            ids(ProductRepository::find_all_without_retailers(&conn)
+                .await
+                .unwrap()),
+            vec![active]
+        );
+        assert_eq!(
+            ids(ProductRepository::find_all_including_archived(&conn)
+                .await
+                .unwrap()),
+            vec![active, archived_id]
+        );
+    }
+
+    mod merge_tests {
+        use super::*;
+        use crate::repositories::{
+            AvailabilityCheckRepository, CreateProductRetailerParams, ProductRetailerRepository,
+            RetailerRepository, TagRepository,
+        };
+        use crate::test_utils::setup_availability_db_with_tags;
+
+        async fn create_product(conn: &DatabaseConnection, name: &str) -> Uuid {
+            ProductRepository::create(conn, Uuid::new_v4(), params(name, "https://a.com"))
+                .await
+                .unwrap()
+                .id
+        }
+
+        async fn add_link(conn: &DatabaseConnection, product_id: Uuid, url: &str) -> Uuid {
+            let domain = url::Url::parse(url)
+                .unwrap()
+                .host_str()
+                .unwrap()
+                .to_string();
+            let retailer = RetailerRepository::find_or_create_by_domain(conn, &domain)
+                .await
+                .unwrap();
+            ProductRetailerRepository::create(
+                conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: url.to_string(),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap()
+            .id
+        }
+
+        async fn add_check(conn: &DatabaseConnection, product_id: Uuid, link: Uuid) -> Uuid {
+            AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                conn,
+                product_id,
+                link,
+                Some(1000),
+                Some("USD"),
+                Utc::now(),
+            )
+            .await
+            .id
+        }
+
+        #[tokio::test]
+        async fn test_merge_reparents_links_and_checks() {
+            let conn = setup_availability_db_with_tags().await;
+            let keep = create_product(&conn, "Keep").await;
+            let merge = create_product(&conn, "Merge").await;
+
+            let keep_link = add_link(&conn, keep, "https://shop.com/item").await;
+            let merge_link = add_link(&conn, merge, "https://other.com/item").await;
+            let keep_check = add_check(&conn, keep, keep_link).await;
+            let merge_check = add_check(&conn, merge, merge_link).await;
+            let legacy_check =
+                AvailabilityCheckRepository::create_with_timestamp(&conn, merge, None, Utc::now())
+                    .await
+                    .id;
+
+            ProductRepository::merge_into(&conn, keep, merge)
+                .await
+                .unwrap();
+
+            assert!(ProductRepository::find_by_id(&conn, merge)
+                .await
+                .unwrap()
+                .is_none());
+
+            let links = ProductRetailerRepository::find_by_product_id(&conn, keep)
+                .await
+                .unwrap();
+            let link_ids: Vec<Uuid> = links.iter().map(|l| l.id).collect();
+            assert_eq!(link_ids, vec![keep_link, merge_link]);
+            assert_eq!(links[1].sort_order, links[0].sort_order + 1);
+
+            let checks = AvailabilityCheck::find().all(&conn).await.unwrap();
+            assert_eq!(checks.len(), 3);
+            assert!(checks.iter().all(|c| c.product_id == keep));
+            let moved = checks.iter().find(|c| c.id == merge_check).unwrap();
+            assert_eq!(moved.product_retailer_id, Some(merge_link));
+            assert!(checks.iter().any(|c| c.id == keep_check));
+            assert!(checks.iter().any(|c| c.id == legacy_check));
+        }
+
+        #[tokio::test]
+        async fn test_merge_keeps_keep_side_of_duplicate_links() {
+            let conn = setup_availability_db_with_tags().await;
+            let keep = create_product(&conn, "Keep").await;
+            let merge = create_product(&conn, "Merge").await;
+
+            let keep_link = add_link(&conn, keep, "https://shop.com/item").await;
+            let duplicate = add_link(&conn, merge, "https://shop.com/item").await;
+            let check = add_check(&conn, merge, duplicate).await;
+
+            ProductRepository::merge_into(&conn, keep, merge)
+                .await
+                .unwrap();
+
+            let links = ProductRetailerRepository::find_by_product_id(&conn, keep)
+                .await
+                .unwrap();
+            assert_eq!(links.len(), 1);
+            assert_eq!(links[0].id, keep_link);
+            assert!(ProductRetailerRepository::find_by_id(&conn, duplicate)
+                .await
+                .unwrap()
+                .is_none());
+
+            let check = AvailabilityCheck::find_by_id(check)
+                .one(&conn)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(check.product_id, keep);
+            assert_eq!(check.product_retailer_id, Some(keep_link));
+        }
+
+        #[tokio::test]
+        async fn test_merge_moves_tags() {
+            let conn = setup_availability_db_with_tags().await;
+            let keep = create_product(&conn, "Keep").await;
+            let merge = create_product(&conn, "Merge").await;
+            let shared = TagRepository::find_or_create_by_name(&conn, "shared")
+                .await
+                .unwrap();
+            let only_merged = TagRepository::find_or_create_by_name(&conn, "gift")
+                .await
+                .unwrap();
+            TagRepository::attach(&conn, keep, shared.id).await.unwrap();
+            TagRepository::attach(&conn, merge, shared.id)
+                .await
+                .unwrap();
+            TagRepository::attach(&conn, merge, only_merged.id)
+                .await
+                .unwrap();
+
+            ProductRepository::merge_into(&conn, keep, merge)
+                .await
+                .unwrap();
+
+            let mut names: Vec<String> = TagRepository::find_by_product_id(&conn, keep)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|t| t.name)
+                .collect();
+            names.sort();
+            assert_eq!(names, vec!["gift", "shared"]);
+        }
+
+        #[tokio::test]
+        async fn test_merge_missing_product_changes_nothing() {
+            let conn = setup_availability_db_with_tags().await;
+            let keep = create_product(&conn, "Keep").await;
+            let link = add_link(&conn, keep, "https://shop.com/item").await;
+
+            let result = ProductRepository::merge_into(&conn, keep, Uuid::new_v4()).await;
+
+            assert!(matches!(result, Err(AppError::NotFound(_))));
+            let links = ProductRetailerRepository::find_by_product_id(&conn, keep)
+                .await
+                .unwrap();
+            assert_eq!(links.len(), 1);
+            assert_eq!(links[0].id, link);
+        }
+
+        #[tokio::test]
+        async fn test_merge_failure_rolls_back() {
+            // No tag tables, so the merge fails after links and checks have moved
+            let conn = setup_availability_db().await;
+            let keep = create_product(&conn, "Keep").await;
+            let merge = create_product(&conn, "Merge").await;
+            let merge_link = add_link(&conn, merge, "https://other.com/item").await;
+            let check = add_check(&conn, merge, merge_link).await;
+
+            let result = ProductRepository::merge_into(&conn, keep, merge).await;
+
+            assert!(result.is_err());
+            assert!(ProductRepository::find_by_id(&conn, merge)
+                .await
+                .unwrap()
+                .is_some());
+            let link = ProductRetailerRepository::find_by_id(&conn, merge_link)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(link.product_id, merge);
+            let check = AvailabilityCheck::find_by_id(check)
+                .one(&conn)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(check.product_id, merge);
+        }
+    }
+    mod clone_tests {
+        use super::*;
+        use crate::repositories::{
+            AvailabilityCheckRepository, CreateProductRetailerParams, ProductRetailerRepository,
+            RetailerRepository, TagRepository,
+        };
+        use crate::test_utils::setup_availability_db_with_tags;
+
+        async fn create_source(conn: &DatabaseConnection) -> ProductModel {
+            let product = ProductRepository::create(
+                conn,
+                Uuid::new_v4(),
+                CreateProductRepoParams {
+                    name: "Widget".to_string(),
+                    url: None,
+                    description: Some("Blue".to_string()),
+                    notes: Some("Size M".to_string()),
+                    check_interval_minutes: Some(15),
+                    target_price_minor_units: Some(4999),
+                    target_price_currency: Some("USD".to_string()),
+                },
+            )
+            .await
+            .unwrap();
+            ProductRepository::update(
+                conn,
+                product,
+                ProductUpdateInput {
+                    currency: Some(Some("USD".to_string())),
+                    is_paused: Some(true),
+                    track_price: Some(false),
+                    currency_locked: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+        }
+
+        async fn add_link(conn: &DatabaseConnection, product_id: Uuid, url: &str) -> Uuid {
+            let retailer = RetailerRepository::find_or_create_by_domain(conn, "shop.com")
+                .await
+                .unwrap();
+            ProductRetailerRepository::create(
+                conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: url.to_string(),
+                    label: Some("64GB".to_string()),
+                },
+            )
+            .await
+            .unwrap()
+            .id
+        }
+
+        #[tokio::test]
+        async fn test_clone_copies_settings_links_and_tags() {
+            let conn = setup_availability_db_with_tags().await;
+            let source = create_source(&conn).await;
+            let link = add_link(&conn, source.id, "https://shop.com/widget").await;
+            AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                &conn,
+                source.id,
+                link,
+                Some(4500),
+                Some("USD"),
+                Utc::now(),
+            )
+            .await;
+            let tag = TagRepository::find_or_create_by_name(&conn, "gift")
+                .await
+                .unwrap();
+            TagRepository::attach(&conn, source.id, tag.id)
+                .await
+                .unwrap();
+
+            let clone = ProductRepository::clone_product(
+                &conn,
+                source.id,
+                Uuid::new_v4(),
+                "Widget (copy)".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+
+            assert_ne!(clone.id, source.id);
+            assert_eq!(clone.name, "Widget (copy)");
+            assert_eq!(clone.description, source.description);
+            assert_eq!(clone.notes, source.notes);
+            assert_eq!(clone.currency, source.currency);
+            assert_eq!(clone.check_interval_minutes, Some(15));
+            assert_eq!(clone.target_price_minor_units, Some(4999));
+            assert_eq!(clone.target_price_currency, source.target_price_currency);
+            assert!(clone.is_paused);
+            assert!(!clone.track_price);
+            assert!(clone.currency_locked);
+            assert_eq!(clone.sort_order, source.sort_order + 1);
+
+            let links = ProductRetailerRepository::find_by_product_id(&conn, clone.id)
+                .await
+                .unwrap();
+            assert_eq!(links.len(), 1);
+            assert_ne!(links[0].id, link);
+            assert_eq!(links[0].url, "https://shop.com/widget");
+            assert_eq!(links[0].label.as_deref(), Some("64GB"));
+
+            let tags = TagRepository::find_by_product_id(&conn, clone.id)
+                .await
+                .unwrap();
+            assert_eq!(tags.len(), 1);
+            assert_eq!(tags[0].id, tag.id);
+
+            let clone_checks = AvailabilityCheck::find()
+                .filter(AvailabilityCheckColumn::ProductId.eq(clone.id))
+                .all(&conn)
+                .await
+                .unwrap();
+            assert!(clone_checks.is_empty());
+            // The source keeps its own link and history
+            assert_eq!(AvailabilityCheck::find().all(&conn).await.unwrap().len(), 1);
+            assert_eq!(
+                ProductRetailerRepository::find_by_product_id(&conn, source.id)
+                    .await
+                    .unwrap()[0]
+                    .id,
+                link
+            );
+        }
+
+        #[tokio::test]
+        async fn test_clone_with_replacement_link() {
+            let conn = setup_availability_db_with_tags().await;
+            let source = create_source(&conn).await;
+            add_link(&conn, source.id, "https://shop.com/widget").await;
+            add_link(&conn, source.id, "https://shop.com/widget-2").await;
+            let retailer = RetailerRepository::find_or_create_by_domain(&conn, "other.com")
+                .await
+                .unwrap();
+
+            let clone = ProductRepository::clone_product(
+                &conn,
+                source.id,
+                Uuid::new_v4(),
+                "Widget (copy)".to_string(),
+                Some(CloneProductLink {
+                    retailer_id: retailer.id,
+                    url: "https://other.com/widget".to_string(),
+                }),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(clone.currency, None);
+            assert!(!clone.currency_locked);
+            assert_eq!(clone.check_interval_minutes, Some(15));
+            let links = ProductRetailerRepository::find_by_product_id(&conn, clone.id)
+                .await
+                .unwrap();
+            assert_eq!(links.len(), 1);
+            assert_eq!(links[0].url, "https://other.com/widget");
+            assert_eq!(links[0].retailer_id, retailer.id);
+            assert_eq!(links[0].label, None);
+        }
+
+        #[tokio::test]
+        async fn test_clone_missing_product() {
+            let conn = setup_availability_db_with_tags().await;
+
+            let result = ProductRepository::clone_product(
+                &conn,
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                "Ghost (copy)".to_string(),
+                None,
+            )
+            .await;
+
+            assert!(matches!(result, Err(AppError::NotFound(_))));
+            assert!(ProductRepository::find_all(&conn).await.unwrap().is_empty());
+        }
+    }
 }
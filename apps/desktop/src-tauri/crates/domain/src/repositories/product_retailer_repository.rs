@@ -1,7 +1,7 @@
 use product_stalker_core::AppError;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
-    TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, TransactionTrait,
 };
 use uuid::Uuid;
 
@@ -19,8 +19,8 @@ pub struct ProductRetailerRepository;
 
 impl ProductRetailerRepository {
     /// Create a new product-retailer link (appends to end of sort order)
-    pub async fn create(
-        conn: &DatabaseConnection,
+    pub async fn create<C: ConnectionTrait>(
+        conn: &C,
         id: Uuid,
         retailer_id: Uuid,
         params: CreateProductRetailerParams,
@@ -37,6 +37,8 @@ impl ProductRetailerRepository {
             url: Set(params.url),
             label: Set(params.label),
             sort_order: Set(count),
+            currency: Set(None),
+            variant_selector: Set(None),
             created_at: Set(now),
         };
 
@@ -57,6 +59,32 @@ impl ProductRetailerRepository {
         Ok(links)
     }
 
+    /// Find all product-retailer links for a product with their retailers,
+    /// ordered by sort_order
+    pub async fn find_by_product_id_with_retailer(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Vec<(ProductRetailerModel, Option<RetailerModel>)>, AppError> {
+        let links = ProductRetailer::find()
+            .find_also_related(crate::entities::retailer::Entity)
+            .filter(ProductRetailerColumn::ProductId.eq(product_id))
+            .order_by_asc(ProductRetailerColumn::SortOrder)
+            .all(conn)
+            .await?;
+        Ok(links)
+    }
+
+    /// URLs of every product-retailer link, archived and paused products included
+    pub async fn find_all_urls(conn: &DatabaseConnection) -> Result<Vec<String>, AppError> {
+        let urls = ProductRetailer::find()
+            .select_only()
+            .column(ProductRetailerColumn::Url)
+            .into_tuple()
+            .all(conn)
+            .await?;
+        Ok(urls)
+    }
+
     /// Find a product-retailer link by ID
     pub async fn find_by_id(
         conn: &DatabaseConnection,
@@ -72,12 +100,16 @@ impl ProductRetailerRepository {
         Ok(result.rows_affected)
     }
 
-    /// Find all product-retailer links with their associated products (for bulk checks)
+    /// Find all product-retailer links with their associated products (for bulk checks).
+    ///
+    /// Links belonging to paused or archived products are excluded.
     pub async fn find_all_with_product(
         conn: &DatabaseConnection,
     ) -> Result<Vec<(ProductRetailerModel, Option<ProductModel>)>, AppError> {
         let results = ProductRetailer::find()
             .find_also_related(crate::entities::product::Entity)
+            .filter(ProductColumn::IsPaused.eq(false))
+            .filter(ProductColumn::IsArchived.eq(false))
             .all(conn)
             .await?;
         Ok(results)
@@ -115,9 +147,66 @@ impl ProductRetailerRepository {
         Ok(())
     }
 
-    /// Count how many retailer links a product has
-    pub async fn count_by_product_id(
+    /// Set or clear a link's variant selector (JSON-encoded)
+    pub async fn update_variant_selector(
+        conn: &DatabaseConnection,
+        id: Uuid,
+        variant_selector: Option<String>,
+    ) -> Result<ProductRetailerModel, AppError> {
+        let link = ProductRetailer::find_by_id(id)
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Product retailer not found: {}", id)))?;
+        let mut active_model: ProductRetailerActiveModel = link.into();
+        active_model.variant_selector = Set(variant_selector);
+        Ok(active_model.update(conn).await?)
+    }
+
+    /// Point a link at a new URL, on the retailer for that URL's domain
+    pub async fn update_url(
+        conn: &DatabaseConnection,
+        id: Uuid,
+        retailer_id: Uuid,
+        url: String,
+    ) -> Result<ProductRetailerModel, AppError> {
+        let link = ProductRetailer::find_by_id(id)
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Product retailer not found: {}", id)))?;
+        let mut active_model: ProductRetailerActiveModel = link.into();
+        active_model.retailer_id = Set(retailer_id);
+        active_model.url = Set(url);
+        Ok(active_model.update(conn).await?)
+    }
+
+    /// Set a link's currency only if it is still `expected`.
+    ///
+    /// A compare-and-set, so two checks of the same link finishing together
+    /// can't overwrite each other's correction. Returns whether the row changed.
+    pub async fn set_currency_if(
         conn: &DatabaseConnection,
+        id: Uuid,
+        expected: Option<&str>,
+        currency: &str,
+    ) -> Result<bool, AppError> {
+        use sea_orm::sea_query::Expr;
+
+        let current = match expected {
+            Some(expected) => ProductRetailerColumn::Currency.eq(expected),
+            None => ProductRetailerColumn::Currency.is_null(),
+        };
+        let result = ProductRetailer::update_many()
+            .col_expr(ProductRetailerColumn::Currency, Expr::value(currency))
+            .filter(ProductRetailerColumn::Id.eq(id))
+            .filter(current)
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Count how many retailer links a product has
+    pub async fn count_by_product_id<C: ConnectionTrait>(
+        conn: &C,
         product_id: Uuid,
     ) -> Result<u64, AppError> {
         use sea_orm::PaginatorTrait;
@@ -132,13 +221,13 @@ impl ProductRetailerRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repositories::RetailerRepository;
+    use crate::repositories::{ProductRepository, ProductUpdateInput, RetailerRepository};
     use crate::test_utils::setup_product_retailer_db;
 
     async fn create_test_data(
         conn: &DatabaseConnection,
     ) -> (ProductModel, RetailerModel, ProductRetailerModel) {
-        use crate::repositories::{CreateProductRepoParams, ProductRepository};
+        use crate::repositories::CreateProductRepoParams;
 
         let product = ProductRepository::create(
             conn,
@@ -148,6 +237,9 @@ mod tests {
                 url: None,
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await
@@ -213,6 +305,20 @@ mod tests {
         assert_eq!(links.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_find_by_product_id_with_retailer() {
+        let conn = setup_product_retailer_db().await;
+        let (product, retailer, pr) = create_test_data(&conn).await;
+
+        let links = ProductRetailerRepository::find_by_product_id_with_retailer(&conn, product.id)
+            .await
+            .unwrap();
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].0.id, pr.id);
+        assert_eq!(links[0].1.as_ref().map(|r| r.id), Some(retailer.id));
+    }
+
     #[tokio::test]
     async fn test_delete_by_id() {
         let conn = setup_product_retailer_db().await;
@@ -241,6 +347,48 @@ mod tests {
         assert!(results[0].1.is_some());
     }
 
+    #[tokio::test]
+    async fn test_find_all_with_product_excludes_paused() {
+        let conn = setup_product_retailer_db().await;
+        let (product, _, _) = create_test_data(&conn).await;
+        ProductRepository::update(
+            &conn,
+            product,
+            ProductUpdateInput {
+                is_paused: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let results = ProductRetailerRepository::find_all_with_product(&conn)
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_all_with_product_excludes_archived() {
+        let conn = setup_product_retailer_db().await;
+        let (product, _, _) = create_test_data(&conn).await;
+        ProductRepository::update(
+            &conn,
+            product,
+            ProductUpdateInput {
+                is_archived: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let results = ProductRetailerRepository::find_all_with_product(&conn)
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
     #[tokio::test]
     async fn test_count_by_product_id() {
         let conn = setup_product_retailer_db().await;
@@ -413,4 +561,88 @@ mod tests {
             ProductRetailerRepository::update_sort_orders(&conn, vec![(Uuid::new_v4(), 0)]).await;
         assert!(matches!(result, Err(AppError::NotFound(_))));
     }
+
+    #[tokio::test]
+    async fn test_update_variant_selector() {
+        let conn = setup_product_retailer_db().await;
+        let (_, _, pr) = create_test_data(&conn).await;
+        assert_eq!(pr.variant_selector, None);
+
+        let updated = ProductRetailerRepository::update_variant_selector(
+            &conn,
+            pr.id,
+            Some(r#"{"size":"M"}"#.to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.variant_selector.as_deref(), Some(r#"{"size":"M"}"#));
+
+        let cleared = ProductRetailerRepository::update_variant_selector(&conn, pr.id, None)
+            .await
+            .unwrap();
+        assert_eq!(cleared.variant_selector, None);
+    }
+
+    #[tokio::test]
+    async fn test_update_variant_selector_not_found() {
+        let conn = setup_product_retailer_db().await;
+        let result =
+            ProductRetailerRepository::update_variant_selector(&conn, Uuid::new_v4(), None).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_new_link_has_no_currency() {
+        let conn = setup_product_retailer_db().await;
+        let (_, _, pr) = create_test_data(&conn).await;
+        assert_eq!(pr.currency, None);
+    }
+
+    #[tokio::test]
+    async fn test_set_currency_if_matches_expected() {
+        let conn = setup_product_retailer_db().await;
+        let (_, _, pr) = create_test_data(&conn).await;
+
+        let changed = ProductRetailerRepository::set_currency_if(&conn, pr.id, None, "AUD")
+            .await
+            .unwrap();
+        assert!(changed);
+
+        let changed = ProductRetailerRepository::set_currency_if(&conn, pr.id, Some("AUD"), "NZD")
+            .await
+            .unwrap();
+        assert!(changed);
+
+        let found = ProductRetailerRepository::find_by_id(&conn, pr.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.currency.as_deref(), Some("NZD"));
+    }
+
+    #[tokio::test]
+    async fn test_set_currency_if_skips_stale_expectation() {
+        let conn = setup_product_retailer_db().await;
+        let (_, _, pr) = create_test_data(&conn).await;
+        ProductRetailerRepository::set_currency_if(&conn, pr.id, None, "AUD")
+            .await
+            .unwrap();
+
+        // Another check already set the currency, so a second "unset" write loses
+        let changed = ProductRetailerRepository::set_currency_if(&conn, pr.id, None, "USD")
+            .await
+            .unwrap();
+        assert!(!changed);
+
+        let changed = ProductRetailerRepository::set_currency_if(&conn, pr.id, Some("GBP"), "USD")
+            .await
+            .unwrap();
+        assert!(!changed);
+
+        let found = ProductRetailerRepository::find_by_id(&conn, pr.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.currency.as_deref(), Some("AUD"));
+    }
 }
@@ -1,9 +1,20 @@
 use product_stalker_core::AppError;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    FromQueryResult, QueryFilter, Set, Statement,
+};
 use uuid::Uuid;
 
 use crate::entities::prelude::*;
 
+/// Outcome counts for the most recent checks across all of a retailer's links
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct RetailerCheckCount {
+    pub retailer_id: Uuid,
+    pub total_checks: i64,
+    pub successful_checks: i64,
+}
+
 /// Repository for retailer data access
 pub struct RetailerRepository;
 
@@ -12,8 +23,8 @@ impl RetailerRepository {
     ///
     /// If a retailer with the given domain exists, returns it.
     /// Otherwise creates a new one with the domain as the name.
-    pub async fn find_or_create_by_domain(
-        conn: &DatabaseConnection,
+    pub async fn find_or_create_by_domain<C: ConnectionTrait>(
+        conn: &C,
         domain: &str,
     ) -> Result<RetailerModel, AppError> {
         if let Some(existing) = Self::find_by_domain(conn, domain).await? {
@@ -35,8 +46,8 @@ impl RetailerRepository {
     }
 
     /// Find a retailer by domain
-    pub async fn find_by_domain(
-        conn: &DatabaseConnection,
+    pub async fn find_by_domain<C: ConnectionTrait>(
+        conn: &C,
         domain: &str,
     ) -> Result<Option<RetailerModel>, AppError> {
         let retailer = Retailer::find()
@@ -54,6 +65,42 @@ impl RetailerRepository {
         let retailer = Retailer::find_by_id(id).one(conn).await?;
         Ok(retailer)
     }
+
+    /// Count successful and total checks per retailer, over the `limit_per_retailer`
+    /// most recent checks across all of the retailer's product links.
+    ///
+    /// Retailers without any checks are omitted.
+    pub async fn count_recent_checks_by_retailer(
+        conn: &DatabaseConnection,
+        limit_per_retailer: u64,
+    ) -> Result<Vec<RetailerCheckCount>, AppError> {
+        let rows = RetailerCheckCount::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            r#"
+                WITH recent AS (
+                    SELECT pr.retailer_id,
+                           ac.error_message,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY pr.retailer_id
+                               ORDER BY ac.checked_at DESC
+                           ) as rn
+                    FROM availability_checks ac
+                    JOIN product_retailers pr ON pr.id = ac.product_retailer_id
+                )
+                SELECT retailer_id,
+                       COUNT(*) as total_checks,
+                       SUM(CASE WHEN error_message IS NULL THEN 1 ELSE 0 END) as successful_checks
+                FROM recent
+                WHERE rn <= ?
+                GROUP BY retailer_id
+            "#,
+            [(limit_per_retailer as i64).into()],
+        ))
+        .all(conn)
+        .await?;
+
+        Ok(rows)
+    }
 }
 
 #[cfg(test)]
@@ -113,4 +160,117 @@ mod tests {
         assert!(found.is_some());
         assert_eq!(found.unwrap().domain, "test.com");
     }
+
+    mod count_recent_checks_tests {
+        use chrono::{Duration, Utc};
+        use sea_orm::{IntoActiveModel, Set};
+
+        use super::*;
+        use crate::repositories::{
+            AvailabilityCheckRepository, CreateProductRetailerParams, ProductRetailerRepository,
+        };
+        use crate::test_utils::{create_test_product_default, setup_availability_db};
+
+        async fn add_link(conn: &DatabaseConnection, product_id: Uuid, url: &str) -> (Uuid, Uuid) {
+            let domain = url::Url::parse(url)
+                .unwrap()
+                .host_str()
+                .unwrap()
+                .to_string();
+            let retailer = RetailerRepository::find_or_create_by_domain(conn, &domain)
+                .await
+                .unwrap();
+            let link = ProductRetailerRepository::create(
+                conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: url.to_string(),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap();
+            (retailer.id, link.id)
+        }
+
+        async fn record_check(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            product_retailer_id: Uuid,
+            failed: bool,
+            minutes_ago: i64,
+        ) {
+            let check = AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                conn,
+                product_id,
+                product_retailer_id,
+                None,
+                None,
+                Utc::now() - Duration::minutes(minutes_ago),
+            )
+            .await;
+            let mut active_model = check.into_active_model();
+            active_model.error_message = Set(failed.then(|| "HTTP 503".to_string()));
+            active_model.update(conn).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_counts_across_all_links_of_a_retailer() {
+            let conn = setup_availability_db().await;
+            let first = create_test_product_default(&conn).await;
+            let second = create_test_product_default(&conn).await;
+            let (shop_id, shop_a) = add_link(&conn, first, "https://shop.com/a").await;
+            let (_, shop_b) = add_link(&conn, second, "https://shop.com/b").await;
+            let (flaky_id, flaky) = add_link(&conn, first, "https://flaky.com/a").await;
+            add_link(&conn, second, "https://unchecked.com/b").await;
+
+            record_check(&conn, first, shop_a, false, 3).await;
+            record_check(&conn, second, shop_b, false, 2).await;
+            record_check(&conn, second, shop_b, true, 1).await;
+            record_check(&conn, first, flaky, true, 2).await;
+            record_check(&conn, first, flaky, true, 1).await;
+
+            let mut counts = RetailerRepository::count_recent_checks_by_retailer(&conn, 10)
+                .await
+                .unwrap();
+            counts.sort_by_key(|c| c.total_checks);
+
+            assert_eq!(
+                counts,
+                vec![
+                    RetailerCheckCount {
+                        retailer_id: flaky_id,
+                        total_checks: 2,
+                        successful_checks: 0,
+                    },
+                    RetailerCheckCount {
+                        retailer_id: shop_id,
+                        total_checks: 3,
+                        successful_checks: 2,
+                    },
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_only_counts_most_recent_checks() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let (_, link) = add_link(&conn, product_id, "https://shop.com/a").await;
+
+            record_check(&conn, product_id, link, true, 30).await;
+            record_check(&conn, product_id, link, false, 20).await;
+            record_check(&conn, product_id, link, false, 10).await;
+
+            let counts = RetailerRepository::count_recent_checks_by_retailer(&conn, 2)
+                .await
+                .unwrap();
+
+            assert_eq!(counts.len(), 1);
+            assert_eq!(counts[0].total_checks, 2);
+            assert_eq!(counts[0].successful_checks, 2);
+        }
+    }
 }
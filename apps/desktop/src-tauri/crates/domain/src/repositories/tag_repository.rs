@@ -0,0 +1,243 @@
+use product_stalker_core::AppError;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, Set,
+};
+use uuid::Uuid;
+
+use crate::entities::prelude::*;
+
+/// Repository for tag data access
+pub struct TagRepository;
+
+impl TagRepository {
+    /// Normalize a tag name for storage and lookup (trimmed, lowercase)
+    pub fn normalize_name(name: &str) -> String {
+        name.trim().to_lowercase()
+    }
+
+    /// Find or create a tag by name.
+    ///
+    /// Names are normalized first, so "Kitchen" and " kitchen " resolve
+    /// to the same tag.
+    pub async fn find_or_create_by_name(
+        conn: &DatabaseConnection,
+        name: &str,
+    ) -> Result<TagModel, AppError> {
+        if let Some(existing) = Self::find_by_name(conn, name).await? {
+            return Ok(existing);
+        }
+
+        let active_model = TagActiveModel {
+            id: Set(Uuid::new_v4()),
+            name: Set(Self::normalize_name(name)),
+            created_at: Set(chrono::Utc::now()),
+        };
+
+        let tag = active_model.insert(conn).await?;
+        Ok(tag)
+    }
+
+    /// Find a tag by name (normalized before lookup)
+    pub async fn find_by_name(
+        conn: &DatabaseConnection,
+        name: &str,
+    ) -> Result<Option<TagModel>, AppError> {
+        let tag = Tag::find()
+            .filter(TagColumn::Name.eq(Self::normalize_name(name)))
+            .one(conn)
+            .await?;
+        Ok(tag)
+    }
+
+    /// Find all tags, ordered by name
+    pub async fn find_all(conn: &DatabaseConnection) -> Result<Vec<TagModel>, AppError> {
+        let tags = Tag::find().order_by_asc(TagColumn::Name).all(conn).await?;
+        Ok(tags)
+    }
+
+    /// Find the tags attached to a product, ordered by name
+    pub async fn find_by_product_id(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Vec<TagModel>, AppError> {
+        let tags = Tag::find()
+            .join(
+                sea_orm::JoinType::InnerJoin,
+                crate::entities::tag::Relation::ProductTags.def(),
+            )
+            .filter(ProductTagColumn::ProductId.eq(product_id))
+            .order_by_asc(TagColumn::Name)
+            .all(conn)
+            .await?;
+        Ok(tags)
+    }
+
+    /// Find the IDs of all products carrying a tag
+    pub async fn find_product_ids_by_tag_id(
+        conn: &DatabaseConnection,
+        tag_id: Uuid,
+    ) -> Result<Vec<Uuid>, AppError> {
+        let links = ProductTag::find()
+            .filter(ProductTagColumn::TagId.eq(tag_id))
+            .all(conn)
+            .await?;
+        Ok(links.into_iter().map(|link| link.product_id).collect())
+    }
+
+    /// Attach a tag to a product. Attaching an already attached tag is a no-op.
+    pub async fn attach(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<(), AppError> {
+        if ProductTag::find_by_id((product_id, tag_id))
+            .one(conn)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let active_model = ProductTagActiveModel {
+            product_id: Set(product_id),
+            tag_id: Set(tag_id),
+        };
+        active_model.insert(conn).await?;
+        Ok(())
+    }
+
+    /// Detach a tag from a product, returning the number of rows removed
+    pub async fn detach(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<u64, AppError> {
+        let result = ProductTag::delete_by_id((product_id, tag_id))
+            .exec(conn)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_product, setup_tag_db};
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(TagRepository::normalize_name("  Kitchen "), "kitchen");
+        assert_eq!(TagRepository::normalize_name("GPU"), "gpu");
+    }
+
+    #[tokio::test]
+    async fn test_find_or_create_new() {
+        let conn = setup_tag_db().await;
+
+        let tag = TagRepository::find_or_create_by_name(&conn, "Kitchen")
+            .await
+            .unwrap();
+
+        assert_eq!(tag.name, "kitchen");
+    }
+
+    #[tokio::test]
+    async fn test_find_or_create_dedups_by_normalized_name() {
+        let conn = setup_tag_db().await;
+
+        let first = TagRepository::find_or_create_by_name(&conn, "kitchen")
+            .await
+            .unwrap();
+        let second = TagRepository::find_or_create_by_name(&conn, "  KITCHEN ")
+            .await
+            .unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(TagRepository::find_all(&conn).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_by_name_none() {
+        let conn = setup_tag_db().await;
+
+        let result = TagRepository::find_by_name(&conn, "missing").await.unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_attach_is_idempotent() {
+        let conn = setup_tag_db().await;
+        let product_id = create_test_product(&conn, "https://a.com").await;
+        let tag = TagRepository::find_or_create_by_name(&conn, "gpu")
+            .await
+            .unwrap();
+
+        TagRepository::attach(&conn, product_id, tag.id)
+            .await
+            .unwrap();
+        TagRepository::attach(&conn, product_id, tag.id)
+            .await
+            .unwrap();
+
+        let ids = TagRepository::find_product_ids_by_tag_id(&conn, tag.id)
+            .await
+            .unwrap();
+        assert_eq!(ids, vec![product_id]);
+    }
+
+    #[tokio::test]
+    async fn test_find_by_product_id() {
+        let conn = setup_tag_db().await;
+        let product_id = create_test_product(&conn, "https://a.com").await;
+        let other_id = create_test_product(&conn, "https://b.com").await;
+        let kitchen = TagRepository::find_or_create_by_name(&conn, "kitchen")
+            .await
+            .unwrap();
+        let gpu = TagRepository::find_or_create_by_name(&conn, "gpu")
+            .await
+            .unwrap();
+        TagRepository::attach(&conn, product_id, kitchen.id)
+            .await
+            .unwrap();
+        TagRepository::attach(&conn, product_id, gpu.id)
+            .await
+            .unwrap();
+        TagRepository::attach(&conn, other_id, kitchen.id)
+            .await
+            .unwrap();
+
+        let tags = TagRepository::find_by_product_id(&conn, product_id)
+            .await
+            .unwrap();
+        let names: Vec<_> = tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["gpu", "kitchen"]);
+    }
+
+    #[tokio::test]
+    async fn test_detach() {
+        let conn = setup_tag_db().await;
+        let product_id = create_test_product(&conn, "https://a.com").await;
+        let tag = TagRepository::find_or_create_by_name(&conn, "gpu")
+            .await
+            .unwrap();
+        TagRepository::attach(&conn, product_id, tag.id)
+            .await
+            .unwrap();
+
+        let removed = TagRepository::detach(&conn, product_id, tag.id)
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+
+        let removed_again = TagRepository::detach(&conn, product_id, tag.id)
+            .await
+            .unwrap();
+        assert_eq!(removed_again, 0);
+        assert!(TagRepository::find_product_ids_by_tag_id(&conn, tag.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
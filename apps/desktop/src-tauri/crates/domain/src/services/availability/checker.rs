@@ -1,20 +1,23 @@
 //! Product availability checking and result processing.
 
+use chrono::{DateTime, Utc};
 use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
-use crate::entities::availability_check::AvailabilityStatus;
-use crate::entities::prelude::{AvailabilityCheckModel, ProductModel};
+use crate::entities::availability_check::{AvailabilityStatus, CheckErrorKind};
+use crate::entities::prelude::{AvailabilityCheckModel, ProductModel, ProductRetailerModel};
 use crate::repositories::{
-    AvailabilityCheckRepository, CreateCheckParams, ProductRepository, ProductRetailerRepository,
+    AvailabilityCheckRepository, Bucket, CreateCheckParams, PriceDrop, PriceExtremes,
+    ProductRepository, ProductRetailerRepository, StatusTransition,
 };
-use crate::services::scraper::has_path_locale;
+use crate::services::currency::format_price;
+use crate::services::scraper::{has_path_locale, FetchOptions, ScrapeDiagnostics, VariantSelector};
 use crate::services::{NotificationService, ScraperService};
 use product_stalker_core::AppError;
 
 use super::types::{
     BulkCheckResult, CheckConfig, CheckProcessingResult, CheckResultWithNotification,
-    DailyPriceComparison, ProductCheckContext,
+    DailyPriceComparison, ProductCheckContext, UrlValidation,
 };
 use super::AvailabilityService;
 
@@ -25,18 +28,27 @@ struct NormalizedPrice {
 }
 
 impl AvailabilityService {
+    /// Days of price history averaged when judging whether a new price is plausible
+    pub const PLAUSIBLE_PRICE_WINDOW_DAYS: i64 = 7;
+
     /// Build CreateCheckParams from a successful scraping result
     fn params_from_success(result: crate::services::scraper::ScrapingResult) -> CreateCheckParams {
         CreateCheckParams {
             status: result.status,
             raw_availability: result.raw_availability,
             error_message: None,
+            error_kind: None,
             price_minor_units: result.price.price_minor_units,
             price_currency: result.price.price_currency,
             raw_price: result.price.raw_price,
+            compare_at_minor_units: result.price.compare_at_minor_units,
             product_retailer_id: None,
             normalized_price_minor_units: None,
             normalized_currency: None,
+            price_valid_until: result.price.price_valid_until,
+            fetch_duration_ms: result.fetch_metrics.map(|m| m.duration_ms),
+            content_bytes: result.fetch_metrics.map(|m| m.content_bytes),
+            confidence: Some(result.confidence),
         }
     }
 
@@ -44,6 +56,7 @@ impl AvailabilityService {
     fn params_from_error(error: &AppError) -> CreateCheckParams {
         CreateCheckParams {
             error_message: Some(error.to_string()),
+            error_kind: Some(CheckErrorKind::from_error(error)),
             ..Default::default()
         }
     }
@@ -108,23 +121,37 @@ impl AvailabilityService {
     }
 
     /// Process a scraping result: build params, auto-set currency, normalize price.
+    ///
+    /// `retailer` is the link being checked, if any; its own currency is
+    /// auto-set alongside the product's.
     async fn process_scraping_result(
         conn: &DatabaseConnection,
         result: Result<crate::services::scraper::ScrapingResult, AppError>,
         product: &ProductModel,
-        check_url: Option<&str>,
+        retailer: Option<&ProductRetailerModel>,
         preferred_currency: &str,
     ) -> CreateCheckParams {
         let mut params = match result {
             Ok(scraping_result) => {
+                let region_mismatch = scraping_result.region_mismatch;
                 let params = Self::params_from_success(scraping_result);
                 Self::auto_set_product_currency(
                     conn,
                     product,
                     params.price_currency.as_deref(),
-                    check_url,
+                    retailer.map(|pr| pr.url.as_str()),
+                    region_mismatch,
                 )
                 .await;
+                if let Some(pr) = retailer {
+                    Self::auto_set_retailer_currency(
+                        conn,
+                        pr,
+                        params.price_currency.as_deref(),
+                        region_mismatch,
+                    )
+                    .await;
+                }
                 params
             }
             Err(e) => Self::params_from_error(&e),
@@ -163,20 +190,53 @@ impl AvailabilityService {
 
         let result = ScraperService::check_availability_with_headless(
             url,
-            config.enable_headless,
-            config.allow_manual_verification,
             conn,
-            config.session_cache_duration_days,
+            &config.fetch,
+            product.track_price,
+            None,
         )
         .await;
 
-        let params =
+        let mut params =
             Self::process_scraping_result(conn, result, &product, None, config.preferred_currency)
                 .await;
+        Self::reject_implausible_price(
+            conn,
+            &mut params,
+            product_id,
+            config.min_plausible_price_percent,
+        )
+        .await;
 
         AvailabilityCheckRepository::create(conn, Uuid::new_v4(), product_id, params).await
     }
 
+    /// Fetch the HTML the scraper sees for a product, for debugging mis-reported checks.
+    ///
+    /// Uses the product's own URL, or its first retailer link when it has none,
+    /// with the same fetch settings as a check. Nothing is stored.
+    pub async fn fetch_raw_html(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        options: &FetchOptions,
+    ) -> Result<String, AppError> {
+        let product = ProductRepository::find_by_id(conn, product_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Product not found: {}", product_id)))?;
+
+        let url = match product.url {
+            Some(url) => url,
+            None => ProductRetailerRepository::find_by_product_id(conn, product_id)
+                .await?
+                .into_iter()
+                .next()
+                .map(|link| link.url)
+                .ok_or_else(|| AppError::Validation("Product has no URL set".to_string()))?,
+        };
+
+        ScraperService::fetch_raw_html(&url, conn, options).await
+    }
+
     /// Check availability for a product-retailer link.
     ///
     /// Uses the product_retailer URL, stores results with both product_id and product_retailer_id.
@@ -199,12 +259,13 @@ impl AvailabilityService {
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Product not found: {}", pr.product_id)))?;
 
+        let variant_selector = Self::parse_variant_selector(&pr);
         let result = ScraperService::check_availability_with_headless(
             &pr.url,
-            config.enable_headless,
-            config.allow_manual_verification,
             conn,
-            config.session_cache_duration_days,
+            &config.fetch,
+            product.track_price,
+            variant_selector.as_ref(),
         )
         .await;
 
@@ -212,30 +273,235 @@ impl AvailabilityService {
             conn,
             result,
             &product,
-            Some(&pr.url),
+            Some(&pr),
             config.preferred_currency,
         )
         .await;
         params.product_retailer_id = Some(product_retailer_id);
+        Self::reject_implausible_price(
+            conn,
+            &mut params,
+            pr.product_id,
+            config.min_plausible_price_percent,
+        )
+        .await;
 
         AvailabilityCheckRepository::create(conn, Uuid::new_v4(), pr.product_id, params).await
     }
 
+    /// Parse a link's stored variant selector; an invalid one is ignored with a warning
+    fn parse_variant_selector(pr: &ProductRetailerModel) -> Option<VariantSelector> {
+        let json = pr.variant_selector.as_deref()?;
+        match VariantSelector::from_json(json) {
+            Ok(selector) => Some(selector),
+            Err(e) => {
+                log::warn!(
+                    "Ignoring variant selector of retailer link {}: {}",
+                    pr.id,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Ignore a scraped price that is implausibly far below the recent average.
+    ///
+    /// The average covers the last [`Self::PLAUSIBLE_PRICE_WINDOW_DAYS`] of checks
+    /// for the same retailer link (or the product, for legacy checks) in the same
+    /// currency. An implausible price is cleared and the check is recorded as
+    /// failed with [`CheckErrorKind::ImplausiblePrice`], so it neither skews price
+    /// averages nor triggers a price drop. The scraped status is kept, so the check
+    /// can still report a restock or sell-out.
+    async fn reject_implausible_price(
+        conn: &DatabaseConnection,
+        params: &mut CreateCheckParams,
+        product_id: Uuid,
+        min_plausible_percent: i32,
+    ) {
+        if min_plausible_percent <= 0 || params.error_message.is_some() {
+            return;
+        }
+        let (Some(price), Some(currency)) = (params.price_minor_units, &params.price_currency)
+        else {
+            return;
+        };
+
+        let now = Utc::now();
+        let from = now - chrono::Duration::days(Self::PLAUSIBLE_PRICE_WINDOW_DAYS);
+        let average = match params.product_retailer_id {
+            Some(product_retailer_id) => {
+                AvailabilityCheckRepository::get_average_price_for_period_by_product_retailer(
+                    conn,
+                    product_retailer_id,
+                    from,
+                    now,
+                )
+                .await
+            }
+            None => AvailabilityCheckRepository::get_original_averages_by_currency_for_period(
+                conn, product_id, from, now,
+            )
+            .await
+            .map(|averages| {
+                averages
+                    .into_iter()
+                    .find(|avg| avg.price_currency.eq_ignore_ascii_case(currency))
+                    .map(|avg| avg.avg_price.round() as i64)
+            }),
+        };
+        let average = match average {
+            Ok(average) => average,
+            Err(e) => {
+                log::warn!(
+                    "Failed to load recent prices for product {}: {}",
+                    product_id,
+                    e
+                );
+                return;
+            }
+        };
+
+        if !Self::is_implausible_price(average, Some(price), min_plausible_percent) {
+            return;
+        }
+        let message = format!(
+            "Ignored implausible price {} (below {}% of the recent average {})",
+            format_price(price, currency),
+            min_plausible_percent,
+            format_price(average.unwrap_or_default(), currency)
+        );
+        log::warn!("Product {}: {}", product_id, message);
+        params.error_message = Some(message);
+        params.error_kind = Some(CheckErrorKind::ImplausiblePrice);
+        params.price_minor_units = None;
+        params.compare_at_minor_units = None;
+        params.normalized_price_minor_units = None;
+        params.normalized_currency = None;
+    }
+
+    /// Scrape a newly added retailer URL once to confirm it can be tracked.
+    ///
+    /// When a strategy matches, the detected status and price are stored as the
+    /// link's first check. A page that can't be fetched or has no product data
+    /// returns a warning instead of an error, so the link is still kept.
+    pub async fn validate_product_retailer(
+        conn: &DatabaseConnection,
+        product_retailer_id: Uuid,
+        config: &CheckConfig<'_>,
+    ) -> Result<UrlValidation, AppError> {
+        let pr = ProductRetailerRepository::find_by_id(conn, product_retailer_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Product retailer not found: {}",
+                    product_retailer_id
+                ))
+            })?;
+
+        let diagnostics = match ScraperService::diagnose(&pr.url, conn, &config.fetch).await {
+            Ok(diagnostics) => diagnostics,
+            Err(e) => {
+                return Ok(UrlValidation {
+                    diagnostics: None,
+                    warning: Some(format!("Couldn't load this URL: {}", e)),
+                })
+            }
+        };
+
+        if diagnostics.strategy.is_none() {
+            return Ok(UrlValidation {
+                warning: Some(format!(
+                    "No product data found on this page, so checks will report Unknown: {}",
+                    diagnostics
+                        .error
+                        .as_deref()
+                        .unwrap_or("no strategy matched")
+                )),
+                diagnostics: Some(diagnostics),
+            });
+        }
+
+        let product = ProductRepository::find_by_id(conn, pr.product_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Product not found: {}", pr.product_id)))?;
+        Self::auto_set_product_currency(
+            conn,
+            &product,
+            diagnostics.price_currency.as_deref(),
+            Some(&pr.url),
+            false,
+        )
+        .await;
+        Self::auto_set_retailer_currency(conn, &pr, diagnostics.price_currency.as_deref(), false)
+            .await;
+
+        let mut params = Self::params_from_diagnostics(&diagnostics);
+        let normalized = Self::normalize_price(
+            conn,
+            params.price_minor_units,
+            params.price_currency.as_deref(),
+            config.preferred_currency,
+        )
+        .await;
+        params.normalized_price_minor_units = normalized.minor_units;
+        params.normalized_currency = normalized.currency;
+        params.product_retailer_id = Some(product_retailer_id);
+        AvailabilityCheckRepository::create(conn, Uuid::new_v4(), pr.product_id, params).await?;
+
+        Ok(UrlValidation {
+            diagnostics: Some(diagnostics),
+            warning: None,
+        })
+    }
+
+    /// Build CreateCheckParams from a dry-run scrape that matched a strategy
+    fn params_from_diagnostics(diagnostics: &ScrapeDiagnostics) -> CreateCheckParams {
+        CreateCheckParams {
+            status: diagnostics.status.clone(),
+            raw_availability: diagnostics.raw_availability.clone(),
+            price_minor_units: diagnostics.price_minor_units,
+            price_currency: diagnostics.price_currency.clone(),
+            raw_price: diagnostics.raw_price.clone(),
+            ..Default::default()
+        }
+    }
+
     /// Auto-set product currency from scraped price data.
     ///
     /// If the product has no currency set and the scrape found one, saves it.
     /// If the product already has a different currency, checks if the URL has a path locale:
     /// - If path locale detected: Updates to the scraped currency (corrects old detection)
     /// - If no path locale: Keeps existing currency (might be user-set or genuinely ambiguous)
+    ///
+    /// An existing currency is never overridden when the page redirected to another
+    /// region (`region_mismatch`), since its prices belong to that region.
+    ///
+    /// A currency the user locked (`currency_locked`) is never changed.
     async fn auto_set_product_currency(
         conn: &DatabaseConnection,
         product: &ProductModel,
         scraped_currency: Option<&str>,
         check_url: Option<&str>,
+        region_mismatch: bool,
     ) {
         let Some(scraped) = scraped_currency else {
             return;
         };
+        if product.currency_locked {
+            if product
+                .currency
+                .as_deref()
+                .is_some_and(|existing| !existing.eq_ignore_ascii_case(scraped))
+            {
+                log::debug!(
+                    "Product {} scraped {} but its currency is locked; keeping it",
+                    product.id,
+                    scraped
+                );
+            }
+            return;
+        }
 
         match &product.currency {
             None => {
@@ -252,6 +518,14 @@ impl AvailabilityService {
                     );
                 }
             }
+            Some(existing) if region_mismatch && !existing.eq_ignore_ascii_case(scraped) => {
+                log::warn!(
+                    "Product {} has currency {} but a region redirect returned {}; keeping existing",
+                    product.id,
+                    existing,
+                    scraped
+                );
+            }
             Some(existing) if !existing.eq_ignore_ascii_case(scraped) => {
                 // Currency mismatch detected
 
@@ -289,29 +563,111 @@ impl AvailabilityService {
         }
     }
 
+    /// Auto-set a retailer link's currency from scraped price data.
+    ///
+    /// Same rules as [`Self::auto_set_product_currency`], applied to the link on
+    /// its own: each retailer can sell in a different region, so the link's
+    /// currency is detected from its own URL regardless of the product's.
+    /// Writes are compare-and-set, so concurrent checks of the same link never
+    /// overwrite a correction with a stale value.
+    async fn auto_set_retailer_currency(
+        conn: &DatabaseConnection,
+        pr: &ProductRetailerModel,
+        scraped_currency: Option<&str>,
+        region_mismatch: bool,
+    ) {
+        let Some(scraped) = scraped_currency else {
+            return;
+        };
+
+        let expected = match pr.currency.as_deref() {
+            None => None,
+            Some(existing) if existing.eq_ignore_ascii_case(scraped) => return,
+            Some(existing) if region_mismatch => {
+                log::warn!(
+                    "Retailer link {} has currency {} but a region redirect returned {}; keeping existing",
+                    pr.id,
+                    existing,
+                    scraped
+                );
+                return;
+            }
+            Some(existing) if has_path_locale(&pr.url) => {
+                log::info!(
+                    "Correcting currency for retailer link {} from {} to {} (path locale detected in URL)",
+                    pr.id,
+                    existing,
+                    scraped
+                );
+                Some(existing)
+            }
+            Some(existing) => {
+                log::warn!(
+                    "Retailer link {} has currency {} but scraped {}; keeping existing (no path locale found)",
+                    pr.id,
+                    existing,
+                    scraped
+                );
+                return;
+            }
+        };
+
+        match ProductRetailerRepository::set_currency_if(conn, pr.id, expected, scraped).await {
+            Ok(true) => {}
+            Ok(false) => log::debug!(
+                "Currency for retailer link {} changed during the check; leaving it",
+                pr.id
+            ),
+            Err(e) => log::warn!(
+                "Failed to auto-set currency for retailer link {}: {}",
+                pr.id,
+                e
+            ),
+        }
+    }
+
     /// Process the result of an availability check into a structured result
     pub fn process_check_result(
         check_result: Result<AvailabilityCheckModel, AppError>,
         previous_status: &Option<AvailabilityStatus>,
         daily_comparison: &DailyPriceComparison,
+        price_drop_threshold_percent: i32,
     ) -> CheckProcessingResult {
         match check_result {
-            Ok(check) if check.error_message.is_some() => Self::result_with_scraper_error(check),
-            Ok(check) => {
-                Self::result_from_successful_check(check, previous_status, daily_comparison)
+            Ok(check) if check.error_message.is_some() => {
+                Self::result_with_scraper_error(check, previous_status)
             }
+            Ok(check) => Self::result_from_successful_check(
+                check,
+                previous_status,
+                daily_comparison,
+                price_drop_threshold_percent,
+            ),
             Err(e) => Self::result_from_infrastructure_error(e),
         }
     }
 
-    /// Build result when scraper failed but a record was created
-    fn result_with_scraper_error(check: AvailabilityCheckModel) -> CheckProcessingResult {
+    /// Build result when scraper failed but a record was created.
+    ///
+    /// Only an ignored implausible price keeps a scraped status, so only then are
+    /// restocks and sell-outs reported.
+    fn result_with_scraper_error(
+        check: AvailabilityCheckModel,
+        previous_status: &Option<AvailabilityStatus>,
+    ) -> CheckProcessingResult {
+        let status = check.status_enum();
+        let error_kind = check.error_kind_enum();
+        let status_scraped = error_kind == Some(CheckErrorKind::ImplausiblePrice);
         CheckProcessingResult {
-            status: check.status_enum(),
+            is_back_in_stock: status_scraped && Self::is_back_in_stock(previous_status, &status),
+            is_sold_out: status_scraped && Self::is_sold_out(previous_status, &status),
+            status,
             price_minor_units: check.price_minor_units,
             price_currency: check.price_currency,
+            compare_at_minor_units: check.compare_at_minor_units,
             error: check.error_message,
-            is_back_in_stock: false,
+            error_kind,
+            confidence: None,
             is_price_drop: false,
         }
     }
@@ -321,21 +677,31 @@ impl AvailabilityService {
         check: AvailabilityCheckModel,
         previous_status: &Option<AvailabilityStatus>,
         daily_comparison: &DailyPriceComparison,
+        price_drop_threshold_percent: i32,
     ) -> CheckProcessingResult {
         let status = check.status_enum();
+        let confidence = check.confidence_enum();
         let is_back_in_stock = Self::is_back_in_stock(previous_status, &status);
-        let is_price_drop = Self::is_price_drop(
-            daily_comparison.yesterday_average_minor_units,
-            daily_comparison.today_average_minor_units,
-        );
+        let is_sold_out = Self::is_sold_out(previous_status, &status);
+        // An expired offer's price is likely stale/cached, so don't report it as a drop
+        let is_price_drop = !check.is_offer_expired()
+            && Self::is_price_drop(
+                daily_comparison.yesterday_average_minor_units,
+                daily_comparison.today_average_minor_units,
+                price_drop_threshold_percent,
+            );
 
         CheckProcessingResult {
             status,
             price_minor_units: check.price_minor_units,
             price_currency: check.price_currency,
+            compare_at_minor_units: check.compare_at_minor_units,
             error: None,
+            error_kind: None,
+            confidence,
             is_back_in_stock,
             is_price_drop,
+            is_sold_out,
         }
     }
 
@@ -345,9 +711,13 @@ impl AvailabilityService {
             status: AvailabilityStatus::Unknown,
             price_minor_units: None,
             price_currency: None,
+            compare_at_minor_units: None,
             error: Some(error.to_string()),
+            error_kind: Some(CheckErrorKind::from_error(&error)),
+            confidence: None,
             is_back_in_stock: false,
             is_price_drop: false,
+            is_sold_out: false,
         }
     }
 
@@ -368,6 +738,7 @@ impl AvailabilityService {
 
         // Step 2: Perform the availability check
         let check_result = Self::check_product(conn, product.id, config).await;
+        let check_id = check_result.as_ref().ok().map(|check| check.id);
 
         // Step 3: Get daily price comparison (includes the new check in today's average)
         let daily_comparison =
@@ -378,9 +749,14 @@ impl AvailabilityService {
                 Err(e) => return Self::build_context_error_result(product, e),
             };
 
-        // Step 4: Process the result
-        let result =
-            Self::process_check_result(check_result, &context.previous_status, &daily_comparison);
+        // Step 4: Process the result, reporting a restock once per in-stock streak
+        let mut result = Self::process_check_result(
+            check_result,
+            &context.previous_status,
+            &daily_comparison,
+            config.price_drop_threshold_percent,
+        );
+        Self::dedupe_bulk_restock(conn, product.id, check_id, &mut result).await;
 
         // Step 5: Build the bulk result
         let bulk_result =
@@ -413,6 +789,7 @@ impl AvailabilityService {
 
         // Step 2: Perform the check via product_retailer
         let check_result = Self::check_product_retailer(conn, product_retailer.id, config).await;
+        let check_id = check_result.as_ref().ok().map(|check| check.id);
 
         // Step 3: Get daily price comparison for this product_retailer
         let daily_comparison =
@@ -423,9 +800,14 @@ impl AvailabilityService {
                 Err(e) => return Self::build_context_error_result(product, e),
             };
 
-        // Step 4: Process result
-        let result =
-            Self::process_check_result(check_result, &context.previous_status, &daily_comparison);
+        // Step 4: Process result, reporting a restock once per in-stock streak
+        let mut result = Self::process_check_result(
+            check_result,
+            &context.previous_status,
+            &daily_comparison,
+            config.price_drop_threshold_percent,
+        );
+        Self::dedupe_bulk_restock(conn, product.id, check_id, &mut result).await;
 
         // Step 5: Build bulk result with retailer info
         let bulk_result =
@@ -435,21 +817,98 @@ impl AvailabilityService {
         (bulk_result, result)
     }
 
+    /// Apply the once-per-streak restock guard to a bulk check result.
+    ///
+    /// A restock already notified in this in-stock streak is no longer reported.
+    /// Otherwise it is recorded right away: the bulk summary reports every restock
+    /// it counts (as a notification, in a digest or in the results), and the next
+    /// check of another retailer must see it. The product is re-read because
+    /// earlier checks in the same run may have recorded a restock.
+    async fn dedupe_bulk_restock(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        check_id: Option<Uuid>,
+        result: &mut CheckProcessingResult,
+    ) {
+        let Some(check_id) = check_id.filter(|_| result.is_back_in_stock) else {
+            return;
+        };
+
+        match Self::record_new_restock(conn, product_id, check_id).await {
+            Ok(is_new) => result.is_back_in_stock = is_new,
+            Err(e) => log::warn!(
+                "Failed to apply restock guard for product {}: {}",
+                product_id,
+                e
+            ),
+        }
+    }
+
+    /// Record `check_id` as the product's restock unless one was already
+    /// notified in this in-stock streak. Returns whether it was recorded.
+    async fn record_new_restock(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        check_id: Uuid,
+    ) -> Result<bool, AppError> {
+        let product = ProductRepository::find_by_id(conn, product_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Product not found: {}", product_id)))?;
+        if Self::already_notified_for_streak(conn, &product).await? {
+            return Ok(false);
+        }
+        ProductRepository::record_restock_notification(conn, product_id, check_id, Utc::now())
+            .await?;
+        Ok(true)
+    }
+
     /// Build error result when context fetch fails
     pub fn build_context_error_result(
         product: &ProductModel,
         error: AppError,
     ) -> (BulkCheckResult, CheckProcessingResult) {
         let error_message = error.to_string();
+        let error_kind = CheckErrorKind::from_error(&error);
+        let result = CheckProcessingResult {
+            status: AvailabilityStatus::Unknown,
+            price_minor_units: None,
+            price_currency: None,
+            compare_at_minor_units: None,
+            error: Some(error_message.clone()),
+            error_kind: Some(error_kind),
+            confidence: None,
+            is_back_in_stock: false,
+            is_price_drop: false,
+            is_sold_out: false,
+        };
+        let bulk_result = BulkCheckResult::error_for_product(product, error_message, error_kind);
+        (bulk_result, result)
+    }
+
+    /// Build error result for a selected product ID that can't be checked
+    /// (malformed or not found), so it is reported without aborting the batch.
+    pub fn build_invalid_selection_result(
+        product_id: &str,
+        error_message: String,
+    ) -> (BulkCheckResult, CheckProcessingResult) {
         let result = CheckProcessingResult {
             status: AvailabilityStatus::Unknown,
             price_minor_units: None,
             price_currency: None,
+            compare_at_minor_units: None,
             error: Some(error_message.clone()),
+            error_kind: Some(CheckErrorKind::Validation),
+            confidence: None,
             is_back_in_stock: false,
             is_price_drop: false,
+            is_sold_out: false,
+        };
+        let bulk_result = BulkCheckResult {
+            product_id: product_id.to_string(),
+            error: Some(error_message),
+            error_kind: Some(CheckErrorKind::Validation),
+            ..Default::default()
         };
-        let bulk_result = BulkCheckResult::error_for_product(product, error_message);
         (bulk_result, result)
     }
 
@@ -472,21 +931,104 @@ impl AvailabilityService {
         AvailabilityCheckRepository::find_latest_for_product(conn, product_id).await
     }
 
-    /// Get the cheapest current price across all retailers for a product
+    /// Get the cheapest current price across all retailers for a product,
+    /// compared in the user's preferred currency where exchange rates allow
     pub async fn get_cheapest_current_price(
         conn: &DatabaseConnection,
         product_id: Uuid,
+        preferred_currency: &str,
     ) -> Result<Option<crate::repositories::CheapestPriceResult>, AppError> {
-        AvailabilityCheckRepository::find_cheapest_current_price(conn, product_id).await
+        AvailabilityCheckRepository::find_cheapest_current_price(
+            conn,
+            product_id,
+            Some(preferred_currency),
+        )
+        .await
+    }
+
+    /// Get the lowest and highest prices ever recorded for a product
+    pub async fn get_price_extremes(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<PriceExtremes, AppError> {
+        AvailabilityCheckRepository::get_price_extremes(conn, product_id).await
+    }
+
+    /// Get when a product was first seen in stock, if ever
+    pub async fn get_first_in_stock_at(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        AvailabilityCheckRepository::get_first_in_stock_at(conn, product_id).await
+    }
+
+    /// Get the availability status transitions for a product, oldest first
+    pub async fn get_status_transitions(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Vec<StatusTransition>, AppError> {
+        AvailabilityCheckRepository::get_status_transitions(conn, product_id).await
+    }
+
+    /// Get products whose status changed since `since`, with their latest check
+    pub async fn get_changes_since(
+        conn: &DatabaseConnection,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(ProductModel, AvailabilityCheckModel)>, AppError> {
+        AvailabilityCheckRepository::find_changed_since(conn, since).await
+    }
+
+    /// Get products whose price dropped since `since`, largest drop first
+    pub async fn get_recent_deals(
+        conn: &DatabaseConnection,
+        since: DateTime<Utc>,
+        limit: u64,
+        preferred_currency: &str,
+    ) -> Result<Vec<(ProductModel, PriceDrop)>, AppError> {
+        let drops = AvailabilityCheckRepository::find_recent_price_drops(
+            conn,
+            since,
+            limit,
+            preferred_currency,
+        )
+        .await?;
+
+        let mut deals = Vec::with_capacity(drops.len());
+        for drop in drops {
+            if let Some(product) = ProductRepository::find_by_id(conn, drop.product_id).await? {
+                deals.push((product, drop));
+            }
+        }
+        Ok(deals)
     }
 
     /// Get the availability check history for a product
+    ///
+    /// `from` and `to` optionally restrict the history to [from, to).
     pub async fn get_history(
         conn: &DatabaseConnection,
         product_id: Uuid,
         limit: Option<u64>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
     ) -> Result<Vec<AvailabilityCheckModel>, AppError> {
-        AvailabilityCheckRepository::find_all_for_product(conn, product_id, limit).await
+        AvailabilityCheckRepository::find_all_for_product(conn, product_id, limit, from, to).await
+    }
+
+    /// Get the average price per time bucket for a product within [from, to)
+    pub async fn get_price_series(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        bucket: Bucket,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, Option<i64>)>, AppError> {
+        if from >= to {
+            return Err(AppError::Validation(
+                "Price history range start must be before its end".to_string(),
+            ));
+        }
+        AvailabilityCheckRepository::get_price_series(conn, product_id, bucket, from, to).await
     }
 
     /// Check product availability and return notification data if applicable
@@ -494,80 +1036,208 @@ impl AvailabilityService {
     /// Encapsulates all business logic for:
     /// - Getting previous status
     /// - Checking availability
-    /// - Determining if notification should be sent (based on back-in-stock + settings)
+    /// - Determining if notification should be sent (based on back-in-stock/sold-out/
+    ///   target price, notification settings and quiet hours)
+    /// - Sending at most one back-in-stock notification per in-stock streak
     /// - Composing notification title/body
     pub async fn check_product_with_notification(
         conn: &DatabaseConnection,
         product_id: Uuid,
         enable_notifications: bool,
+        is_quiet_time: bool,
         config: &CheckConfig<'_>,
     ) -> Result<CheckResultWithNotification, AppError> {
-        // Step 1: Get previous status before checking
+        let product = ProductRepository::find_by_id(conn, product_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Product not found: {}", product_id)))?;
+
+        // Step 1: Get previous check before checking
         let previous_check = Self::get_latest(conn, product_id).await?;
-        let previous_status = previous_check.map(|c| c.status_enum());
+        let previous_status = previous_check.as_ref().map(|c| c.status_enum());
 
         // Step 2: Check retailers first, fall back to legacy product.url
         let retailers = ProductRetailerRepository::find_by_product_id(conn, product_id).await?;
 
-        let (check, any_back_in_stock) = if retailers.is_empty() {
+        // `restocked_check_id` is the check that came back in stock, if any
+        let (check, restocked_check_id, any_sold_out, any_target_reached) = if retailers.is_empty()
+        {
             // Legacy path: product has no retailer links, use product.url
             let check = Self::check_product(conn, product_id, config).await?;
-            let is_back = Self::is_back_in_stock(&previous_status, &check.status_enum());
-            (check, is_back)
+            let restocked =
+                Self::is_back_in_stock(&previous_status, &check.status_enum()).then_some(check.id);
+            let is_sold_out = Self::is_sold_out(&previous_status, &check.status_enum());
+            let is_target_reached =
+                Self::detect_target_price_crossing(conn, &product, previous_check.as_ref(), &check)
+                    .await;
+            (check, restocked, is_sold_out, is_target_reached)
         } else {
-            // Multi-retailer path: check all retailers, track back-in-stock per-retailer
+            // Multi-retailer path: check all retailers, track transitions per-retailer
             let mut last_check = None;
-            let mut back_in_stock = false;
+            let mut back_in_stock = None;
+            let mut sold_out = false;
+            let mut target_reached = false;
             for retailer in &retailers {
                 let retailer_previous =
                     AvailabilityCheckRepository::find_latest_for_product_retailer(
                         conn,
                         retailer.id,
                     )
-                    .await?
-                    .map(|c| c.status_enum());
+                    .await?;
+                let retailer_previous_status = retailer_previous.as_ref().map(|c| c.status_enum());
 
                 let result = Self::check_product_retailer(conn, retailer.id, config).await?;
 
-                if Self::is_back_in_stock(&retailer_previous, &result.status_enum()) {
-                    back_in_stock = true;
+                if Self::is_back_in_stock(&retailer_previous_status, &result.status_enum()) {
+                    back_in_stock = Some(result.id);
+                }
+                if Self::is_sold_out(&retailer_previous_status, &result.status_enum()) {
+                    sold_out = true;
+                }
+                if Self::detect_target_price_crossing(
+                    conn,
+                    &product,
+                    retailer_previous.as_ref(),
+                    &result,
+                )
+                .await
+                {
+                    target_reached = true;
                 }
                 last_check = Some(result);
             }
-            (last_check.expect("retailers is non-empty"), back_in_stock)
+            (
+                last_check.expect("retailers is non-empty"),
+                back_in_stock,
+                sold_out,
+                target_reached,
+            )
         };
 
         // Step 3: Get daily price comparison (includes the new check in today's average)
         let daily_comparison =
             Self::get_daily_price_comparison(conn, product_id, config.preferred_currency).await?;
 
-        // Step 4: Determine if back in stock
-        let is_back_in_stock = any_back_in_stock;
+        // Step 4: Determine if back in stock, once per in-stock streak
+        let is_back_in_stock = match restocked_check_id {
+            Some(_) => !Self::already_notified_for_streak(conn, &product).await?,
+            None => false,
+        };
 
         // Step 5: Build notification if applicable (using NotificationService)
         let notification = NotificationService::build_single_notification(
             conn,
             product_id,
             enable_notifications,
+            is_quiet_time,
             is_back_in_stock,
+            any_sold_out,
+            any_target_reached,
         )
         .await?;
 
+        // Remember the alert so the rest of this in-stock streak stays quiet
+        if let Some(check_id) =
+            restocked_check_id.filter(|_| is_back_in_stock && notification.is_some())
+        {
+            ProductRepository::record_restock_notification(conn, product_id, check_id, Utc::now())
+                .await?;
+        }
+
         Ok(CheckResultWithNotification {
             check,
             notification,
             daily_comparison,
         })
     }
+
+    /// Whether a back-in-stock notification was already sent since the product was
+    /// last seen out of stock.
+    ///
+    /// Guards against re-alerting when the previous status is re-read as not
+    /// available (e.g. a failed check or an app restart) in the middle of a streak.
+    async fn already_notified_for_streak(
+        conn: &DatabaseConnection,
+        product: &ProductModel,
+    ) -> Result<bool, AppError> {
+        let Some(last_notified_at) = product.last_notified_at else {
+            return Ok(false);
+        };
+        let out_of_stock_at =
+            AvailabilityCheckRepository::find_latest_out_of_stock_at(conn, product.id).await?;
+        Ok(match out_of_stock_at {
+            Some(at) => last_notified_at > at,
+            None => true,
+        })
+    }
+
+    /// Whether a new check crossed the product's target price.
+    ///
+    /// When the target is set in a different currency than the scraped price, it is
+    /// converted into the check's currency with the current exchange rate. If no rate
+    /// is available the crossing cannot be decided and no alert is raised.
+    async fn detect_target_price_crossing(
+        conn: &DatabaseConnection,
+        product: &ProductModel,
+        previous: Option<&AvailabilityCheckModel>,
+        check: &AvailabilityCheckModel,
+    ) -> bool {
+        let Some(target) = product.target_price_minor_units else {
+            return false;
+        };
+
+        let target = match (
+            product.target_price_currency.as_deref(),
+            check.price_currency.as_deref(),
+        ) {
+            (Some(target_currency), Some(check_currency)) => {
+                Self::normalize_price(conn, Some(target), Some(target_currency), check_currency)
+                    .await
+                    .minor_units
+            }
+            _ => Some(target),
+        };
+        let Some(target) = target else {
+            return false;
+        };
+
+        // A previous price in another currency is not comparable to the new one
+        let previous_price = previous
+            .filter(|p| p.price_currency == check.price_currency)
+            .and_then(|p| p.price_minor_units);
+
+        Self::is_target_price_reached(previous_price, check.price_minor_units, target)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::availability_check::Confidence;
     use crate::repositories::CreateCheckParams;
+    use crate::services::scraper::DEFAULT_MAX_JSON_LD_BLOCK_BYTES;
+    use crate::services::{FetchOptions, FetchTimeouts, ProductGroupStrategy};
     use crate::test_utils::{create_test_product, setup_availability_db};
 
-    /// Tests for get_latest and get_history methods
+    /// Plain HTTP only: no headless or manual verification fallback
+    fn test_fetch_options() -> FetchOptions {
+        FetchOptions {
+            enable_headless: false,
+            allow_manual_verification: false,
+            headless_profile_reuse: true,
+            session_cache_duration_days: 14,
+            timeouts: FetchTimeouts::default(),
+            force_headless_domains: Vec::new(),
+            respect_robots_txt: false,
+            rotate_user_agent: false,
+            product_group_strategy: ProductGroupStrategy::First,
+            headless_browser_path: None,
+            insecure_hosts: Vec::new(),
+            max_redirects: 10,
+            max_json_ld_block_bytes: DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+        }
+    }
+
+    /// Tests for get_latest and get_history methods
     mod history_tests {
         use super::*;
 
@@ -583,12 +1253,58 @@ mod tests {
             assert!(latest.is_none());
         }
 
+        #[tokio::test]
+        async fn test_get_price_series_rejects_inverted_range() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com").await;
+            let now = Utc::now();
+
+            let result = AvailabilityService::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Daily,
+                now,
+                now - chrono::Duration::days(7),
+            )
+            .await;
+
+            assert!(matches!(result, Err(AppError::Validation(_))));
+        }
+
+        #[tokio::test]
+        async fn test_get_price_series_returns_buckets() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com").await;
+            let now = Utc::now();
+
+            AvailabilityCheckRepository::create_with_timestamp(
+                &conn,
+                product_id,
+                Some(1000),
+                now - chrono::Duration::hours(1),
+            )
+            .await;
+
+            let series = AvailabilityService::get_price_series(
+                &conn,
+                product_id,
+                Bucket::Weekly,
+                now - chrono::Duration::days(7),
+                now,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(series.len(), 1);
+            assert_eq!(series[0].1, Some(1000));
+        }
+
         #[tokio::test]
         async fn test_get_history_empty() {
             let conn = setup_availability_db().await;
             let product_id = create_test_product(&conn, "https://example.com").await;
 
-            let history = AvailabilityService::get_history(&conn, product_id, None)
+            let history = AvailabilityService::get_history(&conn, product_id, None, None, None)
                 .await
                 .unwrap();
 
@@ -614,7 +1330,7 @@ mod tests {
                 .unwrap();
             }
 
-            let history = AvailabilityService::get_history(&conn, product_id, Some(3))
+            let history = AvailabilityService::get_history(&conn, product_id, Some(3), None, None)
                 .await
                 .unwrap();
 
@@ -644,7 +1360,7 @@ mod tests {
                 .unwrap();
             }
 
-            let history = AvailabilityService::get_history(&conn, product_id, None)
+            let history = AvailabilityService::get_history(&conn, product_id, None, None, None)
                 .await
                 .unwrap();
 
@@ -689,16 +1405,247 @@ mod tests {
             let fake_id = Uuid::new_v4();
 
             let config = CheckConfig {
-                enable_headless: false,
-                allow_manual_verification: false,
-                session_cache_duration_days: 14,
+                fetch: test_fetch_options(),
                 preferred_currency: "AUD",
+                price_drop_threshold_percent: 5,
+                min_plausible_price_percent: 10,
             };
             let result = AvailabilityService::check_product(&conn, fake_id, &config).await;
 
             assert!(result.is_err());
             assert!(matches!(result, Err(AppError::NotFound(_))));
         }
+
+        #[tokio::test]
+        async fn test_check_product_stores_error_kind() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "ftp://example.com/item").await;
+
+            let config = CheckConfig {
+                fetch: test_fetch_options(),
+                preferred_currency: "AUD",
+                price_drop_threshold_percent: 5,
+                min_plausible_price_percent: 10,
+            };
+            let check = AvailabilityService::check_product(&conn, product_id, &config)
+                .await
+                .unwrap();
+
+            assert!(check.error_message.is_some());
+            assert_eq!(check.error_kind.as_deref(), Some("validation"));
+
+            let result = AvailabilityService::process_check_result(
+                Ok(check),
+                &None,
+                &DailyPriceComparison::default(),
+                5,
+            );
+            assert_eq!(result.error_kind, Some(CheckErrorKind::Validation));
+        }
+
+        async fn create_check_valid_until(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            price_valid_until: chrono::NaiveDate,
+        ) -> AvailabilityCheckModel {
+            AvailabilityCheckRepository::create(
+                conn,
+                Uuid::new_v4(),
+                product_id,
+                CreateCheckParams {
+                    status: AvailabilityStatus::InStock,
+                    price_minor_units: Some(8000),
+                    price_currency: Some("USD".to_string()),
+                    price_valid_until: Some(price_valid_until),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+        }
+
+        fn dropped_comparison() -> DailyPriceComparison {
+            DailyPriceComparison {
+                today_average_minor_units: Some(8000),
+                yesterday_average_minor_units: Some(10000),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_process_check_result_skips_price_drop_for_expired_offer() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com").await;
+            let yesterday = chrono::Utc::now().date_naive() - chrono::Duration::days(1);
+            let check = create_check_valid_until(&conn, product_id, yesterday).await;
+
+            let result = AvailabilityService::process_check_result(
+                Ok(check),
+                &Some(AvailabilityStatus::InStock),
+                &dropped_comparison(),
+                5,
+            );
+
+            assert!(!result.is_price_drop);
+            assert_eq!(result.price_minor_units, Some(8000));
+        }
+
+        #[tokio::test]
+        async fn test_process_check_result_price_drop_for_valid_offer() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com").await;
+            let next_month = chrono::Utc::now().date_naive() + chrono::Duration::days(30);
+            let check = create_check_valid_until(&conn, product_id, next_month).await;
+
+            let result = AvailabilityService::process_check_result(
+                Ok(check),
+                &Some(AvailabilityStatus::InStock),
+                &dropped_comparison(),
+                5,
+            );
+
+            assert!(result.is_price_drop);
+        }
+    }
+
+    mod implausible_price_tests {
+        use super::*;
+
+        /// Two checks at $500.00 over the last couple of days
+        async fn seed_history(conn: &DatabaseConnection, product_id: Uuid) {
+            for days_ago in [1, 2] {
+                AvailabilityCheckRepository::create_with_timestamp(
+                    conn,
+                    product_id,
+                    Some(50000),
+                    Utc::now() - chrono::Duration::days(days_ago),
+                )
+                .await;
+            }
+        }
+
+        fn scraped(price_minor_units: i64, currency: &str) -> CreateCheckParams {
+            CreateCheckParams {
+                status: AvailabilityStatus::InStock,
+                price_minor_units: Some(price_minor_units),
+                price_currency: Some(currency.to_string()),
+                normalized_price_minor_units: Some(price_minor_units),
+                normalized_currency: Some(currency.to_string()),
+                ..Default::default()
+            }
+        }
+
+        async fn guard(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            price_minor_units: i64,
+            currency: &str,
+        ) -> CreateCheckParams {
+            let mut params = scraped(price_minor_units, currency);
+            AvailabilityService::reject_implausible_price(conn, &mut params, product_id, 10).await;
+            params
+        }
+
+        #[tokio::test]
+        async fn test_zero_price_is_ignored() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com").await;
+            seed_history(&conn, product_id).await;
+
+            let params = guard(&conn, product_id, 0, "USD").await;
+
+            assert_eq!(params.error_kind, Some(CheckErrorKind::ImplausiblePrice));
+            assert_eq!(
+                params.error_message.as_deref(),
+                Some("Ignored implausible price $0.00 (below 10% of the recent average $500.00)")
+            );
+            assert_eq!(params.price_minor_units, None);
+            assert_eq!(params.normalized_price_minor_units, None);
+            assert_eq!(params.status, AvailabilityStatus::InStock);
+        }
+
+        #[tokio::test]
+        async fn test_one_dollar_on_five_hundred_is_ignored() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com").await;
+            seed_history(&conn, product_id).await;
+
+            let params = guard(&conn, product_id, 100, "usd").await;
+
+            assert_eq!(params.error_kind, Some(CheckErrorKind::ImplausiblePrice));
+            assert_eq!(params.price_minor_units, None);
+
+            // Stored as a failed check, so it can't be reported as a price drop
+            let check =
+                AvailabilityCheckRepository::create(&conn, Uuid::new_v4(), product_id, params)
+                    .await
+                    .unwrap();
+            let result = AvailabilityService::process_check_result(
+                Ok(check),
+                &Some(AvailabilityStatus::InStock),
+                &DailyPriceComparison {
+                    today_average_minor_units: Some(50000),
+                    yesterday_average_minor_units: Some(50000),
+                },
+                5,
+            );
+            assert!(!result.is_price_drop);
+            assert_eq!(result.error_kind, Some(CheckErrorKind::ImplausiblePrice));
+        }
+
+        #[tokio::test]
+        async fn test_genuine_sale_is_kept() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com").await;
+            seed_history(&conn, product_id).await;
+
+            let params = guard(&conn, product_id, 15000, "USD").await;
+
+            assert!(params.error_kind.is_none());
+            assert_eq!(params.price_minor_units, Some(15000));
+        }
+
+        #[tokio::test]
+        async fn test_without_recent_prices_in_currency_is_kept() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com").await;
+
+            assert_eq!(
+                guard(&conn, product_id, 0, "USD").await.price_minor_units,
+                Some(0)
+            );
+
+            // History in another currency isn't comparable
+            seed_history(&conn, product_id).await;
+            assert_eq!(
+                guard(&conn, product_id, 100, "JPY").await.price_minor_units,
+                Some(100)
+            );
+
+            // Prices older than the window don't count
+            let old = create_test_product(&conn, "https://example.com/old").await;
+            AvailabilityCheckRepository::create_with_timestamp(
+                &conn,
+                old,
+                Some(50000),
+                Utc::now()
+                    - chrono::Duration::days(AvailabilityService::PLAUSIBLE_PRICE_WINDOW_DAYS + 1),
+            )
+            .await;
+            assert_eq!(guard(&conn, old, 0, "USD").await.price_minor_units, Some(0));
+        }
+
+        #[tokio::test]
+        async fn test_zero_percent_turns_guard_off() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com").await;
+            seed_history(&conn, product_id).await;
+
+            let mut params = scraped(0, "USD");
+            AvailabilityService::reject_implausible_price(&conn, &mut params, product_id, 0).await;
+
+            assert!(params.error_kind.is_none());
+            assert_eq!(params.price_minor_units, Some(0));
+        }
     }
 
     /// Tests for check_product_with_notification retailer routing
@@ -706,7 +1653,7 @@ mod tests {
         use super::*;
         use crate::repositories::{
             CreateProductRepoParams, CreateProductRetailerParams, ProductRetailerRepository,
-            RetailerRepository,
+            ProductUpdateInput, RetailerRepository,
         };
 
         #[tokio::test]
@@ -723,6 +1670,9 @@ mod tests {
                     url: None,
                     description: None,
                     notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units: None,
+                    target_price_currency: None,
                 },
             )
             .await
@@ -750,13 +1700,13 @@ mod tests {
             // The scraping will fail (no network in tests), but the error is caught and
             // stored as a check result, so this should return Ok.
             let config = CheckConfig {
-                enable_headless: false,
-                allow_manual_verification: false,
-                session_cache_duration_days: 14,
+                fetch: test_fetch_options(),
                 preferred_currency: "AUD",
+                price_drop_threshold_percent: 5,
+                min_plausible_price_percent: 10,
             };
             let result = AvailabilityService::check_product_with_notification(
-                &conn, product_id, false, &config,
+                &conn, product_id, false, false, &config,
             )
             .await;
 
@@ -787,6 +1737,9 @@ mod tests {
                     url: None,
                     description: None,
                     notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units: None,
+                    target_price_currency: None,
                 },
             )
             .await
@@ -830,13 +1783,13 @@ mod tests {
 
             // Run the check — scraping fails for both, but both should get check records
             let config = CheckConfig {
-                enable_headless: false,
-                allow_manual_verification: false,
-                session_cache_duration_days: 14,
+                fetch: test_fetch_options(),
                 preferred_currency: "AUD",
+                price_drop_threshold_percent: 5,
+                min_plausible_price_percent: 10,
             };
             let result = AvailabilityService::check_product_with_notification(
-                &conn, product_id, false, &config,
+                &conn, product_id, false, false, &config,
             )
             .await;
 
@@ -881,6 +1834,9 @@ mod tests {
                     url: None,
                     description: None,
                     notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units: None,
+                    target_price_currency: None,
                 },
             )
             .await
@@ -952,13 +1908,13 @@ mod tests {
 
             // Run the check — scraping fails (Unknown status), neither retailer transitions to InStock
             let config = CheckConfig {
-                enable_headless: false,
-                allow_manual_verification: false,
-                session_cache_duration_days: 14,
+                fetch: test_fetch_options(),
                 preferred_currency: "AUD",
+                price_drop_threshold_percent: 5,
+                min_plausible_price_percent: 10,
             };
             let result = AvailabilityService::check_product_with_notification(
-                &conn, product_id, true, &config,
+                &conn, product_id, true, false, &config,
             )
             .await;
 
@@ -977,6 +1933,77 @@ mod tests {
             );
         }
 
+        #[tokio::test]
+        async fn test_check_product_with_notification_checks_paused_product() {
+            let conn = setup_availability_db().await;
+
+            let product = ProductRepository::create(
+                &conn,
+                Uuid::new_v4(),
+                CreateProductRepoParams {
+                    name: "Discontinued Product".to_string(),
+                    url: None,
+                    description: None,
+                    notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units: None,
+                    target_price_currency: None,
+                },
+            )
+            .await
+            .unwrap();
+            let product = ProductRepository::update(
+                &conn,
+                product,
+                ProductUpdateInput {
+                    is_paused: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+            let retailer = RetailerRepository::find_or_create_by_domain(&conn, "example.com")
+                .await
+                .unwrap();
+            ProductRetailerRepository::create(
+                &conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id: product.id,
+                    url: "https://example.com/product".to_string(),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap();
+
+            // Excluded from bulk checks...
+            let bulk = ProductRetailerRepository::find_all_with_product(&conn)
+                .await
+                .unwrap();
+            assert!(bulk.is_empty());
+
+            // ...but a manual check still runs and records a result
+            let config = CheckConfig {
+                fetch: test_fetch_options(),
+                preferred_currency: "AUD",
+                price_drop_threshold_percent: 5,
+                min_plausible_price_percent: 10,
+            };
+            let result = AvailabilityService::check_product_with_notification(
+                &conn, product.id, false, false, &config,
+            )
+            .await;
+            assert!(result.is_ok());
+
+            let latest = AvailabilityService::get_latest(&conn, product.id)
+                .await
+                .unwrap();
+            assert!(latest.is_some());
+        }
+
         #[tokio::test]
         async fn test_check_product_with_notification_no_url_no_retailers_fails() {
             let conn = setup_availability_db().await;
@@ -991,6 +2018,9 @@ mod tests {
                     url: None,
                     description: None,
                     notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units: None,
+                    target_price_currency: None,
                 },
             )
             .await
@@ -998,13 +2028,13 @@ mod tests {
 
             // Should fail with the legacy "Product has no URL set" validation error
             let config = CheckConfig {
-                enable_headless: false,
-                allow_manual_verification: false,
-                session_cache_duration_days: 14,
+                fetch: test_fetch_options(),
                 preferred_currency: "AUD",
+                price_drop_threshold_percent: 5,
+                min_plausible_price_percent: 10,
             };
             let result = AvailabilityService::check_product_with_notification(
-                &conn, product_id, false, &config,
+                &conn, product_id, false, false, &config,
             )
             .await;
 
@@ -1017,280 +2047,1557 @@ mod tests {
         }
     }
 
-    /// Tests for auto_set_product_currency method
-    mod auto_set_currency_tests {
+    /// Tests for sending one back-in-stock notification per in-stock streak
+    mod restock_dedupe_tests {
         use super::*;
-        use crate::repositories::{CreateProductRepoParams, ProductRepository};
-        use crate::test_utils::setup_availability_db;
+        use crate::services::FetchTimeouts;
+        use crate::test_utils::{MockResponse, MockServer};
+        use product_stalker_core::entities::app_setting::Entity as AppSettingEntity;
+        use product_stalker_core::entities::verified_session::Entity as VerifiedSessionEntity;
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Schema};
+
+        const IN_STOCK_PAGE: &str = r#"<html><head>
+            <script type="application/ld+json">
+            {"@type": "Product", "name": "Widget",
+             "offers": {"@type": "Offer", "price": "19.99", "priceCurrency": "USD",
+                        "availability": "https://schema.org/InStock"}}
+            </script></head><body></body></html>"#;
+
+        /// Availability tables plus the settings/session tables a real fetch reads
+        pub(super) async fn setup_fetch_db() -> DatabaseConnection {
+            let conn = setup_availability_db().await;
+            let schema = Schema::new(DatabaseBackend::Sqlite);
+            for stmt in [
+                schema.create_table_from_entity(AppSettingEntity),
+                schema.create_table_from_entity(VerifiedSessionEntity),
+            ] {
+                conn.execute(conn.get_database_backend().build(&stmt))
+                    .await
+                    .unwrap();
+            }
+            conn
+        }
 
-        async fn create_product_with_url(
+        /// Serve the in-stock page to every request
+        pub(super) async fn in_stock_server() -> MockServer {
+            MockServer::serving(MockResponse::html(IN_STOCK_PAGE)).await
+        }
+
+        pub(super) fn config() -> CheckConfig<'static> {
+            CheckConfig {
+                fetch: FetchOptions {
+                    timeouts: FetchTimeouts {
+                        min_host_interval_ms: 0,
+                        ..FetchTimeouts::default()
+                    },
+                    ..test_fetch_options()
+                },
+                preferred_currency: "USD",
+                price_drop_threshold_percent: 5,
+                min_plausible_price_percent: 10,
+            }
+        }
+
+        async fn seed_check(
             conn: &DatabaseConnection,
-            name: &str,
-            url: &str,
-        ) -> ProductModel {
-            let id = Uuid::new_v4();
-            ProductRepository::create(
+            product_id: Uuid,
+            params: CreateCheckParams,
+        ) {
+            AvailabilityCheckRepository::create(conn, Uuid::new_v4(), product_id, params)
+                .await
+                .unwrap();
+        }
+
+        async fn seed_status(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            status: AvailabilityStatus,
+        ) {
+            seed_check(
                 conn,
-                id,
-                CreateProductRepoParams {
-                    name: name.to_string(),
-                    url: Some(url.to_string()),
-                    description: None,
-                    notes: None,
+                product_id,
+                CreateCheckParams {
+                    status,
+                    ..Default::default()
                 },
             )
-            .await
-            .unwrap();
-            ProductRepository::find_by_id(conn, id)
-                .await
-                .unwrap()
-                .unwrap()
+            .await;
         }
 
-        #[tokio::test]
-        async fn test_sets_currency_on_first_check() {
-            let conn = setup_availability_db().await;
-            let product = create_product_with_url(
-                &conn,
-                "Test Product",
-                "https://example.com/en-au/products/test",
+        /// A failed check mid-streak reads back as Unknown, like after a restart
+        async fn seed_failed_check(conn: &DatabaseConnection, product_id: Uuid) {
+            seed_check(
+                conn,
+                product_id,
+                CreateCheckParams {
+                    error_message: Some("Network error: connection reset".to_string()),
+                    ..Default::default()
+                },
             )
             .await;
+        }
 
-            assert_eq!(product.currency, None);
-
-            AvailabilityService::auto_set_product_currency(&conn, &product, Some("AUD"), None)
-                .await;
+        async fn notifies(conn: &DatabaseConnection, product_id: Uuid) -> bool {
+            AvailabilityService::check_product_with_notification(
+                conn,
+                product_id,
+                true,
+                false,
+                &config(),
+            )
+            .await
+            .unwrap()
+            .notification
+            .is_some()
+        }
 
-            let updated = ProductRepository::find_by_id(&conn, product.id)
+        #[tokio::test]
+        async fn test_repeated_in_stock_checks_notify_once() {
+            let conn = setup_fetch_db().await;
+            let server = in_stock_server().await;
+            let url = server.url("/widget");
+            let product_id = create_test_product(&conn, &url).await;
+            seed_status(&conn, product_id, AvailabilityStatus::OutOfStock).await;
+
+            assert!(notifies(&conn, product_id).await);
+            let product = ProductRepository::find_by_id(&conn, product_id)
                 .await
                 .unwrap()
                 .unwrap();
-            assert_eq!(updated.currency, Some("AUD".to_string()));
+            let latest = AvailabilityService::get_latest(&conn, product_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(product.last_notified_check_id, Some(latest.id));
+            assert!(product.last_notified_at.is_some());
+
+            // Staying in stock, even across failed checks, doesn't alert again
+            assert!(!notifies(&conn, product_id).await);
+            seed_failed_check(&conn, product_id).await;
+            assert!(!notifies(&conn, product_id).await);
+            seed_status(&conn, product_id, AvailabilityStatus::Unknown).await;
+            assert!(!notifies(&conn, product_id).await);
+        }
+
+        #[tokio::test]
+        async fn test_new_streak_after_out_of_stock_notifies_again() {
+            let conn = setup_fetch_db().await;
+            let server = in_stock_server().await;
+            let url = server.url("/widget");
+            let product_id = create_test_product(&conn, &url).await;
+
+            seed_status(&conn, product_id, AvailabilityStatus::OutOfStock).await;
+            assert!(notifies(&conn, product_id).await);
+
+            seed_status(&conn, product_id, AvailabilityStatus::OutOfStock).await;
+            assert!(notifies(&conn, product_id).await);
+        }
+
+        #[tokio::test]
+        async fn test_suppressed_notification_is_not_recorded() {
+            let conn = setup_fetch_db().await;
+            let server = in_stock_server().await;
+            let url = server.url("/widget");
+            let product_id = create_test_product(&conn, &url).await;
+            seed_status(&conn, product_id, AvailabilityStatus::OutOfStock).await;
+
+            // Notifications disabled: nothing sent, so nothing to dedupe against
+            AvailabilityService::check_product_with_notification(
+                &conn,
+                product_id,
+                false,
+                false,
+                &config(),
+            )
+            .await
+            .unwrap();
+
+            let product = ProductRepository::find_by_id(&conn, product_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(product.last_notified_check_id, None);
+            assert_eq!(product.last_notified_at, None);
+        }
+    }
+
+    /// Tests for the once-per-streak restock guard in bulk checks
+    mod bulk_restock_dedupe_tests {
+        use super::restock_dedupe_tests::{config, in_stock_server, setup_fetch_db};
+        use super::*;
+        use crate::repositories::{
+            CreateProductRetailerParams, ProductRetailerRepository, RetailerRepository,
+        };
+        use crate::test_utils::{MockResponse, MockServer};
+
+        async fn seed_status(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            product_retailer_id: Option<Uuid>,
+            status: AvailabilityStatus,
+        ) {
+            AvailabilityCheckRepository::create(
+                conn,
+                Uuid::new_v4(),
+                product_id,
+                CreateCheckParams {
+                    product_retailer_id,
+                    status,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        async fn find_product(conn: &DatabaseConnection, product_id: Uuid) -> ProductModel {
+            ProductRepository::find_by_id(conn, product_id)
+                .await
+                .unwrap()
+                .unwrap()
+        }
+
+        async fn bulk_restocked(conn: &DatabaseConnection, product_id: Uuid) -> bool {
+            let product = find_product(conn, product_id).await;
+            let (bulk_result, result) =
+                AvailabilityService::check_single_product(conn, &product, &config()).await;
+            assert_eq!(bulk_result.is_back_in_stock, result.is_back_in_stock);
+            result.is_back_in_stock
+        }
+
+        #[tokio::test]
+        async fn test_bulk_check_reports_restock_with_implausible_price() {
+            let conn = setup_fetch_db().await;
+            let server = MockServer::serving(MockResponse::html(
+                r#"<script type="application/ld+json">
+                {"@type": "Product", "name": "Widget",
+                 "offers": {"@type": "Offer", "price": "1.00", "priceCurrency": "USD",
+                            "availability": "https://schema.org/InStock"}}
+                </script>"#,
+            ))
+            .await;
+            let product_id = create_test_product(&conn, &server.url("/widget")).await;
+            for days_ago in [1, 2] {
+                AvailabilityCheckRepository::create_with_timestamp(
+                    &conn,
+                    product_id,
+                    Some(50000),
+                    Utc::now() - chrono::Duration::days(days_ago),
+                )
+                .await;
+            }
+            seed_status(&conn, product_id, None, AvailabilityStatus::OutOfStock).await;
+
+            let product = find_product(&conn, product_id).await;
+            let (bulk_result, result) =
+                AvailabilityService::check_single_product(&conn, &product, &config()).await;
+
+            assert_eq!(result.error_kind, Some(CheckErrorKind::ImplausiblePrice));
+            assert_eq!(result.price_minor_units, None);
+            assert!(result.is_back_in_stock);
+            assert!(bulk_result.is_back_in_stock);
+            assert!(!result.is_price_drop);
+        }
+
+        #[tokio::test]
+        async fn test_bulk_check_reports_restock_once_per_streak() {
+            let conn = setup_fetch_db().await;
+            let server = in_stock_server().await;
+            let product_id = create_test_product(&conn, &server.url("/widget")).await;
+            seed_status(&conn, product_id, None, AvailabilityStatus::OutOfStock).await;
+
+            assert!(bulk_restocked(&conn, product_id).await);
+            let latest = AvailabilityService::get_latest(&conn, product_id)
+                .await
+                .unwrap()
+                .unwrap();
+            let product = find_product(&conn, product_id).await;
+            assert_eq!(product.last_notified_check_id, Some(latest.id));
+
+            // An Unknown check mid-streak doesn't make the next in-stock check a restock
+            seed_status(&conn, product_id, None, AvailabilityStatus::Unknown).await;
+            assert!(!bulk_restocked(&conn, product_id).await);
+
+            // A new streak after going out of stock is reported again
+            seed_status(&conn, product_id, None, AvailabilityStatus::OutOfStock).await;
+            assert!(bulk_restocked(&conn, product_id).await);
+        }
+
+        #[tokio::test]
+        async fn test_bulk_retailer_check_skips_restock_already_notified() {
+            let conn = setup_fetch_db().await;
+            let server = in_stock_server().await;
+            let product_id = create_test_product(&conn, &server.url("/widget")).await;
+            let retailer = RetailerRepository::find_or_create_by_domain(&conn, "127.0.0.1")
+                .await
+                .unwrap();
+            let link = ProductRetailerRepository::create(
+                &conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: server.url("/widget"),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap();
+            seed_status(
+                &conn,
+                product_id,
+                Some(link.id),
+                AvailabilityStatus::OutOfStock,
+            )
+            .await;
+
+            // A single-product check already notified this streak
+            let notified = AvailabilityService::check_product_with_notification(
+                &conn,
+                product_id,
+                true,
+                false,
+                &config(),
+            )
+            .await
+            .unwrap();
+            assert!(notified.notification.is_some());
+
+            seed_status(
+                &conn,
+                product_id,
+                Some(link.id),
+                AvailabilityStatus::Unknown,
+            )
+            .await;
+            let product = find_product(&conn, product_id).await;
+            let (_, result) = AvailabilityService::check_single_product_retailer(
+                &conn,
+                &product,
+                &link,
+                &config(),
+            )
+            .await;
+            assert_eq!(result.status, AvailabilityStatus::InStock);
+            assert!(!result.is_back_in_stock);
+        }
+    }
+
+    mod track_price_tests {
+        use super::restock_dedupe_tests::{config, in_stock_server, setup_fetch_db};
+        use super::*;
+        use crate::repositories::ProductUpdateInput;
+
+        #[tokio::test]
+        async fn test_stock_only_product_records_no_price() {
+            let conn = setup_fetch_db().await;
+            let server = in_stock_server().await;
+            let url = server.url("/widget");
+            let product_id = create_test_product(&conn, &url).await;
+            let product = ProductRepository::find_by_id(&conn, product_id)
+                .await
+                .unwrap()
+                .unwrap();
+            ProductRepository::update(
+                &conn,
+                product,
+                ProductUpdateInput {
+                    track_price: Some(false),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+            let check = AvailabilityService::check_product(&conn, product_id, &config())
+                .await
+                .unwrap();
+
+            assert_eq!(check.status, AvailabilityStatus::InStock.as_str());
+            assert_eq!(check.price_minor_units, None);
+            assert_eq!(check.price_currency, None);
+            assert_eq!(check.normalized_price_minor_units, None);
+            let product = ProductRepository::find_by_id(&conn, product_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(product.currency, None);
+        }
+
+        #[tokio::test]
+        async fn test_price_tracking_product_records_price() {
+            let conn = setup_fetch_db().await;
+            let server = in_stock_server().await;
+            let url = server.url("/widget");
+            let product_id = create_test_product(&conn, &url).await;
+
+            let check = AvailabilityService::check_product(&conn, product_id, &config())
+                .await
+                .unwrap();
+
+            assert_eq!(check.price_minor_units, Some(1999));
+            assert_eq!(check.price_currency, Some("USD".to_string()));
+        }
+    }
+
+    /// Tests for detect_target_price_crossing method
+    mod target_price_crossing_tests {
+        use super::*;
+        use crate::repositories::{CreateProductRepoParams, ProductRepository};
+        use crate::test_utils::setup_availability_db_with_exchange_rates;
+        use product_stalker_core::repositories::ExchangeRateRepository;
+
+        async fn create_product_with_target(
+            conn: &DatabaseConnection,
+            target_price_minor_units: Option<i64>,
+            target_price_currency: Option<&str>,
+        ) -> ProductModel {
+            ProductRepository::create(
+                conn,
+                Uuid::new_v4(),
+                CreateProductRepoParams {
+                    name: "Target Product".to_string(),
+                    url: Some("https://example.com/product".to_string()),
+                    description: None,
+                    notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units,
+                    target_price_currency: target_price_currency.map(str::to_string),
+                },
+            )
+            .await
+            .unwrap()
+        }
+
+        async fn create_check(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            price_minor_units: i64,
+            currency: &str,
+        ) -> AvailabilityCheckModel {
+            AvailabilityCheckRepository::create(
+                conn,
+                Uuid::new_v4(),
+                product_id,
+                CreateCheckParams {
+                    status: AvailabilityStatus::InStock,
+                    price_minor_units: Some(price_minor_units),
+                    price_currency: Some(currency.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_no_target_never_crosses() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product = create_product_with_target(&conn, None, None).await;
+            let previous = create_check(&conn, product.id, 12000, "USD").await;
+            let check = create_check(&conn, product.id, 100, "USD").await;
+
+            assert!(
+                !AvailabilityService::detect_target_price_crossing(
+                    &conn,
+                    &product,
+                    Some(&previous),
+                    &check
+                )
+                .await
+            );
+        }
+
+        #[tokio::test]
+        async fn test_same_currency_crossing() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product = create_product_with_target(&conn, Some(10000), Some("USD")).await;
+            let previous = create_check(&conn, product.id, 12000, "USD").await;
+            let check = create_check(&conn, product.id, 9500, "USD").await;
+
+            assert!(
+                AvailabilityService::detect_target_price_crossing(
+                    &conn,
+                    &product,
+                    Some(&previous),
+                    &check
+                )
+                .await
+            );
+            // Staying below the target does not alert again
+            let next = create_check(&conn, product.id, 9000, "USD").await;
+            assert!(
+                !AvailabilityService::detect_target_price_crossing(
+                    &conn,
+                    &product,
+                    Some(&check),
+                    &next
+                )
+                .await
+            );
+        }
+
+        #[tokio::test]
+        async fn test_target_without_currency_uses_scraped_currency() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product = create_product_with_target(&conn, Some(10000), None).await;
+            let previous = create_check(&conn, product.id, 12000, "AUD").await;
+            let check = create_check(&conn, product.id, 10000, "AUD").await;
+
+            assert!(
+                AvailabilityService::detect_target_price_crossing(
+                    &conn,
+                    &product,
+                    Some(&previous),
+                    &check
+                )
+                .await
+            );
+        }
+
+        #[tokio::test]
+        async fn test_converts_target_to_scraped_currency() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            // Target of USD 100.00 is AUD 150.00 at this rate
+            ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+                .await
+                .unwrap();
+            let product = create_product_with_target(&conn, Some(10000), Some("USD")).await;
+            let previous = create_check(&conn, product.id, 16000, "AUD").await;
+
+            let above = create_check(&conn, product.id, 15500, "AUD").await;
+            assert!(
+                !AvailabilityService::detect_target_price_crossing(
+                    &conn,
+                    &product,
+                    Some(&previous),
+                    &above
+                )
+                .await
+            );
+
+            let below = create_check(&conn, product.id, 14900, "AUD").await;
+            assert!(
+                AvailabilityService::detect_target_price_crossing(
+                    &conn,
+                    &product,
+                    Some(&above),
+                    &below
+                )
+                .await
+            );
+        }
+
+        #[tokio::test]
+        async fn test_missing_exchange_rate_does_not_alert() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product = create_product_with_target(&conn, Some(10000), Some("USD")).await;
+            let previous = create_check(&conn, product.id, 16000, "EUR").await;
+            let check = create_check(&conn, product.id, 100, "EUR").await;
+
+            assert!(
+                !AvailabilityService::detect_target_price_crossing(
+                    &conn,
+                    &product,
+                    Some(&previous),
+                    &check
+                )
+                .await
+            );
+        }
+    }
+
+    /// Tests for normalize_price (bulk-check currency conversion)
+    mod normalize_price_tests {
+        use super::*;
+        use crate::test_utils::setup_availability_db_with_exchange_rates;
+        use product_stalker_core::repositories::ExchangeRateRepository;
+        use product_stalker_core::services::ExchangeRateService;
+
+        #[tokio::test]
+        async fn test_normalize_price_uses_api_rate() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+                .await
+                .unwrap();
+
+            let normalized =
+                AvailabilityService::normalize_price(&conn, Some(1000), Some("USD"), "AUD").await;
+            assert_eq!(normalized.minor_units, Some(1500));
+            assert_eq!(normalized.currency, Some("AUD".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_normalize_price_prefers_manual_rate() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+                .await
+                .unwrap();
+            ExchangeRateService::set_manual_rate(&conn, "USD", "AUD", 1.6)
+                .await
+                .unwrap();
+            // A later refresh must not replace the pinned rate
+            ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.4, "api")
+                .await
+                .unwrap();
+
+            let normalized =
+                AvailabilityService::normalize_price(&conn, Some(1000), Some("USD"), "AUD").await;
+            assert_eq!(normalized.minor_units, Some(1600));
+            assert_eq!(normalized.currency, Some("AUD".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_normalize_price_missing_rate() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+
+            let normalized =
+                AvailabilityService::normalize_price(&conn, Some(1000), Some("USD"), "AUD").await;
+            assert_eq!(normalized.minor_units, None);
+            assert_eq!(normalized.currency, None);
+        }
+    }
+
+    /// Tests for auto_set_product_currency method
+    mod auto_set_currency_tests {
+        use super::*;
+        use crate::repositories::{CreateProductRepoParams, ProductRepository};
+        use crate::test_utils::setup_availability_db;
+
+        async fn create_product_with_url(
+            conn: &DatabaseConnection,
+            name: &str,
+            url: &str,
+        ) -> ProductModel {
+            let id = Uuid::new_v4();
+            ProductRepository::create(
+                conn,
+                id,
+                CreateProductRepoParams {
+                    name: name.to_string(),
+                    url: Some(url.to_string()),
+                    description: None,
+                    notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units: None,
+                    target_price_currency: None,
+                },
+            )
+            .await
+            .unwrap();
+            ProductRepository::find_by_id(conn, id)
+                .await
+                .unwrap()
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_sets_currency_on_first_check() {
+            let conn = setup_availability_db().await;
+            let product = create_product_with_url(
+                &conn,
+                "Test Product",
+                "https://example.com/en-au/products/test",
+            )
+            .await;
+
+            assert_eq!(product.currency, None);
+
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product,
+                Some("AUD"),
+                None,
+                false,
+            )
+            .await;
+
+            let updated = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.currency, Some("AUD".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_keeps_matching_currency() {
+            let conn = setup_availability_db().await;
+            let product = create_product_with_url(
+                &conn,
+                "Test Product",
+                "https://example.com/en-au/products/test",
+            )
+            .await;
+
+            // Set initial currency
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product,
+                Some("AUD"),
+                None,
+                false,
+            )
+            .await;
+
+            let product = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+
+            // Try to set the same currency again
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product,
+                Some("AUD"),
+                None,
+                false,
+            )
+            .await;
+
+            let updated = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.currency, Some("AUD".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_corrects_wrong_currency_when_path_locale_present() {
+            let conn = setup_availability_db().await;
+
+            // Create product with /en-au/ path
+            let product = create_product_with_url(
+                &conn,
+                "Reyllen Backpack",
+                "https://reyllen.com/en-au/products/backpack",
+            )
+            .await;
+
+            // Simulate old scraper setting wrong currency (GBP instead of AUD)
+            let update = crate::repositories::ProductUpdateInput {
+                currency: Some(Some("GBP".to_string())),
+                ..Default::default()
+            };
+            ProductRepository::update(&conn, product.clone(), update)
+                .await
+                .unwrap();
+
+            let product = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(product.currency, Some("GBP".to_string()));
+
+            // New scraper detects AUD - should auto-correct because path locale is present
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product,
+                Some("AUD"),
+                None,
+                false,
+            )
+            .await;
+
+            let updated = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.currency, Some("AUD".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_keeps_existing_currency_when_no_path_locale() {
+            let conn = setup_availability_db().await;
+
+            // Create product WITHOUT path locale
+            let product =
+                create_product_with_url(&conn, "Test Product", "https://example.com/products/test")
+                    .await;
+
+            // Set currency to USD
+            let update = crate::repositories::ProductUpdateInput {
+                currency: Some(Some("USD".to_string())),
+                ..Default::default()
+            };
+            ProductRepository::update(&conn, product.clone(), update)
+                .await
+                .unwrap();
+
+            let product = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(product.currency, Some("USD".to_string()));
+
+            // Scraper detects GBP - should NOT override (no path locale to guide us)
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product,
+                Some("GBP"),
+                None,
+                false,
+            )
+            .await;
+
+            let updated = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+            // Should still be USD (not corrected)
+            assert_eq!(updated.currency, Some("USD".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_never_overrides_locked_currency() {
+            let conn = setup_availability_db().await;
+            let product = create_product_with_url(
+                &conn,
+                "Reyllen Backpack",
+                "https://reyllen.com/en-au/products/backpack",
+            )
+            .await;
+            let update = crate::repositories::ProductUpdateInput {
+                currency: Some(Some("GBP".to_string())),
+                currency_locked: Some(true),
+                ..Default::default()
+            };
+            let product = ProductRepository::update(&conn, product, update)
+                .await
+                .unwrap();
+
+            // Neither a path locale nor a plain mismatch changes a locked currency
+            for check_url in [None, Some("https://reyllen.com/en-nz/products/backpack")] {
+                AvailabilityService::auto_set_product_currency(
+                    &conn,
+                    &product,
+                    Some("AUD"),
+                    check_url,
+                    false,
+                )
+                .await;
+            }
+
+            let updated = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.currency, Some("GBP".to_string()));
+            assert!(updated.currency_locked);
+        }
+
+        #[tokio::test]
+        async fn test_locked_empty_currency_stays_empty() {
+            let conn = setup_availability_db().await;
+            let product =
+                create_product_with_url(&conn, "Test Product", "https://example.com/products/test")
+                    .await;
+            let update = crate::repositories::ProductUpdateInput {
+                currency_locked: Some(true),
+                ..Default::default()
+            };
+            let product = ProductRepository::update(&conn, product, update)
+                .await
+                .unwrap();
+
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product,
+                Some("USD"),
+                None,
+                false,
+            )
+            .await;
+
+            let updated = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.currency, None);
+        }
+
+        #[tokio::test]
+        async fn test_corrects_multiple_path_locales() {
+            let conn = setup_availability_db().await;
+
+            // Test /en-nz/ -> NZD correction
+            let product_nz = create_product_with_url(
+                &conn,
+                "NZ Product",
+                "https://example.com/en-nz/products/test",
+            )
+            .await;
+
+            let update = crate::repositories::ProductUpdateInput {
+                currency: Some(Some("GBP".to_string())),
+                ..Default::default()
+            };
+            ProductRepository::update(&conn, product_nz.clone(), update)
+                .await
+                .unwrap();
+
+            let product_nz = ProductRepository::find_by_id(&conn, product_nz.id)
+                .await
+                .unwrap()
+                .unwrap();
+
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product_nz,
+                Some("NZD"),
+                None,
+                false,
+            )
+            .await;
+
+            let updated_nz = ProductRepository::find_by_id(&conn, product_nz.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated_nz.currency, Some("NZD".to_string()));
+
+            // Test /en-gb/ -> GBP correction
+            let product_gb = create_product_with_url(
+                &conn,
+                "GB Product",
+                "https://example.com/en-gb/products/test",
+            )
+            .await;
+
+            let update = crate::repositories::ProductUpdateInput {
+                currency: Some(Some("USD".to_string())),
+                ..Default::default()
+            };
+            ProductRepository::update(&conn, product_gb.clone(), update)
+                .await
+                .unwrap();
+
+            let product_gb = ProductRepository::find_by_id(&conn, product_gb.id)
+                .await
+                .unwrap()
+                .unwrap();
+
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product_gb,
+                Some("GBP"),
+                None,
+                false,
+            )
+            .await;
+
+            let updated_gb = ProductRepository::find_by_id(&conn, product_gb.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated_gb.currency, Some("GBP".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_corrects_currency_via_check_url_when_product_has_no_url() {
+            let conn = setup_availability_db().await;
+
+            // Create product with NO URL (multi-retailer scenario)
+            let id = Uuid::new_v4();
+            ProductRepository::create(
+                &conn,
+                id,
+                CreateProductRepoParams {
+                    name: "Multi-Retailer Product".to_string(),
+                    url: None,
+                    description: None,
+                    notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units: None,
+                    target_price_currency: None,
+                },
+            )
+            .await
+            .unwrap();
+
+            let product = ProductRepository::find_by_id(&conn, id)
+                .await
+                .unwrap()
+                .unwrap();
+
+            // Set initial currency to GBP (simulating old detection)
+            let update = crate::repositories::ProductUpdateInput {
+                currency: Some(Some("GBP".to_string())),
+                ..Default::default()
+            };
+            ProductRepository::update(&conn, product.clone(), update)
+                .await
+                .unwrap();
+
+            let product = ProductRepository::find_by_id(&conn, id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(product.currency, Some("GBP".to_string()));
+
+            // Call with check_url containing a path locale — should correct to AUD
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product,
+                Some("AUD"),
+                Some("https://example.com/en-au/products/item"),
+                false,
+            )
+            .await;
+
+            let updated = ProductRepository::find_by_id(&conn, id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.currency, Some("AUD".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_region_mismatch_keeps_existing_currency() {
+            let conn = setup_availability_db().await;
+
+            // Path locale would normally let the scraped currency win
+            let product = create_product_with_url(
+                &conn,
+                "AU Product",
+                "https://example.com/en-au/products/test",
+            )
+            .await;
+            let update = crate::repositories::ProductUpdateInput {
+                currency: Some(Some("AUD".to_string())),
+                ..Default::default()
+            };
+            let product = ProductRepository::update(&conn, product, update)
+                .await
+                .unwrap();
+
+            // Redirected to the UK store: GBP prices must not replace AUD
+            AvailabilityService::auto_set_product_currency(
+                &conn,
+                &product,
+                Some("GBP"),
+                None,
+                true,
+            )
+            .await;
+
+            let updated = ProductRepository::find_by_id(&conn, product.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.currency, Some("AUD".to_string()));
+        }
+    }
+
+    /// Tests for auto_set_retailer_currency method
+    mod auto_set_retailer_currency_tests {
+        use super::*;
+        use crate::repositories::{CreateProductRetailerParams, RetailerRepository};
+        use crate::services::scraper::{PriceInfo, ScrapingResult};
+        use crate::test_utils::{create_test_product, setup_availability_db};
+
+        async fn create_link(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            url: &str,
+        ) -> ProductRetailerModel {
+            let retailer = RetailerRepository::find_or_create_by_domain(conn, "example.com")
+                .await
+                .unwrap();
+            ProductRetailerRepository::create(
+                conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: url.to_string(),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap()
+        }
+
+        async fn link_currency(conn: &DatabaseConnection, id: Uuid) -> Option<String> {
+            ProductRetailerRepository::find_by_id(conn, id)
+                .await
+                .unwrap()
+                .unwrap()
+                .currency
+        }
+
+        fn scraped(currency: &str) -> Result<ScrapingResult, AppError> {
+            Ok(ScrapingResult {
+                status: AvailabilityStatus::InStock,
+                raw_availability: None,
+                price: PriceInfo {
+                    price_minor_units: Some(1999),
+                    price_currency: Some(currency.to_string()),
+                    ..Default::default()
+                },
+                region_mismatch: false,
+                fetch_metrics: None,
+                canonical_url: None,
+                confidence: Confidence::High,
+            })
+        }
+
+        #[tokio::test]
+        async fn test_links_with_different_locales_get_own_currency() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com/product").await;
+            let au = create_link(&conn, product_id, "https://example.com/en-au/p/widget").await;
+            let nz = create_link(&conn, product_id, "https://example.com/en-nz/p/widget").await;
+
+            for (link, currency) in [(&au, "AUD"), (&nz, "NZD")] {
+                let product = ProductRepository::find_by_id(&conn, product_id)
+                    .await
+                    .unwrap()
+                    .unwrap();
+                AvailabilityService::process_scraping_result(
+                    &conn,
+                    scraped(currency),
+                    &product,
+                    Some(link),
+                    "USD",
+                )
+                .await;
+            }
+
+            assert_eq!(link_currency(&conn, au.id).await.as_deref(), Some("AUD"));
+            assert_eq!(link_currency(&conn, nz.id).await.as_deref(), Some("NZD"));
         }
 
         #[tokio::test]
-        async fn test_keeps_matching_currency() {
+        async fn test_link_currency_independent_of_product_currency() {
             let conn = setup_availability_db().await;
-            let product = create_product_with_url(
-                &conn,
-                "Test Product",
-                "https://example.com/en-au/products/test",
-            )
-            .await;
-
-            // Set initial currency
-            AvailabilityService::auto_set_product_currency(&conn, &product, Some("AUD"), None)
-                .await;
-
-            let product = ProductRepository::find_by_id(&conn, product.id)
+            let product_id = create_test_product(&conn, "https://example.com/product").await;
+            let product = ProductRepository::find_by_id(&conn, product_id)
                 .await
                 .unwrap()
                 .unwrap();
+            ProductRepository::update(
+                &conn,
+                product,
+                crate::repositories::ProductUpdateInput {
+                    currency: Some(Some("USD".to_string())),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+            let link = create_link(&conn, product_id, "https://example.com/item").await;
 
-            // Try to set the same currency again
-            AvailabilityService::auto_set_product_currency(&conn, &product, Some("AUD"), None)
-                .await;
+            AvailabilityService::auto_set_retailer_currency(&conn, &link, Some("GBP"), false).await;
 
-            let updated = ProductRepository::find_by_id(&conn, product.id)
+            assert_eq!(link_currency(&conn, link.id).await.as_deref(), Some("GBP"));
+            let product = ProductRepository::find_by_id(&conn, product_id)
                 .await
                 .unwrap()
                 .unwrap();
-            assert_eq!(updated.currency, Some("AUD".to_string()));
+            assert_eq!(product.currency.as_deref(), Some("USD"));
         }
 
         #[tokio::test]
-        async fn test_corrects_wrong_currency_when_path_locale_present() {
+        async fn test_corrects_link_currency_with_path_locale() {
             let conn = setup_availability_db().await;
-
-            // Create product with /en-au/ path
-            let product = create_product_with_url(
-                &conn,
-                "Reyllen Backpack",
-                "https://reyllen.com/en-au/products/backpack",
-            )
-            .await;
-
-            // Simulate old scraper setting wrong currency (GBP instead of AUD)
-            let update = crate::repositories::ProductUpdateInput {
-                currency: Some(Some("GBP".to_string())),
-                ..Default::default()
-            };
-            ProductRepository::update(&conn, product.clone(), update)
+            let product_id = create_test_product(&conn, "https://example.com/product").await;
+            let link = create_link(&conn, product_id, "https://example.com/en-nz/p/widget").await;
+            ProductRetailerRepository::set_currency_if(&conn, link.id, None, "AUD")
                 .await
                 .unwrap();
-
-            let product = ProductRepository::find_by_id(&conn, product.id)
+            let link = ProductRetailerRepository::find_by_id(&conn, link.id)
                 .await
                 .unwrap()
                 .unwrap();
-            assert_eq!(product.currency, Some("GBP".to_string()));
 
-            // New scraper detects AUD - should auto-correct because path locale is present
-            AvailabilityService::auto_set_product_currency(&conn, &product, Some("AUD"), None)
-                .await;
+            AvailabilityService::auto_set_retailer_currency(&conn, &link, Some("NZD"), false).await;
 
-            let updated = ProductRepository::find_by_id(&conn, product.id)
+            assert_eq!(link_currency(&conn, link.id).await.as_deref(), Some("NZD"));
+        }
+
+        #[tokio::test]
+        async fn test_keeps_link_currency_without_path_locale() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com/product").await;
+            let link = create_link(&conn, product_id, "https://example.com/p/widget").await;
+            ProductRetailerRepository::set_currency_if(&conn, link.id, None, "AUD")
+                .await
+                .unwrap();
+            let link = ProductRetailerRepository::find_by_id(&conn, link.id)
                 .await
                 .unwrap()
                 .unwrap();
-            assert_eq!(updated.currency, Some("AUD".to_string()));
+
+            AvailabilityService::auto_set_retailer_currency(&conn, &link, Some("NZD"), false).await;
+
+            assert_eq!(link_currency(&conn, link.id).await.as_deref(), Some("AUD"));
         }
 
         #[tokio::test]
-        async fn test_keeps_existing_currency_when_no_path_locale() {
+        async fn test_region_mismatch_keeps_link_currency() {
             let conn = setup_availability_db().await;
-
-            // Create product WITHOUT path locale
-            let product =
-                create_product_with_url(&conn, "Test Product", "https://example.com/products/test")
-                    .await;
-
-            // Set currency to USD
-            let update = crate::repositories::ProductUpdateInput {
-                currency: Some(Some("USD".to_string())),
-                ..Default::default()
-            };
-            ProductRepository::update(&conn, product.clone(), update)
+            let product_id = create_test_product(&conn, "https://example.com/product").await;
+            let link = create_link(&conn, product_id, "https://example.com/en-au/p/widget").await;
+            ProductRetailerRepository::set_currency_if(&conn, link.id, None, "AUD")
                 .await
                 .unwrap();
-
-            let product = ProductRepository::find_by_id(&conn, product.id)
+            let link = ProductRetailerRepository::find_by_id(&conn, link.id)
                 .await
                 .unwrap()
                 .unwrap();
-            assert_eq!(product.currency, Some("USD".to_string()));
 
-            // Scraper detects GBP - should NOT override (no path locale to guide us)
-            AvailabilityService::auto_set_product_currency(&conn, &product, Some("GBP"), None)
-                .await;
+            AvailabilityService::auto_set_retailer_currency(&conn, &link, Some("USD"), true).await;
 
-            let updated = ProductRepository::find_by_id(&conn, product.id)
+            assert_eq!(link_currency(&conn, link.id).await.as_deref(), Some("AUD"));
+        }
+
+        #[tokio::test]
+        async fn test_stale_link_does_not_overwrite_newer_currency() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://example.com/product").await;
+            let stale = create_link(&conn, product_id, "https://example.com/en-au/p/widget").await;
+            // Another check set the currency after `stale` was loaded
+            ProductRetailerRepository::set_currency_if(&conn, stale.id, None, "AUD")
                 .await
-                .unwrap()
                 .unwrap();
-            // Should still be USD (not corrected)
-            assert_eq!(updated.currency, Some("USD".to_string()));
+
+            AvailabilityService::auto_set_retailer_currency(&conn, &stale, Some("USD"), false)
+                .await;
+
+            assert_eq!(link_currency(&conn, stale.id).await.as_deref(), Some("AUD"));
         }
+    }
 
-        #[tokio::test]
-        async fn test_corrects_multiple_path_locales() {
+    mod validate_product_retailer_tests {
+        use super::*;
+        use crate::repositories::{
+            CreateProductRetailerParams, ProductRetailerRepository, RetailerRepository,
+        };
+        use crate::test_utils::{MockResponse, MockServer};
+        use product_stalker_core::entities::app_setting::Entity as AppSettingEntity;
+        use product_stalker_core::entities::verified_session::Entity as VerifiedSessionEntity;
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Schema};
+
+        const SCHEMA_ORG_PAGE: &str = r#"<html><head><script type="application/ld+json">
+            {"@type": "Product", "name": "Widget",
+             "offers": {"@type": "Offer", "price": "19.99", "priceCurrency": "AUD",
+                        "availability": "http://schema.org/InStock"}}
+        </script></head><body></body></html>"#;
+
+        /// Price in Schema.org but stock only from the add-to-cart button
+        const BUTTON_PAGE: &str = r#"<html><head><script type="application/ld+json">
+            {"@type": "Product", "name": "Widget",
+             "offers": {"@type": "Offer", "price": "19.99", "priceCurrency": "AUD"}}
+        </script></head><body><button class="add-to-cart">Add to Cart</button></body></html>"#;
+
+        const PLAIN_PAGE: &str = "<html><body><h1>About us</h1></body></html>";
+
+        /// Availability tables plus the settings and session tables read by the page fetch
+        pub(super) async fn setup_db() -> DatabaseConnection {
             let conn = setup_availability_db().await;
+            let schema = Schema::new(DatabaseBackend::Sqlite);
+            for stmt in [
+                schema.create_table_from_entity(AppSettingEntity),
+                schema.create_table_from_entity(VerifiedSessionEntity),
+            ] {
+                conn.execute(conn.get_database_backend().build(&stmt))
+                    .await
+                    .unwrap();
+            }
+            conn
+        }
 
-            // Test /en-nz/ -> NZD correction
-            let product_nz = create_product_with_url(
-                &conn,
-                "NZ Product",
-                "https://example.com/en-nz/products/test",
-            )
-            .await;
+        /// Serve `/product` as a Schema.org product page, `/button` as a page whose stock
+        /// is only shown by its button, and anything else as plain HTML
+        pub(super) async fn store() -> MockServer {
+            MockServer::start(|request| match request.path() {
+                "/product" => MockResponse::html(SCHEMA_ORG_PAGE),
+                "/button" => MockResponse::html(BUTTON_PAGE),
+                _ => MockResponse::html(PLAIN_PAGE),
+            })
+            .await
+        }
 
-            let update = crate::repositories::ProductUpdateInput {
-                currency: Some(Some("GBP".to_string())),
-                ..Default::default()
-            };
-            ProductRepository::update(&conn, product_nz.clone(), update)
+        async fn add_link(conn: &DatabaseConnection, url: &str) -> (Uuid, Uuid) {
+            let product_id = create_test_product(conn, url).await;
+            let retailer = RetailerRepository::find_or_create_by_domain(conn, "127.0.0.1")
                 .await
                 .unwrap();
+            let link = ProductRetailerRepository::create(
+                conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: url.to_string(),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap();
+            (product_id, link.id)
+        }
 
-            let product_nz = ProductRepository::find_by_id(&conn, product_nz.id)
+        pub(super) fn config() -> CheckConfig<'static> {
+            CheckConfig {
+                fetch: FetchOptions {
+                    timeouts: FetchTimeouts {
+                        min_host_interval_ms: 0,
+                        ..FetchTimeouts::default()
+                    },
+                    ..test_fetch_options()
+                },
+                preferred_currency: "AUD",
+                price_drop_threshold_percent: 5,
+                min_plausible_price_percent: 10,
+            }
+        }
+
+        #[tokio::test]
+        async fn test_schema_org_url_validates_and_stores_first_check() {
+            let conn = setup_db().await;
+            let server = store().await;
+            let (product_id, link_id) = add_link(&conn, &server.url("/product")).await;
+
+            let validation =
+                AvailabilityService::validate_product_retailer(&conn, link_id, &config())
+                    .await
+                    .unwrap();
+
+            assert!(validation.warning.is_none());
+            let diagnostics = validation.diagnostics.unwrap();
+            assert_eq!(
+                diagnostics.strategy,
+                Some(crate::services::scraper::ExtractionStrategy::SchemaOrg)
+            );
+
+            let check =
+                AvailabilityCheckRepository::find_latest_for_product_retailer(&conn, link_id)
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(check.product_id, product_id);
+            assert_eq!(check.status_enum(), AvailabilityStatus::InStock);
+            assert_eq!(check.price_minor_units, Some(1999));
+            assert_eq!(check.normalized_price_minor_units, Some(1999));
+        }
+
+        #[tokio::test]
+        async fn test_successful_check_records_fetch_metrics() {
+            let conn = setup_db().await;
+            let server = store().await;
+            let (_, link_id) = add_link(&conn, &server.url("/product")).await;
+
+            let check = AvailabilityService::check_product_retailer(&conn, link_id, &config())
                 .await
-                .unwrap()
                 .unwrap();
 
-            AvailabilityService::auto_set_product_currency(&conn, &product_nz, Some("NZD"), None)
+            assert!(check.error_message.is_none());
+            assert!(check.fetch_duration_ms.is_some_and(|ms| ms >= 0));
+            assert_eq!(check.content_bytes, Some(SCHEMA_ORG_PAGE.len() as i64));
+
+            let stored =
+                AvailabilityCheckRepository::find_latest_for_product_retailer(&conn, link_id)
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(stored.fetch_duration_ms, check.fetch_duration_ms);
+            assert_eq!(stored.content_bytes, check.content_bytes);
+        }
+
+        #[tokio::test]
+        async fn test_bulk_check_reports_status_confidence() {
+            let conn = setup_db().await;
+            let server = store().await;
+
+            for (path, expected) in [
+                ("/product", Some(Confidence::High)),
+                ("/button", Some(Confidence::Low)),
+                ("/about", None),
+            ] {
+                let (product_id, link_id) = add_link(&conn, &server.url(path)).await;
+                let product = ProductRepository::find_by_id(&conn, product_id)
+                    .await
+                    .unwrap()
+                    .unwrap();
+                let link = ProductRetailerRepository::find_by_id(&conn, link_id)
+                    .await
+                    .unwrap()
+                    .unwrap();
+
+                let (bulk_result, _) = AvailabilityService::check_single_product_retailer(
+                    &conn,
+                    &product,
+                    &link,
+                    &config(),
+                )
                 .await;
 
-            let updated_nz = ProductRepository::find_by_id(&conn, product_nz.id)
-                .await
-                .unwrap()
-                .unwrap();
-            assert_eq!(updated_nz.currency, Some("NZD".to_string()));
+                assert_eq!(bulk_result.confidence, expected, "{}", path);
+                let stored =
+                    AvailabilityCheckRepository::find_latest_for_product_retailer(&conn, link_id)
+                        .await
+                        .unwrap()
+                        .unwrap();
+                assert_eq!(stored.confidence_enum(), expected, "{}", path);
+            }
+        }
 
-            // Test /en-gb/ -> GBP correction
-            let product_gb = create_product_with_url(
+        #[tokio::test]
+        async fn test_check_ignores_price_far_below_recent_average() {
+            let conn = setup_db().await;
+            let server = store().await;
+            let (product_id, link_id) = add_link(&conn, &server.url("/product")).await;
+            // The store now serves $19.99 for a product that was $500 yesterday
+            AvailabilityCheckRepository::create_with_timestamp_and_retailer(
                 &conn,
-                "GB Product",
-                "https://example.com/en-gb/products/test",
+                product_id,
+                link_id,
+                Some(50000),
+                Some("AUD"),
+                Utc::now() - chrono::Duration::days(1),
             )
             .await;
 
-            let update = crate::repositories::ProductUpdateInput {
-                currency: Some(Some("USD".to_string())),
-                ..Default::default()
-            };
-            ProductRepository::update(&conn, product_gb.clone(), update)
+            let check = AvailabilityService::check_product_retailer(&conn, link_id, &config())
                 .await
                 .unwrap();
 
-            let product_gb = ProductRepository::find_by_id(&conn, product_gb.id)
-                .await
-                .unwrap()
-                .unwrap();
+            assert_eq!(check.error_kind.as_deref(), Some("implausible_price"));
+            assert_eq!(check.price_minor_units, None);
+            assert_eq!(check.status_enum(), AvailabilityStatus::InStock);
+        }
 
-            AvailabilityService::auto_set_product_currency(&conn, &product_gb, Some("GBP"), None)
-                .await;
+        #[tokio::test]
+        async fn test_plain_html_url_yields_warning() {
+            let conn = setup_db().await;
+            let server = store().await;
+            let (_, link_id) = add_link(&conn, &server.url("/about")).await;
 
-            let updated_gb = ProductRepository::find_by_id(&conn, product_gb.id)
-                .await
+            let validation =
+                AvailabilityService::validate_product_retailer(&conn, link_id, &config())
+                    .await
+                    .unwrap();
+
+            assert!(validation.warning.is_some());
+            assert!(validation.diagnostics.unwrap().strategy.is_none());
+            let check =
+                AvailabilityCheckRepository::find_latest_for_product_retailer(&conn, link_id)
+                    .await
+                    .unwrap();
+            assert!(check.is_none());
+        }
+
+        #[tokio::test]
+        async fn test_unreachable_url_yields_warning() {
+            let conn = setup_db().await;
+            let (_, link_id) = add_link(&conn, "ftp://127.0.0.1/item").await;
+
+            let validation =
+                AvailabilityService::validate_product_retailer(&conn, link_id, &config())
+                    .await
+                    .unwrap();
+
+            assert!(validation.diagnostics.is_none());
+            assert!(validation
+                .warning
                 .unwrap()
-                .unwrap();
-            assert_eq!(updated_gb.currency, Some("GBP".to_string()));
+                .starts_with("Couldn't load this URL"));
         }
 
         #[tokio::test]
-        async fn test_corrects_currency_via_check_url_when_product_has_no_url() {
-            let conn = setup_availability_db().await;
+        async fn test_missing_link_is_not_found() {
+            let conn = setup_db().await;
 
-            // Create product with NO URL (multi-retailer scenario)
-            let id = Uuid::new_v4();
+            let result =
+                AvailabilityService::validate_product_retailer(&conn, Uuid::new_v4(), &config())
+                    .await;
+
+            assert!(matches!(result, Err(AppError::NotFound(_))));
+        }
+    }
+
+    mod fetch_raw_html_tests {
+        use super::validate_product_retailer_tests::{config, setup_db, store};
+        use super::*;
+        use crate::repositories::CreateProductRepoParams;
+        use crate::services::{AddRetailerParams, ProductRetailerService};
+
+        async fn create_product_without_url(conn: &DatabaseConnection) -> Uuid {
             ProductRepository::create(
-                &conn,
-                id,
+                conn,
+                Uuid::new_v4(),
                 CreateProductRepoParams {
-                    name: "Multi-Retailer Product".to_string(),
+                    name: "No URL".to_string(),
                     url: None,
                     description: None,
                     notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units: None,
+                    target_price_currency: None,
                 },
             )
             .await
-            .unwrap();
+            .unwrap()
+            .id
+        }
 
-            let product = ProductRepository::find_by_id(&conn, id)
-                .await
-                .unwrap()
-                .unwrap();
+        #[tokio::test]
+        async fn test_returns_html_for_product_url() {
+            let conn = setup_db().await;
+            let server = store().await;
+            let product_id = create_test_product(&conn, &server.url("/product")).await;
 
-            // Set initial currency to GBP (simulating old detection)
-            let update = crate::repositories::ProductUpdateInput {
-                currency: Some(Some("GBP".to_string())),
-                ..Default::default()
-            };
-            ProductRepository::update(&conn, product.clone(), update)
+            let html = AvailabilityService::fetch_raw_html(&conn, product_id, &config().fetch)
                 .await
                 .unwrap();
 
-            let product = ProductRepository::find_by_id(&conn, id)
-                .await
-                .unwrap()
-                .unwrap();
-            assert_eq!(product.currency, Some("GBP".to_string()));
+            assert!(html.contains("application/ld+json"));
+            assert!(html.contains("\"price\": \"19.99\""));
+        }
 
-            // Call with check_url containing a path locale — should correct to AUD
-            AvailabilityService::auto_set_product_currency(
+        #[tokio::test]
+        async fn test_falls_back_to_first_retailer_link() {
+            let conn = setup_db().await;
+            let server = store().await;
+            let product_id = create_product_without_url(&conn).await;
+            ProductRetailerService::add_retailer(
                 &conn,
-                &product,
-                Some("AUD"),
-                Some("https://example.com/en-au/products/item"),
+                AddRetailerParams {
+                    product_id,
+                    url: server.url("/about"),
+                    label: None,
+                },
             )
-            .await;
+            .await
+            .unwrap();
 
-            let updated = ProductRepository::find_by_id(&conn, id)
+            let html = AvailabilityService::fetch_raw_html(&conn, product_id, &config().fetch)
                 .await
-                .unwrap()
                 .unwrap();
-            assert_eq!(updated.currency, Some("AUD".to_string()));
+
+            assert!(html.contains("About us"));
+        }
+
+        #[tokio::test]
+        async fn test_product_without_url_is_rejected() {
+            let conn = setup_db().await;
+            let product_id = create_product_without_url(&conn).await;
+
+            let result =
+                AvailabilityService::fetch_raw_html(&conn, product_id, &config().fetch).await;
+
+            assert!(matches!(result, Err(AppError::Validation(_))));
+        }
+
+        #[tokio::test]
+        async fn test_missing_product_is_not_found() {
+            let conn = setup_db().await;
+
+            let result =
+                AvailabilityService::fetch_raw_html(&conn, Uuid::new_v4(), &config().fetch).await;
+
+            assert!(matches!(result, Err(AppError::NotFound(_))));
         }
     }
 }
@@ -1,14 +1,15 @@
-//! Price comparison and stock transition detection.
+//! Price comparison, product comparison and stock transition detection.
 
 use chrono::{DateTime, Utc};
 use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
 use crate::entities::availability_check::AvailabilityStatus;
-use crate::repositories::{AvailabilityCheckRepository, CurrencyAverageResult};
+use crate::repositories::{AvailabilityCheckRepository, Bucket, CurrencyAverageResult, PriceTrend};
+use crate::services::ProductService;
 use product_stalker_core::AppError;
 
-use super::types::DailyPriceComparison;
+use super::types::{DailyPriceComparison, ProductComparison, RestockStats};
 use super::AvailabilityService;
 
 /// Rolling 24-hour time windows for daily price comparison.
@@ -21,12 +22,19 @@ fn daily_time_windows() -> (DateTime<Utc>, DateTime<Utc>, DateTime<Utc>) {
 }
 
 impl AvailabilityService {
+    /// Default window for restock statistics, in days
+    pub const RESTOCK_STATS_WINDOW_DAYS: i64 = 90;
+
+    /// Default window for the price trend, in days
+    pub const PRICE_TREND_WINDOW_DAYS: i64 = 30;
+
     /// Determines if a product has transitioned back to being in stock.
     ///
     /// A product is considered "back in stock" only if:
     /// 1. There was a previous check (first check doesn't count as "back")
-    /// 2. The previous status was NOT in_stock
-    /// 3. The new status IS in_stock
+    /// 2. The previous status was NOT purchasable (in_stock or limited_availability)
+    /// 3. The new status IS purchasable
+    /// 4. The previous status was NOT pre_order (a launch is expected, not a restock)
     ///
     /// This ensures we only notify users about meaningful transitions,
     /// not products that were always in stock or are being checked for the first time.
@@ -35,21 +43,195 @@ impl AvailabilityService {
         new_status: &AvailabilityStatus,
     ) -> bool {
         match previous_status {
-            Some(prev) => {
-                *prev != AvailabilityStatus::InStock && *new_status == AvailabilityStatus::InStock
-            }
+            Some(AvailabilityStatus::PreOrder) => false,
+            Some(prev) => !prev.is_available() && new_status.is_available(),
             None => false,
         }
     }
 
-    /// Check if today's average price dropped compared to yesterday's
-    pub fn is_price_drop(yesterday_average: Option<i64>, today_average: Option<i64>) -> bool {
+    /// Determines if a product with limited stock has just sold out.
+    ///
+    /// Only a LimitedAvailability -> OutOfStock transition counts, so users
+    /// hear about products that were running low and are now gone.
+    pub fn is_sold_out(
+        previous_status: &Option<AvailabilityStatus>,
+        new_status: &AvailabilityStatus,
+    ) -> bool {
+        matches!(
+            previous_status,
+            Some(AvailabilityStatus::LimitedAvailability)
+        ) && *new_status == AvailabilityStatus::OutOfStock
+    }
+
+    /// Check if today's average price dropped by more than `threshold_percent`
+    /// of yesterday's average.
+    ///
+    /// A threshold of 0 counts any drop, so small fluctuations can be filtered out
+    /// without losing the raw comparison (see [`DailyPriceComparison::is_lower`]).
+    pub fn is_price_drop(
+        yesterday_average: Option<i64>,
+        today_average: Option<i64>,
+        threshold_percent: i32,
+    ) -> bool {
         match (yesterday_average, today_average) {
-            (Some(prev), Some(new)) => new < prev,
+            (Some(prev), Some(new)) if new < prev && prev > 0 => {
+                // drop / prev > threshold / 100, in integers to avoid rounding
+                (prev - new) as i128 * 100 > threshold_percent.max(0) as i128 * prev as i128
+            }
             _ => false, // No price drop if either is None
         }
     }
 
+    /// Check if a scraped price is below `min_plausible_percent` of the recent
+    /// average, which points to a glitch on the site (e.g. $0.00 or $1 for a
+    /// $500 product) rather than a genuine sale.
+    ///
+    /// A percent of 0 turns the guard off. Without a recent average every price
+    /// is plausible.
+    pub fn is_implausible_price(
+        recent_average: Option<i64>,
+        price: Option<i64>,
+        min_plausible_percent: i32,
+    ) -> bool {
+        match (recent_average, price) {
+            (Some(average), Some(price)) if average > 0 && min_plausible_percent > 0 => {
+                // price / average < percent / 100, in integers to avoid rounding
+                (price as i128) * 100 < min_plausible_percent as i128 * average as i128
+            }
+            _ => false,
+        }
+    }
+
+    /// Percentage discount of `price` against a strikethrough `compare_at` price,
+    /// rounded to the nearest whole percent.
+    ///
+    /// Returns `None` unless both prices are known and `compare_at` is higher.
+    pub fn discount_percent(price: Option<i64>, compare_at: Option<i64>) -> Option<u32> {
+        match (price, compare_at) {
+            (Some(price), Some(compare_at)) if compare_at > price && price >= 0 => {
+                let off = (compare_at - price) as f64 / compare_at as f64 * 100.0;
+                Some(off.round() as u32)
+            }
+            _ => None,
+        }
+    }
+
+    /// Determines if a price has just crossed down to or below the target price.
+    ///
+    /// Only the crossing counts: the previous price must have been above the target
+    /// (or unknown, e.g. first check or out of stock), so users are notified once
+    /// rather than on every check while the price stays low.
+    pub fn is_target_price_reached(
+        previous_price: Option<i64>,
+        new_price: Option<i64>,
+        target_price: i64,
+    ) -> bool {
+        match (previous_price, new_price) {
+            (Some(prev), Some(new)) => prev > target_price && new <= target_price,
+            (None, Some(new)) => new <= target_price,
+            _ => false,
+        }
+    }
+
+    /// Average time between consecutive restocks, in seconds.
+    ///
+    /// `restock_times` must be sorted oldest first. Returns `None` with fewer
+    /// than two restocks since there is no interval to average.
+    pub fn average_restock_interval(restock_times: &[DateTime<Utc>]) -> Option<i64> {
+        match restock_times {
+            [first, .., last] => {
+                let intervals = restock_times.len() as i64 - 1;
+                Some((*last - *first).num_seconds() / intervals)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get how often a product came back in stock since `since`.
+    pub async fn get_restock_stats(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<RestockStats, AppError> {
+        let restock_times =
+            AvailabilityCheckRepository::find_restock_times(conn, product_id, since).await?;
+
+        Ok(RestockStats {
+            since,
+            restock_count: restock_times.len() as u32,
+            average_restock_interval_secs: Self::average_restock_interval(&restock_times),
+        })
+    }
+
+    /// Classify a product's price over the last `days` as rising, falling or stable.
+    pub async fn get_price_trend(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        days: i64,
+    ) -> Result<PriceTrend, AppError> {
+        AvailabilityCheckRepository::get_price_trend(conn, product_id, days).await
+    }
+
+    /// Compare two products' daily average prices over the last `days` UTC days
+    /// (including today), both converted into `currency` at historical rates.
+    ///
+    /// The series are aligned on the same date axis, with `None` on days a product
+    /// has no convertible price.
+    pub async fn compare_products(
+        conn: &DatabaseConnection,
+        product_a_id: Uuid,
+        product_b_id: Uuid,
+        days: u32,
+        currency: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ProductComparison, AppError> {
+        if days == 0 {
+            return Err(AppError::Validation(
+                "Comparison window must be at least one day".to_string(),
+            ));
+        }
+        if product_a_id == product_b_id {
+            return Err(AppError::Validation(
+                "Cannot compare a product with itself".to_string(),
+            ));
+        }
+        ProductService::get_by_id(conn, product_a_id).await?;
+        ProductService::get_by_id(conn, product_b_id).await?;
+
+        let today = now
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc();
+        let first_day = today - chrono::Duration::days(days as i64 - 1);
+        let to = today + chrono::Duration::days(1);
+
+        let mut series = Vec::with_capacity(2);
+        for product_id in [product_a_id, product_b_id] {
+            series.push(
+                Self::get_price_series_in_currency(
+                    conn,
+                    product_id,
+                    Bucket::Daily,
+                    first_day,
+                    to,
+                    currency,
+                )
+                .await?,
+            );
+        }
+
+        Ok(ProductComparison::align(
+            product_a_id,
+            product_b_id,
+            currency.to_uppercase(),
+            first_day,
+            days,
+            &series[0],
+            &series[1],
+        ))
+    }
+
     /// Re-normalize per-currency average prices to the preferred currency.
     ///
     /// Takes per-currency averages (from `get_original_averages_by_currency_for_period`)
@@ -274,43 +456,452 @@ mod tests {
                 &AvailabilityStatus::Unknown
             ));
         }
+
+        #[test]
+        fn test_from_pre_order_is_not_back_in_stock() {
+            let previous = Some(AvailabilityStatus::PreOrder);
+            assert!(!AvailabilityService::is_back_in_stock(
+                &previous,
+                &AvailabilityStatus::InStock
+            ));
+        }
+
+        #[test]
+        fn test_out_of_stock_to_limited_availability() {
+            let previous = Some(AvailabilityStatus::OutOfStock);
+            assert!(AvailabilityService::is_back_in_stock(
+                &previous,
+                &AvailabilityStatus::LimitedAvailability
+            ));
+        }
+
+        #[test]
+        fn test_limited_availability_to_in_stock() {
+            let previous = Some(AvailabilityStatus::LimitedAvailability);
+            assert!(!AvailabilityService::is_back_in_stock(
+                &previous,
+                &AvailabilityStatus::InStock
+            ));
+        }
+    }
+
+    /// Tests for is_sold_out logic
+    mod sold_out_tests {
+        use super::*;
+
+        #[test]
+        fn test_limited_availability_to_out_of_stock() {
+            let previous = Some(AvailabilityStatus::LimitedAvailability);
+            assert!(AvailabilityService::is_sold_out(
+                &previous,
+                &AvailabilityStatus::OutOfStock
+            ));
+        }
+
+        #[test]
+        fn test_in_stock_to_out_of_stock() {
+            let previous = Some(AvailabilityStatus::InStock);
+            assert!(!AvailabilityService::is_sold_out(
+                &previous,
+                &AvailabilityStatus::OutOfStock
+            ));
+        }
+
+        #[test]
+        fn test_limited_availability_to_in_stock() {
+            let previous = Some(AvailabilityStatus::LimitedAvailability);
+            assert!(!AvailabilityService::is_sold_out(
+                &previous,
+                &AvailabilityStatus::InStock
+            ));
+        }
+
+        #[test]
+        fn test_no_previous() {
+            assert!(!AvailabilityService::is_sold_out(
+                &None,
+                &AvailabilityStatus::OutOfStock
+            ));
+        }
     }
 
     /// Tests for is_price_drop logic
     mod price_drop_tests {
         use super::*;
 
+        const THRESHOLD: i32 = 5;
+
         #[test]
         fn test_from_higher() {
-            assert!(AvailabilityService::is_price_drop(Some(10000), Some(8000)));
+            assert!(AvailabilityService::is_price_drop(
+                Some(10000),
+                Some(8000),
+                THRESHOLD
+            ));
         }
 
         #[test]
         fn test_same_price() {
             assert!(!AvailabilityService::is_price_drop(
                 Some(10000),
-                Some(10000)
+                Some(10000),
+                THRESHOLD
             ));
         }
 
         #[test]
         fn test_price_increase() {
-            assert!(!AvailabilityService::is_price_drop(Some(8000), Some(10000)));
+            assert!(!AvailabilityService::is_price_drop(
+                Some(8000),
+                Some(10000),
+                THRESHOLD
+            ));
         }
 
         #[test]
         fn test_no_previous() {
-            assert!(!AvailabilityService::is_price_drop(None, Some(10000)));
+            assert!(!AvailabilityService::is_price_drop(
+                None,
+                Some(10000),
+                THRESHOLD
+            ));
         }
 
         #[test]
         fn test_no_new() {
-            assert!(!AvailabilityService::is_price_drop(Some(10000), None));
+            assert!(!AvailabilityService::is_price_drop(
+                Some(10000),
+                None,
+                THRESHOLD
+            ));
         }
 
         #[test]
         fn test_both_none() {
-            assert!(!AvailabilityService::is_price_drop(None, None));
+            assert!(!AvailabilityService::is_price_drop(None, None, THRESHOLD));
+        }
+
+        #[test]
+        fn test_just_below_threshold() {
+            // 4.99% drop
+            assert!(!AvailabilityService::is_price_drop(
+                Some(10000),
+                Some(9501),
+                THRESHOLD
+            ));
+        }
+
+        #[test]
+        fn test_exactly_at_threshold() {
+            // The drop has to exceed the threshold
+            assert!(!AvailabilityService::is_price_drop(
+                Some(10000),
+                Some(9500),
+                THRESHOLD
+            ));
+        }
+
+        #[test]
+        fn test_just_above_threshold() {
+            // 5.01% drop
+            assert!(AvailabilityService::is_price_drop(
+                Some(10000),
+                Some(9499),
+                THRESHOLD
+            ));
+        }
+
+        #[test]
+        fn test_small_fluctuation_ignored() {
+            assert!(!AvailabilityService::is_price_drop(
+                Some(4999),
+                Some(4998),
+                THRESHOLD
+            ));
+        }
+
+        #[test]
+        fn test_zero_threshold_counts_any_drop() {
+            assert!(AvailabilityService::is_price_drop(
+                Some(4999),
+                Some(4998),
+                0
+            ));
+            assert!(!AvailabilityService::is_price_drop(
+                Some(4999),
+                Some(4999),
+                0
+            ));
+        }
+    }
+
+    /// Tests for is_implausible_price logic
+    mod implausible_price_tests {
+        use super::*;
+
+        const MIN_PERCENT: i32 = 10;
+
+        #[test]
+        fn test_zero_price_is_implausible() {
+            assert!(AvailabilityService::is_implausible_price(
+                Some(50000),
+                Some(0),
+                MIN_PERCENT
+            ));
+        }
+
+        #[test]
+        fn test_one_dollar_on_five_hundred_is_implausible() {
+            assert!(AvailabilityService::is_implausible_price(
+                Some(50000),
+                Some(100),
+                MIN_PERCENT
+            ));
+        }
+
+        #[test]
+        fn test_boundary() {
+            assert!(AvailabilityService::is_implausible_price(
+                Some(50000),
+                Some(4999),
+                MIN_PERCENT
+            ));
+            assert!(!AvailabilityService::is_implausible_price(
+                Some(50000),
+                Some(5000),
+                MIN_PERCENT
+            ));
+        }
+
+        #[test]
+        fn test_deep_sale_is_plausible() {
+            assert!(!AvailabilityService::is_implausible_price(
+                Some(50000),
+                Some(15000),
+                MIN_PERCENT
+            ));
+        }
+
+        #[test]
+        fn test_without_history_or_price() {
+            assert!(!AvailabilityService::is_implausible_price(
+                None,
+                Some(0),
+                MIN_PERCENT
+            ));
+            assert!(!AvailabilityService::is_implausible_price(
+                Some(50000),
+                None,
+                MIN_PERCENT
+            ));
+            assert!(!AvailabilityService::is_implausible_price(
+                Some(0),
+                Some(0),
+                MIN_PERCENT
+            ));
+        }
+
+        #[test]
+        fn test_zero_percent_turns_guard_off() {
+            assert!(!AvailabilityService::is_implausible_price(
+                Some(50000),
+                Some(0),
+                0
+            ));
+        }
+    }
+
+    /// Tests for discount_percent logic
+    mod discount_percent_tests {
+        use super::*;
+
+        #[test]
+        fn test_discount_percent() {
+            assert_eq!(
+                AvailabilityService::discount_percent(Some(7000), Some(10000)),
+                Some(30)
+            );
+            assert_eq!(
+                AvailabilityService::discount_percent(Some(6999), Some(9999)),
+                Some(30)
+            );
+        }
+
+        #[test]
+        fn test_discount_percent_not_discounted() {
+            assert_eq!(
+                AvailabilityService::discount_percent(Some(10000), Some(10000)),
+                None
+            );
+            assert_eq!(
+                AvailabilityService::discount_percent(Some(12000), Some(10000)),
+                None
+            );
+        }
+
+        #[test]
+        fn test_discount_percent_missing_prices() {
+            assert_eq!(
+                AvailabilityService::discount_percent(None, Some(10000)),
+                None
+            );
+            assert_eq!(
+                AvailabilityService::discount_percent(Some(7000), None),
+                None
+            );
+        }
+    }
+
+    /// Tests for is_target_price_reached logic
+    mod target_price_tests {
+        use super::*;
+
+        #[test]
+        fn test_crosses_below_target() {
+            assert!(AvailabilityService::is_target_price_reached(
+                Some(12000),
+                Some(9000),
+                10000
+            ));
+        }
+
+        #[test]
+        fn test_lands_exactly_on_target() {
+            assert!(AvailabilityService::is_target_price_reached(
+                Some(12000),
+                Some(10000),
+                10000
+            ));
+        }
+
+        #[test]
+        fn test_already_below_target() {
+            assert!(!AvailabilityService::is_target_price_reached(
+                Some(9500),
+                Some(9000),
+                10000
+            ));
+        }
+
+        #[test]
+        fn test_still_above_target() {
+            assert!(!AvailabilityService::is_target_price_reached(
+                Some(12000),
+                Some(11000),
+                10000
+            ));
+        }
+
+        #[test]
+        fn test_no_previous_price() {
+            assert!(AvailabilityService::is_target_price_reached(
+                None,
+                Some(9000),
+                10000
+            ));
+        }
+
+        #[test]
+        fn test_no_new_price() {
+            assert!(!AvailabilityService::is_target_price_reached(
+                Some(12000),
+                None,
+                10000
+            ));
+        }
+    }
+
+    /// Tests for average_restock_interval and get_restock_stats
+    mod restock_stats_tests {
+        use super::*;
+        use crate::test_utils::{create_test_product_default, setup_availability_db};
+        use chrono::Duration;
+        use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+
+        #[test]
+        fn test_average_interval_needs_two_restocks() {
+            let now = Utc::now();
+            assert_eq!(AvailabilityService::average_restock_interval(&[]), None);
+            assert_eq!(AvailabilityService::average_restock_interval(&[now]), None);
+        }
+
+        #[test]
+        fn test_average_interval_of_two_restocks() {
+            let start = Utc::now();
+            let times = [start, start + Duration::days(3)];
+            assert_eq!(
+                AvailabilityService::average_restock_interval(&times),
+                Some(Duration::days(3).num_seconds())
+            );
+        }
+
+        #[test]
+        fn test_average_interval_of_uneven_restocks() {
+            let start = Utc::now();
+            let times = [start, start + Duration::days(1), start + Duration::days(5)];
+            // Gaps of 1 and 4 days average to 2.5 days
+            assert_eq!(
+                AvailabilityService::average_restock_interval(&times),
+                Some(Duration::hours(60).num_seconds())
+            );
+        }
+
+        #[tokio::test]
+        async fn test_get_restock_stats_from_history() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let start = Utc::now() - Duration::days(30);
+
+            let sequence = [
+                (AvailabilityStatus::InStock, 0),
+                (AvailabilityStatus::OutOfStock, 1),
+                (AvailabilityStatus::InStock, 2),
+                (AvailabilityStatus::OutOfStock, 5),
+                (AvailabilityStatus::InStock, 8),
+                (AvailabilityStatus::OutOfStock, 10),
+                (AvailabilityStatus::InStock, 14),
+            ];
+            for (status, day) in sequence {
+                let check = AvailabilityCheckRepository::create_with_timestamp(
+                    &conn,
+                    product_id,
+                    None,
+                    start + Duration::days(day),
+                )
+                .await;
+                let mut active_model = check.into_active_model();
+                active_model.status = Set(status.as_str().to_string());
+                active_model.update(&conn).await.unwrap();
+            }
+
+            let stats = AvailabilityService::get_restock_stats(&conn, product_id, start)
+                .await
+                .unwrap();
+
+            assert_eq!(stats.since, start);
+            assert_eq!(stats.restock_count, 3);
+            // Restocks on days 2, 8 and 14
+            assert_eq!(
+                stats.average_restock_interval_secs,
+                Some(Duration::days(6).num_seconds())
+            );
+        }
+
+        #[tokio::test]
+        async fn test_get_restock_stats_without_history() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let stats = AvailabilityService::get_restock_stats(
+                &conn,
+                product_id,
+                Utc::now() - Duration::days(AvailabilityService::RESTOCK_STATS_WINDOW_DAYS),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(stats.restock_count, 0);
+            assert_eq!(stats.average_restock_interval_secs, None);
         }
     }
 
@@ -448,7 +1039,6 @@ mod tests {
     /// percentage changes when actual prices haven't changed.
     mod renormalization_tests {
         use super::*;
-        use crate::repositories::AvailabilityCheckRepository;
         use crate::test_utils::{create_test_product, setup_availability_db_with_exchange_rates};
         use product_stalker_core::repositories::ExchangeRateRepository;
 
@@ -468,12 +1058,18 @@ mod tests {
                 status: Set("in_stock".to_string()),
                 raw_availability: Set(None),
                 error_message: Set(None),
+                error_kind: Set(None),
                 checked_at: Set(chrono::Utc::now() - chrono::Duration::hours(hours_ago)),
                 price_minor_units: Set(Some(price)),
                 price_currency: Set(Some(currency.to_string())),
                 raw_price: Set(None),
                 normalized_price_minor_units: Set(None),
                 normalized_currency: Set(None),
+                compare_at_minor_units: Set(None),
+                price_valid_until: Set(None),
+                fetch_duration_ms: Set(None),
+                content_bytes: Set(None),
+                confidence: Set(None),
             };
             model.insert(conn).await.unwrap();
         }
@@ -608,4 +1204,122 @@ mod tests {
             assert_eq!(comparison.today_average_minor_units, Some(15000));
         }
     }
+
+    mod compare_products_tests {
+        use super::*;
+        use crate::test_utils::{create_test_product, setup_availability_db_with_exchange_rates};
+        use chrono::{Duration, TimeZone};
+        use product_stalker_core::repositories::ExchangeRateRepository;
+        use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+
+        fn day(d: u32) -> DateTime<Utc> {
+            Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap()
+        }
+
+        async fn record_price(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            price: i64,
+            currency: &str,
+            at: DateTime<Utc>,
+        ) {
+            let check = AvailabilityCheckRepository::create_with_timestamp(
+                conn,
+                product_id,
+                Some(price),
+                at,
+            )
+            .await;
+            let mut active_model = check.into_active_model();
+            active_model.price_currency = Set(Some(currency.to_string()));
+            active_model.update(conn).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_aligns_overlapping_series_in_common_currency() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let a = create_test_product(&conn, "https://a.com/gpu").await;
+            let b = create_test_product(&conn, "https://b.com/gpu").await;
+            ExchangeRateRepository::record_history(&conn, "EUR", "USD", 2.0, "api", day(1))
+                .await
+                .unwrap();
+
+            // A is priced in USD on days 1, 2 and 4; B in EUR on days 2, 3 and 4
+            record_price(&conn, a, 1000, "USD", day(1) + Duration::hours(9)).await;
+            record_price(&conn, a, 900, "USD", day(2) + Duration::hours(9)).await;
+            record_price(&conn, a, 800, "USD", day(4) + Duration::hours(9)).await;
+            record_price(&conn, b, 500, "EUR", day(2) + Duration::hours(10)).await;
+            record_price(&conn, b, 450, "EUR", day(3) + Duration::hours(10)).await;
+            record_price(&conn, b, 400, "EUR", day(4) + Duration::hours(10)).await;
+
+            let comparison = AvailabilityService::compare_products(
+                &conn,
+                a,
+                b,
+                4,
+                "usd",
+                day(4) + Duration::hours(18),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(comparison.currency, "USD");
+            let points: Vec<(DateTime<Utc>, Option<i64>, Option<i64>)> = comparison
+                .points
+                .iter()
+                .map(|p| (p.date, p.a_minor_units, p.b_minor_units))
+                .collect();
+            assert_eq!(
+                points,
+                vec![
+                    (day(1), Some(1000), None),
+                    (day(2), Some(900), Some(1000)),
+                    (day(3), None, Some(900)),
+                    (day(4), Some(800), Some(800)),
+                ]
+            );
+            assert_eq!(comparison.a_change_percent, Some(-20.0));
+            assert_eq!(comparison.b_change_percent, Some(-20.0));
+        }
+
+        #[tokio::test]
+        async fn test_window_excludes_older_checks() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let a = create_test_product(&conn, "https://a.com/gpu").await;
+            let b = create_test_product(&conn, "https://b.com/gpu").await;
+            record_price(&conn, a, 2000, "USD", day(1)).await;
+            record_price(&conn, a, 1500, "USD", day(9)).await;
+            record_price(&conn, b, 1200, "USD", day(10)).await;
+
+            let comparison = AvailabilityService::compare_products(&conn, a, b, 2, "USD", day(10))
+                .await
+                .unwrap();
+
+            assert_eq!(comparison.points.len(), 2);
+            assert_eq!(comparison.points[0].date, day(9));
+            assert_eq!(comparison.points[0].a_minor_units, Some(1500));
+            assert_eq!(comparison.points[1].b_minor_units, Some(1200));
+            // A single priced day has no change
+            assert_eq!(comparison.a_change_percent, None);
+        }
+
+        #[tokio::test]
+        async fn test_rejects_invalid_requests() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let a = create_test_product(&conn, "https://a.com/gpu").await;
+            let b = create_test_product(&conn, "https://b.com/gpu").await;
+            let now = Utc::now();
+
+            let zero_days = AvailabilityService::compare_products(&conn, a, b, 0, "USD", now).await;
+            assert!(matches!(zero_days, Err(AppError::Validation(_))));
+
+            let same = AvailabilityService::compare_products(&conn, a, a, 7, "USD", now).await;
+            assert!(matches!(same, Err(AppError::Validation(_))));
+
+            let missing =
+                AvailabilityService::compare_products(&conn, a, Uuid::new_v4(), 7, "USD", now)
+                    .await;
+            assert!(matches!(missing, Err(AppError::NotFound(_))));
+        }
+    }
 }
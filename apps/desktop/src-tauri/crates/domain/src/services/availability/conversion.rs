@@ -0,0 +1,355 @@
+//! Conversion of prices and price history into a target currency.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use product_stalker_core::services::ExchangeRateService;
+use product_stalker_core::AppError;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::repositories::{AvailabilityCheckRepository, Bucket};
+use crate::services::currency::currency_exponent;
+
+use super::AvailabilityService;
+
+impl AvailabilityService {
+    /// Convert a batch of `(minor_units, currency)` prices into `to`.
+    ///
+    /// Each distinct source currency is looked up once. Items whose currency
+    /// has no exchange rate come back as `None`; other errors are returned.
+    pub async fn convert_prices(
+        conn: &DatabaseConnection,
+        items: &[(i64, String)],
+        to: &str,
+    ) -> Result<Vec<Option<i64>>, AppError> {
+        let rates = Self::fetch_rates(conn, items.iter().map(|(_, c)| c.as_str()), to).await?;
+        let to_exp = currency_exponent(to);
+
+        Ok(items
+            .iter()
+            .map(|(amount, from)| {
+                let rate = rates.get(&from.to_uppercase()).copied().flatten()?;
+                Some(ExchangeRateService::convert_minor_units(
+                    *amount,
+                    rate,
+                    currency_exponent(from),
+                    to_exp,
+                ))
+            })
+            .collect())
+    }
+
+    /// Get the average price per time bucket for a product within [from, to), with
+    /// every check converted into `currency` first.
+    ///
+    /// Each check is converted at the historical rate recorded nearest its
+    /// timestamp, so a retailer switching currency doesn't produce a jump in the
+    /// chart. Buckets where no price could be converted yield `None`.
+    pub async fn get_price_series_in_currency(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        bucket: Bucket,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        currency: &str,
+    ) -> Result<Vec<(DateTime<Utc>, Option<i64>)>, AppError> {
+        if from >= to {
+            return Err(AppError::Validation(
+                "Price history range start must be before its end".to_string(),
+            ));
+        }
+
+        let checks =
+            AvailabilityCheckRepository::get_bucketed_prices(conn, product_id, bucket, from, to)
+                .await?;
+        let target = currency.to_uppercase();
+        let to_exp = currency_exponent(&target);
+
+        // Rate history per source currency, oldest first
+        let mut histories: HashMap<String, Vec<(DateTime<Utc>, f64)>> = HashMap::new();
+        for (_, point) in &checks {
+            let Some(code) = point.as_ref().and_then(|p| p.price_currency.as_deref()) else {
+                continue;
+            };
+            let code = code.to_uppercase();
+            if code == target || histories.contains_key(&code) {
+                continue;
+            }
+            let history = ExchangeRateService::get_rate_history(conn, &code, &target).await?;
+            histories.insert(code, history);
+        }
+
+        // (bucket start, sum of converted prices, number of converted prices)
+        let mut buckets: Vec<(DateTime<Utc>, i64, i64)> = Vec::new();
+        for (start, point) in checks {
+            if buckets.last().map(|(last, _, _)| *last) != Some(start) {
+                buckets.push((start, 0, 0));
+            }
+            let converted = point.and_then(|point| {
+                let code = point.price_currency?.to_uppercase();
+                if code == target {
+                    return Some(point.price_minor_units);
+                }
+                let rate =
+                    ExchangeRateService::nearest_rate(histories.get(&code)?, point.checked_at)?;
+                Some(ExchangeRateService::convert_minor_units(
+                    point.price_minor_units,
+                    rate,
+                    currency_exponent(&code),
+                    to_exp,
+                ))
+            });
+            if let (Some(price), Some((_, sum, count))) = (converted, buckets.last_mut()) {
+                *sum += price;
+                *count += 1;
+            }
+        }
+
+        Ok(buckets
+            .into_iter()
+            .map(|(start, sum, count)| {
+                let avg = (count > 0).then(|| (sum as f64 / count as f64).round() as i64);
+                (start, avg)
+            })
+            .collect())
+    }
+
+    /// Look up the rate into `to` for each distinct currency, keyed by uppercase code
+    async fn fetch_rates<'a>(
+        conn: &DatabaseConnection,
+        currencies: impl Iterator<Item = &'a str>,
+        to: &str,
+    ) -> Result<HashMap<String, Option<f64>>, AppError> {
+        let mut rates: HashMap<String, Option<f64>> = HashMap::new();
+        for currency in currencies {
+            let code = currency.to_uppercase();
+            if rates.contains_key(&code) {
+                continue;
+            }
+            let rate = match ExchangeRateService::get_rate(conn, &code, to).await {
+                Ok(rate) => Some(rate),
+                Err(AppError::NotFound(_)) => None,
+                Err(e) => return Err(e),
+            };
+            rates.insert(code, rate);
+        }
+        Ok(rates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use product_stalker_core::repositories::ExchangeRateRepository;
+
+    use crate::test_utils::setup_availability_db_with_exchange_rates;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fetch_rates_looks_up_each_currency_once() {
+        let conn = setup_availability_db_with_exchange_rates().await;
+        ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+            .await
+            .unwrap();
+
+        let currencies = ["USD", "usd", "USD", "AUD", "EUR", "eur"];
+        let rates = AvailabilityService::fetch_rates(&conn, currencies.into_iter(), "AUD")
+            .await
+            .unwrap();
+
+        assert_eq!(rates.len(), 3);
+        assert_eq!(rates.get("USD"), Some(&Some(1.5)));
+        assert_eq!(rates.get("AUD"), Some(&Some(1.0)));
+        assert_eq!(rates.get("EUR"), Some(&None));
+    }
+
+    #[tokio::test]
+    async fn test_convert_prices_batch() {
+        let conn = setup_availability_db_with_exchange_rates().await;
+        ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+            .await
+            .unwrap();
+        ExchangeRateRepository::upsert_rate(&conn, "JPY", "AUD", 0.01, "api")
+            .await
+            .unwrap();
+
+        let items = vec![
+            (1000, "USD".to_string()),
+            (2000, "usd".to_string()),
+            (1500, "JPY".to_string()),
+            (999, "AUD".to_string()),
+        ];
+        let converted = AvailabilityService::convert_prices(&conn, &items, "AUD")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            converted,
+            vec![Some(1500), Some(3000), Some(1500), Some(999)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_convert_prices_missing_rate_is_none() {
+        let conn = setup_availability_db_with_exchange_rates().await;
+        ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+            .await
+            .unwrap();
+
+        let items = vec![(1000, "EUR".to_string()), (1000, "USD".to_string())];
+        let converted = AvailabilityService::convert_prices(&conn, &items, "AUD")
+            .await
+            .unwrap();
+
+        assert_eq!(converted, vec![None, Some(1500)]);
+    }
+
+    #[tokio::test]
+    async fn test_convert_prices_empty() {
+        let conn = setup_availability_db_with_exchange_rates().await;
+
+        let converted = AvailabilityService::convert_prices(&conn, &[], "AUD")
+            .await
+            .unwrap();
+
+        assert!(converted.is_empty());
+    }
+
+    mod price_series_in_currency_tests {
+        use chrono::{Duration, TimeZone};
+
+        use super::*;
+        use crate::test_utils::create_test_product_default;
+
+        fn day(d: u32) -> DateTime<Utc> {
+            Utc.with_ymd_and_hms(2026, 1, d, 12, 0, 0).unwrap()
+        }
+
+        async fn record_price(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            price: Option<i64>,
+            currency: Option<&str>,
+            at: DateTime<Utc>,
+        ) {
+            use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+
+            let check =
+                AvailabilityCheckRepository::create_with_timestamp(conn, product_id, price, at)
+                    .await;
+            let mut active_model = check.into_active_model();
+            active_model.price_currency = Set(currency.map(str::to_string));
+            active_model.update(conn).await.unwrap();
+        }
+
+        async fn series(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            currency: &str,
+        ) -> Vec<(DateTime<Utc>, Option<i64>)> {
+            AvailabilityService::get_price_series_in_currency(
+                conn,
+                product_id,
+                Bucket::Daily,
+                day(1) - Duration::hours(12),
+                day(10),
+                currency,
+            )
+            .await
+            .unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_converts_across_a_currency_switch_at_historical_rates() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product_id = create_test_product_default(&conn).await;
+            ExchangeRateRepository::record_history(&conn, "USD", "AUD", 1.5, "api", day(1))
+                .await
+                .unwrap();
+            ExchangeRateRepository::record_history(&conn, "USD", "AUD", 1.6, "api", day(3))
+                .await
+                .unwrap();
+
+            // The retailer priced in USD, then switched to AUD on day 4
+            record_price(&conn, product_id, Some(1000), Some("USD"), day(1)).await;
+            record_price(&conn, product_id, Some(1000), Some("USD"), day(3)).await;
+            record_price(&conn, product_id, Some(1600), Some("AUD"), day(4)).await;
+            record_price(&conn, product_id, None, None, day(5)).await;
+
+            let points = series(&conn, product_id, "aud").await;
+
+            let day_start = |d| Utc.with_ymd_and_hms(2026, 1, d, 0, 0, 0).unwrap();
+            assert_eq!(
+                points,
+                vec![
+                    (day_start(1), Some(1500)),
+                    (day_start(3), Some(1600)),
+                    (day_start(4), Some(1600)),
+                    (day_start(5), None),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_uses_nearest_rate_and_averages_within_bucket() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product_id = create_test_product_default(&conn).await;
+            ExchangeRateRepository::record_history(&conn, "USD", "AUD", 1.5, "api", day(1))
+                .await
+                .unwrap();
+            ExchangeRateRepository::record_history(&conn, "USD", "AUD", 2.0, "api", day(9))
+                .await
+                .unwrap();
+
+            // Day 2 is nearer the day 1 rate; the AUD price needs no conversion
+            record_price(&conn, product_id, Some(1000), Some("USD"), day(2)).await;
+            record_price(
+                &conn,
+                product_id,
+                Some(2500),
+                Some("AUD"),
+                day(2) + Duration::hours(1),
+            )
+            .await;
+
+            let points = series(&conn, product_id, "AUD").await;
+
+            assert_eq!(points.len(), 1);
+            assert_eq!(points[0].1, Some(2000));
+        }
+
+        #[tokio::test]
+        async fn test_points_without_a_rate_are_null() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            record_price(&conn, product_id, Some(1000), Some("EUR"), day(1)).await;
+            record_price(&conn, product_id, Some(900), None, day(2)).await;
+            record_price(&conn, product_id, Some(1500), Some("AUD"), day(3)).await;
+
+            let points = series(&conn, product_id, "AUD").await;
+
+            let values: Vec<Option<i64>> = points.iter().map(|(_, v)| *v).collect();
+            assert_eq!(values, vec![None, None, Some(1500)]);
+        }
+
+        #[tokio::test]
+        async fn test_rejects_inverted_range() {
+            let conn = setup_availability_db_with_exchange_rates().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let result = AvailabilityService::get_price_series_in_currency(
+                &conn,
+                product_id,
+                Bucket::Daily,
+                day(2),
+                day(1),
+                "AUD",
+            )
+            .await;
+
+            assert!(matches!(result, Err(AppError::Validation(_))));
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! Per-host scraper success rates, for spotting sites that stopped working.
+
+use std::collections::BTreeMap;
+
+use product_stalker_core::AppError;
+use sea_orm::DatabaseConnection;
+
+use crate::entities::availability_check::CheckErrorKind;
+use crate::repositories::{AvailabilityCheckRepository, HostOutcomeCount};
+
+use super::{AvailabilityService, DomainHealth};
+
+impl AvailabilityService {
+    /// Number of most recent checks per host considered for scraper health
+    pub const SCRAPER_HEALTH_SAMPLE_SIZE: u64 = 20;
+
+    /// Get scraper health for every checked host, least healthy first
+    pub async fn get_scraper_health(
+        conn: &DatabaseConnection,
+    ) -> Result<Vec<DomainHealth>, AppError> {
+        let rows = AvailabilityCheckRepository::count_recent_outcomes_by_host(
+            conn,
+            Self::SCRAPER_HEALTH_SAMPLE_SIZE,
+        )
+        .await?;
+        Ok(Self::build_domain_health(rows))
+    }
+
+    /// Fold per-outcome counts into one `DomainHealth` per host
+    fn build_domain_health(rows: Vec<HostOutcomeCount>) -> Vec<DomainHealth> {
+        let mut by_host: BTreeMap<String, Vec<HostOutcomeCount>> = BTreeMap::new();
+        for row in rows {
+            by_host.entry(row.host.clone()).or_default().push(row);
+        }
+
+        let mut health: Vec<DomainHealth> = by_host
+            .into_iter()
+            .map(|(host, rows)| {
+                let mut success_count = 0;
+                let mut failure_count = 0;
+                let mut kind_counts: BTreeMap<&'static str, u32> = BTreeMap::new();
+                for row in &rows {
+                    let count = row.check_count as u32;
+                    if !row.failed {
+                        success_count += count;
+                        continue;
+                    }
+                    failure_count += count;
+                    if let Some(kind) = row
+                        .error_kind
+                        .as_deref()
+                        .and_then(|kind| kind.parse::<CheckErrorKind>().ok())
+                    {
+                        *kind_counts.entry(kind.as_str()).or_default() += count;
+                    }
+                }
+
+                let total_checks = success_count + failure_count;
+                let bot_protection_count = kind_counts
+                    .get(CheckErrorKind::BotProtection.as_str())
+                    .copied()
+                    .unwrap_or(0);
+                // Ties go to the alphabetically first kind so results are stable
+                let most_common_error_kind = kind_counts
+                    .iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                    .and_then(|(kind, _)| kind.parse().ok());
+
+                DomainHealth {
+                    host,
+                    total_checks,
+                    success_count,
+                    failure_count,
+                    bot_protection_count,
+                    success_rate: if total_checks == 0 {
+                        0.0
+                    } else {
+                        success_count as f64 / total_checks as f64
+                    },
+                    most_common_error_kind,
+                }
+            })
+            .collect();
+
+        health.sort_by(|a, b| {
+            a.success_rate
+                .total_cmp(&b.success_rate)
+                .then_with(|| a.host.cmp(&b.host))
+        });
+        health
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(host: &str, error_kind: Option<&str>, failed: bool, count: i64) -> HostOutcomeCount {
+        HostOutcomeCount {
+            host: host.to_string(),
+            failed,
+            error_kind: error_kind.map(str::to_string),
+            check_count: count,
+        }
+    }
+
+    #[test]
+    fn test_build_domain_health_counts_outcomes() {
+        let health = AvailabilityService::build_domain_health(vec![
+            row("a.com", None, false, 3),
+            row("a.com", Some("bot_protection"), true, 4),
+            row("a.com", Some("network"), true, 1),
+            row("a.com", None, true, 2),
+        ]);
+
+        assert_eq!(
+            health,
+            vec![DomainHealth {
+                host: "a.com".to_string(),
+                total_checks: 10,
+                success_count: 3,
+                failure_count: 7,
+                bot_protection_count: 4,
+                success_rate: 0.3,
+                most_common_error_kind: Some(CheckErrorKind::BotProtection),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_build_domain_health_orders_least_healthy_first() {
+        let health = AvailabilityService::build_domain_health(vec![
+            row("healthy.com", None, false, 5),
+            row("broken.com", Some("http_status"), true, 2),
+            row("broken.com", None, false, 2),
+        ]);
+
+        let hosts: Vec<&str> = health.iter().map(|h| h.host.as_str()).collect();
+        assert_eq!(hosts, vec!["broken.com", "healthy.com"]);
+        assert_eq!(health[0].success_rate, 0.5);
+        assert_eq!(
+            health[0].most_common_error_kind,
+            Some(CheckErrorKind::HttpStatus)
+        );
+        assert_eq!(health[1].most_common_error_kind, None);
+    }
+
+    #[test]
+    fn test_build_domain_health_breaks_kind_ties_alphabetically() {
+        let health = AvailabilityService::build_domain_health(vec![
+            row("a.com", Some("scraping"), true, 2),
+            row("a.com", Some("network"), true, 2),
+        ]);
+
+        assert_eq!(
+            health[0].most_common_error_kind,
+            Some(CheckErrorKind::Network)
+        );
+    }
+
+    mod integration_tests {
+        use chrono::{Duration, Utc};
+        use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+        use uuid::Uuid;
+
+        use super::*;
+        use crate::repositories::{
+            CreateProductRetailerParams, ProductRetailerRepository, RetailerRepository,
+        };
+        use crate::services::ProductRetailerService;
+        use crate::test_utils::{create_test_product, setup_availability_db};
+
+        async fn add_retailer(conn: &DatabaseConnection, product_id: Uuid, url: &str) -> Uuid {
+            let domain = ProductRetailerService::extract_domain(url).unwrap();
+            let retailer = RetailerRepository::find_or_create_by_domain(conn, &domain)
+                .await
+                .unwrap();
+            ProductRetailerRepository::create(
+                conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: url.to_string(),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap()
+            .id
+        }
+
+        /// Record a check `minutes_ago`, failed with `error_kind` when given
+        async fn record_check(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            product_retailer_id: Option<Uuid>,
+            error_kind: Option<CheckErrorKind>,
+            minutes_ago: i64,
+        ) {
+            let check = AvailabilityCheckRepository::create_with_timestamp(
+                conn,
+                product_id,
+                None,
+                Utc::now() - Duration::minutes(minutes_ago),
+            )
+            .await;
+            let mut active_model = check.into_active_model();
+            active_model.product_retailer_id = Set(product_retailer_id);
+            active_model.error_message = Set(error_kind.map(|kind| kind.to_string()));
+            active_model.error_kind = Set(error_kind.map(|kind| kind.as_str().to_string()));
+            active_model.update(conn).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_get_scraper_health_across_two_hosts() {
+            let conn = setup_availability_db().await;
+
+            // Legacy product URL on shop.com, plus a retailer link on the same host
+            let legacy = create_test_product(&conn, "https://Shop.com/legacy").await;
+            record_check(&conn, legacy, None, None, 50).await;
+            let linked = create_test_product(&conn, "https://other.com/item").await;
+            let shop_link = add_retailer(&conn, linked, "https://shop.com/item").await;
+            record_check(&conn, linked, Some(shop_link), None, 40).await;
+            record_check(
+                &conn,
+                linked,
+                Some(shop_link),
+                Some(CheckErrorKind::Network),
+                30,
+            )
+            .await;
+
+            // blocked.com is mostly behind a bot challenge
+            let blocked_link = add_retailer(&conn, linked, "https://blocked.com/p/1").await;
+            record_check(&conn, linked, Some(blocked_link), None, 60).await;
+            for minutes_ago in [20, 10, 5] {
+                record_check(
+                    &conn,
+                    linked,
+                    Some(blocked_link),
+                    Some(CheckErrorKind::BotProtection),
+                    minutes_ago,
+                )
+                .await;
+            }
+
+            let health = AvailabilityService::get_scraper_health(&conn)
+                .await
+                .unwrap();
+
+            assert_eq!(health.len(), 2);
+            let blocked = &health[0];
+            assert_eq!(blocked.host, "blocked.com");
+            assert_eq!(blocked.total_checks, 4);
+            assert_eq!(blocked.failure_count, 3);
+            assert_eq!(blocked.bot_protection_count, 3);
+            assert_eq!(blocked.success_rate, 0.25);
+            assert_eq!(
+                blocked.most_common_error_kind,
+                Some(CheckErrorKind::BotProtection)
+            );
+
+            let shop = &health[1];
+            assert_eq!(shop.host, "shop.com");
+            assert_eq!(shop.total_checks, 3);
+            assert_eq!(shop.success_count, 2);
+            assert_eq!(shop.bot_protection_count, 0);
+            assert_eq!(shop.most_common_error_kind, Some(CheckErrorKind::Network));
+        }
+
+        #[tokio::test]
+        async fn test_get_scraper_health_only_counts_recent_checks() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product(&conn, "https://flaky.com/item").await;
+
+            // Old failures fall outside the sample once enough newer checks exist
+            for minutes_ago in 0..5 {
+                record_check(
+                    &conn,
+                    product_id,
+                    None,
+                    Some(CheckErrorKind::HttpStatus),
+                    1000 + minutes_ago,
+                )
+                .await;
+            }
+            for minutes_ago in 0..AvailabilityService::SCRAPER_HEALTH_SAMPLE_SIZE as i64 {
+                record_check(&conn, product_id, None, None, minutes_ago).await;
+            }
+
+            let health = AvailabilityService::get_scraper_health(&conn)
+                .await
+                .unwrap();
+
+            assert_eq!(health.len(), 1);
+            assert_eq!(
+                health[0].total_checks as u64,
+                AvailabilityService::SCRAPER_HEALTH_SAMPLE_SIZE
+            );
+            assert_eq!(health[0].success_rate, 1.0);
+        }
+
+        #[tokio::test]
+        async fn test_get_scraper_health_empty() {
+            let conn = setup_availability_db().await;
+
+            let health = AvailabilityService::get_scraper_health(&conn)
+                .await
+                .unwrap();
+
+            assert!(health.is_empty());
+        }
+    }
+}
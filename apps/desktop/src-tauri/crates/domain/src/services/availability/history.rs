@@ -0,0 +1,143 @@
+//! Persisted bulk check run summaries.
+
+use chrono::{DateTime, Utc};
+use product_stalker_core::AppError;
+use sea_orm::DatabaseConnection;
+
+use crate::entities::prelude::BulkCheckRunModel;
+use crate::repositories::{BulkCheckRunRepository, CreateBulkCheckRunParams};
+
+use super::{AvailabilityService, BulkCheckSummary};
+
+impl AvailabilityService {
+    /// Maximum number of bulk check runs returned by a single history query
+    pub const MAX_BULK_CHECK_HISTORY_LIMIT: u64 = 500;
+
+    /// Record the totals of a finished bulk check
+    pub async fn record_bulk_check_run(
+        conn: &DatabaseConnection,
+        summary: &BulkCheckSummary,
+        ran_at: DateTime<Utc>,
+    ) -> Result<BulkCheckRunModel, AppError> {
+        BulkCheckRunRepository::create(
+            conn,
+            CreateBulkCheckRunParams {
+                ran_at,
+                total: Self::count_to_i32(summary.total),
+                successful: Self::count_to_i32(summary.successful),
+                failed: Self::count_to_i32(summary.failed),
+                back_in_stock_count: Self::count_to_i32(summary.back_in_stock_count),
+                price_drop_count: Self::count_to_i32(summary.price_drop_count),
+            },
+        )
+        .await
+    }
+
+    /// Get the most recent bulk check runs, newest first
+    pub async fn get_bulk_check_history(
+        conn: &DatabaseConnection,
+        limit: u64,
+    ) -> Result<Vec<BulkCheckRunModel>, AppError> {
+        if limit == 0 {
+            return Err(AppError::Validation(
+                "History limit must be at least 1".to_string(),
+            ));
+        }
+        BulkCheckRunRepository::find_recent(conn, limit.min(Self::MAX_BULK_CHECK_HISTORY_LIMIT))
+            .await
+    }
+
+    fn count_to_i32(count: usize) -> i32 {
+        i32::try_from(count).unwrap_or(i32::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::test_utils::setup_bulk_check_runs_db;
+
+    fn summary(total: usize, back_in_stock_count: usize) -> BulkCheckSummary {
+        BulkCheckSummary {
+            total,
+            successful: total - 1,
+            failed: 1,
+            back_in_stock_count,
+            price_drop_count: 2,
+            sold_out_count: 0,
+            results: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_bulk_check_run_stores_summary_totals() {
+        let conn = setup_bulk_check_runs_db().await;
+        let ran_at = Utc::now();
+
+        let run = AvailabilityService::record_bulk_check_run(&conn, &summary(10, 3), ran_at)
+            .await
+            .unwrap();
+
+        assert_eq!(run.ran_at, ran_at);
+        assert_eq!(run.total, 10);
+        assert_eq!(run.successful, 9);
+        assert_eq!(run.failed, 1);
+        assert_eq!(run.back_in_stock_count, 3);
+        assert_eq!(run.price_drop_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_bulk_check_history_orders_newest_first() {
+        let conn = setup_bulk_check_runs_db().await;
+        let now = Utc::now();
+        for (days_ago, total) in [(2, 20), (0, 40), (1, 30)] {
+            AvailabilityService::record_bulk_check_run(
+                &conn,
+                &summary(total, 0),
+                now - Duration::days(days_ago),
+            )
+            .await
+            .unwrap();
+        }
+
+        let history = AvailabilityService::get_bulk_check_history(&conn, 10)
+            .await
+            .unwrap();
+
+        let totals: Vec<i32> = history.iter().map(|run| run.total).collect();
+        assert_eq!(totals, vec![40, 30, 20]);
+    }
+
+    #[tokio::test]
+    async fn test_get_bulk_check_history_respects_limit() {
+        let conn = setup_bulk_check_runs_db().await;
+        let now = Utc::now();
+        for minutes_ago in 0..5 {
+            AvailabilityService::record_bulk_check_run(
+                &conn,
+                &summary(10 + minutes_ago as usize, 0),
+                now - Duration::minutes(minutes_ago),
+            )
+            .await
+            .unwrap();
+        }
+
+        let history = AvailabilityService::get_bulk_check_history(&conn, 2)
+            .await
+            .unwrap();
+
+        let totals: Vec<i32> = history.iter().map(|run| run.total).collect();
+        assert_eq!(totals, vec![10, 11]);
+    }
+
+    #[tokio::test]
+    async fn test_get_bulk_check_history_rejects_zero_limit() {
+        let conn = setup_bulk_check_runs_db().await;
+
+        let result = AvailabilityService::get_bulk_check_history(&conn, 0).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+}
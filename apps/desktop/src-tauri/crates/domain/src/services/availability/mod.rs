@@ -2,19 +2,35 @@
 //!
 //! Organized into focused submodules:
 //! - `checker`: Product availability checking and result processing
-//! - `comparison`: Price comparison and stock transition detection
-//! - `summary`: Bulk check summary building and counter management
+//! - `comparison`: Price comparison, product comparison and stock transition detection
+//! - `conversion`: Conversion of prices and price history into a target currency
+//! - `health`: Per-host scraper success rates from recent checks
+//! - `history`: Persisted bulk check run summaries
+//! - `portfolio`: Stock and value totals across all tracked products
+//! - `reliability`: Per-retailer reliability scores for comparing a product's retailers
+//! - `retention`: Pruning of check history beyond the data retention window
+//! - `schedule`: Per-product check intervals and product selection for bulk checks
+//! - `summary`: Bulk check summary building, counter management and concurrency
 //! - `types`: Data types for availability checks and bulk operations
 
 mod checker;
 mod comparison;
+mod conversion;
+mod health;
+mod history;
+mod portfolio;
+mod reliability;
 mod renormalize;
+mod retention;
+mod schedule;
 mod summary;
 mod types;
 
 pub use types::{
     BulkCheckResult, BulkCheckSummary, CheckConfig, CheckProcessingResult,
-    CheckResultWithNotification, DailyPriceComparison, ProductCheckContext,
+    CheckResultWithNotification, ComparisonPoint, DailyPriceComparison, DomainHealth,
+    PortfolioSummary, ProductCheckContext, ProductComparison, ReliabilityLabel, RestockStats,
+    RetailerComparison, SelectedCheckTargets, UrlValidation,
 };
 
 /// Service layer for availability checking business logic
@@ -0,0 +1,320 @@
+//! Stock and value totals across all tracked products.
+
+use std::collections::HashMap;
+
+use product_stalker_core::AppError;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::entities::availability_check::AvailabilityStatus;
+use crate::repositories::AvailabilityCheckRepository;
+
+use super::{AvailabilityService, PortfolioSummary};
+
+/// What a product's latest checks say about it, across its retailers
+#[derive(Default)]
+struct ProductStock {
+    in_stock: bool,
+    out_of_stock: bool,
+    /// Cheapest converted price among the in-stock retailers
+    cheapest_minor_units: Option<i64>,
+}
+
+impl AvailabilityService {
+    /// Count unarchived products by stock status and total the value of those in stock.
+    ///
+    /// A product is in stock when the latest check of any of its retailer links
+    /// says so, and is valued at its cheapest in-stock price converted into
+    /// `preferred_currency`. In-stock products without a price, or whose
+    /// currency has no exchange rate, are counted but left out of the total.
+    pub async fn get_portfolio_summary(
+        conn: &DatabaseConnection,
+        preferred_currency: &str,
+    ) -> Result<PortfolioSummary, AppError> {
+        let checks = AvailabilityCheckRepository::find_latest_checks_per_target(conn).await?;
+
+        let in_stock_prices: Vec<(usize, (i64, String))> = checks
+            .iter()
+            .enumerate()
+            .filter(|(_, check)| check.status.as_ref().is_some_and(|s| s.is_available()))
+            .filter_map(|(i, check)| {
+                Some((i, (check.price_minor_units?, check.price_currency.clone()?)))
+            })
+            .collect();
+        let items: Vec<(i64, String)> = in_stock_prices
+            .iter()
+            .map(|(_, item)| item.clone())
+            .collect();
+        let converted = Self::convert_prices(conn, &items, preferred_currency).await?;
+        let converted_by_check: HashMap<usize, i64> = in_stock_prices
+            .iter()
+            .zip(converted)
+            .filter_map(|((i, _), price)| Some((*i, price?)))
+            .collect();
+
+        let mut products: HashMap<Uuid, ProductStock> = HashMap::new();
+        for (i, check) in checks.iter().enumerate() {
+            let stock = products.entry(check.product_id).or_default();
+            match &check.status {
+                Some(status) if status.is_available() => {
+                    stock.in_stock = true;
+                    if let Some(&price) = converted_by_check.get(&i) {
+                        stock.cheapest_minor_units = Some(
+                            stock
+                                .cheapest_minor_units
+                                .map_or(price, |cheapest| cheapest.min(price)),
+                        );
+                    }
+                }
+                Some(
+                    AvailabilityStatus::OutOfStock
+                    | AvailabilityStatus::BackOrder
+                    | AvailabilityStatus::PreOrder,
+                ) => stock.out_of_stock = true,
+                _ => {}
+            }
+        }
+
+        let mut summary = PortfolioSummary {
+            currency: preferred_currency.to_string(),
+            total_value_minor_units: 0,
+            in_stock_count: 0,
+            out_of_stock_count: 0,
+            unknown_count: 0,
+            unpriced_in_stock_count: 0,
+        };
+        for stock in products.values() {
+            if stock.in_stock {
+                summary.in_stock_count += 1;
+                match stock.cheapest_minor_units {
+                    Some(price) => summary.total_value_minor_units += price,
+                    None => summary.unpriced_in_stock_count += 1,
+                }
+            } else if stock.out_of_stock {
+                summary.out_of_stock_count += 1;
+            } else {
+                summary.unknown_count += 1;
+            }
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use product_stalker_core::repositories::ExchangeRateRepository;
+
+    use crate::repositories::{
+        CreateCheckParams, CreateProductRetailerParams, ProductRepository,
+        ProductRetailerRepository, ProductUpdateInput, RetailerRepository,
+    };
+    use crate::test_utils::{
+        create_test_product_default, setup_availability_db_with_exchange_rates,
+    };
+
+    use super::*;
+
+    async fn add_check(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        product_retailer_id: Option<Uuid>,
+        status: AvailabilityStatus,
+        price: Option<(i64, &str)>,
+    ) {
+        AvailabilityCheckRepository::create(
+            conn,
+            Uuid::new_v4(),
+            product_id,
+            CreateCheckParams {
+                status,
+                price_minor_units: price.map(|(amount, _)| amount),
+                price_currency: price.map(|(_, currency)| currency.to_string()),
+                product_retailer_id,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn add_link(conn: &DatabaseConnection, product_id: Uuid, domain: &str) -> Uuid {
+        let retailer = RetailerRepository::find_or_create_by_domain(conn, domain)
+            .await
+            .unwrap();
+        ProductRetailerRepository::create(
+            conn,
+            Uuid::new_v4(),
+            retailer.id,
+            CreateProductRetailerParams {
+                product_id,
+                url: format!("https://{}/item", domain),
+                label: None,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_converts_mixed_currencies() {
+        let conn = setup_availability_db_with_exchange_rates().await;
+        ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+            .await
+            .unwrap();
+        ExchangeRateRepository::upsert_rate(&conn, "JPY", "AUD", 0.01, "api")
+            .await
+            .unwrap();
+
+        let usd = create_test_product_default(&conn).await;
+        add_check(
+            &conn,
+            usd,
+            None,
+            AvailabilityStatus::InStock,
+            Some((2000, "USD")),
+        )
+        .await;
+        let jpy = create_test_product_default(&conn).await;
+        add_check(
+            &conn,
+            jpy,
+            None,
+            AvailabilityStatus::LimitedAvailability,
+            Some((5000, "JPY")),
+        )
+        .await;
+        let aud = create_test_product_default(&conn).await;
+        add_check(
+            &conn,
+            aud,
+            None,
+            AvailabilityStatus::InStock,
+            Some((1234, "AUD")),
+        )
+        .await;
+        // No rate for EUR, and no price at all: counted but not valued
+        let eur = create_test_product_default(&conn).await;
+        add_check(
+            &conn,
+            eur,
+            None,
+            AvailabilityStatus::InStock,
+            Some((999, "EUR")),
+        )
+        .await;
+        let unpriced = create_test_product_default(&conn).await;
+        add_check(&conn, unpriced, None, AvailabilityStatus::InStock, None).await;
+
+        let summary = AvailabilityService::get_portfolio_summary(&conn, "AUD")
+            .await
+            .unwrap();
+
+        assert_eq!(summary.currency, "AUD");
+        assert_eq!(summary.total_value_minor_units, 3000 + 5000 + 1234);
+        assert_eq!(summary.in_stock_count, 5);
+        assert_eq!(summary.unpriced_in_stock_count, 2);
+        assert_eq!(summary.out_of_stock_count, 0);
+        assert_eq!(summary.unknown_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_counts_latest_status_per_product() {
+        let conn = setup_availability_db_with_exchange_rates().await;
+
+        // Was in stock, latest check says sold out
+        let sold_out = create_test_product_default(&conn).await;
+        AvailabilityCheckRepository::create_with_timestamp(
+            &conn,
+            sold_out,
+            Some(5000),
+            Utc::now() - Duration::hours(1),
+        )
+        .await;
+        add_check(&conn, sold_out, None, AvailabilityStatus::OutOfStock, None).await;
+        let pre_order = create_test_product_default(&conn).await;
+        add_check(&conn, pre_order, None, AvailabilityStatus::PreOrder, None).await;
+        let unknown = create_test_product_default(&conn).await;
+        add_check(&conn, unknown, None, AvailabilityStatus::Unknown, None).await;
+        create_test_product_default(&conn).await; // never checked
+        let archived = create_test_product_default(&conn).await;
+        add_check(
+            &conn,
+            archived,
+            None,
+            AvailabilityStatus::InStock,
+            Some((100, "USD")),
+        )
+        .await;
+        let model = ProductRepository::find_by_id(&conn, archived)
+            .await
+            .unwrap()
+            .unwrap();
+        ProductRepository::update(
+            &conn,
+            model,
+            ProductUpdateInput {
+                is_archived: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let summary = AvailabilityService::get_portfolio_summary(&conn, "USD")
+            .await
+            .unwrap();
+
+        assert_eq!(summary.in_stock_count, 0);
+        assert_eq!(summary.out_of_stock_count, 2);
+        assert_eq!(summary.unknown_count, 2);
+        assert_eq!(summary.total_value_minor_units, 0);
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_uses_cheapest_in_stock_retailer() {
+        let conn = setup_availability_db_with_exchange_rates().await;
+        ExchangeRateRepository::upsert_rate(&conn, "USD", "AUD", 1.5, "api")
+            .await
+            .unwrap();
+
+        let product = create_test_product_default(&conn).await;
+        let a = add_link(&conn, product, "shop-a.com").await;
+        let b = add_link(&conn, product, "shop-b.com").await;
+        let c = add_link(&conn, product, "shop-c.com").await;
+        add_check(
+            &conn,
+            product,
+            Some(a),
+            AvailabilityStatus::InStock,
+            Some((2000, "AUD")),
+        )
+        .await;
+        add_check(
+            &conn,
+            product,
+            Some(b),
+            AvailabilityStatus::InStock,
+            Some((1000, "USD")),
+        )
+        .await;
+        // Cheapest, but sold out
+        add_check(
+            &conn,
+            product,
+            Some(c),
+            AvailabilityStatus::OutOfStock,
+            Some((500, "AUD")),
+        )
+        .await;
+
+        let summary = AvailabilityService::get_portfolio_summary(&conn, "AUD")
+            .await
+            .unwrap();
+
+        assert_eq!(summary.in_stock_count, 1);
+        assert_eq!(summary.out_of_stock_count, 0);
+        assert_eq!(summary.total_value_minor_units, 1500);
+    }
+}
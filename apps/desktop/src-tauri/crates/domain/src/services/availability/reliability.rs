@@ -0,0 +1,284 @@
+//! Per-retailer reliability scores for comparing a product's retailers.
+
+use std::collections::HashMap;
+
+use product_stalker_core::AppError;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::repositories::{
+    AvailabilityCheckRepository, ProductRetailerRepository, RetailerCheckCount, RetailerRepository,
+};
+
+use super::{AvailabilityService, ReliabilityLabel, RetailerComparison};
+
+impl AvailabilityService {
+    /// Number of most recent checks per retailer considered for its reliability
+    pub const RELIABILITY_SAMPLE_SIZE: u64 = 50;
+
+    /// Fewest checks a retailer needs before it is given a reliability score
+    pub const RELIABILITY_MIN_CHECKS: i64 = 5;
+
+    /// Compare a product's retailer links, in the user's sort order.
+    ///
+    /// Each entry carries the link's latest check and a reliability score for
+    /// its retailer, computed over recent checks of all the retailer's links
+    /// (not just this product's), so one-off failures don't dominate.
+    pub async fn get_retailer_comparison(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Vec<RetailerComparison>, AppError> {
+        let links =
+            ProductRetailerRepository::find_by_product_id_with_retailer(conn, product_id).await?;
+        let scores: HashMap<Uuid, Option<f64>> =
+            RetailerRepository::count_recent_checks_by_retailer(
+                conn,
+                Self::RELIABILITY_SAMPLE_SIZE,
+            )
+            .await?
+            .iter()
+            .map(|count| (count.retailer_id, Self::reliability_score(count)))
+            .collect();
+
+        let mut comparison = Vec::with_capacity(links.len());
+        for (link, retailer) in links {
+            let latest =
+                AvailabilityCheckRepository::find_latest_for_product_retailer(conn, link.id)
+                    .await?;
+            let reliability_score = scores.get(&link.retailer_id).copied().flatten();
+
+            comparison.push(RetailerComparison {
+                product_retailer_id: link.id,
+                retailer_id: link.retailer_id,
+                domain: retailer.map(|r| r.domain).unwrap_or_default(),
+                url: link.url,
+                label: link.label,
+                status: latest.as_ref().map(|check| check.status_enum()),
+                price_minor_units: latest.as_ref().and_then(|c| c.price_minor_units),
+                price_currency: latest.as_ref().and_then(|c| c.price_currency.clone()),
+                normalized_price_minor_units: latest
+                    .as_ref()
+                    .and_then(|c| c.normalized_price_minor_units),
+                normalized_currency: latest.as_ref().and_then(|c| c.normalized_currency.clone()),
+                checked_at: latest.as_ref().map(|c| c.checked_at),
+                reliability_score,
+                reliability_label: reliability_score.map(Self::reliability_label),
+            });
+        }
+        Ok(comparison)
+    }
+
+    /// Share of a retailer's checks that succeeded, or `None` with too few checks
+    fn reliability_score(count: &RetailerCheckCount) -> Option<f64> {
+        if count.total_checks < Self::RELIABILITY_MIN_CHECKS {
+            return None;
+        }
+        Some(count.successful_checks as f64 / count.total_checks as f64)
+    }
+
+    /// Label a reliability score: at least 90% is reliable, below 60% unreliable
+    pub fn reliability_label(score: f64) -> ReliabilityLabel {
+        if score >= 0.9 {
+            ReliabilityLabel::Reliable
+        } else if score >= 0.6 {
+            ReliabilityLabel::Mixed
+        } else {
+            ReliabilityLabel::Unreliable
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(total_checks: i64, successful_checks: i64) -> RetailerCheckCount {
+        RetailerCheckCount {
+            retailer_id: Uuid::new_v4(),
+            total_checks,
+            successful_checks,
+        }
+    }
+
+    #[test]
+    fn test_reliability_score() {
+        assert_eq!(
+            AvailabilityService::reliability_score(&count(10, 7)),
+            Some(0.7)
+        );
+        assert_eq!(
+            AvailabilityService::reliability_score(&count(5, 0)),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_reliability_score_needs_enough_checks() {
+        assert_eq!(AvailabilityService::reliability_score(&count(4, 4)), None);
+        assert_eq!(AvailabilityService::reliability_score(&count(0, 0)), None);
+    }
+
+    #[test]
+    fn test_reliability_label_thresholds() {
+        assert_eq!(
+            AvailabilityService::reliability_label(1.0),
+            ReliabilityLabel::Reliable
+        );
+        assert_eq!(
+            AvailabilityService::reliability_label(0.9),
+            ReliabilityLabel::Reliable
+        );
+        assert_eq!(
+            AvailabilityService::reliability_label(0.89),
+            ReliabilityLabel::Mixed
+        );
+        assert_eq!(
+            AvailabilityService::reliability_label(0.6),
+            ReliabilityLabel::Mixed
+        );
+        assert_eq!(
+            AvailabilityService::reliability_label(0.59),
+            ReliabilityLabel::Unreliable
+        );
+    }
+
+    mod integration_tests {
+        use chrono::{Duration, Utc};
+        use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+
+        use super::*;
+        use crate::entities::availability_check::AvailabilityStatus;
+        use crate::services::{AddRetailerParams, ProductRetailerService};
+        use crate::test_utils::{create_test_product_default, setup_availability_db};
+
+        async fn add_link(conn: &DatabaseConnection, product_id: Uuid, url: &str) -> Uuid {
+            ProductRetailerService::add_retailer(
+                conn,
+                AddRetailerParams {
+                    product_id,
+                    url: url.to_string(),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap()
+            .id
+        }
+
+        /// Record `successes` successful checks followed by `failures` failed ones
+        async fn seed_checks(
+            conn: &DatabaseConnection,
+            product_id: Uuid,
+            product_retailer_id: Uuid,
+            successes: i64,
+            failures: i64,
+        ) {
+            let total = successes + failures;
+            for i in 0..total {
+                let check = AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                    conn,
+                    product_id,
+                    product_retailer_id,
+                    Some(1000 + i),
+                    Some("USD"),
+                    Utc::now() - Duration::minutes(total - i),
+                )
+                .await;
+                if i >= successes {
+                    let mut active_model = check.into_active_model();
+                    active_model.status = Set(AvailabilityStatus::Unknown.as_str().to_string());
+                    active_model.price_minor_units = Set(None);
+                    active_model.price_currency = Set(None);
+                    active_model.error_message = Set(Some("HTTP 503".to_string()));
+                    active_model.update(conn).await.unwrap();
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn test_comparison_scores_retailers_by_failure_rate() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let other_product = create_test_product_default(&conn).await;
+
+            let steady = add_link(&conn, product_id, "https://steady.com/item").await;
+            let flaky = add_link(&conn, product_id, "https://flaky.com/item").await;
+            let broken = add_link(&conn, product_id, "https://broken.com/item").await;
+            let fresh = add_link(&conn, product_id, "https://fresh.com/item").await;
+
+            seed_checks(&conn, product_id, steady, 10, 0).await;
+            // flaky.com's other product link counts towards its score too
+            seed_checks(&conn, product_id, flaky, 3, 1).await;
+            let flaky_other = add_link(&conn, other_product, "https://flaky.com/other").await;
+            seed_checks(&conn, other_product, flaky_other, 4, 2).await;
+            seed_checks(&conn, product_id, broken, 1, 5).await;
+            seed_checks(&conn, product_id, fresh, 2, 0).await;
+
+            let comparison = AvailabilityService::get_retailer_comparison(&conn, product_id)
+                .await
+                .unwrap();
+
+            let domains: Vec<&str> = comparison.iter().map(|c| c.domain.as_str()).collect();
+            assert_eq!(
+                domains,
+                vec!["steady.com", "flaky.com", "broken.com", "fresh.com"]
+            );
+
+            assert_eq!(comparison[0].reliability_score, Some(1.0));
+            assert_eq!(
+                comparison[0].reliability_label,
+                Some(ReliabilityLabel::Reliable)
+            );
+            assert_eq!(comparison[0].price_minor_units, Some(1009));
+            assert_eq!(comparison[0].status, Some(AvailabilityStatus::InStock));
+
+            assert_eq!(comparison[1].reliability_score, Some(0.7));
+            assert_eq!(
+                comparison[1].reliability_label,
+                Some(ReliabilityLabel::Mixed)
+            );
+            assert_eq!(comparison[1].status, Some(AvailabilityStatus::Unknown));
+            assert_eq!(comparison[1].price_minor_units, None);
+
+            assert_eq!(
+                comparison[2].reliability_label,
+                Some(ReliabilityLabel::Unreliable)
+            );
+
+            // Too few checks to judge yet
+            assert_eq!(comparison[3].reliability_score, None);
+            assert_eq!(comparison[3].reliability_label, None);
+            assert_eq!(comparison[3].price_minor_units, Some(1001));
+        }
+
+        #[tokio::test]
+        async fn test_comparison_includes_unchecked_links() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let link = add_link(&conn, product_id, "https://shop.com/item").await;
+
+            let comparison = AvailabilityService::get_retailer_comparison(&conn, product_id)
+                .await
+                .unwrap();
+
+            assert_eq!(comparison.len(), 1);
+            assert_eq!(comparison[0].product_retailer_id, link);
+            assert_eq!(comparison[0].url, "https://shop.com/item");
+            assert_eq!(comparison[0].status, None);
+            assert_eq!(comparison[0].checked_at, None);
+            assert_eq!(comparison[0].reliability_score, None);
+        }
+
+        #[tokio::test]
+        async fn test_comparison_without_links_is_empty() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let comparison = AvailabilityService::get_retailer_comparison(&conn, product_id)
+                .await
+                .unwrap();
+
+            assert!(comparison.is_empty());
+        }
+    }
+}
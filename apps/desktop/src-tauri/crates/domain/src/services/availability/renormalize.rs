@@ -146,6 +146,9 @@ mod tests {
             schema.create_table_from_entity(crate::entities::product_retailer::Entity),
             schema.create_table_from_entity(crate::entities::availability_check::Entity),
             schema.create_table_from_entity(ExchangeRateEntity),
+            schema.create_table_from_entity(
+                product_stalker_core::entities::exchange_rate_history::Entity,
+            ),
         ] {
             conn.execute(conn.get_database_backend().build(&entity_stmt))
                 .await
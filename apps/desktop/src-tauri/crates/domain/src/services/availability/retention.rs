@@ -0,0 +1,196 @@
+//! Pruning of old availability checks beyond the data retention window, and
+//! repair of checks orphaned by a deleted product or retailer link.
+
+use chrono::{DateTime, Duration, Utc};
+use product_stalker_core::AppError;
+use sea_orm::DatabaseConnection;
+
+use crate::repositories::{AvailabilityCheckRepository, ProductRepository, RepairReport};
+
+use super::AvailabilityService;
+
+impl AvailabilityService {
+    /// Delete checks older than `retention_days` before `now`, returning how many
+    /// were removed.
+    ///
+    /// A retention of 0 keeps history forever. The latest check for each product
+    /// (and each retailer link) is always kept so the next check still detects
+    /// stock transitions.
+    pub async fn prune_check_history(
+        conn: &DatabaseConnection,
+        retention_days: i32,
+        now: DateTime<Utc>,
+    ) -> Result<u64, AppError> {
+        if retention_days <= 0 {
+            return Ok(0);
+        }
+        let cutoff = now - Duration::days(retention_days as i64);
+
+        let mut deleted = 0;
+        for product in ProductRepository::find_all_including_archived(conn).await? {
+            deleted +=
+                AvailabilityCheckRepository::delete_older_than(conn, product.id, cutoff).await?;
+        }
+        Ok(deleted)
+    }
+
+    /// Reassign or delete checks whose product or retailer link no longer exists
+    ///
+    /// See [`AvailabilityCheckRepository::repair_orphaned_checks`] for the rules.
+    pub async fn repair_orphaned_checks(
+        conn: &DatabaseConnection,
+    ) -> Result<RepairReport, AppError> {
+        AvailabilityCheckRepository::repair_orphaned_checks(conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::repositories::{
+        CreateProductRetailerParams, ProductRetailerRepository, RetailerRepository,
+    };
+    use crate::test_utils::{create_test_product_default, setup_availability_db};
+
+    async fn check_ids(conn: &DatabaseConnection, product_id: Uuid) -> Vec<Uuid> {
+        AvailabilityCheckRepository::find_all_for_product(conn, product_id, None, None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_zero_retention_keeps_everything() {
+        let conn = setup_availability_db().await;
+        let product_id = create_test_product_default(&conn).await;
+        let now = Utc::now();
+        AvailabilityCheckRepository::create_with_timestamp(
+            &conn,
+            product_id,
+            Some(100),
+            now - Duration::days(400),
+        )
+        .await;
+        AvailabilityCheckRepository::create_with_timestamp(&conn, product_id, Some(100), now).await;
+
+        let deleted = AvailabilityService::prune_check_history(&conn, 0, now)
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, 0);
+        assert_eq!(check_ids(&conn, product_id).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_prunes_old_checks_but_keeps_recent() {
+        let conn = setup_availability_db().await;
+        let product_id = create_test_product_default(&conn).await;
+        let now = Utc::now();
+        for days_ago in [120, 95, 60, 1] {
+            AvailabilityCheckRepository::create_with_timestamp(
+                &conn,
+                product_id,
+                Some(100),
+                now - Duration::days(days_ago),
+            )
+            .await;
+        }
+
+        let deleted = AvailabilityService::prune_check_history(&conn, 90, now)
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, 2);
+        let remaining =
+            AvailabilityCheckRepository::find_all_for_product(&conn, product_id, None, None, None)
+                .await
+                .unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining
+            .iter()
+            .all(|c| c.checked_at >= now - Duration::days(90)));
+    }
+
+    #[tokio::test]
+    async fn test_keeps_latest_check_even_if_old() {
+        let conn = setup_availability_db().await;
+        let product_id = create_test_product_default(&conn).await;
+        let now = Utc::now();
+        AvailabilityCheckRepository::create_with_timestamp(
+            &conn,
+            product_id,
+            Some(100),
+            now - Duration::days(300),
+        )
+        .await;
+        let latest = AvailabilityCheckRepository::create_with_timestamp(
+            &conn,
+            product_id,
+            Some(90),
+            now - Duration::days(200),
+        )
+        .await;
+
+        let deleted = AvailabilityService::prune_check_history(&conn, 30, now)
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, 1);
+        assert_eq!(check_ids(&conn, product_id).await, vec![latest.id]);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_latest_check_per_retailer() {
+        let conn = setup_availability_db().await;
+        let product_id = create_test_product_default(&conn).await;
+        let now = Utc::now();
+
+        let mut latest_ids = Vec::new();
+        for domain in ["shop-a.com", "shop-b.com"] {
+            let retailer = RetailerRepository::find_or_create_by_domain(&conn, domain)
+                .await
+                .unwrap();
+            let link = ProductRetailerRepository::create(
+                &conn,
+                Uuid::new_v4(),
+                retailer.id,
+                CreateProductRetailerParams {
+                    product_id,
+                    url: format!("https://{}/product", domain),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap();
+            let offset = if domain == "shop-a.com" { 0 } else { 1 };
+            for days_ago in [200 + offset, 100 + offset] {
+                let check = AvailabilityCheckRepository::create_with_timestamp_and_retailer(
+                    &conn,
+                    product_id,
+                    link.id,
+                    Some(100),
+                    Some("USD"),
+                    now - Duration::days(days_ago),
+                )
+                .await;
+                if days_ago < 200 {
+                    latest_ids.push(check.id);
+                }
+            }
+        }
+
+        let deleted = AvailabilityService::prune_check_history(&conn, 30, now)
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, 2);
+        let mut remaining = check_ids(&conn, product_id).await;
+        remaining.sort();
+        latest_ids.sort();
+        assert_eq!(remaining, latest_ids);
+    }
+}
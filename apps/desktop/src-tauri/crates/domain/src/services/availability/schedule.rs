@@ -0,0 +1,489 @@
+//! Per-product check scheduling and product selection for bulk checks.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::entities::prelude::{ProductModel, ProductRetailerModel};
+use crate::repositories::{
+    AvailabilityCheckRepository, ProductRepository, ProductRetailerRepository,
+};
+use product_stalker_core::AppError;
+
+use super::types::SelectedCheckTargets;
+use super::AvailabilityService;
+
+impl AvailabilityService {
+    /// Check interval for a product: its own override, or the global interval when NULL.
+    pub fn effective_check_interval(product: &ProductModel, global_interval_minutes: i32) -> i32 {
+        product
+            .check_interval_minutes
+            .unwrap_or(global_interval_minutes)
+    }
+
+    /// How often the background checker should wake up.
+    ///
+    /// This is the shortest interval across all unpaused products, so that products
+    /// with a short override are not held back by the global interval.
+    pub fn background_tick_minutes(products: &[ProductModel], global_interval_minutes: i32) -> i32 {
+        products
+            .iter()
+            .filter(|p| !p.is_paused)
+            .filter_map(|p| p.check_interval_minutes)
+            .fold(global_interval_minutes, i32::min)
+            .max(1)
+    }
+
+    /// Whether a product should be checked in the current background pass.
+    ///
+    /// A product is due when its last check (of any retailer) plus its interval falls
+    /// at or before `due_by`, typically the start of the next pass. Checking slightly
+    /// early avoids a product being pushed back a whole tick by the time the previous
+    /// pass took. Products that have never been checked are always due.
+    pub async fn is_check_due(
+        conn: &DatabaseConnection,
+        product: &ProductModel,
+        global_interval_minutes: i32,
+        due_by: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        let latest = AvailabilityCheckRepository::find_latest_for_product(conn, product.id).await?;
+        let interval = Self::effective_check_interval(product, global_interval_minutes);
+
+        Ok(Self::is_due(
+            latest.map(|check| check.checked_at),
+            interval,
+            due_by,
+        ))
+    }
+
+    /// Collect the IDs of the given products that are due for a background check.
+    pub async fn find_due_product_ids(
+        conn: &DatabaseConnection,
+        products: &[&ProductModel],
+        global_interval_minutes: i32,
+        due_by: DateTime<Utc>,
+    ) -> Result<HashSet<Uuid>, AppError> {
+        let mut due = HashSet::new();
+        for &product in products {
+            if due.contains(&product.id) {
+                continue;
+            }
+            if Self::is_check_due(conn, product, global_interval_minutes, due_by).await? {
+                due.insert(product.id);
+            }
+        }
+        Ok(due)
+    }
+
+    /// Narrow a bulk check's work list to the given product IDs.
+    ///
+    /// Applied to both the product-retailer links and the legacy (no retailer)
+    /// products; links without a loaded product are dropped.
+    pub fn retain_product_ids(
+        product_retailers: &mut Vec<(ProductRetailerModel, Option<ProductModel>)>,
+        legacy_products: &mut Vec<ProductModel>,
+        product_ids: &HashSet<Uuid>,
+    ) {
+        product_retailers.retain(|(_, p)| p.as_ref().is_some_and(|p| product_ids.contains(&p.id)));
+        legacy_products.retain(|p| product_ids.contains(&p.id));
+    }
+
+    /// Resolve an explicit list of product IDs into a bulk check work list.
+    ///
+    /// Unlike a full bulk check, paused products are included since the user
+    /// picked them. Duplicate IDs are checked once; malformed or unknown IDs are
+    /// collected in `invalid` instead of failing the whole batch.
+    pub async fn find_selected_targets(
+        conn: &DatabaseConnection,
+        product_ids: &[String],
+    ) -> Result<SelectedCheckTargets, AppError> {
+        let mut targets = SelectedCheckTargets::default();
+        let mut seen = HashSet::new();
+
+        for raw_id in product_ids {
+            if !seen.insert(raw_id.as_str()) {
+                continue;
+            }
+            let Ok(id) = Uuid::parse_str(raw_id) else {
+                targets
+                    .invalid
+                    .push((raw_id.clone(), format!("Invalid product ID: {}", raw_id)));
+                continue;
+            };
+            let Some(product) = ProductRepository::find_by_id(conn, id).await? else {
+                targets
+                    .invalid
+                    .push((raw_id.clone(), format!("Product not found: {}", id)));
+                continue;
+            };
+
+            let links = ProductRetailerRepository::find_by_product_id(conn, product.id).await?;
+            if links.is_empty() {
+                targets.legacy_products.push(product);
+            } else {
+                targets
+                    .product_retailers
+                    .extend(links.into_iter().map(|link| (link, Some(product.clone()))));
+            }
+        }
+
+        Ok(targets)
+    }
+
+    fn is_due(
+        last_checked_at: Option<DateTime<Utc>>,
+        interval_minutes: i32,
+        due_by: DateTime<Utc>,
+    ) -> bool {
+        match last_checked_at {
+            Some(checked_at) => checked_at + Duration::minutes(interval_minutes as i64) <= due_by,
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product_with_interval(check_interval_minutes: Option<i32>) -> ProductModel {
+        let now = Utc::now();
+        ProductModel {
+            id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            url: None,
+            description: None,
+            notes: None,
+            currency: None,
+            sort_order: 0,
+            check_interval_minutes,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_effective_check_interval_uses_override() {
+        let product = product_with_interval(Some(15));
+        assert_eq!(
+            AvailabilityService::effective_check_interval(&product, 60),
+            15
+        );
+    }
+
+    #[test]
+    fn test_effective_check_interval_falls_back_to_global() {
+        let product = product_with_interval(None);
+        assert_eq!(
+            AvailabilityService::effective_check_interval(&product, 60),
+            60
+        );
+    }
+
+    #[test]
+    fn test_background_tick_minutes() {
+        let products = vec![
+            product_with_interval(None),
+            product_with_interval(Some(15)),
+            product_with_interval(Some(1440)),
+        ];
+        assert_eq!(
+            AvailabilityService::background_tick_minutes(&products, 60),
+            15
+        );
+        assert_eq!(AvailabilityService::background_tick_minutes(&[], 60), 60);
+        assert_eq!(
+            AvailabilityService::background_tick_minutes(&products[2..], 60),
+            60
+        );
+    }
+
+    #[test]
+    fn test_background_tick_minutes_ignores_paused() {
+        let mut paused = product_with_interval(Some(5));
+        paused.is_paused = true;
+        let products = vec![paused, product_with_interval(Some(30))];
+        assert_eq!(
+            AvailabilityService::background_tick_minutes(&products, 60),
+            30
+        );
+    }
+
+    fn retailer_link(product_id: Uuid) -> ProductRetailerModel {
+        ProductRetailerModel {
+            id: Uuid::new_v4(),
+            product_id,
+            retailer_id: Uuid::new_v4(),
+            url: "https://example.com/item".to_string(),
+            label: None,
+            sort_order: 0,
+            currency: None,
+            variant_selector: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_retain_product_ids() {
+        let kept = product_with_interval(None);
+        let dropped = product_with_interval(None);
+        let legacy_kept = product_with_interval(None);
+        let legacy_dropped = product_with_interval(None);
+        let mut product_retailers = vec![
+            (retailer_link(kept.id), Some(kept.clone())),
+            (retailer_link(dropped.id), Some(dropped.clone())),
+            (retailer_link(Uuid::new_v4()), None),
+        ];
+        let mut legacy_products = vec![legacy_kept.clone(), legacy_dropped];
+        let ids = HashSet::from([kept.id, legacy_kept.id]);
+
+        AvailabilityService::retain_product_ids(&mut product_retailers, &mut legacy_products, &ids);
+
+        assert_eq!(product_retailers.len(), 1);
+        assert_eq!(product_retailers[0].1.as_ref().unwrap().id, kept.id);
+        assert_eq!(legacy_products.len(), 1);
+        assert_eq!(legacy_products[0].id, legacy_kept.id);
+    }
+
+    #[test]
+    fn test_is_due_never_checked() {
+        assert!(AvailabilityService::is_due(None, 60, Utc::now()));
+    }
+
+    #[test]
+    fn test_is_due_boundary() {
+        let now = Utc::now();
+        let checked_at = now - Duration::minutes(60);
+        assert!(AvailabilityService::is_due(Some(checked_at), 60, now));
+        assert!(!AvailabilityService::is_due(
+            Some(checked_at + Duration::seconds(1)),
+            60,
+            now
+        ));
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::repositories::{
+        CreateProductRepoParams, CreateProductRetailerParams, RetailerRepository,
+    };
+    use crate::test_utils::setup_availability_db;
+
+    async fn seed_product(
+        conn: &DatabaseConnection,
+        url: &str,
+        check_interval_minutes: Option<i32>,
+        last_checked_at: Option<DateTime<Utc>>,
+    ) -> ProductModel {
+        let product = ProductRepository::create(
+            conn,
+            Uuid::new_v4(),
+            CreateProductRepoParams {
+                name: "Test Product".to_string(),
+                url: Some(url.to_string()),
+                description: None,
+                notes: None,
+                check_interval_minutes,
+                target_price_minor_units: None,
+                target_price_currency: None,
+            },
+        )
+        .await
+        .unwrap();
+        if let Some(checked_at) = last_checked_at {
+            AvailabilityCheckRepository::create_with_timestamp(
+                conn,
+                product.id,
+                Some(1000),
+                checked_at,
+            )
+            .await;
+        }
+        product
+    }
+
+    #[tokio::test]
+    async fn test_is_check_due_respects_product_override() {
+        let conn = setup_availability_db().await;
+        let now = Utc::now();
+        let product = seed_product(
+            &conn,
+            "https://a.com",
+            Some(15),
+            Some(now - Duration::minutes(20)),
+        )
+        .await;
+
+        let due = AvailabilityService::is_check_due(&conn, &product, 60, now)
+            .await
+            .unwrap();
+        assert!(due);
+    }
+
+    #[tokio::test]
+    async fn test_is_check_due_skips_recently_checked() {
+        let conn = setup_availability_db().await;
+        let now = Utc::now();
+        let product = seed_product(
+            &conn,
+            "https://a.com",
+            Some(1440),
+            Some(now - Duration::minutes(120)),
+        )
+        .await;
+
+        let due = AvailabilityService::is_check_due(&conn, &product, 60, now)
+            .await
+            .unwrap();
+        assert!(!due);
+    }
+
+    #[tokio::test]
+    async fn test_is_check_due_null_interval_uses_global() {
+        let conn = setup_availability_db().await;
+        let now = Utc::now();
+        let product = seed_product(
+            &conn,
+            "https://a.com",
+            None,
+            Some(now - Duration::minutes(30)),
+        )
+        .await;
+
+        assert!(!AvailabilityService::is_check_due(&conn, &product, 60, now)
+            .await
+            .unwrap());
+        assert!(AvailabilityService::is_check_due(&conn, &product, 30, now)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_is_check_due_uses_latest_check() {
+        let conn = setup_availability_db().await;
+        let now = Utc::now();
+        let product = seed_product(
+            &conn,
+            "https://a.com",
+            Some(60),
+            Some(now - Duration::minutes(180)),
+        )
+        .await;
+        AvailabilityCheckRepository::create_with_timestamp(
+            &conn,
+            product.id,
+            Some(1000),
+            now - Duration::minutes(10),
+        )
+        .await;
+
+        let due = AvailabilityService::is_check_due(&conn, &product, 60, now)
+            .await
+            .unwrap();
+        assert!(!due);
+    }
+
+    #[tokio::test]
+    async fn test_find_selected_targets_mixed_ids() {
+        let conn = setup_availability_db().await;
+        let with_retailer = seed_product(&conn, "https://a.com", None, None).await;
+        let legacy = seed_product(&conn, "https://b.com", None, None).await;
+        let retailer = RetailerRepository::find_or_create_by_domain(&conn, "a.com")
+            .await
+            .unwrap();
+        ProductRetailerRepository::create(
+            &conn,
+            Uuid::new_v4(),
+            retailer.id,
+            CreateProductRetailerParams {
+                product_id: with_retailer.id,
+                url: "https://a.com/item".to_string(),
+                label: None,
+            },
+        )
+        .await
+        .unwrap();
+        let unknown = Uuid::new_v4();
+
+        let ids = vec![
+            with_retailer.id.to_string(),
+            legacy.id.to_string(),
+            legacy.id.to_string(),
+            unknown.to_string(),
+            "not-a-uuid".to_string(),
+        ];
+        let targets = AvailabilityService::find_selected_targets(&conn, &ids)
+            .await
+            .unwrap();
+
+        assert_eq!(targets.product_retailers.len(), 1);
+        assert_eq!(targets.product_retailers[0].0.product_id, with_retailer.id);
+        assert_eq!(targets.legacy_products.len(), 1);
+        assert_eq!(targets.legacy_products[0].id, legacy.id);
+        assert_eq!(
+            targets.invalid,
+            vec![
+                (
+                    unknown.to_string(),
+                    format!("Product not found: {}", unknown)
+                ),
+                (
+                    "not-a-uuid".to_string(),
+                    "Invalid product ID: not-a-uuid".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_due_product_ids() {
+        let conn = setup_availability_db().await;
+        let now = Utc::now();
+        let never_checked = seed_product(&conn, "https://a.com", None, None).await;
+        let fast = seed_product(
+            &conn,
+            "https://b.com",
+            Some(15),
+            Some(now - Duration::minutes(16)),
+        )
+        .await;
+        let slow = seed_product(
+            &conn,
+            "https://c.com",
+            Some(1440),
+            Some(now - Duration::minutes(16)),
+        )
+        .await;
+        let global = seed_product(
+            &conn,
+            "https://d.com",
+            None,
+            Some(now - Duration::minutes(16)),
+        )
+        .await;
+
+        let products = vec![&never_checked, &fast, &slow, &global];
+        let due = AvailabilityService::find_due_product_ids(&conn, &products, 60, now)
+            .await
+            .unwrap();
+
+        assert_eq!(due.len(), 2);
+        assert!(due.contains(&never_checked.id));
+        assert!(due.contains(&fast.id));
+        assert!(!due.contains(&slow.id));
+        assert!(!due.contains(&global.id));
+    }
+}
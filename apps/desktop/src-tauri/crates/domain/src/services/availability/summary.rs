@@ -1,4 +1,10 @@
-//! Bulk check summary building and counter management.
+//! Bulk check summary building, counter management and concurrency.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use futures::stream::{self, StreamExt};
+use url::Url;
 
 use crate::services::NotificationService;
 use product_stalker_core::services::notification_helpers::NotificationData;
@@ -7,6 +13,75 @@ use super::types::{BulkCheckCounters, BulkCheckResult, BulkCheckSummary, CheckPr
 use super::AvailabilityService;
 
 impl AvailabilityService {
+    /// Host a bulk check target fetches from, used to keep same-host checks apart.
+    pub fn bulk_check_host(url: &str) -> Option<String> {
+        Url::parse(url)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(str::to_lowercase))
+    }
+
+    /// Run bulk checks with up to `concurrency` checks in flight.
+    ///
+    /// Targets sharing a host (per `host_of`) are checked one after another in
+    /// a single slot, so a store is never hit by two checks at once and no slot
+    /// sits idle waiting for another check of the same store. Targets without a
+    /// host are never held back. Spacing between requests to a host is enforced
+    /// by the scraper's per-host rate limiter, not here.
+    ///
+    /// `on_complete` is called in completion order with a 1-based count of
+    /// finished checks, so progress reporting stays monotonic. The returned
+    /// results are in target order.
+    pub async fn run_bulk_checks<T, R, H, F, Fut, P>(
+        targets: Vec<T>,
+        concurrency: usize,
+        host_of: H,
+        check: F,
+        mut on_complete: P,
+    ) -> Vec<R>
+    where
+        H: Fn(&T) -> Option<String>,
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = R>,
+        P: FnMut(usize, &R),
+    {
+        // Group same-host targets, keeping groups in order of their first target
+        let mut groups: Vec<Vec<(usize, T)>> = Vec::new();
+        let mut host_groups: HashMap<String, usize> = HashMap::new();
+        for (index, target) in targets.into_iter().enumerate() {
+            let group = match host_of(&target) {
+                Some(host) => *host_groups.entry(host).or_insert_with(|| {
+                    groups.push(Vec::new());
+                    groups.len() - 1
+                }),
+                None => {
+                    groups.push(Vec::new());
+                    groups.len() - 1
+                }
+            };
+            groups[group].push((index, target));
+        }
+
+        let check = &check;
+
+        let mut in_flight = stream::iter(groups)
+            .map(|group| {
+                Box::pin(
+                    stream::iter(group)
+                        .then(|(index, target)| async move { (index, check(target).await) }),
+                )
+            })
+            .flatten_unordered(concurrency.max(1));
+
+        let mut results = Vec::new();
+        while let Some((index, result)) = in_flight.next().await {
+            on_complete(results.len() + 1, &result);
+            results.push((index, result));
+        }
+
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
     /// Build summary from collected results
     pub fn build_summary_from_results(
         total: usize,
@@ -37,6 +112,9 @@ impl AvailabilityService {
         if result.is_price_drop {
             counters.price_drop_count += 1;
         }
+        if result.is_sold_out {
+            counters.sold_out_count += 1;
+        }
     }
 
     /// Build the final bulk check summary from counters and results
@@ -51,6 +129,7 @@ impl AvailabilityService {
             failed: counters.failed,
             back_in_stock_count: counters.back_in_stock_count,
             price_drop_count: counters.price_drop_count,
+            sold_out_count: counters.sold_out_count,
             results,
         }
     }
@@ -60,13 +139,181 @@ impl AvailabilityService {
     /// Delegates to NotificationService for actual notification composition.
     pub fn build_bulk_notification_with_settings(
         enable_notifications: bool,
+        is_quiet_time: bool,
         summary: &BulkCheckSummary,
     ) -> Option<NotificationData> {
         NotificationService::build_bulk_notification(
             enable_notifications,
+            is_quiet_time,
             summary.back_in_stock_count,
             summary.price_drop_count,
+            summary.sold_out_count,
             &summary.results,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_bulk_check_host() {
+        assert_eq!(
+            AvailabilityService::bulk_check_host("https://Shop.Example.com/p/1?x=y"),
+            Some("shop.example.com".to_string())
+        );
+        assert_eq!(AvailabilityService::bulk_check_host("not a url"), None);
+    }
+
+    /// Run checks that sleep briefly while tracking how many run at once per host.
+    async fn run_tracked(
+        targets: Vec<(&'static str, u32)>,
+        concurrency: usize,
+    ) -> (Vec<u32>, Vec<usize>, HashMap<&'static str, usize>, usize) {
+        let active: std::sync::Mutex<HashMap<&str, usize>> = Default::default();
+        let max_per_host: std::sync::Mutex<HashMap<&'static str, usize>> = Default::default();
+        let active_total = AtomicUsize::new(0);
+        let max_total = AtomicUsize::new(0);
+        let mut progress = Vec::new();
+
+        let results = AvailabilityService::run_bulk_checks(
+            targets,
+            concurrency,
+            |(host, _)| Some(host.to_string()),
+            |(host, id)| {
+                let (active, max_per_host) = (&active, &max_per_host);
+                let (active_total, max_total) = (&active_total, &max_total);
+                async move {
+                    {
+                        let mut active = active.lock().unwrap();
+                        let count = active.entry(host).or_default();
+                        *count += 1;
+                        let mut max = max_per_host.lock().unwrap();
+                        let host_max = max.entry(host).or_default();
+                        *host_max = (*host_max).max(*count);
+                    }
+                    let now = active_total.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_total.fetch_max(now, Ordering::SeqCst);
+
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+
+                    active_total.fetch_sub(1, Ordering::SeqCst);
+                    *active.lock().unwrap().get_mut(host).unwrap() -= 1;
+                    id
+                }
+            },
+            |current, _| progress.push(current),
+        )
+        .await;
+
+        let max_per_host = max_per_host.into_inner().unwrap();
+        (
+            results,
+            progress,
+            max_per_host,
+            max_total.load(Ordering::SeqCst),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_run_bulk_checks_returns_all_results_in_target_order() {
+        let targets = vec![("a.com", 1), ("b.com", 2), ("c.com", 3), ("a.com", 4)];
+
+        let (results, progress, _, _) = run_tracked(targets, 3).await;
+
+        assert_eq!(results, vec![1, 2, 3, 4]);
+        assert_eq!(progress, vec![1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_run_bulk_checks_never_overlaps_same_host() {
+        let targets = vec![
+            ("a.com", 1),
+            ("a.com", 2),
+            ("a.com", 3),
+            ("b.com", 4),
+            ("b.com", 5),
+            ("c.com", 6),
+        ];
+
+        let (results, _, max_per_host, max_total) = run_tracked(targets, 4).await;
+
+        assert_eq!(results.len(), 6);
+        assert!(max_per_host.values().all(|&max| max == 1));
+        assert!(max_total > 1, "different hosts should run in parallel");
+    }
+
+    #[tokio::test]
+    async fn test_run_bulk_checks_same_host_queue_does_not_hold_slots() {
+        let started = std::sync::Mutex::new(Vec::new());
+        let targets = vec![("a.com", 1), ("a.com", 2), ("a.com", 3), ("b.com", 4)];
+
+        AvailabilityService::run_bulk_checks(
+            targets,
+            2,
+            |(host, _)| Some(host.to_string()),
+            |(_, id)| {
+                let started = &started;
+                async move {
+                    started.lock().unwrap().push(id);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+            },
+            |_, _| {},
+        )
+        .await;
+
+        // b.com gets the second slot straight away instead of queueing behind a.com
+        assert_eq!(started.into_inner().unwrap()[..2], [1, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_run_bulk_checks_concurrency_one_is_sequential() {
+        let targets = vec![("a.com", 1), ("b.com", 2), ("c.com", 3)];
+
+        let (results, _, _, max_total) = run_tracked(targets, 1).await;
+
+        assert_eq!(results, vec![1, 2, 3]);
+        assert_eq!(max_total, 1);
+    }
+
+    #[test]
+    fn test_summary_counts_invalid_selection_as_failed() {
+        let checked = || CheckProcessingResult {
+            status: crate::entities::availability_check::AvailabilityStatus::InStock,
+            price_minor_units: Some(1000),
+            price_currency: Some("USD".to_string()),
+            compare_at_minor_units: None,
+            error: None,
+            error_kind: None,
+            confidence: None,
+            is_back_in_stock: true,
+            is_price_drop: false,
+            is_sold_out: false,
+        };
+        let results = vec![
+            (BulkCheckResult::default(), checked()),
+            (BulkCheckResult::default(), checked()),
+            AvailabilityService::build_invalid_selection_result(
+                "not-a-uuid",
+                "Invalid product ID: not-a-uuid".to_string(),
+            ),
+        ];
+
+        let summary = AvailabilityService::build_summary_from_results(3, results);
+
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.successful, 2);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.back_in_stock_count, 2);
+        assert_eq!(summary.results[2].product_id, "not-a-uuid");
+        assert_eq!(
+            summary.results[2].error.as_deref(),
+            Some("Invalid product ID: not-a-uuid")
+        );
+    }
+}
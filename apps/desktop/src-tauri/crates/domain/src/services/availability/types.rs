@@ -1,12 +1,17 @@
 //! Data types for availability checks and bulk operations.
 
+use chrono::{DateTime, Utc};
 use serde::Serialize;
+use uuid::Uuid;
 
-use crate::entities::availability_check::AvailabilityStatus;
-use crate::entities::prelude::{AvailabilityCheckModel, ProductModel};
+use crate::entities::availability_check::{AvailabilityStatus, CheckErrorKind, Confidence};
+use crate::entities::prelude::{AvailabilityCheckModel, ProductModel, ProductRetailerModel};
 use crate::services::currency;
+use crate::services::scraper::{FetchOptions, ScrapeDiagnostics};
 use product_stalker_core::services::notification_helpers::NotificationData;
 
+use super::AvailabilityService;
+
 /// Result of a single product availability check in a bulk operation
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct BulkCheckResult {
@@ -20,10 +25,22 @@ pub struct BulkCheckResult {
     pub price_minor_units: Option<i64>,
     pub price_currency: Option<String>,
     pub currency_exponent: Option<u32>,
+    /// Strikethrough ("was") price in minor units, same currency as the price
+    pub compare_at_minor_units: Option<i64>,
+    /// Percentage off the compare-at price (e.g. 30 for "30% off")
+    pub discount_percent: Option<u32>,
     pub today_average_price_minor_units: Option<i64>,
     pub yesterday_average_price_minor_units: Option<i64>,
     pub is_price_drop: bool,
+    pub is_sold_out: bool,
     pub error: Option<String>,
+    /// Category of the failure, so the UI can offer a retry for transient errors
+    pub error_kind: Option<CheckErrorKind>,
+    /// `Low` when the status was inferred from page heuristics rather than
+    /// structured data; `None` for failed checks
+    pub confidence: Option<Confidence>,
+    /// The product's notifications are snoozed, so its events don't notify
+    pub notifications_snoozed: bool,
 }
 
 /// Summary of a bulk check operation
@@ -34,6 +51,7 @@ pub struct BulkCheckSummary {
     pub failed: usize,
     pub back_in_stock_count: usize,
     pub price_drop_count: usize,
+    pub sold_out_count: usize,
     pub results: Vec<BulkCheckResult>,
 }
 
@@ -50,9 +68,23 @@ pub struct CheckProcessingResult {
     pub status: AvailabilityStatus,
     pub price_minor_units: Option<i64>,
     pub price_currency: Option<String>,
+    pub compare_at_minor_units: Option<i64>,
     pub error: Option<String>,
+    pub error_kind: Option<CheckErrorKind>,
+    pub confidence: Option<Confidence>,
     pub is_back_in_stock: bool,
     pub is_price_drop: bool,
+    pub is_sold_out: bool,
+}
+
+/// Work list for checking an explicit selection of products
+#[derive(Debug, Default)]
+pub struct SelectedCheckTargets {
+    pub product_retailers: Vec<(ProductRetailerModel, Option<ProductModel>)>,
+    /// Selected products with no retailer links (deprecated url path)
+    pub legacy_products: Vec<ProductModel>,
+    /// Requested IDs that can't be checked, paired with the reason
+    pub invalid: Vec<(String, String)>,
 }
 
 /// Context for checking a single product in a bulk operation
@@ -65,10 +97,13 @@ pub struct ProductCheckContext {
 /// Groups the settings that are threaded through check calls,
 /// avoiding long positional parameter lists.
 pub struct CheckConfig<'a> {
-    pub enable_headless: bool,
-    pub allow_manual_verification: bool,
-    pub session_cache_duration_days: i32,
+    pub fetch: FetchOptions,
     pub preferred_currency: &'a str,
+    /// Minimum drop (percent of yesterday's average) reported as a price drop
+    pub price_drop_threshold_percent: i32,
+    /// Scraped prices below this percent of the recent average are ignored
+    /// as glitches (0 = off)
+    pub min_plausible_price_percent: i32,
 }
 
 /// Accumulated counters for bulk check results
@@ -78,6 +113,7 @@ pub struct BulkCheckCounters {
     pub failed: usize,
     pub back_in_stock_count: usize,
     pub price_drop_count: usize,
+    pub sold_out_count: usize,
 }
 
 /// Result of comparing today's average price vs yesterday's average price
@@ -87,6 +123,172 @@ pub struct DailyPriceComparison {
     pub yesterday_average_minor_units: Option<i64>,
 }
 
+/// How often a product came back in stock within a time window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RestockStats {
+    /// Start of the window the stats cover
+    pub since: DateTime<Utc>,
+    /// Number of OutOfStock -> InStock transitions in the window
+    pub restock_count: u32,
+    /// Average time between consecutive restocks (`None` with fewer than two)
+    pub average_restock_interval_secs: Option<i64>,
+}
+
+/// Stock and value totals across all unarchived products
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PortfolioSummary {
+    /// Currency of `total_value_minor_units` (the preferred currency)
+    pub currency: String,
+    /// Sum of each in-stock product's cheapest in-stock price
+    pub total_value_minor_units: i64,
+    pub in_stock_count: u32,
+    /// Out of stock, back-ordered or pre-order only
+    pub out_of_stock_count: u32,
+    /// Never checked, or the latest checks couldn't tell
+    pub unknown_count: u32,
+    /// In-stock products left out of the total for lacking a convertible price
+    pub unpriced_in_stock_count: u32,
+}
+
+/// Outcome of scraping a retailer URL once when it is added
+#[derive(Debug, Clone, Serialize)]
+pub struct UrlValidation {
+    /// Dry-run scrape result, or `None` if the page couldn't be fetched
+    pub diagnostics: Option<ScrapeDiagnostics>,
+    /// Why the URL may not be trackable; the link is kept either way
+    pub warning: Option<String>,
+}
+
+/// Scraper success over the most recent checks against one host
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DomainHealth {
+    pub host: String,
+    pub total_checks: u32,
+    pub success_count: u32,
+    pub failure_count: u32,
+    pub bot_protection_count: u32,
+    /// Share of checks that succeeded, from 0.0 to 1.0
+    pub success_rate: f64,
+    /// Most frequent error kind among the failures (`None` without any)
+    pub most_common_error_kind: Option<CheckErrorKind>,
+}
+
+/// How consistently checks against a retailer succeed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReliabilityLabel {
+    Reliable,
+    Mixed,
+    Unreliable,
+}
+
+/// One retailer link of a product with its latest check, for comparing retailers
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RetailerComparison {
+    pub product_retailer_id: Uuid,
+    pub retailer_id: Uuid,
+    pub domain: String,
+    pub url: String,
+    pub label: Option<String>,
+    /// Latest check at this link (`None` if never checked)
+    pub status: Option<AvailabilityStatus>,
+    pub price_minor_units: Option<i64>,
+    pub price_currency: Option<String>,
+    pub normalized_price_minor_units: Option<i64>,
+    pub normalized_currency: Option<String>,
+    pub checked_at: Option<DateTime<Utc>>,
+    /// Share of the retailer's recent checks, across all of its links, that
+    /// succeeded (`None` until it has enough checks to judge)
+    pub reliability_score: Option<f64>,
+    pub reliability_label: Option<ReliabilityLabel>,
+}
+
+/// Two products' daily average prices side by side, in a common currency
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProductComparison {
+    pub product_a_id: Uuid,
+    pub product_b_id: Uuid,
+    /// Currency both series are converted into
+    pub currency: String,
+    /// One point per UTC day of the window, oldest first
+    pub points: Vec<ComparisonPoint>,
+    /// Change from product A's first to last priced day, in percent (`None` with
+    /// fewer than two priced days)
+    pub a_change_percent: Option<f64>,
+    pub b_change_percent: Option<f64>,
+}
+
+/// Both products' average price on one day (`None` where a product has no
+/// convertible price that day)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComparisonPoint {
+    pub date: DateTime<Utc>,
+    pub a_minor_units: Option<i64>,
+    pub b_minor_units: Option<i64>,
+}
+
+impl ProductComparison {
+    /// Align two daily series onto the `days` days starting at `first_day`,
+    /// leaving days missing from a series as `None`
+    pub fn align(
+        product_a_id: Uuid,
+        product_b_id: Uuid,
+        currency: String,
+        first_day: DateTime<Utc>,
+        days: u32,
+        a: &[(DateTime<Utc>, Option<i64>)],
+        b: &[(DateTime<Utc>, Option<i64>)],
+    ) -> Self {
+        let price_on = |series: &[(DateTime<Utc>, Option<i64>)], date| {
+            series
+                .iter()
+                .find(|(start, _)| *start == date)
+                .and_then(|(_, price)| *price)
+        };
+        let points: Vec<ComparisonPoint> = (0..days)
+            .map(|offset| {
+                let date = first_day + chrono::Duration::days(offset as i64);
+                ComparisonPoint {
+                    date,
+                    a_minor_units: price_on(a, date),
+                    b_minor_units: price_on(b, date),
+                }
+            })
+            .collect();
+
+        Self {
+            product_a_id,
+            product_b_id,
+            currency,
+            a_change_percent: change_percent(points.iter().filter_map(|p| p.a_minor_units)),
+            b_change_percent: change_percent(points.iter().filter_map(|p| p.b_minor_units)),
+            points,
+        }
+    }
+}
+
+/// Percentage change from the first to the last price
+fn change_percent(mut prices: impl Iterator<Item = i64>) -> Option<f64> {
+    let first = prices.next()?;
+    let last = prices.last()?;
+    if first == 0 {
+        return None;
+    }
+    Some((last - first) as f64 / first as f64 * 100.0)
+}
+
+impl DailyPriceComparison {
+    /// Whether today's average is lower than yesterday's at all, ignoring the
+    /// price drop threshold (for charts and trend indicators)
+    pub fn is_lower(&self) -> bool {
+        AvailabilityService::is_price_drop(
+            self.yesterday_average_minor_units,
+            self.today_average_minor_units,
+            0,
+        )
+    }
+}
+
 impl BulkCheckResult {
     /// Build a result from a successful processing result with daily comparison data
     pub fn from_processing_result(
@@ -111,10 +313,19 @@ impl BulkCheckResult {
             price_minor_units: result.price_minor_units,
             price_currency: result.price_currency.clone(),
             currency_exponent,
+            compare_at_minor_units: result.compare_at_minor_units,
+            discount_percent: AvailabilityService::discount_percent(
+                result.price_minor_units,
+                result.compare_at_minor_units,
+            ),
             today_average_price_minor_units: daily_comparison.today_average_minor_units,
             yesterday_average_price_minor_units: daily_comparison.yesterday_average_minor_units,
             is_price_drop: result.is_price_drop,
+            is_sold_out: result.is_sold_out,
             error: result.error.clone(),
+            error_kind: result.error_kind,
+            confidence: result.confidence,
+            notifications_snoozed: product.is_notifications_snoozed(Utc::now()),
         }
     }
 
@@ -129,11 +340,16 @@ impl BulkCheckResult {
     }
 
     /// Build an error result when context or infrastructure fails
-    pub fn error_for_product(product: &ProductModel, error_message: String) -> Self {
+    pub fn error_for_product(
+        product: &ProductModel,
+        error_message: String,
+        error_kind: CheckErrorKind,
+    ) -> Self {
         Self {
             product_id: product.id.to_string(),
             product_name: product.name.clone(),
             error: Some(error_message),
+            error_kind: Some(error_kind),
             ..Default::default()
         }
     }
@@ -196,6 +412,8 @@ mod tests {
                 url: "https://amazon.com/dp/B123".to_string(),
                 label: Some("64GB".to_string()),
                 sort_order: 0,
+                currency: None,
+                variant_selector: None,
                 created_at: chrono::Utc::now(),
             };
 
@@ -212,6 +430,94 @@ mod tests {
     }
 
     /// Tests for BulkCheckSummary struct
+    /// Tests for BulkCheckResult::from_processing_result
+    mod from_processing_result_tests {
+        use super::*;
+
+        fn test_product() -> ProductModel {
+            let now = chrono::Utc::now();
+            ProductModel {
+                id: Uuid::new_v4(),
+                name: "Discounted Product".to_string(),
+                url: None,
+                description: None,
+                notes: None,
+                currency: Some("AUD".to_string()),
+                sort_order: 0,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
+                is_paused: false,
+                is_archived: false,
+                track_price: true,
+                currency_locked: false,
+                last_notified_check_id: None,
+                last_notified_at: None,
+                notifications_snoozed_until: None,
+                created_at: now,
+                updated_at: now,
+            }
+        }
+
+        fn processing_result(compare_at_minor_units: Option<i64>) -> CheckProcessingResult {
+            CheckProcessingResult {
+                status: AvailabilityStatus::InStock,
+                price_minor_units: Some(7000),
+                price_currency: Some("AUD".to_string()),
+                compare_at_minor_units,
+                error: None,
+                error_kind: None,
+                confidence: None,
+                is_back_in_stock: false,
+                is_price_drop: false,
+                is_sold_out: false,
+            }
+        }
+
+        #[test]
+        fn test_computes_discount_percent() {
+            let result = BulkCheckResult::from_processing_result(
+                &test_product(),
+                &processing_result(Some(10000)),
+                &ProductCheckContext {
+                    previous_status: None,
+                },
+                &DailyPriceComparison::default(),
+            );
+
+            assert_eq!(result.compare_at_minor_units, Some(10000));
+            assert_eq!(result.discount_percent, Some(30));
+        }
+
+        #[test]
+        fn test_no_compare_at_price_has_no_discount() {
+            let result = BulkCheckResult::from_processing_result(
+                &test_product(),
+                &processing_result(None),
+                &ProductCheckContext {
+                    previous_status: None,
+                },
+                &DailyPriceComparison::default(),
+            );
+
+            assert_eq!(result.discount_percent, None);
+        }
+    }
+
+    mod daily_price_comparison_tests {
+        use super::*;
+
+        #[test]
+        fn test_is_lower_ignores_threshold() {
+            let comparison = DailyPriceComparison {
+                today_average_minor_units: Some(4998),
+                yesterday_average_minor_units: Some(4999),
+            };
+            assert!(comparison.is_lower());
+            assert!(!DailyPriceComparison::default().is_lower());
+        }
+    }
+
     mod bulk_check_summary_tests {
         use super::*;
 
@@ -223,6 +529,7 @@ mod tests {
                 failed: 2,
                 back_in_stock_count: 3,
                 price_drop_count: 2,
+                sold_out_count: 1,
                 results: vec![],
             };
             let json = serde_json::to_string(&summary).unwrap();
@@ -231,6 +538,7 @@ mod tests {
             assert!(json.contains("\"failed\":2"));
             assert!(json.contains("\"back_in_stock_count\":3"));
             assert!(json.contains("\"price_drop_count\":2"));
+            assert!(json.contains("\"sold_out_count\":1"));
         }
 
         #[test]
@@ -251,6 +559,7 @@ mod tests {
                 failed: 0,
                 back_in_stock_count: 1,
                 price_drop_count: 0,
+                sold_out_count: 0,
                 results: vec![result],
             };
             let json = serde_json::to_string(&summary).unwrap();
@@ -272,19 +581,22 @@ mod tests {
                 status: "in_stock".to_string(),
                 raw_availability: Some("http://schema.org/InStock".to_string()),
                 error_message: None,
+                error_kind: None,
                 checked_at: chrono::Utc::now(),
                 price_minor_units: Some(78900),
                 price_currency: Some("USD".to_string()),
                 raw_price: Some("789.00".to_string()),
                 normalized_price_minor_units: None,
                 normalized_currency: None,
+                compare_at_minor_units: None,
+                price_valid_until: None,
+                fetch_duration_ms: None,
+                content_bytes: None,
+                confidence: None,
             };
             let result = CheckResultWithNotification {
                 check,
-                notification: Some(NotificationData {
-                    title: "Back in Stock!".to_string(),
-                    body: "Product available".to_string(),
-                }),
+                notification: Some(NotificationData::new("Back in Stock!", "Product available")),
                 daily_comparison: DailyPriceComparison {
                     today_average_minor_units: Some(78900),
                     yesterday_average_minor_units: Some(89900),
@@ -304,12 +616,18 @@ mod tests {
                 status: "out_of_stock".to_string(),
                 raw_availability: None,
                 error_message: None,
+                error_kind: None,
                 checked_at: chrono::Utc::now(),
                 price_minor_units: None,
                 price_currency: None,
                 raw_price: None,
                 normalized_price_minor_units: None,
                 normalized_currency: None,
+                compare_at_minor_units: None,
+                price_valid_until: None,
+                fetch_duration_ms: None,
+                content_bytes: None,
+                confidence: None,
             };
             let result = CheckResultWithNotification {
                 check,
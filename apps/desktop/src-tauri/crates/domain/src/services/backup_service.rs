@@ -0,0 +1,678 @@
+//! Portable JSON backup of the tracked product set.
+//!
+//! A backup holds products (including target prices), the retailers they are
+//! tracked at and the product-retailer links. Check history is not included.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::prelude::{ProductModel, ProductRetailerModel, RetailerModel};
+use crate::repositories::{
+    CreateProductRepoParams, CreateProductRetailerParams, ProductRepository,
+    ProductRetailerRepository, ProductUpdateInput, RetailerRepository,
+};
+use product_stalker_core::AppError;
+
+/// Current backup format version. Bump when the document shape changes.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Versioned backup document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProductBackup {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub retailers: Vec<RetailerBackup>,
+    /// Products in display order
+    pub products: Vec<ProductBackupEntry>,
+    /// Product-retailer links in display order
+    pub product_retailers: Vec<ProductRetailerBackup>,
+}
+
+/// Retailer in a backup (matched by domain on import)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetailerBackup {
+    pub id: Uuid,
+    pub domain: String,
+    pub name: String,
+}
+
+/// Product in a backup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProductBackupEntry {
+    pub id: Uuid,
+    pub name: String,
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub notes: Option<String>,
+    pub currency: Option<String>,
+    pub check_interval_minutes: Option<i32>,
+    pub target_price_minor_units: Option<i64>,
+    pub target_price_currency: Option<String>,
+    pub is_paused: bool,
+    /// Missing in backups made before archiving existed
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Missing in backups made before stock-only checks existed
+    #[serde(default = "default_track_price")]
+    pub track_price: bool,
+    /// Missing in backups made before currencies could be locked
+    #[serde(default)]
+    pub currency_locked: bool,
+}
+
+fn default_track_price() -> bool {
+    true
+}
+
+/// Product-retailer link in a backup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProductRetailerBackup {
+    pub product_id: Uuid,
+    pub retailer_id: Uuid,
+    pub url: String,
+    pub label: Option<String>,
+}
+
+impl From<&RetailerModel> for RetailerBackup {
+    fn from(model: &RetailerModel) -> Self {
+        Self {
+            id: model.id,
+            domain: model.domain.clone(),
+            name: model.name.clone(),
+        }
+    }
+}
+
+impl From<&ProductModel> for ProductBackupEntry {
+    fn from(model: &ProductModel) -> Self {
+        Self {
+            id: model.id,
+            name: model.name.clone(),
+            url: model.url.clone(),
+            description: model.description.clone(),
+            notes: model.notes.clone(),
+            currency: model.currency.clone(),
+            check_interval_minutes: model.check_interval_minutes,
+            target_price_minor_units: model.target_price_minor_units,
+            target_price_currency: model.target_price_currency.clone(),
+            is_paused: model.is_paused,
+            is_archived: model.is_archived,
+            track_price: model.track_price,
+            currency_locked: model.currency_locked,
+        }
+    }
+}
+
+impl From<&ProductRetailerModel> for ProductRetailerBackup {
+    fn from(model: &ProductRetailerModel) -> Self {
+        Self {
+            product_id: model.product_id,
+            retailer_id: model.retailer_id,
+            url: model.url.clone(),
+            label: model.label.clone(),
+        }
+    }
+}
+
+/// How an imported backup is combined with the existing products
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// Add products that aren't already present (matched by ID); keep everything else
+    Merge,
+    /// Delete all existing products (and their history) before importing
+    Replace,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportSummary {
+    pub products_imported: usize,
+    /// Products skipped because one with the same ID already exists
+    pub products_skipped: usize,
+    pub retailer_links_imported: usize,
+}
+
+/// Only the version field, read before parsing the rest of the document
+#[derive(Deserialize)]
+struct BackupVersion {
+    version: Option<u32>,
+}
+
+/// Service for exporting and importing product backups
+pub struct BackupService;
+
+impl BackupService {
+    /// Build a backup of all products, their retailers and retailer links
+    pub async fn export(conn: &DatabaseConnection) -> Result<ProductBackup, AppError> {
+        let products = ProductRepository::find_all_including_archived(conn).await?;
+        let mut retailers: Vec<RetailerBackup> = Vec::new();
+        let mut seen_retailers = HashSet::new();
+        let mut product_retailers = Vec::new();
+
+        for product in &products {
+            for link in ProductRetailerRepository::find_by_product_id(conn, product.id).await? {
+                if seen_retailers.insert(link.retailer_id) {
+                    let retailer = RetailerRepository::find_by_id(conn, link.retailer_id)
+                        .await?
+                        .ok_or_else(|| {
+                            AppError::NotFound(format!("Retailer not found: {}", link.retailer_id))
+                        })?;
+                    retailers.push(RetailerBackup::from(&retailer));
+                }
+                product_retailers.push(ProductRetailerBackup::from(&link));
+            }
+        }
+
+        Ok(ProductBackup {
+            version: BACKUP_VERSION,
+            exported_at: Utc::now(),
+            retailers,
+            products: products.iter().map(ProductBackupEntry::from).collect(),
+            product_retailers,
+        })
+    }
+
+    /// Export all products as a pretty-printed JSON backup document
+    pub async fn export_json(conn: &DatabaseConnection) -> Result<String, AppError> {
+        let backup = Self::export(conn).await?;
+        serde_json::to_string_pretty(&backup)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize backup: {}", e)))
+    }
+
+    /// Parse and validate a JSON backup document
+    pub fn parse(json: &str) -> Result<ProductBackup, AppError> {
+        let probe: BackupVersion = serde_json::from_str(json)
+            .map_err(|e| AppError::Validation(format!("Invalid backup file: {}", e)))?;
+        match probe.version {
+            None => {
+                return Err(AppError::Validation(
+                    "Invalid backup file: missing version".to_string(),
+                ))
+            }
+            Some(version) if version != BACKUP_VERSION => {
+                return Err(AppError::Validation(format!(
+                    "Unsupported backup version {} (expected {})",
+                    version, BACKUP_VERSION
+                )))
+            }
+            Some(_) => {}
+        }
+
+        let backup: ProductBackup = serde_json::from_str(json)
+            .map_err(|e| AppError::Validation(format!("Invalid backup file: {}", e)))?;
+        Self::validate(&backup)?;
+        Ok(backup)
+    }
+
+    /// Import a JSON backup document.
+    ///
+    /// The whole document is validated before anything is written, so an invalid
+    /// file never clears existing products in `Replace` mode.
+    pub async fn import_json(
+        conn: &DatabaseConnection,
+        json: &str,
+        mode: ImportMode,
+    ) -> Result<ImportSummary, AppError> {
+        let backup = Self::parse(json)?;
+        Self::import(conn, backup, mode).await
+    }
+
+    /// Import a parsed and validated backup (see [`Self::import_json`]).
+    ///
+    /// Runs in one transaction, so a failure part-way (including in `Replace`
+    /// mode, after the existing products were deleted) leaves everything as it was.
+    async fn import(
+        conn: &DatabaseConnection,
+        backup: ProductBackup,
+        mode: ImportMode,
+    ) -> Result<ImportSummary, AppError> {
+        let txn = conn.begin().await?;
+
+        if mode == ImportMode::Replace {
+            let deleted = ProductRepository::delete_all(&txn).await?;
+            log::info!("Backup import: removing {} existing products", deleted);
+        }
+
+        // Retailers are deduplicated by domain, so backup IDs map to local IDs
+        let mut retailer_ids = HashMap::new();
+        for retailer in &backup.retailers {
+            let local =
+                RetailerRepository::find_or_create_by_domain(&txn, &retailer.domain).await?;
+            retailer_ids.insert(retailer.id, local.id);
+        }
+
+        let mut summary = ImportSummary::default();
+        let mut imported_products = HashSet::new();
+        for product in backup.products {
+            if ProductRepository::find_by_id(&txn, product.id)
+                .await?
+                .is_some()
+            {
+                summary.products_skipped += 1;
+                continue;
+            }
+            Self::create_product(&txn, product.clone()).await?;
+            imported_products.insert(product.id);
+            summary.products_imported += 1;
+        }
+
+        for link in backup.product_retailers {
+            if !imported_products.contains(&link.product_id) {
+                continue;
+            }
+            let retailer_id = *retailer_ids.get(&link.retailer_id).ok_or_else(|| {
+                AppError::Validation(format!(
+                    "Invalid backup file: retailer link references unknown retailer {}",
+                    link.retailer_id
+                ))
+            })?;
+            ProductRetailerRepository::create(
+                &txn,
+                Uuid::new_v4(),
+                retailer_id,
+                CreateProductRetailerParams {
+                    product_id: link.product_id,
+                    url: link.url,
+                    label: link.label,
+                },
+            )
+            .await?;
+            summary.retailer_links_imported += 1;
+        }
+
+        txn.commit().await?;
+        Ok(summary)
+    }
+
+    async fn create_product<C: ConnectionTrait>(
+        conn: &C,
+        product: ProductBackupEntry,
+    ) -> Result<(), AppError> {
+        let created = ProductRepository::create(
+            conn,
+            product.id,
+            CreateProductRepoParams {
+                name: product.name,
+                url: product.url,
+                description: product.description,
+                notes: product.notes,
+                check_interval_minutes: product.check_interval_minutes,
+                target_price_minor_units: product.target_price_minor_units,
+                target_price_currency: product.target_price_currency,
+            },
+        )
+        .await?;
+
+        // Fields that aren't set on creation
+        if product.currency.is_some()
+            || product.is_paused
+            || product.is_archived
+            || !product.track_price
+            || product.currency_locked
+        {
+            ProductRepository::update(
+                conn,
+                created,
+                ProductUpdateInput {
+                    currency: Some(product.currency),
+                    is_paused: Some(product.is_paused),
+                    is_archived: Some(product.is_archived),
+                    track_price: Some(product.track_price),
+                    currency_locked: Some(product.currency_locked),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Check that the backup is internally consistent
+    fn validate(backup: &ProductBackup) -> Result<(), AppError> {
+        let mut product_ids = HashSet::new();
+        for product in &backup.products {
+            if product.name.trim().is_empty() {
+                return Err(AppError::Validation(format!(
+                    "Invalid backup file: product {} has no name",
+                    product.id
+                )));
+            }
+            if !product_ids.insert(product.id) {
+                return Err(AppError::Validation(format!(
+                    "Invalid backup file: duplicate product {}",
+                    product.id
+                )));
+            }
+        }
+
+        let retailer_ids: HashSet<Uuid> = backup.retailers.iter().map(|r| r.id).collect();
+        for link in &backup.product_retailers {
+            if !product_ids.contains(&link.product_id) {
+                return Err(AppError::Validation(format!(
+                    "Invalid backup file: retailer link references unknown product {}",
+                    link.product_id
+                )));
+            }
+            if !retailer_ids.contains(&link.retailer_id) {
+                return Err(AppError::Validation(format!(
+                    "Invalid backup file: retailer link references unknown retailer {}",
+                    link.retailer_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backup_json(version: serde_json::Value) -> String {
+        serde_json::json!({
+            "version": version,
+            "exported_at": "2026-02-21T00:00:00Z",
+            "retailers": [],
+            "products": [],
+            "product_retailers": []
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_parse_accepts_current_version() {
+        let backup = BackupService::parse(&backup_json(serde_json::json!(1))).unwrap();
+        assert_eq!(backup.version, BACKUP_VERSION);
+        assert!(backup.products.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_other_versions() {
+        let result = BackupService::parse(&backup_json(serde_json::json!(2)));
+        assert!(
+            matches!(result, Err(AppError::Validation(msg)) if msg.contains("Unsupported backup version 2"))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_version() {
+        let result = BackupService::parse(r#"{"products": []}"#);
+        assert!(
+            matches!(result, Err(AppError::Validation(msg)) if msg.contains("missing version"))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_json() {
+        assert!(matches!(
+            BackupService::parse("not json"),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_link_to_unknown_product() {
+        let json = serde_json::json!({
+            "version": 1,
+            "exported_at": "2026-02-21T00:00:00Z",
+            "retailers": [{"id": Uuid::nil(), "domain": "example.com", "name": "example.com"}],
+            "products": [],
+            "product_retailers": [{
+                "product_id": Uuid::new_v4(),
+                "retailer_id": Uuid::nil(),
+                "url": "https://example.com/p",
+                "label": null
+            }]
+        })
+        .to_string();
+
+        let result = BackupService::parse(&json);
+        assert!(
+            matches!(result, Err(AppError::Validation(msg)) if msg.contains("unknown product"))
+        );
+    }
+
+    #[test]
+    fn test_parse_defaults_missing_product_flags() {
+        let json = serde_json::json!({
+            "version": 1,
+            "exported_at": "2026-02-21T00:00:00Z",
+            "retailers": [],
+            "products": [{
+                "id": Uuid::new_v4(),
+                "name": "Widget",
+                "url": null,
+                "description": null,
+                "notes": null,
+                "currency": null,
+                "check_interval_minutes": null,
+                "target_price_minor_units": null,
+                "target_price_currency": null,
+                "is_paused": false
+            }],
+            "product_retailers": []
+        })
+        .to_string();
+
+        let backup = BackupService::parse(&json).unwrap();
+        assert!(!backup.products[0].is_archived);
+        assert!(backup.products[0].track_price);
+        assert!(!backup.products[0].currency_locked);
+    }
+
+    #[test]
+    fn test_import_mode_deserializes_snake_case() {
+        let mode: ImportMode = serde_json::from_str("\"replace\"").unwrap();
+        assert_eq!(mode, ImportMode::Replace);
+        let mode: ImportMode = serde_json::from_str("\"merge\"").unwrap();
+        assert_eq!(mode, ImportMode::Merge);
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_utils::setup_availability_db;
+
+    async fn create_product(conn: &DatabaseConnection, name: &str) -> ProductModel {
+        ProductRepository::create(
+            conn,
+            Uuid::new_v4(),
+            CreateProductRepoParams {
+                name: name.to_string(),
+                url: None,
+                description: Some(format!("{} description", name)),
+                notes: None,
+                check_interval_minutes: Some(120),
+                target_price_minor_units: Some(4999),
+                target_price_currency: Some("AUD".to_string()),
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn link(conn: &DatabaseConnection, product_id: Uuid, domain: &str, label: Option<&str>) {
+        let retailer = RetailerRepository::find_or_create_by_domain(conn, domain)
+            .await
+            .unwrap();
+        ProductRetailerRepository::create(
+            conn,
+            Uuid::new_v4(),
+            retailer.id,
+            CreateProductRetailerParams {
+                product_id,
+                url: format!("https://{}/item", domain),
+                label: label.map(str::to_string),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Two products: one tracked at two retailers (one shared), one paused,
+    /// stock-only and with a locked currency
+    async fn seed(conn: &DatabaseConnection) {
+        let console = create_product(conn, "Console").await;
+        link(conn, console.id, "shop-a.com", Some("Bundle")).await;
+        link(conn, console.id, "shop-b.com", None).await;
+
+        let widget = create_product(conn, "Widget").await;
+        link(conn, widget.id, "shop-a.com", None).await;
+        ProductRepository::update(
+            conn,
+            widget,
+            ProductUpdateInput {
+                currency: Some(Some("AUD".to_string())),
+                is_paused: Some(true),
+                track_price: Some(false),
+                currency_locked: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Backup content without the export timestamp, for comparing exports
+    fn content(backup: ProductBackup) -> ProductBackup {
+        ProductBackup {
+            exported_at: DateTime::<Utc>::default(),
+            ..backup
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_collects_products_retailers_and_links() {
+        let conn = setup_availability_db().await;
+        seed(&conn).await;
+
+        let backup = BackupService::export(&conn).await.unwrap();
+        assert_eq!(backup.version, BACKUP_VERSION);
+        assert_eq!(backup.products.len(), 2);
+        assert_eq!(backup.products[0].name, "Console");
+        assert_eq!(backup.products[0].target_price_minor_units, Some(4999));
+        assert!(backup.products[1].is_paused);
+        assert!(backup.products[0].track_price);
+        assert!(!backup.products[1].track_price);
+        assert!(!backup.products[0].currency_locked);
+        assert!(backup.products[1].currency_locked);
+        assert_eq!(backup.retailers.len(), 2);
+        assert_eq!(backup.product_retailers.len(), 3);
+        assert_eq!(backup.product_retailers[0].label.as_deref(), Some("Bundle"));
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_into_fresh_database() {
+        let source = setup_availability_db().await;
+        seed(&source).await;
+        let exported = BackupService::export(&source).await.unwrap();
+        let json = BackupService::export_json(&source).await.unwrap();
+
+        let target = setup_availability_db().await;
+        let summary = BackupService::import_json(&target, &json, ImportMode::Merge)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            summary,
+            ImportSummary {
+                products_imported: 2,
+                products_skipped: 0,
+                retailer_links_imported: 3,
+            }
+        );
+
+        let mut reexported = BackupService::export(&target).await.unwrap();
+        // Retailers are recreated with new IDs in the fresh database
+        let retailer_ids: HashMap<Uuid, Uuid> = reexported
+            .retailers
+            .iter()
+            .zip(&exported.retailers)
+            .map(|(new, old)| (new.id, old.id))
+            .collect();
+        for retailer in &mut reexported.retailers {
+            retailer.id = retailer_ids[&retailer.id];
+        }
+        for link in &mut reexported.product_retailers {
+            link.retailer_id = retailer_ids[&link.retailer_id];
+        }
+
+        assert_eq!(content(reexported), content(exported));
+    }
+
+    #[tokio::test]
+    async fn test_merge_skips_existing_products() {
+        let conn = setup_availability_db().await;
+        seed(&conn).await;
+        let json = BackupService::export_json(&conn).await.unwrap();
+
+        let summary = BackupService::import_json(&conn, &json, ImportMode::Merge)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.products_imported, 0);
+        assert_eq!(summary.products_skipped, 2);
+        assert_eq!(summary.retailer_links_imported, 0);
+        assert_eq!(ProductRepository::find_all(&conn).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_replace_removes_products_not_in_backup() {
+        let source = setup_availability_db().await;
+        seed(&source).await;
+        let json = BackupService::export_json(&source).await.unwrap();
+
+        let target = setup_availability_db().await;
+        let existing = create_product(&target, "Old product").await;
+
+        let summary = BackupService::import_json(&target, &json, ImportMode::Replace)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.products_imported, 2);
+        let products = ProductRepository::find_all(&target).await.unwrap();
+        assert_eq!(products.len(), 2);
+        assert!(products.iter().all(|p| p.id != existing.id));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_backup_leaves_products_untouched() {
+        let conn = setup_availability_db().await;
+        create_product(&conn, "Keep me").await;
+
+        let result = BackupService::import_json(
+            &conn,
+            r#"{"version": 99, "products": []}"#,
+            ImportMode::Replace,
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert_eq!(ProductRepository::find_all(&conn).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_replace_rolls_back() {
+        let source = setup_availability_db().await;
+        seed(&source).await;
+        let mut backup = BackupService::export(&source).await.unwrap();
+        // Fails on the links, after the products were replaced
+        backup.retailers.clear();
+
+        let conn = setup_availability_db().await;
+        let existing = create_product(&conn, "Keep me").await;
+
+        let result = BackupService::import(&conn, backup, ImportMode::Replace).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        let products = ProductRepository::find_all(&conn).await.unwrap();
+        assert_eq!(products.len(), 1);
+        assert_eq!(products[0].id, existing.id);
+    }
+}
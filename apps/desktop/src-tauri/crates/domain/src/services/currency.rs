@@ -1,4 +1,5 @@
-//! Currency exponent utilities for converting between major and minor units.
+//! Currency exponent utilities for converting between major and minor units,
+//! and for formatting prices for display.
 
 /// Zero-decimal currencies (no fractional unit)
 const ZERO_DECIMAL_CURRENCIES: &[&str] = &["JPY", "KRW", "VND"];
@@ -34,6 +35,62 @@ pub fn minor_unit_multiplier(code: &str) -> i64 {
     10_i64.pow(currency_exponent(code))
 }
 
+/// Symbols for common currencies; others are prefixed with their ISO code
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+    ("USD", "$"),
+    ("AUD", "A$"),
+    ("CAD", "CA$"),
+    ("NZD", "NZ$"),
+    ("HKD", "HK$"),
+    ("SGD", "S$"),
+    ("EUR", "€"),
+    ("GBP", "£"),
+    ("JPY", "¥"),
+    ("CNY", "CN¥"),
+    ("KRW", "₩"),
+    ("INR", "₹"),
+];
+
+/// Format a price in minor units for display, e.g. `$12.34`, `¥1,500` or `KWD 1.250`.
+///
+/// Uses `currency_exponent` for the number of decimal places and groups
+/// thousands with commas.
+pub fn format_price(minor_units: i64, currency: &str) -> String {
+    let code = currency.to_uppercase();
+    let exponent = currency_exponent(&code);
+    let divisor = 10_u64.pow(exponent);
+    let abs = minor_units.unsigned_abs();
+
+    let whole = group_thousands(abs / divisor);
+    let amount = if exponent == 0 {
+        whole
+    } else {
+        format!(
+            "{}.{:0width$}",
+            whole,
+            abs % divisor,
+            width = exponent as usize
+        )
+    };
+
+    let sign = if minor_units < 0 { "-" } else { "" };
+    match CURRENCY_SYMBOLS.iter().find(|(c, _)| *c == code) {
+        Some((_, symbol)) => format!("{}{}{}", sign, symbol, amount),
+        None => format!("{}{} {}", sign, code, amount),
+    }
+}
+
+/// Insert a comma between each group of three digits
+fn group_thousands(value: u64) -> String {
+    let reversed: Vec<char> = value.to_string().chars().rev().collect();
+    let groups: Vec<String> = reversed
+        .chunks(3)
+        .rev()
+        .map(|group| group.iter().rev().collect())
+        .collect();
+    groups.join(",")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +152,29 @@ mod tests {
         assert_eq!(minor_unit_multiplier("KWD"), 1000);
         assert_eq!(minor_unit_multiplier("BHD"), 1000);
     }
+
+    #[test]
+    fn test_format_price_usd() {
+        assert_eq!(format_price(1234, "USD"), "$12.34");
+        assert_eq!(format_price(5, "USD"), "$0.05");
+        assert_eq!(format_price(123456789, "usd"), "$1,234,567.89");
+    }
+
+    #[test]
+    fn test_format_price_jpy() {
+        assert_eq!(format_price(1500, "JPY"), "¥1,500");
+        assert_eq!(format_price(980, "JPY"), "¥980");
+    }
+
+    #[test]
+    fn test_format_price_kwd() {
+        assert_eq!(format_price(1250, "KWD"), "KWD 1.250");
+        assert_eq!(format_price(1234567, "KWD"), "KWD 1,234.567");
+    }
+
+    #[test]
+    fn test_format_price_unknown_currency_and_negative() {
+        assert_eq!(format_price(999, "CHF"), "CHF 9.99");
+        assert_eq!(format_price(-250, "AUD"), "-A$2.50");
+    }
 }
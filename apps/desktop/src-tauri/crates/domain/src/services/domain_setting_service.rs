@@ -4,14 +4,24 @@
 //! (availability checking, headless browser, etc.) and would be
 //! removed when creating a new project from the template.
 
-use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Datelike, Utc, Weekday};
+use reqwest::header::{HeaderName, HeaderValue};
 use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 use product_stalker_core::entities::app_setting::SettingScope;
 use product_stalker_core::repositories::{ScopedSettingsReader, SettingsHelpers};
+use product_stalker_core::services::notification_helpers::{to_display_time, QuietHours};
 use product_stalker_core::AppError;
 
+use super::scraper::{
+    FetchOptions, FetchTimeouts, ProductGroupStrategy, DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+};
+
 /// Setting keys for domain-specific settings
 pub mod keys {
     pub const BACKGROUND_CHECK_ENABLED: &str = "background_check_enabled";
@@ -19,6 +29,28 @@ pub mod keys {
     pub const ENABLE_HEADLESS_BROWSER: &str = "enable_headless_browser";
     pub const ALLOW_MANUAL_VERIFICATION: &str = "allow_manual_verification";
     pub const SESSION_CACHE_DURATION_DAYS: &str = "session_cache_duration_days";
+    pub const BULK_CHECK_DELAY_MS: &str = "bulk_check_delay_ms";
+    pub const HEADLESS_PROFILE_REUSE: &str = "headless_profile_reuse";
+    pub const HTTP_TIMEOUT_SECS: &str = "http_timeout_secs";
+    pub const HEADLESS_TIMEOUT_SECS: &str = "headless_timeout_secs";
+    pub const PRICE_DROP_THRESHOLD_PERCENT: &str = "price_drop_threshold_percent";
+    pub const BULK_CHECK_CONCURRENCY: &str = "bulk_check_concurrency";
+    pub const FORCE_HEADLESS_DOMAINS: &str = "force_headless_domains";
+    pub const DATA_RETENTION_DAYS: &str = "data_retention_days";
+    pub const VALIDATE_URL_ON_CREATE: &str = "validate_url_on_create";
+    pub const MIN_PLAUSIBLE_PRICE_PERCENT: &str = "min_plausible_price_percent";
+    pub const RESPECT_ROBOTS_TXT: &str = "respect_robots_txt";
+    pub const PRODUCT_GROUP_STRATEGY: &str = "product_group_strategy";
+    pub const HEADLESS_BROWSER_PATH: &str = "headless_browser_path";
+    pub const ROTATE_USER_AGENT: &str = "rotate_user_agent";
+    pub const BACKGROUND_CHECK_ACTIVE_HOURS_START: &str = "background_check_active_hours_start";
+    pub const BACKGROUND_CHECK_ACTIVE_HOURS_END: &str = "background_check_active_hours_end";
+    pub const BACKGROUND_CHECK_DAYS: &str = "background_check_days";
+    pub const INSECURE_HOSTS: &str = "insecure_hosts";
+    pub const MAX_REDIRECTS: &str = "max_redirects";
+    pub const MAX_JSON_LD_BLOCK_KB: &str = "max_json_ld_block_kb";
+    pub const DOMAIN_REQUEST_OVERRIDES: &str = "domain_request_overrides";
+    pub const HEADLESS_WAIT_SELECTORS: &str = "headless_wait_selectors";
 }
 
 /// Default values for domain-specific settings
@@ -28,6 +60,29 @@ pub mod defaults {
     pub const ENABLE_HEADLESS_BROWSER: bool = true;
     pub const ALLOW_MANUAL_VERIFICATION: bool = false;
     pub const SESSION_CACHE_DURATION_DAYS: i32 = 14;
+    pub const BULK_CHECK_DELAY_MS: i32 = 500;
+    pub const HEADLESS_PROFILE_REUSE: bool = true;
+    pub const HTTP_TIMEOUT_SECS: i32 = 30;
+    pub const HEADLESS_TIMEOUT_SECS: i32 = 60;
+    pub const PRICE_DROP_THRESHOLD_PERCENT: i32 = 5;
+    pub const BULK_CHECK_CONCURRENCY: i32 = 1;
+    pub const FORCE_HEADLESS_DOMAINS: &str = "";
+    pub const DATA_RETENTION_DAYS: i32 = 0;
+    pub const VALIDATE_URL_ON_CREATE: bool = false;
+    pub const MIN_PLAUSIBLE_PRICE_PERCENT: i32 = 10;
+    pub const RESPECT_ROBOTS_TXT: bool = true;
+    pub const PRODUCT_GROUP_STRATEGY: &str = "first";
+    pub const HEADLESS_BROWSER_PATH: &str = "";
+    pub const ROTATE_USER_AGENT: bool = true;
+    /// Empty = background checks run around the clock
+    pub const BACKGROUND_CHECK_ACTIVE_HOURS_START: &str = "";
+    pub const BACKGROUND_CHECK_ACTIVE_HOURS_END: &str = "";
+    /// Empty = background checks run every day
+    pub const BACKGROUND_CHECK_DAYS: &str = "";
+    pub const INSECURE_HOSTS: &str = "";
+    /// reqwest's own redirect limit
+    pub const MAX_REDIRECTS: i32 = 10;
+    pub const MAX_JSON_LD_BLOCK_KB: i32 = (super::DEFAULT_MAX_JSON_LD_BLOCK_BYTES / 1024) as i32;
 }
 
 /// Domain-specific settings
@@ -38,6 +93,124 @@ pub struct DomainSettings {
     pub enable_headless_browser: bool,
     pub allow_manual_verification: bool,
     pub session_cache_duration_days: i32,
+    /// Minimum interval between requests to the same host
+    pub bulk_check_delay_ms: i32,
+    /// Keep a persistent headless browser profile per domain, so cookies from a
+    /// passed bot challenge are reused on later checks
+    pub headless_profile_reuse: bool,
+    /// Timeout for plain HTTP page fetches
+    pub http_timeout_secs: i32,
+    /// Page load timeout for the headless browser
+    pub headless_timeout_secs: i32,
+    /// Minimum drop (percent of yesterday's average price) that counts as a price drop
+    pub price_drop_threshold_percent: i32,
+    /// Maximum number of checks running at once in a bulk operation
+    /// (checks against the same host never overlap)
+    pub bulk_check_concurrency: i32,
+    /// Comma-separated hosts that always block plain HTTP, so checks skip
+    /// straight to the headless browser (subdomains match too)
+    pub force_headless_domains: String,
+    /// Days of check history to keep (0 = keep forever); the latest check per
+    /// product is never pruned
+    pub data_retention_days: i32,
+    /// Scrape retailer URLs once when they're added, warning when no product
+    /// data can be found on the page
+    pub validate_url_on_create: bool,
+    /// Scraped prices below this percent of the recent average are ignored as
+    /// site glitches instead of being reported as price drops (0 = off)
+    pub min_plausible_price_percent: i32,
+    /// Skip pages a site's robots.txt disallows and honor its `Crawl-delay`
+    pub respect_robots_txt: bool,
+    /// Which variant a multi-variant product reports when the URL doesn't pick
+    /// one: "first" or "cheapest_in_stock"
+    pub product_group_strategy: String,
+    /// Chrome/Chromium binary for the headless browser (empty = search the
+    /// usual install locations)
+    pub headless_browser_path: String,
+    /// Send each host a User-Agent (and matching client hints) picked from a
+    /// pool of current desktop browsers instead of always the same one
+    pub rotate_user_agent: bool,
+    /// Start of the daily hours background checks run in ("HH:MM" in the
+    /// display timezone, empty = all day)
+    pub background_check_active_hours_start: String,
+    /// End of the active hours ("HH:MM", exclusive; may be before the start)
+    pub background_check_active_hours_end: String,
+    /// Comma-separated days background checks run on ("mon,tue,...", empty = every day)
+    pub background_check_days: String,
+    /// Comma-separated hosts whose TLS certificates aren't verified, for self-hosted
+    /// stores with self-signed certs (exact hosts only; subdomains don't match)
+    pub insecure_hosts: String,
+    /// Most redirects followed for one page fetch (0 = don't follow any)
+    pub max_redirects: i32,
+    /// JSON-LD blocks larger than this (in KB) are skipped instead of parsed
+    pub max_json_ld_block_kb: i32,
+}
+
+impl DomainSettings {
+    /// Fetch timeouts to use for availability checks
+    pub fn fetch_timeouts(&self) -> FetchTimeouts {
+        FetchTimeouts {
+            http_secs: self.http_timeout_secs as u64,
+            headless_secs: self.headless_timeout_secs as u64,
+            min_host_interval_ms: self.bulk_check_delay_ms as u64,
+        }
+    }
+
+    /// Options for fetching product pages during availability checks
+    pub fn fetch_options(&self) -> FetchOptions {
+        FetchOptions {
+            enable_headless: self.enable_headless_browser,
+            allow_manual_verification: self.allow_manual_verification,
+            headless_profile_reuse: self.headless_profile_reuse,
+            session_cache_duration_days: self.session_cache_duration_days,
+            timeouts: self.fetch_timeouts(),
+            force_headless_domains: self.force_headless_hosts(),
+            respect_robots_txt: self.respect_robots_txt,
+            product_group_strategy: ProductGroupStrategy::from_setting(
+                &self.product_group_strategy,
+            ),
+            headless_browser_path: self.headless_browser_path(),
+            rotate_user_agent: self.rotate_user_agent,
+            insecure_hosts: self.insecure_host_list(),
+            max_redirects: self.max_redirects.max(0) as usize,
+            max_json_ld_block_bytes: self.max_json_ld_block_kb.max(1) as usize * 1024,
+        }
+    }
+
+    /// When background checks may run, from the active hours and days settings
+    pub fn background_check_window(&self) -> BackgroundCheckWindow {
+        BackgroundCheckWindow {
+            hours: QuietHours::from_settings(
+                &self.background_check_active_hours_start,
+                &self.background_check_active_hours_end,
+            ),
+            days: parse_weekdays(&self.background_check_days),
+        }
+    }
+
+    /// The configured browser binary, or `None` to search the system
+    pub fn headless_browser_path(&self) -> Option<PathBuf> {
+        let path = self.headless_browser_path.trim();
+        (!path.is_empty()).then(|| PathBuf::from(path))
+    }
+
+    /// Parse `force_headless_domains` into lowercase hosts, skipping blank entries
+    pub fn force_headless_hosts(&self) -> Vec<String> {
+        parse_host_list(&self.force_headless_domains)
+    }
+
+    /// Parse `insecure_hosts` into lowercase hosts, skipping blank entries
+    pub fn insecure_host_list(&self) -> Vec<String> {
+        parse_host_list(&self.insecure_hosts)
+    }
+}
+
+/// Split a comma-separated host list into lowercase hosts, skipping blank entries
+fn parse_host_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|host| host.trim().to_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect()
 }
 
 impl Default for DomainSettings {
@@ -48,10 +221,67 @@ impl Default for DomainSettings {
             enable_headless_browser: defaults::ENABLE_HEADLESS_BROWSER,
             allow_manual_verification: defaults::ALLOW_MANUAL_VERIFICATION,
             session_cache_duration_days: defaults::SESSION_CACHE_DURATION_DAYS,
+            bulk_check_delay_ms: defaults::BULK_CHECK_DELAY_MS,
+            headless_profile_reuse: defaults::HEADLESS_PROFILE_REUSE,
+            http_timeout_secs: defaults::HTTP_TIMEOUT_SECS,
+            headless_timeout_secs: defaults::HEADLESS_TIMEOUT_SECS,
+            price_drop_threshold_percent: defaults::PRICE_DROP_THRESHOLD_PERCENT,
+            bulk_check_concurrency: defaults::BULK_CHECK_CONCURRENCY,
+            force_headless_domains: defaults::FORCE_HEADLESS_DOMAINS.to_string(),
+            data_retention_days: defaults::DATA_RETENTION_DAYS,
+            validate_url_on_create: defaults::VALIDATE_URL_ON_CREATE,
+            min_plausible_price_percent: defaults::MIN_PLAUSIBLE_PRICE_PERCENT,
+            respect_robots_txt: defaults::RESPECT_ROBOTS_TXT,
+            product_group_strategy: defaults::PRODUCT_GROUP_STRATEGY.to_string(),
+            headless_browser_path: defaults::HEADLESS_BROWSER_PATH.to_string(),
+            rotate_user_agent: defaults::ROTATE_USER_AGENT,
+            background_check_active_hours_start: defaults::BACKGROUND_CHECK_ACTIVE_HOURS_START
+                .to_string(),
+            background_check_active_hours_end: defaults::BACKGROUND_CHECK_ACTIVE_HOURS_END
+                .to_string(),
+            background_check_days: defaults::BACKGROUND_CHECK_DAYS.to_string(),
+            insecure_hosts: defaults::INSECURE_HOSTS.to_string(),
+            max_redirects: defaults::MAX_REDIRECTS,
+            max_json_ld_block_kb: defaults::MAX_JSON_LD_BLOCK_KB,
         }
     }
 }
 
+/// Hours and days of the week during which background checks run.
+///
+/// The active hours follow the same rules as notification quiet hours (start
+/// inclusive, end exclusive, may cross midnight). Days are matched against the
+/// local date at the time of the check, so with an overnight window the hours
+/// after midnight belong to the next day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackgroundCheckWindow {
+    /// `None` = all day
+    hours: Option<QuietHours>,
+    /// Empty = every day
+    days: Vec<Weekday>,
+}
+
+impl BackgroundCheckWindow {
+    /// Whether background checks may run at `now` in the given display timezone
+    pub fn is_open_at(&self, now: DateTime<Utc>, timezone: &str) -> bool {
+        let local = to_display_time(now, timezone);
+        let day_allowed = self.days.is_empty() || self.days.contains(&local.weekday());
+        let hour_allowed = match self.hours {
+            Some(hours) => hours.contains(local.time()),
+            None => true,
+        };
+        day_allowed && hour_allowed
+    }
+}
+
+/// Parse comma-separated weekday names ("mon", "Tuesday", ...), skipping
+/// unrecognised entries
+fn parse_weekdays(raw: &str) -> Vec<Weekday> {
+    raw.split(',')
+        .filter_map(|day| day.trim().parse::<Weekday>().ok())
+        .collect()
+}
+
 /// Parameters for updating domain settings (all fields optional for partial updates)
 #[derive(Default, Deserialize)]
 pub struct UpdateDomainSettingsParams {
@@ -60,6 +290,44 @@ pub struct UpdateDomainSettingsParams {
     pub enable_headless_browser: Option<bool>,
     pub allow_manual_verification: Option<bool>,
     pub session_cache_duration_days: Option<i32>,
+    pub bulk_check_delay_ms: Option<i32>,
+    pub headless_profile_reuse: Option<bool>,
+    pub http_timeout_secs: Option<i32>,
+    pub headless_timeout_secs: Option<i32>,
+    pub price_drop_threshold_percent: Option<i32>,
+    pub bulk_check_concurrency: Option<i32>,
+    pub force_headless_domains: Option<String>,
+    pub data_retention_days: Option<i32>,
+    pub validate_url_on_create: Option<bool>,
+    pub min_plausible_price_percent: Option<i32>,
+    pub respect_robots_txt: Option<bool>,
+    pub product_group_strategy: Option<String>,
+    pub headless_browser_path: Option<String>,
+    pub rotate_user_agent: Option<bool>,
+    pub background_check_active_hours_start: Option<String>,
+    pub background_check_active_hours_end: Option<String>,
+    pub background_check_days: Option<String>,
+    pub insecure_hosts: Option<String>,
+    pub max_redirects: Option<i32>,
+    pub max_json_ld_block_kb: Option<i32>,
+}
+
+/// Custom headers and cookies sent with every plain HTTP request to one host,
+/// e.g. to see member prices on stores that require a login.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomainRequestOverrides {
+    /// Header name -> value; replaces the default browser header of the same name
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Cookie name -> value; sent alongside any verified session cookies
+    #[serde(default)]
+    pub cookies: BTreeMap<String, String>,
+}
+
+impl DomainRequestOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty() && self.cookies.is_empty()
+    }
 }
 
 /// Cached domain settings for bulk operations.
@@ -127,6 +395,66 @@ impl DomainSettingsCache {
         self.settings.session_cache_duration_days
     }
 
+    /// Get the minimum interval between requests to the same host (milliseconds)
+    pub fn bulk_check_delay_ms(&self) -> i32 {
+        self.settings.bulk_check_delay_ms
+    }
+
+    /// Check if headless browser profiles are kept per domain
+    pub fn headless_profile_reuse(&self) -> bool {
+        self.settings.headless_profile_reuse
+    }
+
+    /// Get the fetch timeouts for availability checks
+    pub fn fetch_timeouts(&self) -> FetchTimeouts {
+        self.settings.fetch_timeouts()
+    }
+
+    /// Get the price drop notification threshold (percent)
+    pub fn price_drop_threshold_percent(&self) -> i32 {
+        self.settings.price_drop_threshold_percent
+    }
+
+    /// Get the maximum number of concurrent checks in a bulk operation
+    pub fn bulk_check_concurrency(&self) -> usize {
+        self.settings.bulk_check_concurrency.max(1) as usize
+    }
+
+    /// Get the options for fetching product pages
+    pub fn fetch_options(&self) -> FetchOptions {
+        self.settings.fetch_options()
+    }
+
+    /// Get the number of days of check history to keep (0 = forever)
+    pub fn data_retention_days(&self) -> i32 {
+        self.settings.data_retention_days
+    }
+
+    /// Get whether retailer URLs are scraped once when added
+    pub fn validate_url_on_create(&self) -> bool {
+        self.settings.validate_url_on_create
+    }
+
+    /// Get the lowest percent of the recent average price accepted as plausible
+    pub fn min_plausible_price_percent(&self) -> i32 {
+        self.settings.min_plausible_price_percent
+    }
+
+    /// Get whether robots.txt rules are respected when fetching pages
+    pub fn respect_robots_txt(&self) -> bool {
+        self.settings.respect_robots_txt
+    }
+
+    /// Get which ProductGroup variant is reported when the URL doesn't select one
+    pub fn product_group_strategy(&self) -> ProductGroupStrategy {
+        ProductGroupStrategy::from_setting(&self.settings.product_group_strategy)
+    }
+
+    /// Get whether page requests rotate between browser User-Agents
+    pub fn rotate_user_agent(&self) -> bool {
+        self.settings.rotate_user_agent
+    }
+
     /// Get when these settings were loaded
     pub fn loaded_at(&self) -> DateTime<Utc> {
         self.loaded_at
@@ -178,6 +506,91 @@ impl DomainSettingService {
                     defaults::SESSION_CACHE_DURATION_DAYS,
                 )
                 .await?,
+            bulk_check_delay_ms: r
+                .i32(keys::BULK_CHECK_DELAY_MS, defaults::BULK_CHECK_DELAY_MS)
+                .await?,
+            headless_profile_reuse: r
+                .bool(
+                    keys::HEADLESS_PROFILE_REUSE,
+                    defaults::HEADLESS_PROFILE_REUSE,
+                )
+                .await?,
+            http_timeout_secs: r
+                .i32(keys::HTTP_TIMEOUT_SECS, defaults::HTTP_TIMEOUT_SECS)
+                .await?,
+            headless_timeout_secs: r
+                .i32(keys::HEADLESS_TIMEOUT_SECS, defaults::HEADLESS_TIMEOUT_SECS)
+                .await?,
+            price_drop_threshold_percent: r
+                .i32(
+                    keys::PRICE_DROP_THRESHOLD_PERCENT,
+                    defaults::PRICE_DROP_THRESHOLD_PERCENT,
+                )
+                .await?,
+            bulk_check_concurrency: r
+                .i32(
+                    keys::BULK_CHECK_CONCURRENCY,
+                    defaults::BULK_CHECK_CONCURRENCY,
+                )
+                .await?,
+            force_headless_domains: r
+                .string(
+                    keys::FORCE_HEADLESS_DOMAINS,
+                    defaults::FORCE_HEADLESS_DOMAINS,
+                )
+                .await?,
+            data_retention_days: r
+                .i32(keys::DATA_RETENTION_DAYS, defaults::DATA_RETENTION_DAYS)
+                .await?,
+            validate_url_on_create: r
+                .bool(
+                    keys::VALIDATE_URL_ON_CREATE,
+                    defaults::VALIDATE_URL_ON_CREATE,
+                )
+                .await?,
+            min_plausible_price_percent: r
+                .i32(
+                    keys::MIN_PLAUSIBLE_PRICE_PERCENT,
+                    defaults::MIN_PLAUSIBLE_PRICE_PERCENT,
+                )
+                .await?,
+            respect_robots_txt: r
+                .bool(keys::RESPECT_ROBOTS_TXT, defaults::RESPECT_ROBOTS_TXT)
+                .await?,
+            product_group_strategy: r
+                .string(
+                    keys::PRODUCT_GROUP_STRATEGY,
+                    defaults::PRODUCT_GROUP_STRATEGY,
+                )
+                .await?,
+            headless_browser_path: r
+                .string(keys::HEADLESS_BROWSER_PATH, defaults::HEADLESS_BROWSER_PATH)
+                .await?,
+            rotate_user_agent: r
+                .bool(keys::ROTATE_USER_AGENT, defaults::ROTATE_USER_AGENT)
+                .await?,
+            background_check_active_hours_start: r
+                .string(
+                    keys::BACKGROUND_CHECK_ACTIVE_HOURS_START,
+                    defaults::BACKGROUND_CHECK_ACTIVE_HOURS_START,
+                )
+                .await?,
+            background_check_active_hours_end: r
+                .string(
+                    keys::BACKGROUND_CHECK_ACTIVE_HOURS_END,
+                    defaults::BACKGROUND_CHECK_ACTIVE_HOURS_END,
+                )
+                .await?,
+            background_check_days: r
+                .string(keys::BACKGROUND_CHECK_DAYS, defaults::BACKGROUND_CHECK_DAYS)
+                .await?,
+            insecure_hosts: r
+                .string(keys::INSECURE_HOSTS, defaults::INSECURE_HOSTS)
+                .await?,
+            max_redirects: r.i32(keys::MAX_REDIRECTS, defaults::MAX_REDIRECTS).await?,
+            max_json_ld_block_kb: r
+                .i32(keys::MAX_JSON_LD_BLOCK_KB, defaults::MAX_JSON_LD_BLOCK_KB)
+                .await?,
         };
 
         // Clamp interval to valid range in case of direct DB manipulation
@@ -191,6 +604,49 @@ impl DomainSettingService {
             Self::MAX_SESSION_CACHE_DURATION_DAYS,
         );
 
+        // Clamp bulk check delay to valid range
+        settings.bulk_check_delay_ms = settings
+            .bulk_check_delay_ms
+            .clamp(0, Self::MAX_BULK_CHECK_DELAY_MS);
+
+        // Clamp timeouts to valid ranges
+        settings.http_timeout_secs = settings
+            .http_timeout_secs
+            .clamp(Self::MIN_HTTP_TIMEOUT_SECS, Self::MAX_HTTP_TIMEOUT_SECS);
+        settings.headless_timeout_secs = settings.headless_timeout_secs.clamp(
+            Self::MIN_HEADLESS_TIMEOUT_SECS,
+            Self::MAX_HEADLESS_TIMEOUT_SECS,
+        );
+
+        // Clamp price drop threshold to a valid percentage
+        settings.price_drop_threshold_percent = settings
+            .price_drop_threshold_percent
+            .clamp(0, Self::MAX_PRICE_DROP_THRESHOLD_PERCENT);
+
+        // Clamp bulk check concurrency to valid range
+        settings.bulk_check_concurrency = settings
+            .bulk_check_concurrency
+            .clamp(1, Self::MAX_BULK_CHECK_CONCURRENCY);
+
+        // Clamp data retention to valid range
+        settings.data_retention_days = settings
+            .data_retention_days
+            .clamp(0, Self::MAX_DATA_RETENTION_DAYS);
+
+        // Clamp plausible price floor to a valid percentage
+        settings.min_plausible_price_percent = settings
+            .min_plausible_price_percent
+            .clamp(0, Self::MAX_MIN_PLAUSIBLE_PRICE_PERCENT);
+
+        // Clamp redirect limit to valid range
+        settings.max_redirects = settings.max_redirects.clamp(0, Self::MAX_MAX_REDIRECTS);
+
+        // Clamp JSON-LD block limit to valid range
+        settings.max_json_ld_block_kb = settings.max_json_ld_block_kb.clamp(
+            Self::MIN_MAX_JSON_LD_BLOCK_KB,
+            Self::MAX_MAX_JSON_LD_BLOCK_KB,
+        );
+
         Ok(settings)
     }
 
@@ -207,6 +663,77 @@ impl DomainSettingService {
             Self::validate_session_cache_duration(duration)?;
         }
 
+        if let Some(delay) = params.bulk_check_delay_ms {
+            Self::validate_bulk_check_delay(delay)?;
+        }
+
+        if let Some(timeout) = params.http_timeout_secs {
+            Self::validate_http_timeout(timeout)?;
+        }
+
+        if let Some(timeout) = params.headless_timeout_secs {
+            Self::validate_headless_timeout(timeout)?;
+        }
+
+        if let Some(threshold) = params.price_drop_threshold_percent {
+            Self::validate_price_drop_threshold(threshold)?;
+        }
+
+        if let Some(concurrency) = params.bulk_check_concurrency {
+            Self::validate_bulk_check_concurrency(concurrency)?;
+        }
+
+        if let Some(redirects) = params.max_redirects {
+            Self::validate_max_redirects(redirects)?;
+        }
+
+        if let Some(kb) = params.max_json_ld_block_kb {
+            Self::validate_max_json_ld_block_kb(kb)?;
+        }
+
+        if let Some(days) = params.data_retention_days {
+            Self::validate_data_retention(days)?;
+        }
+
+        if let Some(percent) = params.min_plausible_price_percent {
+            Self::validate_min_plausible_price(percent)?;
+        }
+
+        if let Some(ref strategy) = params.product_group_strategy {
+            Self::validate_product_group_strategy(strategy)?;
+        }
+
+        if let Some(ref time) = params.background_check_active_hours_start {
+            Self::validate_active_hours_time(time)?;
+        }
+        if let Some(ref time) = params.background_check_active_hours_end {
+            Self::validate_active_hours_time(time)?;
+        }
+
+        let background_check_days = params
+            .background_check_days
+            .as_deref()
+            .map(Self::normalize_background_check_days)
+            .transpose()?;
+
+        let headless_browser_path = params
+            .headless_browser_path
+            .as_deref()
+            .map(Self::normalize_headless_browser_path)
+            .transpose()?;
+
+        let force_headless_domains = params
+            .force_headless_domains
+            .as_deref()
+            .map(Self::normalize_force_headless_domains)
+            .transpose()?;
+
+        let insecure_hosts = params
+            .insecure_hosts
+            .as_deref()
+            .map(Self::normalize_insecure_hosts)
+            .transpose()?;
+
         let scope = SettingScope::Global;
 
         if let Some(v) = params.background_check_enabled {
@@ -225,10 +752,206 @@ impl DomainSettingService {
         if let Some(v) = params.session_cache_duration_days {
             SettingsHelpers::set_i32(conn, &scope, keys::SESSION_CACHE_DURATION_DAYS, v).await?;
         }
+        if let Some(v) = params.bulk_check_delay_ms {
+            SettingsHelpers::set_i32(conn, &scope, keys::BULK_CHECK_DELAY_MS, v).await?;
+        }
+        if let Some(v) = params.headless_profile_reuse {
+            SettingsHelpers::set_bool(conn, &scope, keys::HEADLESS_PROFILE_REUSE, v).await?;
+        }
+        if let Some(v) = params.http_timeout_secs {
+            SettingsHelpers::set_i32(conn, &scope, keys::HTTP_TIMEOUT_SECS, v).await?;
+        }
+        if let Some(v) = params.headless_timeout_secs {
+            SettingsHelpers::set_i32(conn, &scope, keys::HEADLESS_TIMEOUT_SECS, v).await?;
+        }
+        if let Some(v) = params.price_drop_threshold_percent {
+            SettingsHelpers::set_i32(conn, &scope, keys::PRICE_DROP_THRESHOLD_PERCENT, v).await?;
+        }
+        if let Some(v) = params.bulk_check_concurrency {
+            SettingsHelpers::set_i32(conn, &scope, keys::BULK_CHECK_CONCURRENCY, v).await?;
+        }
+        if let Some(v) = force_headless_domains {
+            SettingsHelpers::set_string(conn, &scope, keys::FORCE_HEADLESS_DOMAINS, &v).await?;
+        }
+        if let Some(v) = params.data_retention_days {
+            SettingsHelpers::set_i32(conn, &scope, keys::DATA_RETENTION_DAYS, v).await?;
+        }
+        if let Some(v) = params.validate_url_on_create {
+            SettingsHelpers::set_bool(conn, &scope, keys::VALIDATE_URL_ON_CREATE, v).await?;
+        }
+        if let Some(v) = params.min_plausible_price_percent {
+            SettingsHelpers::set_i32(conn, &scope, keys::MIN_PLAUSIBLE_PRICE_PERCENT, v).await?;
+        }
+        if let Some(v) = params.respect_robots_txt {
+            SettingsHelpers::set_bool(conn, &scope, keys::RESPECT_ROBOTS_TXT, v).await?;
+        }
+        if let Some(v) = params.product_group_strategy {
+            SettingsHelpers::set_string(conn, &scope, keys::PRODUCT_GROUP_STRATEGY, &v).await?;
+        }
+        if let Some(v) = headless_browser_path {
+            SettingsHelpers::set_string(conn, &scope, keys::HEADLESS_BROWSER_PATH, &v).await?;
+        }
+        if let Some(v) = params.rotate_user_agent {
+            SettingsHelpers::set_bool(conn, &scope, keys::ROTATE_USER_AGENT, v).await?;
+        }
+        if let Some(v) = params.background_check_active_hours_start {
+            SettingsHelpers::set_string(
+                conn,
+                &scope,
+                keys::BACKGROUND_CHECK_ACTIVE_HOURS_START,
+                &v,
+            )
+            .await?;
+        }
+        if let Some(v) = params.background_check_active_hours_end {
+            SettingsHelpers::set_string(conn, &scope, keys::BACKGROUND_CHECK_ACTIVE_HOURS_END, &v)
+                .await?;
+        }
+        if let Some(v) = background_check_days {
+            SettingsHelpers::set_string(conn, &scope, keys::BACKGROUND_CHECK_DAYS, &v).await?;
+        }
+        if let Some(v) = insecure_hosts {
+            SettingsHelpers::set_string(conn, &scope, keys::INSECURE_HOSTS, &v).await?;
+        }
+        if let Some(v) = params.max_redirects {
+            SettingsHelpers::set_i32(conn, &scope, keys::MAX_REDIRECTS, v).await?;
+        }
+        if let Some(v) = params.max_json_ld_block_kb {
+            SettingsHelpers::set_i32(conn, &scope, keys::MAX_JSON_LD_BLOCK_KB, v).await?;
+        }
 
         Self::get(conn).await
     }
 
+    /// Get the request overrides for every host, keyed by lowercase host
+    pub async fn get_request_overrides(
+        conn: &DatabaseConnection,
+    ) -> Result<BTreeMap<String, DomainRequestOverrides>, AppError> {
+        SettingsHelpers::get_json_or(
+            conn,
+            &SettingScope::Global,
+            keys::DOMAIN_REQUEST_OVERRIDES,
+            BTreeMap::new(),
+        )
+        .await
+    }
+
+    /// Get the request overrides for one host, if any are set
+    pub async fn get_request_overrides_for_host(
+        conn: &DatabaseConnection,
+        host: &str,
+    ) -> Result<Option<DomainRequestOverrides>, AppError> {
+        let mut all = Self::get_request_overrides(conn).await?;
+        Ok(all.remove(&host.to_lowercase()))
+    }
+
+    /// Set the request overrides for a host, replacing any existing ones.
+    ///
+    /// Empty overrides remove the host's entry. Header and cookie names and
+    /// values are validated so they can't inject extra headers into requests.
+    pub async fn set_request_overrides(
+        conn: &DatabaseConnection,
+        host: &str,
+        overrides: DomainRequestOverrides,
+    ) -> Result<BTreeMap<String, DomainRequestOverrides>, AppError> {
+        let host = Self::parse_bare_host(host.trim()).ok_or_else(|| {
+            AppError::Validation(format!(
+                "Invalid domain '{}'. Enter a host like example.com",
+                host
+            ))
+        })?;
+        Self::validate_request_overrides(&overrides)?;
+
+        let mut all = Self::get_request_overrides(conn).await?;
+        if overrides.is_empty() {
+            all.remove(&host);
+        } else {
+            all.insert(host, overrides);
+        }
+        SettingsHelpers::set_json(
+            conn,
+            &SettingScope::Global,
+            keys::DOMAIN_REQUEST_OVERRIDES,
+            &all,
+        )
+        .await?;
+
+        Ok(all)
+    }
+
+    /// Get the headless wait selector for every host, keyed by lowercase host
+    pub async fn get_headless_wait_selectors(
+        conn: &DatabaseConnection,
+    ) -> Result<BTreeMap<String, String>, AppError> {
+        SettingsHelpers::get_json_or(
+            conn,
+            &SettingScope::Global,
+            keys::HEADLESS_WAIT_SELECTORS,
+            BTreeMap::new(),
+        )
+        .await
+    }
+
+    /// Get the CSS selector the headless browser waits for on one host, if any
+    pub async fn get_headless_wait_selector_for_host(
+        conn: &DatabaseConnection,
+        host: &str,
+    ) -> Result<Option<String>, AppError> {
+        let mut all = Self::get_headless_wait_selectors(conn).await?;
+        Ok(all.remove(&host.to_lowercase()))
+    }
+
+    /// Set the CSS selector (e.g. `[itemprop=price]`) the headless browser waits
+    /// for before reading a host's pages, for stores that render prices after load.
+    ///
+    /// `None` or a blank selector removes the host's entry.
+    pub async fn set_headless_wait_selector(
+        conn: &DatabaseConnection,
+        host: &str,
+        selector: Option<String>,
+    ) -> Result<BTreeMap<String, String>, AppError> {
+        let host = Self::parse_bare_host(host.trim()).ok_or_else(|| {
+            AppError::Validation(format!(
+                "Invalid domain '{}'. Enter a host like example.com",
+                host
+            ))
+        })?;
+        let selector = selector
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        if let Some(selector) = &selector {
+            Self::validate_wait_selector(selector)?;
+        }
+
+        let mut all = Self::get_headless_wait_selectors(conn).await?;
+        match selector {
+            Some(selector) => all.insert(host, selector),
+            None => all.remove(&host),
+        };
+        SettingsHelpers::set_json(
+            conn,
+            &SettingScope::Global,
+            keys::HEADLESS_WAIT_SELECTORS,
+            &all,
+        )
+        .await?;
+
+        Ok(all)
+    }
+
+    /// Longest accepted headless wait selector, in characters
+    const MAX_WAIT_SELECTOR_LEN: usize = 200;
+
+    /// Headers that can't be overridden: they describe the connection or body,
+    /// or (for cookies) have their own field
+    const RESERVED_OVERRIDE_HEADERS: &'static [&'static str] = &[
+        "host",
+        "content-length",
+        "transfer-encoding",
+        "connection",
+        "cookie",
+    ];
+
     /// Maximum background check interval: 1 week (10080 minutes)
     const MAX_BACKGROUND_CHECK_INTERVAL_MINUTES: i32 = 10080;
 
@@ -238,6 +961,42 @@ impl DomainSettingService {
     /// Maximum session cache duration: 90 days
     const MAX_SESSION_CACHE_DURATION_DAYS: i32 = 90;
 
+    /// Maximum delay between bulk checks: 60 seconds
+    const MAX_BULK_CHECK_DELAY_MS: i32 = 60_000;
+
+    /// Minimum HTTP timeout: 5 seconds
+    const MIN_HTTP_TIMEOUT_SECS: i32 = 5;
+
+    /// Maximum HTTP timeout: 2 minutes
+    const MAX_HTTP_TIMEOUT_SECS: i32 = 120;
+
+    /// Minimum headless page load timeout: 10 seconds
+    const MIN_HEADLESS_TIMEOUT_SECS: i32 = 10;
+
+    /// Maximum headless page load timeout: 5 minutes
+    const MAX_HEADLESS_TIMEOUT_SECS: i32 = 300;
+
+    /// Maximum price drop threshold: 100%
+    const MAX_PRICE_DROP_THRESHOLD_PERCENT: i32 = 100;
+
+    /// Maximum concurrent checks in a bulk operation
+    const MAX_BULK_CHECK_CONCURRENCY: i32 = 10;
+
+    /// Maximum check history retention (10 years)
+    const MAX_DATA_RETENTION_DAYS: i32 = 3650;
+
+    /// Highest plausible price floor; anything above would reject genuine sales
+    const MAX_MIN_PLAUSIBLE_PRICE_PERCENT: i32 = 50;
+
+    /// Most redirects a page fetch may be allowed to follow
+    const MAX_MAX_REDIRECTS: i32 = 20;
+
+    /// Smallest JSON-LD block limit; product blocks are rarely under a few KB
+    const MIN_MAX_JSON_LD_BLOCK_KB: i32 = 64;
+
+    /// Largest JSON-LD block limit (64 MB), to keep bulk checks' memory bounded
+    const MAX_MAX_JSON_LD_BLOCK_KB: i32 = 64 * 1024;
+
     fn validate_background_check_interval(interval: i32) -> Result<(), AppError> {
         if interval <= 0 {
             return Err(AppError::Validation(
@@ -268,58 +1027,331 @@ impl DomainSettingService {
         }
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn validate_bulk_check_delay(delay: i32) -> Result<(), AppError> {
+        if delay < 0 {
+            return Err(AppError::Validation(
+                "Bulk check delay cannot be negative".to_string(),
+            ));
+        }
+        if delay > Self::MAX_BULK_CHECK_DELAY_MS {
+            return Err(AppError::Validation(format!(
+                "Bulk check delay cannot exceed {} ms",
+                Self::MAX_BULK_CHECK_DELAY_MS
+            )));
+        }
+        Ok(())
+    }
 
-    #[test]
-    fn test_default_domain_settings() {
-        let settings = DomainSettings::default();
-        assert!(!settings.background_check_enabled);
-        assert_eq!(settings.background_check_interval_minutes, 60);
-        assert!(settings.enable_headless_browser);
-        assert!(!settings.allow_manual_verification);
-        assert_eq!(settings.session_cache_duration_days, 14);
+    fn validate_http_timeout(timeout: i32) -> Result<(), AppError> {
+        if !(Self::MIN_HTTP_TIMEOUT_SECS..=Self::MAX_HTTP_TIMEOUT_SECS).contains(&timeout) {
+            return Err(AppError::Validation(format!(
+                "HTTP timeout must be between {} and {} seconds",
+                Self::MIN_HTTP_TIMEOUT_SECS,
+                Self::MAX_HTTP_TIMEOUT_SECS
+            )));
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_validate_background_check_interval_accepts_positive_values() {
-        assert!(DomainSettingService::validate_background_check_interval(15).is_ok());
-        assert!(DomainSettingService::validate_background_check_interval(60).is_ok());
-        assert!(DomainSettingService::validate_background_check_interval(1440).is_ok());
+    fn validate_headless_timeout(timeout: i32) -> Result<(), AppError> {
+        if !(Self::MIN_HEADLESS_TIMEOUT_SECS..=Self::MAX_HEADLESS_TIMEOUT_SECS).contains(&timeout) {
+            return Err(AppError::Validation(format!(
+                "Headless browser timeout must be between {} and {} seconds",
+                Self::MIN_HEADLESS_TIMEOUT_SECS,
+                Self::MAX_HEADLESS_TIMEOUT_SECS
+            )));
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_validate_background_check_interval_rejects_zero() {
-        assert!(DomainSettingService::validate_background_check_interval(0).is_err());
+    fn validate_price_drop_threshold(threshold: i32) -> Result<(), AppError> {
+        if !(0..=Self::MAX_PRICE_DROP_THRESHOLD_PERCENT).contains(&threshold) {
+            return Err(AppError::Validation(format!(
+                "Price drop threshold must be between 0 and {} percent",
+                Self::MAX_PRICE_DROP_THRESHOLD_PERCENT
+            )));
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_validate_background_check_interval_rejects_negative() {
-        assert!(DomainSettingService::validate_background_check_interval(-1).is_err());
+    fn validate_bulk_check_concurrency(concurrency: i32) -> Result<(), AppError> {
+        if !(1..=Self::MAX_BULK_CHECK_CONCURRENCY).contains(&concurrency) {
+            return Err(AppError::Validation(format!(
+                "Bulk check concurrency must be between 1 and {}",
+                Self::MAX_BULK_CHECK_CONCURRENCY
+            )));
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_validate_background_check_interval_rejects_exceeding_max() {
-        assert!(DomainSettingService::validate_background_check_interval(10081).is_err());
+    fn validate_max_redirects(redirects: i32) -> Result<(), AppError> {
+        if !(0..=Self::MAX_MAX_REDIRECTS).contains(&redirects) {
+            return Err(AppError::Validation(format!(
+                "Maximum redirects must be between 0 and {}",
+                Self::MAX_MAX_REDIRECTS
+            )));
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_validate_background_check_interval_accepts_max() {
-        assert!(DomainSettingService::validate_background_check_interval(10080).is_ok());
+    fn validate_max_json_ld_block_kb(kb: i32) -> Result<(), AppError> {
+        if !(Self::MIN_MAX_JSON_LD_BLOCK_KB..=Self::MAX_MAX_JSON_LD_BLOCK_KB).contains(&kb) {
+            return Err(AppError::Validation(format!(
+                "Maximum JSON-LD block size must be between {} and {} KB",
+                Self::MIN_MAX_JSON_LD_BLOCK_KB,
+                Self::MAX_MAX_JSON_LD_BLOCK_KB
+            )));
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_validate_session_cache_duration_accepts_valid_values() {
-        assert!(DomainSettingService::validate_session_cache_duration(1).is_ok());
-        assert!(DomainSettingService::validate_session_cache_duration(14).is_ok());
-        assert!(DomainSettingService::validate_session_cache_duration(30).is_ok());
-        assert!(DomainSettingService::validate_session_cache_duration(90).is_ok());
+    fn validate_data_retention(days: i32) -> Result<(), AppError> {
+        if !(0..=Self::MAX_DATA_RETENTION_DAYS).contains(&days) {
+            return Err(AppError::Validation(format!(
+                "Data retention must be between 0 (keep forever) and {} days",
+                Self::MAX_DATA_RETENTION_DAYS
+            )));
+        }
+        Ok(())
     }
 
-    #[test]
+    fn validate_min_plausible_price(percent: i32) -> Result<(), AppError> {
+        if !(0..=Self::MAX_MIN_PLAUSIBLE_PRICE_PERCENT).contains(&percent) {
+            return Err(AppError::Validation(format!(
+                "Minimum plausible price must be between 0 (off) and {} percent",
+                Self::MAX_MIN_PLAUSIBLE_PRICE_PERCENT
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_product_group_strategy(strategy: &str) -> Result<(), AppError> {
+        match strategy {
+            "first" | "cheapest_in_stock" => Ok(()),
+            _ => Err(AppError::Validation(format!(
+                "Invalid product group strategy: {}. Must be 'first' or 'cheapest_in_stock'",
+                strategy
+            ))),
+        }
+    }
+
+    /// Trim a browser path, requiring a non-empty one to be an existing file
+    fn normalize_headless_browser_path(raw: &str) -> Result<String, AppError> {
+        let path = raw.trim();
+        if !path.is_empty() && !Path::new(path).is_file() {
+            return Err(AppError::Validation(format!(
+                "No browser found at '{}'. Enter the full path to the Chrome or Chromium executable",
+                path
+            )));
+        }
+        Ok(path.to_string())
+    }
+
+    fn normalize_force_headless_domains(raw: &str) -> Result<String, AppError> {
+        Self::normalize_host_list(raw, "force headless domain")
+    }
+
+    fn normalize_insecure_hosts(raw: &str) -> Result<String, AppError> {
+        Self::normalize_host_list(raw, "insecure host")
+    }
+
+    /// Validate a comma-separated host list and normalize it to lowercase
+    /// `a.com,b.com` form, dropping blanks and duplicates.
+    ///
+    /// Entries must be bare hosts: no scheme, port, path or whitespace.
+    /// `setting` names an entry in error messages.
+    fn normalize_host_list(raw: &str, setting: &str) -> Result<String, AppError> {
+        let mut hosts: Vec<String> = Vec::new();
+        for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let host = Self::parse_bare_host(entry).ok_or_else(|| {
+                AppError::Validation(format!(
+                    "Invalid {} '{}'. Enter hosts like example.com, separated by commas",
+                    setting, entry
+                ))
+            })?;
+            if !hosts.contains(&host) {
+                hosts.push(host);
+            }
+        }
+        Ok(hosts.join(","))
+    }
+
+    /// Active hours times are "HH:MM" (24-hour); empty means all day
+    fn validate_active_hours_time(time: &str) -> Result<(), AppError> {
+        if time.is_empty() || QuietHours::parse_time(time).is_some() {
+            return Ok(());
+        }
+        Err(AppError::Validation(format!(
+            "Invalid background check time: {}. Must be HH:MM (24-hour)",
+            time
+        )))
+    }
+
+    /// Validate background check days and normalize them to lowercase
+    /// three-letter names in week order ("mon,wed,fri"), dropping duplicates.
+    fn normalize_background_check_days(raw: &str) -> Result<String, AppError> {
+        let mut days = Vec::new();
+        for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let day = entry.parse::<Weekday>().map_err(|_| {
+                AppError::Validation(format!(
+                    "Invalid background check day '{}'. Use names like mon, tue, wed",
+                    entry
+                ))
+            })?;
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+        days.sort_by_key(|day| day.num_days_from_monday());
+        Ok(days
+            .iter()
+            .map(|day| day.to_string().to_lowercase())
+            .collect::<Vec<_>>()
+            .join(","))
+    }
+
+    /// Lowercase `entry` if it is a bare host: no scheme, port, path or whitespace
+    fn parse_bare_host(entry: &str) -> Option<String> {
+        let host = entry.to_lowercase();
+        Url::parse(&format!("https://{}/", host))
+            .ok()
+            .filter(|url| url.host_str() == Some(host.as_str()) && url.port().is_none())
+            .map(|_| host)
+    }
+
+    fn validate_wait_selector(selector: &str) -> Result<(), AppError> {
+        if selector.chars().count() > Self::MAX_WAIT_SELECTOR_LEN {
+            return Err(AppError::Validation(format!(
+                "Wait selector cannot be longer than {} characters",
+                Self::MAX_WAIT_SELECTOR_LEN
+            )));
+        }
+        if selector.chars().any(char::is_control) {
+            return Err(AppError::Validation(
+                "Wait selector cannot contain control characters".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_request_overrides(overrides: &DomainRequestOverrides) -> Result<(), AppError> {
+        for (name, value) in &overrides.headers {
+            let header = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| AppError::Validation(format!("Invalid header name '{}'", name)))?;
+            if Self::RESERVED_OVERRIDE_HEADERS.contains(&header.as_str()) {
+                return Err(AppError::Validation(format!(
+                    "The '{}' header can't be overridden",
+                    name
+                )));
+            }
+            HeaderValue::from_str(value).map_err(|_| {
+                AppError::Validation(format!("Invalid value for header '{}'", name))
+            })?;
+        }
+
+        for (name, value) in &overrides.cookies {
+            if name.is_empty() || !name.chars().all(is_cookie_name_char) {
+                return Err(AppError::Validation(format!(
+                    "Invalid cookie name '{}'",
+                    name
+                )));
+            }
+            if !value.chars().all(is_cookie_value_char) {
+                return Err(AppError::Validation(format!(
+                    "Invalid value for cookie '{}'",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Cookie names are HTTP tokens (RFC 6265 section 4.1.1)
+fn is_cookie_name_char(c: char) -> bool {
+    c.is_ascii_graphic() && !"()<>@,;:\\\"/[]?={}".contains(c)
+}
+
+/// Cookie values are printable ASCII without whitespace, quotes, commas,
+/// semicolons or backslashes (RFC 6265 section 4.1.1)
+fn is_cookie_value_char(c: char) -> bool {
+    c.is_ascii_graphic() && !"\",;\\".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_domain_settings() {
+        let settings = DomainSettings::default();
+        assert!(!settings.background_check_enabled);
+        assert_eq!(settings.background_check_interval_minutes, 60);
+        assert!(settings.enable_headless_browser);
+        assert!(!settings.allow_manual_verification);
+        assert_eq!(settings.session_cache_duration_days, 14);
+        assert_eq!(settings.bulk_check_delay_ms, 500);
+        assert!(settings.headless_profile_reuse);
+        assert_eq!(settings.http_timeout_secs, 30);
+        assert_eq!(settings.headless_timeout_secs, 60);
+        assert_eq!(settings.price_drop_threshold_percent, 5);
+        assert_eq!(settings.bulk_check_concurrency, 1);
+        assert_eq!(settings.force_headless_domains, "");
+        assert_eq!(settings.data_retention_days, 0);
+        assert!(!settings.validate_url_on_create);
+        assert_eq!(settings.min_plausible_price_percent, 10);
+        assert!(settings.respect_robots_txt);
+        assert_eq!(settings.product_group_strategy, "first");
+        assert_eq!(settings.headless_browser_path, "");
+        assert_eq!(settings.fetch_options().headless_browser_path, None);
+        assert!(settings.rotate_user_agent);
+    }
+
+    #[test]
+    fn test_default_fetch_timeouts_match_scraper_defaults() {
+        assert_eq!(
+            DomainSettings::default().fetch_timeouts(),
+            FetchTimeouts::default()
+        );
+    }
+
+    #[test]
+    fn test_validate_background_check_interval_accepts_positive_values() {
+        assert!(DomainSettingService::validate_background_check_interval(15).is_ok());
+        assert!(DomainSettingService::validate_background_check_interval(60).is_ok());
+        assert!(DomainSettingService::validate_background_check_interval(1440).is_ok());
+    }
+
+    #[test]
+    fn test_validate_background_check_interval_rejects_zero() {
+        assert!(DomainSettingService::validate_background_check_interval(0).is_err());
+    }
+
+    #[test]
+    fn test_validate_background_check_interval_rejects_negative() {
+        assert!(DomainSettingService::validate_background_check_interval(-1).is_err());
+    }
+
+    #[test]
+    fn test_validate_background_check_interval_rejects_exceeding_max() {
+        assert!(DomainSettingService::validate_background_check_interval(10081).is_err());
+    }
+
+    #[test]
+    fn test_validate_background_check_interval_accepts_max() {
+        assert!(DomainSettingService::validate_background_check_interval(10080).is_ok());
+    }
+
+    #[test]
+    fn test_validate_session_cache_duration_accepts_valid_values() {
+        assert!(DomainSettingService::validate_session_cache_duration(1).is_ok());
+        assert!(DomainSettingService::validate_session_cache_duration(14).is_ok());
+        assert!(DomainSettingService::validate_session_cache_duration(30).is_ok());
+        assert!(DomainSettingService::validate_session_cache_duration(90).is_ok());
+    }
+
+    #[test]
     fn test_validate_session_cache_duration_rejects_zero() {
         assert!(DomainSettingService::validate_session_cache_duration(0).is_err());
     }
@@ -334,6 +1366,202 @@ mod tests {
         assert!(DomainSettingService::validate_session_cache_duration(91).is_err());
     }
 
+    #[test]
+    fn test_validate_bulk_check_delay_accepts_valid_values() {
+        assert!(DomainSettingService::validate_bulk_check_delay(0).is_ok());
+        assert!(DomainSettingService::validate_bulk_check_delay(500).is_ok());
+        assert!(DomainSettingService::validate_bulk_check_delay(60_000).is_ok());
+    }
+
+    #[test]
+    fn test_validate_bulk_check_delay_rejects_negative() {
+        assert!(DomainSettingService::validate_bulk_check_delay(-1).is_err());
+    }
+
+    #[test]
+    fn test_validate_bulk_check_delay_rejects_exceeding_max() {
+        assert!(DomainSettingService::validate_bulk_check_delay(60_001).is_err());
+    }
+
+    #[test]
+    fn test_validate_http_timeout() {
+        assert!(DomainSettingService::validate_http_timeout(5).is_ok());
+        assert!(DomainSettingService::validate_http_timeout(30).is_ok());
+        assert!(DomainSettingService::validate_http_timeout(120).is_ok());
+        assert!(DomainSettingService::validate_http_timeout(4).is_err());
+        assert!(DomainSettingService::validate_http_timeout(121).is_err());
+    }
+
+    #[test]
+    fn test_validate_headless_timeout() {
+        assert!(DomainSettingService::validate_headless_timeout(10).is_ok());
+        assert!(DomainSettingService::validate_headless_timeout(300).is_ok());
+        assert!(DomainSettingService::validate_headless_timeout(9).is_err());
+        assert!(DomainSettingService::validate_headless_timeout(301).is_err());
+    }
+
+    #[test]
+    fn test_validate_price_drop_threshold() {
+        assert!(DomainSettingService::validate_price_drop_threshold(0).is_ok());
+        assert!(DomainSettingService::validate_price_drop_threshold(5).is_ok());
+        assert!(DomainSettingService::validate_price_drop_threshold(100).is_ok());
+        assert!(DomainSettingService::validate_price_drop_threshold(-1).is_err());
+        assert!(DomainSettingService::validate_price_drop_threshold(101).is_err());
+    }
+
+    #[test]
+    fn test_normalize_force_headless_domains() {
+        assert_eq!(
+            DomainSettingService::normalize_force_headless_domains(
+                " Shop.Example.com, other.test ,,shop.example.com"
+            )
+            .unwrap(),
+            "shop.example.com,other.test"
+        );
+        assert_eq!(
+            DomainSettingService::normalize_force_headless_domains("  ").unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_normalize_force_headless_domains_rejects_non_hosts() {
+        for invalid in [
+            "https://example.com",
+            "example.com/path",
+            "example.com:8080",
+            "exa mple.com",
+        ] {
+            assert!(
+                DomainSettingService::normalize_force_headless_domains(invalid).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_force_headless_hosts_parses_list() {
+        let settings = DomainSettings {
+            force_headless_domains: "a.com, B.com,,".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.force_headless_hosts(),
+            vec!["a.com".to_string(), "b.com".to_string()]
+        );
+        assert!(DomainSettings::default().force_headless_hosts().is_empty());
+    }
+
+    mod background_check_window_tests {
+        use super::*;
+        use chrono::TimeZone;
+
+        fn window(start: &str, end: &str, days: &str) -> BackgroundCheckWindow {
+            DomainSettings {
+                background_check_active_hours_start: start.to_string(),
+                background_check_active_hours_end: end.to_string(),
+                background_check_days: days.to_string(),
+                ..Default::default()
+            }
+            .background_check_window()
+        }
+
+        /// 2026-01-15 is a Thursday; London is on UTC in January
+        fn thursday_at(hour: u32) -> DateTime<Utc> {
+            Utc.with_ymd_and_hms(2026, 1, 15, hour, 0, 0).unwrap()
+        }
+
+        const LONDON: &str = "Europe/London";
+
+        #[test]
+        fn test_default_window_is_always_open() {
+            let window = DomainSettings::default().background_check_window();
+            for hour in [0, 3, 12, 23] {
+                assert!(window.is_open_at(thursday_at(hour), LONDON));
+            }
+        }
+
+        #[test]
+        fn test_business_hours() {
+            let window = window("09:00", "17:00", "");
+            assert!(!window.is_open_at(thursday_at(8), LONDON));
+            assert!(window.is_open_at(thursday_at(9), LONDON));
+            assert!(window.is_open_at(thursday_at(16), LONDON));
+            assert!(!window.is_open_at(thursday_at(17), LONDON));
+            assert!(!window.is_open_at(thursday_at(2), LONDON));
+        }
+
+        #[test]
+        fn test_hours_crossing_midnight() {
+            let window = window("22:00", "02:00", "");
+            assert!(window.is_open_at(thursday_at(23), LONDON));
+            assert!(window.is_open_at(thursday_at(1), LONDON));
+            assert!(!window.is_open_at(thursday_at(12), LONDON));
+        }
+
+        #[test]
+        fn test_weekdays_only() {
+            let window = window("", "", "mon,tue,wed,thu,fri");
+            assert!(window.is_open_at(thursday_at(12), LONDON));
+            let saturday = thursday_at(12) + chrono::Duration::days(2);
+            assert!(!window.is_open_at(saturday, LONDON));
+            let sunday = thursday_at(12) + chrono::Duration::days(3);
+            assert!(!window.is_open_at(sunday, LONDON));
+        }
+
+        #[test]
+        fn test_hours_and_days_combined() {
+            let window = window("09:00", "17:00", "thu");
+            assert!(window.is_open_at(thursday_at(10), LONDON));
+            assert!(!window.is_open_at(thursday_at(18), LONDON));
+            let friday = thursday_at(10) + chrono::Duration::days(1);
+            assert!(!window.is_open_at(friday, LONDON));
+        }
+
+        #[test]
+        fn test_day_is_taken_in_display_timezone() {
+            let window = window("", "", "mon,tue,wed,thu,fri");
+            // Friday 16:00 UTC is already Saturday 03:00 in Sydney (AEDT, UTC+11)
+            let friday = thursday_at(16) + chrono::Duration::days(1);
+            assert!(!window.is_open_at(friday, "Australia/Sydney"));
+            assert!(window.is_open_at(friday, "America/New_York"));
+        }
+
+        #[test]
+        fn test_unrecognised_days_are_ignored() {
+            let window = window("", "", "thu, someday");
+            assert!(window.is_open_at(thursday_at(12), LONDON));
+            let friday = thursday_at(12) + chrono::Duration::days(1);
+            assert!(!window.is_open_at(friday, LONDON));
+        }
+    }
+
+    #[test]
+    fn test_validate_bulk_check_concurrency() {
+        assert!(DomainSettingService::validate_bulk_check_concurrency(1).is_ok());
+        assert!(DomainSettingService::validate_bulk_check_concurrency(10).is_ok());
+        assert!(DomainSettingService::validate_bulk_check_concurrency(0).is_err());
+        assert!(DomainSettingService::validate_bulk_check_concurrency(11).is_err());
+    }
+
+    #[test]
+    fn test_validate_max_redirects() {
+        assert!(DomainSettingService::validate_max_redirects(0).is_ok());
+        assert!(DomainSettingService::validate_max_redirects(20).is_ok());
+        assert!(DomainSettingService::validate_max_redirects(-1).is_err());
+        assert!(DomainSettingService::validate_max_redirects(21).is_err());
+    }
+
+    #[test]
+    fn test_validate_max_json_ld_block_kb() {
+        assert!(DomainSettingService::validate_max_json_ld_block_kb(64).is_ok());
+        assert!(DomainSettingService::validate_max_json_ld_block_kb(2048).is_ok());
+        assert!(DomainSettingService::validate_max_json_ld_block_kb(65536).is_ok());
+        assert!(DomainSettingService::validate_max_json_ld_block_kb(63).is_err());
+        assert!(DomainSettingService::validate_max_json_ld_block_kb(65537).is_err());
+    }
+
     #[test]
     fn test_domain_settings_serialize() {
         let settings = DomainSettings::default();
@@ -343,6 +1571,21 @@ mod tests {
         assert!(json.contains("\"enable_headless_browser\":true"));
         assert!(json.contains("\"allow_manual_verification\":false"));
         assert!(json.contains("\"session_cache_duration_days\":14"));
+        assert!(json.contains("\"bulk_check_delay_ms\":500"));
+        assert!(json.contains("\"headless_profile_reuse\":true"));
+        assert!(json.contains("\"http_timeout_secs\":30"));
+        assert!(json.contains("\"headless_timeout_secs\":60"));
+        assert!(json.contains("\"price_drop_threshold_percent\":5"));
+        assert!(json.contains("\"bulk_check_concurrency\":1"));
+        assert!(json.contains("\"force_headless_domains\":\"\""));
+        assert!(json.contains("\"data_retention_days\":0"));
+        assert!(json.contains("\"validate_url_on_create\":false"));
+        assert!(json.contains("\"min_plausible_price_percent\":10"));
+        assert!(json.contains("\"respect_robots_txt\":true"));
+        assert!(json.contains("\"rotate_user_agent\":true"));
+        assert!(json.contains("\"insecure_hosts\":\"\""));
+        assert!(json.contains("\"max_redirects\":10"));
+        assert!(json.contains("\"max_json_ld_block_kb\":2048"));
     }
 }
 
@@ -374,6 +1617,26 @@ mod integration_tests {
             enable_headless_browser: Some(false),
             allow_manual_verification: None,
             session_cache_duration_days: None,
+            bulk_check_delay_ms: None,
+            headless_profile_reuse: None,
+            http_timeout_secs: None,
+            headless_timeout_secs: None,
+            price_drop_threshold_percent: None,
+            bulk_check_concurrency: None,
+            force_headless_domains: None,
+            data_retention_days: None,
+            validate_url_on_create: None,
+            min_plausible_price_percent: None,
+            respect_robots_txt: None,
+            product_group_strategy: None,
+            headless_browser_path: None,
+            rotate_user_agent: None,
+            background_check_active_hours_start: None,
+            background_check_active_hours_end: None,
+            background_check_days: None,
+            insecure_hosts: None,
+            max_redirects: None,
+            max_json_ld_block_kb: None,
         };
 
         let result = DomainSettingService::update(&conn, params).await;
@@ -467,6 +1730,8 @@ mod integration_tests {
         assert!(cache.enable_headless_browser());
         assert!(!cache.allow_manual_verification());
         assert_eq!(cache.session_cache_duration_days(), 14);
+        assert_eq!(cache.bulk_check_delay_ms(), 500);
+        assert!(cache.headless_profile_reuse());
     }
 
     #[tokio::test]
@@ -480,6 +1745,26 @@ mod integration_tests {
             enable_headless_browser: Some(false),
             allow_manual_verification: None,
             session_cache_duration_days: None,
+            bulk_check_delay_ms: None,
+            headless_profile_reuse: None,
+            http_timeout_secs: None,
+            headless_timeout_secs: None,
+            price_drop_threshold_percent: None,
+            bulk_check_concurrency: None,
+            force_headless_domains: None,
+            data_retention_days: None,
+            validate_url_on_create: None,
+            min_plausible_price_percent: None,
+            respect_robots_txt: None,
+            product_group_strategy: None,
+            headless_browser_path: None,
+            rotate_user_agent: None,
+            background_check_active_hours_start: None,
+            background_check_active_hours_end: None,
+            background_check_days: None,
+            insecure_hosts: None,
+            max_redirects: None,
+            max_json_ld_block_kb: None,
         };
         DomainSettingService::update(&conn, params).await.unwrap();
 
@@ -576,4 +1861,646 @@ mod integration_tests {
             DomainSettingService::MAX_SESSION_CACHE_DURATION_DAYS
         );
     }
+
+    #[tokio::test]
+    async fn test_update_bulk_check_delay() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            bulk_check_delay_ms: Some(0),
+            ..Default::default()
+        };
+
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.bulk_check_delay_ms, 0);
+    }
+
+    #[tokio::test]
+    async fn test_update_validates_bulk_check_delay() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            bulk_check_delay_ms: Some(60_001),
+            ..Default::default()
+        };
+
+        let result = DomainSettingService::update(&conn, params).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_headless_profile_reuse() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            headless_profile_reuse: Some(false),
+            ..Default::default()
+        };
+
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert!(!updated.headless_profile_reuse);
+        assert!(
+            !DomainSettingService::get(&conn)
+                .await
+                .unwrap()
+                .headless_profile_reuse
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_validate_url_on_create() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            validate_url_on_create: Some(true),
+            ..Default::default()
+        };
+
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert!(updated.validate_url_on_create);
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert!(cache.validate_url_on_create());
+    }
+
+    #[tokio::test]
+    async fn test_update_respect_robots_txt() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            respect_robots_txt: Some(false),
+            ..Default::default()
+        };
+
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert!(!updated.respect_robots_txt);
+        assert!(!updated.fetch_options().respect_robots_txt);
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert!(!cache.respect_robots_txt());
+    }
+
+    #[tokio::test]
+    async fn test_update_insecure_hosts() {
+        let conn = setup_app_settings_db().await;
+        assert!(DomainSettingService::get(&conn)
+            .await
+            .unwrap()
+            .fetch_options()
+            .insecure_hosts
+            .is_empty());
+
+        let params = UpdateDomainSettingsParams {
+            insecure_hosts: Some("NAS.local, 192.168.1.20,nas.local".to_string()),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.insecure_hosts, "nas.local,192.168.1.20");
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert_eq!(
+            cache.fetch_options().insecure_hosts,
+            vec!["nas.local".to_string(), "192.168.1.20".to_string()]
+        );
+
+        let params = UpdateDomainSettingsParams {
+            insecure_hosts: Some("https://nas.local:8443".to_string()),
+            ..Default::default()
+        };
+        let result = DomainSettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_max_redirects() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            max_redirects: Some(3),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.max_redirects, 3);
+        assert_eq!(updated.fetch_options().max_redirects, 3);
+
+        let params = UpdateDomainSettingsParams {
+            max_redirects: Some(50),
+            ..Default::default()
+        };
+        let result = DomainSettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_max_json_ld_block_kb() {
+        let conn = setup_app_settings_db().await;
+
+        let defaults = DomainSettingService::get(&conn).await.unwrap();
+        assert_eq!(
+            defaults.fetch_options().max_json_ld_block_bytes,
+            DEFAULT_MAX_JSON_LD_BLOCK_BYTES
+        );
+
+        let params = UpdateDomainSettingsParams {
+            max_json_ld_block_kb: Some(512),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.max_json_ld_block_kb, 512);
+        assert_eq!(updated.fetch_options().max_json_ld_block_bytes, 512 * 1024);
+
+        let params = UpdateDomainSettingsParams {
+            max_json_ld_block_kb: Some(0),
+            ..Default::default()
+        };
+        let result = DomainSettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_rotate_user_agent() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            rotate_user_agent: Some(false),
+            ..Default::default()
+        };
+
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert!(!updated.rotate_user_agent);
+        assert!(!updated.fetch_options().rotate_user_agent);
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert!(!cache.rotate_user_agent());
+    }
+
+    #[tokio::test]
+    async fn test_update_background_check_window() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            background_check_active_hours_start: Some("09:00".to_string()),
+            background_check_active_hours_end: Some("17:30".to_string()),
+            background_check_days: Some("Fri, mon,MONDAY ,".to_string()),
+            ..Default::default()
+        };
+
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.background_check_active_hours_start, "09:00");
+        assert_eq!(updated.background_check_active_hours_end, "17:30");
+        assert_eq!(updated.background_check_days, "mon,fri");
+    }
+
+    #[tokio::test]
+    async fn test_update_background_check_window_rejects_invalid_values() {
+        let conn = setup_app_settings_db().await;
+
+        let bad_time = UpdateDomainSettingsParams {
+            background_check_active_hours_start: Some("9am".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            DomainSettingService::update(&conn, bad_time).await,
+            Err(AppError::Validation(_))
+        ));
+
+        let bad_day = UpdateDomainSettingsParams {
+            background_check_days: Some("mon,funday".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            DomainSettingService::update(&conn, bad_day).await,
+            Err(AppError::Validation(_))
+        ));
+
+        let settings = DomainSettingService::get(&conn).await.unwrap();
+        assert_eq!(settings.background_check_active_hours_start, "");
+        assert_eq!(settings.background_check_days, "");
+    }
+
+    #[tokio::test]
+    async fn test_update_product_group_strategy() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            product_group_strategy: Some("cheapest_in_stock".to_string()),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.product_group_strategy, "cheapest_in_stock");
+        assert_eq!(
+            updated.fetch_options().product_group_strategy,
+            ProductGroupStrategy::CheapestInStock
+        );
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert_eq!(
+            cache.product_group_strategy(),
+            ProductGroupStrategy::CheapestInStock
+        );
+
+        let params = UpdateDomainSettingsParams {
+            product_group_strategy: Some("cheapest".to_string()),
+            ..Default::default()
+        };
+        let result = DomainSettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_headless_browser_path() {
+        let conn = setup_app_settings_db().await;
+        let dir = std::env::temp_dir().join(format!("product-stalker-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let binary = dir.join("chromium");
+        std::fs::write(&binary, b"").unwrap();
+
+        let params = UpdateDomainSettingsParams {
+            headless_browser_path: Some(format!("  {}  ", binary.display())),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.headless_browser_path, binary.display().to_string());
+        assert_eq!(
+            updated.fetch_options().headless_browser_path,
+            Some(binary.clone())
+        );
+
+        for invalid in [dir.join("missing"), dir.clone()] {
+            let params = UpdateDomainSettingsParams {
+                headless_browser_path: Some(invalid.display().to_string()),
+                ..Default::default()
+            };
+            let result = DomainSettingService::update(&conn, params).await;
+            assert!(matches!(result, Err(AppError::Validation(_))));
+        }
+
+        // Clearing the path goes back to searching the system
+        let params = UpdateDomainSettingsParams {
+            headless_browser_path: Some(String::new()),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.fetch_options().headless_browser_path, None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_min_plausible_price_percent() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            min_plausible_price_percent: Some(25),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.min_plausible_price_percent, 25);
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert_eq!(cache.min_plausible_price_percent(), 25);
+
+        for invalid in [-1, 51] {
+            let params = UpdateDomainSettingsParams {
+                min_plausible_price_percent: Some(invalid),
+                ..Default::default()
+            };
+            let result = DomainSettingService::update(&conn, params).await;
+            assert!(matches!(result, Err(AppError::Validation(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_clamps_invalid_bulk_check_delay_from_database() {
+        let conn = setup_app_settings_db().await;
+        let scope = SettingScope::Global;
+
+        SettingsHelpers::set_i32(&conn, &scope, keys::BULK_CHECK_DELAY_MS, -10)
+            .await
+            .unwrap();
+        let settings = DomainSettingService::get(&conn).await.unwrap();
+        assert_eq!(settings.bulk_check_delay_ms, 0);
+
+        SettingsHelpers::set_i32(&conn, &scope, keys::BULK_CHECK_DELAY_MS, 999_999)
+            .await
+            .unwrap();
+        let settings = DomainSettingService::get(&conn).await.unwrap();
+        assert_eq!(
+            settings.bulk_check_delay_ms,
+            DomainSettingService::MAX_BULK_CHECK_DELAY_MS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_fetch_timeouts() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            http_timeout_secs: Some(10),
+            headless_timeout_secs: Some(120),
+            ..Default::default()
+        };
+
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(
+            updated.fetch_timeouts(),
+            FetchTimeouts {
+                http_secs: 10,
+                headless_secs: 120,
+                min_host_interval_ms: 500,
+            }
+        );
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert_eq!(cache.fetch_timeouts().http_secs, 10);
+    }
+
+    #[tokio::test]
+    async fn test_update_validates_http_timeout() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            http_timeout_secs: Some(1),
+            ..Default::default()
+        };
+
+        let result = DomainSettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_clamps_invalid_timeouts_from_database() {
+        let conn = setup_app_settings_db().await;
+        let scope = SettingScope::Global;
+
+        SettingsHelpers::set_i32(&conn, &scope, keys::HTTP_TIMEOUT_SECS, 0)
+            .await
+            .unwrap();
+        SettingsHelpers::set_i32(&conn, &scope, keys::HEADLESS_TIMEOUT_SECS, 10_000)
+            .await
+            .unwrap();
+
+        let settings = DomainSettingService::get(&conn).await.unwrap();
+        assert_eq!(
+            settings.http_timeout_secs,
+            DomainSettingService::MIN_HTTP_TIMEOUT_SECS
+        );
+        assert_eq!(
+            settings.headless_timeout_secs,
+            DomainSettingService::MAX_HEADLESS_TIMEOUT_SECS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_price_drop_threshold() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            price_drop_threshold_percent: Some(10),
+            ..Default::default()
+        };
+
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.price_drop_threshold_percent, 10);
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert_eq!(cache.price_drop_threshold_percent(), 10);
+
+        let params = UpdateDomainSettingsParams {
+            price_drop_threshold_percent: Some(150),
+            ..Default::default()
+        };
+        let result = DomainSettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_bulk_check_concurrency() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            bulk_check_concurrency: Some(4),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.bulk_check_concurrency, 4);
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert_eq!(cache.bulk_check_concurrency(), 4);
+
+        let params = UpdateDomainSettingsParams {
+            bulk_check_concurrency: Some(0),
+            ..Default::default()
+        };
+        let result = DomainSettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_force_headless_domains() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            force_headless_domains: Some("Example.com, shop.test".to_string()),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.force_headless_domains, "example.com,shop.test");
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert_eq!(
+            cache.fetch_options().force_headless_domains,
+            vec!["example.com".to_string(), "shop.test".to_string()]
+        );
+
+        let params = UpdateDomainSettingsParams {
+            force_headless_domains: Some("https://example.com".to_string()),
+            ..Default::default()
+        };
+        let result = DomainSettingService::update(&conn, params).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_data_retention_days() {
+        let conn = setup_app_settings_db().await;
+
+        let params = UpdateDomainSettingsParams {
+            data_retention_days: Some(180),
+            ..Default::default()
+        };
+        let updated = DomainSettingService::update(&conn, params).await.unwrap();
+        assert_eq!(updated.data_retention_days, 180);
+        let cache = DomainSettingsCache::load(&conn).await.unwrap();
+        assert_eq!(cache.data_retention_days(), 180);
+
+        for invalid in [-1, 3651] {
+            let params = UpdateDomainSettingsParams {
+                data_retention_days: Some(invalid),
+                ..Default::default()
+            };
+            let result = DomainSettingService::update(&conn, params).await;
+            assert!(matches!(result, Err(AppError::Validation(_))));
+        }
+    }
+
+    fn overrides(headers: &[(&str, &str)], cookies: &[(&str, &str)]) -> DomainRequestOverrides {
+        let to_map = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        DomainRequestOverrides {
+            headers: to_map(headers),
+            cookies: to_map(cookies),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_overrides_roundtrip() {
+        let conn = setup_app_settings_db().await;
+        let member = overrides(&[("X-Member-Tier", "gold")], &[("session", "abc123")]);
+
+        let all =
+            DomainSettingService::set_request_overrides(&conn, "Shop.Example.com", member.clone())
+                .await
+                .unwrap();
+        assert_eq!(all.len(), 1);
+
+        let found = DomainSettingService::get_request_overrides_for_host(&conn, "shop.example.com")
+            .await
+            .unwrap();
+        assert_eq!(found, Some(member));
+        let other = DomainSettingService::get_request_overrides_for_host(&conn, "example.com")
+            .await
+            .unwrap();
+        assert_eq!(other, None);
+    }
+
+    #[tokio::test]
+    async fn test_empty_request_overrides_remove_host() {
+        let conn = setup_app_settings_db().await;
+        DomainSettingService::set_request_overrides(
+            &conn,
+            "shop.example.com",
+            overrides(&[], &[("session", "abc")]),
+        )
+        .await
+        .unwrap();
+
+        let all = DomainSettingService::set_request_overrides(
+            &conn,
+            "shop.example.com",
+            DomainRequestOverrides::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(all.is_empty());
+        assert!(DomainSettingService::get_request_overrides(&conn)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_request_overrides_reject_unsafe_values() {
+        let conn = setup_app_settings_db().await;
+        let invalid = [
+            ("https://example.com", overrides(&[], &[("a", "b")])),
+            ("example.com", overrides(&[("Bad Header", "x")], &[])),
+            (
+                "example.com",
+                overrides(&[("X-Test", "a\r\nInjected: 1")], &[]),
+            ),
+            ("example.com", overrides(&[("Host", "evil.com")], &[])),
+            ("example.com", overrides(&[("Cookie", "a=b")], &[])),
+            ("example.com", overrides(&[], &[("bad;name", "x")])),
+            ("example.com", overrides(&[], &[("name", "a; other=b")])),
+            ("example.com", overrides(&[], &[("", "x")])),
+        ];
+
+        for (host, invalid_overrides) in invalid {
+            let result =
+                DomainSettingService::set_request_overrides(&conn, host, invalid_overrides.clone())
+                    .await;
+            assert!(
+                matches!(result, Err(AppError::Validation(_))),
+                "{:?} for {} should be rejected",
+                invalid_overrides,
+                host
+            );
+        }
+        assert!(DomainSettingService::get_request_overrides(&conn)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_headless_wait_selector_roundtrip() {
+        let conn = setup_app_settings_db().await;
+
+        let all = DomainSettingService::set_headless_wait_selector(
+            &conn,
+            "Shop.Example.com",
+            Some("  [itemprop=price]  ".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(all.len(), 1);
+
+        let found =
+            DomainSettingService::get_headless_wait_selector_for_host(&conn, "shop.example.com")
+                .await
+                .unwrap();
+        assert_eq!(found.as_deref(), Some("[itemprop=price]"));
+        let other = DomainSettingService::get_headless_wait_selector_for_host(&conn, "example.com")
+            .await
+            .unwrap();
+        assert_eq!(other, None);
+    }
+
+    #[tokio::test]
+    async fn test_blank_headless_wait_selector_removes_host() {
+        let conn = setup_app_settings_db().await;
+        DomainSettingService::set_headless_wait_selector(
+            &conn,
+            "shop.example.com",
+            Some(".price".to_string()),
+        )
+        .await
+        .unwrap();
+
+        for cleared in [Some("   ".to_string()), None] {
+            let all = DomainSettingService::set_headless_wait_selector(
+                &conn,
+                "shop.example.com",
+                cleared,
+            )
+            .await
+            .unwrap();
+            assert!(all.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_headless_wait_selector_rejects_invalid_input() {
+        let conn = setup_app_settings_db().await;
+        let invalid = [
+            ("https://example.com", ".price".to_string()),
+            ("example.com", ".price\n.other".to_string()),
+            ("example.com", "a".repeat(201)),
+        ];
+
+        for (host, selector) in invalid {
+            let result = DomainSettingService::set_headless_wait_selector(
+                &conn,
+                host,
+                Some(selector.clone()),
+            )
+            .await;
+            assert!(
+                matches!(result, Err(AppError::Validation(_))),
+                "{:?} for {} should be rejected",
+                selector,
+                host
+            );
+        }
+        assert!(DomainSettingService::get_headless_wait_selectors(&conn)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
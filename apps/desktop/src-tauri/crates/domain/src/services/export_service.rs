@@ -0,0 +1,285 @@
+//! Export of the tracked product list for use outside the app.
+
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+
+use crate::entities::prelude::{AvailabilityCheckModel, ProductModel};
+use crate::repositories::{
+    AvailabilityCheckRepository, ProductRepository, ProductRetailerRepository,
+};
+use crate::services::currency;
+use product_stalker_core::AppError;
+
+/// Column headers of the product CSV export
+const CSV_HEADER: [&str; 7] = [
+    "name",
+    "url",
+    "status",
+    "price",
+    "currency",
+    "last_checked",
+    "target_price",
+];
+
+/// Service for exporting products
+pub struct ExportService;
+
+impl ExportService {
+    /// Export all products with their latest check as CSV text.
+    ///
+    /// Products with several retailers get one row per retailer, each with that
+    /// retailer's latest check. Products without retailers use their own URL.
+    pub async fn products_csv(conn: &DatabaseConnection) -> Result<String, AppError> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(CSV_HEADER).map_err(Self::csv_error)?;
+
+        for product in ProductRepository::find_all_including_archived(conn).await? {
+            let links = ProductRetailerRepository::find_by_product_id(conn, product.id).await?;
+
+            if links.is_empty() {
+                let latest =
+                    AvailabilityCheckRepository::find_latest_for_product(conn, product.id).await?;
+                writer
+                    .write_record(Self::csv_row(
+                        &product,
+                        product.url.as_deref(),
+                        latest.as_ref(),
+                    ))
+                    .map_err(Self::csv_error)?;
+                continue;
+            }
+
+            for link in links {
+                let latest =
+                    AvailabilityCheckRepository::find_latest_for_product_retailer(conn, link.id)
+                        .await?;
+                writer
+                    .write_record(Self::csv_row(&product, Some(&link.url), latest.as_ref()))
+                    .map_err(Self::csv_error)?;
+            }
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| AppError::Internal(format!("Failed to write CSV: {}", e)))?;
+        String::from_utf8(bytes)
+            .map_err(|e| AppError::Internal(format!("CSV export is not valid UTF-8: {}", e)))
+    }
+
+    /// Build one CSV row (matches `CSV_HEADER`)
+    fn csv_row(
+        product: &ProductModel,
+        url: Option<&str>,
+        latest: Option<&AvailabilityCheckModel>,
+    ) -> [String; 7] {
+        let price_currency = latest.and_then(|check| check.price_currency.as_deref());
+        let price = latest
+            .and_then(|check| check.price_minor_units)
+            .zip(price_currency.or(product.currency.as_deref()))
+            .map(|(minor_units, code)| Self::format_price(minor_units, code));
+
+        let target_currency = product
+            .target_price_currency
+            .as_deref()
+            .or(product.currency.as_deref())
+            .or(price_currency);
+        let target_price = product.target_price_minor_units.map(|minor_units| {
+            target_currency.map_or_else(
+                || minor_units.to_string(),
+                |code| Self::format_price(minor_units, code),
+            )
+        });
+
+        [
+            product.name.clone(),
+            url.unwrap_or_default().to_string(),
+            latest.map(|check| check.status.clone()).unwrap_or_default(),
+            price.unwrap_or_default(),
+            price_currency.unwrap_or_default().to_string(),
+            latest
+                .map(|check| check.checked_at.to_rfc3339())
+                .unwrap_or_default(),
+            target_price.unwrap_or_default(),
+        ]
+    }
+
+    /// Format minor units as a decimal amount (e.g. 4999 AUD -> "49.99")
+    fn format_price(minor_units: i64, currency_code: &str) -> String {
+        Decimal::new(minor_units, currency::currency_exponent(currency_code)).to_string()
+    }
+
+    fn csv_error(e: csv::Error) -> AppError {
+        AppError::Internal(format!("Failed to write CSV: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_price_uses_currency_exponent() {
+        assert_eq!(ExportService::format_price(4999, "AUD"), "49.99");
+        assert_eq!(ExportService::format_price(1500, "JPY"), "1500");
+        assert_eq!(ExportService::format_price(12345, "KWD"), "12.345");
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::entities::availability_check::AvailabilityStatus;
+    use crate::repositories::{
+        CreateCheckParams, CreateProductRepoParams, CreateProductRetailerParams, RetailerRepository,
+    };
+    use crate::test_utils::setup_availability_db;
+    use uuid::Uuid;
+
+    async fn create_product(
+        conn: &DatabaseConnection,
+        name: &str,
+        url: Option<&str>,
+        target_price_minor_units: Option<i64>,
+    ) -> ProductModel {
+        ProductRepository::create(
+            conn,
+            Uuid::new_v4(),
+            CreateProductRepoParams {
+                name: name.to_string(),
+                url: url.map(str::to_string),
+                description: None,
+                notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units,
+                target_price_currency: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn link_retailer(conn: &DatabaseConnection, product_id: Uuid, url: &str) -> Uuid {
+        let domain = url::Url::parse(url)
+            .unwrap()
+            .host_str()
+            .unwrap()
+            .to_string();
+        let retailer = RetailerRepository::find_or_create_by_domain(conn, &domain)
+            .await
+            .unwrap();
+        ProductRetailerRepository::create(
+            conn,
+            Uuid::new_v4(),
+            retailer.id,
+            CreateProductRetailerParams {
+                product_id,
+                url: url.to_string(),
+                label: None,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    fn rows(csv: &str) -> Vec<Vec<String>> {
+        csv::Reader::from_reader(csv.as_bytes())
+            .records()
+            .map(|record| record.unwrap().iter().map(str::to_string).collect())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_products_csv_header_only_when_empty() {
+        let conn = setup_availability_db().await;
+
+        let csv = ExportService::products_csv(&conn).await.unwrap();
+        assert_eq!(
+            csv,
+            "name,url,status,price,currency,last_checked,target_price\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_products_csv_quotes_fields_with_commas() {
+        let conn = setup_availability_db().await;
+        create_product(
+            &conn,
+            "Widget, Large \"Pro\"",
+            Some("https://example.com/widget"),
+            None,
+        )
+        .await;
+
+        let csv = ExportService::products_csv(&conn).await.unwrap();
+        let line = csv.lines().nth(1).unwrap();
+        assert!(line.starts_with("\"Widget, Large \"\"Pro\"\"\",https://example.com/widget,"));
+        assert_eq!(rows(&csv)[0][0], "Widget, Large \"Pro\"");
+    }
+
+    #[tokio::test]
+    async fn test_products_csv_includes_latest_check() {
+        let conn = setup_availability_db().await;
+        let product = create_product(
+            &conn,
+            "Widget",
+            Some("https://example.com/widget"),
+            Some(3999),
+        )
+        .await;
+        for price in [5999, 4999] {
+            AvailabilityCheckRepository::create(
+                &conn,
+                Uuid::new_v4(),
+                product.id,
+                CreateCheckParams {
+                    status: AvailabilityStatus::InStock,
+                    price_minor_units: Some(price),
+                    price_currency: Some("AUD".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let csv = ExportService::products_csv(&conn).await.unwrap();
+        let rows = rows(&csv);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][0], "Widget");
+        assert_eq!(rows[0][1], "https://example.com/widget");
+        assert_eq!(rows[0][2], "in_stock");
+        assert_eq!(rows[0][3], "49.99");
+        assert_eq!(rows[0][4], "AUD");
+        assert!(!rows[0][5].is_empty());
+        assert_eq!(rows[0][6], "39.99");
+    }
+
+    #[tokio::test]
+    async fn test_products_csv_one_row_per_retailer() {
+        let conn = setup_availability_db().await;
+        let product = create_product(&conn, "Console", None, None).await;
+        let first = link_retailer(&conn, product.id, "https://shop-a.com/console").await;
+        link_retailer(&conn, product.id, "https://shop-b.com/console").await;
+        AvailabilityCheckRepository::create(
+            &conn,
+            Uuid::new_v4(),
+            product.id,
+            CreateCheckParams {
+                status: AvailabilityStatus::OutOfStock,
+                product_retailer_id: Some(first),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let csv = ExportService::products_csv(&conn).await.unwrap();
+        let rows = rows(&csv);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][1], "https://shop-a.com/console");
+        assert_eq!(rows[0][2], "out_of_stock");
+        assert_eq!(rows[1][1], "https://shop-b.com/console");
+        assert_eq!(rows[1][2], "");
+    }
+}
@@ -1,11 +1,12 @@
 //! Headless browser service for fetching pages with bot protection.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 use headless_chrome::{Browser, LaunchOptions};
 use rand::Rng;
+use serde::Serialize;
 
 use product_stalker_core::AppError;
 
@@ -51,19 +52,40 @@ const COMPREHENSIVE_STEALTH_SCRIPT: &str = r#"
     };
 "#;
 
+/// Whether a usable Chrome/Chromium binary was found
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HeadlessBrowserStatus {
+    pub available: bool,
+    /// Binary that will be launched, when one was found
+    pub path: Option<String>,
+    /// Why no browser is available
+    pub error: Option<String>,
+}
+
 /// Service for headless browser automation
 ///
 /// Used as a fallback when HTTP requests are blocked by bot protection
 /// (Cloudflare, etc.). Requires Chrome/Chromium to be installed.
 pub struct HeadlessService {
     browser: Option<Arc<Browser>>,
+    /// Browser binary from settings, used instead of searching the system
+    browser_path: Option<PathBuf>,
     user_data_dir: PathBuf,
+    /// Whether `user_data_dir` is a throwaway profile to delete on drop
+    temporary_profile: bool,
+    /// How long to wait for a page to finish loading
+    page_timeout: Duration,
+    /// CSS selector to wait for after load, for prices rendered by JavaScript
+    wait_for_selector: Option<String>,
 }
 
 impl HeadlessService {
-    /// Page load timeout for headless browser (longer than HTTP due to JS execution)
+    /// Default page load timeout for headless browser (longer than HTTP due to JS execution)
     pub(crate) const PAGE_TIMEOUT_SECS: u64 = 60;
 
+    /// Longest wait for `wait_for_selector` once the page has loaded
+    pub(crate) const WAIT_FOR_SELECTOR_TIMEOUT_SECS: u64 = 10;
+
     /// Create a new headless service instance
     pub fn new() -> Self {
         let user_data_dir = Self::get_user_data_dir().unwrap_or_else(|e| {
@@ -73,12 +95,71 @@ impl HeadlessService {
             );
             PathBuf::from(".")
         });
+        Self::with_user_data_dir(user_data_dir)
+    }
+
+    /// Create an instance using the persistent profile for a domain.
+    ///
+    /// Cookies from a passed bot challenge stay in the profile, so later
+    /// fetches for the same domain can skip the challenge.
+    pub fn for_domain(domain: &str) -> Self {
+        match Self::get_domain_user_data_dir(domain) {
+            Ok(user_data_dir) => Self::with_user_data_dir(user_data_dir),
+            Err(e) => {
+                log::warn!(
+                    "Failed to create profile directory for {}: {}. Using shared profile.",
+                    domain,
+                    e
+                );
+                Self::new()
+            }
+        }
+    }
+
+    /// Create an instance with a fresh, empty profile that is deleted on drop
+    pub fn with_temporary_profile() -> Self {
+        let user_data_dir =
+            std::env::temp_dir().join(format!("product-stalker-chrome-{}", uuid::Uuid::new_v4()));
+        let mut service = Self::with_user_data_dir(user_data_dir);
+        service.temporary_profile = true;
+        service
+    }
+
+    fn with_user_data_dir(user_data_dir: PathBuf) -> Self {
         Self {
             browser: None,
+            browser_path: None,
             user_data_dir,
+            temporary_profile: false,
+            page_timeout: Duration::from_secs(Self::PAGE_TIMEOUT_SECS),
+            wait_for_selector: None,
         }
     }
 
+    /// Launch this browser binary instead of searching the usual install locations
+    pub fn with_browser_path(mut self, browser_path: Option<PathBuf>) -> Self {
+        self.browser_path = browser_path;
+        self
+    }
+
+    /// Override how long to wait for a page to finish loading
+    pub fn with_page_timeout(mut self, page_timeout: Duration) -> Self {
+        self.page_timeout = page_timeout;
+        self
+    }
+
+    /// Wait for an element matching `selector` before reading the page, so
+    /// prices fetched by JavaScript after load are in the HTML
+    pub fn with_wait_for_selector(mut self, selector: impl Into<String>) -> Self {
+        self.wait_for_selector = Some(selector.into());
+        self
+    }
+
+    /// How long to wait for `wait_for_selector`: capped by the page timeout
+    fn wait_for_selector_timeout(&self) -> Duration {
+        Duration::from_secs(Self::WAIT_FOR_SELECTOR_TIMEOUT_SECS).min(self.page_timeout)
+    }
+
     /// Get the user data directory for Chrome profile persistence
     ///
     /// Creates a persistent profile directory for Chrome to store cookies,
@@ -113,6 +194,47 @@ impl HeadlessService {
         Ok(chrome_profile)
     }
 
+    /// Get the persistent profile directory for a domain, creating it if needed
+    pub fn get_domain_user_data_dir(domain: &str) -> Result<PathBuf, AppError> {
+        let profile = Self::domain_profile_dir(&Self::get_user_data_dir()?, domain);
+
+        std::fs::create_dir_all(&profile).map_err(|e| {
+            AppError::Internal(format!(
+                "Failed to create Chrome profile directory at {}: {}",
+                profile.display(),
+                e
+            ))
+        })?;
+
+        Ok(profile)
+    }
+
+    /// Profile directory for a domain under the base profile directory.
+    ///
+    /// The domain is lowercased and any character that isn't safe in a path
+    /// component is replaced, so the same domain always maps to the same directory.
+    pub fn domain_profile_dir(base: &Path, domain: &str) -> PathBuf {
+        let name: String = domain
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        // Never resolve to "", "." or ".." (which would escape the domains dir)
+        let name = if name.chars().all(|c| c == '.') {
+            format!("_{}", name)
+        } else {
+            name
+        };
+        base.join("domains").join(name)
+    }
+
     /// Fetch a page using headless Chrome
     ///
     /// Lazily initializes the browser on first use. Falls back to clear
@@ -159,7 +281,7 @@ impl HeadlessService {
         // on Cloudflare-protected sites (Turnstile keeps polling, so network idle never fires)
         log::debug!("Headless: waiting for navigation to complete");
         let start = std::time::Instant::now();
-        let timeout = Duration::from_secs(Self::PAGE_TIMEOUT_SECS);
+        let timeout = self.page_timeout;
 
         loop {
             if start.elapsed() > timeout {
@@ -183,6 +305,17 @@ impl HeadlessService {
             std::thread::sleep(Duration::from_millis(500));
         }
 
+        // Prices rendered after load (e.g. via XHR) may not be in the DOM yet;
+        // read the page anyway if the element never appears
+        if let Some(selector) = &self.wait_for_selector {
+            log::debug!("Headless: waiting for '{}' on {}", selector, url);
+            if let Err(e) =
+                tab.wait_for_element_with_custom_timeout(selector, self.wait_for_selector_timeout())
+            {
+                log::warn!("Headless: '{}' did not appear on {}: {}", selector, url, e);
+            }
+        }
+
         // Re-inject script after navigation in case page reset it
         if let Err(e) = tab.evaluate(COMPREHENSIVE_STEALTH_SCRIPT, false) {
             log::warn!(
@@ -217,12 +350,8 @@ impl HeadlessService {
 
     /// Launch Chrome browser with appropriate options
     fn launch_browser(&self) -> Result<Arc<Browser>, AppError> {
-        let chrome_path = Self::find_chrome_binary().ok_or_else(|| {
-            AppError::External(
-                "Chrome/Chromium not found. This site has bot protection and requires Chrome to check availability. \
-                Please install Chrome from https://www.google.com/chrome/".to_string()
-            )
-        })?;
+        let chrome_path = Self::resolve_browser_path(self.browser_path.as_deref())?;
+        log::debug!("Headless: using browser at {}", chrome_path.display());
 
         // Build user-agent arg from the shared constant in the scraper module
         let user_agent_arg = format!("--user-agent={}", super::scraper::USER_AGENT);
@@ -236,7 +365,7 @@ impl HeadlessService {
             .path(Some(chrome_path))
             .headless(false) // We'll add --headless=new manually
             .sandbox(true)
-            .idle_browser_timeout(Duration::from_secs(Self::PAGE_TIMEOUT_SECS))
+            .idle_browser_timeout(self.page_timeout)
             // Anti-detection arguments to bypass Cloudflare fingerprinting
             .args(vec![
                 // Use Chrome's new headless mode (less detectable than old --headless)
@@ -276,17 +405,59 @@ impl HeadlessService {
         Ok(Arc::new(browser))
     }
 
+    /// Resolve the browser binary to launch.
+    ///
+    /// A `configured` path (the `headless_browser_path` setting) must point at an
+    /// existing file; otherwise the system is searched with [`Self::find_chrome_binary`].
+    pub fn resolve_browser_path(configured: Option<&Path>) -> Result<PathBuf, AppError> {
+        if let Some(path) = configured {
+            if path.is_file() {
+                return Ok(path.to_path_buf());
+            }
+            return Err(AppError::Internal(format!(
+                "Chrome not found at {}. Check the headless browser path in Settings > Advanced.",
+                path.display()
+            )));
+        }
+
+        Self::find_chrome_binary().ok_or_else(|| {
+            AppError::Internal(
+                "Chrome not found. Sites with bot protection need Google Chrome or Chromium: \
+                install it from https://www.google.com/chrome/ or set the headless browser \
+                path in Settings > Advanced."
+                    .to_string(),
+            )
+        })
+    }
+
+    /// Report whether a browser can be launched, and which one
+    pub fn browser_status(configured: Option<&Path>) -> HeadlessBrowserStatus {
+        match Self::resolve_browser_path(configured) {
+            Ok(path) => HeadlessBrowserStatus {
+                available: true,
+                path: Some(path.display().to_string()),
+                error: None,
+            },
+            Err(e) => HeadlessBrowserStatus {
+                available: false,
+                path: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
     /// Find Chrome/Chromium binary on the system
     ///
     /// Checks:
     /// 1. CHROME_PATH environment variable
     /// 2. Platform-specific common installation paths
+    /// 3. Browser executables on `PATH`
     pub fn find_chrome_binary() -> Option<PathBuf> {
         // Check environment variable first
         let from_env = std::env::var("CHROME_PATH")
             .ok()
             .map(PathBuf::from)
-            .filter(|p| p.exists());
+            .filter(|p| p.is_file());
 
         if from_env.is_some() {
             return from_env;
@@ -294,19 +465,18 @@ impl HeadlessService {
 
         // Platform-specific paths
         #[cfg(target_os = "windows")]
-        {
-            Self::find_chrome_windows()
-        }
+        let found = Self::find_chrome_windows();
 
         #[cfg(target_os = "macos")]
-        {
-            Self::find_chrome_macos()
-        }
+        let found = Self::find_chrome_macos();
 
         #[cfg(target_os = "linux")]
-        {
-            Self::find_chrome_linux()
-        }
+        let found = Self::find_chrome_linux();
+
+        found.or_else(|| {
+            let path_var = std::env::var_os("PATH")?;
+            find_on_path(CHROME_EXECUTABLE_NAMES, &path_var)
+        })
     }
 
     #[cfg(target_os = "windows")]
@@ -332,21 +502,27 @@ impl HeadlessService {
                     .join("Application")
                     .join("chrome.exe")
             })
-            .filter(|p| p.exists());
+            .filter(|p| p.is_file());
 
         if user_chrome.is_some() {
             return user_chrome;
         }
 
-        paths.iter().map(PathBuf::from).find(|p| p.exists())
+        first_existing_file(&paths)
     }
 
     #[cfg(target_os = "macos")]
     fn find_chrome_macos() -> Option<PathBuf> {
         let paths = [
             "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+            "/Applications/Google Chrome Beta.app/Contents/MacOS/Google Chrome Beta",
+            "/Applications/Google Chrome Canary.app/Contents/MacOS/Google Chrome Canary",
             "/Applications/Chromium.app/Contents/MacOS/Chromium",
             "/Applications/Microsoft Edge.app/Contents/MacOS/Microsoft Edge",
+            "/Applications/Brave Browser.app/Contents/MacOS/Brave Browser",
+            // Homebrew on Apple silicon, then Intel
+            "/opt/homebrew/bin/chromium",
+            "/usr/local/bin/chromium",
         ];
 
         // Also check user's Applications folder
@@ -360,13 +536,13 @@ impl HeadlessService {
                     .join("MacOS")
                     .join("Google Chrome")
             })
-            .filter(|p| p.exists());
+            .filter(|p| p.is_file());
 
         if user_chrome.is_some() {
             return user_chrome;
         }
 
-        paths.iter().map(PathBuf::from).find(|p| p.exists())
+        first_existing_file(&paths)
     }
 
     #[cfg(target_os = "linux")]
@@ -377,9 +553,13 @@ impl HeadlessService {
             "/usr/bin/chromium",
             "/usr/bin/chromium-browser",
             "/snap/bin/chromium",
+            "/opt/google/chrome/chrome",
+            // Debian/Ubuntu arm64 and Raspberry Pi OS install the real binary here
+            "/usr/lib/chromium/chromium",
+            "/usr/lib/chromium-browser/chromium-browser",
         ];
 
-        paths.iter().map(PathBuf::from).find(|p| p.exists())
+        first_existing_file(&paths)
     }
 
     /// Check if the HTML contains a CAPTCHA challenge
@@ -403,12 +583,59 @@ impl HeadlessService {
     }
 }
 
+/// Executable names searched for on `PATH` when no install location matches
+#[cfg(target_os = "windows")]
+const CHROME_EXECUTABLE_NAMES: &[&str] = &["chrome.exe", "msedge.exe"];
+#[cfg(not(target_os = "windows"))]
+const CHROME_EXECUTABLE_NAMES: &[&str] = &[
+    "google-chrome",
+    "google-chrome-stable",
+    "chromium",
+    "chromium-browser",
+    "chrome",
+];
+
+/// First of `paths` that is an existing file (a directory such as an `.app`
+/// bundle doesn't count)
+fn first_existing_file(paths: &[&str]) -> Option<PathBuf> {
+    paths.iter().map(PathBuf::from).find(|p| p.is_file())
+}
+
+/// Search the directories of a `PATH`-style list for the first of `names`
+fn find_on_path(names: &[&str], path_var: &std::ffi::OsStr) -> Option<PathBuf> {
+    std::env::split_paths(path_var).find_map(|dir| {
+        names
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
 impl Default for HeadlessService {
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl Drop for HeadlessService {
+    fn drop(&mut self) {
+        if !self.temporary_profile {
+            return;
+        }
+        // Close the browser before removing the profile it is using
+        self.browser.take();
+        if let Err(e) = std::fs::remove_dir_all(&self.user_data_dir) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::debug!(
+                    "Failed to remove temporary Chrome profile {}: {}",
+                    self.user_data_dir.display(),
+                    e
+                );
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -428,6 +655,79 @@ mod tests {
         assert!(!service.user_data_dir.as_os_str().is_empty());
     }
 
+    #[test]
+    fn test_wait_for_selector_is_opt_in() {
+        let service = HeadlessService::with_temporary_profile();
+        assert_eq!(service.wait_for_selector, None);
+
+        let service = service.with_wait_for_selector("[itemprop=price]");
+        assert_eq!(
+            service.wait_for_selector.as_deref(),
+            Some("[itemprop=price]")
+        );
+    }
+
+    #[test]
+    fn test_wait_for_selector_timeout_capped_by_page_timeout() {
+        let service = HeadlessService::with_temporary_profile();
+        assert_eq!(
+            service.wait_for_selector_timeout(),
+            Duration::from_secs(HeadlessService::WAIT_FOR_SELECTOR_TIMEOUT_SECS)
+        );
+
+        let service = service.with_page_timeout(Duration::from_secs(3));
+        assert_eq!(service.wait_for_selector_timeout(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_domain_profile_dir_is_stable_per_domain() {
+        let base = Path::new("/profiles");
+        let first = HeadlessService::domain_profile_dir(base, "shop.example.com");
+        let second = HeadlessService::domain_profile_dir(base, "Shop.Example.com");
+
+        assert_eq!(first, second);
+        assert_eq!(first, base.join("domains").join("shop.example.com"));
+    }
+
+    #[test]
+    fn test_domain_profile_dir_differs_between_domains() {
+        let base = Path::new("/profiles");
+        assert_ne!(
+            HeadlessService::domain_profile_dir(base, "a.example.com"),
+            HeadlessService::domain_profile_dir(base, "b.example.com")
+        );
+    }
+
+    #[test]
+    fn test_domain_profile_dir_sanitizes_path_characters() {
+        let base = Path::new("/profiles");
+        let dir = HeadlessService::domain_profile_dir(base, "../evil/host:8080");
+
+        assert_eq!(dir.parent(), Some(base.join("domains").as_path()));
+        assert_eq!(dir.file_name().unwrap(), ".._evil_host_8080");
+    }
+
+    #[test]
+    fn test_domain_profile_dir_never_escapes_base() {
+        let base = Path::new("/profiles");
+        for domain in ["", ".", ".."] {
+            let dir = HeadlessService::domain_profile_dir(base, domain);
+            assert_eq!(dir.parent(), Some(base.join("domains").as_path()));
+        }
+    }
+
+    #[test]
+    fn test_temporary_profile_is_unique_and_removed_on_drop() {
+        let first = HeadlessService::with_temporary_profile();
+        let second = HeadlessService::with_temporary_profile();
+        assert_ne!(first.user_data_dir, second.user_data_dir);
+
+        let dir = first.user_data_dir.clone();
+        std::fs::create_dir_all(&dir).unwrap();
+        drop(first);
+        assert!(!dir.exists());
+    }
+
     #[test]
     fn test_is_captcha_challenge_detects_recaptcha() {
         let html = r#"<div class="g-recaptcha" data-sitekey="abc"></div>"#;
@@ -469,6 +769,82 @@ mod tests {
         assert!(!HeadlessService::is_captcha_challenge(html));
     }
 
+    /// Directory holding an empty file standing in for a browser binary
+    fn fake_browser_dir(name: &str) -> (PathBuf, PathBuf) {
+        let dir =
+            std::env::temp_dir().join(format!("product-stalker-browser-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let binary = dir.join(name);
+        std::fs::write(&binary, b"").unwrap();
+        (dir, binary)
+    }
+
+    #[test]
+    fn test_resolve_browser_path_uses_configured_binary() {
+        let (dir, binary) = fake_browser_dir("my-chrome");
+
+        let resolved = HeadlessService::resolve_browser_path(Some(&binary)).unwrap();
+        assert_eq!(resolved, binary);
+
+        let status = HeadlessService::browser_status(Some(&binary));
+        assert!(status.available);
+        assert_eq!(status.path, Some(binary.display().to_string()));
+        assert_eq!(status.error, None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_browser_path_rejects_missing_configured_binary() {
+        let missing = std::env::temp_dir().join("product-stalker-no-such-browser");
+
+        let err = HeadlessService::resolve_browser_path(Some(&missing)).unwrap_err();
+        assert!(matches!(&err, AppError::Internal(msg) if msg.starts_with("Chrome not found at")));
+
+        let status = HeadlessService::browser_status(Some(&missing));
+        assert!(!status.available);
+        assert_eq!(status.path, None);
+        assert!(status.error.unwrap().contains("Chrome not found"));
+    }
+
+    #[test]
+    fn test_resolve_browser_path_rejects_directory() {
+        let (dir, _) = fake_browser_dir("chrome");
+        assert!(HeadlessService::resolve_browser_path(Some(&dir)).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_on_path_returns_first_match_in_path_order() {
+        let (first_dir, _) = fake_browser_dir("chromium");
+        let (second_dir, _) = fake_browser_dir("google-chrome");
+        let path_var = std::env::join_paths([&first_dir, &second_dir]).unwrap();
+
+        assert_eq!(
+            find_on_path(&["google-chrome", "chromium"], &path_var),
+            Some(first_dir.join("chromium"))
+        );
+        assert_eq!(find_on_path(&["brave"], &path_var), None);
+
+        std::fs::remove_dir_all(first_dir).unwrap();
+        std::fs::remove_dir_all(second_dir).unwrap();
+    }
+
+    #[test]
+    fn test_first_existing_file_skips_missing_and_directories() {
+        let (dir, binary) = fake_browser_dir("chrome");
+        let missing = dir.join("missing");
+
+        let found = first_existing_file(&[
+            missing.to_str().unwrap(),
+            dir.to_str().unwrap(),
+            binary.to_str().unwrap(),
+        ]);
+        assert_eq!(found, Some(binary));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
     #[test]
     fn test_find_chrome_binary_returns_option() {
         // This test just verifies the function runs without panic
@@ -13,6 +13,8 @@ use super::scraper::USER_AGENT;
 /// Service for manual CAPTCHA verification with visible browser
 pub struct ManualVerificationService {
     user_data_dir: PathBuf,
+    /// Browser binary from settings, used instead of searching the system
+    browser_path: Option<PathBuf>,
 }
 
 impl ManualVerificationService {
@@ -21,7 +23,29 @@ impl ManualVerificationService {
         let user_data_dir =
             HeadlessService::get_user_data_dir().unwrap_or_else(|_| PathBuf::from("."));
 
-        Self { user_data_dir }
+        Self {
+            user_data_dir,
+            browser_path: None,
+        }
+    }
+
+    /// Create an instance using the domain's persistent headless profile, so the
+    /// cookies from a solved challenge are available to later headless fetches
+    pub fn for_domain(domain: &str) -> Self {
+        let user_data_dir = HeadlessService::get_domain_user_data_dir(domain)
+            .or_else(|_| HeadlessService::get_user_data_dir())
+            .unwrap_or_else(|_| PathBuf::from("."));
+
+        Self {
+            user_data_dir,
+            browser_path: None,
+        }
+    }
+
+    /// Launch this browser binary instead of searching the usual install locations
+    pub fn with_browser_path(mut self, browser_path: Option<PathBuf>) -> Self {
+        self.browser_path = browser_path;
+        self
     }
 
     /// Launch visible browser for manual CAPTCHA solving
@@ -30,9 +54,7 @@ impl ManualVerificationService {
     pub fn launch_visible_browser(&self, url: &str) -> Result<(String, String), AppError> {
         log::info!("ManualVerification: launching visible browser for {}", url);
 
-        let chrome_path = HeadlessService::find_chrome_binary().ok_or_else(|| {
-            AppError::External("Chrome/Chromium not found. Please install Chrome.".to_string())
-        })?;
+        let chrome_path = HeadlessService::resolve_browser_path(self.browser_path.as_deref())?;
 
         let user_agent_arg = format!("--user-agent={}", USER_AGENT);
         let user_data_arg = format!("--user-data-dir={}", self.user_data_dir.display());
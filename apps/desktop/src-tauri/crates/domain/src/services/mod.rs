@@ -1,25 +1,38 @@
 //! Domain services
 
 mod availability;
+mod backup_service;
 pub mod currency;
 mod domain_setting_service;
+mod export_service;
 mod headless_service;
 mod manual_verification_service;
 mod notification_service;
 mod product_retailer_service;
 mod product_service;
 pub mod scraper;
+mod tag_service;
+mod url_import_service;
+mod webhook_service;
 
 pub use availability::{
     AvailabilityService, BulkCheckResult, BulkCheckSummary, CheckConfig, CheckProcessingResult,
-    CheckResultWithNotification, DailyPriceComparison, ProductCheckContext,
+    CheckResultWithNotification, ComparisonPoint, DailyPriceComparison, DomainHealth,
+    PortfolioSummary, ProductCheckContext, ProductComparison, ReliabilityLabel, RestockStats,
+    RetailerComparison, SelectedCheckTargets, UrlValidation,
+};
+pub use backup_service::{
+    BackupService, ImportMode, ImportSummary, ProductBackup, ProductBackupEntry,
+    ProductRetailerBackup, RetailerBackup, BACKUP_VERSION,
 };
 pub use domain_setting_service::{
-    DomainSettingService, DomainSettings, DomainSettingsCache, UpdateDomainSettingsParams,
+    BackgroundCheckWindow, DomainRequestOverrides, DomainSettingService, DomainSettings,
+    DomainSettingsCache, UpdateDomainSettingsParams,
 };
-pub use headless_service::HeadlessService;
+pub use export_service::ExportService;
+pub use headless_service::{HeadlessBrowserStatus, HeadlessService};
 pub use manual_verification_service::ManualVerificationService;
-pub use notification_service::NotificationService;
+pub use notification_service::{NotificationDigest, NotificationService};
 pub use product_retailer_service::{
     AddRetailerParams, ProductRetailerService, ReorderRetailersParams,
 };
@@ -27,4 +40,9 @@ pub use product_service::{
     CreateProductParams, ProductService, ReorderProductsParams, UpdateProductParams,
 };
 pub use product_stalker_core::services::notification_helpers::NotificationData;
-pub use scraper::ScraperService;
+pub use scraper::{
+    FetchOptions, FetchTimeouts, ProductGroupStrategy, ScraperService, VariantSelector,
+};
+pub use tag_service::TagService;
+pub use url_import_service::{ImportReport, UrlImportService};
+pub use webhook_service::{WebhookPayload, WebhookService};
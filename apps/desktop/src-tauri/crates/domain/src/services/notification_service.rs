@@ -1,13 +1,66 @@
 //! Notification service for composing notification content.
 
+use chrono::{DateTime, Utc};
 use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
-use crate::repositories::ProductRepository;
+use crate::entities::prelude::AvailabilityCheckModel;
+use crate::repositories::{
+    AvailabilityCheckRepository, PriceExtremes, PricePoint, ProductRepository,
+};
 use product_stalker_core::services::notification_helpers::NotificationData;
 use product_stalker_core::AppError;
 
-use super::availability::BulkCheckResult;
+use super::availability::{BulkCheckResult, BulkCheckSummary};
+
+/// Smallest discount worth calling out in a notification (e.g. "30% off")
+const MIN_NOTIFIED_DISCOUNT_PERCENT: u32 = 10;
+
+/// Changes collected by the background checker for a digest notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationDigest {
+    /// When collection started for the current digest period
+    pub started_at: DateTime<Utc>,
+    pub back_in_stock_count: usize,
+    pub price_drop_count: usize,
+    pub sold_out_count: usize,
+}
+
+impl NotificationDigest {
+    /// Start an empty digest period at `started_at`
+    pub fn new(started_at: DateTime<Utc>) -> Self {
+        Self {
+            started_at,
+            back_in_stock_count: 0,
+            price_drop_count: 0,
+            sold_out_count: 0,
+        }
+    }
+
+    /// Add the events from a bulk check, skipping products whose notifications are snoozed
+    pub fn add_summary(&mut self, summary: &BulkCheckSummary) {
+        let snoozed = summary.results.iter().filter(|r| r.notifications_snoozed);
+        let (mut back_in_stock, mut price_drops, mut sold_out) = (0, 0, 0);
+        for result in snoozed {
+            back_in_stock += usize::from(result.is_back_in_stock);
+            price_drops += usize::from(result.is_price_drop);
+            sold_out += usize::from(result.is_sold_out);
+        }
+        self.back_in_stock_count += summary.back_in_stock_count.saturating_sub(back_in_stock);
+        self.price_drop_count += summary.price_drop_count.saturating_sub(price_drops);
+        self.sold_out_count += summary.sold_out_count.saturating_sub(sold_out);
+    }
+
+    /// Whether no events have been collected
+    pub fn is_empty(&self) -> bool {
+        self.back_in_stock_count == 0 && self.price_drop_count == 0 && self.sold_out_count == 0
+    }
+
+    /// Whether the digest period of length `period` has ended by `now`
+    pub fn is_due(&self, now: DateTime<Utc>, period: chrono::Duration) -> bool {
+        now - self.started_at >= period
+    }
+}
 
 /// Service layer for notification building business logic
 ///
@@ -20,8 +73,16 @@ impl NotificationService {
     /// Build notification data for a single product check using pre-fetched settings
     ///
     /// Returns `Some(NotificationData)` if:
-    /// - The product transitioned to "back in stock"
+    /// - The product transitioned to "back in stock", sold out after limited availability,
+    ///   or its price dropped to or below the user's target price
     /// - Notifications are enabled in settings
+    /// - It is not currently quiet hours (`is_quiet_time`)
+    /// - The product's notifications are not snoozed
+    ///
+    /// Back-in-stock takes precedence, then target price, then sold out, when several
+    /// transitions happened across retailers.
+    /// A back-in-stock notification mentions when the current price is the lowest
+    /// seen since tracking began.
     ///
     /// This is the preferred method when settings have already been fetched
     /// by the orchestrator, avoiding duplicate database queries.
@@ -29,13 +90,16 @@ impl NotificationService {
         conn: &DatabaseConnection,
         product_id: Uuid,
         enable_notifications: bool,
+        is_quiet_time: bool,
         is_back_in_stock: bool,
+        is_sold_out: bool,
+        is_target_price_reached: bool,
     ) -> Result<Option<NotificationData>, AppError> {
-        if !is_back_in_stock {
+        if !is_back_in_stock && !is_sold_out && !is_target_price_reached {
             return Ok(None);
         }
 
-        if !enable_notifications {
+        if !enable_notifications || is_quiet_time {
             return Ok(None);
         }
 
@@ -44,63 +108,214 @@ impl NotificationService {
         let Some(product) = product else {
             return Ok(None);
         };
+        if product.is_notifications_snoozed(Utc::now()) {
+            return Ok(None);
+        }
 
-        Ok(Some(Self::compose_single_back_in_stock(&product.name)))
+        let notification = if is_back_in_stock {
+            let lowest_in_days = Self::find_lowest_price_span_days(conn, product_id).await?;
+            Self::compose_single_back_in_stock(&product.name, lowest_in_days)
+        } else if is_target_price_reached {
+            Self::compose_single_target_price(&product.name)
+        } else {
+            Self::compose_single_sold_out(&product.name)
+        };
+        Ok(Some(notification.for_product(product_id)))
     }
 
-    /// Build notification data for a single product that is back in stock
-    fn compose_single_back_in_stock(product_name: &str) -> NotificationData {
-        NotificationData {
-            title: "Product Back in Stock!".to_string(),
-            body: format!("{} is now available!", product_name),
+    /// Number of days the latest price has been the lowest on record, if it is
+    async fn find_lowest_price_span_days(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Option<i64>, AppError> {
+        let Some(latest) =
+            AvailabilityCheckRepository::find_latest_for_product(conn, product_id).await?
+        else {
+            return Ok(None);
+        };
+        let extremes = AvailabilityCheckRepository::get_price_extremes(conn, product_id).await?;
+        Ok(Self::lowest_price_span_days(&latest, &extremes))
+    }
+
+    /// Days of history over which `current` is the lowest price.
+    ///
+    /// Returns `None` when the current price is not the recorded minimum (or is in a
+    /// different currency), or when there is less than a day of history. Prices are
+    /// compared normalized when available, as in [`PriceExtremes`].
+    pub(crate) fn lowest_price_span_days(
+        current: &AvailabilityCheckModel,
+        extremes: &PriceExtremes,
+    ) -> Option<i64> {
+        let price = PricePoint::from_check(current)?;
+        let lowest = extremes.lowest.as_ref()?;
+        if price.price_minor_units != lowest.price_minor_units
+            || price.price_currency != lowest.price_currency
+        {
+            return None;
         }
+
+        let days = (current.checked_at - extremes.first_checked_at?).num_days();
+        (days >= 1).then_some(days)
+    }
+
+    /// Build notification data for a single product that is back in stock
+    fn compose_single_back_in_stock(
+        product_name: &str,
+        lowest_in_days: Option<i64>,
+    ) -> NotificationData {
+        let body = match lowest_in_days {
+            Some(1) => format!(
+                "{} is now available at its lowest price in 1 day!",
+                product_name
+            ),
+            Some(days) => format!(
+                "{} is now available at its lowest price in {} days!",
+                product_name, days
+            ),
+            None => format!("{} is now available!", product_name),
+        };
+        NotificationData::new("Product Back in Stock!", body)
+    }
+
+    /// Build notification data for a single product whose price reached the user's target
+    fn compose_single_target_price(product_name: &str) -> NotificationData {
+        NotificationData::new(
+            "Target Price Reached!",
+            format!("{} is now at or below your target price!", product_name),
+        )
+    }
+
+    /// Build notification data for a single product that sold out after limited availability
+    fn compose_single_sold_out(product_name: &str) -> NotificationData {
+        NotificationData::new(
+            "Product Sold Out",
+            format!("{} has sold out.", product_name),
+        )
     }
 
     /// Build notification data for a bulk check using pre-fetched settings
     ///
     /// Returns `Some(NotificationData)` if:
-    /// - There are products back in stock OR price drops
+    /// - There are products back in stock, price drops, OR sold out products
+    ///   whose notifications are not snoozed
     /// - Notifications are enabled in settings
+    /// - It is not currently quiet hours (`is_quiet_time`)
     ///
     /// This is the preferred method when settings have already been fetched
     /// by the orchestrator, avoiding duplicate database queries.
     pub fn build_bulk_notification(
         enable_notifications: bool,
+        is_quiet_time: bool,
         back_in_stock_count: usize,
         price_drop_count: usize,
+        sold_out_count: usize,
         results: &[BulkCheckResult],
     ) -> Option<NotificationData> {
-        if back_in_stock_count == 0 && price_drop_count == 0 {
+        if back_in_stock_count == 0 && price_drop_count == 0 && sold_out_count == 0 {
             return None;
         }
 
-        if !enable_notifications {
+        if !enable_notifications || is_quiet_time {
+            return None;
+        }
+
+        let back_in_stock = Self::collect_product_labels(results, |r| r.is_back_in_stock);
+        let price_drops = Self::collect_product_labels(results, |r| r.is_price_drop);
+        let sold_out = Self::collect_product_labels(results, |r| r.is_sold_out);
+        if back_in_stock.is_empty() && price_drops.is_empty() && sold_out.is_empty() {
+            return None;
+        }
+        let back_in_stock: Vec<&str> = back_in_stock.iter().map(String::as_str).collect();
+        let price_drops: Vec<&str> = price_drops.iter().map(String::as_str).collect();
+        let sold_out: Vec<&str> = sold_out.iter().map(String::as_str).collect();
+
+        let body = Self::compose_notification_body(&back_in_stock, &price_drops, &sold_out);
+        let title = Self::compose_notification_title(&back_in_stock, &price_drops, &sold_out);
+
+        let notification = NotificationData::new(title, body);
+        match Self::single_notified_product(results) {
+            Some(product_id) => Some(notification.for_product(product_id)),
+            None => Some(notification),
+        }
+    }
+
+    /// The product a bulk notification is about, when it mentions exactly one
+    fn single_notified_product(results: &[BulkCheckResult]) -> Option<&str> {
+        let mut notified = results
+            .iter()
+            .filter(|r| {
+                !r.notifications_snoozed && (r.is_back_in_stock || r.is_price_drop || r.is_sold_out)
+            })
+            .map(|r| r.product_id.as_str());
+        let first = notified.next()?;
+        notified.all(|id| id == first).then_some(first)
+    }
+
+    /// Build the summary notification for a digest period
+    ///
+    /// Returns `None` when notifications are disabled or nothing changed.
+    /// The body counts each event type, e.g. "3 restocked, 2 price drops".
+    pub fn build_digest_notification(
+        enable_notifications: bool,
+        digest_interval: &str,
+        digest: &NotificationDigest,
+    ) -> Option<NotificationData> {
+        if !enable_notifications || digest.is_empty() {
             return None;
         }
 
-        let back_in_stock = Self::collect_product_names(results, |r| r.is_back_in_stock);
-        let price_drops = Self::collect_product_names(results, |r| r.is_price_drop);
+        let mut parts = Vec::new();
+        if digest.back_in_stock_count > 0 {
+            parts.push(format!("{} restocked", digest.back_in_stock_count));
+        }
+        match digest.price_drop_count {
+            0 => {}
+            1 => parts.push("1 price drop".to_string()),
+            count => parts.push(format!("{} price drops", count)),
+        }
+        if digest.sold_out_count > 0 {
+            parts.push(format!("{} sold out", digest.sold_out_count));
+        }
 
-        let body = Self::compose_notification_body(&back_in_stock, &price_drops);
-        let title = Self::compose_notification_title(&back_in_stock, &price_drops);
+        let title = match digest_interval {
+            "weekly" => "Weekly Summary",
+            _ => "Daily Summary",
+        };
 
-        Some(NotificationData { title, body })
+        Some(NotificationData::new(title, parts.join(", ")))
     }
 
-    /// Collect product names from results based on a filter predicate
-    fn collect_product_names<F>(results: &[BulkCheckResult], predicate: F) -> Vec<&str>
+    /// Collect labels of non-snoozed products from results based on a filter predicate
+    fn collect_product_labels<F>(results: &[BulkCheckResult], predicate: F) -> Vec<String>
     where
         F: Fn(&BulkCheckResult) -> bool,
     {
         results
             .iter()
-            .filter(|r| predicate(r))
-            .map(|r| r.product_name.as_str())
+            .filter(|r| !r.notifications_snoozed && predicate(r))
+            .map(Self::product_label)
             .collect()
     }
 
-    /// Compose the notification body from back-in-stock and price drop product lists
-    fn compose_notification_body(back_in_stock: &[&str], price_drops: &[&str]) -> String {
+    /// Product name, followed by e.g. "(30% off)" when a purchasable product is
+    /// discounted by at least `MIN_NOTIFIED_DISCOUNT_PERCENT` against its compare-at price
+    pub(crate) fn product_label(result: &BulkCheckResult) -> String {
+        match result.discount_percent {
+            Some(percent)
+                if percent >= MIN_NOTIFIED_DISCOUNT_PERCENT && result.status.is_available() =>
+            {
+                format!("{} ({}% off)", result.product_name, percent)
+            }
+            _ => result.product_name.clone(),
+        }
+    }
+
+    /// Compose the notification body from back-in-stock, price drop and sold out product lists
+    fn compose_notification_body(
+        back_in_stock: &[&str],
+        price_drops: &[&str],
+        sold_out: &[&str],
+    ) -> String {
         let mut parts = Vec::new();
 
         if !back_in_stock.is_empty() {
@@ -111,6 +326,10 @@ impl NotificationService {
             parts.push(Self::format_price_drop_message(price_drops));
         }
 
+        if !sold_out.is_empty() {
+            parts.push(Self::format_sold_out_message(sold_out));
+        }
+
         parts.join(" ")
     }
 
@@ -140,14 +359,31 @@ impl NotificationService {
         }
     }
 
+    /// Format the sold out portion of a notification message
+    pub(crate) fn format_sold_out_message(products: &[&str]) -> String {
+        if products.len() == 1 {
+            format!("{} has sold out.", products[0])
+        } else {
+            format!(
+                "{} products have sold out: {}",
+                products.len(),
+                products.join(", ")
+            )
+        }
+    }
+
     /// Compose the notification title based on what events occurred
     pub(crate) fn compose_notification_title(
         back_in_stock: &[&str],
         price_drops: &[&str],
+        sold_out: &[&str],
     ) -> String {
-        match (!back_in_stock.is_empty(), !price_drops.is_empty()) {
+        let has_stock_change = !back_in_stock.is_empty() || !sold_out.is_empty();
+        match (has_stock_change, !price_drops.is_empty()) {
             (true, true) => "Stock & Price Updates!".to_string(),
-            (true, false) => "Products Back in Stock!".to_string(),
+            (true, false) if sold_out.is_empty() => "Products Back in Stock!".to_string(),
+            (true, false) if back_in_stock.is_empty() => "Products Sold Out!".to_string(),
+            (true, false) => "Stock Updates!".to_string(),
             (false, true) => "Price Drops!".to_string(),
             (false, false) => String::new(), // Should not happen given earlier checks
         }
@@ -198,7 +434,7 @@ mod tests {
             let back_in_stock = vec!["Product A"];
             let price_drops = vec!["Product B"];
             let title =
-                NotificationService::compose_notification_title(&back_in_stock, &price_drops);
+                NotificationService::compose_notification_title(&back_in_stock, &price_drops, &[]);
             assert_eq!(title, "Stock & Price Updates!");
         }
 
@@ -207,7 +443,7 @@ mod tests {
             let back_in_stock = vec!["Product A"];
             let price_drops: Vec<&str> = vec![];
             let title =
-                NotificationService::compose_notification_title(&back_in_stock, &price_drops);
+                NotificationService::compose_notification_title(&back_in_stock, &price_drops, &[]);
             assert_eq!(title, "Products Back in Stock!");
         }
 
@@ -216,7 +452,7 @@ mod tests {
             let back_in_stock: Vec<&str> = vec![];
             let price_drops = vec!["Product B"];
             let title =
-                NotificationService::compose_notification_title(&back_in_stock, &price_drops);
+                NotificationService::compose_notification_title(&back_in_stock, &price_drops, &[]);
             assert_eq!(title, "Price Drops!");
         }
 
@@ -224,7 +460,8 @@ mod tests {
         fn test_compose_notification_body_both_events() {
             let back_in_stock = vec!["Product A"];
             let price_drops = vec!["Product B"];
-            let body = NotificationService::compose_notification_body(&back_in_stock, &price_drops);
+            let body =
+                NotificationService::compose_notification_body(&back_in_stock, &price_drops, &[]);
             assert_eq!(
                 body,
                 "Product A is back in stock! Product B has a price drop!"
@@ -235,7 +472,8 @@ mod tests {
         fn test_compose_notification_body_only_back_in_stock() {
             let back_in_stock = vec!["Product A", "Product B"];
             let price_drops: Vec<&str> = vec![];
-            let body = NotificationService::compose_notification_body(&back_in_stock, &price_drops);
+            let body =
+                NotificationService::compose_notification_body(&back_in_stock, &price_drops, &[]);
             assert_eq!(body, "2 products back in stock: Product A, Product B");
         }
 
@@ -243,19 +481,86 @@ mod tests {
         fn test_compose_notification_body_only_price_drops() {
             let back_in_stock: Vec<&str> = vec![];
             let price_drops = vec!["Product C"];
-            let body = NotificationService::compose_notification_body(&back_in_stock, &price_drops);
+            let body =
+                NotificationService::compose_notification_body(&back_in_stock, &price_drops, &[]);
             assert_eq!(body, "Product C has a price drop!");
         }
 
+        #[test]
+        fn test_format_sold_out_message_single_product() {
+            let products = vec!["Product A"];
+            let message = NotificationService::format_sold_out_message(&products);
+            assert_eq!(message, "Product A has sold out.");
+        }
+
+        #[test]
+        fn test_format_sold_out_message_multiple_products() {
+            let products = vec!["Product A", "Product B"];
+            let message = NotificationService::format_sold_out_message(&products);
+            assert_eq!(message, "2 products have sold out: Product A, Product B");
+        }
+
+        #[test]
+        fn test_compose_notification_title_only_sold_out() {
+            let title = NotificationService::compose_notification_title(&[], &[], &["Product A"]);
+            assert_eq!(title, "Products Sold Out!");
+        }
+
+        #[test]
+        fn test_compose_notification_title_back_in_stock_and_sold_out() {
+            let title = NotificationService::compose_notification_title(
+                &["Product A"],
+                &[],
+                &["Product B"],
+            );
+            assert_eq!(title, "Stock Updates!");
+        }
+
+        #[test]
+        fn test_compose_single_sold_out() {
+            let notification = NotificationService::compose_single_sold_out("Test Product");
+            assert_eq!(notification.title, "Product Sold Out");
+            assert_eq!(notification.body, "Test Product has sold out.");
+        }
+
+        #[test]
+        fn test_compose_single_target_price() {
+            let notification = NotificationService::compose_single_target_price("Test Product");
+            assert_eq!(notification.title, "Target Price Reached!");
+            assert_eq!(
+                notification.body,
+                "Test Product is now at or below your target price!"
+            );
+        }
+
         #[test]
         fn test_compose_single_back_in_stock() {
-            let notification = NotificationService::compose_single_back_in_stock("Test Product");
+            let notification =
+                NotificationService::compose_single_back_in_stock("Test Product", None);
             assert_eq!(notification.title, "Product Back in Stock!");
             assert_eq!(notification.body, "Test Product is now available!");
         }
 
         #[test]
-        fn test_collect_product_names_filters_correctly() {
+        fn test_compose_single_back_in_stock_at_lowest_price() {
+            let notification =
+                NotificationService::compose_single_back_in_stock("Test Product", Some(30));
+            assert_eq!(notification.title, "Product Back in Stock!");
+            assert_eq!(
+                notification.body,
+                "Test Product is now available at its lowest price in 30 days!"
+            );
+
+            let notification =
+                NotificationService::compose_single_back_in_stock("Test Product", Some(1));
+            assert_eq!(
+                notification.body,
+                "Test Product is now available at its lowest price in 1 day!"
+            );
+        }
+
+        #[test]
+        fn test_collect_product_labels_filters_correctly() {
             let results = vec![
                 BulkCheckResult {
                     product_id: "1".to_string(),
@@ -277,11 +582,11 @@ mod tests {
             ];
 
             let back_in_stock =
-                NotificationService::collect_product_names(&results, |r| r.is_back_in_stock);
+                NotificationService::collect_product_labels(&results, |r| r.is_back_in_stock);
             assert_eq!(back_in_stock, vec!["Product A"]);
 
             let price_drops =
-                NotificationService::collect_product_names(&results, |r| r.is_price_drop);
+                NotificationService::collect_product_labels(&results, |r| r.is_price_drop);
             assert_eq!(price_drops, vec!["Product B"]);
         }
     }
@@ -289,12 +594,56 @@ mod tests {
     /// Tests for build_bulk_notification
     mod build_bulk_notification_tests {
         use super::*;
+        use crate::entities::availability_check::AvailabilityStatus;
+
+        #[test]
+        fn test_no_notification_when_only_snoozed_products_changed() {
+            let results = vec![BulkCheckResult {
+                product_id: "1".to_string(),
+                product_name: "Product A".to_string(),
+                is_back_in_stock: true,
+                notifications_snoozed: true,
+                ..Default::default()
+            }];
+
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 1, 0, 0, &results);
+
+            assert!(notification.is_none());
+        }
+
+        #[test]
+        fn test_notification_omits_snoozed_products() {
+            let results = vec![
+                BulkCheckResult {
+                    product_id: "1".to_string(),
+                    product_name: "Product A".to_string(),
+                    is_back_in_stock: true,
+                    notifications_snoozed: true,
+                    ..Default::default()
+                },
+                BulkCheckResult {
+                    product_id: "2".to_string(),
+                    product_name: "Product B".to_string(),
+                    is_back_in_stock: true,
+                    ..Default::default()
+                },
+            ];
+
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 2, 0, 0, &results)
+                    .unwrap();
+
+            assert!(notification.body.contains("Product B"));
+            assert!(!notification.body.contains("Product A"));
+        }
 
         #[test]
         fn test_no_notification_when_no_events() {
             let results: Vec<BulkCheckResult> = vec![];
 
-            let notification = NotificationService::build_bulk_notification(true, 0, 0, &results);
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 0, 0, 0, &results);
 
             assert!(notification.is_none());
         }
@@ -308,11 +657,29 @@ mod tests {
                 ..Default::default()
             }];
 
-            let notification = NotificationService::build_bulk_notification(false, 1, 0, &results);
+            let notification =
+                NotificationService::build_bulk_notification(false, false, 1, 0, 0, &results);
 
             assert!(notification.is_none());
         }
 
+        #[test]
+        fn test_no_notification_during_quiet_hours() {
+            let results = vec![BulkCheckResult {
+                product_id: "1".to_string(),
+                product_name: "Product A".to_string(),
+                is_back_in_stock: true,
+                ..Default::default()
+            }];
+
+            let quiet = NotificationService::build_bulk_notification(true, true, 1, 0, 0, &results);
+            assert!(quiet.is_none());
+
+            let outside =
+                NotificationService::build_bulk_notification(true, false, 1, 0, 0, &results);
+            assert!(outside.is_some());
+        }
+
         #[test]
         fn test_notification_with_back_in_stock() {
             let results = vec![BulkCheckResult {
@@ -322,7 +689,8 @@ mod tests {
                 ..Default::default()
             }];
 
-            let notification = NotificationService::build_bulk_notification(true, 1, 0, &results);
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 1, 0, 0, &results);
 
             assert!(notification.is_some());
             let notification = notification.unwrap();
@@ -339,7 +707,8 @@ mod tests {
                 ..Default::default()
             }];
 
-            let notification = NotificationService::build_bulk_notification(true, 0, 1, &results);
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 0, 1, 0, &results);
 
             assert!(notification.is_some());
             let notification = notification.unwrap();
@@ -347,6 +716,60 @@ mod tests {
             assert_eq!(notification.body, "Product A has a price drop!");
         }
 
+        #[test]
+        fn test_notification_mentions_discount() {
+            let results = vec![BulkCheckResult {
+                product_id: "1".to_string(),
+                product_name: "Product A".to_string(),
+                status: AvailabilityStatus::InStock,
+                is_price_drop: true,
+                discount_percent: Some(30),
+                ..Default::default()
+            }];
+
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 0, 1, 0, &results)
+                    .unwrap();
+
+            assert_eq!(notification.body, "Product A (30% off) has a price drop!");
+        }
+
+        #[test]
+        fn test_notification_omits_small_discount() {
+            let results = vec![BulkCheckResult {
+                product_id: "1".to_string(),
+                product_name: "Product A".to_string(),
+                status: AvailabilityStatus::InStock,
+                is_back_in_stock: true,
+                discount_percent: Some(5),
+                ..Default::default()
+            }];
+
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 1, 0, 0, &results)
+                    .unwrap();
+
+            assert_eq!(notification.body, "Product A is back in stock!");
+        }
+
+        #[test]
+        fn test_notification_omits_discount_when_sold_out() {
+            let results = vec![BulkCheckResult {
+                product_id: "1".to_string(),
+                product_name: "Product A".to_string(),
+                status: AvailabilityStatus::OutOfStock,
+                is_sold_out: true,
+                discount_percent: Some(30),
+                ..Default::default()
+            }];
+
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 0, 0, 1, &results)
+                    .unwrap();
+
+            assert_eq!(notification.body, "Product A has sold out.");
+        }
+
         #[test]
         fn test_notification_with_both_events() {
             let results = vec![
@@ -364,7 +787,8 @@ mod tests {
                 },
             ];
 
-            let notification = NotificationService::build_bulk_notification(true, 1, 1, &results);
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 1, 1, 0, &results);
 
             assert!(notification.is_some());
             let notification = notification.unwrap();
@@ -372,5 +796,485 @@ mod tests {
             assert!(notification.body.contains("Product A is back in stock!"));
             assert!(notification.body.contains("Product B has a price drop!"));
         }
+
+        #[test]
+        fn test_notification_with_sold_out() {
+            let results = vec![BulkCheckResult {
+                product_id: "1".to_string(),
+                product_name: "Product A".to_string(),
+                is_sold_out: true,
+                ..Default::default()
+            }];
+
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 0, 0, 1, &results);
+
+            let notification = notification.unwrap();
+            assert_eq!(notification.title, "Products Sold Out!");
+            assert_eq!(notification.body, "Product A has sold out.");
+        }
+
+        #[test]
+        fn test_single_product_notification_links_to_product() {
+            let results = vec![
+                BulkCheckResult {
+                    product_id: "1".to_string(),
+                    product_name: "Product A".to_string(),
+                    is_back_in_stock: true,
+                    is_price_drop: true,
+                    ..Default::default()
+                },
+                BulkCheckResult {
+                    product_id: "2".to_string(),
+                    product_name: "Product B".to_string(),
+                    is_back_in_stock: true,
+                    notifications_snoozed: true,
+                    ..Default::default()
+                },
+                BulkCheckResult {
+                    product_id: "3".to_string(),
+                    product_name: "Product C".to_string(),
+                    ..Default::default()
+                },
+            ];
+
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 2, 1, 0, &results)
+                    .unwrap();
+
+            assert_eq!(notification.product_id.as_deref(), Some("1"));
+            assert_eq!(notification.action_url.as_deref(), Some("/products/1"));
+        }
+
+        #[test]
+        fn test_multi_product_notification_has_no_link() {
+            let results = vec![
+                BulkCheckResult {
+                    product_id: "1".to_string(),
+                    product_name: "Product A".to_string(),
+                    is_back_in_stock: true,
+                    ..Default::default()
+                },
+                BulkCheckResult {
+                    product_id: "2".to_string(),
+                    product_name: "Product B".to_string(),
+                    is_sold_out: true,
+                    ..Default::default()
+                },
+            ];
+
+            let notification =
+                NotificationService::build_bulk_notification(true, false, 1, 0, 1, &results)
+                    .unwrap();
+
+            assert_eq!(notification.product_id, None);
+            assert_eq!(notification.action_url, None);
+        }
+    }
+
+    mod digest_tests {
+        use super::*;
+        use chrono::{Duration, TimeZone};
+
+        fn digest(back_in_stock: usize, price_drops: usize, sold_out: usize) -> NotificationDigest {
+            NotificationDigest {
+                back_in_stock_count: back_in_stock,
+                price_drop_count: price_drops,
+                sold_out_count: sold_out,
+                ..NotificationDigest::new(Utc::now())
+            }
+        }
+
+        fn digest_body(digest: &NotificationDigest) -> String {
+            NotificationService::build_digest_notification(true, "daily", digest)
+                .unwrap()
+                .body
+        }
+
+        #[test]
+        fn test_digest_body_counts_each_event_type() {
+            assert_eq!(
+                digest_body(&digest(3, 2, 1)),
+                "3 restocked, 2 price drops, 1 sold out"
+            );
+        }
+
+        #[test]
+        fn test_digest_body_omits_empty_event_types() {
+            assert_eq!(digest_body(&digest(3, 2, 0)), "3 restocked, 2 price drops");
+            assert_eq!(digest_body(&digest(0, 0, 4)), "4 sold out");
+            assert_eq!(digest_body(&digest(1, 0, 0)), "1 restocked");
+        }
+
+        #[test]
+        fn test_digest_body_singular_price_drop() {
+            assert_eq!(digest_body(&digest(0, 1, 0)), "1 price drop");
+        }
+
+        #[test]
+        fn test_digest_title_follows_interval() {
+            let daily =
+                NotificationService::build_digest_notification(true, "daily", &digest(1, 0, 0));
+            let weekly =
+                NotificationService::build_digest_notification(true, "weekly", &digest(1, 0, 0));
+            assert_eq!(daily.unwrap().title, "Daily Summary");
+            assert_eq!(weekly.unwrap().title, "Weekly Summary");
+        }
+
+        #[test]
+        fn test_no_digest_when_empty_or_disabled() {
+            assert!(NotificationService::build_digest_notification(
+                true,
+                "daily",
+                &digest(0, 0, 0)
+            )
+            .is_none());
+            assert!(NotificationService::build_digest_notification(
+                false,
+                "daily",
+                &digest(2, 1, 0)
+            )
+            .is_none());
+        }
+
+        #[test]
+        fn test_digest_accumulates_bulk_summaries() {
+            let mut digest = NotificationDigest::new(Utc::now());
+            let summary = BulkCheckSummary {
+                total: 5,
+                successful: 5,
+                failed: 0,
+                back_in_stock_count: 2,
+                price_drop_count: 1,
+                sold_out_count: 0,
+                results: Vec::new(),
+            };
+
+            digest.add_summary(&summary);
+            digest.add_summary(&summary);
+
+            assert_eq!(digest.back_in_stock_count, 4);
+            assert_eq!(digest.price_drop_count, 2);
+            assert_eq!(digest.sold_out_count, 0);
+            assert!(!digest.is_empty());
+        }
+
+        #[test]
+        fn test_digest_skips_snoozed_products() {
+            let mut digest = NotificationDigest::new(Utc::now());
+            let summary = BulkCheckSummary {
+                total: 2,
+                successful: 2,
+                failed: 0,
+                back_in_stock_count: 2,
+                price_drop_count: 1,
+                sold_out_count: 0,
+                results: vec![
+                    BulkCheckResult {
+                        is_back_in_stock: true,
+                        is_price_drop: true,
+                        notifications_snoozed: true,
+                        ..Default::default()
+                    },
+                    BulkCheckResult {
+                        is_back_in_stock: true,
+                        ..Default::default()
+                    },
+                ],
+            };
+
+            digest.add_summary(&summary);
+
+            assert_eq!(digest.back_in_stock_count, 1);
+            assert_eq!(digest.price_drop_count, 0);
+        }
+
+        #[test]
+        fn test_digest_is_due_at_interval_boundary() {
+            let start = Utc.with_ymd_and_hms(2026, 1, 15, 9, 0, 0).unwrap();
+            let digest = NotificationDigest::new(start);
+
+            assert!(!digest.is_due(start + Duration::hours(23), Duration::days(1)));
+            assert!(digest.is_due(start + Duration::days(1), Duration::days(1)));
+            assert!(!digest.is_due(start + Duration::days(6), Duration::weeks(1)));
+            assert!(digest.is_due(start + Duration::days(8), Duration::weeks(1)));
+        }
+    }
+
+    mod lowest_price_tests {
+        use super::*;
+        use crate::repositories::PricePoint;
+        use chrono::{DateTime, Duration, Utc};
+
+        fn check(price: Option<i64>, currency: &str) -> AvailabilityCheckModel {
+            AvailabilityCheckModel {
+                id: Uuid::new_v4(),
+                product_id: Uuid::new_v4(),
+                product_retailer_id: None,
+                status: "in_stock".to_string(),
+                raw_availability: None,
+                error_message: None,
+                error_kind: None,
+                checked_at: Utc::now(),
+                price_minor_units: price,
+                price_currency: Some(currency.to_string()),
+                raw_price: None,
+                normalized_price_minor_units: None,
+                normalized_currency: None,
+                compare_at_minor_units: None,
+                price_valid_until: None,
+                fetch_duration_ms: None,
+                content_bytes: None,
+                confidence: None,
+            }
+        }
+
+        fn extremes(lowest: i64, currency: &str, first_checked_at: DateTime<Utc>) -> PriceExtremes {
+            PriceExtremes {
+                lowest: Some(PricePoint {
+                    price_minor_units: lowest,
+                    price_currency: Some(currency.to_string()),
+                    checked_at: Utc::now(),
+                }),
+                highest: None,
+                first_checked_at: Some(first_checked_at),
+            }
+        }
+
+        #[test]
+        fn test_current_price_is_lowest() {
+            let current = check(Some(900), "USD");
+            let result = NotificationService::lowest_price_span_days(
+                &current,
+                &extremes(900, "USD", current.checked_at - Duration::days(45)),
+            );
+            assert_eq!(result, Some(45));
+        }
+
+        #[test]
+        fn test_current_price_above_lowest() {
+            let current = check(Some(1000), "USD");
+            let result = NotificationService::lowest_price_span_days(
+                &current,
+                &extremes(900, "USD", current.checked_at - Duration::days(45)),
+            );
+            assert_eq!(result, None);
+        }
+
+        #[test]
+        fn test_different_currency_is_not_compared() {
+            let current = check(Some(900), "AUD");
+            let result = NotificationService::lowest_price_span_days(
+                &current,
+                &extremes(900, "USD", current.checked_at - Duration::days(45)),
+            );
+            assert_eq!(result, None);
+        }
+
+        #[test]
+        fn test_no_current_price() {
+            let current = check(None, "USD");
+            let result = NotificationService::lowest_price_span_days(
+                &current,
+                &extremes(900, "USD", current.checked_at - Duration::days(45)),
+            );
+            assert_eq!(result, None);
+        }
+
+        #[test]
+        fn test_less_than_a_day_of_history() {
+            let current = check(Some(900), "USD");
+            let result = NotificationService::lowest_price_span_days(
+                &current,
+                &extremes(900, "USD", current.checked_at - Duration::hours(3)),
+            );
+            assert_eq!(result, None);
+        }
+    }
+
+    mod build_single_notification_tests {
+        use super::*;
+        use crate::test_utils::{create_test_product_default, setup_availability_db};
+        use chrono::{Duration, Utc};
+
+        #[tokio::test]
+        async fn test_back_in_stock_mentions_lowest_price() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let now = Utc::now();
+
+            for (price, days_ago) in [
+                (Some(1500), 20),
+                (Some(1200), 10),
+                (None, 2),
+                (Some(999), 0),
+            ] {
+                AvailabilityCheckRepository::create_with_timestamp(
+                    &conn,
+                    product_id,
+                    price,
+                    now - Duration::days(days_ago),
+                )
+                .await;
+            }
+
+            let notification = NotificationService::build_single_notification(
+                &conn, product_id, true, false, true, false, false,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+            assert_eq!(
+                notification.body,
+                "Test Product is now available at its lowest price in 20 days!"
+            );
+        }
+
+        #[tokio::test]
+        async fn test_back_in_stock_above_lowest_price() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            let now = Utc::now();
+
+            for (price, days_ago) in [(Some(800), 20), (Some(1200), 0)] {
+                AvailabilityCheckRepository::create_with_timestamp(
+                    &conn,
+                    product_id,
+                    price,
+                    now - Duration::days(days_ago),
+                )
+                .await;
+            }
+
+            let notification = NotificationService::build_single_notification(
+                &conn, product_id, true, false, true, false, false,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+            assert_eq!(notification.body, "Test Product is now available!");
+        }
+
+        #[tokio::test]
+        async fn test_target_price_notification() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let notification = NotificationService::build_single_notification(
+                &conn, product_id, true, false, false, false, true,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+            assert_eq!(notification.title, "Target Price Reached!");
+
+            let disabled = NotificationService::build_single_notification(
+                &conn, product_id, false, false, false, false, true,
+            )
+            .await
+            .unwrap();
+            assert!(disabled.is_none());
+        }
+
+        #[tokio::test]
+        async fn test_no_notification_during_quiet_hours() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let notification = NotificationService::build_single_notification(
+                &conn, product_id, true, true, true, false, false,
+            )
+            .await
+            .unwrap();
+            assert!(notification.is_none());
+        }
+
+        #[tokio::test]
+        async fn test_no_notification_while_snoozed() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            ProductRepository::set_notifications_snoozed_until(
+                &conn,
+                product_id,
+                Some(Utc::now() + Duration::hours(1)),
+            )
+            .await
+            .unwrap();
+
+            let notification = NotificationService::build_single_notification(
+                &conn, product_id, true, false, true, false, true,
+            )
+            .await
+            .unwrap();
+            assert!(notification.is_none());
+        }
+
+        #[tokio::test]
+        async fn test_notifications_resume_after_snooze_ends() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+            ProductRepository::set_notifications_snoozed_until(
+                &conn,
+                product_id,
+                Some(Utc::now() - Duration::minutes(1)),
+            )
+            .await
+            .unwrap();
+
+            let notification = NotificationService::build_single_notification(
+                &conn, product_id, true, false, false, false, true,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+            assert_eq!(notification.title, "Target Price Reached!");
+        }
+
+        #[tokio::test]
+        async fn test_back_in_stock_takes_precedence_over_target_price() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            let notification = NotificationService::build_single_notification(
+                &conn, product_id, true, false, true, false, true,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+            assert_eq!(notification.title, "Product Back in Stock!");
+        }
+
+        #[tokio::test]
+        async fn test_notification_links_to_product() {
+            let conn = setup_availability_db().await;
+            let product_id = create_test_product_default(&conn).await;
+
+            for (is_back_in_stock, is_sold_out, is_target_price_reached) in [
+                (true, false, false),
+                (false, true, false),
+                (false, false, true),
+            ] {
+                let notification = NotificationService::build_single_notification(
+                    &conn,
+                    product_id,
+                    true,
+                    false,
+                    is_back_in_stock,
+                    is_sold_out,
+                    is_target_price_reached,
+                )
+                .await
+                .unwrap()
+                .unwrap();
+
+                assert_eq!(notification.product_id, Some(product_id.to_string()));
+                assert_eq!(
+                    notification.action_url,
+                    Some(format!("/products/{}", product_id))
+                );
+            }
+        }
     }
 }
@@ -7,6 +7,8 @@ use crate::entities::prelude::ProductRetailerModel;
 use crate::repositories::{
     CreateProductRetailerParams, ProductRetailerRepository, RetailerRepository,
 };
+use crate::services::scraper::canonical_redirect_url;
+use crate::services::VariantSelector;
 use product_stalker_core::AppError;
 
 /// Parameters for adding a retailer to a product
@@ -76,6 +78,62 @@ impl ProductRetailerService {
         ProductRetailerRepository::update_sort_orders(conn, params.updates).await
     }
 
+    /// Set the variant attributes checked on a link, or clear them with `None`.
+    ///
+    /// An empty selector is stored as no selector.
+    pub async fn set_variant_selector(
+        conn: &DatabaseConnection,
+        product_retailer_id: Uuid,
+        variant_selector: Option<VariantSelector>,
+    ) -> Result<ProductRetailerModel, AppError> {
+        let json = variant_selector
+            .filter(|selector| !selector.is_empty())
+            .map(|selector| serde_json::to_string(&selector))
+            .transpose()
+            .map_err(|e| AppError::Internal(format!("Failed to encode variant selector: {}", e)))?;
+        ProductRetailerRepository::update_variant_selector(conn, product_retailer_id, json).await
+    }
+
+    /// Replace a link's URL with the normalized address it redirects to.
+    ///
+    /// Only a redirect that normalizes the URL is accepted (see
+    /// [`canonical_redirect_url`]), so this can't point a link at another page.
+    /// Moving between `www.` and the bare domain also moves the link to that
+    /// domain's retailer.
+    pub async fn normalize_url(
+        conn: &DatabaseConnection,
+        product_retailer_id: Uuid,
+        canonical_url: &str,
+    ) -> Result<ProductRetailerModel, AppError> {
+        let link = ProductRetailerRepository::find_by_id(conn, product_retailer_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Product retailer not found: {}",
+                    product_retailer_id
+                ))
+            })?;
+        Self::validate_url(canonical_url)?;
+        let url = canonical_redirect_url(&link.url, canonical_url).ok_or_else(|| {
+            AppError::Validation(format!(
+                "{} is not a normalized form of {}",
+                canonical_url, link.url
+            ))
+        })?;
+
+        let siblings = ProductRetailerRepository::find_by_product_id(conn, link.product_id).await?;
+        if siblings.iter().any(|other| other.url == url) {
+            return Err(AppError::Validation(format!(
+                "This product already has a link to {}",
+                url
+            )));
+        }
+
+        let domain = Self::extract_domain(&url)?;
+        let retailer = RetailerRepository::find_or_create_by_domain(conn, &domain).await?;
+        ProductRetailerRepository::update_url(conn, product_retailer_id, retailer.id, url).await
+    }
+
     /// Remove a retailer link
     pub async fn remove_retailer(
         conn: &DatabaseConnection,
@@ -101,7 +159,7 @@ impl ProductRetailerService {
             .ok_or_else(|| AppError::Validation("URL has no host".to_string()))
     }
 
-    fn validate_url(url: &str) -> Result<(), AppError> {
+    pub(crate) fn validate_url(url: &str) -> Result<(), AppError> {
         if url.trim().is_empty() {
             return Err(AppError::Validation("URL cannot be empty".to_string()));
         }
@@ -182,6 +240,9 @@ mod integration_tests {
                 url: None,
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await
@@ -203,6 +264,118 @@ mod integration_tests {
         assert_eq!(pr.label, Some("Amazon".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_set_variant_selector() {
+        let conn = setup_product_retailer_db().await;
+        let product_id = crate::test_utils::create_test_product_default(&conn).await;
+        let pr = ProductRetailerService::add_retailer(
+            &conn,
+            AddRetailerParams {
+                product_id,
+                url: "https://shop.example.com/tee/black".to_string(),
+                label: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let selector = VariantSelector::from_json(r#"{"size":"M","color":"black"}"#).unwrap();
+        let updated = ProductRetailerService::set_variant_selector(&conn, pr.id, Some(selector))
+            .await
+            .unwrap();
+        assert_eq!(
+            updated.variant_selector.as_deref(),
+            Some(r#"{"color":"black","size":"M"}"#)
+        );
+
+        let cleared = ProductRetailerService::set_variant_selector(
+            &conn,
+            pr.id,
+            Some(VariantSelector::default()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(cleared.variant_selector, None);
+    }
+
+    #[tokio::test]
+    async fn test_normalize_url() {
+        let conn = setup_product_retailer_db().await;
+        let product_id = crate::test_utils::create_test_product_default(&conn).await;
+        let pr = ProductRetailerService::add_retailer(
+            &conn,
+            AddRetailerParams {
+                product_id,
+                url: "http://store.com/p".to_string(),
+                label: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let updated =
+            ProductRetailerService::normalize_url(&conn, pr.id, "https://www.store.com/p")
+                .await
+                .unwrap();
+
+        assert_eq!(updated.url, "https://www.store.com/p");
+        let retailer = RetailerRepository::find_by_domain(&conn, "www.store.com")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.retailer_id, retailer.id);
+    }
+
+    #[tokio::test]
+    async fn test_normalize_url_rejects_other_page() {
+        let conn = setup_product_retailer_db().await;
+        let product_id = crate::test_utils::create_test_product_default(&conn).await;
+        let pr = ProductRetailerService::add_retailer(
+            &conn,
+            AddRetailerParams {
+                product_id,
+                url: "http://store.com/p".to_string(),
+                label: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let result =
+            ProductRetailerService::normalize_url(&conn, pr.id, "https://other.com/p").await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let missing =
+            ProductRetailerService::normalize_url(&conn, Uuid::new_v4(), "https://store.com/p")
+                .await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_normalize_url_rejects_existing_link() {
+        let conn = setup_product_retailer_db().await;
+        let product_id = crate::test_utils::create_test_product_default(&conn).await;
+        for url in ["http://store.com/p", "https://store.com/p"] {
+            ProductRetailerService::add_retailer(
+                &conn,
+                AddRetailerParams {
+                    product_id,
+                    url: url.to_string(),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+        let links = ProductRetailerService::get_retailers_for_product(&conn, product_id)
+            .await
+            .unwrap();
+
+        let result =
+            ProductRetailerService::normalize_url(&conn, links[0].id, "https://store.com/p").await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
     #[tokio::test]
     async fn test_add_retailer_creates_retailer() {
         let conn = setup_product_retailer_db().await;
@@ -214,6 +387,9 @@ mod integration_tests {
                 url: None,
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await
@@ -248,6 +424,9 @@ mod integration_tests {
                 url: None,
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await
@@ -292,6 +471,9 @@ mod integration_tests {
                 url: None,
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await
@@ -336,6 +518,9 @@ mod integration_tests {
                 url: None,
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await
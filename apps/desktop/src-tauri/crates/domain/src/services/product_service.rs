@@ -1,10 +1,16 @@
 //! Product service for business logic around products.
 
+use chrono::{DateTime, Utc};
 use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
+use crate::entities::availability_check::AvailabilityStatus;
 use crate::entities::prelude::ProductModel;
-use crate::repositories::{CreateProductRepoParams, ProductRepository, ProductUpdateInput};
+use crate::repositories::{
+    CloneProductLink, CreateProductRepoParams, ProductRepository, ProductUpdateInput,
+    RetailerRepository,
+};
+use crate::services::ProductRetailerService;
 use product_stalker_core::AppError;
 
 /// Parameters for creating a new product
@@ -12,6 +18,12 @@ pub struct CreateProductParams {
     pub name: String,
     pub description: Option<String>,
     pub notes: Option<String>,
+    /// Background check interval override in minutes (None = use the global interval)
+    pub check_interval_minutes: Option<i32>,
+    /// Notify when the price drops to or below this amount (in minor units)
+    pub target_price_minor_units: Option<i64>,
+    /// ISO 4217 currency of the target price (None = same as the scraped price)
+    pub target_price_currency: Option<String>,
 }
 
 /// Parameters for updating an existing product (all fields optional for partial updates)
@@ -19,6 +31,20 @@ pub struct UpdateProductParams {
     pub name: Option<String>,
     pub description: Option<String>,
     pub notes: Option<String>,
+    /// `Some(None)` clears the override so the product falls back to the global interval
+    pub check_interval_minutes: Option<Option<i32>>,
+    /// `Some(None)` clears the target price alert
+    pub target_price_minor_units: Option<Option<i64>>,
+    pub target_price_currency: Option<Option<String>>,
+    /// Paused products are only checked on demand
+    pub is_paused: Option<bool>,
+    /// Stock-only products skip price extraction when checked
+    pub track_price: Option<bool>,
+    /// ISO 4217 currency the product's prices are in. Setting one locks it
+    /// and `Some(None)` unlocks it, unless `currency_locked` says otherwise
+    pub currency: Option<Option<String>>,
+    /// Locked currencies are never changed by auto-detection during checks
+    pub currency_locked: Option<bool>,
 }
 
 /// Parameters for reordering products
@@ -33,9 +59,16 @@ pub struct ReorderProductsParams {
 pub struct ProductService;
 
 impl ProductService {
-    /// Get all products
-    pub async fn get_all(conn: &DatabaseConnection) -> Result<Vec<ProductModel>, AppError> {
-        ProductRepository::find_all(conn).await
+    /// Get all products, leaving out archived ones unless `include_archived` is set
+    pub async fn get_all(
+        conn: &DatabaseConnection,
+        include_archived: bool,
+    ) -> Result<Vec<ProductModel>, AppError> {
+        if include_archived {
+            ProductRepository::find_all_including_archived(conn).await
+        } else {
+            ProductRepository::find_all(conn).await
+        }
     }
 
     /// Get a product by ID
@@ -45,12 +78,43 @@ impl ProductService {
             .ok_or_else(|| AppError::NotFound(format!("Product not found: {}", id)))
     }
 
+    /// Get all products whose most recent availability check has the given status
+    pub async fn get_by_latest_status(
+        conn: &DatabaseConnection,
+        status: AvailabilityStatus,
+    ) -> Result<Vec<ProductModel>, AppError> {
+        ProductRepository::find_by_latest_status(conn, status).await
+    }
+
+    /// Search products by name, description or notes.
+    ///
+    /// A blank query returns all products. Archived products are never matched.
+    pub async fn search(
+        conn: &DatabaseConnection,
+        query: &str,
+    ) -> Result<Vec<ProductModel>, AppError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Self::get_all(conn, false).await;
+        }
+        ProductRepository::search(conn, query).await
+    }
+
     /// Create a new product
     pub async fn create(
         conn: &DatabaseConnection,
         params: CreateProductParams,
     ) -> Result<ProductModel, AppError> {
         Self::validate_name(&params.name)?;
+        if let Some(interval) = params.check_interval_minutes {
+            Self::validate_check_interval(interval)?;
+        }
+        if let Some(target) = params.target_price_minor_units {
+            Self::validate_target_price(target)?;
+        }
+        if let Some(ref currency) = params.target_price_currency {
+            Self::validate_currency_code(currency)?;
+        }
 
         let id = Uuid::new_v4();
         ProductRepository::create(
@@ -61,6 +125,9 @@ impl ProductService {
                 url: None,
                 description: params.description,
                 notes: params.notes,
+                check_interval_minutes: params.check_interval_minutes,
+                target_price_minor_units: params.target_price_minor_units,
+                target_price_currency: params.target_price_currency,
             },
         )
         .await
@@ -76,6 +143,22 @@ impl ProductService {
         if let Some(ref name) = params.name {
             Self::validate_name(name)?;
         }
+        if let Some(Some(interval)) = params.check_interval_minutes {
+            Self::validate_check_interval(interval)?;
+        }
+        if let Some(Some(target)) = params.target_price_minor_units {
+            Self::validate_target_price(target)?;
+        }
+        if let Some(Some(ref currency)) = params.target_price_currency {
+            Self::validate_currency_code(currency)?;
+        }
+        if let Some(Some(ref currency)) = params.currency {
+            Self::validate_currency_code(currency)?;
+        }
+        // A currency the user picks is kept; clearing it hands it back to auto-detection
+        let currency_locked = params
+            .currency_locked
+            .or_else(|| params.currency.as_ref().map(Option::is_some));
 
         // Fetch existing product
         let product = Self::get_by_id(conn, id).await?;
@@ -89,7 +172,14 @@ impl ProductService {
                 url: None,
                 description: params.description.map(Some),
                 notes: params.notes.map(Some),
-                currency: None,
+                currency: params.currency,
+                check_interval_minutes: params.check_interval_minutes,
+                target_price_minor_units: params.target_price_minor_units,
+                target_price_currency: params.target_price_currency,
+                is_paused: params.is_paused,
+                is_archived: None,
+                track_price: params.track_price,
+                currency_locked,
             },
         )
         .await
@@ -129,6 +219,112 @@ impl ProductService {
         Ok(())
     }
 
+    /// Merge a duplicate product into another, returning the kept product.
+    ///
+    /// `merge_id`'s retailer links, check history and tags move to `keep_id`,
+    /// then `merge_id` is deleted. Nothing changes if any step fails.
+    pub async fn merge(
+        conn: &DatabaseConnection,
+        keep_id: Uuid,
+        merge_id: Uuid,
+    ) -> Result<ProductModel, AppError> {
+        if keep_id == merge_id {
+            return Err(AppError::Validation(
+                "Cannot merge a product into itself".to_string(),
+            ));
+        }
+
+        ProductRepository::merge_into(conn, keep_id, merge_id).await?;
+        Self::get_by_id(conn, keep_id).await
+    }
+
+    /// Clone a product as a template for tracking a similar item, named
+    /// "<name> (copy)".
+    ///
+    /// Settings and tags are copied, but not check history. The source's retailer
+    /// links are copied unless `new_url` is given, which becomes the clone's only link.
+    pub async fn clone(
+        conn: &DatabaseConnection,
+        id: Uuid,
+        new_url: Option<String>,
+    ) -> Result<ProductModel, AppError> {
+        let source = Self::get_by_id(conn, id).await?;
+
+        let replacement_link = match new_url {
+            Some(url) => {
+                let url = url.trim().to_string();
+                ProductRetailerService::validate_url(&url)?;
+                let domain = ProductRetailerService::extract_domain(&url)?;
+                let retailer = RetailerRepository::find_or_create_by_domain(conn, &domain).await?;
+                Some(CloneProductLink {
+                    retailer_id: retailer.id,
+                    url,
+                })
+            }
+            None => None,
+        };
+
+        ProductRepository::clone_product(
+            conn,
+            id,
+            Uuid::new_v4(),
+            format!("{} (copy)", source.name),
+            replacement_link,
+        )
+        .await
+    }
+
+    /// Snooze a product's notifications until `until`, or resume them with `None`.
+    ///
+    /// Checks keep running and being recorded while snoozed; only the
+    /// notifications they would trigger are suppressed.
+    pub async fn snooze_notifications(
+        conn: &DatabaseConnection,
+        id: Uuid,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<ProductModel, AppError> {
+        if let Some(until) = until {
+            if until <= Utc::now() {
+                return Err(AppError::Validation(
+                    "Snooze time must be in the future".to_string(),
+                ));
+            }
+        }
+
+        ProductRepository::set_notifications_snoozed_until(conn, id, until).await
+    }
+
+    /// Archive a product: it is hidden from the product list and skipped by
+    /// bulk checks, but keeps its retailers and check history
+    pub async fn archive(conn: &DatabaseConnection, id: Uuid) -> Result<ProductModel, AppError> {
+        Self::set_archived(conn, id, true).await
+    }
+
+    /// Restore an archived product to the product list and bulk checks
+    pub async fn unarchive(conn: &DatabaseConnection, id: Uuid) -> Result<ProductModel, AppError> {
+        Self::set_archived(conn, id, false).await
+    }
+
+    async fn set_archived(
+        conn: &DatabaseConnection,
+        id: Uuid,
+        is_archived: bool,
+    ) -> Result<ProductModel, AppError> {
+        let product = Self::get_by_id(conn, id).await?;
+        ProductRepository::update(
+            conn,
+            product,
+            ProductUpdateInput {
+                is_archived: Some(is_archived),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Maximum per-product check interval: 1 week (10080 minutes)
+    const MAX_CHECK_INTERVAL_MINUTES: i32 = 10080;
+
     // Private validation helpers
 
     fn validate_name(name: &str) -> Result<(), AppError> {
@@ -137,6 +333,40 @@ impl ProductService {
         }
         Ok(())
     }
+
+    fn validate_check_interval(interval: i32) -> Result<(), AppError> {
+        if interval <= 0 {
+            return Err(AppError::Validation(
+                "Check interval must be a positive number of minutes".to_string(),
+            ));
+        }
+        if interval > Self::MAX_CHECK_INTERVAL_MINUTES {
+            return Err(AppError::Validation(format!(
+                "Check interval cannot exceed {} minutes (1 week)",
+                Self::MAX_CHECK_INTERVAL_MINUTES
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_target_price(target_price_minor_units: i64) -> Result<(), AppError> {
+        if target_price_minor_units <= 0 {
+            return Err(AppError::Validation(
+                "Target price must be greater than zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_currency_code(currency: &str) -> Result<(), AppError> {
+        if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(AppError::Validation(format!(
+                "Invalid currency: {}. Must be a 3-letter ISO 4217 code",
+                currency
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +388,30 @@ mod tests {
         assert!(ProductService::validate_name("My Product").is_ok());
     }
 
+    #[test]
+    fn test_validate_check_interval() {
+        assert!(ProductService::validate_check_interval(15).is_ok());
+        assert!(ProductService::validate_check_interval(10080).is_ok());
+        assert!(ProductService::validate_check_interval(0).is_err());
+        assert!(ProductService::validate_check_interval(-5).is_err());
+        assert!(ProductService::validate_check_interval(10081).is_err());
+    }
+
+    #[test]
+    fn test_validate_target_price() {
+        assert!(ProductService::validate_target_price(1).is_ok());
+        assert!(ProductService::validate_target_price(0).is_err());
+        assert!(ProductService::validate_target_price(-100).is_err());
+    }
+
+    #[test]
+    fn test_validate_currency_code() {
+        assert!(ProductService::validate_currency_code("AUD").is_ok());
+        assert!(ProductService::validate_currency_code("aud").is_err());
+        assert!(ProductService::validate_currency_code("AU").is_err());
+        assert!(ProductService::validate_currency_code("A$D").is_err());
+    }
+
     #[test]
     fn test_reorder_validates_negative_sort_order() {
         let params = ReorderProductsParams {
@@ -177,6 +431,33 @@ mod integration_tests {
     use super::*;
     use crate::test_utils::setup_products_db;
 
+    #[tokio::test]
+    async fn test_search_trims_query_and_blank_returns_all() {
+        let conn = setup_products_db().await;
+        for name in ["RTX 4090", "Coffee Grinder"] {
+            ProductService::create(
+                &conn,
+                CreateProductParams {
+                    name: name.to_string(),
+                    description: None,
+                    notes: None,
+                    check_interval_minutes: None,
+                    target_price_minor_units: None,
+                    target_price_currency: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let results = ProductService::search(&conn, "  rtx  ").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "RTX 4090");
+
+        let results = ProductService::search(&conn, "   ").await.unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_create_product_validates_name() {
         let conn = setup_products_db().await;
@@ -186,6 +467,9 @@ mod integration_tests {
                 name: "".to_string(),
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await;
@@ -201,6 +485,9 @@ mod integration_tests {
                 name: "Test Product".to_string(),
                 description: Some("A description".to_string()),
                 notes: Some("Some notes".to_string()),
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await;
@@ -221,6 +508,9 @@ mod integration_tests {
                 name: "Minimal Product".to_string(),
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
             },
         )
         .await;
@@ -245,6 +535,9 @@ mod integration_tests {
             name: name.to_string(),
             description: None,
             notes: None,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
         }
     }
 
@@ -263,7 +556,7 @@ mod integration_tests {
     #[tokio::test]
     async fn test_get_all_empty() {
         let conn = setup_products_db().await;
-        let result = ProductService::get_all(&conn).await;
+        let result = ProductService::get_all(&conn, false).await;
 
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
@@ -283,7 +576,7 @@ mod integration_tests {
             .await
             .unwrap();
 
-        let result = ProductService::get_all(&conn).await;
+        let result = ProductService::get_all(&conn, false).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 3);
     }
@@ -302,6 +595,13 @@ mod integration_tests {
                 name: Some("Updated Name".to_string()),
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
+                is_paused: None,
+                track_price: None,
+                currency: None,
+                currency_locked: None,
             },
         )
         .await;
@@ -323,6 +623,13 @@ mod integration_tests {
                 name: None,
                 description: Some("New description".to_string()),
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
+                is_paused: None,
+                track_price: None,
+                currency: None,
+                currency_locked: None,
             },
         )
         .await;
@@ -344,6 +651,13 @@ mod integration_tests {
                 name: Some("Name".to_string()),
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
+                is_paused: None,
+                track_price: None,
+                currency: None,
+                currency_locked: None,
             },
         )
         .await;
@@ -363,6 +677,44 @@ mod integration_tests {
                 name: Some("".to_string()),
                 description: None,
                 notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
+                is_paused: None,
+                track_price: None,
+                currency: None,
+                currency_locked: None,
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_product_with_check_interval() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(
+            &conn,
+            CreateProductParams {
+                check_interval_minutes: Some(15),
+                ..params("Priority")
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(created.check_interval_minutes, Some(15));
+    }
+
+    #[tokio::test]
+    async fn test_create_validates_check_interval() {
+        let conn = setup_products_db().await;
+        let result = ProductService::create(
+            &conn,
+            CreateProductParams {
+                check_interval_minutes: Some(0),
+                ..params("Invalid")
             },
         )
         .await;
@@ -370,6 +722,334 @@ mod integration_tests {
         assert!(matches!(result, Err(AppError::Validation(_))));
     }
 
+    #[tokio::test]
+    async fn test_update_sets_and_clears_check_interval() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(&conn, params("Test")).await.unwrap();
+        assert_eq!(created.check_interval_minutes, None);
+
+        let updated = ProductService::update(
+            &conn,
+            created.id,
+            UpdateProductParams {
+                name: None,
+                description: None,
+                notes: None,
+                check_interval_minutes: Some(Some(1440)),
+                target_price_minor_units: None,
+                target_price_currency: None,
+                is_paused: None,
+                track_price: None,
+                currency: None,
+                currency_locked: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.check_interval_minutes, Some(1440));
+
+        let cleared = ProductService::update(
+            &conn,
+            created.id,
+            UpdateProductParams {
+                name: None,
+                description: None,
+                notes: None,
+                check_interval_minutes: Some(None),
+                target_price_minor_units: None,
+                target_price_currency: None,
+                is_paused: None,
+                track_price: None,
+                currency: None,
+                currency_locked: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(cleared.check_interval_minutes, None);
+    }
+
+    #[tokio::test]
+    async fn test_update_validates_check_interval() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(&conn, params("Test")).await.unwrap();
+
+        let result = ProductService::update(
+            &conn,
+            created.id,
+            UpdateProductParams {
+                name: None,
+                description: None,
+                notes: None,
+                check_interval_minutes: Some(Some(20000)),
+                target_price_minor_units: None,
+                target_price_currency: None,
+                is_paused: None,
+                track_price: None,
+                currency: None,
+                currency_locked: None,
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_product_with_target_price() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(
+            &conn,
+            CreateProductParams {
+                target_price_minor_units: Some(4999),
+                target_price_currency: Some("USD".to_string()),
+                ..params("Deal")
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(created.target_price_minor_units, Some(4999));
+        assert_eq!(created.target_price_currency, Some("USD".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_create_validates_target_price() {
+        let conn = setup_products_db().await;
+        let result = ProductService::create(
+            &conn,
+            CreateProductParams {
+                target_price_minor_units: Some(0),
+                ..params("Invalid")
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let result = ProductService::create(
+            &conn,
+            CreateProductParams {
+                target_price_minor_units: Some(4999),
+                target_price_currency: Some("dollars".to_string()),
+                ..params("Invalid")
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_sets_and_clears_target_price() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(&conn, params("Test")).await.unwrap();
+
+        let updated = ProductService::update(
+            &conn,
+            created.id,
+            UpdateProductParams {
+                name: None,
+                description: None,
+                notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: Some(Some(2500)),
+                target_price_currency: Some(Some("EUR".to_string())),
+                is_paused: None,
+                track_price: None,
+                currency: None,
+                currency_locked: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.target_price_minor_units, Some(2500));
+        assert_eq!(updated.target_price_currency, Some("EUR".to_string()));
+
+        let cleared = ProductService::update(
+            &conn,
+            created.id,
+            UpdateProductParams {
+                name: None,
+                description: None,
+                notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: Some(None),
+                target_price_currency: Some(None),
+                is_paused: None,
+                track_price: None,
+                currency: None,
+                currency_locked: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(cleared.target_price_minor_units, None);
+        assert_eq!(cleared.target_price_currency, None);
+    }
+
+    #[tokio::test]
+    async fn test_update_pause_and_resume() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(&conn, params("Test")).await.unwrap();
+        assert!(!created.is_paused);
+
+        let pause = |is_paused| UpdateProductParams {
+            name: None,
+            description: None,
+            notes: None,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: Some(is_paused),
+            track_price: None,
+            currency: None,
+            currency_locked: None,
+        };
+
+        let paused = ProductService::update(&conn, created.id, pause(true))
+            .await
+            .unwrap();
+        assert!(paused.is_paused);
+
+        let resumed = ProductService::update(&conn, created.id, pause(false))
+            .await
+            .unwrap();
+        assert!(!resumed.is_paused);
+    }
+
+    #[tokio::test]
+    async fn test_update_currency_locks_it() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(&conn, params("Test")).await.unwrap();
+        assert!(!created.currency_locked);
+
+        let currency = |currency: Option<&str>, currency_locked| UpdateProductParams {
+            name: None,
+            description: None,
+            notes: None,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: None,
+            track_price: None,
+            currency: Some(currency.map(str::to_string)),
+            currency_locked,
+        };
+
+        let locked = ProductService::update(&conn, created.id, currency(Some("GBP"), None))
+            .await
+            .unwrap();
+        assert_eq!(locked.currency.as_deref(), Some("GBP"));
+        assert!(locked.currency_locked);
+
+        let unlocked =
+            ProductService::update(&conn, created.id, currency(Some("EUR"), Some(false)))
+                .await
+                .unwrap();
+        assert_eq!(unlocked.currency.as_deref(), Some("EUR"));
+        assert!(!unlocked.currency_locked);
+
+        let relocked = ProductService::update(&conn, created.id, currency(Some("EUR"), None))
+            .await
+            .unwrap();
+        let cleared = ProductService::update(&conn, created.id, currency(None, None))
+            .await
+            .unwrap();
+        assert!(relocked.currency_locked);
+        assert_eq!(cleared.currency, None);
+        assert!(!cleared.currency_locked);
+
+        let invalid = ProductService::update(&conn, created.id, currency(Some("gbp"), None)).await;
+        assert!(matches!(invalid, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_snooze_and_resume_notifications() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(&conn, params("Test")).await.unwrap();
+        let until = Utc::now() + chrono::Duration::days(3);
+
+        let snoozed = ProductService::snooze_notifications(&conn, created.id, Some(until))
+            .await
+            .unwrap();
+        assert_eq!(snoozed.notifications_snoozed_until, Some(until));
+        assert!(snoozed.is_notifications_snoozed(Utc::now()));
+
+        let resumed = ProductService::snooze_notifications(&conn, created.id, None)
+            .await
+            .unwrap();
+        assert_eq!(resumed.notifications_snoozed_until, None);
+        assert!(!resumed.is_notifications_snoozed(Utc::now()));
+    }
+
+    #[tokio::test]
+    async fn test_archived_products_hidden_by_default() {
+        let conn = setup_products_db().await;
+        let kept = ProductService::create(&conn, params("Kept")).await.unwrap();
+        let archived = ProductService::create(&conn, params("Archived"))
+            .await
+            .unwrap();
+
+        let result = ProductService::archive(&conn, archived.id).await.unwrap();
+        assert!(result.is_archived);
+
+        let visible = ProductService::get_all(&conn, false).await.unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].id, kept.id);
+
+        let all = ProductService::get_all(&conn, true).await.unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unarchive_restores_product() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(&conn, params("Test")).await.unwrap();
+        ProductService::archive(&conn, created.id).await.unwrap();
+
+        let restored = ProductService::unarchive(&conn, created.id).await.unwrap();
+
+        assert!(!restored.is_archived);
+        let visible = ProductService::get_all(&conn, false).await.unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].id, created.id);
+    }
+
+    #[tokio::test]
+    async fn test_archive_not_found() {
+        let conn = setup_products_db().await;
+
+        let result = ProductService::archive(&conn, Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_snooze_rejects_past_time() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(&conn, params("Test")).await.unwrap();
+
+        let result = ProductService::snooze_notifications(
+            &conn,
+            created.id,
+            Some(Utc::now() - chrono::Duration::minutes(1)),
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_snooze_not_found() {
+        let conn = setup_products_db().await;
+        let result = ProductService::snooze_notifications(
+            &conn,
+            Uuid::new_v4(),
+            Some(Utc::now() + chrono::Duration::days(1)),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
     #[tokio::test]
     async fn test_delete_not_found() {
         let conn = setup_products_db().await;
@@ -392,6 +1072,101 @@ mod integration_tests {
         assert!(matches!(find_result, Err(AppError::NotFound(_))));
     }
 
+    #[tokio::test]
+    async fn test_merge_into_itself_rejected() {
+        let conn = setup_products_db().await;
+        let created = ProductService::create(&conn, params("Duplicate"))
+            .await
+            .unwrap();
+
+        let result = ProductService::merge(&conn, created.id, created.id).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert!(ProductService::get_by_id(&conn, created.id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_merge_deletes_merged_product() {
+        let conn = crate::test_utils::setup_availability_db_with_tags().await;
+        let keep = ProductService::create(&conn, params("Keep")).await.unwrap();
+        let merge = ProductService::create(&conn, params("Merge"))
+            .await
+            .unwrap();
+
+        let kept = ProductService::merge(&conn, keep.id, merge.id)
+            .await
+            .unwrap();
+
+        assert_eq!(kept.id, keep.id);
+        let find_result = ProductService::get_by_id(&conn, merge.id).await;
+        assert!(matches!(find_result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_clone_appends_copy_to_name() {
+        let conn = crate::test_utils::setup_availability_db_with_tags().await;
+        let source = ProductService::create(&conn, params("Widget"))
+            .await
+            .unwrap();
+
+        let clone = ProductService::clone(&conn, source.id, None).await.unwrap();
+
+        assert_ne!(clone.id, source.id);
+        assert_eq!(clone.name, "Widget (copy)");
+        assert_eq!(
+            ProductService::get_all(&conn, false).await.unwrap().len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clone_with_new_url() {
+        let conn = crate::test_utils::setup_availability_db_with_tags().await;
+        let source = ProductService::create(&conn, params("Widget"))
+            .await
+            .unwrap();
+
+        let clone = ProductService::clone(
+            &conn,
+            source.id,
+            Some(" https://shop.com/widget-2 ".to_string()),
+        )
+        .await
+        .unwrap();
+
+        let links =
+            crate::services::ProductRetailerService::get_retailers_for_product(&conn, clone.id)
+                .await
+                .unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://shop.com/widget-2");
+    }
+
+    #[tokio::test]
+    async fn test_clone_rejects_invalid_url() {
+        let conn = crate::test_utils::setup_availability_db_with_tags().await;
+        let source = ProductService::create(&conn, params("Widget"))
+            .await
+            .unwrap();
+
+        let result = ProductService::clone(&conn, source.id, Some("not a url".to_string())).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert_eq!(
+            ProductService::get_all(&conn, false).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clone_missing_product() {
+        let conn = crate::test_utils::setup_availability_db_with_tags().await;
+
+        let result = ProductService::clone(&conn, Uuid::new_v4(), None).await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
     #[tokio::test]
     async fn test_reorder_products() {
         let conn = setup_products_db().await;
@@ -414,7 +1189,7 @@ mod integration_tests {
         .await
         .unwrap();
 
-        let products = ProductService::get_all(&conn).await.unwrap();
+        let products = ProductService::get_all(&conn, false).await.unwrap();
         assert_eq!(products[0].name, "Gamma");
         assert_eq!(products[1].name, "Beta");
         assert_eq!(products[2].name, "Alpha");
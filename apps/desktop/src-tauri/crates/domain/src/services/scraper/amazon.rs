@@ -0,0 +1,327 @@
+//! Amazon adapter for parsing product data from the product page DOM.
+//!
+//! Amazon does not publish Schema.org product offers, so availability is read
+//! from the `#availability` block and price from the `.a-offscreen` text inside
+//! the core price widget.
+
+use scraper::Html;
+use url::Url;
+
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
+use product_stalker_core::AppError;
+
+use super::dom::select_first_text;
+use super::price_parser::{
+    infer_currency_from_domain, normalize_decimal_comma, parse_price_to_minor_units, PriceInfo,
+};
+use super::ScrapingResult;
+
+/// Amazon storefront domains
+const AMAZON_DOMAINS: &[&str] = &[
+    "amazon.com",
+    "amazon.co.uk",
+    "amazon.com.au",
+    "amazon.ca",
+    "amazon.de",
+    "amazon.fr",
+    "amazon.it",
+    "amazon.es",
+    "amazon.nl",
+    "amazon.se",
+    "amazon.pl",
+    "amazon.com.be",
+    "amazon.ie",
+    "amazon.co.jp",
+    "amazon.in",
+    "amazon.sg",
+    "amazon.ae",
+    "amazon.sa",
+    "amazon.com.mx",
+    "amazon.com.br",
+    "amazon.com.tr",
+];
+
+/// Selectors for the availability message, in priority order.
+const AVAILABILITY_SELECTORS: &[&str] = &["#availability", "#outOfStock"];
+
+/// Selectors for the displayed price, in priority order.
+const PRICE_SELECTORS: &[&str] = &[
+    "#corePrice_feature_div .a-price .a-offscreen",
+    "#corePriceDisplay_desktop_feature_div .a-price .a-offscreen",
+    "#priceblock_ourprice",
+    "#priceblock_dealprice",
+    ".a-price .a-offscreen",
+];
+
+/// Availability text fragments that mean the item cannot be bought.
+const OUT_OF_STOCK_PHRASES: &[&str] = &["currently unavailable", "out of stock"];
+
+/// Availability text fragments that mean the item can be ordered but ships later.
+const BACK_ORDER_PHRASES: &[&str] = &["temporarily out of stock"];
+
+/// Availability text fragments that mean the item can be pre-ordered.
+const PRE_ORDER_PHRASES: &[&str] = &["pre-order", "preorder"];
+
+/// Availability text fragments that mean the item ships now.
+const IN_STOCK_PHRASES: &[&str] = &[
+    "in stock",
+    "usually ships within",
+    "usually dispatched within",
+];
+
+/// Unambiguous currency symbols shown in Amazon price text.
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[("£", "GBP"), ("€", "EUR"), ("¥", "JPY"), ("₹", "INR")];
+
+/// Check if the URL is for an Amazon storefront (amazon.com, amazon.co.uk, etc.)
+pub fn is_amazon_url(url: &str) -> bool {
+    Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .is_some_and(|host| {
+            AMAZON_DOMAINS
+                .iter()
+                .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+        })
+}
+
+/// Parse product availability and price from an Amazon product page.
+///
+/// Currency is taken from the price symbol when unambiguous (e.g. £, €).
+/// Use [`with_domain_currency`] to fill in the currency for `$` storefronts.
+pub fn parse_amazon_data(html: &str) -> Result<ScrapingResult, AppError> {
+    let document = Html::parse_document(html);
+
+    let availability_text = select_first_text(&document, AVAILABILITY_SELECTORS)?;
+    let raw_price = select_first_text(&document, PRICE_SELECTORS)?;
+
+    if availability_text.is_none() && raw_price.is_none() {
+        return Err(AppError::External(
+            "No availability or price found in Amazon page".to_string(),
+        ));
+    }
+
+    let status = availability_text
+        .as_deref()
+        .map(map_availability_status)
+        .unwrap_or_default();
+
+    let price_currency = raw_price.as_deref().and_then(currency_from_symbol);
+    let price = build_price_info(raw_price, price_currency);
+
+    Ok(ScrapingResult {
+        status,
+        raw_availability: availability_text,
+        price,
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
+    })
+}
+
+/// Fill in a missing price currency from the storefront domain (e.g. amazon.com.au → AUD).
+pub fn with_domain_currency(mut result: ScrapingResult, url: &str) -> ScrapingResult {
+    if result.price.price_currency.is_some() {
+        return result;
+    }
+    if let Some(currency) = infer_currency_from_domain(url) {
+        let raw_price = result.price.raw_price.take();
+        result.price = build_price_info(raw_price, Some(currency));
+    }
+    result
+}
+
+/// Map Amazon availability text to an AvailabilityStatus
+fn map_availability_status(text: &str) -> AvailabilityStatus {
+    let normalized = text.to_lowercase();
+    let contains_any = |phrases: &[&str]| phrases.iter().any(|p| normalized.contains(p));
+
+    if contains_any(BACK_ORDER_PHRASES) {
+        return AvailabilityStatus::BackOrder;
+    }
+
+    if contains_any(OUT_OF_STOCK_PHRASES) {
+        return AvailabilityStatus::OutOfStock;
+    }
+
+    if is_low_stock_message(&normalized) {
+        return AvailabilityStatus::LimitedAvailability;
+    }
+
+    if contains_any(PRE_ORDER_PHRASES) {
+        return AvailabilityStatus::PreOrder;
+    }
+
+    if contains_any(IN_STOCK_PHRASES) {
+        return AvailabilityStatus::InStock;
+    }
+
+    AvailabilityStatus::Unknown
+}
+
+/// Detect "Only N left in stock" style messages
+fn is_low_stock_message(normalized: &str) -> bool {
+    let Some(rest) = normalized.split("only ").nth(1) else {
+        return false;
+    };
+    rest.starts_with(|c: char| c.is_ascii_digit()) && rest.contains(" left")
+}
+
+/// Detect the currency from an unambiguous symbol in the price text
+fn currency_from_symbol(raw_price: &str) -> Option<String> {
+    CURRENCY_SYMBOLS
+        .iter()
+        .find(|(symbol, _)| raw_price.contains(symbol))
+        .map(|(_, code)| (*code).to_string())
+}
+
+/// Convert price text (e.g. "$49.99", "49,99 €") into a PriceInfo.
+fn build_price_info(raw_price: Option<String>, price_currency: Option<String>) -> PriceInfo {
+    let price_minor_units = raw_price.as_deref().and_then(|p| {
+        parse_price_to_minor_units(&normalize_decimal_comma(p), price_currency.as_deref())
+    });
+
+    PriceInfo {
+        price_minor_units,
+        price_currency,
+        raw_price,
+        compare_at_minor_units: None,
+        price_valid_until: None,
+        price_range: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amazon_html(availability: &str, price: &str) -> String {
+        format!(
+            r#"<html><body>
+                <div id="corePrice_feature_div">
+                    <span class="a-price"><span class="a-offscreen">{}</span></span>
+                </div>
+                <div id="availability">
+                    <span class="a-size-medium a-color-success">
+                        {}
+                    </span>
+                </div>
+            </body></html>"#,
+            price, availability
+        )
+    }
+
+    #[test]
+    fn test_is_amazon_url() {
+        assert!(is_amazon_url("https://www.amazon.com/dp/B0123"));
+        assert!(is_amazon_url("https://amazon.co.uk/dp/B0123"));
+        assert!(is_amazon_url("https://www.amazon.com.au/gp/product/B0123"));
+        assert!(is_amazon_url("https://smile.amazon.de/dp/B0123"));
+        assert!(!is_amazon_url("https://notamazon.com/dp/B0123"));
+        assert!(!is_amazon_url("https://amazon.example.com/dp/B0123"));
+        assert!(!is_amazon_url("https://www.amazon.evil.net/dp/B0123"));
+        assert!(!is_amazon_url("https://example.com/amazon"));
+        assert!(!is_amazon_url("not-a-url"));
+    }
+
+    #[test]
+    fn test_parse_in_stock() {
+        let html = amazon_html("In Stock", "$49.99");
+
+        let result = parse_amazon_data(&html).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.raw_availability, Some("In Stock".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(4999));
+        assert_eq!(result.price.raw_price, Some("$49.99".to_string()));
+        assert!(result.price.price_currency.is_none());
+    }
+
+    #[test]
+    fn test_parse_currently_unavailable() {
+        let html = r#"<html><body>
+            <div id="availability"><span>Currently unavailable.</span>
+            <span>We don't know when or if this item will be back in stock.</span></div>
+        </body></html>"#;
+
+        let result = parse_amazon_data(html).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert!(result.price.price_minor_units.is_none());
+    }
+
+    #[test]
+    fn test_parse_only_n_left() {
+        let html = amazon_html("Only 3 left in stock - order soon.", "£12.50");
+
+        let result = parse_amazon_data(&html).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::LimitedAvailability);
+        assert_eq!(result.price.price_minor_units, Some(1250));
+        assert_eq!(result.price.price_currency, Some("GBP".to_string()));
+    }
+
+    #[test]
+    fn test_parse_temporarily_out_of_stock_is_back_order() {
+        let html = amazon_html(
+            "Temporarily out of stock. Order now and we'll deliver when available.",
+            "$20.00",
+        );
+
+        let result = parse_amazon_data(&html).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::BackOrder);
+    }
+
+    #[test]
+    fn test_parse_euro_price() {
+        let html = amazon_html("Auf Lager", "1.234,56 €");
+
+        let result = parse_amazon_data(&html).unwrap();
+        assert_eq!(result.price.price_minor_units, Some(123456));
+        assert_eq!(result.price.price_currency, Some("EUR".to_string()));
+    }
+
+    #[test]
+    fn test_parse_no_data_fails() {
+        let html = "<html><body><h1>Robot check</h1></body></html>";
+        assert!(parse_amazon_data(html).is_err());
+    }
+
+    #[test]
+    fn test_with_domain_currency_fills_missing_currency() {
+        let result = parse_amazon_data(&amazon_html("In Stock", "$59.00")).unwrap();
+
+        let result = with_domain_currency(result, "https://www.amazon.com.au/dp/B0123");
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(5900));
+    }
+
+    #[test]
+    fn test_with_domain_currency_keeps_symbol_currency() {
+        let result = parse_amazon_data(&amazon_html("In Stock", "£10.00")).unwrap();
+
+        let result = with_domain_currency(result, "https://www.amazon.com/dp/B0123");
+        assert_eq!(result.price.price_currency, Some("GBP".to_string()));
+    }
+
+    #[test]
+    fn test_map_availability_status() {
+        assert_eq!(
+            map_availability_status("in stock"),
+            AvailabilityStatus::InStock
+        );
+        assert_eq!(
+            map_availability_status("Usually ships within 2 to 3 days."),
+            AvailabilityStatus::InStock
+        );
+        assert_eq!(
+            map_availability_status("Available to pre-order."),
+            AvailabilityStatus::PreOrder
+        );
+        assert_eq!(
+            map_availability_status("Only 1 left in stock."),
+            AvailabilityStatus::LimitedAvailability
+        );
+        assert_eq!(
+            map_availability_status("Only available from third-party sellers."),
+            AvailabilityStatus::Unknown
+        );
+    }
+}
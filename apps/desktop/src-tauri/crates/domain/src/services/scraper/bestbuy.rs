@@ -0,0 +1,271 @@
+//! Best Buy adapter for parsing the embedded initial state JSON.
+//!
+//! Best Buy product pages carry no usable Schema.org offers. Instead, the
+//! server-rendered page assigns a JSON blob to `window.__INITIAL_STATE__` that
+//! holds the SKU's add-to-cart button state and current price.
+
+use serde_json::Value;
+use url::Url;
+
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
+use product_stalker_core::AppError;
+
+use super::price_parser::{parse_price_to_minor_units, scalar_to_string, PriceInfo};
+use super::ScrapingResult;
+
+/// Best Buy US prices are always in US dollars.
+const CURRENCY: &str = "USD";
+
+/// Script assignment that precedes the embedded state JSON.
+const INITIAL_STATE_MARKER: &str = "window.__INITIAL_STATE__";
+
+/// Check if the URL is for Best Buy US (bestbuy.com)
+pub fn is_bestbuy_url(url: &str) -> bool {
+    Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .is_some_and(|host| host == "bestbuy.com" || host.ends_with(".bestbuy.com"))
+}
+
+/// Parse product availability and price from a Best Buy product page.
+///
+/// The SKU entry is found anywhere in the initial state by its `skuId`,
+/// preferring the `skuId` query parameter of the URL when present:
+/// ```json
+/// {
+///   "skuId": "6505727",
+///   "buttonState": "ADD_TO_CART",
+///   "currentPrice": 499.99
+/// }
+/// ```
+///
+/// `buttonState` may also be nested as `{"buttonState": {"buttonState": "SOLD_OUT"}}`.
+pub fn parse_bestbuy_data(html: &str, url: &str) -> Result<ScrapingResult, AppError> {
+    let state = extract_initial_state(html)?;
+    let sku_id = sku_id_from_url(url);
+
+    let sku = find_sku(&state, sku_id.as_deref())
+        .ok_or_else(|| AppError::External("No SKU data found in Best Buy page".to_string()))?;
+
+    let button_state = button_state(sku);
+    let status = button_state
+        .as_deref()
+        .map(map_button_state)
+        .unwrap_or_default();
+
+    let raw_price = sku.get("currentPrice").and_then(scalar_to_string);
+    let price_minor_units = raw_price
+        .as_deref()
+        .and_then(|p| parse_price_to_minor_units(p, Some(CURRENCY)));
+
+    Ok(ScrapingResult {
+        status,
+        raw_availability: button_state,
+        price: PriceInfo {
+            price_minor_units,
+            price_currency: price_minor_units.map(|_| CURRENCY.to_string()),
+            raw_price,
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            price_range: None,
+        },
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
+    })
+}
+
+/// Extract and parse the JSON assigned to `window.__INITIAL_STATE__`.
+///
+/// Only the first JSON value after the `=` is parsed, so a trailing `;` or
+/// further script statements are ignored.
+fn extract_initial_state(html: &str) -> Result<Value, AppError> {
+    let not_found =
+        || AppError::External("No __INITIAL_STATE__ found in Best Buy page".to_string());
+
+    let marker = html.find(INITIAL_STATE_MARKER).ok_or_else(not_found)?;
+    let after_marker = &html[marker + INITIAL_STATE_MARKER.len()..];
+    let json_start = after_marker
+        .trim_start()
+        .strip_prefix('=')
+        .ok_or_else(not_found)?;
+
+    serde_json::Deserializer::from_str(json_start)
+        .into_iter::<Value>()
+        .next()
+        .ok_or_else(not_found)?
+        .map_err(|e| AppError::External(format!("Failed to parse __INITIAL_STATE__ JSON: {}", e)))
+}
+
+/// Read the `skuId` query parameter from a product URL
+fn sku_id_from_url(url: &str) -> Option<String> {
+    Url::parse(url).ok().and_then(|parsed| {
+        parsed
+            .query_pairs()
+            .find(|(key, _)| key == "skuId")
+            .map(|(_, value)| value.to_string())
+    })
+}
+
+/// Find the SKU object carrying a button state, matching `sku_id` when given.
+///
+/// Falls back to the first SKU object found if none matches the requested ID.
+fn find_sku<'a>(state: &'a Value, sku_id: Option<&str>) -> Option<&'a Value> {
+    let mut candidates = Vec::new();
+    collect_skus(state, &mut candidates);
+
+    sku_id
+        .and_then(|id| {
+            candidates
+                .iter()
+                .find(|sku| sku.get("skuId").and_then(scalar_to_string).as_deref() == Some(id))
+        })
+        .or_else(|| candidates.first())
+        .copied()
+}
+
+/// Depth-first collection of objects that have both `skuId` and `buttonState`
+fn collect_skus<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(map) => {
+            if map.contains_key("skuId") && map.contains_key("buttonState") {
+                out.push(value);
+            }
+            map.values().for_each(|v| collect_skus(v, out));
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_skus(v, out)),
+        _ => {}
+    }
+}
+
+/// Read the button state, which may be a string or a nested `{buttonState}` object
+fn button_state(sku: &Value) -> Option<String> {
+    let value = sku.get("buttonState")?;
+    value
+        .as_str()
+        .or_else(|| value.get("buttonState").and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+/// Map a Best Buy button state to an AvailabilityStatus
+fn map_button_state(state: &str) -> AvailabilityStatus {
+    match state.to_ascii_uppercase().as_str() {
+        "ADD_TO_CART" => AvailabilityStatus::InStock,
+        "PRE_ORDER" => AvailabilityStatus::PreOrder,
+        "SOLD_OUT" | "COMING_SOON" | "CHECK_STORES" | "UNAVAILABLE" => {
+            AvailabilityStatus::OutOfStock
+        }
+        _ => AvailabilityStatus::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bestbuy_html(state: &str) -> String {
+        format!(
+            r#"<html><head><script>window.__INITIAL_STATE__ = {};
+            window.__CONFIG__ = {{}};</script></head><body></body></html>"#,
+            state
+        )
+    }
+
+    #[test]
+    fn test_is_bestbuy_url() {
+        assert!(is_bestbuy_url(
+            "https://www.bestbuy.com/site/some-tv/6505727.p?skuId=6505727"
+        ));
+        assert!(is_bestbuy_url("https://bestbuy.com/site/item/123.p"));
+        assert!(!is_bestbuy_url("https://www.notbestbuy.com/item"));
+        assert!(!is_bestbuy_url("https://example.com/bestbuy.com"));
+    }
+
+    #[test]
+    fn test_parse_add_to_cart_in_stock() {
+        let html = bestbuy_html(
+            r#"{"product":{"sku":{"skuId":"6505727","buttonState":"ADD_TO_CART","currentPrice":499.99}}}"#,
+        );
+
+        let result =
+            parse_bestbuy_data(&html, "https://www.bestbuy.com/site/tv/6505727.p").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.raw_availability, Some("ADD_TO_CART".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(49999));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+        assert_eq!(result.price.raw_price, Some("499.99".to_string()));
+    }
+
+    #[test]
+    fn test_parse_sold_out() {
+        let html = bestbuy_html(
+            r#"{"sku":{"skuId":"6505727","buttonState":"SOLD_OUT","currentPrice":"1,299.00"}}"#,
+        );
+
+        let result =
+            parse_bestbuy_data(&html, "https://www.bestbuy.com/site/tv/6505727.p").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(129900));
+    }
+
+    #[test]
+    fn test_parse_nested_button_state() {
+        let html = bestbuy_html(
+            r#"{"buttonStates":[{"skuId":6505727,"buttonState":{"buttonState":"PRE_ORDER"},"currentPrice":59.99}]}"#,
+        );
+
+        let result =
+            parse_bestbuy_data(&html, "https://www.bestbuy.com/site/game/6505727.p").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::PreOrder);
+        assert_eq!(result.price.price_minor_units, Some(5999));
+    }
+
+    #[test]
+    fn test_parse_matches_sku_from_url() {
+        let html = bestbuy_html(
+            r#"{"skus":[
+                {"skuId":"111","buttonState":"ADD_TO_CART","currentPrice":10},
+                {"skuId":"222","buttonState":"SOLD_OUT","currentPrice":20}
+            ]}"#,
+        );
+
+        let result =
+            parse_bestbuy_data(&html, "https://www.bestbuy.com/site/item/222.p?skuId=222").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(2000));
+    }
+
+    #[test]
+    fn test_parse_unmatched_sku_falls_back_to_first() {
+        let html = bestbuy_html(
+            r#"{"sku":{"skuId":"111","buttonState":"ADD_TO_CART","currentPrice":10}}"#,
+        );
+
+        let result =
+            parse_bestbuy_data(&html, "https://www.bestbuy.com/site/item.p?skuId=999").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+    }
+
+    #[test]
+    fn test_parse_unknown_button_state() {
+        let html = bestbuy_html(r#"{"sku":{"skuId":"1","buttonState":"SOMETHING_NEW"}}"#);
+
+        let result = parse_bestbuy_data(&html, "https://www.bestbuy.com/site/item.p").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::Unknown);
+        assert_eq!(result.price.price_minor_units, None);
+        assert_eq!(result.price.price_currency, None);
+    }
+
+    #[test]
+    fn test_parse_missing_initial_state_errors() {
+        let html = "<html><body><p>No state here</p></body></html>";
+        assert!(parse_bestbuy_data(html, "https://www.bestbuy.com/site/item.p").is_err());
+    }
+
+    #[test]
+    fn test_parse_state_without_sku_errors() {
+        let html = bestbuy_html(r#"{"page":{"title":"Best Buy"}}"#);
+        assert!(parse_bestbuy_data(&html, "https://www.bestbuy.com/site/item.p").is_err());
+    }
+}
@@ -0,0 +1,411 @@
+//! Add-to-cart button heuristics for inferring availability from the DOM.
+//!
+//! Used as a fallback when structured data exposes a price but no
+//! availability. Stores almost always render a purchase button, and its state
+//! (enabled, `disabled`, `aria-disabled`, or relabelled to "Sold out" /
+//! "Notify me") reliably reflects whether the product can be bought. Pages
+//! without a purchase button often show a "notify me when back in stock" email
+//! form instead, which is an equally strong out-of-stock signal.
+
+use scraper::{ElementRef, Html, Selector};
+
+use crate::entities::availability_check::AvailabilityStatus;
+
+/// Elements that can act as a purchase button.
+const BUTTON_SELECTOR: &str = r#"button, input[type="submit"], input[type="button"]"#;
+
+/// Label fragments identifying an add-to-cart button.
+const ADD_TO_CART_LABELS: &[&str] = &[
+    "add to cart",
+    "add to bag",
+    "add to basket",
+    "add to trolley",
+    "buy now",
+    "in den warenkorb",
+    "au panier",
+    "カートに入れる",
+    "カートに追加",
+];
+
+/// Fragments in `class`, `id` or `name` attributes identifying an add-to-cart button.
+const ADD_TO_CART_ATTRIBUTE_HINTS: &[&str] = &["add-to-cart", "add_to_cart", "addtocart"];
+
+/// Label fragments identifying a button that replaces add-to-cart when the
+/// product cannot be bought.
+const UNAVAILABLE_LABELS: &[&str] = &[
+    "sold out",
+    "out of stock",
+    "notify me",
+    "email me when available",
+    "currently unavailable",
+];
+
+/// Email inputs identifying a stock alert sign-up form.
+const EMAIL_INPUT_SELECTOR: &str = r#"input[type="email"], input[name*="email" i]"#;
+
+/// Text fragments identifying a stock alert form, as opposed to a newsletter sign-up.
+const NOTIFY_FORM_PHRASES: &[&str] = &[
+    "notify me",
+    "notify when",
+    "back in stock",
+    "in stock again",
+    "when available",
+    "becomes available",
+    "is available",
+    "restock",
+    "stock alert",
+];
+
+/// How many ancestors of an email input without a `<form>` are searched for notify text.
+const NOTIFY_FORM_MAX_DEPTH: usize = 3;
+
+/// Containers with more text than this are page sections rather than a form, so
+/// their text says nothing about the input.
+const NOTIFY_FORM_MAX_TEXT_LEN: usize = 600;
+
+/// Infer availability from the first add-to-cart style button in the document.
+///
+/// Returns `OutOfStock` when the button is disabled (`disabled` attribute or
+/// `aria-disabled="true"`) or labelled "Sold out" / "Notify me", `InStock`
+/// when an enabled add-to-cart button is found, and `None` when the page has
+/// no recognizable purchase button. Only the first match is considered since
+/// the main product's button precedes related-product carousels. Pages without
+/// a purchase button but with a visible "notify me" form are `OutOfStock`.
+pub fn detect_add_to_cart_state(html: &str) -> Option<AvailabilityStatus> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(BUTTON_SELECTOR).ok()?;
+
+    document
+        .select(&selector)
+        .find_map(classify_button)
+        .or_else(|| has_notify_me_form(&document).then_some(AvailabilityStatus::OutOfStock))
+}
+
+/// Whether the document shows a "notify me when available" email form.
+///
+/// An email input counts when its enclosing `<form>` (or, without one, one of
+/// its nearest ancestors) mentions notifying or restocking. Hidden forms are
+/// ignored since themes often render the form for every variant and toggle it
+/// with script.
+pub fn has_notify_me_form(document: &Html) -> bool {
+    let Ok(selector) = Selector::parse(EMAIL_INPUT_SELECTOR) else {
+        return false;
+    };
+
+    document
+        .select(&selector)
+        .any(|input| !is_hidden_in_tree(&input) && notify_context_mentions_stock(&input))
+}
+
+/// Check the input's own hints and its surrounding form for notify phrases.
+fn notify_context_mentions_stock(input: &ElementRef) -> bool {
+    let value = input.value();
+    let own_hints = ["placeholder", "aria-label", "name", "id"]
+        .iter()
+        .filter_map(|attr| value.attr(attr))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if mentions_notify(&own_hints) {
+        return true;
+    }
+
+    let ancestors: Vec<ElementRef> = input.ancestors().filter_map(ElementRef::wrap).collect();
+    let containers: Vec<ElementRef> = match ancestors.iter().find(|el| el.value().name() == "form")
+    {
+        Some(form) => vec![*form],
+        None => ancestors.into_iter().take(NOTIFY_FORM_MAX_DEPTH).collect(),
+    };
+
+    containers.iter().any(|container| {
+        let text = collapsed_text(container);
+        text.len() <= NOTIFY_FORM_MAX_TEXT_LEN && mentions_notify(&text)
+    })
+}
+
+fn mentions_notify(text: &str) -> bool {
+    let text = text.to_lowercase();
+    NOTIFY_FORM_PHRASES.iter().any(|p| text.contains(p))
+}
+
+/// Text content plus button values and labels, whitespace-collapsed.
+fn collapsed_text(element: &ElementRef) -> String {
+    let labels = element
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .flat_map(|el| {
+            let value = el.value();
+            [value.attr("value"), value.attr("placeholder")]
+        })
+        .flatten();
+
+    element
+        .text()
+        .chain(labels)
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// An element is hidden by the `hidden` attribute or an inline `display: none`
+/// on itself or any ancestor.
+fn is_hidden_in_tree(element: &ElementRef) -> bool {
+    std::iter::once(*element)
+        .chain(element.ancestors().filter_map(ElementRef::wrap))
+        .any(|el| {
+            let value = el.value();
+            value.attr("hidden").is_some()
+                || value.attr("style").is_some_and(|style| {
+                    style
+                        .to_lowercase()
+                        .split(';')
+                        .any(|decl| decl.replace(' ', "") == "display:none")
+                })
+        })
+}
+
+/// Classify a single button element, or `None` if it is not a purchase button.
+fn classify_button(element: ElementRef) -> Option<AvailabilityStatus> {
+    let label = button_label(&element);
+
+    if UNAVAILABLE_LABELS.iter().any(|l| label.contains(l)) {
+        return Some(AvailabilityStatus::OutOfStock);
+    }
+
+    let is_add_to_cart =
+        ADD_TO_CART_LABELS.iter().any(|l| label.contains(l)) || has_add_to_cart_attribute(&element);
+    if !is_add_to_cart {
+        return None;
+    }
+
+    if is_disabled(&element) {
+        Some(AvailabilityStatus::OutOfStock)
+    } else {
+        Some(AvailabilityStatus::InStock)
+    }
+}
+
+/// Lowercased, whitespace-collapsed label from text content, `value` and `aria-label`.
+fn button_label(element: &ElementRef) -> String {
+    let value = element.value();
+    let parts = element
+        .text()
+        .chain(value.attr("value"))
+        .chain(value.attr("aria-label"));
+
+    parts
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Check `class`, `id` and `name` for add-to-cart hints.
+fn has_add_to_cart_attribute(element: &ElementRef) -> bool {
+    let value = element.value();
+    ["class", "id", "name"]
+        .iter()
+        .filter_map(|attr| value.attr(attr))
+        .map(str::to_lowercase)
+        .any(|v| ADD_TO_CART_ATTRIBUTE_HINTS.iter().any(|h| v.contains(h)))
+}
+
+/// A button is disabled via the `disabled` attribute or `aria-disabled="true"`.
+fn is_disabled(element: &ElementRef) -> bool {
+    let value = element.value();
+    value.attr("disabled").is_some()
+        || value
+            .attr("aria-disabled")
+            .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enabled_add_to_cart_button_is_in_stock() {
+        let html = r#"<form><button type="submit" class="btn">Add to Cart</button></form>"#;
+        assert_eq!(
+            detect_add_to_cart_state(html),
+            Some(AvailabilityStatus::InStock)
+        );
+    }
+
+    #[test]
+    fn test_disabled_add_to_cart_button_is_out_of_stock() {
+        let html = r#"<button type="submit" disabled>Add to Cart</button>"#;
+        assert_eq!(
+            detect_add_to_cart_state(html),
+            Some(AvailabilityStatus::OutOfStock)
+        );
+    }
+
+    #[test]
+    fn test_aria_disabled_add_to_cart_button_is_out_of_stock() {
+        let html = r#"<button aria-disabled="true">Add to Bag</button>"#;
+        assert_eq!(
+            detect_add_to_cart_state(html),
+            Some(AvailabilityStatus::OutOfStock)
+        );
+    }
+
+    #[test]
+    fn test_aria_disabled_false_is_in_stock() {
+        let html = r#"<button aria-disabled="false">Add to Bag</button>"#;
+        assert_eq!(
+            detect_add_to_cart_state(html),
+            Some(AvailabilityStatus::InStock)
+        );
+    }
+
+    #[test]
+    fn test_sold_out_button_is_out_of_stock() {
+        let html = r#"<button class="product-form__submit">
+            <span>Sold Out</span>
+        </button>"#;
+        assert_eq!(
+            detect_add_to_cart_state(html),
+            Some(AvailabilityStatus::OutOfStock)
+        );
+    }
+
+    #[test]
+    fn test_notify_me_button_is_out_of_stock() {
+        let html = r#"<button class="btn">Notify me when available</button>"#;
+        assert_eq!(
+            detect_add_to_cart_state(html),
+            Some(AvailabilityStatus::OutOfStock)
+        );
+    }
+
+    #[test]
+    fn test_submit_input_value_is_used_as_label() {
+        let html = r#"<input type="submit" value="Add to basket">"#;
+        assert_eq!(
+            detect_add_to_cart_state(html),
+            Some(AvailabilityStatus::InStock)
+        );
+    }
+
+    #[test]
+    fn test_icon_button_identified_by_attribute() {
+        let html = r#"<button id="AddToCart-main" disabled><svg></svg></button>"#;
+        assert_eq!(
+            detect_add_to_cart_state(html),
+            Some(AvailabilityStatus::OutOfStock)
+        );
+    }
+
+    #[test]
+    fn test_first_purchase_button_wins() {
+        let html = r#"
+            <button disabled>Add to Cart</button>
+            <div class="related"><button>Add to Cart</button></div>
+        "#;
+        assert_eq!(
+            detect_add_to_cart_state(html),
+            Some(AvailabilityStatus::OutOfStock)
+        );
+    }
+
+    #[test]
+    fn test_unrelated_buttons_are_ignored() {
+        let html = r#"<button>Search</button><button disabled>Next</button>"#;
+        assert_eq!(detect_add_to_cart_state(html), None);
+    }
+
+    mod notify_form {
+        use super::*;
+
+        fn has_form(html: &str) -> bool {
+            has_notify_me_form(&Html::parse_document(html))
+        }
+
+        #[test]
+        fn test_back_in_stock_form_is_out_of_stock() {
+            let html = r#"
+                <h1>Trail Runner</h1>
+                <form action="/contact" class="back-in-stock">
+                    <p>Get notified when this item is back in stock</p>
+                    <input type="email" name="contact[email]" placeholder="Email address">
+                    <input type="submit" value="Submit">
+                </form>
+            "#;
+            assert_eq!(
+                detect_add_to_cart_state(html),
+                Some(AvailabilityStatus::OutOfStock)
+            );
+        }
+
+        #[test]
+        fn test_notify_form_without_form_element() {
+            let html = r#"
+                <div class="klaviyo-bis">
+                    <span>Email me when available</span>
+                    <div><input type="email" placeholder="you@example.com"></div>
+                </div>
+            "#;
+            assert!(has_form(html));
+        }
+
+        #[test]
+        fn test_notify_phrase_in_input_placeholder() {
+            let html = r#"<input type="email" placeholder="Notify me when it's restocked">"#;
+            assert!(has_form(html));
+        }
+
+        #[test]
+        fn test_newsletter_form_is_ignored() {
+            let html = r#"
+                <footer>
+                    <form action="/newsletter">
+                        <p>Subscribe for 10% off your first order</p>
+                        <input type="email" name="email">
+                        <button>Sign up</button>
+                    </form>
+                </footer>
+            "#;
+            assert!(!has_form(html));
+            assert_eq!(detect_add_to_cart_state(html), None);
+        }
+
+        #[test]
+        fn test_hidden_notify_form_is_ignored() {
+            let html = r#"
+                <div class="product-notify" style="display: none;">
+                    <form><p>Notify me when back in stock</p><input type="email"></form>
+                </div>
+                <div hidden><form>Restock alert <input type="email"></form></div>
+            "#;
+            assert!(!has_form(html));
+        }
+
+        #[test]
+        fn test_purchase_button_takes_precedence() {
+            let html = r#"
+                <button class="add-to-cart">Add to Cart</button>
+                <form><p>Notify me when back in stock</p><input type="email"></form>
+            "#;
+            assert_eq!(
+                detect_add_to_cart_state(html),
+                Some(AvailabilityStatus::InStock)
+            );
+        }
+
+        #[test]
+        fn test_notify_text_far_from_input_is_ignored() {
+            let filler = "Lorem ipsum dolor sit amet. ".repeat(30);
+            let html = format!(
+                r#"<section><p>Back in stock soon!</p><p>{}</p><input type="email"></section>"#,
+                filler
+            );
+            assert!(!has_form(&html));
+        }
+    }
+
+    #[test]
+    fn test_add_to_cart_text_outside_button_is_ignored() {
+        let html = r#"<p>Click add to cart to continue</p>"#;
+        assert_eq!(detect_add_to_cart_state(html), None);
+    }
+}
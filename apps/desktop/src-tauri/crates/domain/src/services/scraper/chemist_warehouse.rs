@@ -6,7 +6,7 @@
 
 use serde_json::Value;
 
-use crate::entities::availability_check::AvailabilityStatus;
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
 use product_stalker_core::AppError;
 
 use super::price_parser::{parse_price_to_minor_units, PriceInfo};
@@ -50,6 +50,10 @@ pub fn parse_chemist_warehouse_data(page_props: &Value) -> Result<ScrapingResult
         status,
         raw_availability: Some(availability_str),
         price,
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
     })
 }
 
@@ -198,6 +202,9 @@ fn extract_price_info(product: &Value) -> PriceInfo {
         price_minor_units,
         price_currency,
         raw_price,
+        compare_at_minor_units: None,
+        price_valid_until: None,
+        price_range: None,
     }
 }
 
@@ -0,0 +1,151 @@
+//! Coles adapter for parsing product data from the page's hydration JSON.
+//!
+//! Coles product pages do not publish Schema.org offers and block headless
+//! browsers, but embed the product in the `__NEXT_DATA__` payload with a boolean
+//! `availability` flag and a dollar `pricing.now` price.
+
+use serde_json::Value;
+
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
+use product_stalker_core::AppError;
+
+use super::price_parser::{parse_price_to_minor_units, scalar_to_string, PriceInfo};
+use super::ScrapingResult;
+
+/// Coles only sells in Australia
+const CURRENCY: &str = "AUD";
+
+/// Boolean stock flags, in priority order
+const AVAILABILITY_KEYS: &[&str] = &["availability", "IsAvailable", "IsInStock"];
+
+/// Check if the URL is for Coles
+pub fn is_coles_url(url: &str) -> bool {
+    url.contains("coles.com.au")
+}
+
+/// Parse product availability from Coles hydration data.
+///
+/// The expected structure within pageProps is:
+/// ```json
+/// {
+///   "product": {
+///     "id": 123456,
+///     "name": "Product Name",
+///     "availability": true,
+///     "pricing": { "now": 4.5, "was": 5.0 }
+///   }
+/// }
+/// ```
+pub fn parse_coles_data(page_props: &Value) -> Result<ScrapingResult, AppError> {
+    let product = page_props.get("product").ok_or_else(|| {
+        AppError::External("No product data found in Coles page props".to_string())
+    })?;
+
+    let is_available = AVAILABILITY_KEYS
+        .iter()
+        .find_map(|key| product.get(*key).and_then(Value::as_bool))
+        .ok_or_else(|| {
+            AppError::External("No availability found in Coles product data".to_string())
+        })?;
+
+    let (status, raw_availability) = if is_available {
+        (AvailabilityStatus::InStock, "in-stock")
+    } else {
+        (AvailabilityStatus::OutOfStock, "unavailable")
+    };
+
+    Ok(ScrapingResult {
+        status,
+        raw_availability: Some(raw_availability.to_string()),
+        price: extract_price_info(product),
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
+    })
+}
+
+/// Extract the current dollar price in cents
+fn extract_price_info(product: &Value) -> PriceInfo {
+    let raw_price = product
+        .get("pricing")
+        .and_then(|pricing| pricing.get("now"))
+        .or_else(|| product.get("price"))
+        .and_then(scalar_to_string);
+
+    let price_minor_units = raw_price
+        .as_deref()
+        .and_then(|p| parse_price_to_minor_units(p, Some(CURRENCY)));
+
+    PriceInfo {
+        price_minor_units,
+        price_currency: raw_price.as_ref().map(|_| CURRENCY.to_string()),
+        raw_price,
+        compare_at_minor_units: None,
+        price_valid_until: None,
+        price_range: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_coles_url() {
+        assert!(is_coles_url(
+            "https://www.coles.com.au/product/coles-full-cream-milk-2l-123456"
+        ));
+        assert!(!is_coles_url(
+            "https://www.woolworths.com.au/shop/productdetails/1"
+        ));
+        assert!(!is_coles_url("https://example.com"));
+    }
+
+    #[test]
+    fn test_parse_available() {
+        let page_props = json!({
+            "product": {"name": "Milk", "availability": true, "pricing": {"now": 3.1, "was": 3.5}}
+        });
+
+        let result = parse_coles_data(&page_props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.raw_availability, Some("in-stock".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(310));
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+    }
+
+    #[test]
+    fn test_parse_unavailable() {
+        let page_props = json!({
+            "product": {"name": "Milk", "availability": false, "pricing": {"now": 3.1}}
+        });
+
+        let result = parse_coles_data(&page_props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.raw_availability, Some("unavailable".to_string()));
+    }
+
+    #[test]
+    fn test_parse_is_in_stock_flag() {
+        let page_props = json!({
+            "product": {"name": "Milk", "IsInStock": false, "price": "2.00"}
+        });
+
+        let result = parse_coles_data(&page_props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(200));
+    }
+
+    #[test]
+    fn test_parse_no_availability() {
+        let page_props = json!({"product": {"name": "Milk", "pricing": {"now": 3.1}}});
+        assert!(parse_coles_data(&page_props).is_err());
+    }
+
+    #[test]
+    fn test_parse_no_product() {
+        assert!(parse_coles_data(&json!({"other": {}})).is_err());
+    }
+}
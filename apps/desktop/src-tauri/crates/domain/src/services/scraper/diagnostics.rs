@@ -0,0 +1,355 @@
+//! Dry-run scrape diagnostics.
+//!
+//! Runs the same fetch and extraction pipeline as a real check without storing
+//! anything, and reports which strategy matched so users can see why a URL
+//! returns Unknown.
+
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+
+use crate::entities::availability_check::AvailabilityStatus;
+use product_stalker_core::AppError;
+
+use super::{http_client, schema_org, FetchOptions, PriceRange, ScraperService, ScrapingResult};
+
+/// Extraction strategy that produced a scraping result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractionStrategy {
+    /// Shopify's products.json endpoint, tried before the page is fetched
+    ShopifyProductsJson,
+    SchemaOrg,
+    Gtm,
+    Shopify,
+    Meta,
+    SiteSpecific,
+}
+
+/// Outcome of a dry-run scrape of a URL
+#[derive(Debug, Clone, Serialize)]
+pub struct ScrapeDiagnostics {
+    pub url: String,
+    /// Strategy that produced the result, or `None` if every strategy failed
+    pub strategy: Option<ExtractionStrategy>,
+    /// Number of `<script type="application/ld+json">` blocks found in the page
+    /// (0 when products.json answered and the page wasn't fetched)
+    pub json_ld_block_count: usize,
+    /// True if the plain HTTP request hit bot protection and a fallback was used
+    pub bot_protection_detected: bool,
+    pub status: AvailabilityStatus,
+    pub raw_availability: Option<String>,
+    pub price_minor_units: Option<i64>,
+    pub price_currency: Option<String>,
+    pub raw_price: Option<String>,
+    /// Low-to-high range when the page lists an `AggregateOffer`
+    pub price_range: Option<PriceRange>,
+    /// Why extraction failed, when no strategy matched
+    pub error: Option<String>,
+    /// Normalized address the URL redirected to, offered as a replacement
+    /// (see [`super::canonical_redirect_url`])
+    pub canonical_url: Option<String>,
+}
+
+impl ScraperService {
+    /// Largest HTML returned by [`Self::fetch_raw_html`], in bytes
+    pub const MAX_RAW_HTML_BYTES: usize = 1_000_000;
+
+    /// Scrape a URL without storing a check, reporting how the result was obtained.
+    ///
+    /// Fetch failures (invalid URL, HTTP errors, unresolved bot protection) are
+    /// returned as errors; extraction failures are reported in the diagnostics.
+    pub async fn diagnose(
+        url: &str,
+        conn: &DatabaseConnection,
+        options: &FetchOptions,
+    ) -> Result<ScrapeDiagnostics, AppError> {
+        Self::validate_url_scheme(url)?;
+
+        if let Some(result) = Self::try_shopify_products_json(url, conn, options).await? {
+            let mut diagnostics = ScrapeDiagnostics::new(url, 0, false);
+            diagnostics.record(result, ExtractionStrategy::ShopifyProductsJson);
+            return Ok(diagnostics);
+        }
+        let page = http_client::fetch_html_with_fallback(url, conn, options).await?;
+
+        let mut diagnostics =
+            Self::diagnose_html(&page.html, url, page.bot_protection_detected, conn, options).await;
+        diagnostics.canonical_url = page
+            .final_url
+            .as_deref()
+            .and_then(|final_url| http_client::canonical_redirect_url(url, final_url));
+        Ok(diagnostics)
+    }
+
+    /// Fetch a URL's HTML as a check would see it, for debugging mis-reported products.
+    ///
+    /// Uses the same HTTP, headless and manual verification fallbacks as a check.
+    /// HTML beyond [`Self::MAX_RAW_HTML_BYTES`] is cut off.
+    pub async fn fetch_raw_html(
+        url: &str,
+        conn: &DatabaseConnection,
+        options: &FetchOptions,
+    ) -> Result<String, AppError> {
+        Self::validate_url_scheme(url)?;
+
+        let page = http_client::fetch_html_with_fallback(url, conn, options).await?;
+        Ok(truncate_html(page.html, Self::MAX_RAW_HTML_BYTES))
+    }
+
+    /// Build diagnostics for already-fetched HTML
+    async fn diagnose_html(
+        html: &str,
+        url: &str,
+        bot_protection_detected: bool,
+        conn: &DatabaseConnection,
+        options: &FetchOptions,
+    ) -> ScrapeDiagnostics {
+        let json_ld_block_count =
+            schema_org::extract_json_ld_blocks(html, options.max_json_ld_block_bytes)
+                .map(|blocks| blocks.len())
+                .unwrap_or(0);
+
+        let mut diagnostics =
+            ScrapeDiagnostics::new(url, json_ld_block_count, bot_protection_detected);
+
+        match Self::extract_with_strategy(html, url, conn, options, None).await {
+            Ok((result, strategy)) => diagnostics.record(result, strategy),
+            Err(e) => diagnostics.error = Some(e.to_string()),
+        }
+
+        diagnostics
+    }
+}
+
+impl ScrapeDiagnostics {
+    /// Diagnostics with no strategy matched yet
+    fn new(url: &str, json_ld_block_count: usize, bot_protection_detected: bool) -> Self {
+        Self {
+            url: url.to_string(),
+            strategy: None,
+            json_ld_block_count,
+            bot_protection_detected,
+            status: AvailabilityStatus::Unknown,
+            raw_availability: None,
+            price_minor_units: None,
+            price_currency: None,
+            raw_price: None,
+            price_range: None,
+            error: None,
+            canonical_url: None,
+        }
+    }
+
+    /// Record the result `strategy` produced
+    fn record(&mut self, result: ScrapingResult, strategy: ExtractionStrategy) {
+        self.strategy = Some(strategy);
+        self.status = result.status;
+        self.raw_availability = result.raw_availability;
+        self.price_minor_units = result.price.price_minor_units;
+        self.price_currency = result.price.price_currency;
+        self.raw_price = result.price.raw_price;
+        self.price_range = result.price.price_range;
+    }
+}
+
+/// Cut `html` to at most `max_bytes`, backing off to a character boundary
+fn truncate_html(mut html: String, max_bytes: usize) -> String {
+    if html.len() > max_bytes {
+        let mut end = max_bytes;
+        while !html.is_char_boundary(end) {
+            end -= 1;
+        }
+        html.truncate(end);
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_html::{
+        html_with_datalayer_push, html_with_next_data, html_with_product_offer,
+    };
+    use super::*;
+    use crate::services::scraper::{
+        FetchTimeouts, ProductGroupStrategy, DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+    };
+    use crate::test_utils::{setup_fetch_db, MockResponse, MockServer};
+
+    /// Diagnose already-fetched HTML with [`test_options`]
+    async fn diagnose_html(
+        html: &str,
+        url: &str,
+        bot_protection_detected: bool,
+    ) -> ScrapeDiagnostics {
+        let conn = setup_fetch_db().await;
+        ScraperService::diagnose_html(html, url, bot_protection_detected, &conn, &test_options())
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_reports_schema_org_strategy() {
+        let html = html_with_product_offer("http://schema.org/InStock", Some("19.99"), Some("USD"));
+
+        let diagnostics = diagnose_html(&html, "https://example.com/item", false).await;
+        assert_eq!(diagnostics.strategy, Some(ExtractionStrategy::SchemaOrg));
+        assert_eq!(diagnostics.json_ld_block_count, 1);
+        assert_eq!(diagnostics.status, AvailabilityStatus::InStock);
+        assert_eq!(diagnostics.price_minor_units, Some(1999));
+        assert_eq!(diagnostics.price_currency, Some("USD".to_string()));
+        assert!(diagnostics.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reports_aggregate_offer_price_range() {
+        let html = r#"<html><head><script type="application/ld+json">
+            {"@type": "Product", "offers": {
+                "@type": "AggregateOffer", "availability": "http://schema.org/InStock",
+                "lowPrice": "10.00", "highPrice": "25.00", "offerCount": 2, "priceCurrency": "USD"
+            }}
+        </script></head><body></body></html>"#;
+
+        let diagnostics = diagnose_html(html, "https://example.com/item", false).await;
+        assert_eq!(diagnostics.price_minor_units, Some(1000));
+        assert_eq!(
+            diagnostics.price_range,
+            Some(PriceRange {
+                low_minor_units: 1000,
+                high_minor_units: 2500,
+                offer_count: Some(2),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reports_gtm_strategy() {
+        let html = html_with_datalayer_push(
+            r#"{"event": "view_item", "currency": "USD", "items": [{"price": 25.00}]}"#,
+            Some(r#"<button class="add-to-cart">Add to Cart</button>"#),
+        );
+
+        let diagnostics = diagnose_html(&html, "https://example.com/item", false).await;
+        assert_eq!(diagnostics.strategy, Some(ExtractionStrategy::Gtm));
+        assert_eq!(diagnostics.json_ld_block_count, 0);
+        assert_eq!(diagnostics.price_minor_units, Some(2500));
+    }
+
+    #[tokio::test]
+    async fn test_reports_meta_strategy() {
+        let html = r#"<html><head>
+            <meta property="product:price:amount" content="5.50">
+            <meta property="product:price:currency" content="AUD">
+            <meta property="og:availability" content="instock">
+        </head><body></body></html>"#;
+
+        let diagnostics = diagnose_html(html, "https://example.com/item", false).await;
+        assert_eq!(diagnostics.strategy, Some(ExtractionStrategy::Meta));
+        assert_eq!(diagnostics.price_minor_units, Some(550));
+    }
+
+    #[tokio::test]
+    async fn test_reports_site_specific_strategy() {
+        let html = html_with_next_data(
+            r#"{"name": "Test Product", "price": "29.99", "availability": "in-stock"}"#,
+        );
+
+        let diagnostics = diagnose_html(
+            &html,
+            "https://www.chemistwarehouse.com.au/buy/1/item",
+            true,
+        )
+        .await;
+        assert_eq!(diagnostics.strategy, Some(ExtractionStrategy::SiteSpecific));
+        assert!(diagnostics.bot_protection_detected);
+        assert_eq!(diagnostics.status, AvailabilityStatus::InStock);
+    }
+
+    #[tokio::test]
+    async fn test_reports_error_when_no_strategy_matches() {
+        let html = "<html><body><h1>Hello</h1></body></html>";
+
+        let diagnostics = diagnose_html(html, "https://example.com/item", false).await;
+        assert!(diagnostics.strategy.is_none());
+        assert_eq!(diagnostics.status, AvailabilityStatus::Unknown);
+        assert!(diagnostics.error.is_some());
+    }
+
+    /// Plain HTTP only, without waiting between requests to the mock server
+    fn test_options() -> FetchOptions {
+        FetchOptions {
+            enable_headless: false,
+            allow_manual_verification: false,
+            headless_profile_reuse: true,
+            session_cache_duration_days: 14,
+            timeouts: FetchTimeouts {
+                min_host_interval_ms: 0,
+                ..FetchTimeouts::default()
+            },
+            force_headless_domains: Vec::new(),
+            respect_robots_txt: false,
+            rotate_user_agent: false,
+            product_group_strategy: ProductGroupStrategy::First,
+            headless_browser_path: None,
+            insecure_hosts: Vec::new(),
+            max_redirects: 10,
+            max_json_ld_block_bytes: DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_rejects_invalid_scheme() {
+        let conn = crate::test_utils::setup_availability_db().await;
+        let result = ScraperService::diagnose("ftp://example.com", &conn, &test_options()).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_reports_shopify_products_json() {
+        let server = MockServer::start(|request| match request.path() {
+            "/products/widget.json" => MockResponse::json(
+                200,
+                r#"{"product": {"variants": [{"id": 111, "price": "19.99", "available": true}]}}"#,
+            ),
+            _ => MockResponse::json(404, "{}"),
+        })
+        .await;
+        let conn = setup_fetch_db().await;
+
+        let diagnostics =
+            ScraperService::diagnose(&server.url("/products/widget"), &conn, &test_options())
+                .await
+                .unwrap();
+
+        assert_eq!(
+            diagnostics.strategy,
+            Some(ExtractionStrategy::ShopifyProductsJson)
+        );
+        assert_eq!(diagnostics.status, AvailabilityStatus::InStock);
+        assert_eq!(diagnostics.price_minor_units, Some(1999));
+        assert_eq!(diagnostics.json_ld_block_count, 0);
+        assert_eq!(server.paths(), vec!["/products/widget.json".to_string()]);
+    }
+
+    #[test]
+    fn test_truncate_html() {
+        assert_eq!(truncate_html("<p>hi</p>".to_string(), 100), "<p>hi</p>");
+        assert_eq!(truncate_html("<p>hi</p>".to_string(), 4), "<p>h");
+        // "é" is two bytes, so a cut through it backs off before it
+        assert_eq!(truncate_html("caf\u{e9}!".to_string(), 4), "caf");
+    }
+
+    #[test]
+    fn test_strategy_serializes_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&ExtractionStrategy::SchemaOrg).unwrap(),
+            r#""schema_org""#
+        );
+        assert_eq!(
+            serde_json::to_string(&ExtractionStrategy::SiteSpecific).unwrap(),
+            r#""site_specific""#
+        );
+        assert_eq!(
+            serde_json::to_string(&ExtractionStrategy::ShopifyProductsJson).unwrap(),
+            r#""shopify_products_json""#
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! DOM helpers shared by the adapters that read the rendered page (Amazon, eBay).
+
+use scraper::{Html, Selector};
+
+use product_stalker_core::AppError;
+
+/// Parse a CSS selector, reporting an invalid one as an error
+pub(super) fn parse_selector(css: &str) -> Result<Selector, AppError> {
+    Selector::parse(css).map_err(|e| AppError::External(format!("Invalid selector: {:?}", e)))
+}
+
+/// Return the whitespace-collapsed text of the first element matching any selector.
+pub(super) fn select_first_text(
+    document: &Html,
+    selectors: &[&str],
+) -> Result<Option<String>, AppError> {
+    for css in selectors {
+        let selector = parse_selector(css)?;
+
+        let text = document.select(&selector).find_map(|el| {
+            let text = el.text().collect::<Vec<_>>().join(" ");
+            let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!collapsed.is_empty()).then_some(collapsed)
+        });
+
+        if text.is_some() {
+            return Ok(text);
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_first_text_collapses_whitespace() {
+        let document = Html::parse_document(
+            r#"<div id="a">  </div><div id="b"> In
+                <span>stock</span> </div>"#,
+        );
+
+        assert_eq!(
+            select_first_text(&document, &["#missing", "#a", "#b"]).unwrap(),
+            Some("In stock".to_string())
+        );
+        assert_eq!(select_first_text(&document, &["#missing"]).unwrap(), None);
+        assert!(select_first_text(&document, &["div >"]).is_err());
+    }
+}
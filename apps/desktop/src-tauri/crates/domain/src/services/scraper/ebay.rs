@@ -0,0 +1,449 @@
+//! eBay adapter for parsing listing data from the item page.
+//!
+//! eBay listings are either fixed-price (Buy It Now) or auctions. Listing state is
+//! read from the embedded `__NEXT_DATA__`-style JSON blob when present, and otherwise
+//! from the DOM: the visible price in `.x-price-primary`, the Buy It Now / bid
+//! buttons, and the status message shown on ended or sold-out listings.
+
+use scraper::Html;
+use serde_json::Value;
+use url::Url;
+
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
+use product_stalker_core::AppError;
+
+use super::dom::{parse_selector, select_first_text};
+use super::nextjs_data;
+use super::price_parser::{
+    infer_currency_from_domain, normalize_decimal_comma, parse_price_to_minor_units, PriceInfo,
+};
+use super::ScrapingResult;
+
+/// eBay storefront domains
+const EBAY_DOMAINS: &[&str] = &[
+    "ebay.com",
+    "ebay.co.uk",
+    "ebay.com.au",
+    "ebay.ca",
+    "ebay.de",
+    "ebay.fr",
+    "ebay.it",
+    "ebay.es",
+    "ebay.ie",
+    "ebay.at",
+    "ebay.ch",
+    "ebay.nl",
+    "ebay.be",
+    "ebay.pl",
+    "ebay.com.sg",
+    "ebay.com.my",
+    "ebay.ph",
+    "ebay.com.hk",
+];
+
+/// Keys under pageProps that may hold the listing object.
+const LISTING_KEYS: &[&str] = &["listing", "item"];
+
+/// Selector for the visible listing price.
+const PRICE_SELECTOR: &str = ".x-price-primary";
+
+/// Selectors for the banner shown on ended or unavailable listings.
+const STATUS_MESSAGE_SELECTORS: &[&str] = &[".d-statusmessage", ".vi-content-status-msg"];
+
+/// Selector for the remaining-quantity message (e.g. "Out of stock", "3 available").
+const QUANTITY_SELECTOR: &str = ".d-quantity__availability";
+
+/// Selectors for the Buy It Now button.
+const BUY_IT_NOW_SELECTORS: &[&str] = &[".x-bin-action", "#binBtn_btn"];
+
+/// Selectors for the place-bid button or current bid price.
+const AUCTION_SELECTORS: &[&str] = &[".x-bid-action", "#bidBtn_btn", ".x-bid-price"];
+
+/// Status text fragments that mean the listing has ended.
+const ENDED_PHRASES: &[&str] = &[
+    "listing has ended",
+    "listing was ended",
+    "no longer available",
+];
+
+/// Text fragments that mean no quantity is left.
+const OUT_OF_STOCK_PHRASES: &[&str] = &["out of stock", "sold out"];
+
+/// Price prefixes eBay shows before the amount, mapped to ISO 4217 codes.
+/// Longer prefixes must come before shorter ones that they contain.
+const PRICE_PREFIXES: &[(&str, &str)] = &[
+    ("US $", "USD"),
+    ("AU $", "AUD"),
+    ("NZ $", "NZD"),
+    ("C $", "CAD"),
+    ("EUR", "EUR"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+];
+
+/// Listing state gathered from either the JSON blob or the DOM.
+#[derive(Debug, Default)]
+struct ListingState {
+    ended: bool,
+    out_of_stock: bool,
+    buy_it_now: bool,
+    auction: bool,
+    raw_price: Option<String>,
+    price_currency: Option<String>,
+}
+
+/// Check if the URL is for an eBay storefront (ebay.com, ebay.co.uk, etc.)
+pub fn is_ebay_url(url: &str) -> bool {
+    Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .is_some_and(|host| {
+            EBAY_DOMAINS
+                .iter()
+                .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+        })
+}
+
+/// Parse listing availability and price from an eBay item page.
+///
+/// - Ended or sold-out listings map to `OutOfStock`
+/// - Active Buy It Now listings map to `InStock` with the extracted price
+/// - Auctions without Buy It Now map to `Unknown` with `raw_availability = "auction"`
+///   and no price, since the current bid is not a purchase price
+///
+/// When the price text has no unambiguous currency, it is inferred from the domain.
+pub fn parse_ebay_data(html: &str, url: &str) -> Result<ScrapingResult, AppError> {
+    let state = match listing_from_json(html) {
+        Some(state) => state,
+        None => listing_from_dom(html)?,
+    };
+
+    let (status, raw_availability) = if state.ended {
+        (AvailabilityStatus::OutOfStock, "ended")
+    } else if state.out_of_stock {
+        (AvailabilityStatus::OutOfStock, "out of stock")
+    } else if state.buy_it_now {
+        (AvailabilityStatus::InStock, "buy it now")
+    } else if state.auction {
+        return Ok(ScrapingResult {
+            status: AvailabilityStatus::Unknown,
+            raw_availability: Some("auction".to_string()),
+            price: PriceInfo::default(),
+            region_mismatch: false,
+            fetch_metrics: None,
+            canonical_url: None,
+            confidence: Confidence::High,
+        });
+    } else {
+        return Err(AppError::External(
+            "No listing information found in eBay page".to_string(),
+        ));
+    };
+
+    let price_currency = state
+        .price_currency
+        .or_else(|| infer_currency_from_domain(url));
+    let price_minor_units = state.raw_price.as_deref().and_then(|p| {
+        parse_price_to_minor_units(&normalize_decimal_comma(p), price_currency.as_deref())
+    });
+
+    Ok(ScrapingResult {
+        status,
+        raw_availability: Some(raw_availability.to_string()),
+        price: PriceInfo {
+            price_minor_units,
+            price_currency,
+            raw_price: state.raw_price,
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            price_range: None,
+        },
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
+    })
+}
+
+/// Read listing state from the embedded JSON blob.
+///
+/// The listing object mirrors eBay's item API:
+/// ```json
+/// {
+///   "listingStatus": "ACTIVE",
+///   "buyingOptions": ["FIXED_PRICE"],
+///   "estimatedAvailabilityStatus": "IN_STOCK",
+///   "price": { "value": "49.99", "currency": "USD" }
+/// }
+/// ```
+fn listing_from_json(html: &str) -> Option<ListingState> {
+    let next_data = nextjs_data::extract_next_data(html).ok()?;
+    let page_props = nextjs_data::get_page_props(&next_data)?;
+    let listing = LISTING_KEYS
+        .iter()
+        .find_map(|key| page_props.get(*key))
+        .filter(|listing| listing.get("buyingOptions").is_some())?;
+
+    let buying_options: Vec<&str> = listing
+        .get("buyingOptions")
+        .and_then(Value::as_array)
+        .map(|options| options.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let str_field = |key: &str| listing.get(key).and_then(Value::as_str);
+    let price = listing.get("price");
+
+    Some(ListingState {
+        ended: str_field("listingStatus").is_some_and(|s| s.eq_ignore_ascii_case("ENDED")),
+        out_of_stock: str_field("estimatedAvailabilityStatus")
+            .is_some_and(|s| s.eq_ignore_ascii_case("OUT_OF_STOCK")),
+        buy_it_now: buying_options.contains(&"FIXED_PRICE"),
+        auction: buying_options.contains(&"AUCTION"),
+        raw_price: price.and_then(json_price_value),
+        price_currency: price
+            .and_then(|p| p.get("currency"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+/// Read a price value that may be encoded as a string or a number
+fn json_price_value(price: &Value) -> Option<String> {
+    match price.get("value")? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Read listing state from the rendered item page.
+fn listing_from_dom(html: &str) -> Result<ListingState, AppError> {
+    let document = Html::parse_document(html);
+
+    let status_message = select_first_text(&document, STATUS_MESSAGE_SELECTORS)?
+        .map(|text| text.to_lowercase())
+        .unwrap_or_default();
+    let quantity = select_first_text(&document, &[QUANTITY_SELECTOR])?
+        .map(|text| text.to_lowercase())
+        .unwrap_or_default();
+    let raw_price = select_first_text(&document, &[PRICE_SELECTOR])?;
+
+    let contains_any =
+        |text: &str, phrases: &[&str]| phrases.iter().any(|phrase| text.contains(phrase));
+
+    Ok(ListingState {
+        ended: contains_any(&status_message, ENDED_PHRASES),
+        out_of_stock: contains_any(&status_message, OUT_OF_STOCK_PHRASES)
+            || contains_any(&quantity, OUT_OF_STOCK_PHRASES),
+        buy_it_now: has_any(&document, BUY_IT_NOW_SELECTORS)?,
+        auction: has_any(&document, AUCTION_SELECTORS)?,
+        price_currency: raw_price.as_deref().and_then(currency_from_prefix),
+        raw_price,
+    })
+}
+
+/// Whether any element matches any of the selectors
+fn has_any(document: &Html, selectors: &[&str]) -> Result<bool, AppError> {
+    for css in selectors {
+        if document.select(&parse_selector(css)?).next().is_some() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Detect the currency from the prefix eBay shows before the price (e.g. "AU $59.00")
+fn currency_from_prefix(raw_price: &str) -> Option<String> {
+    PRICE_PREFIXES
+        .iter()
+        .find(|(prefix, _)| raw_price.contains(prefix))
+        .map(|(_, code)| (*code).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ebay_html(price: &str, actions: &str, status: &str) -> String {
+        format!(
+            r#"<html><body>
+                <div class="d-statusmessage">{}</div>
+                <div class="x-price-primary"><span class="ux-textspans">{}</span></div>
+                <div class="d-quantity__availability"><span>3 available</span></div>
+                {}
+            </body></html>"#,
+            status, price, actions
+        )
+    }
+
+    fn ebay_json_html(listing_json: &str) -> String {
+        format!(
+            r#"<html><head>
+                <script id="__NEXT_DATA__" type="application/json">
+                {{"props": {{"pageProps": {{"listing": {}}}}}}}
+                </script>
+            </head><body></body></html>"#,
+            listing_json
+        )
+    }
+
+    const BIN_BUTTON: &str = r#"<a class="x-bin-action" href="/action">Buy It Now</a>"#;
+    const BID_BUTTON: &str = r#"<a class="x-bid-action" href="/action">Place bid</a>"#;
+
+    #[test]
+    fn test_is_ebay_url() {
+        assert!(is_ebay_url("https://www.ebay.com/itm/123456"));
+        assert!(is_ebay_url("https://www.ebay.co.uk/itm/123456"));
+        assert!(is_ebay_url("https://ebay.com.au/itm/123456"));
+        assert!(is_ebay_url("https://m.ebay.de/itm/123456"));
+        assert!(!is_ebay_url("https://notebay.com/itm/123456"));
+        assert!(!is_ebay_url("https://ebay.example.com/itm/123456"));
+        assert!(!is_ebay_url("https://shop.ebay.evil.net/itm/123456"));
+        assert!(!is_ebay_url("https://example.com/ebay"));
+        assert!(!is_ebay_url("not-a-url"));
+    }
+
+    #[test]
+    fn test_parse_buy_it_now_in_stock() {
+        let html = ebay_html("US $49.99", BIN_BUTTON, "");
+
+        let result = parse_ebay_data(&html, "https://www.ebay.com/itm/1").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.raw_availability, Some("buy it now".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(4999));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+        assert_eq!(result.price.raw_price, Some("US $49.99".to_string()));
+    }
+
+    #[test]
+    fn test_parse_price_prefix_currency() {
+        let html = ebay_html("AU $1,299.00", BIN_BUTTON, "");
+
+        let result = parse_ebay_data(&html, "https://www.ebay.com/itm/1").unwrap();
+        assert_eq!(result.price.price_minor_units, Some(129900));
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+    }
+
+    #[test]
+    fn test_parse_euro_decimal_comma() {
+        let html = ebay_html("EUR 49,99", BIN_BUTTON, "");
+
+        let result = parse_ebay_data(&html, "https://www.ebay.de/itm/1").unwrap();
+        assert_eq!(result.price.price_minor_units, Some(4999));
+        assert_eq!(result.price.price_currency, Some("EUR".to_string()));
+    }
+
+    #[test]
+    fn test_parse_currency_falls_back_to_domain() {
+        let html = ebay_html("$20.00", BIN_BUTTON, "");
+
+        let result = parse_ebay_data(&html, "https://www.ebay.com.au/itm/1").unwrap();
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(2000));
+    }
+
+    #[test]
+    fn test_parse_ended_listing() {
+        let html = ebay_html(
+            "£12.50",
+            BIN_BUTTON,
+            "This listing was ended by the seller because the item is no longer available.",
+        );
+
+        let result = parse_ebay_data(&html, "https://www.ebay.co.uk/itm/1").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.raw_availability, Some("ended".to_string()));
+        assert_eq!(result.price.price_currency, Some("GBP".to_string()));
+    }
+
+    #[test]
+    fn test_parse_out_of_stock_quantity() {
+        let html = r#"<html><body>
+            <div class="x-price-primary"><span>US $15.00</span></div>
+            <div class="d-quantity__availability"><span>Out of stock</span></div>
+        </body></html>"#;
+
+        let result = parse_ebay_data(html, "https://www.ebay.com/itm/1").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.raw_availability, Some("out of stock".to_string()));
+    }
+
+    #[test]
+    fn test_parse_auction_is_unknown() {
+        let html = ebay_html("US $5.50", BID_BUTTON, "");
+
+        let result = parse_ebay_data(&html, "https://www.ebay.com/itm/1").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::Unknown);
+        assert_eq!(result.raw_availability, Some("auction".to_string()));
+        assert!(result.price.price_minor_units.is_none());
+    }
+
+    #[test]
+    fn test_parse_auction_with_buy_it_now_is_in_stock() {
+        let html = ebay_html("US $80.00", &format!("{}{}", BID_BUTTON, BIN_BUTTON), "");
+
+        let result = parse_ebay_data(&html, "https://www.ebay.com/itm/1").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.price.price_minor_units, Some(8000));
+    }
+
+    #[test]
+    fn test_parse_json_fixed_price() {
+        let html = ebay_json_html(
+            r#"{
+                "listingStatus": "ACTIVE",
+                "buyingOptions": ["FIXED_PRICE", "BEST_OFFER"],
+                "estimatedAvailabilityStatus": "IN_STOCK",
+                "price": {"value": "129.95", "currency": "AUD"}
+            }"#,
+        );
+
+        let result = parse_ebay_data(&html, "https://www.ebay.com/itm/1").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.price.price_minor_units, Some(12995));
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+    }
+
+    #[test]
+    fn test_parse_json_ended_and_out_of_stock() {
+        let ended = ebay_json_html(
+            r#"{"listingStatus": "ENDED", "buyingOptions": ["FIXED_PRICE"],
+                "price": {"value": 10, "currency": "USD"}}"#,
+        );
+        let result = parse_ebay_data(&ended, "https://www.ebay.com/itm/1").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(1000));
+
+        let sold_out = ebay_json_html(
+            r#"{"listingStatus": "ACTIVE", "buyingOptions": ["FIXED_PRICE"],
+                "estimatedAvailabilityStatus": "OUT_OF_STOCK"}"#,
+        );
+        let result = parse_ebay_data(&sold_out, "https://www.ebay.com/itm/1").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+    }
+
+    #[test]
+    fn test_parse_json_auction() {
+        let html = ebay_json_html(
+            r#"{"listingStatus": "ACTIVE", "buyingOptions": ["AUCTION"],
+                "price": {"value": "5.50", "currency": "USD"}}"#,
+        );
+
+        let result = parse_ebay_data(&html, "https://www.ebay.com/itm/1").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::Unknown);
+        assert_eq!(result.raw_availability, Some("auction".to_string()));
+    }
+
+    #[test]
+    fn test_parse_no_listing_fails() {
+        let html = "<html><body><h1>Security measure</h1></body></html>";
+        assert!(parse_ebay_data(html, "https://www.ebay.com/itm/1").is_err());
+    }
+
+    #[test]
+    fn test_currency_from_prefix() {
+        assert_eq!(currency_from_prefix("US $1.00"), Some("USD".to_string()));
+        assert_eq!(currency_from_prefix("C $1.00"), Some("CAD".to_string()));
+        assert_eq!(currency_from_prefix("EUR 1,00"), Some("EUR".to_string()));
+        assert_eq!(currency_from_prefix("$1.00"), None);
+    }
+}
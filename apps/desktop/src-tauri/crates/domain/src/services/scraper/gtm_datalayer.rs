@@ -10,7 +10,7 @@ use product_stalker_core::AppError;
 
 use super::price_parser::{parse_price_to_minor_units, PriceInfo};
 use super::ScrapingResult;
-use crate::entities::availability_check::AvailabilityStatus;
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
 
 /// A single GA4 ecommerce item within a `dataLayer.push()` event.
 #[derive(Debug, Deserialize, Default)]
@@ -94,6 +94,9 @@ pub fn extract_from_datalayer(html: &str) -> Result<ScrapingResult, AppError> {
 }
 
 /// Build a ScrapingResult from inferred availability and extracted price.
+///
+/// The dataLayer carries no stock data, so the status is only a guess from the
+/// page's add-to-cart buttons and has low confidence.
 fn build_result(html: &str, price: PriceInfo) -> ScrapingResult {
     let status = infer_availability(html);
     let raw_availability = Some(format!("gtm_datalayer:{}", status.as_str()));
@@ -101,6 +104,10 @@ fn build_result(html: &str, price: PriceInfo) -> ScrapingResult {
         status,
         raw_availability,
         price,
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::Low,
     }
 }
 
@@ -406,6 +413,9 @@ fn extract_price_from_value(
         price_minor_units: minor_units,
         price_currency: currency.map(|c| c.to_string()),
         raw_price: Some(raw_price),
+        compare_at_minor_units: None,
+        price_valid_until: None,
+        price_range: None,
     })
 }
 
@@ -1,25 +1,44 @@
 //! HTTP client utilities for fetching web pages with browser-like headers.
 
-use std::time::Duration;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
+use chrono::Utc;
 use product_stalker_core::AppError;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use url::Url;
 
 use super::bot_detection::is_cloudflare_challenge;
-use crate::services::{HeadlessService, ManualVerificationService};
+use super::rate_limiter::HostRateLimiter;
+use super::robots::RobotsCache;
+use super::schema_org::ProductGroupStrategy;
+use super::user_agent::{profile_for_url, BrowserProfile, DEFAULT_PROFILE};
+use crate::entities::availability_check::{
+    HTTP_STATUS_ERROR_PREFIX, NETWORK_ERROR_PREFIX, TIMEOUT_ERROR_PREFIX, UNREACHABLE_ERROR_PREFIX,
+};
+use crate::services::domain_setting_service::defaults::BULK_CHECK_DELAY_MS;
+use crate::services::{
+    DomainRequestOverrides, DomainSettingService, HeadlessService, ManualVerificationService,
+};
 use product_stalker_core::repositories::VerifiedSessionRepository;
 
-/// HTTP request timeout
-const TIMEOUT_SECS: u64 = 30;
-
 use super::USER_AGENT;
 
 /// HTTP Accept header for HTML content
 const ACCEPT_HEADER: &str =
     "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8";
 
-/// Sec-Ch-Ua header for Chrome browser fingerprint
-const SEC_CH_UA: &str = r#""Not_A Brand";v="8", "Chromium";v="120", "Google Chrome";v="120""#;
+/// Error message for URLs the site's robots.txt disallows.
+pub(crate) const ROBOTS_DISALLOWED_MESSAGE: &str =
+    "This page is disallowed by the site's robots.txt. Turn off \"Respect robots.txt\" in settings to check it anyway.";
+
+/// Error message for pages whose body can't be decompressed or decoded.
+const UNDECODABLE_BODY_MESSAGE: &str = "The page's content could not be decoded as text";
 
 /// Error message shown when bot protection is detected and headless browser is disabled.
 const BOT_PROTECTION_MESSAGE: &str =
@@ -30,66 +49,428 @@ const BOT_PROTECTION_MESSAGE: &str =
 /// Used within the scraper module to preserve structured error data
 /// (e.g., HTTP status codes) for control flow decisions before
 /// converting to the generic `AppError::External` at the boundary.
+#[derive(Debug)]
 enum FetchPageError {
-    /// HTTP client or network error (connection refused, timeout, DNS, TLS)
+    /// HTTP client or network error not covered below (TLS, dropped connection, ...)
     Http(String),
+    /// The host's name didn't resolve or it refused the connection
+    Unreachable(String),
+    /// No response (or no complete body) within the request timeout
+    Timeout(String),
     /// HTTP response returned a non-success status code
     HttpStatus { status: u16, url: String },
+    /// The response body couldn't be decompressed or decoded as text
+    Decode(String),
+    /// Gave up following the page's redirects
+    Redirect(RedirectError),
+}
+
+/// Why the redirect policy stopped following a page's redirects
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RedirectError {
+    /// A redirect led back to this already-requested URL
+    Loop(String),
+    /// The page redirected more than this many times
+    TooMany(usize),
+}
+
+impl fmt::Display for RedirectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Loop(url) => write!(f, "Redirect loop: redirected back to {}", url),
+            Self::TooMany(max) => write!(f, "Too many redirects (more than {})", max),
+        }
+    }
+}
+
+impl std::error::Error for RedirectError {}
+
+impl FetchPageError {
+    /// Classify a failed request, telling unreachable hosts and timeouts apart
+    /// from other network errors.
+    ///
+    /// reqwest reports DNS failures and refused connections alike as connect
+    /// errors, so the cause is found by walking the error's source chain.
+    fn from_request_error(error: &reqwest::Error) -> Self {
+        if let Some(redirect) = redirect_cause(error) {
+            return Self::Redirect(redirect.clone());
+        }
+        if error.is_timeout() {
+            return Self::Timeout(error_chain_message(error));
+        }
+        if error.is_connect() && is_unreachable_cause(error) {
+            return Self::Unreachable(error_chain_message(error));
+        }
+        Self::Http(error.to_string())
+    }
+}
+
+/// Whether a connect error was caused by a failed DNS lookup or a refused connection
+fn is_unreachable_cause(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(error);
+    while let Some(err) = source {
+        if err.downcast_ref::<DnsLookupError>().is_some() {
+            return true;
+        }
+        if err
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|io| io.kind() == std::io::ErrorKind::ConnectionRefused)
+        {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// A host name that couldn't be resolved
+#[derive(Debug)]
+struct DnsLookupError {
+    host: String,
+    source: std::io::Error,
+}
+
+impl fmt::Display for DnsLookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to resolve {}: {}", self.host, self.source)
+    }
+}
+
+impl std::error::Error for DnsLookupError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// System resolver for the page clients. hyper-util's own resolver error type
+/// isn't public, so lookups go through here to fail with a [`DnsLookupError`]
+/// that [`is_unreachable_cause`] can recognise.
+struct PageResolver;
+
+impl reqwest::dns::Resolve for PageResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let lookup = {
+                let host = host.clone();
+                tokio::task::spawn_blocking(move || {
+                    std::net::ToSocketAddrs::to_socket_addrs(&(host.as_str(), 0))
+                })
+            };
+            let addrs = lookup
+                .await?
+                .map_err(|source| DnsLookupError { host, source })?;
+            let addrs: reqwest::dns::Addrs = Box::new(addrs);
+            Ok(addrs)
+        })
+    }
+}
+
+/// The redirect policy's reason for failing a request, if that's what failed it
+fn redirect_cause<'a>(error: &'a (dyn std::error::Error + 'static)) -> Option<&'a RedirectError> {
+    let mut source = Some(error);
+    while let Some(err) = source {
+        if let Some(redirect) = err.downcast_ref::<RedirectError>() {
+            return Some(redirect);
+        }
+        source = err.source();
+    }
+    None
+}
+
+/// The error and each of its causes, joined with ": "
+/// (e.g. "error sending request ...: client error (Connect): dns error: ...")
+fn error_chain_message(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut parts = vec![error.to_string()];
+    let mut source = error.source();
+    while let Some(err) = source {
+        parts.push(err.to_string());
+        source = err.source();
+    }
+    parts.join(": ")
+}
+
+/// Leading bytes of a gzip stream, used to spot compressed bodies sent without
+/// a `Content-Encoding` header
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// How long to wait for a page before giving up, and how far apart to space
+/// requests to the same host
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FetchTimeouts {
+    /// Timeout for the plain HTTP request
+    pub http_secs: u64,
+    /// Page load timeout for the headless browser (longer, as it executes JS)
+    pub headless_secs: u64,
+    /// Minimum interval between requests to the same host (0 disables)
+    pub min_host_interval_ms: u64,
+}
+
+impl Default for FetchTimeouts {
+    fn default() -> Self {
+        Self {
+            http_secs: 30,
+            headless_secs: HeadlessService::PAGE_TIMEOUT_SECS,
+            min_host_interval_ms: BULK_CHECK_DELAY_MS as u64,
+        }
+    }
+}
+
+/// How pages are fetched: bot protection fallbacks, verified sessions and timeouts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FetchOptions {
+    /// Fall back to the headless browser when bot protection is detected
+    pub enable_headless: bool,
+    /// Fall back to a visible browser when the headless browser is challenged
+    pub allow_manual_verification: bool,
+    /// Keep a persistent headless browser profile per domain
+    pub headless_profile_reuse: bool,
+    /// How long a verified session's cookies are replayed
+    pub session_cache_duration_days: i32,
+    pub timeouts: FetchTimeouts,
+    /// Hosts that always block plain HTTP and go straight to the headless browser
+    /// (subdomains match too)
+    pub force_headless_domains: Vec<String>,
+    /// Skip URLs disallowed by the site's robots.txt and honor its `Crawl-delay`
+    pub respect_robots_txt: bool,
+    /// Send each host a User-Agent picked from a pool of current desktop browsers
+    /// (with matching client hints) instead of always the default one
+    pub rotate_user_agent: bool,
+    /// Which ProductGroup variant to report when the URL doesn't select one
+    pub product_group_strategy: ProductGroupStrategy,
+    /// Browser binary for the headless and manual verification fallbacks
+    /// (`None` = search the usual install locations)
+    pub headless_browser_path: Option<PathBuf>,
+    /// Hosts whose TLS certificates aren't verified, for self-hosted stores with
+    /// self-signed certs (exact hosts only; never applied to any other host)
+    pub insecure_hosts: Vec<String>,
+    /// Most redirects followed for one page; a redirect back to an address
+    /// already requested fails straight away as a loop
+    pub max_redirects: usize,
+    /// JSON-LD blocks larger than this are skipped instead of parsed
+    pub max_json_ld_block_bytes: usize,
+}
+
+/// A cookie captured from a verified browser session.
+///
+/// Only the fields needed to replay the cookie are read; the rest of the
+/// browser's cookie record is ignored.
+#[derive(Debug, Deserialize)]
+struct StoredCookie {
+    name: String,
+    value: String,
+}
+
+/// HTML fetched for a URL, along with how it was obtained
+pub struct FetchedPage {
+    pub html: String,
+    /// True if the plain HTTP request hit bot protection and a fallback was used
+    pub bot_protection_detected: bool,
+    /// URL the plain HTTP request ended up at after redirects (`None` for fallbacks)
+    pub final_url: Option<String>,
+    /// Time spent fetching, including any fallbacks but not the rate limit wait
+    pub fetch_duration: Duration,
+}
+
+/// Timing and size of a page fetch, recorded with each check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchMetrics {
+    pub duration_ms: i64,
+    pub content_bytes: i64,
+}
+
+impl FetchedPage {
+    /// Timing and size of this fetch
+    pub fn metrics(&self) -> FetchMetrics {
+        FetchMetrics {
+            duration_ms: i64::try_from(self.fetch_duration.as_millis()).unwrap_or(i64::MAX),
+            content_bytes: self.html.len() as i64,
+        }
+    }
+}
+
+/// Check robots.txt and wait for the host's rate limit before any request to `url`.
+///
+/// Every request for a product goes through here first, so robots.txt rules and
+/// `Crawl-delay` apply no matter which endpoint is fetched.
+pub(crate) async fn wait_for_host_turn(url: &str, options: &FetchOptions) -> Result<(), AppError> {
+    let mut min_host_interval = Duration::from_millis(options.timeouts.min_host_interval_ms);
+    if options.respect_robots_txt {
+        if let Some(rules) = RobotsCache::global().rules_for(url, options).await {
+            if !rules.is_url_allowed(url) {
+                log::info!("Skipping {}: disallowed by robots.txt", url);
+                return Err(AppError::Validation(ROBOTS_DISALLOWED_MESSAGE.to_string()));
+            }
+            if let Some(crawl_delay) = rules.crawl_delay {
+                min_host_interval = min_host_interval.max(crawl_delay);
+            }
+        }
+    }
+
+    HostRateLimiter::global().wait(url, min_host_interval).await;
+    Ok(())
 }
 
 /// Fetch HTML content, falling back to headless browser or manual verification if needed
 ///
-/// Tries HTTP first (fast path). If bot protection is detected (Cloudflare challenge,
+/// With `respect_robots_txt`, URLs the site's robots.txt disallows fail with a
+/// validation error before any request, and its `Crawl-delay` raises the
+/// per-host interval when longer.
+///
+/// Waits for the URL's host to be free under the per-host rate limit, then
+/// tries HTTP first (fast path), replaying cookies from a verified session for the
+/// URL's host if one is stored. If bot protection is detected (Cloudflare challenge,
 /// 403/503 status), falls back to headless browser if enabled. If headless browser
 /// encounters a CAPTCHA and manual verification is allowed, launches a visible browser
 /// for the user to solve the CAPTCHA manually.
+///
+/// Hosts listed in `force_headless_domains` skip the HTTP request and go straight
+/// to the headless browser, or fail with the bot protection error if headless is
+/// disabled.
+///
+/// With `headless_profile_reuse`, the headless browser uses a persistent profile per
+/// domain; otherwise each fetch starts from a fresh profile (see
+/// [`should_reuse_headless_profile`]).
 pub async fn fetch_html_with_fallback(
     url: &str,
-    enable_headless: bool,
-    allow_manual_verification: bool,
     conn: &DatabaseConnection,
-    session_cache_duration_days: i32,
-) -> Result<String, AppError> {
-    let needs_headless = match fetch_page(url).await {
-        Ok(html) if !is_cloudflare_challenge(200, &html) => return Ok(html),
+    options: &FetchOptions,
+) -> Result<FetchedPage, AppError> {
+    let force_headless = is_force_headless_host(url, &options.force_headless_domains);
+    if force_headless && !options.enable_headless {
+        log::info!("Headless browser disabled for force-headless host {}", url);
+        return Err(AppError::External(BOT_PROTECTION_MESSAGE.to_string()));
+    }
+
+    wait_for_host_turn(url, options).await?;
+    let started = Instant::now();
+
+    if force_headless {
+        log::info!("Skipping HTTP request for force-headless host {}", url);
+        return fetch_with_bot_protection_fallback(url, conn, options, started).await;
+    }
+
+    let cookie_header =
+        load_session_cookie_header(conn, url, options.session_cache_duration_days).await?;
+    let overrides = load_request_overrides(conn, url).await?;
+    let http_timeout = Duration::from_secs(options.timeouts.http_secs);
+    let profile = request_profile(url, cookie_header.is_some(), options.rotate_user_agent);
+    match fetch_page(
+        url,
+        profile,
+        cookie_header.as_deref(),
+        overrides.as_ref(),
+        &options.insecure_hosts,
+        options.max_redirects,
+        http_timeout,
+    )
+    .await
+    {
+        Ok((html, final_url)) if !is_cloudflare_challenge(200, &html) => {
+            return Ok(FetchedPage {
+                html,
+                bot_protection_detected: false,
+                final_url: Some(final_url),
+                fetch_duration: started.elapsed(),
+            })
+        }
         Ok(_) => {
             log::info!("Detected bot protection challenge for {}", url);
-            true
         }
         Err(FetchPageError::HttpStatus { status, .. }) if status == 403 || status == 503 => {
             log::info!("HTTP request blocked ({}) for {}", status, url);
-            true
         }
         Err(FetchPageError::HttpStatus { status, url }) => {
-            let msg = format!("HTTP {} for URL: {}", status, url);
+            let msg = format!("{}{} for URL: {}", HTTP_STATUS_ERROR_PREFIX, status, url);
             log::error!("HTTP fetch failed for {}: {}", url, msg);
             return Err(AppError::External(msg));
         }
         Err(FetchPageError::Http(msg)) => {
             log::error!("HTTP fetch failed for {}: {}", url, msg);
-            return Err(AppError::External(msg));
+            return Err(AppError::External(format!(
+                "{}{}",
+                NETWORK_ERROR_PREFIX, msg
+            )));
+        }
+        Err(FetchPageError::Unreachable(msg)) => {
+            log::error!("Could not reach {}: {}", url, msg);
+            return Err(AppError::External(format!(
+                "{}{}",
+                UNREACHABLE_ERROR_PREFIX, msg
+            )));
+        }
+        Err(FetchPageError::Timeout(msg)) => {
+            log::error!("HTTP fetch timed out for {}: {}", url, msg);
+            return Err(AppError::External(format!(
+                "{}{}",
+                TIMEOUT_ERROR_PREFIX, msg
+            )));
+        }
+        Err(FetchPageError::Decode(msg)) => {
+            log::error!("Undecodable response from {}: {}", url, msg);
+            return Err(AppError::External(format!(
+                "{} ({})",
+                UNDECODABLE_BODY_MESSAGE, msg
+            )));
+        }
+        Err(FetchPageError::Redirect(reason)) => {
+            log::error!("Stopped following redirects for {}: {}", url, reason);
+            return Err(AppError::External(reason.to_string()));
         }
     };
 
-    if needs_headless && enable_headless {
+    fetch_with_bot_protection_fallback(url, conn, options, started).await
+}
+
+/// Fetch a page that blocks plain HTTP via the headless browser, then manual
+/// verification, as far as the options allow.
+///
+/// `started` is when the fetch began, so the recorded duration includes any
+/// HTTP attempt made before falling back.
+async fn fetch_with_bot_protection_fallback(
+    url: &str,
+    conn: &DatabaseConnection,
+    options: &FetchOptions,
+    started: Instant,
+) -> Result<FetchedPage, AppError> {
+    if options.enable_headless {
         log::info!("Attempting headless fallback for {}", url);
-        match fetch_with_headless(url).await {
-            Ok(html) => return Ok(html),
+        let html = match fetch_with_headless(
+            url,
+            options.headless_profile_reuse,
+            conn,
+            options.timeouts.headless_secs,
+            options.headless_browser_path.clone(),
+        )
+        .await
+        {
+            Ok(html) => html,
             Err(e) => {
                 log::warn!("Headless browser failed for {}: {}", url, e);
 
-                if allow_manual_verification {
+                if options.allow_manual_verification {
                     log::info!("Attempting manual verification for {}", url);
-                    return fetch_with_manual_verification(url, conn, session_cache_duration_days)
-                        .await;
+                    fetch_with_manual_verification(
+                        url,
+                        conn,
+                        options.session_cache_duration_days,
+                        options.headless_browser_path.clone(),
+                    )
+                    .await?
+                } else {
+                    return Err(e);
                 }
-
-                return Err(e);
             }
-        }
+        };
+        return Ok(FetchedPage {
+            html,
+            bot_protection_detected: true,
+            final_url: None,
+            fetch_duration: started.elapsed(),
+        });
     }
 
-    if allow_manual_verification {
+    if options.allow_manual_verification {
         Err(AppError::External(
             "This site has bot protection. Manual verification is enabled but headless browser must be enabled first.".to_string()
         ))
@@ -98,24 +479,270 @@ pub async fn fetch_html_with_fallback(
     }
 }
 
+/// The address to store instead of `requested_url` when a fetch was redirected
+/// to `final_url` only to normalize it.
+///
+/// That's an upgrade from `http` to `https`, adding or dropping `www.`, or adding
+/// or dropping a trailing slash, with the same path and query. Any other redirect
+/// (another site, page or region, or a downgrade to `http`) returns `None`, as
+/// does no redirect. A fragment on the requested URL is kept.
+pub fn canonical_redirect_url(requested_url: &str, final_url: &str) -> Option<String> {
+    let requested = Url::parse(requested_url).ok()?;
+    let mut canonical = Url::parse(final_url).ok()?;
+    canonical.set_fragment(requested.fragment());
+    if canonical == requested {
+        return None;
+    }
+
+    let same_scheme = requested.scheme() == canonical.scheme()
+        || (requested.scheme() == "http" && canonical.scheme() == "https");
+    let site = |url: &Url| {
+        url.host_str().map(|host| {
+            let host = host.to_lowercase();
+            host.strip_prefix("www.")
+                .map(str::to_string)
+                .unwrap_or(host)
+        })
+    };
+    let path = |url: &Url| url.path().trim_end_matches('/').to_string();
+
+    let is_normalization = same_scheme
+        && site(&requested).is_some()
+        && site(&requested) == site(&canonical)
+        && requested.port() == canonical.port()
+        && path(&requested) == path(&canonical)
+        && requested.query() == canonical.query();
+    is_normalization.then(|| canonical.to_string())
+}
+
+/// Whether the URL's host is exactly one of `hosts`.
+///
+/// Unlike [`is_force_headless_host`], subdomains don't match: certificate checks
+/// are only skipped for the hosts the user listed.
+pub(crate) fn is_insecure_host(url: &str, hosts: &[String]) -> bool {
+    ManualVerificationService::extract_domain(url)
+        .is_ok_and(|host| hosts.contains(&host.to_lowercase()))
+}
+
+/// Whether the URL's host is one of `domains` or a subdomain of one
+pub(crate) fn is_force_headless_host(url: &str, domains: &[String]) -> bool {
+    let Ok(host) = ManualVerificationService::extract_domain(url) else {
+        return false;
+    };
+    let host = host.to_lowercase();
+    domains.iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+/// Build a `Cookie` header from the verified session stored for a URL's host.
+///
+/// Returns `None` when there is no session, when it has expired, or when it is
+/// older than `session_cache_duration_days` (the setting may have been lowered
+/// since the session was stored).
+pub(crate) async fn load_session_cookie_header(
+    conn: &DatabaseConnection,
+    url: &str,
+    session_cache_duration_days: i32,
+) -> Result<Option<String>, AppError> {
+    // Unparseable URLs are left for the HTTP request to report
+    let Ok(domain) = ManualVerificationService::extract_domain(url) else {
+        return Ok(None);
+    };
+    let Some(session) = VerifiedSessionRepository::find_by_domain(conn, &domain).await? else {
+        return Ok(None);
+    };
+
+    let max_age = chrono::Duration::days(session_cache_duration_days as i64);
+    if session.created_at + max_age <= Utc::now() {
+        return Ok(None);
+    }
+
+    Ok(cookie_header_from_json(&session.cookies_json))
+}
+
+/// Load the custom headers and cookies configured for a URL's exact host
+pub(crate) async fn load_request_overrides(
+    conn: &DatabaseConnection,
+    url: &str,
+) -> Result<Option<DomainRequestOverrides>, AppError> {
+    // Unparseable URLs are left for the HTTP request to report
+    let Ok(host) = ManualVerificationService::extract_domain(url) else {
+        return Ok(None);
+    };
+    DomainSettingService::get_request_overrides_for_host(conn, &host).await
+}
+
+/// A request to `url` sent the way a page fetch for the same host would be:
+/// through the page client (so `insecure_hosts` and `max_redirects` apply), with
+/// the HTTP timeout, the host's User-Agent and client hints, its verified session
+/// and override cookies, and its override headers.
+///
+/// For store APIs used alongside or in place of the page. `accept` replaces the
+/// page `Accept` header unless the host overrides it.
+pub(crate) async fn host_request(
+    method: reqwest::Method,
+    url: &str,
+    accept: &'static str,
+    conn: &DatabaseConnection,
+    options: &FetchOptions,
+) -> Result<reqwest::RequestBuilder, AppError> {
+    let cookie_header =
+        load_session_cookie_header(conn, url, options.session_cache_duration_days).await?;
+    let overrides = load_request_overrides(conn, url).await?;
+    let profile = request_profile(url, cookie_header.is_some(), options.rotate_user_agent);
+    let request = client_for_url(url, options)?
+        .request(method, url)
+        .timeout(Duration::from_secs(options.timeouts.http_secs))
+        .header(reqwest::header::ACCEPT, HeaderValue::from_static(accept));
+    Ok(with_identity(
+        request,
+        profile,
+        cookie_header.as_deref(),
+        overrides.as_ref(),
+    ))
+}
+
+/// User-Agent profile for a request to `url`. Verified session cookies are tied
+/// to the User-Agent they were issued to, so requests replaying them use the default.
+fn request_profile(
+    url: &str,
+    has_session_cookies: bool,
+    rotate_user_agent: bool,
+) -> &'static BrowserProfile {
+    if has_session_cookies {
+        &DEFAULT_PROFILE
+    } else {
+        profile_for_url(url, rotate_user_agent)
+    }
+}
+
+/// Add `profile`'s identity headers, the merged `Cookie` header and the override
+/// headers (which replace any of the same name) to `request`
+fn with_identity(
+    request: reqwest::RequestBuilder,
+    profile: &BrowserProfile,
+    cookie_header: Option<&str>,
+    overrides: Option<&DomainRequestOverrides>,
+) -> reqwest::RequestBuilder {
+    let mut request = request.headers(profile.headers());
+    if let Some(cookie_header) = merge_cookie_header(cookie_header, overrides) {
+        request = request.header("Cookie", cookie_header);
+    }
+    if let Some(overrides) = overrides {
+        request = request.headers(override_header_map(overrides));
+    }
+    request
+}
+
+/// Join verified session cookies and override cookies into one `Cookie` header value
+fn merge_cookie_header(
+    session_cookies: Option<&str>,
+    overrides: Option<&DomainRequestOverrides>,
+) -> Option<String> {
+    let override_cookies = overrides
+        .into_iter()
+        .flat_map(|o| o.cookies.iter())
+        .map(|(name, value)| format!("{}={}", name, value));
+    let header = session_cookies
+        .map(str::to_string)
+        .into_iter()
+        .chain(override_cookies)
+        .collect::<Vec<_>>()
+        .join("; ");
+    (!header.is_empty()).then_some(header)
+}
+
+/// Build the override headers, skipping any that are no longer valid
+/// (they are validated when saved, but the stored JSON may have been edited)
+fn override_header_map(overrides: &DomainRequestOverrides) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &overrides.headers {
+        match (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            (Ok(name), Ok(value)) => {
+                headers.insert(name, value);
+            }
+            _ => log::warn!("Ignoring invalid override header '{}'", name),
+        }
+    }
+    headers
+}
+
+/// Format stored cookies JSON as a `Cookie` header value (`name=value; ...`)
+fn cookie_header_from_json(cookies_json: &str) -> Option<String> {
+    let cookies: Vec<StoredCookie> = match serde_json::from_str(cookies_json) {
+        Ok(cookies) => cookies,
+        Err(e) => {
+            log::warn!("Ignoring unreadable verified session cookies: {}", e);
+            return None;
+        }
+    };
+
+    let header = cookies
+        .iter()
+        .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+        .collect::<Vec<_>>()
+        .join("; ");
+    (!header.is_empty()).then_some(header)
+}
+
+/// Whether the headless browser should use the domain's persistent profile.
+///
+/// A domain with a stored verified session always reuses its profile, since that
+/// is where the cookies from the solved challenge live.
+pub(crate) async fn should_reuse_headless_profile(
+    conn: &DatabaseConnection,
+    domain: &str,
+    headless_profile_reuse: bool,
+) -> Result<bool, AppError> {
+    if headless_profile_reuse {
+        return Ok(true);
+    }
+    Ok(VerifiedSessionRepository::find_by_domain(conn, domain)
+        .await?
+        .is_some())
+}
+
 /// Fetch page HTML using headless browser
 ///
 /// Runs the blocking headless browser operations on a dedicated thread pool
 /// to avoid blocking the async runtime.
-async fn fetch_with_headless(url: &str) -> Result<String, AppError> {
+async fn fetch_with_headless(
+    url: &str,
+    headless_profile_reuse: bool,
+    conn: &DatabaseConnection,
+    page_timeout_secs: u64,
+    browser_path: Option<PathBuf>,
+) -> Result<String, AppError> {
+    let domain = ManualVerificationService::extract_domain(url)?;
+    let reuse_profile =
+        should_reuse_headless_profile(conn, &domain, headless_profile_reuse).await?;
+    let wait_for_selector =
+        DomainSettingService::get_headless_wait_selector_for_host(conn, &domain).await?;
+
     let url_owned = url.to_string();
     let task = tokio::task::spawn_blocking(move || {
-        let mut headless = HeadlessService::new();
+        let mut headless = if reuse_profile {
+            HeadlessService::for_domain(&domain)
+        } else {
+            HeadlessService::with_temporary_profile()
+        }
+        .with_page_timeout(Duration::from_secs(page_timeout_secs))
+        .with_browser_path(browser_path);
+        if let Some(selector) = wait_for_selector {
+            headless = headless.with_wait_for_selector(selector);
+        }
         headless.fetch_page(&url_owned)
     });
 
-    // 30s margin above PAGE_TIMEOUT_SECS so the outer timeout outlasts the inner page load timeout
-    match tokio::time::timeout(
-        Duration::from_secs(HeadlessService::PAGE_TIMEOUT_SECS + 30),
-        task,
-    )
-    .await
-    {
+    // 30s margin above the page timeout so the outer timeout outlasts the inner page load timeout
+    match tokio::time::timeout(Duration::from_secs(page_timeout_secs + 30), task).await {
         Ok(join_result) => {
             join_result.map_err(|e| AppError::Internal(format!("Headless task failed: {}", e)))?
         }
@@ -127,27 +754,29 @@ async fn fetch_with_headless(url: &str) -> Result<String, AppError> {
 
 /// Fetch page with manual verification workflow
 ///
-/// Checks for cached verified session first, then launches visible browser if needed.
+/// Launches a visible browser for the user to solve the challenge and stores the
+/// resulting cookies as the domain's verified session.
 async fn fetch_with_manual_verification(
     url: &str,
     conn: &DatabaseConnection,
     session_cache_duration_days: i32,
+    browser_path: Option<PathBuf>,
 ) -> Result<String, AppError> {
     let domain = ManualVerificationService::extract_domain(url)?;
 
-    // Check if we have a cached session
-    if let Some(_session) = VerifiedSessionRepository::find_by_domain(conn, &domain).await? {
-        log::info!("Using cached verified session for {}", domain);
-
-        // TODO: Use the cached session cookies to fetch the page
-        // This would require adding cookie support to the HTTP client
-        // For Phase 3 MVP, we'll just re-verify each time (noted in plan as limitation)
+    // Stored cookies were already replayed on the HTTP request; reaching this
+    // point means they no longer pass the challenge, so replace the session
+    if VerifiedSessionRepository::delete_by_domain(conn, &domain).await? {
+        log::info!("Verified session for {} no longer accepted", domain);
     }
 
-    // Launch visible browser for manual verification
+    // Launch visible browser for manual verification, in the domain's profile so
+    // later headless fetches pick up the verified cookies
     let url_owned = url.to_string();
+    let profile_domain = domain.clone();
     let (html, cookies_json) = tokio::task::spawn_blocking(move || {
-        let verification_service = ManualVerificationService::new();
+        let verification_service =
+            ManualVerificationService::for_domain(&profile_domain).with_browser_path(browser_path);
         verification_service.launch_visible_browser(&url_owned)
     })
     .await
@@ -168,32 +797,210 @@ async fn fetch_with_manual_verification(
     Ok(html)
 }
 
-/// Fetch a page's HTML content using HTTP
-async fn fetch_page(url: &str) -> Result<String, FetchPageError> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(TIMEOUT_SECS))
+/// Browser-like headers sent with every page request, apart from the
+/// User-Agent and client hints (see [`BrowserProfile::headers`])
+fn default_page_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in [
+        ("accept", ACCEPT_HEADER),
+        ("accept-language", "en-US,en;q=0.9"),
+        ("accept-encoding", "gzip, deflate, br"),
+        ("cache-control", "no-cache"),
+        ("pragma", "no-cache"),
+        ("sec-fetch-dest", "document"),
+        ("sec-fetch-mode", "navigate"),
+        ("sec-fetch-site", "none"),
+        ("sec-fetch-user", "?1"),
+        ("upgrade-insecure-requests", "1"),
+    ] {
+        headers.insert(name, HeaderValue::from_static(value));
+    }
+    headers
+}
+
+/// Client builder with the default page headers. An `uncompressed` client asks
+/// for an uncompressed body and ignores any `Content-Encoding` it gets back.
+fn page_client_builder(uncompressed: bool) -> reqwest::ClientBuilder {
+    let mut headers = default_page_headers();
+    let builder = reqwest::Client::builder().dns_resolver(Arc::new(PageResolver));
+    if !uncompressed {
+        return builder.default_headers(headers);
+    }
+    headers.insert("accept-encoding", HeaderValue::from_static("identity"));
+    builder
+        .default_headers(headers)
+        .no_gzip()
+        .no_brotli()
+        .no_deflate()
+}
+
+/// Redirect policy following at most `max_redirects` redirects. A redirect back
+/// to a URL already requested fails straight away as a loop rather than running
+/// up to the limit.
+///
+/// With `insecure_hosts`, redirects are only followed to those hosts.
+fn redirect_policy(
+    max_redirects: usize,
+    insecure_hosts: Option<Vec<String>>,
+) -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().contains(attempt.url()) {
+            let url = attempt.url().to_string();
+            return attempt.error(RedirectError::Loop(url));
+        }
+        // `previous` includes the original request, so it counts the redirects so far
+        if attempt.previous().len() > max_redirects {
+            return attempt.error(RedirectError::TooMany(max_redirects));
+        }
+        match &insecure_hosts {
+            Some(hosts) if !is_insecure_host(attempt.url().as_str(), hosts) => {
+                let message = format!("refusing insecure redirect to {}", attempt.url());
+                attempt.error(message)
+            }
+            _ => attempt.follow(),
+        }
+    })
+}
+
+/// HTTP client shared by all fetches with the same redirect limit, so
+/// connection pools and TLS sessions survive across a bulk check. Timeouts are
+/// set per request.
+///
+/// An `uncompressed` client is used to retry a page whose compressed response
+/// couldn't be decoded. `reqwest::Client` is a handle to a shared pool, so the
+/// returned clone uses the same connections. Clients live for the rest of the
+/// process; there are only as many as there are valid redirect limits.
+fn shared_client(max_redirects: usize, uncompressed: bool) -> reqwest::Result<reqwest::Client> {
+    static CLIENTS: OnceLock<Mutex<HashMap<(usize, bool), reqwest::Client>>> = OnceLock::new();
+    let mut clients = CLIENTS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(client) = clients.get(&(max_redirects, uncompressed)) {
+        return Ok(client.clone());
+    }
+    let client = page_client_builder(uncompressed)
+        .redirect(redirect_policy(max_redirects, None))
+        .build()?;
+    clients.insert((max_redirects, uncompressed), client.clone());
+    Ok(client)
+}
+
+/// Client that accepts invalid TLS certificates, for a page on one of
+/// `insecure_hosts`.
+///
+/// Built per request rather than shared, so it can never serve another host.
+/// Redirects are only followed to other listed hosts, so an unverified
+/// connection never reaches a host the user didn't list.
+fn insecure_client(
+    insecure_hosts: &[String],
+    max_redirects: usize,
+    uncompressed: bool,
+) -> reqwest::Result<reqwest::Client> {
+    page_client_builder(uncompressed)
+        .danger_accept_invalid_certs(true)
+        .redirect(redirect_policy(
+            max_redirects,
+            Some(insecure_hosts.to_vec()),
+        ))
         .build()
-        .map_err(|e| FetchPageError::Http(e.to_string()))?;
-
-    let response = client
-        .get(url)
-        .header("User-Agent", USER_AGENT)
-        .header("Accept", ACCEPT_HEADER)
-        .header("Accept-Language", "en-US,en;q=0.9")
-        .header("Accept-Encoding", "gzip, deflate, br")
-        .header("Cache-Control", "no-cache")
-        .header("Pragma", "no-cache")
-        .header("Sec-Ch-Ua", SEC_CH_UA)
-        .header("Sec-Ch-Ua-Mobile", "?0")
-        .header("Sec-Ch-Ua-Platform", "\"Windows\"")
-        .header("Sec-Fetch-Dest", "document")
-        .header("Sec-Fetch-Mode", "navigate")
-        .header("Sec-Fetch-Site", "none")
-        .header("Sec-Fetch-User", "?1")
-        .header("Upgrade-Insecure-Requests", "1")
+}
+
+/// Client for a page request: the shared (or uncompressed) client, or for a host
+/// in `insecure_hosts`, one that skips certificate checks
+fn page_client(
+    url: &str,
+    insecure_hosts: &[String],
+    max_redirects: usize,
+    uncompressed: bool,
+) -> reqwest::Result<reqwest::Client> {
+    if is_insecure_host(url, insecure_hosts) {
+        log::warn!(
+            "TLS certificate verification is DISABLED for {} (listed in insecure hosts)",
+            url
+        );
+        return insecure_client(insecure_hosts, max_redirects, uncompressed);
+    }
+    shared_client(max_redirects, uncompressed)
+}
+
+/// Client for a request to `url` outside a page fetch (robots.txt, store APIs),
+/// chosen like a page's: certificate checks are only skipped for the hosts in
+/// `insecure_hosts`, and at most `max_redirects` redirects are followed. Set a
+/// timeout on each request.
+pub(crate) fn client_for_url(
+    url: &str,
+    options: &FetchOptions,
+) -> Result<reqwest::Client, AppError> {
+    page_client(url, &options.insecure_hosts, options.max_redirects, false)
+        .map_err(|e| AppError::External(e.to_string()))
+}
+
+/// Fetch a page's HTML content using HTTP as `profile`, optionally sending a `Cookie` header
+///
+/// Domain overrides add their cookies to the `Cookie` header and replace default
+/// headers of the same name. Returns the HTML with the final URL after redirects.
+///
+/// A body that can't be decompressed or decoded (e.g. a wrong `Content-Encoding`,
+/// or gzip sent without one) is fetched once more without compression.
+///
+/// TLS certificates aren't verified for hosts in `insecure_hosts`. At most
+/// `max_redirects` redirects are followed, and a redirect loop fails right away.
+async fn fetch_page(
+    url: &str,
+    profile: &BrowserProfile,
+    cookie_header: Option<&str>,
+    overrides: Option<&DomainRequestOverrides>,
+    insecure_hosts: &[String],
+    max_redirects: usize,
+    timeout: Duration,
+) -> Result<(String, String), FetchPageError> {
+    match request_page(
+        &page_client(url, insecure_hosts, max_redirects, false)
+            .map_err(|e| FetchPageError::Http(e.to_string()))?,
+        url,
+        profile,
+        cookie_header,
+        overrides,
+        timeout,
+    )
+    .await
+    {
+        Err(FetchPageError::Decode(reason)) => {
+            log::warn!(
+                "Could not decode response from {} ({}); retrying without compression",
+                url,
+                reason
+            );
+            request_page(
+                &page_client(url, insecure_hosts, max_redirects, true)
+                    .map_err(|e| FetchPageError::Http(e.to_string()))?,
+                url,
+                profile,
+                cookie_header,
+                overrides,
+                timeout,
+            )
+            .await
+        }
+        result => result,
+    }
+}
+
+/// Send a single page request with `client` and decode the body
+async fn request_page(
+    client: &reqwest::Client,
+    url: &str,
+    profile: &BrowserProfile,
+    cookie_header: Option<&str>,
+    overrides: Option<&DomainRequestOverrides>,
+    timeout: Duration,
+) -> Result<(String, String), FetchPageError> {
+    let request = client.get(url).timeout(timeout);
+    let response = with_identity(request, profile, cookie_header, overrides)
         .send()
         .await
-        .map_err(|e| FetchPageError::Http(e.to_string()))?;
+        .map_err(|e| FetchPageError::from_request_error(&e))?;
 
     if !response.status().is_success() {
         return Err(FetchPageError::HttpStatus {
@@ -202,9 +1009,1160 @@ async fn fetch_page(url: &str) -> Result<String, FetchPageError> {
         });
     }
 
-    let html = response
-        .text()
+    let final_url = response.url().to_string();
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response.bytes().await.map_err(|e| {
+        if e.is_decode() {
+            FetchPageError::Decode(e.to_string())
+        } else {
+            FetchPageError::from_request_error(&e)
+        }
+    })?;
+    let html = decode_body(&body, content_type.as_deref())?;
+    Ok((html, final_url))
+}
+
+/// Decode a response body as text in the charset named by `Content-Type`
+/// (UTF-8 when none is given).
+///
+/// Unlike `reqwest::Response::text`, undecodable bytes are an error rather than
+/// replacement characters, so a compressed or binary body isn't handed to the
+/// parsers as garbage.
+fn decode_body(body: &[u8], content_type: Option<&str>) -> Result<String, FetchPageError> {
+    if body.starts_with(&GZIP_MAGIC) {
+        return Err(FetchPageError::Decode(
+            "gzip data without a matching Content-Encoding".to_string(),
+        ));
+    }
+
+    let encoding = content_type
+        .and_then(|content_type| {
+            content_type.split(';').find_map(|param| {
+                let (name, value) = param.split_once('=')?;
+                name.trim()
+                    .eq_ignore_ascii_case("charset")
+                    .then(|| value.trim().trim_matches('"'))
+            })
+        })
+        .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+
+    let (text, had_errors) = encoding.decode_with_bom_removal(body);
+    if had_errors {
+        return Err(FetchPageError::Decode(format!(
+            "body is not valid {}",
+            encoding.name()
+        )));
+    }
+    Ok(text.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::availability_check::CheckErrorKind;
+    use crate::services::scraper::DEFAULT_MAX_JSON_LD_BLOCK_BYTES;
+    use crate::test_utils::{MockRequest, MockResponse, MockServer};
+    use product_stalker_core::entities::verified_session::Entity as VerifiedSessionEntity;
+    use product_stalker_core::test_utils::setup_app_settings_db;
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Schema};
+
+    /// Verified sessions plus app_settings (for domain request overrides)
+    async fn setup_verified_session_db() -> DatabaseConnection {
+        let conn = setup_app_settings_db().await;
+        let stmt =
+            Schema::new(DatabaseBackend::Sqlite).create_table_from_entity(VerifiedSessionEntity);
+        conn.execute(conn.get_database_backend().build(&stmt))
+            .await
+            .unwrap();
+        conn
+    }
+
+    /// Plain HTTP only: no headless or manual verification fallback
+    fn test_options() -> FetchOptions {
+        FetchOptions {
+            enable_headless: false,
+            allow_manual_verification: false,
+            headless_profile_reuse: true,
+            session_cache_duration_days: 14,
+            timeouts: FetchTimeouts::default(),
+            force_headless_domains: Vec::new(),
+            respect_robots_txt: false,
+            rotate_user_agent: false,
+            product_group_strategy: ProductGroupStrategy::First,
+            headless_browser_path: None,
+            insecure_hosts: Vec::new(),
+            max_redirects: 10,
+            max_json_ld_block_bytes: DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+        }
+    }
+
+    const COOKIES_JSON: &str = r#"[
+        {"name": "session", "value": "abc123", "domain": "127.0.0.1", "path": "/", "httpOnly": true},
+        {"name": "cf_clearance", "value": "xyz", "domain": "127.0.0.1", "path": "/", "secure": true}
+    ]"#;
+
+    async fn store_session(conn: &DatabaseConnection, domain: &str, days: i32) {
+        VerifiedSessionRepository::create(
+            conn,
+            domain.to_string(),
+            COOKIES_JSON.to_string(),
+            USER_AGENT.to_string(),
+            days,
+        )
         .await
-        .map_err(|e| FetchPageError::Http(e.to_string()))?;
-    Ok(html)
+        .unwrap();
+    }
+
+    const PLAIN_PAGE: &str = "<html><body>Widget</body></html>";
+
+    /// Serve a small HTML page to every request
+    async fn mock_page_server() -> MockServer {
+        MockServer::serving(MockResponse::html(PLAIN_PAGE)).await
+    }
+
+    /// The one request `server` received
+    fn only_request(server: &MockServer) -> MockRequest {
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        requests[0].clone()
+    }
+
+    async fn store_overrides(conn: &DatabaseConnection, host: &str) {
+        let overrides = DomainRequestOverrides {
+            headers: [("User-Agent".to_string(), "MemberBot/1.0".to_string())].into(),
+            cookies: [("member".to_string(), "gold".to_string())].into(),
+        };
+        DomainSettingService::set_request_overrides(conn, host, overrides)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_cookie_header_from_json() {
+        assert_eq!(
+            cookie_header_from_json(COOKIES_JSON),
+            Some("session=abc123; cf_clearance=xyz".to_string())
+        );
+        assert_eq!(cookie_header_from_json("[]"), None);
+        assert_eq!(cookie_header_from_json("not json"), None);
+    }
+
+    #[tokio::test]
+    async fn test_load_session_cookie_header_for_host() {
+        let conn = setup_verified_session_db().await;
+        store_session(&conn, "shop.example.com", 14).await;
+
+        let header = load_session_cookie_header(&conn, "https://shop.example.com/item/1", 14)
+            .await
+            .unwrap();
+        assert_eq!(header.as_deref(), Some("session=abc123; cf_clearance=xyz"));
+
+        let other = load_session_cookie_header(&conn, "https://other.com/item/1", 14)
+            .await
+            .unwrap();
+        assert_eq!(other, None);
+    }
+
+    #[tokio::test]
+    async fn test_load_session_cookie_header_ignores_expired_session() {
+        let conn = setup_verified_session_db().await;
+        store_session(&conn, "example.com", -1).await;
+
+        let header = load_session_cookie_header(&conn, "https://example.com/item", 14)
+            .await
+            .unwrap();
+        assert_eq!(header, None);
+    }
+
+    #[tokio::test]
+    async fn test_load_session_cookie_header_respects_cache_duration() {
+        let conn = setup_verified_session_db().await;
+        store_session(&conn, "example.com", 14).await;
+
+        // Setting lowered to zero days since the session was stored
+        let header = load_session_cookie_header(&conn, "https://example.com/item", 0)
+            .await
+            .unwrap();
+        assert_eq!(header, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_replays_verified_session_cookies() {
+        let conn = setup_verified_session_db().await;
+        store_session(&conn, "127.0.0.1", 14).await;
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+
+        let fetched = fetch_html_with_fallback(&url, &conn, &test_options())
+            .await
+            .unwrap();
+        assert!(fetched.html.contains("Widget"));
+
+        let request = only_request(&server);
+        assert_eq!(
+            request.header("cookie"),
+            Some("session=abc123; cf_clearance=xyz")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_without_session_sends_no_cookies() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+
+        fetch_html_with_fallback(&url, &conn, &test_options())
+            .await
+            .unwrap();
+
+        let request = only_request(&server);
+        assert_eq!(request.header("cookie"), None);
+    }
+
+    #[tokio::test]
+    async fn test_should_reuse_headless_profile_when_enabled() {
+        let conn = setup_verified_session_db().await;
+
+        assert!(should_reuse_headless_profile(&conn, "example.com", true)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_should_not_reuse_headless_profile_when_disabled() {
+        let conn = setup_verified_session_db().await;
+
+        assert!(!should_reuse_headless_profile(&conn, "example.com", false)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verified_session_forces_headless_profile_reuse() {
+        let conn = setup_verified_session_db().await;
+        VerifiedSessionRepository::create(
+            &conn,
+            "example.com".to_string(),
+            "[]".to_string(),
+            USER_AGENT.to_string(),
+            14,
+        )
+        .await
+        .unwrap();
+
+        assert!(should_reuse_headless_profile(&conn, "example.com", false)
+            .await
+            .unwrap());
+        assert!(!should_reuse_headless_profile(&conn, "other.com", false)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_applies_http_timeout() {
+        let conn = setup_verified_session_db().await;
+        // Accept the connection but don't respond in time
+        let server =
+            MockServer::serving(MockResponse::html(PLAIN_PAGE).delayed(Duration::from_secs(10)))
+                .await;
+        let url = server.url("/slow");
+
+        let options = FetchOptions {
+            timeouts: FetchTimeouts {
+                http_secs: 1,
+                ..Default::default()
+            },
+            ..test_options()
+        };
+        let started = std::time::Instant::now();
+        let result = fetch_html_with_fallback(&url, &conn, &options).await;
+
+        assert!(matches!(result, Err(AppError::External(_))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_is_force_headless_host() {
+        let domains = vec!["example.com".to_string(), "shop.test".to_string()];
+
+        assert!(is_force_headless_host("https://example.com/item", &domains));
+        assert!(is_force_headless_host(
+            "https://www.Example.com/item",
+            &domains
+        ));
+        assert!(is_force_headless_host("http://a.b.shop.test/", &domains));
+        assert!(!is_force_headless_host(
+            "https://notexample.com/item",
+            &domains
+        ));
+        assert!(!is_force_headless_host(
+            "https://example.com.au/item",
+            &domains
+        ));
+        assert!(!is_force_headless_host("not a url", &domains));
+        assert!(!is_force_headless_host("https://example.com/item", &[]));
+    }
+
+    #[tokio::test]
+    async fn test_force_headless_host_skips_http_request() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+
+        let options = FetchOptions {
+            force_headless_domains: vec!["127.0.0.1".to_string()],
+            ..test_options()
+        };
+        let result = fetch_html_with_fallback(&url, &conn, &options).await;
+
+        // Headless is disabled, so the force-listed host fails as bot protection
+        match result {
+            Err(AppError::External(msg)) => assert_eq!(msg, BOT_PROTECTION_MESSAGE),
+            _ => panic!("expected bot protection error"),
+        }
+        assert_eq!(
+            server.connections(),
+            0,
+            "HTTP request should not be attempted"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_host_still_uses_http() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+
+        let options = FetchOptions {
+            force_headless_domains: vec!["example.com".to_string()],
+            ..test_options()
+        };
+        let fetched = fetch_html_with_fallback(&url, &conn, &options)
+            .await
+            .unwrap();
+
+        assert!(fetched.html.contains("Widget"));
+        assert!(!fetched.bot_protection_detected);
+        assert_eq!(fetched.final_url.as_deref(), Some(url.as_str()));
+    }
+
+    /// Serve `robots_txt` at /robots.txt and a product page elsewhere, recording
+    /// each request's path
+    async fn mock_store_with_robots(robots_txt: &'static str) -> MockServer {
+        MockServer::start(move |request| match request.path() {
+            "/robots.txt" => MockResponse::ok(robots_txt),
+            _ => MockResponse::ok(PLAIN_PAGE),
+        })
+        .await
+    }
+
+    fn robots_options() -> FetchOptions {
+        FetchOptions {
+            timeouts: FetchTimeouts {
+                min_host_interval_ms: 0,
+                ..Default::default()
+            },
+            respect_robots_txt: true,
+            rotate_user_agent: false,
+            product_group_strategy: ProductGroupStrategy::First,
+            headless_browser_path: None,
+            ..test_options()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_robots_disallowed_url_is_skipped() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_store_with_robots("User-agent: *\nDisallow: /private\n").await;
+
+        let result =
+            fetch_html_with_fallback(&server.url("/private/item"), &conn, &robots_options()).await;
+
+        match result {
+            Err(AppError::Validation(msg)) => assert_eq!(msg, ROBOTS_DISALLOWED_MESSAGE),
+            _ => panic!("expected robots.txt validation error"),
+        }
+        assert_eq!(server.paths(), vec!["/robots.txt".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_robots_allowed_url_is_fetched() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_store_with_robots("User-agent: *\nDisallow: /private\n").await;
+
+        let fetched = fetch_html_with_fallback(&server.url("/product"), &conn, &robots_options())
+            .await
+            .unwrap();
+
+        assert!(fetched.html.contains("Widget"));
+        assert_eq!(
+            server.paths(),
+            vec!["/robots.txt".to_string(), "/product".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_robots_ignored_when_not_respected() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_store_with_robots("User-agent: *\nDisallow: /\n").await;
+        let options = FetchOptions {
+            respect_robots_txt: false,
+            rotate_user_agent: false,
+            product_group_strategy: ProductGroupStrategy::First,
+            headless_browser_path: None,
+            ..robots_options()
+        };
+
+        let fetched = fetch_html_with_fallback(&server.url("/product"), &conn, &options)
+            .await
+            .unwrap();
+
+        assert!(fetched.html.contains("Widget"));
+        assert_eq!(server.paths(), vec!["/product".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_robots_crawl_delay_spaces_requests() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_store_with_robots("User-agent: *\nCrawl-delay: 0.3\n").await;
+        let url = server.url("/product");
+        let started = std::time::Instant::now();
+
+        fetch_html_with_fallback(&url, &conn, &robots_options())
+            .await
+            .unwrap();
+        fetch_html_with_fallback(&url, &conn, &robots_options())
+            .await
+            .unwrap();
+
+        assert!(started.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_merge_cookie_header() {
+        let overrides = DomainRequestOverrides {
+            cookies: [("member".to_string(), "gold".to_string())].into(),
+            ..Default::default()
+        };
+
+        assert_eq!(merge_cookie_header(None, None), None);
+        assert_eq!(
+            merge_cookie_header(Some("session=abc"), None).as_deref(),
+            Some("session=abc")
+        );
+        assert_eq!(
+            merge_cookie_header(None, Some(&overrides)).as_deref(),
+            Some("member=gold")
+        );
+        assert_eq!(
+            merge_cookie_header(Some("session=abc"), Some(&overrides)).as_deref(),
+            Some("session=abc; member=gold")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_applies_overrides_for_matching_host() {
+        let conn = setup_verified_session_db().await;
+        store_session(&conn, "127.0.0.1", 14).await;
+        store_overrides(&conn, "127.0.0.1").await;
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+
+        fetch_html_with_fallback(&url, &conn, &test_options())
+            .await
+            .unwrap();
+
+        let request = only_request(&server);
+        assert_eq!(request.header_values("user-agent"), vec!["MemberBot/1.0"]);
+        assert_eq!(
+            request.header("cookie"),
+            Some("session=abc123; cf_clearance=xyz; member=gold")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_ignores_overrides_for_other_hosts() {
+        let conn = setup_verified_session_db().await;
+        store_overrides(&conn, "shop.example.com").await;
+        store_overrides(&conn, "localhost").await;
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+
+        fetch_html_with_fallback(&url, &conn, &test_options())
+            .await
+            .unwrap();
+
+        let request = only_request(&server);
+        assert_eq!(request.header_values("user-agent"), vec![USER_AGENT]);
+        assert_eq!(request.header("cookie"), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rotates_user_agent_with_matching_client_hints() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+        let options = FetchOptions {
+            rotate_user_agent: true,
+            ..test_options()
+        };
+
+        fetch_html_with_fallback(&url, &conn, &options)
+            .await
+            .unwrap();
+
+        let request = only_request(&server);
+        let expected = profile_for_url(&url, true);
+        assert_eq!(
+            request.header_values("user-agent"),
+            vec![expected.user_agent]
+        );
+        let hints = expected.client_hints;
+        assert_eq!(
+            request.header_values("sec-ch-ua"),
+            hints
+                .map(|h| h.brands.to_string())
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            request.header_values("sec-ch-ua-platform"),
+            hints
+                .map(|h| h.platform.to_string())
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_verified_session_keeps_default_user_agent() {
+        let conn = setup_verified_session_db().await;
+        store_session(&conn, "127.0.0.1", 14).await;
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+        let options = FetchOptions {
+            rotate_user_agent: true,
+            ..test_options()
+        };
+
+        fetch_html_with_fallback(&url, &conn, &options)
+            .await
+            .unwrap();
+
+        let request = only_request(&server);
+        assert_eq!(request.header_values("user-agent"), vec![USER_AGENT]);
+        assert!(request.header("cookie").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_mislabeled_gzip_retries_without_compression() {
+        let server =
+            MockServer::serving(MockResponse::html(PLAIN_PAGE).header("content-encoding", "gzip"))
+                .await;
+        let url = server.url("/product");
+
+        let (html, _) = fetch_page(
+            &url,
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            10,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(html, PLAIN_PAGE);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].header("accept-encoding"),
+            Some("gzip, deflate, br")
+        );
+        assert_eq!(requests[1].header("accept-encoding"), Some("identity"));
+    }
+
+    #[tokio::test]
+    async fn test_undeclared_gzip_retries_without_compression() {
+        let server = MockServer::start(|request| {
+            if request.header("accept-encoding") == Some("identity") {
+                MockResponse::html(PLAIN_PAGE)
+            } else {
+                MockResponse::html(vec![0x1f, 0x8b, 0x08, 0x00, 0xde, 0xad, 0xbe, 0xef])
+            }
+        })
+        .await;
+        let url = server.url("/product");
+
+        let (html, _) = fetch_page(
+            &url,
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            10,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(html, PLAIN_PAGE);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    /// URL on localhost whose port nothing is listening on
+    fn refused_port_url() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/product", listener.local_addr().unwrap());
+        drop(listener);
+        url
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_host_is_unreachable() {
+        // `.invalid` is reserved and never resolves
+        let result = fetch_page(
+            "http://product-stalker-test.invalid/product",
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            10,
+            Duration::from_secs(5),
+        )
+        .await;
+
+        match result {
+            Err(FetchPageError::Unreachable(msg)) => {
+                assert!(msg.contains("dns error"), "{}", msg);
+                assert!(msg.contains("product-stalker-test.invalid"), "{}", msg);
+            }
+            other => panic!("expected Unreachable, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refused_connection_is_unreachable() {
+        let result = fetch_page(
+            &refused_port_url(),
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            10,
+            Duration::from_secs(5),
+        )
+        .await;
+
+        assert!(
+            matches!(result, Err(FetchPageError::Unreachable(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_silent_server_times_out() {
+        // Accept the connection but don't answer in time
+        let server =
+            MockServer::serving(MockResponse::html(PLAIN_PAGE).delayed(Duration::from_secs(5)))
+                .await;
+        let url = server.url("/product");
+
+        let result = fetch_page(
+            &url,
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            10,
+            Duration::from_millis(200),
+        )
+        .await;
+
+        assert!(
+            matches!(result, Err(FetchPageError::Timeout(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_site_reports_unreachable_error_kind() {
+        let conn = setup_verified_session_db().await;
+
+        let error =
+            match fetch_html_with_fallback(&refused_port_url(), &conn, &test_options()).await {
+                Err(err) => err,
+                Ok(_) => panic!("expected unreachable error"),
+            };
+
+        assert!(error.to_string().contains(UNREACHABLE_ERROR_PREFIX));
+        assert_eq!(
+            CheckErrorKind::from_error(&error),
+            CheckErrorKind::Unreachable
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undecodable_body_fails_with_clear_error() {
+        let conn = setup_verified_session_db().await;
+        let server = MockServer::serving(
+            MockResponse::ok(vec![0x3c, 0x68, 0x74, 0xff, 0xfe, 0x00, 0xc3])
+                .header("content-type", "text/html; charset=utf-8"),
+        )
+        .await;
+        let url = server.url("/product");
+
+        let result = fetch_html_with_fallback(&url, &conn, &test_options()).await;
+
+        let err = match result {
+            Err(err) => err,
+            Ok(_) => panic!("expected undecodable body error"),
+        };
+        assert!(err.to_string().contains(UNDECODABLE_BODY_MESSAGE));
+        assert_eq!(
+            crate::entities::availability_check::CheckErrorKind::from_error(&err),
+            crate::entities::availability_check::CheckErrorKind::Scraping
+        );
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_decode_body_uses_declared_charset() {
+        let body = b"<p>Caf\xe9</p>";
+        assert_eq!(
+            decode_body(body, Some("text/html; charset=ISO-8859-1")).unwrap(),
+            "<p>Café</p>"
+        );
+        assert_eq!(
+            decode_body(b"<p>ok</p>", Some("text/html; charset=\"utf-8\"")).unwrap(),
+            "<p>ok</p>"
+        );
+    }
+
+    #[test]
+    fn test_decode_body_rejects_invalid_utf8() {
+        assert!(matches!(
+            decode_body(b"<p>Caf\xe9</p>", Some("text/html")),
+            Err(FetchPageError::Decode(_))
+        ));
+        assert!(matches!(
+            decode_body(b"<p>Caf\xe9</p>", None),
+            Err(FetchPageError::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_body_strips_utf8_bom() {
+        assert_eq!(
+            decode_body(b"\xef\xbb\xbf<html></html>", None).unwrap(),
+            "<html></html>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shared_client_is_reused() {
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+
+        for client in [
+            shared_client(10, false).unwrap(),
+            shared_client(10, false).unwrap(),
+        ] {
+            client.get(&url).send().await.unwrap();
+        }
+        assert_eq!(server.connections(), 1);
+
+        // Another redirect limit is another client, with its own connections
+        shared_client(3, false)
+            .unwrap()
+            .get(&url)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(server.connections(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_fetches_reuse_connection() {
+        // Keep-alive server answering every request on a connection
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+
+        for _ in 0..2 {
+            let (html, _) = fetch_page(
+                &url,
+                &DEFAULT_PROFILE,
+                None,
+                None,
+                &[],
+                10,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+            assert!(html.contains("Widget"));
+        }
+
+        assert_eq!(server.connections(), 1);
+    }
+
+    #[test]
+    fn test_canonical_redirect_url_accepts_normalizing_redirects() {
+        for (requested, final_url, expected) in [
+            (
+                "http://store.com/p",
+                "https://www.store.com/p",
+                "https://www.store.com/p",
+            ),
+            (
+                "https://www.store.com/p?variant=1",
+                "https://store.com/p?variant=1",
+                "https://store.com/p?variant=1",
+            ),
+            (
+                "https://store.com/p",
+                "https://store.com/p/",
+                "https://store.com/p/",
+            ),
+            (
+                "http://store.com/p#reviews",
+                "https://store.com/p",
+                "https://store.com/p#reviews",
+            ),
+        ] {
+            assert_eq!(
+                canonical_redirect_url(requested, final_url).as_deref(),
+                Some(expected),
+                "{} -> {}",
+                requested,
+                final_url
+            );
+        }
+    }
+
+    #[test]
+    fn test_canonical_redirect_url_rejects_other_redirects() {
+        for (requested, final_url) in [
+            // No redirect
+            ("https://store.com/p", "https://store.com/p"),
+            // Downgrade to http
+            ("https://store.com/p", "http://store.com/p"),
+            // Another page, region or site
+            ("https://store.com/p", "https://store.com/q"),
+            ("https://store.com/p", "https://store.com/en-gb/p"),
+            ("https://store.com/p", "https://shop.store.com/p"),
+            ("https://store.com/p", "https://other.com/p"),
+            // Query or port changed
+            ("https://store.com/p?variant=1", "https://store.com/p"),
+            ("http://store.com:8080/p", "http://store.com/p"),
+            ("not a url", "https://store.com/p"),
+        ] {
+            assert_eq!(
+                canonical_redirect_url(requested, final_url),
+                None,
+                "{} -> {}",
+                requested,
+                final_url
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_insecure_host() {
+        let hosts = vec!["nas.local".to_string(), "192.168.1.20".to_string()];
+
+        assert!(is_insecure_host("https://nas.local/shop/item", &hosts));
+        assert!(is_insecure_host("https://NAS.local:8443/item", &hosts));
+        assert!(is_insecure_host("https://192.168.1.20/item", &hosts));
+        // Only the listed hosts themselves, never their subdomains or other hosts
+        assert!(!is_insecure_host("https://store.nas.local/item", &hosts));
+        assert!(!is_insecure_host("https://example.com/item", &hosts));
+        assert!(!is_insecure_host("not a url", &hosts));
+        assert!(!is_insecure_host("https://nas.local/item", &[]));
+    }
+
+    /// Serve `/product` as a redirect to `location(port)`, and a product page elsewhere
+    async fn mock_redirecting_store(
+        location: impl Fn(u16) -> String + Send + Sync + 'static,
+    ) -> MockServer {
+        MockServer::start(move |request| {
+            let port = request
+                .header("host")
+                .and_then(|host| host.rsplit(':').next()?.parse().ok())
+                .unwrap_or_default();
+            match request.path() {
+                "/product" => MockResponse::redirect(302, location(port)),
+                _ => MockResponse::ok(PLAIN_PAGE),
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_insecure_host_follows_redirects_within_listed_hosts() {
+        let server =
+            mock_redirecting_store(|port| format!("http://127.0.0.1:{}/final", port)).await;
+        let url = server.url("/product");
+        let insecure_hosts = vec!["127.0.0.1".to_string()];
+
+        let (html, final_url) = fetch_page(
+            &url,
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &insecure_hosts,
+            10,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+        assert!(html.contains("Widget"));
+        assert!(final_url.ends_with("/final"));
+    }
+
+    #[tokio::test]
+    async fn test_insecure_client_is_not_used_for_unlisted_redirect_target() {
+        let server =
+            mock_redirecting_store(|port| format!("http://localhost:{}/final", port)).await;
+        let url = server.url("/product");
+
+        // From a listed host, the unverified client refuses to follow to another host
+        let result = fetch_page(
+            &url,
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &["127.0.0.1".to_string()],
+            10,
+            Duration::from_secs(5),
+        )
+        .await;
+        match result {
+            Err(FetchPageError::Http(msg)) => assert!(msg.contains("redirect"), "{}", msg),
+            _ => panic!("expected the insecure redirect to be refused"),
+        }
+
+        // The verified shared client follows the same redirect as usual
+        let (html, _) = fetch_page(
+            &url,
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &["nas.local".to_string()],
+            10,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert!(html.contains("Widget"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_insecure_hosts_from_options() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_page_server().await;
+        let url = server.url("/product");
+
+        let options = FetchOptions {
+            insecure_hosts: vec!["127.0.0.1".to_string()],
+            ..test_options()
+        };
+        let fetched = fetch_html_with_fallback(&url, &conn, &options)
+            .await
+            .unwrap();
+
+        assert!(fetched.html.contains("Widget"));
+    }
+
+    /// Answer each path with a redirect to `next(path)`, or a product page when it's `None`
+    async fn mock_redirect_chain(
+        next: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> MockServer {
+        MockServer::start(move |request| match next(request.path()) {
+            Some(location) => MockResponse::redirect(302, location),
+            None => MockResponse::ok(PLAIN_PAGE),
+        })
+        .await
+    }
+
+    /// `/hop/0` redirects to `/hop/1` and so on, until `/hop/{hops}` serves the page
+    fn hops(hops: usize) -> impl Fn(&str) -> Option<String> + Send + Sync + 'static {
+        move |path| {
+            let hop: usize = path.strip_prefix("/hop/")?.parse().ok()?;
+            (hop < hops).then(|| format!("/hop/{}", hop + 1))
+        }
+    }
+
+    fn ping_pong(path: &str) -> Option<String> {
+        match path {
+            "/a" => Some("/b".to_string()),
+            _ => Some("/a".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redirect_loop_is_detected() {
+        let server = mock_redirect_chain(ping_pong).await;
+
+        let result = fetch_page(
+            &server.url("/a"),
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            10,
+            Duration::from_secs(5),
+        )
+        .await;
+
+        match result {
+            Err(FetchPageError::Redirect(RedirectError::Loop(url))) => {
+                assert!(url.ends_with("/a"), "{}", url)
+            }
+            other => panic!("expected a redirect loop, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redirect_to_itself_is_a_loop() {
+        let server = mock_redirect_chain(|_| Some("/self".to_string())).await;
+
+        let result = fetch_page(
+            &server.url("/self"),
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            10,
+            Duration::from_secs(5),
+        )
+        .await;
+
+        assert!(
+            matches!(
+                result,
+                Err(FetchPageError::Redirect(RedirectError::Loop(_)))
+            ),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_loop_is_detected_for_insecure_hosts() {
+        let server = mock_redirect_chain(ping_pong).await;
+
+        let result = fetch_page(
+            &server.url("/a"),
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &["127.0.0.1".to_string()],
+            10,
+            Duration::from_secs(5),
+        )
+        .await;
+
+        assert!(
+            matches!(
+                result,
+                Err(FetchPageError::Redirect(RedirectError::Loop(_)))
+            ),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirects_beyond_limit_fail() {
+        let server = mock_redirect_chain(hops(4)).await;
+        let url = server.url("/hop/0");
+
+        let result = fetch_page(
+            &url,
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            3,
+            Duration::from_secs(5),
+        )
+        .await;
+        assert!(
+            matches!(
+                result,
+                Err(FetchPageError::Redirect(RedirectError::TooMany(3)))
+            ),
+            "{:?}",
+            result
+        );
+
+        // Exactly at the limit is fine
+        let (html, final_url) = fetch_page(
+            &url,
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            4,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert!(html.contains("Widget"));
+        assert!(final_url.ends_with("/hop/4"));
+    }
+
+    #[tokio::test]
+    async fn test_zero_max_redirects_refuses_any_redirect() {
+        let server = mock_redirect_chain(hops(1)).await;
+
+        let result = fetch_page(
+            &server.url("/hop/0"),
+            &DEFAULT_PROFILE,
+            None,
+            None,
+            &[],
+            0,
+            Duration::from_secs(5),
+        )
+        .await;
+
+        assert!(
+            matches!(
+                result,
+                Err(FetchPageError::Redirect(RedirectError::TooMany(0)))
+            ),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_loop_fails_check_as_scraping_error() {
+        let conn = setup_verified_session_db().await;
+        let server = mock_redirect_chain(ping_pong).await;
+
+        let error = match fetch_html_with_fallback(&server.url("/a"), &conn, &test_options()).await
+        {
+            Err(err) => err,
+            Ok(_) => panic!("expected the redirect loop to fail the fetch"),
+        };
+
+        assert!(
+            matches!(&error, AppError::External(msg) if msg.starts_with("Redirect loop")),
+            "{:?}",
+            error
+        );
+        assert_eq!(CheckErrorKind::from_error(&error), CheckErrorKind::Scraping);
+    }
 }
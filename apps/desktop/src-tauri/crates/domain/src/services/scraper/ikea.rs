@@ -0,0 +1,388 @@
+//! IKEA adapter for parsing the embedded price and availability JSON.
+//!
+//! IKEA product pages hydrate their price and buy modules from JSON stored in
+//! `data-hydration-props` attributes. The buy module carries the response of
+//! IKEA's availability API, which reports stock per item number (the 8-digit
+//! article number at the end of the product URL).
+
+use scraper::{Html, Selector};
+use serde_json::Value;
+use url::Url;
+
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
+use product_stalker_core::AppError;
+
+use super::price_parser::{parse_price_to_minor_units, scalar_to_string, PriceInfo};
+use super::ScrapingResult;
+
+/// Attribute holding the JSON props of each hydrated page module.
+const HYDRATION_PROPS_SELECTOR: &str = "[data-hydration-props]";
+
+/// Length of an IKEA item (article) number
+const ITEM_NUMBER_LEN: usize = 8;
+
+/// Check if the URL is for an IKEA store (`ikea.com` or a country domain
+/// such as `ikea.cn` or `ikea.co.uk`)
+pub fn is_ikea_url(url: &str) -> bool {
+    Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .is_some_and(|host| {
+            let labels: Vec<&str> = host.split('.').collect();
+            let Some(ikea_at) = labels.iter().rposition(|label| *label == "ikea") else {
+                return false;
+            };
+            match &labels[ikea_at + 1..] {
+                [_tld] => true,
+                [second_level, _tld] => matches!(*second_level, "com" | "co"),
+                _ => false,
+            }
+        })
+}
+
+/// Extract the item number from an IKEA product URL.
+///
+/// Product paths end in `<slug>-<item number>`, e.g.
+/// `/au/en/p/billy-bookcase-white-00263850/`. Combination products prefix the
+/// number with `s` (`...-s49264501`); the prefix is dropped.
+pub fn extract_item_number(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    let segment = parsed.path_segments()?.rev().find(|s| !s.is_empty())?;
+    let last = segment.rsplit('-').next()?;
+    let digits = last.strip_prefix(['s', 'S']).unwrap_or(last);
+    is_item_number(digits).then(|| digits.to_string())
+}
+
+/// Parse product availability and price from an IKEA product page.
+///
+/// The page's hydration props are searched for the price and for entries of
+/// the availability API response, preferring the entry for the URL's item:
+/// ```json
+/// {
+///   "price": { "priceNumeral": 149.99, "currencyCode": "AUD" },
+///   "availabilities": [{
+///     "itemKey": { "itemNo": "00263850" },
+///     "buyingOption": { "homeDelivery": { "availability": {
+///       "probability": { "thisDay": { "messageType": "HIGH_IN_STOCK" } }
+///     } } }
+///   }]
+/// }
+/// ```
+///
+/// An item flagged `"onlineSellable": false` is out of stock online.
+pub fn parse_ikea_data(html: &str, url: &str) -> Result<ScrapingResult, AppError> {
+    let props = extract_hydration_props(html)?;
+    let item_number = extract_item_number(url);
+
+    let mut entries = Vec::new();
+    for value in &props {
+        collect_availabilities(value, &mut entries);
+    }
+    let entry = select_entry(&entries, item_number.as_deref());
+
+    let raw_availability = entry.and_then(online_availability);
+    let (raw_price, currency) = props
+        .iter()
+        .find_map(find_price)
+        .map_or((None, None), |(price, currency)| (Some(price), currency));
+
+    if raw_availability.is_none() && raw_price.is_none() {
+        return Err(AppError::External(
+            "No product data found in IKEA page".to_string(),
+        ));
+    }
+
+    let status = raw_availability
+        .as_deref()
+        .map(map_availability_status)
+        .unwrap_or_default();
+    let price_minor_units = raw_price
+        .as_deref()
+        .and_then(|p| parse_price_to_minor_units(p, currency.as_deref()));
+
+    Ok(ScrapingResult {
+        status,
+        raw_availability,
+        price: PriceInfo {
+            price_minor_units,
+            price_currency: price_minor_units.and(currency),
+            raw_price,
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            price_range: None,
+        },
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
+    })
+}
+
+/// An item number is exactly eight digits
+fn is_item_number(value: &str) -> bool {
+    value.len() == ITEM_NUMBER_LEN && value.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Parse every `data-hydration-props` attribute that holds valid JSON
+fn extract_hydration_props(html: &str) -> Result<Vec<Value>, AppError> {
+    let selector = Selector::parse(HYDRATION_PROPS_SELECTOR)
+        .map_err(|e| AppError::External(format!("Invalid selector: {:?}", e)))?;
+    let document = Html::parse_document(html);
+
+    let props: Vec<Value> = document
+        .select(&selector)
+        .filter_map(|el| el.value().attr("data-hydration-props"))
+        .filter_map(|json| serde_json::from_str(json).ok())
+        .collect();
+
+    if props.is_empty() {
+        return Err(AppError::External(
+            "No embedded product data found in IKEA page".to_string(),
+        ));
+    }
+    Ok(props)
+}
+
+/// Depth-first collection of availability entries (objects with an `itemKey`)
+fn collect_availabilities<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(map) => {
+            if map.contains_key("itemKey") {
+                out.push(value);
+            }
+            map.values().for_each(|v| collect_availabilities(v, out));
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_availabilities(v, out)),
+        _ => {}
+    }
+}
+
+/// The entry for `item_number`, or the first entry if none match
+fn select_entry<'a>(entries: &[&'a Value], item_number: Option<&str>) -> Option<&'a Value> {
+    let matching = item_number.and_then(|item_number| {
+        entries.iter().copied().find(|entry| {
+            entry
+                .get("itemKey")
+                .and_then(|key| key.get("itemNo"))
+                .and_then(Value::as_str)
+                == Some(item_number)
+        })
+    });
+    matching.or_else(|| entries.first().copied())
+}
+
+/// Read the home delivery stock message, or `OUT_OF_STOCK` when the item
+/// can't be bought online at all
+fn online_availability(entry: &Value) -> Option<String> {
+    if entry.get("onlineSellable").and_then(Value::as_bool) == Some(false) {
+        return Some("OUT_OF_STOCK".to_string());
+    }
+    entry
+        .get("buyingOption")?
+        .get("homeDelivery")?
+        .get("availability")?
+        .get("probability")?
+        .get("thisDay")?
+        .get("messageType")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Find the first `priceNumeral`, with the `currencyCode` beside it
+fn find_price(value: &Value) -> Option<(String, Option<String>)> {
+    match value {
+        Value::Object(map) => {
+            if let Some(price) = map.get("priceNumeral").and_then(scalar_to_string) {
+                let currency = map
+                    .get("currencyCode")
+                    .and_then(Value::as_str)
+                    .map(str::to_ascii_uppercase);
+                return Some((price, currency));
+            }
+            map.values().find_map(find_price)
+        }
+        Value::Array(items) => items.iter().find_map(find_price),
+        _ => None,
+    }
+}
+
+/// Map an availability API stock message to an AvailabilityStatus
+fn map_availability_status(message_type: &str) -> AvailabilityStatus {
+    match message_type.to_ascii_uppercase().as_str() {
+        "HIGH_IN_STOCK" | "MEDIUM_IN_STOCK" => AvailabilityStatus::InStock,
+        "LOW_IN_STOCK" => AvailabilityStatus::LimitedAvailability,
+        "OUT_OF_STOCK" => AvailabilityStatus::OutOfStock,
+        _ => AvailabilityStatus::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRODUCT_URL: &str = "https://www.ikea.com/au/en/p/billy-bookcase-white-00263850/";
+
+    /// Embed JSON props the way IKEA does: HTML-escaped in a module attribute
+    fn module(props: &str) -> String {
+        let escaped = props.replace('&', "&amp;").replace('"', "&quot;");
+        format!(
+            r#"<div class="pip-module" data-hydration-props="{}"></div>"#,
+            escaped
+        )
+    }
+
+    fn ikea_html(modules: &[String]) -> String {
+        format!("<html><body>{}</body></html>", modules.join(""))
+    }
+
+    fn price_props(price: &str, currency: &str) -> String {
+        format!(
+            r#"{{"price":{{"mainPriceProps":{{"priceNumeral":{},"currencyCode":"{}"}}}}}}"#,
+            price, currency
+        )
+    }
+
+    fn availability_entry(item_no: &str, message_type: &str) -> String {
+        format!(
+            r#"{{"classUnitKey":{{"classUnitCode":"AU","classUnitType":"RU"}},"itemKey":{{"itemNo":"{}","itemType":"ART"}},"buyingOption":{{"homeDelivery":{{"availability":{{"probability":{{"thisDay":{{"messageType":"{}"}}}}}}}}}}}}"#,
+            item_no, message_type
+        )
+    }
+
+    fn buy_module_props(entries: &[String]) -> String {
+        format!(
+            r#"{{"buyModule":{{"availabilities":[{}]}}}}"#,
+            entries.join(",")
+        )
+    }
+
+    #[test]
+    fn test_is_ikea_url() {
+        assert!(is_ikea_url(PRODUCT_URL));
+        assert!(is_ikea_url("https://ikea.com/us/en/p/item-00263850/"));
+        assert!(is_ikea_url("https://www.ikea.cn/cn/zh/p/item-00263850/"));
+        assert!(!is_ikea_url("https://www.notikea.com/p/item-00263850/"));
+        assert!(!is_ikea_url("https://ikea.example.com.evil.io/"));
+        assert!(!is_ikea_url("not a url"));
+    }
+
+    #[test]
+    fn test_extract_item_number() {
+        assert_eq!(
+            extract_item_number(PRODUCT_URL),
+            Some("00263850".to_string())
+        );
+        assert_eq!(
+            extract_item_number("https://www.ikea.com/us/en/p/billy-bookcase-combo-s49264501"),
+            Some("49264501".to_string())
+        );
+        assert_eq!(
+            extract_item_number("https://www.ikea.com/au/en/cat/bookcases-10382/"),
+            None
+        );
+        assert_eq!(extract_item_number("https://www.ikea.com/"), None);
+    }
+
+    #[test]
+    fn test_parse_in_stock_with_price() {
+        let html = ikea_html(&[
+            module(&price_props("149.99", "aud")),
+            module(&buy_module_props(&[availability_entry(
+                "00263850",
+                "HIGH_IN_STOCK",
+            )])),
+        ]);
+
+        let result = parse_ikea_data(&html, PRODUCT_URL).unwrap();
+
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.raw_availability, Some("HIGH_IN_STOCK".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(14999));
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+        assert_eq!(result.price.raw_price, Some("149.99".to_string()));
+    }
+
+    #[test]
+    fn test_parse_out_of_stock_online() {
+        let html = ikea_html(&[
+            module(&price_props("79", "USD")),
+            module(&buy_module_props(&[availability_entry(
+                "00263850",
+                "OUT_OF_STOCK",
+            )])),
+        ]);
+
+        let result = parse_ikea_data(&html, PRODUCT_URL).unwrap();
+
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(7900));
+    }
+
+    #[test]
+    fn test_parse_not_sold_online_is_out_of_stock() {
+        let props =
+            r#"{"availabilities":[{"itemKey":{"itemNo":"00263850"},"onlineSellable":false}]}"#;
+        let html = ikea_html(&[module(props)]);
+
+        let result = parse_ikea_data(&html, PRODUCT_URL).unwrap();
+
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, None);
+    }
+
+    #[test]
+    fn test_parse_prefers_entry_for_url_item() {
+        let html = ikea_html(&[module(&buy_module_props(&[
+            availability_entry("10263851", "HIGH_IN_STOCK"),
+            availability_entry("00263850", "LOW_IN_STOCK"),
+        ]))]);
+
+        let result = parse_ikea_data(&html, PRODUCT_URL).unwrap();
+
+        assert_eq!(result.status, AvailabilityStatus::LimitedAvailability);
+    }
+
+    #[test]
+    fn test_parse_price_only() {
+        let html = ikea_html(&[module(&price_props("\"1299\"", "SEK"))]);
+
+        let result = parse_ikea_data(&html, PRODUCT_URL).unwrap();
+
+        assert_eq!(result.status, AvailabilityStatus::Unknown);
+        assert_eq!(result.raw_availability, None);
+        assert_eq!(result.price.price_minor_units, Some(129900));
+        assert_eq!(result.price.price_currency, Some("SEK".to_string()));
+    }
+
+    #[test]
+    fn test_parse_without_hydration_props_fails() {
+        let result = parse_ikea_data("<html><body></body></html>", PRODUCT_URL);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_without_product_data_fails() {
+        let html = ikea_html(&[module(r#"{"breadcrumbs":[]}"#)]);
+        assert!(parse_ikea_data(&html, PRODUCT_URL).is_err());
+    }
+
+    #[test]
+    fn test_map_availability_status() {
+        assert_eq!(
+            map_availability_status("MEDIUM_IN_STOCK"),
+            AvailabilityStatus::InStock
+        );
+        assert_eq!(
+            map_availability_status("low_in_stock"),
+            AvailabilityStatus::LimitedAvailability
+        );
+        assert_eq!(
+            map_availability_status("OUT_OF_STOCK"),
+            AvailabilityStatus::OutOfStock
+        );
+        assert_eq!(
+            map_availability_status("SOMETHING_NEW"),
+            AvailabilityStatus::Unknown
+        );
+    }
+}
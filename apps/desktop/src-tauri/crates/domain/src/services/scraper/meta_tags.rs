@@ -0,0 +1,282 @@
+//! Open Graph / product meta tag extraction for price and availability data.
+//!
+//! Many long-tail stores expose no JSON-LD or dataLayer but still emit
+//! `<meta property="product:price:amount">` style tags for social previews.
+//! This module is a generic last-resort parser for those tags. Without an
+//! availability tag, a visible "notify me when back in stock" form still marks
+//! the product out of stock.
+
+use scraper::{Html, Selector};
+
+use product_stalker_core::AppError;
+
+use super::button_heuristics;
+use super::price_parser::{parse_price_to_minor_units, PriceInfo};
+use super::ScrapingResult;
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
+
+/// Meta properties carrying the price amount, in priority order.
+const PRICE_AMOUNT_PROPERTIES: &[&str] = &["product:price:amount", "og:price:amount"];
+
+/// Meta properties carrying the ISO 4217 currency code, in priority order.
+const PRICE_CURRENCY_PROPERTIES: &[&str] = &["product:price:currency", "og:price:currency"];
+
+/// Meta properties carrying the availability string, in priority order.
+const AVAILABILITY_PROPERTIES: &[&str] = &["og:availability", "product:availability"];
+
+/// Availability values (after normalization) that mean out of stock but are
+/// not covered by the Schema.org indicators.
+const OUT_OF_STOCK_VALUES: &[&str] = &["oos", "unavailable"];
+
+/// Extract product data from Open Graph / product meta tags in the HTML.
+///
+/// Succeeds when at least a parseable price or a recognizable availability
+/// value is present. A missing availability falls back to `OutOfStock` when the
+/// page shows a stock alert sign-up form. Meta tags are often stale or generic,
+/// so results are low confidence.
+pub fn extract_from_meta_tags(html: &str) -> Result<ScrapingResult, AppError> {
+    let document = Html::parse_document(html);
+
+    let raw_price = find_meta_content(&document, PRICE_AMOUNT_PROPERTIES)?;
+    let currency =
+        find_meta_content(&document, PRICE_CURRENCY_PROPERTIES)?.map(|c| c.to_uppercase());
+    let raw_availability = find_meta_content(&document, AVAILABILITY_PROPERTIES)?;
+
+    let price = build_price_info(raw_price, currency);
+    let mut status = raw_availability
+        .as_deref()
+        .map(map_availability)
+        .unwrap_or_default();
+    if status == AvailabilityStatus::Unknown && button_heuristics::has_notify_me_form(&document) {
+        status = AvailabilityStatus::OutOfStock;
+    }
+
+    if price.price_minor_units.is_none() && status == AvailabilityStatus::Unknown {
+        return Err(AppError::External(
+            "No product price or availability meta tags found".to_string(),
+        ));
+    }
+
+    Ok(ScrapingResult {
+        status,
+        raw_availability,
+        price,
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::Low,
+    })
+}
+
+/// Return the trimmed `content` of the first non-empty meta tag matching any property.
+///
+/// Checks both `property` (Open Graph) and `name` attributes since stores use either.
+fn find_meta_content(document: &Html, properties: &[&str]) -> Result<Option<String>, AppError> {
+    for property in properties {
+        let selector = Selector::parse(&format!(
+            r#"meta[property="{0}"], meta[name="{0}"]"#,
+            property
+        ))
+        .map_err(|e| AppError::External(format!("Invalid selector: {:?}", e)))?;
+
+        let content = document
+            .select(&selector)
+            .filter_map(|el| el.value().attr("content"))
+            .map(str::trim)
+            .find(|c| !c.is_empty());
+
+        if let Some(content) = content {
+            return Ok(Some(content.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Convert the raw amount and currency into a PriceInfo.
+fn build_price_info(raw_price: Option<String>, currency: Option<String>) -> PriceInfo {
+    let Some(raw_price) = raw_price else {
+        return PriceInfo::default();
+    };
+
+    let price_minor_units = parse_price_to_minor_units(&raw_price, currency.as_deref());
+    PriceInfo {
+        price_minor_units,
+        price_currency: price_minor_units.and(currency),
+        raw_price: Some(raw_price),
+        compare_at_minor_units: None,
+        price_valid_until: None,
+        price_range: None,
+    }
+}
+
+/// Map a meta tag availability string (e.g. "instock", "out of stock", "pre-order")
+/// to an AvailabilityStatus.
+fn map_availability(value: &str) -> AvailabilityStatus {
+    let normalized: String = value
+        .to_lowercase()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .collect();
+
+    if OUT_OF_STOCK_VALUES.contains(&normalized.as_str()) {
+        return AvailabilityStatus::OutOfStock;
+    }
+
+    AvailabilityStatus::from_schema_org(&normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn html_with_meta(tags: &[(&str, &str)]) -> String {
+        let metas: String = tags
+            .iter()
+            .map(|(property, content)| {
+                format!(r#"<meta property="{}" content="{}">"#, property, content)
+            })
+            .collect();
+        format!(
+            "<!DOCTYPE html><html><head>{}</head><body></body></html>",
+            metas
+        )
+    }
+
+    #[test]
+    fn test_extract_in_stock_with_price() {
+        let html = html_with_meta(&[
+            ("product:price:amount", "49.99"),
+            ("product:price:currency", "USD"),
+            ("og:availability", "instock"),
+        ]);
+
+        let result = extract_from_meta_tags(&html).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.raw_availability, Some("instock".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(4999));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+        assert_eq!(result.price.raw_price, Some("49.99".to_string()));
+        assert_eq!(result.confidence, Confidence::Low);
+    }
+
+    #[test]
+    fn test_extract_out_of_stock() {
+        let html = html_with_meta(&[
+            ("product:price:amount", "10.00"),
+            ("product:price:currency", "EUR"),
+            ("og:availability", "oos"),
+        ]);
+
+        let result = extract_from_meta_tags(&html).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+    }
+
+    #[test]
+    fn test_extract_pre_order() {
+        let html = html_with_meta(&[("og:availability", "preorder")]);
+
+        let result = extract_from_meta_tags(&html).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::PreOrder);
+        assert!(result.price.price_minor_units.is_none());
+    }
+
+    #[test]
+    fn test_extract_og_price_fallback_properties() {
+        let html = html_with_meta(&[("og:price:amount", "1,299"), ("og:price:currency", "jpy")]);
+
+        let result = extract_from_meta_tags(&html).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::Unknown);
+        assert_eq!(result.price.price_minor_units, Some(1299));
+        assert_eq!(result.price.price_currency, Some("JPY".to_string()));
+    }
+
+    #[test]
+    fn test_extract_name_attribute() {
+        let html = r#"<html><head>
+            <meta name="product:price:amount" content="5.50">
+            <meta name="product:price:currency" content="AUD">
+            <meta name="product:availability" content="in stock">
+        </head></html>"#;
+
+        let result = extract_from_meta_tags(html).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.price.price_minor_units, Some(550));
+    }
+
+    #[test]
+    fn test_extract_no_meta_tags_fails() {
+        let html = html_with_meta(&[("og:title", "Some Product")]);
+        assert!(extract_from_meta_tags(&html).is_err());
+    }
+
+    #[test]
+    fn test_extract_unparseable_price_and_no_availability_fails() {
+        let html = html_with_meta(&[("product:price:amount", "call for price")]);
+        assert!(extract_from_meta_tags(&html).is_err());
+    }
+
+    #[test]
+    fn test_notify_form_without_availability_tag_is_out_of_stock() {
+        let html = r#"<html><head>
+            <meta property="product:price:amount" content="49.95">
+            <meta property="product:price:currency" content="AUD">
+        </head><body>
+            <form><label>Notify me when back in stock</label><input type="email"></form>
+        </body></html>"#;
+
+        let result = extract_from_meta_tags(html).unwrap();
+
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.raw_availability, None);
+        assert_eq!(result.price.price_minor_units, Some(4995));
+    }
+
+    #[test]
+    fn test_notify_form_alone_is_out_of_stock() {
+        let html = r#"<html><body>
+            <h1>Limited Edition Mug</h1>
+            <div class="restock-signup">
+                <p>Sold out. Enter your email and we'll let you know when it's back in stock.</p>
+                <input type="email" placeholder="Email">
+                <button>Submit</button>
+            </div>
+        </body></html>"#;
+
+        let result = extract_from_meta_tags(html).unwrap();
+
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, None);
+    }
+
+    #[test]
+    fn test_availability_tag_wins_over_notify_form() {
+        let html = r#"<html><head>
+            <meta property="og:availability" content="instock">
+        </head><body>
+            <form><p>Notify me when back in stock</p><input type="email"></form>
+        </body></html>"#;
+
+        let result = extract_from_meta_tags(html).unwrap();
+
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+    }
+
+    #[test]
+    fn test_map_availability_variants() {
+        assert_eq!(map_availability("instock"), AvailabilityStatus::InStock);
+        assert_eq!(map_availability("In Stock"), AvailabilityStatus::InStock);
+        assert_eq!(map_availability("in_stock"), AvailabilityStatus::InStock);
+        assert_eq!(
+            map_availability("out of stock"),
+            AvailabilityStatus::OutOfStock
+        );
+        assert_eq!(map_availability("oos"), AvailabilityStatus::OutOfStock);
+        assert_eq!(map_availability("pre-order"), AvailabilityStatus::PreOrder);
+        assert_eq!(map_availability("backorder"), AvailabilityStatus::BackOrder);
+        assert_eq!(
+            map_availability("http://schema.org/LimitedAvailability"),
+            AvailabilityStatus::LimitedAvailability
+        );
+        assert_eq!(map_availability("maybe"), AvailabilityStatus::Unknown);
+    }
+}
@@ -6,7 +6,8 @@
 //!
 //! 1. **Schema.org JSON-LD** (`schema_org`) — Most reliable. Parses structured
 //!    `<script type="application/ld+json">` data for Product/ProductGroup types.
-//!    Handles variant matching via URL query parameters.
+//!    Handles variant matching via URL query parameters. When offers carry a
+//!    price but no availability, stock is inferred from the add-to-cart button.
 //!
 //! 2. **GTM dataLayer** (`gtm_datalayer`) — Extracts price from `dataLayer.push()`
 //!    calls injected by Google Tag Manager. Supports GA4 ecommerce events,
@@ -15,47 +16,91 @@
 //!
 //! 3. **Shopify Cart API** (`shopify`) — For URLs matching Shopify's `/products/`
 //!    pattern. Uses the store's cart API (`/cart/add.js`) to check variant
-//!    availability, since Shopify pages often lack Schema.org data.
+//!    availability, since Shopify pages often lack Schema.org data. Before any
+//!    HTML is fetched, `/products/<handle>.json` is tried as a fast path; when it
+//!    reports variant availability the page is never scraped.
 //!
-//! 4. **Site-specific parsers** — Fallback for sites that don't use any standard
-//!    format. Currently supports Chemist Warehouse (via `nextjs_data`).
+//! 4. **Open Graph / product meta tags** (`meta_tags`) — Generic fallback for
+//!    long-tail stores exposing `product:price:amount`, `product:price:currency`
+//!    and `og:availability` meta tags. Without an availability tag, a visible
+//!    "notify me when back in stock" form marks the product out of stock. A
+//!    price with no stock status is only used when no site-specific parser matches.
+//!
+//! 5. **Site-specific parsers** — Fallback for sites that don't use any standard
+//!    format. Currently supports Chemist Warehouse, Woolworths, Coles and
+//!    Walmart (via `nextjs_data`), Amazon (via DOM selectors), eBay (via embedded listing
+//!    JSON or DOM selectors), Best Buy (via embedded initial state JSON),
+//!    Target (via embedded RedSky API data) and IKEA (via embedded hydration
+//!    props carrying availability API data).
 //!
 //! # Adding a New Strategy
 //!
 //! To add a new extraction strategy:
 //! 1. Create a new submodule (e.g., `my_strategy.rs`)
 //! 2. Implement a function returning `Result<ScrapingResult, AppError>`
-//! 3. Add the attempt in `check_availability_with_headless()` at the appropriate
-//!    priority level (prefer earlier = more reliable)
+//! 3. Add the attempt in `extract_with_strategy()` at the appropriate
+//!    priority level (prefer earlier = more reliable), with a matching
+//!    `ExtractionStrategy` variant
 //!
 //! # Submodules
 //!
+//! - `amazon`: Site-specific adapter for Amazon storefronts
+//! - `bestbuy`: Site-specific adapter for Best Buy (embedded initial state JSON)
 //! - `bot_detection`: Cloudflare and bot protection detection
+//! - `button_heuristics`: Add-to-cart button and notify-me form heuristics for availability
 //! - `chemist_warehouse`: Site-specific adapter for Chemist Warehouse
+//! - `coles`: Site-specific adapter for Coles
+//! - `diagnostics`: Dry-run scrape diagnostics (which strategy matched and why)
+//! - `ebay`: Site-specific adapter for eBay listings (Buy It Now and auctions)
 //! - `gtm_datalayer`: GTM dataLayer.push() ecommerce data extraction
 //! - `http_client`: HTTP fetching with browser-like headers and headless fallback
-//! - `nextjs_data`: Next.js __NEXT_DATA__ extraction
+//! - `ikea`: Site-specific adapter for IKEA (embedded availability API data)
+//! - `meta_tags`: Open Graph / product meta tag extraction
+//! - `nextjs_data`: Next.js __NEXT_DATA__ and app-router __next_f extraction
 //! - `price_parser`: Price extraction and normalization
+//! - `rate_limiter`: Per-host minimum interval between page fetches
+//! - `robots`: robots.txt rules and `Crawl-delay`, cached per origin
 //! - `schema_org`: JSON-LD Schema.org data parsing
 //! - `shopify`: Shopify store adapter using cart API for availability
+//! - `target`: Site-specific adapter for Target (embedded RedSky API data)
+//! - `walmart`: Site-specific adapter for Walmart (embedded Next.js product data)
+//! - `woolworths`: Site-specific adapter for Woolworths
 
+mod amazon;
+mod bestbuy;
 mod bot_detection;
+mod button_heuristics;
 mod chemist_warehouse;
+mod coles;
+mod diagnostics;
+mod dom;
+mod ebay;
 mod gtm_datalayer;
 mod http_client;
+mod ikea;
+mod meta_tags;
 mod nextjs_data;
 mod price_parser;
+mod rate_limiter;
+mod robots;
 mod schema_org;
 mod shopify;
+mod target;
+mod user_agent;
+mod walmart;
+mod woolworths;
 
 use sea_orm::DatabaseConnection;
 use url::Url;
 
-use crate::entities::availability_check::AvailabilityStatus;
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
 use product_stalker_core::AppError;
 
 // Re-export types that are part of the public API
-pub use price_parser::{has_path_locale, PriceInfo};
+pub use diagnostics::{ExtractionStrategy, ScrapeDiagnostics};
+pub use http_client::{canonical_redirect_url, FetchMetrics, FetchOptions, FetchTimeouts};
+pub use price_parser::{has_path_locale, PriceInfo, PriceRange};
+pub use schema_org::{ProductGroupStrategy, VariantSelector, DEFAULT_MAX_JSON_LD_BLOCK_BYTES};
 
 /// User-Agent header mimicking Chrome browser.
 ///
@@ -69,6 +114,17 @@ pub struct ScrapingResult {
     pub status: AvailabilityStatus,
     pub raw_availability: Option<String>,
     pub price: PriceInfo,
+    /// The page redirected to a different region than requested (see
+    /// [`price_parser::is_region_redirect`]), so its currency may not be the product's
+    pub region_mismatch: bool,
+    /// Timing and size of the page fetch; `None` when no HTML was fetched
+    /// (e.g. Shopify's products.json answered)
+    pub fetch_metrics: Option<FetchMetrics>,
+    /// Where the URL permanently lives when the fetch was only redirected to
+    /// normalize it (see [`canonical_redirect_url`]), so the stored URL can be updated
+    pub canonical_url: Option<String>,
+    /// Whether the status came from structured data or was inferred from page heuristics
+    pub confidence: Confidence,
 }
 
 /// Service for scraping product availability from web pages
@@ -84,46 +140,158 @@ impl ScraperService {
         url: &str,
         conn: &DatabaseConnection,
     ) -> Result<ScrapingResult, AppError> {
-        Self::check_availability_with_headless(url, true, false, conn, 14).await
+        let options = FetchOptions {
+            enable_headless: true,
+            allow_manual_verification: false,
+            headless_profile_reuse: true,
+            session_cache_duration_days: 14,
+            timeouts: FetchTimeouts::default(),
+            force_headless_domains: Vec::new(),
+            respect_robots_txt: false,
+            rotate_user_agent: false,
+            product_group_strategy: ProductGroupStrategy::First,
+            headless_browser_path: None,
+            insecure_hosts: Vec::new(),
+            max_redirects: 10,
+            max_json_ld_block_bytes: DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+        };
+        Self::check_availability_with_headless(url, conn, &options, true, None).await
     }
 
     /// Check availability with control over headless fallback and manual verification
     ///
     /// This is the main orchestrator function that coordinates the scraping workflow:
     /// 1. Validate URL scheme
-    /// 2. Fetch HTML (with automatic headless fallback if bot protection detected)
+    /// 2. Fetch HTML (with automatic headless fallback if bot protection detected),
+    ///    unless Shopify's products.json already answers
     /// 3. Try Schema.org extraction first
     /// 4. Try GTM dataLayer extraction (GA4 ecommerce events)
     /// 5. Try Shopify-specific extraction for Shopify stores
-    /// 6. Fall back to other site-specific parsers (e.g., Next.js data)
+    /// 6. Try Open Graph / product meta tags
+    /// 7. Fall back to other site-specific parsers (e.g., Next.js data)
+    ///
+    /// With `track_price` off the check is stock-only. The extractors still
+    /// parse prices (ProductGroup variant selection can depend on them), but the
+    /// price is discarded from the result and the region check is skipped, so
+    /// nothing is stored, converted, or used to auto-set a currency.
+    /// `variant_selector` picks the ProductGroup variant when the URL doesn't.
     pub async fn check_availability_with_headless(
         url: &str,
-        enable_headless: bool,
-        allow_manual_verification: bool,
         conn: &DatabaseConnection,
-        session_cache_duration_days: i32,
+        options: &FetchOptions,
+        track_price: bool,
+        variant_selector: Option<&VariantSelector>,
+    ) -> Result<ScrapingResult, AppError> {
+        let mut result = Self::scrape(url, conn, options, track_price, variant_selector).await?;
+        if !track_price {
+            result.price = PriceInfo::default();
+        }
+        Ok(result)
+    }
+
+    /// Fetch the page and run the extraction strategies (steps 1-7 above)
+    async fn scrape(
+        url: &str,
+        conn: &DatabaseConnection,
+        options: &FetchOptions,
+        track_price: bool,
+        variant_selector: Option<&VariantSelector>,
     ) -> Result<ScrapingResult, AppError> {
         // Step 1: Validate URL scheme
         Self::validate_url_scheme(url)?;
 
-        // Step 2: Fetch HTML (tries HTTP first, falls back to headless if needed)
-        let html = http_client::fetch_html_with_fallback(
-            url,
-            enable_headless,
-            allow_manual_verification,
-            conn,
-            session_cache_duration_days,
-        )
-        .await?;
+        // Step 2: Shopify products.json fast path, then fetch HTML (tries HTTP
+        // first, falls back to headless if needed)
+        if let Some(result) = Self::try_shopify_products_json(url, conn, options).await? {
+            return Ok(result);
+        }
+        let page = http_client::fetch_html_with_fallback(url, conn, options).await?;
+
+        // Steps 3-7: Run the extraction strategies in priority order
+        let (mut result, _) =
+            Self::extract_with_strategy(&page.html, url, conn, options, variant_selector).await?;
+        result.fetch_metrics = Some(page.metrics());
+
+        // Flag pages served for another region after a redirect; only the price
+        // depends on the region
+        if let Some(final_url) = page.final_url.as_deref().filter(|_| track_price) {
+            if price_parser::is_region_redirect(url, final_url) {
+                log::warn!(
+                    "{} redirected to {}; prices may be for a different region",
+                    url,
+                    final_url
+                );
+                result.region_mismatch = true;
+            }
+        }
+        result.canonical_url = page
+            .final_url
+            .as_deref()
+            .and_then(|final_url| canonical_redirect_url(url, final_url));
+        if let Some(canonical_url) = &result.canonical_url {
+            log::info!(
+                "{} redirects to {}; its URL can be updated",
+                url,
+                canonical_url
+            );
+        }
+        Ok(result)
+    }
+
+    /// Try Shopify's products.json endpoint before fetching the product page.
+    ///
+    /// Skipped for hosts that block plain HTTP (force headless) and for stores
+    /// recently found to have no product.json. Errors only if robots.txt
+    /// disallows the URL; any other failure returns `None` so the regular HTML
+    /// scrape runs.
+    async fn try_shopify_products_json(
+        url: &str,
+        conn: &DatabaseConnection,
+        options: &FetchOptions,
+    ) -> Result<Option<ScrapingResult>, AppError> {
+        if !shopify::is_potential_shopify_product_url(url)
+            || http_client::is_force_headless_host(url, &options.force_headless_domains)
+            || shopify::NonShopifyOrigins::global().contains(url)
+        {
+            return Ok(None);
+        }
 
+        http_client::wait_for_host_turn(url, options).await?;
+        match shopify::fetch_products_json(url, conn, options).await {
+            Ok(result) => Ok(Some(result)),
+            Err(e) => {
+                log::debug!("Shopify products.json fast path failed for {}: {}", url, e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Run the extraction strategies in priority order on fetched HTML.
+    ///
+    /// Returns the first successful result together with the strategy that produced it.
+    /// JSON-LD blocks over `options.max_json_ld_block_bytes` are skipped. Store API
+    /// requests (Shopify) are sent like the page's (see [`http_client::host_request`]).
+    async fn extract_with_strategy(
+        html: &str,
+        url: &str,
+        conn: &DatabaseConnection,
+        options: &FetchOptions,
+        variant_selector: Option<&VariantSelector>,
+    ) -> Result<(ScrapingResult, ExtractionStrategy), AppError> {
         // Step 3: Try Schema.org extraction first
-        if let Ok(result) = Self::try_schema_org_extraction(&html, url) {
-            return Ok(result);
+        if let Ok(result) = Self::try_schema_org_extraction(
+            html,
+            url,
+            options.product_group_strategy,
+            variant_selector,
+            options.max_json_ld_block_bytes,
+        ) {
+            return Ok((result, ExtractionStrategy::SchemaOrg));
         }
 
         // Step 4: Try GTM dataLayer extraction (GA4 ecommerce events)
-        if let Ok(result) = gtm_datalayer::extract_from_datalayer(&html) {
-            return Ok(result);
+        if let Ok(result) = gtm_datalayer::extract_from_datalayer(html) {
+            return Ok((result, ExtractionStrategy::Gtm));
         }
 
         // Step 5: Try Shopify extraction (async - uses cart API)
@@ -132,19 +300,44 @@ impl ScraperService {
                 "URL matches Shopify pattern, trying Shopify extraction for {}",
                 url
             );
-            if let Ok(result) = shopify::check_shopify_availability(url, &html).await {
-                return Ok(result);
+            if let Ok(result) = shopify::check_shopify_availability(url, html, conn, options).await
+            {
+                return Ok((result, ExtractionStrategy::Shopify));
             }
         }
 
-        // Step 6: Fall back to other site-specific parsers (sync)
-        Self::try_site_specific_extraction(&html, url)
+        // Step 6: Try generic Open Graph / product meta tags. A price without a
+        // stock status is only used if no site-specific parser matches.
+        let price_only_meta = match meta_tags::extract_from_meta_tags(html) {
+            Ok(result) if result.status != AvailabilityStatus::Unknown => {
+                return Ok((result, ExtractionStrategy::Meta));
+            }
+            Ok(result) => Some(result),
+            Err(_) => None,
+        };
+
+        // Step 7: Fall back to other site-specific parsers (sync)
+        match Self::try_site_specific_extraction(html, url) {
+            Ok(result) => Ok((result, ExtractionStrategy::SiteSpecific)),
+            Err(e) => price_only_meta
+                .map(|result| (result, ExtractionStrategy::Meta))
+                .ok_or(e),
+        }
     }
 
     /// Try to extract availability from Schema.org JSON-LD data
-    fn try_schema_org_extraction(html: &str, url: &str) -> Result<ScrapingResult, AppError> {
+    ///
+    /// `variant_selector`, then `group_strategy`, pick the ProductGroup variant
+    /// when the URL has no variant ID. Blocks over `max_json_ld_block_bytes` are skipped.
+    fn try_schema_org_extraction(
+        html: &str,
+        url: &str,
+        group_strategy: ProductGroupStrategy,
+        variant_selector: Option<&VariantSelector>,
+        max_json_ld_block_bytes: usize,
+    ) -> Result<ScrapingResult, AppError> {
         let variant_id = schema_org::extract_variant_id(url);
-        let json_ld_blocks = schema_org::extract_json_ld_blocks(html)?;
+        let json_ld_blocks = schema_org::extract_json_ld_blocks(html, max_json_ld_block_bytes)?;
 
         log::debug!(
             "Schema.org extraction: found {} JSON-LD block(s) for URL: {}",
@@ -159,9 +352,13 @@ impl ScraperService {
                 .unwrap_or_else(|| "unknown".to_string());
             log::debug!("JSON-LD block {}: @type = {}", i, block_type);
 
-            if let Some((availability, price)) =
-                schema_org::extract_availability_and_price(block, variant_id.as_deref(), url)
-            {
+            if let Some((availability, price)) = schema_org::extract_availability_and_price(
+                block,
+                variant_id.as_deref(),
+                variant_selector,
+                url,
+                group_strategy,
+            ) {
                 log::debug!(
                     "Extracted raw availability value: '{}' -> status: {:?}",
                     availability,
@@ -171,6 +368,32 @@ impl ScraperService {
                     status: AvailabilityStatus::from_schema_org(&availability),
                     raw_availability: Some(availability),
                     price,
+                    region_mismatch: false,
+                    fetch_metrics: None,
+                    canonical_url: None,
+                    confidence: Confidence::High,
+                });
+            }
+        }
+
+        // Price without availability: infer stock from the add-to-cart button
+        let price = json_ld_blocks
+            .iter()
+            .find_map(|block| schema_org::extract_price_without_availability(block, url));
+        if let Some(price) = price {
+            if let Some(status) = button_heuristics::detect_add_to_cart_state(html) {
+                log::debug!(
+                    "Schema.org offer has no availability; inferred {:?} from add-to-cart button",
+                    status
+                );
+                return Ok(ScrapingResult {
+                    status,
+                    raw_availability: None,
+                    price,
+                    region_mismatch: false,
+                    fetch_metrics: None,
+                    canonical_url: None,
+                    confidence: Confidence::Low,
                 });
             }
         }
@@ -187,6 +410,45 @@ impl ScraperService {
             return Self::try_chemist_warehouse_extraction(html);
         }
 
+        // Woolworths / Coles: hydration JSON with boolean stock flags in __NEXT_DATA__
+        if woolworths::is_woolworths_url(url) {
+            return Self::try_next_data_extraction(html, woolworths::parse_woolworths_data);
+        }
+        if coles::is_coles_url(url) {
+            return Self::try_next_data_extraction(html, coles::parse_coles_data);
+        }
+
+        // Walmart: availabilityStatus and priceInfo in __NEXT_DATA__ initialData
+        if walmart::is_walmart_url(url) {
+            return Self::try_next_data_extraction(html, walmart::parse_walmart_data);
+        }
+
+        // Amazon: no Schema.org offers, availability and price live in the DOM
+        if amazon::is_amazon_url(url) {
+            return amazon::parse_amazon_data(html)
+                .map(|result| amazon::with_domain_currency(result, url));
+        }
+
+        // eBay: listing state lives in an embedded JSON blob or the item page DOM
+        if ebay::is_ebay_url(url) {
+            return ebay::parse_ebay_data(html, url);
+        }
+
+        // Best Buy: SKU button state and price live in window.__INITIAL_STATE__
+        if bestbuy::is_bestbuy_url(url) {
+            return bestbuy::parse_bestbuy_data(html, url);
+        }
+
+        // Target: RedSky product data embedded in __TGT_DATA__ or __NEXT_DATA__
+        if target::is_target_url(url) {
+            return target::parse_target_data(html, url);
+        }
+
+        // IKEA: price and availability API data embedded in hydration props
+        if ikea::is_ikea_url(url) {
+            return ikea::parse_ikea_data(html, url);
+        }
+
         // No site-specific parser matched
         Err(AppError::External(
             "No availability information found. Site does not use Schema.org or a supported data format.".to_string(),
@@ -195,10 +457,29 @@ impl ScraperService {
 
     /// Extract availability from Chemist Warehouse using Next.js data
     fn try_chemist_warehouse_extraction(html: &str) -> Result<ScrapingResult, AppError> {
-        let next_data = nextjs_data::extract_next_data(html)?;
+        Self::try_next_data_extraction(html, chemist_warehouse::parse_chemist_warehouse_data)
+    }
+
+    /// Extract availability by running a site parser on the Next.js pageProps
+    ///
+    /// Falls back to the app router's `__next_f` streaming data when the page
+    /// has no `__NEXT_DATA__` script.
+    fn try_next_data_extraction(
+        html: &str,
+        parse: fn(&serde_json::Value) -> Result<ScrapingResult, AppError>,
+    ) -> Result<ScrapingResult, AppError> {
+        let next_data = match nextjs_data::extract_next_data(html) {
+            Ok(next_data) => next_data,
+            Err(err) => {
+                return match nextjs_data::extract_next_f_page_props(html) {
+                    Ok(page_props) => parse(&page_props),
+                    Err(_) => Err(err),
+                };
+            }
+        };
         let page_props = nextjs_data::get_page_props(&next_data)
             .ok_or_else(|| AppError::External("No pageProps found in Next.js data".to_string()))?;
-        chemist_warehouse::parse_chemist_warehouse_data(page_props)
+        parse(page_props)
     }
 
     /// Validate that the URL uses http or https scheme
@@ -223,7 +504,13 @@ impl ScraperService {
     /// and just need to parse it. Delegates to `try_schema_org_extraction`.
     #[cfg(test)]
     pub fn parse_schema_org_with_url(html: &str, url: &str) -> Result<ScrapingResult, AppError> {
-        Self::try_schema_org_extraction(html, url)
+        Self::try_schema_org_extraction(
+            html,
+            url,
+            ProductGroupStrategy::First,
+            None,
+            DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+        )
     }
 }
 
@@ -381,6 +668,37 @@ mod tests {
         html_with_product_offer, VariantInfo,
     };
     use super::*;
+    use crate::test_utils::setup_fetch_db;
+
+    fn fetch_options() -> FetchOptions {
+        FetchOptions {
+            enable_headless: false,
+            allow_manual_verification: false,
+            headless_profile_reuse: true,
+            session_cache_duration_days: 14,
+            timeouts: FetchTimeouts {
+                min_host_interval_ms: 0,
+                ..FetchTimeouts::default()
+            },
+            force_headless_domains: Vec::new(),
+            respect_robots_txt: false,
+            rotate_user_agent: false,
+            product_group_strategy: ProductGroupStrategy::First,
+            headless_browser_path: None,
+            insecure_hosts: Vec::new(),
+            max_redirects: 10,
+            max_json_ld_block_bytes: DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+        }
+    }
+
+    /// Run the extraction strategies on `html` with the default fetch options
+    async fn extract(
+        html: &str,
+        url: &str,
+    ) -> Result<(ScrapingResult, ExtractionStrategy), AppError> {
+        let conn = setup_fetch_db().await;
+        ScraperService::extract_with_strategy(html, url, &conn, &fetch_options(), None).await
+    }
 
     #[test]
     fn test_parse_schema_org_in_stock() {
@@ -413,6 +731,43 @@ mod tests {
         assert_eq!(result.status, AvailabilityStatus::BackOrder);
     }
 
+    #[test]
+    fn test_parse_schema_org_pre_order() {
+        let html =
+            html_with_product_offer("http://schema.org/PreOrder", Some("69.99"), Some("USD"));
+
+        let result =
+            ScraperService::parse_schema_org_with_url(&html, "https://example.com").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::PreOrder);
+        assert_eq!(
+            result.raw_availability,
+            Some("http://schema.org/PreOrder".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_org_pre_sale() {
+        let html = html_with_product_offer("https://schema.org/PreSale", None, None);
+
+        let result =
+            ScraperService::parse_schema_org_with_url(&html, "https://example.com").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::PreOrder);
+    }
+
+    #[test]
+    fn test_parse_schema_org_limited_availability() {
+        let html = html_with_product_offer(
+            "http://schema.org/LimitedAvailability",
+            Some("19.99"),
+            Some("USD"),
+        );
+
+        let result =
+            ScraperService::parse_schema_org_with_url(&html, "https://example.com").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::LimitedAvailability);
+        assert_eq!(result.price.price_minor_units, Some(1999));
+    }
+
     #[test]
     fn test_parse_schema_org_product_group_with_variant() {
         let html = html_with_product_group(&[
@@ -496,6 +851,54 @@ mod tests {
         assert_eq!(result.price.price_currency, Some("AUD".to_string()));
     }
 
+    #[test]
+    fn test_schema_org_price_without_availability_uses_button_state() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">
+            {"@type": "Product", "offers": {"price": "49.00", "priceCurrency": "USD"}}
+            </script>
+            </head><body>
+            <button type="submit" name="add" disabled>Add to Cart</button>
+            </body></html>"#;
+
+        let result =
+            ScraperService::parse_schema_org_with_url(html, "https://example.com/p").unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.raw_availability, None);
+        assert_eq!(result.price.price_minor_units, Some(4900));
+        assert_eq!(result.confidence, Confidence::Low);
+    }
+
+    #[test]
+    fn test_schema_org_extraction_uses_configured_block_limit() {
+        let html = html_with_product_offer("http://schema.org/InStock", Some("49.00"), None);
+        let extract = |max_block_bytes| {
+            ScraperService::try_schema_org_extraction(
+                &html,
+                "https://example.com/p",
+                ProductGroupStrategy::First,
+                None,
+                max_block_bytes,
+            )
+        };
+
+        assert!(extract(DEFAULT_MAX_JSON_LD_BLOCK_BYTES).is_ok());
+        // The block is skipped rather than parsed when over the limit
+        assert!(extract(64).is_err());
+    }
+
+    #[test]
+    fn test_schema_org_price_without_availability_or_button_errors() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">
+            {"@type": "Product", "offers": {"price": "49.00", "priceCurrency": "USD"}}
+            </script>
+            </head><body></body></html>"#;
+
+        let result = ScraperService::parse_schema_org_with_url(html, "https://example.com/p");
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_chemist_warehouse_extraction_in_stock() {
         let html = html_with_next_data(
@@ -549,6 +952,157 @@ mod tests {
         assert_eq!(result.price.price_minor_units, Some(2999));
     }
 
+    #[test]
+    fn test_site_specific_extraction_falls_back_to_next_f_chunks() {
+        let html = concat!(
+            "<html><body>",
+            r#"<script>self.__next_f.push([1,"1:I[\"./app/page.js\",[],\"Page\"]\n"])</script>"#,
+            r#"<script>self.__next_f.push([1,"2:[\"$\",\"$L1\",null,{\"product\":{\"name\":\"Test Product\","])</script>"#,
+            r#"<script>self.__next_f.push([1,"\"price\":\"29.99\",\"availability\":\"out-of-stock\"}}]\n"])</script>"#,
+            "</body></html>"
+        );
+
+        let result = ScraperService::try_site_specific_extraction(
+            html,
+            "https://www.chemistwarehouse.com.au/buy/87324/curash-simply-water-wipes",
+        )
+        .unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(2999));
+    }
+
+    #[test]
+    fn test_site_specific_extraction_woolworths() {
+        let html = html_with_next_data(
+            r#"{
+                "Stockcode": 123456,
+                "Name": "Woolworths Full Cream Milk 3L",
+                "Price": 4.65,
+                "IsAvailable": true,
+                "IsInStock": false
+            }"#,
+        );
+
+        let result = ScraperService::try_site_specific_extraction(
+            &html,
+            "https://www.woolworths.com.au/shop/productdetails/123456/woolworths-full-cream-milk",
+        )
+        .unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(465));
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+    }
+
+    #[test]
+    fn test_site_specific_extraction_coles() {
+        let html = html_with_next_data(
+            r#"{
+                "id": 8150288,
+                "name": "Full Cream Milk 3L",
+                "availability": true,
+                "pricing": {"now": 4.65, "was": 0}
+            }"#,
+        );
+
+        let result = ScraperService::try_site_specific_extraction(
+            &html,
+            "https://www.coles.com.au/product/coles-full-cream-milk-3l-8150288",
+        )
+        .unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.price.price_minor_units, Some(465));
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+    }
+
+    #[test]
+    fn test_site_specific_extraction_walmart() {
+        let html = r#"<html><head>
+            <script id="__NEXT_DATA__" type="application/json">
+            {"props": {"pageProps": {"initialData": {"data": {"product": {
+                "usItemId": "5129928603",
+                "name": "Great Value Whole Milk, 1 Gallon",
+                "availabilityStatus": "OUT_OF_STOCK",
+                "priceInfo": {"currentPrice": {"price": 3.48, "priceString": "$3.48", "currencyUnit": "USD"}}
+            }}}}}}
+            </script>
+        </head><body></body></html>"#;
+
+        let result = ScraperService::try_site_specific_extraction(
+            html,
+            "https://www.walmart.com/ip/Great-Value-Whole-Milk-1-Gallon/5129928603",
+        )
+        .unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.raw_availability, Some("OUT_OF_STOCK".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(348));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+    }
+
+    #[test]
+    fn test_site_specific_extraction_ebay() {
+        let html = r#"<html><body>
+            <div class="x-price-primary"><span>US $35.00</span></div>
+            <a class="x-bin-action" href="/bin">Buy It Now</a>
+        </body></html>"#;
+
+        let result =
+            ScraperService::try_site_specific_extraction(html, "https://www.ebay.com/itm/1234")
+                .unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.price.price_minor_units, Some(3500));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+    }
+
+    #[test]
+    fn test_site_specific_extraction_bestbuy() {
+        let html = r#"<html><head><script>
+            window.__INITIAL_STATE__ = {"sku":{"skuId":"6505727","buttonState":"SOLD_OUT","currentPrice":349.99}};
+        </script></head></html>"#;
+
+        let result = ScraperService::try_site_specific_extraction(
+            html,
+            "https://www.bestbuy.com/site/item/6505727.p?skuId=6505727",
+        )
+        .unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(34999));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+    }
+
+    #[test]
+    fn test_site_specific_extraction_target() {
+        let html = r#"<html><head><script>
+            window.__TGT_DATA__ = {"product":{"tcin":"12345678","price":{"current_retail":29.99},
+                "fulfillment":{"shipping_options":{"availability_status":"OUT_OF_STOCK"}}}};
+        </script></head></html>"#;
+
+        let result = ScraperService::try_site_specific_extraction(
+            html,
+            "https://www.target.com/p/some-item/-/A-12345678",
+        )
+        .unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(2999));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+    }
+
+    #[test]
+    fn test_site_specific_extraction_ikea() {
+        let html = r#"<html><body>
+            <div data-hydration-props="{&quot;price&quot;:{&quot;priceNumeral&quot;:149,&quot;currencyCode&quot;:&quot;AUD&quot;}}"></div>
+            <div data-hydration-props="{&quot;availabilities&quot;:[{&quot;itemKey&quot;:{&quot;itemNo&quot;:&quot;00263850&quot;},&quot;onlineSellable&quot;:false}]}"></div>
+        </body></html>"#;
+
+        let result = ScraperService::try_site_specific_extraction(
+            html,
+            "https://www.ikea.com/au/en/p/billy-bookcase-white-00263850/",
+        )
+        .unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(14900));
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+    }
+
     #[tokio::test]
     async fn test_check_availability_rejects_file_scheme() {
         let conn = crate::test_utils::setup_availability_db().await;
@@ -630,4 +1184,246 @@ mod tests {
         let result = gtm_datalayer::extract_from_datalayer(&html).unwrap();
         assert_eq!(result.status, AvailabilityStatus::Unknown);
     }
+
+    #[tokio::test]
+    async fn test_price_only_meta_falls_through_to_site_specific() {
+        let price_meta = r#"<meta property="product:price:amount" content="49.99">"#;
+        let amazon = format!(
+            r#"<html><head>{}</head><body>
+            <span class="a-price"><span class="a-offscreen">$45.00</span></span>
+            <div id="availability"><span>In Stock</span></div>
+            </body></html>"#,
+            price_meta
+        );
+        let generic = format!("<html><head>{}</head><body></body></html>", price_meta);
+
+        let (result, strategy) = extract(&amazon, "https://www.amazon.com/dp/B0123")
+            .await
+            .unwrap();
+        assert_eq!(strategy, ExtractionStrategy::SiteSpecific);
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+
+        // Without a site-specific match the price alone is still reported
+        let (result, strategy) = extract(&generic, "https://example.com/p").await.unwrap();
+        assert_eq!(strategy, ExtractionStrategy::Meta);
+        assert_eq!(result.status, AvailabilityStatus::Unknown);
+        assert_eq!(result.price.price_minor_units, Some(4999));
+    }
+
+    #[tokio::test]
+    async fn test_extraction_confidence_per_strategy() {
+        let schema_org = html_with_product_offer("http://schema.org/InStock", Some("49.00"), None);
+        let schema_org_price_and_button = r#"<html><head>
+            <script type="application/ld+json">
+            {"@type": "Product", "offers": {"price": "49.00", "priceCurrency": "USD"}}
+            </script>
+            </head><body><button class="add-to-cart">Add to Cart</button></body></html>"#;
+        let gtm = html_with_datalayer_push(
+            r#"{"event": "view_item", "currency": "USD", "items": [{"price": 25.00}]}"#,
+            Some(r#"<button class="add-to-cart">Add to Cart</button>"#),
+        );
+        let meta = r#"<html><head>
+            <meta property="product:price:amount" content="49.99">
+            <meta property="og:availability" content="instock">
+            </head><body></body></html>"#;
+        let amazon = r#"<html><body>
+            <span class="a-price"><span class="a-offscreen">$49.99</span></span>
+            <div id="availability"><span>In Stock</span></div>
+            </body></html>"#;
+
+        let cases = [
+            (
+                schema_org.as_str(),
+                "https://example.com/p",
+                ExtractionStrategy::SchemaOrg,
+                Confidence::High,
+            ),
+            (
+                schema_org_price_and_button,
+                "https://example.com/p",
+                ExtractionStrategy::SchemaOrg,
+                Confidence::Low,
+            ),
+            (
+                gtm.as_str(),
+                "https://example.com/p",
+                ExtractionStrategy::Gtm,
+                Confidence::Low,
+            ),
+            (
+                meta,
+                "https://example.com/p",
+                ExtractionStrategy::Meta,
+                Confidence::Low,
+            ),
+            (
+                amazon,
+                "https://www.amazon.com/dp/B0123",
+                ExtractionStrategy::SiteSpecific,
+                Confidence::High,
+            ),
+        ];
+        for (html, url, expected_strategy, expected_confidence) in cases {
+            let (result, strategy) = extract(html, url).await.unwrap();
+            assert_eq!(strategy, expected_strategy, "{}", url);
+            assert_eq!(
+                result.confidence, expected_confidence,
+                "{:?} for {}",
+                strategy, url
+            );
+        }
+    }
+
+    mod region_redirect_tests {
+        use super::*;
+        use crate::test_utils::{MockResponse, MockServer};
+
+        /// Store that 302-redirects `/item/widget` to `/en-gb/item/widget`, which
+        /// serves a GBP-priced product page. `/item/gadget` 301s to `/item/gadget/`,
+        /// and `/old/gadget` 301s to `/item/gadget` (a chain of two redirects).
+        async fn redirecting_store() -> MockServer {
+            let page = test_html::html_with_product_offer(
+                "http://schema.org/InStock",
+                Some("49.99"),
+                Some("GBP"),
+            );
+
+            MockServer::start(move |request| match request.path() {
+                "/item/widget" => MockResponse::redirect(302, "/en-gb/item/widget"),
+                "/item/gadget" => MockResponse::redirect(301, "/item/gadget/"),
+                "/old/gadget" => MockResponse::redirect(301, "/item/gadget"),
+                _ => MockResponse::html(page.clone()),
+            })
+            .await
+        }
+
+        #[tokio::test]
+        async fn test_redirect_to_other_locale_flags_region_mismatch() {
+            let conn = setup_fetch_db().await;
+            let server = redirecting_store().await;
+
+            let result = ScraperService::check_availability_with_headless(
+                &server.url("/item/widget"),
+                &conn,
+                &fetch_options(),
+                true,
+                None,
+            )
+            .await
+            .unwrap();
+
+            assert!(result.region_mismatch);
+            assert_eq!(result.price.price_currency, Some("GBP".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_direct_locale_page_has_no_region_mismatch() {
+            let conn = setup_fetch_db().await;
+            let server = redirecting_store().await;
+
+            let result = ScraperService::check_availability_with_headless(
+                &server.url("/en-gb/item/widget"),
+                &conn,
+                &fetch_options(),
+                true,
+                None,
+            )
+            .await
+            .unwrap();
+
+            assert!(!result.region_mismatch);
+            assert_eq!(result.price.price_currency, Some("GBP".to_string()));
+        }
+
+        #[tokio::test]
+        async fn test_price_dropped_when_not_tracking_price() {
+            let conn = setup_fetch_db().await;
+            let server = redirecting_store().await;
+
+            let result = ScraperService::check_availability_with_headless(
+                &server.url("/item/widget"),
+                &conn,
+                &fetch_options(),
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result.status, AvailabilityStatus::InStock);
+            assert_eq!(result.price.price_minor_units, None);
+            assert_eq!(result.price.price_currency, None);
+            assert_eq!(result.price.raw_price, None);
+            assert!(!result.region_mismatch);
+        }
+
+        #[tokio::test]
+        async fn test_trailing_slash_redirect_reports_canonical_url() {
+            let conn = setup_fetch_db().await;
+            let server = redirecting_store().await;
+
+            let result = ScraperService::check_availability_with_headless(
+                &server.url("/item/gadget"),
+                &conn,
+                &fetch_options(),
+                true,
+                None,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result.canonical_url, Some(server.url("/item/gadget/")));
+            assert!(!result.region_mismatch);
+        }
+
+        #[tokio::test]
+        async fn test_redirect_chain_to_other_page_has_no_canonical_url() {
+            let conn = setup_fetch_db().await;
+            let server = redirecting_store().await;
+
+            let result = ScraperService::check_availability_with_headless(
+                &server.url("/old/gadget"),
+                &conn,
+                &fetch_options(),
+                true,
+                None,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result.canonical_url, None);
+            assert_eq!(result.status, AvailabilityStatus::InStock);
+        }
+
+        #[tokio::test]
+        async fn test_diagnose_reports_canonical_url() {
+            let conn = setup_fetch_db().await;
+            let server = redirecting_store().await;
+
+            let diagnostics =
+                ScraperService::diagnose(&server.url("/item/gadget"), &conn, &fetch_options())
+                    .await
+                    .unwrap();
+
+            assert_eq!(diagnostics.canonical_url, Some(server.url("/item/gadget/")));
+        }
+
+        #[tokio::test]
+        async fn test_unredirected_page_has_no_canonical_url() {
+            let conn = setup_fetch_db().await;
+            let server = redirecting_store().await;
+
+            let result = ScraperService::check_availability_with_headless(
+                &server.url("/item/gadget/"),
+                &conn,
+                &fetch_options(),
+                true,
+                None,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result.canonical_url, None);
+        }
+    }
 }
@@ -3,6 +3,11 @@
 //! Many modern e-commerce sites use Next.js which embeds page data in a
 //! `<script id="__NEXT_DATA__">` tag. This module provides utilities to
 //! extract and parse this data as a fallback when Schema.org data is not available.
+//!
+//! Sites on the newer app router have no `__NEXT_DATA__` tag; instead the React
+//! Server Components payload is streamed in `self.__next_f.push([...])` chunks.
+//! [`extract_next_f_page_props`] reassembles those chunks and finds the props
+//! object carrying the product, so site parsers can treat it like pageProps.
 
 use scraper::{Html, Selector};
 use serde_json::Value;
@@ -47,6 +52,84 @@ pub fn get_page_props(next_data: &Value) -> Option<&Value> {
     next_data.get("props")?.get("pageProps")
 }
 
+/// Marker that starts each streamed app-router chunk
+const NEXT_F_PUSH_MARKER: &str = "self.__next_f.push(";
+
+/// Keys identifying the props object that carries product data
+const PRODUCT_PROPS_KEYS: &[&str] = &["product", "productDetails"];
+
+/// Reassemble the app-router flight payload from `self.__next_f.push` chunks.
+///
+/// Each chunk is a JSON array; `[1, "..."]` chunks carry consecutive slices of
+/// the payload text, other chunk types (bootstrap, form state, binary) are skipped:
+/// ```html
+/// <script>self.__next_f.push([1,"0:[\"$\",\"div\",null,{}]\n"])</script>
+/// ```
+pub fn extract_next_f_payload(html: &str) -> Result<String, AppError> {
+    let mut payload = String::new();
+    let mut found = false;
+
+    for (start, _) in html.match_indices(NEXT_F_PUSH_MARKER) {
+        let args = &html[start + NEXT_F_PUSH_MARKER.len()..];
+        let Some(Ok(Value::Array(chunk))) = serde_json::Deserializer::from_str(args)
+            .into_iter::<Value>()
+            .next()
+        else {
+            continue;
+        };
+        found = true;
+        if let [Value::Number(kind), Value::String(text)] = chunk.as_slice() {
+            if kind.as_u64() == Some(1) {
+                payload.push_str(text);
+            }
+        }
+    }
+
+    if !found {
+        return Err(AppError::External(
+            "No __next_f streaming data found".to_string(),
+        ));
+    }
+    Ok(payload)
+}
+
+/// Find the props object carrying product data in app-router streaming data.
+///
+/// The flight payload is a list of `<id>:<row>` lines. JSON rows are searched
+/// depth-first for the first object with a `product` or `productDetails` object,
+/// which is returned in place of pageProps. Non-JSON rows (module references,
+/// text) are skipped.
+pub fn extract_next_f_page_props(html: &str) -> Result<Value, AppError> {
+    let payload = extract_next_f_payload(html)?;
+
+    payload
+        .lines()
+        .filter_map(|line| line.split_once(':').map(|(_, row)| row))
+        .filter(|row| row.starts_with('[') || row.starts_with('{'))
+        .filter_map(|row| serde_json::from_str::<Value>(row).ok())
+        .find_map(|row| find_product_props(&row).cloned())
+        .ok_or_else(|| {
+            AppError::External("No product data found in __next_f streaming data".to_string())
+        })
+}
+
+/// Depth-first search for an object holding one of [`PRODUCT_PROPS_KEYS`]
+fn find_product_props(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Object(map) => {
+            if PRODUCT_PROPS_KEYS
+                .iter()
+                .any(|key| map.get(*key).is_some_and(Value::is_object))
+            {
+                return Some(value);
+            }
+            map.values().find_map(find_product_props)
+        }
+        Value::Array(items) => items.iter().find_map(find_product_props),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +281,87 @@ mod tests {
         assert_eq!(product["price"].as_str().unwrap(), "23.99");
         assert_eq!(product["availability"].as_str().unwrap(), "in-stock");
     }
+
+    mod next_f {
+        use super::*;
+
+        /// Wrap flight payload slices in `self.__next_f.push` script tags
+        fn html_with_chunks(chunks: &[&str]) -> String {
+            let scripts: String = std::iter::once(
+                "<script>(self.__next_f=self.__next_f||[]).push([0])</script>".to_string(),
+            )
+            .chain(chunks.iter().map(|chunk| {
+                format!(
+                    "<script>self.__next_f.push([1,{}])</script>",
+                    serde_json::to_string(chunk).unwrap()
+                )
+            }))
+            .collect();
+            format!(
+                "<!DOCTYPE html><html><head></head><body>{}</body></html>",
+                scripts
+            )
+        }
+
+        #[test]
+        fn test_extract_payload_joins_chunks_in_order() {
+            let html = html_with_chunks(&["0:[\"$\",\"di", "v\",null,{}]\n"]);
+
+            let payload = extract_next_f_payload(&html).unwrap();
+
+            assert_eq!(payload, "0:[\"$\",\"div\",null,{}]\n");
+        }
+
+        #[test]
+        fn test_extract_payload_no_chunks() {
+            let result = extract_next_f_payload("<html><body></body></html>");
+
+            match result {
+                Err(AppError::External(msg)) => {
+                    assert!(msg.contains("No __next_f streaming data found"));
+                }
+                other => panic!("Expected External error, got {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_extract_page_props_across_split_chunks() {
+            let html = html_with_chunks(&[
+                "1:I[\"./app/page.js\",[\"app/page\"],\"ProductPage\"]\n",
+                "2:[\"$\",\"$L1\",null,{\"product\":{\"name\":\"Water Wipes\",\"price\":\"23",
+                ".99\",\"availability\":\"in-stock\"}}]\n",
+                "3:[\"$\",\"footer\",null,{}]\n",
+            ]);
+
+            let props = extract_next_f_page_props(&html).unwrap();
+
+            assert_eq!(props["product"]["name"], "Water Wipes");
+            assert_eq!(props["product"]["price"], "23.99");
+        }
+
+        #[test]
+        fn test_extract_page_props_skips_text_rows_and_references() {
+            let html = html_with_chunks(&[
+                "4:T1c,not json but mentions product\n",
+                "5:{\"product\":\"$L6\"}\n",
+                "6:[\"$\",\"section\",null,{\"productDetails\":{\"Product\":{\"IsInStock\":true}}}]\n",
+            ]);
+
+            let props = extract_next_f_page_props(&html).unwrap();
+
+            assert_eq!(props["productDetails"]["Product"]["IsInStock"], true);
+        }
+
+        #[test]
+        fn test_extract_page_props_no_product() {
+            let html = html_with_chunks(&["0:[\"$\",\"div\",null,{\"children\":\"Hi\"}]\n"]);
+
+            let result = extract_next_f_page_props(&html);
+
+            match result {
+                Err(AppError::External(msg)) => assert!(msg.contains("No product data found")),
+                other => panic!("Expected External error, got {:?}", other),
+            }
+        }
+    }
 }
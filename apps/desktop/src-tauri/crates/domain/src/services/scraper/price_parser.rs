@@ -1,17 +1,37 @@
 //! Price parsing utilities for extracting and normalizing prices from Schema.org data.
 
+use chrono::NaiveDate;
 use rust_decimal::Decimal;
+use serde::Serialize;
 use std::str::FromStr;
 use url::Url;
 
 use super::super::currency;
 
+/// Schema.org `priceType` values marking the pre-sale ("was") price of an offer
+const COMPARE_AT_PRICE_TYPES: &[&str] = &["strikethroughprice", "listprice"];
+
 /// Price information extracted from Schema.org data
 #[derive(Debug, Clone, Default)]
 pub struct PriceInfo {
     pub price_minor_units: Option<i64>,
     pub price_currency: Option<String>,
     pub raw_price: Option<String>,
+    /// Strikethrough ("was") price in minor units, only set when above the current price
+    pub compare_at_minor_units: Option<i64>,
+    /// Last day the offer's price is valid (`priceValidUntil`)
+    pub price_valid_until: Option<NaiveDate>,
+    /// Range across the offers of an `AggregateOffer`, whose low price is tracked
+    pub price_range: Option<PriceRange>,
+}
+
+/// Lowest and highest price of an `AggregateOffer`, in the tracked price's currency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PriceRange {
+    pub low_minor_units: i64,
+    pub high_minor_units: i64,
+    /// Number of offers the range covers (`offerCount`), when given
+    pub offer_count: Option<u32>,
 }
 
 /// Parse a price string to minor units (smallest currency unit) using exact decimal arithmetic.
@@ -41,6 +61,24 @@ pub fn parse_price_to_minor_units(price_str: &str, currency_code: Option<&str>)
     minor_units.round().to_string().parse::<i64>().ok()
 }
 
+/// Convert European-style "1.234,56" prices to "1234.56".
+///
+/// A comma followed by exactly two digits at the end is treated as the decimal separator.
+pub fn normalize_decimal_comma(price: &str) -> String {
+    let digits: String = price
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+
+    match digits.rfind(',') {
+        Some(pos) if digits.len() - pos == 3 => {
+            let (whole, fraction) = digits.split_at(pos);
+            format!("{}.{}", whole.replace('.', ""), &fraction[1..])
+        }
+        _ => digits.replace(',', ""),
+    }
+}
+
 /// Domain suffix to currency code mappings
 /// Within each inner slice, more specific suffixes (e.g., ".com.au") must come before
 /// generic ones (e.g., ".au") so that `ends_with` matches the longest suffix first.
@@ -132,6 +170,27 @@ pub fn has_path_locale(url: &str) -> bool {
     infer_currency_from_path(url).is_some()
 }
 
+/// Check if a redirect landed on a different region than the requested URL
+///
+/// True when the final URL's host (ignoring `www.`) or path locale currency
+/// differs from the requested one, e.g. an IP-based redirect from `/products/x`
+/// to `/en-gb/products/x`. Prices on such pages may be in the wrong currency.
+pub fn is_region_redirect(requested_url: &str, final_url: &str) -> bool {
+    let host = |url: &str| {
+        Url::parse(url).ok().and_then(|parsed| {
+            parsed.host_str().map(|host| {
+                let host = host.to_lowercase();
+                host.strip_prefix("www.")
+                    .map(str::to_string)
+                    .unwrap_or(host)
+            })
+        })
+    };
+
+    host(requested_url) != host(final_url)
+        || infer_currency_from_path(requested_url) != infer_currency_from_path(final_url)
+}
+
 /// Extract price info from an offer object
 ///
 /// Currency is determined in order of precedence:
@@ -139,15 +198,32 @@ pub fn has_path_locale(url: &str) -> bool {
 /// 2. Currency from the offer data (API-provided, reflects what the store charges)
 /// 3. Inferred from the store's domain TLD (e.g., .com.au → AUD) - weakest heuristic
 /// 4. None if none of the above are available
+///
+/// When the offer has no `price` of its own, the price and currency are read from
+/// its `priceSpecification` instead. A `priceSpecification` with a strikethrough or
+/// list `priceType` is read as the compare-at price, and `priceValidUntil` is kept so stale offers can be spotted.
+///
+/// For an `AggregateOffer`, `lowPrice` is the tracked price and the range up to
+/// `highPrice` is recorded.
 pub fn get_price_from_offer(offer: &serde_json::Value, url: &str) -> PriceInfo {
-    let raw_price = offer.get("price").and_then(|p| match p {
-        serde_json::Value::String(s) => Some(s.clone()),
-        serde_json::Value::Number(n) => Some(n.to_string()),
-        _ => None,
+    let is_aggregate = is_aggregate_offer(offer);
+    let raw_price = is_aggregate
+        .then(|| offer.get("lowPrice").and_then(scalar_to_string))
+        .flatten()
+        .or_else(|| offer.get("price").and_then(scalar_to_string));
+    let current_spec = raw_price
+        .is_none()
+        .then(|| get_current_price_spec(offer))
+        .flatten();
+    let raw_price = raw_price.or_else(|| {
+        current_spec
+            .and_then(|spec| spec.get("price"))
+            .and_then(scalar_to_string)
     });
 
     let api_currency = offer
         .get("priceCurrency")
+        .or_else(|| current_spec.and_then(|spec| spec.get("priceCurrency")))
         .and_then(|c| c.as_str())
         .map(|s| s.to_string());
 
@@ -164,10 +240,131 @@ pub fn get_price_from_offer(offer: &serde_json::Value, url: &str) -> PriceInfo {
         .as_ref()
         .and_then(|p| parse_price_to_minor_units(p, price_currency.as_deref()));
 
+    let compare_at_minor_units = get_compare_at_from_offer(offer)
+        .and_then(|p| parse_price_to_minor_units(&p, price_currency.as_deref()));
+
+    let price_range = if is_aggregate {
+        get_price_range(offer, price_minor_units, price_currency.as_deref())
+    } else {
+        None
+    };
+
     PriceInfo {
         price_minor_units,
         price_currency,
         raw_price,
+        compare_at_minor_units: compare_at_above_price(price_minor_units, compare_at_minor_units),
+        price_valid_until: offer
+            .get("priceValidUntil")
+            .and_then(|v| v.as_str())
+            .and_then(parse_price_valid_until),
+        price_range,
+    }
+}
+
+/// Whether an offer's `@type` (a string or an array of types) is `AggregateOffer`
+fn is_aggregate_offer(offer: &serde_json::Value) -> bool {
+    match offer.get("@type") {
+        Some(serde_json::Value::String(t)) => t == "AggregateOffer",
+        Some(serde_json::Value::Array(types)) => {
+            types.iter().any(|t| t.as_str() == Some("AggregateOffer"))
+        }
+        _ => false,
+    }
+}
+
+/// Build an `AggregateOffer`'s price range from its parsed low price and `highPrice`.
+///
+/// A missing or unparseable `highPrice`, or one below the low price, yields no range.
+fn get_price_range(
+    offer: &serde_json::Value,
+    low_minor_units: Option<i64>,
+    currency_code: Option<&str>,
+) -> Option<PriceRange> {
+    let low_minor_units = low_minor_units?;
+    let high_minor_units = offer
+        .get("highPrice")
+        .and_then(scalar_to_string)
+        .and_then(|p| parse_price_to_minor_units(&p, currency_code))
+        .filter(|high| *high >= low_minor_units)?;
+    let offer_count = match offer.get("offerCount") {
+        Some(serde_json::Value::Number(n)) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+        Some(serde_json::Value::String(s)) => s.trim().parse().ok(),
+        _ => None,
+    };
+
+    Some(PriceRange {
+        low_minor_units,
+        high_minor_units,
+        offer_count,
+    })
+}
+
+/// Parse a `priceValidUntil` value, either a date ("2025-12-31") or a
+/// datetime ("2025-12-31T23:59:59Z") of which only the date is kept
+fn parse_price_valid_until(value: &str) -> Option<NaiveDate> {
+    let date = value.trim().get(..10)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// An offer's `priceSpecification` entries (a single object or an array)
+fn price_specifications(offer: &serde_json::Value) -> Vec<&serde_json::Value> {
+    match offer.get("priceSpecification") {
+        Some(serde_json::Value::Array(specs)) => specs.iter().collect(),
+        Some(spec) => vec![spec],
+        None => Vec::new(),
+    }
+}
+
+/// Find the `priceSpecification` holding the current price: the first one with a
+/// `price` that isn't a strikethrough/list price
+fn get_current_price_spec(offer: &serde_json::Value) -> Option<&serde_json::Value> {
+    price_specifications(offer).into_iter().find(|spec| {
+        spec.get("price").and_then(scalar_to_string).is_some()
+            && !spec
+                .get("priceType")
+                .and_then(|t| t.as_str())
+                .is_some_and(is_compare_at_price_type)
+    })
+}
+
+/// Find the raw strikethrough/list price in an offer's `priceSpecification`
+fn get_compare_at_from_offer(offer: &serde_json::Value) -> Option<String> {
+    price_specifications(offer)
+        .into_iter()
+        .filter(|spec| {
+            spec.get("priceType")
+                .and_then(|t| t.as_str())
+                .is_some_and(is_compare_at_price_type)
+        })
+        .find_map(|spec| spec.get("price").and_then(scalar_to_string))
+}
+
+/// Whether a `priceType` (full schema.org URL or bare name) is a strikethrough/list price
+fn is_compare_at_price_type(price_type: &str) -> bool {
+    let name = price_type.rsplit('/').next().unwrap_or(price_type);
+    COMPARE_AT_PRICE_TYPES.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// Keep a compare-at price only when it is above the current price.
+///
+/// Stores often repeat the current price as the "list" price when nothing is on sale.
+pub fn compare_at_above_price(
+    price_minor_units: Option<i64>,
+    compare_at_minor_units: Option<i64>,
+) -> Option<i64> {
+    match (price_minor_units, compare_at_minor_units) {
+        (Some(price), Some(compare_at)) if compare_at > price => Some(compare_at),
+        _ => None,
+    }
+}
+
+/// Read an ID or price that may be encoded as a JSON string or number
+pub(super) fn scalar_to_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
     }
 }
 
@@ -175,6 +372,20 @@ pub fn get_price_from_offer(offer: &serde_json::Value, url: &str) -> PriceInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_scalar_to_string() {
+        assert_eq!(
+            scalar_to_string(&serde_json::json!("12345")),
+            Some("12345".to_string())
+        );
+        assert_eq!(
+            scalar_to_string(&serde_json::json!(199.99)),
+            Some("199.99".to_string())
+        );
+        assert_eq!(scalar_to_string(&serde_json::json!(null)), None);
+        assert_eq!(scalar_to_string(&serde_json::json!({"price": 1})), None);
+    }
+
     #[test]
     fn test_parse_price_simple() {
         assert_eq!(
@@ -241,6 +452,22 @@ mod tests {
         // JPY: factor = 1 (no fractional units)
         assert_eq!(parse_price_to_minor_units("1500", Some("JPY")), Some(1500));
         assert_eq!(parse_price_to_minor_units("2980", Some("JPY")), Some(2980));
+        assert_eq!(
+            parse_price_to_minor_units("69300", Some("JPY")),
+            Some(69300)
+        );
+        assert_eq!(
+            parse_price_to_minor_units("¥69,300", Some("JPY")),
+            Some(69300)
+        );
+    }
+
+    #[test]
+    fn test_parse_price_krw_zero_decimals() {
+        assert_eq!(
+            parse_price_to_minor_units("₩1,290,000", Some("KRW")),
+            Some(1290000)
+        );
     }
 
     #[test]
@@ -289,6 +516,235 @@ mod tests {
         assert_eq!(price.raw_price, None);
     }
 
+    #[test]
+    fn test_get_price_from_offer_strikethrough_price() {
+        let offer = serde_json::json!({
+            "price": "69.99",
+            "priceCurrency": "USD",
+            "priceSpecification": {
+                "@type": "UnitPriceSpecification",
+                "priceType": "https://schema.org/StrikethroughPrice",
+                "price": "99.99",
+                "priceCurrency": "USD"
+            }
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_minor_units, Some(6999));
+        assert_eq!(price.compare_at_minor_units, Some(9999));
+    }
+
+    #[test]
+    fn test_get_price_from_offer_list_price_in_array() {
+        let offer = serde_json::json!({
+            "price": 1500,
+            "priceCurrency": "JPY",
+            "priceSpecification": [
+                {"@type": "UnitPriceSpecification", "price": 1500},
+                {"@type": "UnitPriceSpecification", "priceType": "ListPrice", "price": 2000}
+            ]
+        });
+        let price = get_price_from_offer(&offer, "https://example.jp/product");
+        assert_eq!(price.compare_at_minor_units, Some(2000));
+    }
+
+    #[test]
+    fn test_get_price_from_offer_price_specification_only() {
+        let offer = serde_json::json!({
+            "@type": "Offer",
+            "priceSpecification": {
+                "@type": "UnitPriceSpecification",
+                "price": "24.95",
+                "priceCurrency": "GBP"
+            }
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_minor_units, Some(2495));
+        assert_eq!(price.price_currency, Some("GBP".to_string()));
+        assert_eq!(price.raw_price, Some("24.95".to_string()));
+        assert_eq!(price.compare_at_minor_units, None);
+    }
+
+    #[test]
+    fn test_get_price_from_offer_price_specification_array() {
+        let offer = serde_json::json!({
+            "@type": "Offer",
+            "priceSpecification": [
+                {"priceType": "https://schema.org/StrikethroughPrice", "price": 129.0, "priceCurrency": "EUR"},
+                {"@type": "UnitPriceSpecification", "price": 99.0, "priceCurrency": "EUR"}
+            ]
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_minor_units, Some(9900));
+        assert_eq!(price.price_currency, Some("EUR".to_string()));
+        assert_eq!(price.compare_at_minor_units, Some(12900));
+    }
+
+    #[test]
+    fn test_get_price_from_offer_prefers_direct_price_over_specification() {
+        let offer = serde_json::json!({
+            "price": "10.00",
+            "priceCurrency": "USD",
+            "priceSpecification": {"price": "12.00", "priceCurrency": "CAD"}
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_minor_units, Some(1000));
+        assert_eq!(price.price_currency, Some("USD".to_string()));
+    }
+
+    #[test]
+    fn test_get_price_from_offer_only_compare_at_specification_has_no_price() {
+        let offer = serde_json::json!({
+            "priceSpecification": {"priceType": "ListPrice", "price": "20.00"}
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_minor_units, None);
+        assert_eq!(price.raw_price, None);
+    }
+
+    #[test]
+    fn test_get_price_from_offer_ignores_compare_at_not_above_price() {
+        let offer = serde_json::json!({
+            "price": "99.99",
+            "priceCurrency": "USD",
+            "priceSpecification": {
+                "priceType": "https://schema.org/ListPrice",
+                "price": "99.99"
+            }
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.compare_at_minor_units, None);
+    }
+
+    #[test]
+    fn test_get_price_from_offer_ignores_other_price_types() {
+        let offer = serde_json::json!({
+            "price": "49.99",
+            "priceCurrency": "USD",
+            "priceSpecification": {
+                "priceType": "https://schema.org/SRP",
+                "price": "79.99"
+            }
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.compare_at_minor_units, None);
+    }
+
+    #[test]
+    fn test_get_price_from_offer_price_valid_until_date() {
+        let offer = serde_json::json!({
+            "price": "49.99",
+            "priceCurrency": "USD",
+            "priceValidUntil": "2020-01-31"
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(
+            price.price_valid_until,
+            NaiveDate::from_ymd_opt(2020, 1, 31)
+        );
+    }
+
+    #[test]
+    fn test_get_price_from_offer_price_valid_until_datetime() {
+        let offer = serde_json::json!({
+            "price": "49.99",
+            "priceCurrency": "USD",
+            "priceValidUntil": "2099-12-31T23:59:59Z"
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(
+            price.price_valid_until,
+            NaiveDate::from_ymd_opt(2099, 12, 31)
+        );
+    }
+
+    #[test]
+    fn test_get_price_from_offer_price_valid_until_invalid_or_missing() {
+        let offer = serde_json::json!({"price": "49.99", "priceValidUntil": "soon"});
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_valid_until, None);
+
+        let offer = serde_json::json!({"price": "49.99"});
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_valid_until, None);
+    }
+
+    #[test]
+    fn test_get_price_from_offer_aggregate_offer_uses_low_price() {
+        let offer = serde_json::json!({
+            "@type": "AggregateOffer",
+            "lowPrice": "1,299",
+            "highPrice": "1,850",
+            "offerCount": "3",
+            "priceCurrency": "JPY"
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_minor_units, Some(1299));
+        assert_eq!(price.price_currency, Some("JPY".to_string()));
+        assert_eq!(
+            price.price_range,
+            Some(PriceRange {
+                low_minor_units: 1299,
+                high_minor_units: 1850,
+                offer_count: Some(3),
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_price_from_offer_aggregate_offer_without_usable_high_price() {
+        for high_price in [serde_json::json!(null), serde_json::json!("5.00")] {
+            let offer = serde_json::json!({
+                "@type": ["AggregateOffer"],
+                "lowPrice": "10.00",
+                "highPrice": high_price,
+                "priceCurrency": "USD"
+            });
+            let price = get_price_from_offer(&offer, "https://example.com/product");
+            assert_eq!(price.price_minor_units, Some(1000));
+            assert_eq!(price.price_range, None);
+        }
+    }
+
+    #[test]
+    fn test_get_price_from_offer_aggregate_offer_falls_back_to_price() {
+        let offer = serde_json::json!({
+            "@type": "AggregateOffer",
+            "price": "12.00",
+            "highPrice": "18.00",
+            "priceCurrency": "USD"
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_minor_units, Some(1200));
+        assert_eq!(
+            price
+                .price_range
+                .map(|r| (r.low_minor_units, r.high_minor_units)),
+            Some((1200, 1800))
+        );
+    }
+
+    #[test]
+    fn test_get_price_from_offer_ignores_low_price_on_plain_offer() {
+        let offer = serde_json::json!({
+            "@type": "Offer",
+            "price": "30.00",
+            "lowPrice": "20.00",
+            "highPrice": "40.00",
+            "priceCurrency": "USD"
+        });
+        let price = get_price_from_offer(&offer, "https://example.com/product");
+        assert_eq!(price.price_minor_units, Some(3000));
+        assert_eq!(price.price_range, None);
+    }
+
+    #[test]
+    fn test_compare_at_above_price() {
+        assert_eq!(compare_at_above_price(Some(7000), Some(10000)), Some(10000));
+        assert_eq!(compare_at_above_price(Some(7000), Some(7000)), None);
+        assert_eq!(compare_at_above_price(Some(7000), None), None);
+        assert_eq!(compare_at_above_price(None, Some(10000)), None);
+    }
+
     #[test]
     fn test_get_price_from_offer_jpy() {
         let offer = serde_json::json!({
@@ -311,6 +767,17 @@ mod tests {
         assert_eq!(price.price_currency, Some("KWD".to_string()));
     }
 
+    #[test]
+    fn test_get_price_from_offer_zero_and_three_decimal_numbers() {
+        let jpy = serde_json::json!({"price": 69300, "priceCurrency": "JPY"});
+        let price = get_price_from_offer(&jpy, "https://example.com/product");
+        assert_eq!(price.price_minor_units, Some(69300));
+
+        let kwd = serde_json::json!({"price": 12.345, "priceCurrency": "KWD"});
+        let price = get_price_from_offer(&kwd, "https://example.com/product");
+        assert_eq!(price.price_minor_units, Some(12345));
+    }
+
     #[test]
     fn test_infer_currency_from_domain() {
         assert_eq!(
@@ -513,4 +980,41 @@ mod tests {
             "https://example.com/products/item?locale=en-au"
         ));
     }
+
+    #[test]
+    fn test_is_region_redirect() {
+        // Same page, trailing redirects and www. don't count
+        assert!(!is_region_redirect(
+            "https://store.com/products/item",
+            "https://www.store.com/products/item?ref=1"
+        ));
+        // Equivalent locales for the same currency
+        assert!(!is_region_redirect(
+            "https://store.com/en-au/products/item",
+            "https://store.com/au/products/item"
+        ));
+
+        // Redirected into (or out of) a locale
+        assert!(is_region_redirect(
+            "https://store.com/products/item",
+            "https://store.com/en-gb/products/item"
+        ));
+        assert!(is_region_redirect(
+            "https://store.com/en-au/products/item",
+            "https://store.com/products/item"
+        ));
+        // Redirected to another country's store
+        assert!(is_region_redirect(
+            "https://store.com/products/item",
+            "https://store.com.au/products/item"
+        ));
+    }
+
+    #[test]
+    fn test_normalize_decimal_comma() {
+        assert_eq!(normalize_decimal_comma("$1,234.56"), "1234.56");
+        assert_eq!(normalize_decimal_comma("1.234,56 €"), "1234.56");
+        assert_eq!(normalize_decimal_comma("49,99"), "49.99");
+        assert_eq!(normalize_decimal_comma("¥1,980"), "1980");
+    }
 }
@@ -0,0 +1,146 @@
+//! Per-host rate limiting for page fetches.
+//!
+//! Each host observes its own minimum interval between requests, so a bulk
+//! check hitting one store many times is spaced out while requests to other
+//! stores proceed immediately. Hosts are derived with `Url::host_str`.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use tokio::time::Instant;
+use url::Url;
+
+/// Tracks when each host may next be requested.
+#[derive(Default)]
+pub struct HostRateLimiter {
+    /// Earliest time the next request to each host may start
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limiter shared by all fetches in the process, so single checks and bulk
+    /// checks running at the same time are spaced against each other.
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<HostRateLimiter> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Wait until `url`'s host may be requested again, claiming the next slot.
+    ///
+    /// Slots are claimed before sleeping, so concurrent callers for the same host
+    /// queue up `min_interval` apart. URLs without a host and a zero interval
+    /// never wait.
+    pub async fn wait(&self, url: &str, min_interval: Duration) {
+        if min_interval.is_zero() {
+            return;
+        }
+        let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(str::to_lowercase))
+        else {
+            return;
+        };
+
+        let start_at = {
+            let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let start_at = next_slot
+                .get(&host)
+                .copied()
+                .filter(|slot| *slot > now)
+                .unwrap_or(now);
+            next_slot.insert(host, start_at + min_interval);
+            start_at
+        };
+
+        if start_at > Instant::now() {
+            log::debug!("Rate limiting request to {}", url);
+            tokio::time::sleep_until(start_at).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    #[tokio::test]
+    async fn test_first_request_to_host_is_immediate() {
+        let limiter = HostRateLimiter::new();
+        let started = Instant::now();
+
+        limiter.wait("https://shop.example.com/a", INTERVAL).await;
+
+        assert!(started.elapsed() < INTERVAL);
+    }
+
+    #[tokio::test]
+    async fn test_same_host_requests_are_spaced() {
+        let limiter = HostRateLimiter::new();
+        let started = Instant::now();
+
+        limiter.wait("https://shop.example.com/a", INTERVAL).await;
+        limiter.wait("https://SHOP.example.com/b", INTERVAL).await;
+
+        assert!(started.elapsed() >= INTERVAL);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_same_host_requests_queue_up() {
+        let limiter = HostRateLimiter::new();
+        let started = Instant::now();
+
+        tokio::join!(
+            limiter.wait("https://shop.example.com/a", INTERVAL),
+            limiter.wait("https://shop.example.com/b", INTERVAL),
+            limiter.wait("https://shop.example.com/c", INTERVAL),
+        );
+
+        assert!(started.elapsed() >= INTERVAL * 2);
+    }
+
+    #[tokio::test]
+    async fn test_different_hosts_proceed_immediately() {
+        let limiter = HostRateLimiter::new();
+        let started = Instant::now();
+
+        limiter.wait("https://one.example.com/a", INTERVAL).await;
+        limiter.wait("https://two.example.com/a", INTERVAL).await;
+        limiter.wait("https://three.example.org/a", INTERVAL).await;
+
+        assert!(started.elapsed() < INTERVAL);
+    }
+
+    #[tokio::test]
+    async fn test_zero_interval_never_waits() {
+        let limiter = HostRateLimiter::new();
+        let started = Instant::now();
+
+        limiter
+            .wait("https://shop.example.com/a", Duration::ZERO)
+            .await;
+        limiter
+            .wait("https://shop.example.com/b", Duration::ZERO)
+            .await;
+
+        assert!(started.elapsed() < INTERVAL);
+    }
+
+    #[tokio::test]
+    async fn test_unparseable_url_never_waits() {
+        let limiter = HostRateLimiter::new();
+        let started = Instant::now();
+
+        limiter.wait("not a url", INTERVAL).await;
+        limiter.wait("not a url", INTERVAL).await;
+
+        assert!(started.elapsed() < INTERVAL);
+    }
+}
@@ -0,0 +1,570 @@
+//! robots.txt fetching, parsing and caching.
+//!
+//! Before a page is fetched, the site's robots.txt is consulted so URLs
+//! disallowed for us are skipped and `Crawl-delay` spaces out requests to the
+//! host. Parsed rules are cached per origin for a day. A missing or unreachable
+//! robots.txt allows everything, as crawlers conventionally treat it; when it
+//! couldn't be fetched, that is only cached briefly so it's retried soon.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use product_stalker_core::AppError;
+use tokio::time::Instant;
+use url::Url;
+
+use super::http_client::{self, FetchOptions};
+use super::rate_limiter::HostRateLimiter;
+use super::USER_AGENT;
+
+/// Product token matched against `User-agent` lines; groups for `*` apply otherwise
+pub const ROBOTS_USER_AGENT_TOKEN: &str = "productstalker";
+
+/// How long parsed robots.txt rules are reused before fetching again
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long the allow-all fallback is reused after robots.txt couldn't be fetched
+/// (network error or server error), before trying again
+const FAILURE_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Longest `Crawl-delay` honored, so a huge value can't stall checks indefinitely
+const MAX_CRAWL_DELAY: Duration = Duration::from_secs(60);
+
+/// Only the start of an oversized robots.txt is parsed
+const MAX_ROBOTS_BYTES: usize = 500 * 1024;
+
+/// Upper bound on how long fetching robots.txt may take
+const MAX_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An `Allow` or `Disallow` line
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RobotsRule {
+    allow: bool,
+    pattern: String,
+}
+
+/// Rules from the robots.txt group that applies to us
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RobotsRules {
+    rules: Vec<RobotsRule>,
+    /// Requested minimum interval between requests, capped at [`MAX_CRAWL_DELAY`]
+    pub crawl_delay: Option<Duration>,
+}
+
+/// A `User-agent` group being parsed
+#[derive(Default)]
+struct RobotsGroup {
+    agents: Vec<String>,
+    rules: Vec<RobotsRule>,
+    crawl_delay: Option<Duration>,
+}
+
+impl RobotsRules {
+    /// Parse robots.txt content, keeping the rules that apply to `user_agent_token`.
+    ///
+    /// Groups naming the token (case-insensitive) take precedence over `*` groups;
+    /// several matching groups are merged. Unknown directives are ignored.
+    pub fn parse(content: &str, user_agent_token: &str) -> Self {
+        let token = user_agent_token.to_lowercase();
+        let mut groups: Vec<RobotsGroup> = Vec::new();
+        let mut in_agent_lines = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            if key == "user-agent" {
+                if !in_agent_lines {
+                    groups.push(RobotsGroup::default());
+                    in_agent_lines = true;
+                }
+                if let Some(group) = groups.last_mut() {
+                    group.agents.push(value.to_lowercase());
+                }
+                continue;
+            }
+
+            in_agent_lines = false;
+            let Some(group) = groups.last_mut() else {
+                continue;
+            };
+            match key.as_str() {
+                "allow" | "disallow" if !value.is_empty() => group.rules.push(RobotsRule {
+                    allow: key == "allow",
+                    pattern: value.to_string(),
+                }),
+                "crawl-delay" => {
+                    group.crawl_delay = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|secs| secs.is_finite() && *secs > 0.0)
+                        .map(|secs| {
+                            Duration::from_secs_f64(secs.min(MAX_CRAWL_DELAY.as_secs_f64()))
+                        });
+                }
+                _ => {}
+            }
+        }
+
+        let names_token = |group: &RobotsGroup| {
+            group
+                .agents
+                .iter()
+                .any(|agent| agent != "*" && token.contains(agent.as_str()))
+        };
+        let matching: Vec<RobotsGroup> = if groups.iter().any(names_token) {
+            groups.into_iter().filter(|g| names_token(g)).collect()
+        } else {
+            groups
+                .into_iter()
+                .filter(|g| g.agents.iter().any(|agent| agent == "*"))
+                .collect()
+        };
+
+        let mut rules = Self::default();
+        for group in matching {
+            rules.rules.extend(group.rules);
+            rules.crawl_delay = rules.crawl_delay.max(group.crawl_delay);
+        }
+        rules
+    }
+
+    /// Whether a path (with query string) may be fetched.
+    ///
+    /// The longest matching pattern wins, with `Allow` winning ties. Paths no
+    /// rule matches are allowed, as is robots.txt itself.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        if path == "/robots.txt" {
+            return true;
+        }
+        let winner = self
+            .rules
+            .iter()
+            .filter(|rule| pattern_matches(&rule.pattern, path))
+            .max_by_key(|rule| (rule.pattern.len(), rule.allow));
+        match winner {
+            Some(rule) => rule.allow,
+            None => true,
+        }
+    }
+
+    /// Whether a URL may be fetched; URLs that can't be parsed are left to the
+    /// HTTP request to report
+    pub fn is_url_allowed(&self, url: &str) -> bool {
+        match Url::parse(url) {
+            Ok(parsed) => self.is_allowed(&path_with_query(&parsed)),
+            Err(_) => true,
+        }
+    }
+}
+
+/// Path and query of a URL, as matched by robots.txt patterns
+fn path_with_query(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+/// Match a robots.txt pattern against a path: a prefix match where `*` matches
+/// any run of characters and a trailing `$` anchors to the end of the path
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        let is_last = i == parts.len() - 1;
+        if is_last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+/// Rules fetched for one origin
+struct CachedRobots {
+    rules: Arc<RobotsRules>,
+    expires_at: Instant,
+}
+
+/// Parsed robots.txt rules per origin (scheme, host and port)
+#[derive(Default)]
+pub struct RobotsCache {
+    entries: Mutex<HashMap<String, CachedRobots>>,
+}
+
+impl RobotsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache shared by all fetches in the process
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<RobotsCache> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Get the rules for a URL's origin, fetching robots.txt when not cached.
+    ///
+    /// The fetch goes through the same client as page fetches (so `insecure_hosts`
+    /// and the redirect limit apply) and waits its turn under the per-host rate
+    /// limit. Returns `None` for URLs without a host.
+    pub async fn rules_for(&self, url: &str, options: &FetchOptions) -> Option<Arc<RobotsRules>> {
+        let parsed = Url::parse(url).ok()?;
+        parsed.host_str()?;
+        let origin = parsed.origin().ascii_serialization();
+
+        if let Some(rules) = self.cached(&origin) {
+            return Some(rules);
+        }
+
+        let robots_url = format!("{}/robots.txt", origin);
+        HostRateLimiter::global()
+            .wait(
+                &robots_url,
+                Duration::from_millis(options.timeouts.min_host_interval_ms),
+            )
+            .await;
+        let (rules, ttl) = match fetch_robots(&robots_url, options).await {
+            Ok(rules) => (rules, CACHE_TTL),
+            Err(e) => {
+                log::debug!("Failed to fetch {}: {}", robots_url, e);
+                (RobotsRules::default(), FAILURE_CACHE_TTL)
+            }
+        };
+
+        let rules = Arc::new(rules);
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                origin,
+                CachedRobots {
+                    rules: Arc::clone(&rules),
+                    expires_at: Instant::now() + ttl,
+                },
+            );
+        Some(rules)
+    }
+
+    fn cached(&self, origin: &str) -> Option<Arc<RobotsRules>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(origin)
+            .filter(|entry| entry.expires_at > Instant::now())
+            .map(|entry| Arc::clone(&entry.rules))
+    }
+}
+
+/// Fetch and parse a robots.txt, reading at most [`MAX_ROBOTS_BYTES`] of it.
+///
+/// A client error status (e.g. 404) means there is no robots.txt, which allows
+/// everything. Network errors, server errors and rate limiting (429) fail, as the
+/// site may just be briefly unavailable.
+async fn fetch_robots(robots_url: &str, options: &FetchOptions) -> Result<RobotsRules, AppError> {
+    let timeout = Duration::from_secs(options.timeouts.http_secs).min(MAX_FETCH_TIMEOUT);
+    let mut response = http_client::client_for_url(robots_url, options)?
+        .get(robots_url)
+        .timeout(timeout)
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .await
+        .map_err(|e| AppError::External(e.to_string()))?;
+
+    let status = response.status();
+    if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(AppError::External(format!("HTTP {}", status)));
+    }
+    if !status.is_success() {
+        log::debug!("No robots.txt at {} ({})", robots_url, status);
+        return Ok(RobotsRules::default());
+    }
+
+    let mut content = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| AppError::External(e.to_string()))?
+    {
+        let remaining = MAX_ROBOTS_BYTES - content.len();
+        content.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+        if content.len() == MAX_ROBOTS_BYTES {
+            log::debug!(
+                "Only parsing the first {} bytes of {}",
+                MAX_ROBOTS_BYTES,
+                robots_url
+            );
+            break;
+        }
+    }
+
+    Ok(RobotsRules::parse(
+        &String::from_utf8_lossy(&content),
+        ROBOTS_USER_AGENT_TOKEN,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::scraper::DEFAULT_MAX_JSON_LD_BLOCK_BYTES;
+    use crate::services::{FetchTimeouts, ProductGroupStrategy};
+    use crate::test_utils::{MockResponse, MockServer};
+    use tokio::net::TcpListener;
+
+    const ROBOTS_TXT: &str = "\
+# Example store
+User-agent: Googlebot
+Disallow: /
+
+User-agent: *
+Disallow: /cart
+Disallow: /search?
+Disallow: /*.pdf$
+Allow: /cart/share
+Crawl-delay: 2
+
+Sitemap: https://shop.example.com/sitemap.xml
+";
+
+    fn rules() -> RobotsRules {
+        RobotsRules::parse(ROBOTS_TXT, ROBOTS_USER_AGENT_TOKEN)
+    }
+
+    #[test]
+    fn test_wildcard_group_applies_when_not_named() {
+        let rules = rules();
+
+        assert!(rules.is_allowed("/products/widget"));
+        assert!(!rules.is_allowed("/cart"));
+        assert!(!rules.is_allowed("/cart/checkout"));
+        assert_eq!(rules.crawl_delay, Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_longest_match_wins_and_allow_wins_ties() {
+        let rules = rules();
+        assert!(rules.is_allowed("/cart/share"));
+
+        let tied = RobotsRules::parse(
+            "User-agent: *\nDisallow: /item\nAllow: /item\n",
+            ROBOTS_USER_AGENT_TOKEN,
+        );
+        assert!(tied.is_allowed("/item/1"));
+    }
+
+    #[test]
+    fn test_wildcard_and_end_anchor_patterns() {
+        let rules = rules();
+
+        assert!(!rules.is_allowed("/manuals/widget.pdf"));
+        assert!(rules.is_allowed("/manuals/widget.pdf?download=1"));
+        assert!(!rules.is_allowed("/search?q=widget"));
+        assert!(rules.is_allowed("/search"));
+    }
+
+    #[test]
+    fn test_named_group_takes_precedence_over_wildcard() {
+        let content = "\
+User-agent: *
+Disallow: /
+
+User-agent: ProductStalker
+Disallow: /private
+Crawl-delay: 5
+";
+        let rules = RobotsRules::parse(content, ROBOTS_USER_AGENT_TOKEN);
+
+        assert!(rules.is_allowed("/products/widget"));
+        assert!(!rules.is_allowed("/private/page"));
+        assert_eq!(rules.crawl_delay, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_other_bots_groups_are_ignored() {
+        let content = "User-agent: Googlebot\nDisallow: /\nCrawl-delay: 10\n";
+        let rules = RobotsRules::parse(content, ROBOTS_USER_AGENT_TOKEN);
+
+        assert!(rules.is_allowed("/anything"));
+        assert_eq!(rules.crawl_delay, None);
+    }
+
+    #[test]
+    fn test_empty_disallow_allows_everything() {
+        let rules = RobotsRules::parse("User-agent: *\nDisallow:\n", ROBOTS_USER_AGENT_TOKEN);
+
+        assert!(rules.is_allowed("/"));
+        assert!(rules.is_allowed("/cart"));
+    }
+
+    #[test]
+    fn test_crawl_delay_is_capped_and_invalid_values_ignored() {
+        let capped = RobotsRules::parse(
+            "User-agent: *\nCrawl-delay: 86400\n",
+            ROBOTS_USER_AGENT_TOKEN,
+        );
+        assert_eq!(capped.crawl_delay, Some(MAX_CRAWL_DELAY));
+
+        for value in ["-1", "0", "soon", "NaN"] {
+            let rules = RobotsRules::parse(
+                &format!("User-agent: *\nCrawl-delay: {}\n", value),
+                ROBOTS_USER_AGENT_TOKEN,
+            );
+            assert_eq!(rules.crawl_delay, None, "Crawl-delay: {}", value);
+        }
+    }
+
+    #[test]
+    fn test_disallow_all_still_allows_robots_txt() {
+        let rules = RobotsRules::parse("User-agent: *\nDisallow: /\n", ROBOTS_USER_AGENT_TOKEN);
+
+        assert!(!rules.is_allowed("/"));
+        assert!(rules.is_allowed("/robots.txt"));
+    }
+
+    #[test]
+    fn test_is_url_allowed_uses_path_and_query() {
+        let rules = rules();
+
+        assert!(!rules.is_url_allowed("https://shop.example.com/search?q=widget"));
+        assert!(rules.is_url_allowed("https://shop.example.com/products/widget"));
+        assert!(rules.is_url_allowed("not a url"));
+    }
+
+    /// Plain fetches with no per-host spacing
+    fn options() -> FetchOptions {
+        FetchOptions {
+            enable_headless: false,
+            allow_manual_verification: false,
+            headless_profile_reuse: true,
+            session_cache_duration_days: 14,
+            timeouts: FetchTimeouts {
+                http_secs: 5,
+                min_host_interval_ms: 0,
+                ..FetchTimeouts::default()
+            },
+            force_headless_domains: Vec::new(),
+            respect_robots_txt: true,
+            rotate_user_agent: false,
+            product_group_strategy: ProductGroupStrategy::First,
+            headless_browser_path: None,
+            insecure_hosts: Vec::new(),
+            max_redirects: 10,
+            max_json_ld_block_bytes: DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+        }
+    }
+
+    /// When the cached entry for `url`'s origin expires
+    fn expires_at(cache: &RobotsCache, url: &str) -> Instant {
+        let origin = Url::parse(url).unwrap().origin().ascii_serialization();
+        cache.entries.lock().unwrap()[&origin].expires_at
+    }
+
+    #[tokio::test]
+    async fn test_cache_fetches_robots_once_per_origin() {
+        let server = MockServer::serving(MockResponse::ok(ROBOTS_TXT)).await;
+        let cache = RobotsCache::new();
+
+        let first = cache
+            .rules_for(&server.url("/cart"), &options())
+            .await
+            .unwrap();
+        let second = cache
+            .rules_for(&server.url("/products/widget"), &options())
+            .await
+            .unwrap();
+
+        assert!(!first.is_allowed("/cart"));
+        assert_eq!(first, second);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_missing_robots_allows_everything() {
+        let server = MockServer::serving(MockResponse::new(404, "Not found")).await;
+        let cache = RobotsCache::new();
+
+        let rules = cache
+            .rules_for(&server.url("/cart"), &options())
+            .await
+            .unwrap();
+
+        assert!(rules.is_allowed("/cart"));
+        assert_eq!(rules.crawl_delay, None);
+        assert!(expires_at(&cache, &server.url("/cart")) > Instant::now() + FAILURE_CACHE_TTL);
+    }
+
+    #[tokio::test]
+    async fn test_server_error_allows_everything_briefly() {
+        let server = MockServer::serving(MockResponse::new(503, "Unavailable")).await;
+        let cache = RobotsCache::new();
+
+        let rules = cache
+            .rules_for(&server.url("/cart"), &options())
+            .await
+            .unwrap();
+
+        assert!(rules.is_allowed("/cart"));
+        assert!(expires_at(&cache, &server.url("/cart")) <= Instant::now() + FAILURE_CACHE_TTL);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_robots_is_truncated() {
+        let robots_txt = format!(
+            "User-agent: *\nDisallow: /cart\n#{}\nDisallow: /late\n",
+            "x".repeat(MAX_ROBOTS_BYTES)
+        );
+        let server = MockServer::serving(MockResponse::ok(robots_txt)).await;
+        let cache = RobotsCache::new();
+
+        let rules = cache
+            .rules_for(&server.url("/cart"), &options())
+            .await
+            .unwrap();
+
+        assert!(!rules.is_allowed("/cart"));
+        assert!(rules.is_allowed("/late"));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_host_allows_everything() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let cache = RobotsCache::new();
+
+        let rules = cache
+            .rules_for(&format!("{}/cart", base_url), &options())
+            .await
+            .unwrap();
+
+        assert!(rules.is_allowed("/cart"));
+        assert!(
+            expires_at(&cache, &format!("{}/cart", base_url)) <= Instant::now() + FAILURE_CACHE_TTL
+        );
+    }
+
+    #[tokio::test]
+    async fn test_url_without_host_has_no_rules() {
+        let cache = RobotsCache::new();
+
+        assert!(cache.rules_for("not a url", &options()).await.is_none());
+    }
+}
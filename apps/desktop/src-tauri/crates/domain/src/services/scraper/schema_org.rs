@@ -1,21 +1,165 @@
 //! Schema.org JSON-LD parsing for extracting product availability and price data.
 
+use std::collections::{BTreeMap, HashSet};
+
 use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::entities::availability_check::AvailabilityStatus;
 use product_stalker_core::AppError;
 
 use super::price_parser::{get_price_from_offer, PriceInfo};
 
+/// Default size limit for a single JSON-LD block (2 MB), until the
+/// `max_json_ld_block_kb` domain setting is changed
+pub const DEFAULT_MAX_JSON_LD_BLOCK_BYTES: usize = 2 * 1024 * 1024;
+
+/// Which variant of a ProductGroup is reported when the URL doesn't select one
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProductGroupStrategy {
+    /// The first variant with availability data
+    #[default]
+    First,
+    /// The lowest-priced variant that is in stock, falling back to the first
+    /// variant when none are
+    CheapestInStock,
+}
+
+impl ProductGroupStrategy {
+    /// Parse the `product_group_strategy` setting value, defaulting to `First`
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "cheapest_in_stock" => Self::CheapestInStock,
+            _ => Self::First,
+        }
+    }
+}
+
+/// Variant attributes chosen for a retailer link, e.g. `{"size": "M", "color": "black"}`.
+///
+/// Picks the ProductGroup variant for stores that don't put it in a
+/// `?variant=` query parameter (path segments, fragments, or nothing at all).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VariantSelector(BTreeMap<String, String>);
+
+impl VariantSelector {
+    /// Parse a selector from its stored JSON: an object of attribute name to value
+    pub fn from_json(json: &str) -> Result<Self, AppError> {
+        let selector: Self = serde_json::from_str(json).map_err(|e| {
+            AppError::Validation(format!(
+                "Invalid variant selector: {}. Must be an object of attribute names to text values",
+                e
+            ))
+        })?;
+        if selector
+            .0
+            .iter()
+            .any(|(name, value)| name.trim().is_empty() || value.trim().is_empty())
+        {
+            return Err(AppError::Validation(
+                "Invalid variant selector: attribute names and values can't be empty".to_string(),
+            ));
+        }
+        Ok(selector)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether a variant has every selected attribute.
+    ///
+    /// An attribute is looked up as the variant's property of the same name
+    /// (e.g. `color`), then as an `additionalProperty` with that name. When the
+    /// variant has neither, the value must appear as whole words in its `name`
+    /// (e.g. "Classic Tee - Black / M").
+    fn matches(&self, variant: &serde_json::Value) -> bool {
+        self.0.iter().all(
+            |(attribute, expected)| match variant_attribute(variant, attribute) {
+                Some(actual) => actual.trim().eq_ignore_ascii_case(expected.trim()),
+                None => variant
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .is_some_and(|name| contains_words(name, expected)),
+            },
+        )
+    }
+}
+
+/// A variant's value for an attribute, from a property of that name (text, or
+/// an object with a `name`) or a matching `additionalProperty`
+fn variant_attribute(variant: &serde_json::Value, attribute: &str) -> Option<String> {
+    let property = variant
+        .as_object()?
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(attribute))
+        .map(|(_, value)| value);
+    let direct = match property {
+        Some(serde_json::Value::String(value)) => Some(value.clone()),
+        Some(value) => value.get("name").and_then(|n| n.as_str()).map(String::from),
+        None => None,
+    };
+
+    direct.or_else(|| {
+        let additional = variant.get("additionalProperty")?;
+        let properties = match additional.as_array() {
+            Some(arr) => arr.iter().collect::<Vec<_>>(),
+            None => vec![additional],
+        };
+        properties.into_iter().find_map(|property| {
+            let name = property.get("name")?.as_str()?;
+            if !name.eq_ignore_ascii_case(attribute) {
+                return None;
+            }
+            match property.get("value")? {
+                serde_json::Value::String(value) => Some(value.clone()),
+                value => Some(value.to_string()),
+            }
+        })
+    })
+}
+
+/// Whether `text` contains the words of `phrase` consecutively, ignoring case
+/// and punctuation
+fn contains_words(text: &str, phrase: &str) -> bool {
+    let words = |s: &str| -> Vec<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (text, phrase) = (words(text), words(phrase));
+    !phrase.is_empty() && text.windows(phrase.len()).any(|window| window == phrase)
+}
+
 /// Extract all JSON-LD blocks from HTML
-pub fn extract_json_ld_blocks(html: &str) -> Result<Vec<serde_json::Value>, AppError> {
+///
+/// Blocks larger than `max_block_bytes` are skipped without being parsed, since
+/// a few multi-megabyte blocks per page add up to a lot of memory in a bulk run.
+pub fn extract_json_ld_blocks(
+    html: &str,
+    max_block_bytes: usize,
+) -> Result<Vec<serde_json::Value>, AppError> {
     let document = Html::parse_document(html);
     let selector = Selector::parse("script[type=\"application/ld+json\"]")
         .map_err(|e| AppError::External(format!("Invalid selector: {:?}", e)))?;
 
     Ok(document
         .select(&selector)
-        .filter_map(|el| serde_json::from_str(&el.inner_html()).ok())
+        .filter_map(|el| {
+            let block = el.inner_html();
+            if block.len() > max_block_bytes {
+                log::debug!(
+                    "Skipping JSON-LD block of {} bytes (limit {})",
+                    block.len(),
+                    max_block_bytes
+                );
+                return None;
+            }
+            serde_json::from_str(&block).ok()
+        })
         .collect())
 }
 
@@ -34,15 +178,26 @@ pub fn extract_variant_id(url: &str) -> Option<String> {
 /// Attempts extraction in the following priority order:
 /// 1. **Direct Product** - JSON with `@type: "Product"` and `offers` containing availability
 /// 2. **ProductGroup** - JSON with `@type: "ProductGroup"` and `hasVariant` array;
-///    matches by `variant_id` if provided, otherwise uses the first variant
-/// 3. **@graph array** - JSON with `@graph` array containing Product or ProductGroup items
+///    matches by `variant_id` if provided, then by `variant_selector` attributes,
+///    otherwise picks a variant by `group_strategy`
+/// 3. **@graph array** - JSON with `@graph` array containing Product or ProductGroup items;
+///    variants referenced by `@id` are resolved from the other graph nodes
 /// 4. **Direct JSON array** - Top-level array containing Product or ProductGroup items
+/// 5. **ItemList** - JSON with `@type: "ItemList"` (e.g. a category or search page),
+///    whose `itemListElement` entry with the same URL path as `url` is used;
+///    ItemLists in a `@graph` or top-level array are searched too
+///
+/// The `url` is used to resolve the offer currency: a path locale (e.g. `/en-au/`)
+/// overrides a contradicting `priceCurrency`, since multi-locale stores often
+/// leave the JSON-LD currency stale while the displayed price is localized.
 ///
 /// Returns `None` if no availability data is found in any of these structures.
 pub fn extract_availability_and_price(
     json: &serde_json::Value,
     variant_id: Option<&str>,
+    variant_selector: Option<&VariantSelector>,
     url: &str,
+    group_strategy: ProductGroupStrategy,
 ) -> Option<(String, PriceInfo)> {
     // 1. Direct Product with offers
     if is_product_type(json) {
@@ -53,47 +208,231 @@ pub fn extract_availability_and_price(
 
     // 2. ProductGroup with hasVariant array
     if is_product_group_type(json) {
-        if let Some(result) = get_availability_and_price_from_product_group(json, variant_id, url) {
+        if let Some(result) = get_availability_and_price_from_product_group(
+            json,
+            variant_id,
+            variant_selector,
+            url,
+            group_strategy,
+        ) {
             return Some(result);
         }
     }
 
     // 3. @graph array containing Product or ProductGroup items
     if let Some(arr) = json.get("@graph").and_then(|g| g.as_array()) {
-        if let Some(result) = find_availability_and_price_in_items(arr, variant_id, url) {
+        if let Some(result) = find_availability_and_price_in_items(
+            arr,
+            variant_id,
+            variant_selector,
+            url,
+            group_strategy,
+        ) {
             return Some(result);
         }
     }
 
     // 4. Direct JSON array containing Product or ProductGroup items
     if let Some(arr) = json.as_array() {
-        if let Some(result) = find_availability_and_price_in_items(arr, variant_id, url) {
+        if let Some(result) = find_availability_and_price_in_items(
+            arr,
+            variant_id,
+            variant_selector,
+            url,
+            group_strategy,
+        ) {
             return Some(result);
         }
     }
 
-    None
+    // 5. ItemList, alone or among @graph / top-level array items
+    let item_lists: Vec<&serde_json::Value> = if is_item_list_type(json) {
+        vec![json]
+    } else {
+        json.get("@graph")
+            .and_then(|g| g.as_array())
+            .or_else(|| json.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter(|item| is_item_list_type(item))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    item_lists.into_iter().find_map(|list| {
+        get_availability_and_price_from_item_list(
+            list,
+            variant_id,
+            variant_selector,
+            url,
+            group_strategy,
+        )
+    })
+}
+
+/// Get availability and price from the `itemListElement` entry matching `url`.
+///
+/// Entries are either `ListItem`s wrapping the product in `item` or the
+/// products themselves. An entry matches when its URL (the product's `url` or
+/// `@id`, else the ListItem's `url`) has the same path as `url`, or the same
+/// last path segment (the product slug or ID). Without a match nothing is
+/// returned, since the other entries are different products.
+fn get_availability_and_price_from_item_list(
+    item_list: &serde_json::Value,
+    variant_id: Option<&str>,
+    variant_selector: Option<&VariantSelector>,
+    url: &str,
+    group_strategy: ProductGroupStrategy,
+) -> Option<(String, PriceInfo)> {
+    let page_url = Url::parse(url).ok()?;
+    let elements = item_list.get("itemListElement")?.as_array()?;
+
+    elements.iter().find_map(|element| {
+        let product = match element.get("item") {
+            Some(item) if item.is_object() => item,
+            _ => element,
+        };
+        let entry_url = ["url", "@id"]
+            .iter()
+            .find_map(|key| product.get(key)?.as_str())
+            .or_else(|| element.get("url")?.as_str())?;
+        let entry_url = page_url.join(entry_url).ok()?;
+        if !same_item_path(&entry_url, &page_url) {
+            return None;
+        }
+
+        if is_product_type(product) {
+            get_availability_and_price_from_product(product, url)
+        } else if is_product_group_type(product) {
+            get_availability_and_price_from_product_group(
+                product,
+                variant_id,
+                variant_selector,
+                url,
+                group_strategy,
+            )
+        } else {
+            None
+        }
+    })
+}
+
+/// Whether two URLs point at the same item: equal paths, or equal last path
+/// segments (ignoring case and trailing slashes)
+fn same_item_path(a: &Url, b: &Url) -> bool {
+    let segments = |url: &Url| -> Vec<String> {
+        url.path_segments()
+            .map(|segments| {
+                segments
+                    .filter(|segment| !segment.is_empty())
+                    .map(str::to_ascii_lowercase)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let (a, b) = (segments(a), segments(b));
+    match (a.last(), b.last()) {
+        (Some(a_slug), Some(b_slug)) => a == b || a_slug == b_slug,
+        _ => false,
+    }
 }
 
 /// Iterate through items looking for availability and price data
+///
+/// A ProductGroup's `hasVariant` may hold only `@id` references to variant
+/// Products elsewhere in the items; those are resolved from the sibling nodes
+/// and the referenced Products are left to their group, so the variant ID can
+/// still pick the right one.
 fn find_availability_and_price_in_items(
     items: &[serde_json::Value],
     variant_id: Option<&str>,
+    variant_selector: Option<&VariantSelector>,
     url: &str,
+    group_strategy: ProductGroupStrategy,
 ) -> Option<(String, PriceInfo)> {
+    let referenced_variant_ids: HashSet<&str> = items
+        .iter()
+        .filter(|item| is_product_group_type(item))
+        .filter_map(|group| group.get("hasVariant")?.as_array())
+        .flatten()
+        .filter_map(variant_reference_id)
+        .collect();
+
     items.iter().find_map(|item| {
         if is_product_type(item) {
-            if let Some(result) = get_availability_and_price_from_product(item, url) {
-                return Some(result);
+            let is_referenced_variant = item
+                .get("@id")
+                .and_then(|id| id.as_str())
+                .is_some_and(|id| referenced_variant_ids.contains(id));
+            if !is_referenced_variant {
+                if let Some(result) = get_availability_and_price_from_product(item, url) {
+                    return Some(result);
+                }
             }
         }
         if is_product_group_type(item) {
-            return get_availability_and_price_from_product_group(item, variant_id, url);
+            let resolved = resolve_variant_references(item, items);
+            return get_availability_and_price_from_product_group(
+                resolved.as_ref().unwrap_or(item),
+                variant_id,
+                variant_selector,
+                url,
+                group_strategy,
+            );
         }
         None
     })
 }
 
+/// The `@id` of a `hasVariant` entry that only references a node elsewhere
+/// (`{"@id": "..."}` without offers, or a bare id string)
+fn variant_reference_id(variant: &serde_json::Value) -> Option<&str> {
+    match variant {
+        serde_json::Value::String(id) => Some(id),
+        serde_json::Value::Object(fields) if !fields.contains_key("offers") => {
+            fields.get("@id")?.as_str()
+        }
+        _ => None,
+    }
+}
+
+/// Copy of a ProductGroup with `@id`-only variants replaced by the matching
+/// nodes from `items`, or `None` if no reference could be resolved
+fn resolve_variant_references(
+    product_group: &serde_json::Value,
+    items: &[serde_json::Value],
+) -> Option<serde_json::Value> {
+    let variants = product_group.get("hasVariant")?.as_array()?;
+    let mut resolved_any = false;
+
+    let resolved: Vec<serde_json::Value> = variants
+        .iter()
+        .map(|variant| {
+            let node = variant_reference_id(variant).and_then(|id| {
+                items.iter().find(|item| {
+                    is_product_type(item) && item.get("@id").and_then(|i| i.as_str()) == Some(id)
+                })
+            });
+            match node {
+                Some(node) => {
+                    resolved_any = true;
+                    node.clone()
+                }
+                None => variant.clone(),
+            }
+        })
+        .collect();
+
+    if !resolved_any {
+        return None;
+    }
+
+    let mut group = product_group.clone();
+    group["hasVariant"] = serde_json::Value::Array(resolved);
+    Some(group)
+}
+
 /// Check if a JSON @type field matches the expected type
 fn has_schema_type(json: &serde_json::Value, expected_type: &str) -> bool {
     let Some(type_value) = json.get("@type") else {
@@ -117,27 +456,46 @@ fn is_product_group_type(json: &serde_json::Value) -> bool {
     has_schema_type(json, "ProductGroup")
 }
 
+/// Check if a JSON value represents an ItemList type
+fn is_item_list_type(json: &serde_json::Value) -> bool {
+    has_schema_type(json, "ItemList")
+}
+
 /// Get availability and price from a ProductGroup by matching variant ID
+///
+/// Without a variant ID, or when none of the variants match it, the first
+/// variant with all of the `variant_selector` attributes is used. Failing
+/// both, the variant is chosen by `group_strategy`.
 fn get_availability_and_price_from_product_group(
     product_group: &serde_json::Value,
     variant_id: Option<&str>,
+    variant_selector: Option<&VariantSelector>,
     url: &str,
+    group_strategy: ProductGroupStrategy,
 ) -> Option<(String, PriceInfo)> {
     let variants = product_group.get("hasVariant")?.as_array()?;
 
-    let Some(vid) = variant_id else {
-        // No variant ID specified, return first variant's availability and price
-        return get_first_variant_availability(variants, url);
-    };
-
     // Try to find the matching variant by ID
-    let matched = find_variant_by_id(variants, vid, url);
-    if matched.is_some() {
-        return matched;
+    if let Some(vid) = variant_id {
+        let matched = find_variant_by_id(variants, vid, url);
+        if matched.is_some() {
+            return matched;
+        }
     }
 
-    // Fallback: return first variant's availability and price
-    get_first_variant_availability(variants, url)
+    // Then by the attributes selected for the link
+    if let Some(selector) = variant_selector.filter(|s| !s.is_empty()) {
+        let matched = find_variant_by_attributes(variants, selector, url);
+        if matched.is_some() {
+            return matched;
+        }
+    }
+
+    match group_strategy {
+        ProductGroupStrategy::First => get_first_variant_availability(variants, url),
+        ProductGroupStrategy::CheapestInStock => get_cheapest_in_stock_variant(variants, url)
+            .or_else(|| get_first_variant_availability(variants, url)),
+    }
 }
 
 /// Find a variant by its ID in the URL query parameters
@@ -173,6 +531,18 @@ fn find_variant_by_id(
     None
 }
 
+/// Find the first variant with all of the selected attributes
+fn find_variant_by_attributes(
+    variants: &[serde_json::Value],
+    selector: &VariantSelector,
+    url: &str,
+) -> Option<(String, PriceInfo)> {
+    variants
+        .iter()
+        .filter(|variant| selector.matches(variant))
+        .find_map(|variant| get_availability_and_price_from_product(variant, url))
+}
+
 /// Get the first variant's availability and price
 fn get_first_variant_availability(
     variants: &[serde_json::Value],
@@ -183,6 +553,23 @@ fn get_first_variant_availability(
         .find_map(|v| get_availability_and_price_from_product(v, url))
 }
 
+/// Get the in-stock variant with the lowest price
+///
+/// Prices are compared in minor units; in-stock variants without a parseable
+/// price are only picked when no in-stock variant has one.
+fn get_cheapest_in_stock_variant(
+    variants: &[serde_json::Value],
+    url: &str,
+) -> Option<(String, PriceInfo)> {
+    variants
+        .iter()
+        .filter_map(|v| get_availability_and_price_from_product(v, url))
+        .filter(|(availability, _)| {
+            AvailabilityStatus::from_schema_org(availability).is_available()
+        })
+        .min_by_key(|(_, price)| (price.price_minor_units.is_none(), price.price_minor_units))
+}
+
 /// Get availability and price from a Product JSON object
 fn get_availability_and_price_from_product(
     product: &serde_json::Value,
@@ -206,9 +593,62 @@ fn get_availability_and_price_from_product(
     })
 }
 
+/// Extract a price from a JSON-LD value whose offers carry no availability.
+///
+/// Searches the same structures as [`extract_availability_and_price`] (direct
+/// Product, ProductGroup variants, `@graph` and top-level arrays) and returns
+/// the first offer with a parseable price. Used as the input for the
+/// add-to-cart button fallback.
+pub fn extract_price_without_availability(
+    json: &serde_json::Value,
+    url: &str,
+) -> Option<PriceInfo> {
+    if is_product_type(json) {
+        if let Some(price) = get_price_from_product(json, url) {
+            return Some(price);
+        }
+    }
+
+    if is_product_group_type(json) {
+        let variants = json.get("hasVariant").and_then(|v| v.as_array());
+        if let Some(price) = variants.and_then(|v| find_price_in_items(v, url)) {
+            return Some(price);
+        }
+    }
+
+    let items = json
+        .get("@graph")
+        .and_then(|g| g.as_array())
+        .or_else(|| json.as_array())?;
+    find_price_in_items(items, url)
+}
+
+/// Iterate through items looking for the first parseable offer price
+fn find_price_in_items(items: &[serde_json::Value], url: &str) -> Option<PriceInfo> {
+    items
+        .iter()
+        .filter(|item| is_product_type(item) || is_product_group_type(item))
+        .find_map(|item| extract_price_without_availability(item, url))
+}
+
+/// Get the first parseable offer price from a Product JSON object
+fn get_price_from_product(product: &serde_json::Value, url: &str) -> Option<PriceInfo> {
+    let offers = product.get("offers")?;
+    let candidates = match offers.as_array() {
+        Some(arr) => arr.iter().collect::<Vec<_>>(),
+        None => vec![offers],
+    };
+
+    candidates
+        .into_iter()
+        .map(|offer| get_price_from_offer(offer, url))
+        .find(|price| price.price_minor_units.is_some())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::scraper::PriceRange;
 
     #[test]
     fn test_extract_variant_id() {
@@ -239,7 +679,24 @@ mod tests {
             <body></body>
             </html>
         "#;
-        let blocks = extract_json_ld_blocks(html).unwrap();
+        let blocks = extract_json_ld_blocks(html, DEFAULT_MAX_JSON_LD_BLOCK_BYTES).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0]["@type"], "Product");
+    }
+
+    #[test]
+    fn test_extract_json_ld_blocks_skips_oversized_block() {
+        let filler = "x".repeat(2048);
+        let html = format!(
+            r#"<html><head>
+                <script type="application/ld+json">{{"@type": "ItemList", "description": "{}"}}</script>
+                <script type="application/ld+json">{{"@type": "Product", "name": "Test"}}</script>
+            </head><body></body></html>"#,
+            filler
+        );
+
+        let blocks = extract_json_ld_blocks(&html, 1024).unwrap();
+
         assert_eq!(blocks.len(), 1);
         assert_eq!(blocks[0]["@type"], "Product");
     }
@@ -255,7 +712,13 @@ mod tests {
                 "priceCurrency": "USD"
             }
         });
-        let result = extract_availability_and_price(&json, None, "https://example.com/product");
+        let result = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/product",
+            ProductGroupStrategy::First,
+        );
         assert!(result.is_some());
         let (avail, price) = result.unwrap();
         assert_eq!(avail, "http://schema.org/InStock");
@@ -285,14 +748,25 @@ mod tests {
         });
 
         // With matching variant ID
-        let result =
-            extract_availability_and_price(&json, Some("456"), "https://example.com/product");
+        let result = extract_availability_and_price(
+            &json,
+            Some("456"),
+            None,
+            "https://example.com/product",
+            ProductGroupStrategy::First,
+        );
         assert!(result.is_some());
         let (avail, _) = result.unwrap();
         assert_eq!(avail, "http://schema.org/InStock");
 
         // Without variant ID - gets first variant
-        let result = extract_availability_and_price(&json, None, "https://example.com/product");
+        let result = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/product",
+            ProductGroupStrategy::First,
+        );
         assert!(result.is_some());
         let (avail, _) = result.unwrap();
         assert_eq!(avail, "http://schema.org/OutOfStock");
@@ -312,12 +786,541 @@ mod tests {
                 }
             ]
         });
-        let result = extract_availability_and_price(&json, None, "https://example.com/product");
+        let result = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/product",
+            ProductGroupStrategy::First,
+        );
         assert!(result.is_some());
         let (avail, _) = result.unwrap();
         assert_eq!(avail, "http://schema.org/InStock");
     }
 
+    /// Graph whose ProductGroup references its variants by `@id`, with the
+    /// variant Products as separate top-level nodes listed before the group
+    fn graph_with_variant_references() -> serde_json::Value {
+        serde_json::json!({
+            "@graph": [
+                {"@type": "WebSite", "name": "Test"},
+                {
+                    "@type": "Product",
+                    "@id": "https://example.com/products/shirt?variant=111#variant",
+                    "name": "Shirt - Small",
+                    "offers": {
+                        "availability": "https://schema.org/OutOfStock",
+                        "price": "10.00",
+                        "priceCurrency": "USD"
+                    }
+                },
+                {
+                    "@type": "Product",
+                    "@id": "https://example.com/products/shirt?variant=222#variant",
+                    "name": "Shirt - Large",
+                    "offers": {
+                        "availability": "https://schema.org/InStock",
+                        "price": "12.00",
+                        "priceCurrency": "USD"
+                    }
+                },
+                {
+                    "@type": "ProductGroup",
+                    "name": "Shirt",
+                    "hasVariant": [
+                        {"@id": "https://example.com/products/shirt?variant=111#variant"},
+                        {"@id": "https://example.com/products/shirt?variant=222#variant"}
+                    ]
+                }
+            ]
+        })
+    }
+
+    fn multi_variant_group() -> serde_json::Value {
+        serde_json::json!({
+            "@type": "ProductGroup",
+            "hasVariant": [
+                {
+                    "@id": "/products/shirt?variant=1",
+                    "@type": "Product",
+                    "offers": {
+                        "availability": "https://schema.org/InStock",
+                        "price": "39.99",
+                        "priceCurrency": "USD"
+                    }
+                },
+                {
+                    "@id": "/products/shirt?variant=2",
+                    "@type": "Product",
+                    "offers": {
+                        "availability": "https://schema.org/OutOfStock",
+                        "price": "19.99",
+                        "priceCurrency": "USD"
+                    }
+                },
+                {
+                    "@id": "/products/shirt?variant=3",
+                    "@type": "Product",
+                    "offers": {
+                        "availability": "https://schema.org/InStock",
+                        "price": "29.99",
+                        "priceCurrency": "USD"
+                    }
+                },
+                {
+                    "@id": "/products/shirt?variant=4",
+                    "@type": "Product",
+                    "offers": {
+                        "availability": "https://schema.org/LimitedAvailability",
+                        "price": "34.99",
+                        "priceCurrency": "USD"
+                    }
+                }
+            ]
+        })
+    }
+
+    /// Group whose variants are only told apart by name and attributes
+    fn tee_group() -> serde_json::Value {
+        serde_json::json!({
+            "@type": "ProductGroup",
+            "name": "Classic Tee",
+            "hasVariant": [
+                {
+                    "@type": "Product",
+                    "name": "Classic Tee - White / M",
+                    "color": "White",
+                    "offers": {
+                        "availability": "https://schema.org/InStock",
+                        "price": "20.00",
+                        "priceCurrency": "USD"
+                    }
+                },
+                {
+                    "@type": "Product",
+                    "name": "Classic Tee - Black / S",
+                    "color": "Black",
+                    "offers": {
+                        "availability": "https://schema.org/InStock",
+                        "price": "21.00",
+                        "priceCurrency": "USD"
+                    }
+                },
+                {
+                    "@type": "Product",
+                    "name": "Classic Tee - Black / M",
+                    "color": "Black",
+                    "additionalProperty": [
+                        {"@type": "PropertyValue", "name": "Fit", "value": "Relaxed"}
+                    ],
+                    "offers": {
+                        "availability": "https://schema.org/OutOfStock",
+                        "price": "22.00",
+                        "priceCurrency": "USD"
+                    }
+                }
+            ]
+        })
+    }
+
+    fn extract_tee(selector: &str, url: &str) -> Option<(String, PriceInfo)> {
+        let selector = VariantSelector::from_json(selector).unwrap();
+        extract_availability_and_price(
+            &tee_group(),
+            extract_variant_id(url).as_deref(),
+            Some(&selector),
+            url,
+            ProductGroupStrategy::First,
+        )
+    }
+
+    #[test]
+    fn test_variant_selector_matches_name_and_attributes() {
+        let (avail, price) = extract_tee(
+            r#"{"color": "black", "size": "M"}"#,
+            "https://shop.example.com/tee/black-m",
+        )
+        .unwrap();
+        assert_eq!(avail, "https://schema.org/OutOfStock");
+        assert_eq!(price.price_minor_units, Some(2200));
+    }
+
+    #[test]
+    fn test_variant_selector_matches_name_only() {
+        let (_, price) = extract_tee(r#"{"size": "S"}"#, "https://shop.example.com/tee").unwrap();
+        assert_eq!(price.price_minor_units, Some(2100));
+    }
+
+    #[test]
+    fn test_variant_selector_matches_additional_property() {
+        let (_, price) =
+            extract_tee(r#"{"fit": "relaxed"}"#, "https://shop.example.com/tee").unwrap();
+        assert_eq!(price.price_minor_units, Some(2200));
+    }
+
+    #[test]
+    fn test_variant_selector_property_mismatch_ignores_name() {
+        // Every variant's name has "Tee", but their color property is compared instead
+        let (_, price) =
+            extract_tee(r#"{"color": "Tee"}"#, "https://shop.example.com/tee").unwrap();
+        // No variant matches, so the group strategy picks the first one
+        assert_eq!(price.price_minor_units, Some(2000));
+    }
+
+    #[test]
+    fn test_variant_id_takes_precedence_over_selector() {
+        let selector = VariantSelector::from_json(r#"{"size": "S"}"#).unwrap();
+        let result = extract_availability_and_price(
+            &multi_variant_group(),
+            Some("3"),
+            Some(&selector),
+            "https://example.com/products/shirt?variant=3",
+            ProductGroupStrategy::First,
+        );
+        let (_, price) = result.unwrap();
+        assert_eq!(price.price_minor_units, Some(2999));
+    }
+
+    #[test]
+    fn test_variant_selector_from_json() {
+        let selector = VariantSelector::from_json(r#"{"size": "M", "color": "black"}"#).unwrap();
+        assert!(!selector.is_empty());
+        assert!(VariantSelector::from_json("{}").unwrap().is_empty());
+
+        for invalid in [
+            r#"["M"]"#,
+            r#"{"size": 10}"#,
+            r#"{"size": " "}"#,
+            "not json",
+        ] {
+            assert!(matches!(
+                VariantSelector::from_json(invalid),
+                Err(AppError::Validation(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_product_group_first_strategy_uses_first_variant() {
+        let (avail, price) = extract_availability_and_price(
+            &multi_variant_group(),
+            None,
+            None,
+            "https://example.com/products/shirt",
+            ProductGroupStrategy::First,
+        )
+        .unwrap();
+
+        assert_eq!(avail, "https://schema.org/InStock");
+        assert_eq!(price.price_minor_units, Some(3999));
+    }
+
+    #[test]
+    fn test_product_group_cheapest_in_stock_strategy() {
+        let (avail, price) = extract_availability_and_price(
+            &multi_variant_group(),
+            None,
+            None,
+            "https://example.com/products/shirt",
+            ProductGroupStrategy::CheapestInStock,
+        )
+        .unwrap();
+
+        // The $19.99 variant is out of stock, so the $29.99 one wins
+        assert_eq!(avail, "https://schema.org/InStock");
+        assert_eq!(price.price_minor_units, Some(2999));
+    }
+
+    #[test]
+    fn test_product_group_variant_id_overrides_strategy() {
+        let (avail, price) = extract_availability_and_price(
+            &multi_variant_group(),
+            Some("2"),
+            None,
+            "https://example.com/products/shirt?variant=2",
+            ProductGroupStrategy::CheapestInStock,
+        )
+        .unwrap();
+
+        assert_eq!(avail, "https://schema.org/OutOfStock");
+        assert_eq!(price.price_minor_units, Some(1999));
+    }
+
+    #[test]
+    fn test_product_group_cheapest_in_stock_falls_back_to_first_when_none_in_stock() {
+        let json = serde_json::json!({
+            "@type": "ProductGroup",
+            "hasVariant": [
+                {
+                    "@type": "Product",
+                    "offers": {"availability": "https://schema.org/OutOfStock", "price": "12.00"}
+                },
+                {
+                    "@type": "Product",
+                    "offers": {"availability": "https://schema.org/SoldOut", "price": "8.00"}
+                }
+            ]
+        });
+
+        let (avail, price) = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/products/mug",
+            ProductGroupStrategy::CheapestInStock,
+        )
+        .unwrap();
+
+        assert_eq!(avail, "https://schema.org/OutOfStock");
+        assert_eq!(price.price_minor_units, Some(1200));
+    }
+
+    #[test]
+    fn test_product_group_cheapest_in_stock_prefers_priced_variants() {
+        let json = serde_json::json!({
+            "@type": "ProductGroup",
+            "hasVariant": [
+                {"@type": "Product", "offers": {"availability": "https://schema.org/InStock"}},
+                {
+                    "@type": "Product",
+                    "offers": {"availability": "https://schema.org/InStock", "price": "15.00"}
+                }
+            ]
+        });
+
+        let (_, price) = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/products/mug",
+            ProductGroupStrategy::CheapestInStock,
+        )
+        .unwrap();
+
+        assert_eq!(price.price_minor_units, Some(1500));
+    }
+
+    #[test]
+    fn test_product_group_strategy_from_setting() {
+        assert_eq!(
+            ProductGroupStrategy::from_setting("cheapest_in_stock"),
+            ProductGroupStrategy::CheapestInStock
+        );
+        assert_eq!(
+            ProductGroupStrategy::from_setting("first"),
+            ProductGroupStrategy::First
+        );
+        assert_eq!(
+            ProductGroupStrategy::from_setting("bogus"),
+            ProductGroupStrategy::First
+        );
+    }
+
+    #[test]
+    fn test_extract_availability_resolves_graph_variant_references() {
+        let json = graph_with_variant_references();
+        let result = extract_availability_and_price(
+            &json,
+            Some("222"),
+            None,
+            "https://example.com/products/shirt",
+            ProductGroupStrategy::First,
+        );
+        let (avail, price) = result.unwrap();
+        assert_eq!(avail, "https://schema.org/InStock");
+        assert_eq!(price.price_minor_units, Some(1200));
+    }
+
+    #[test]
+    fn test_extract_availability_graph_variant_references_default_to_first() {
+        let json = graph_with_variant_references();
+        let result = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/products/shirt",
+            ProductGroupStrategy::First,
+        );
+        let (avail, price) = result.unwrap();
+        assert_eq!(avail, "https://schema.org/OutOfStock");
+        assert_eq!(price.price_minor_units, Some(1000));
+    }
+
+    /// Search results page listing two products as ListItems
+    fn item_list() -> serde_json::Value {
+        serde_json::json!({
+            "@context": "https://schema.org",
+            "@type": "ItemList",
+            "itemListElement": [
+                {
+                    "@type": "ListItem",
+                    "position": 1,
+                    "item": {
+                        "@type": "Product",
+                        "name": "Red Sneaker",
+                        "url": "https://example.com/p/red-sneaker-123",
+                        "offers": {
+                            "@type": "Offer",
+                            "price": "89.00",
+                            "priceCurrency": "USD",
+                            "availability": "https://schema.org/OutOfStock"
+                        }
+                    }
+                },
+                {
+                    "@type": "ListItem",
+                    "position": 2,
+                    "url": "/p/blue-sneaker-456",
+                    "item": {
+                        "@type": "Product",
+                        "name": "Blue Sneaker",
+                        "offers": {
+                            "@type": "Offer",
+                            "price": "79.00",
+                            "priceCurrency": "USD",
+                            "availability": "https://schema.org/InStock"
+                        }
+                    }
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_extract_availability_from_item_list_matches_url_path() {
+        let json = item_list();
+
+        let (avail, price) = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/p/blue-sneaker-456/",
+            ProductGroupStrategy::First,
+        )
+        .unwrap();
+        assert_eq!(avail, "https://schema.org/InStock");
+        assert_eq!(price.price_minor_units, Some(7900));
+
+        let (avail, price) = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/p/red-sneaker-123",
+            ProductGroupStrategy::First,
+        )
+        .unwrap();
+        assert_eq!(avail, "https://schema.org/OutOfStock");
+        assert_eq!(price.price_minor_units, Some(8900));
+    }
+
+    #[test]
+    fn test_extract_availability_from_item_list_matches_slug() {
+        let result = extract_availability_and_price(
+            &item_list(),
+            None,
+            None,
+            "https://example.com/search/red-sneaker-123?q=sneaker",
+            ProductGroupStrategy::First,
+        );
+        let (avail, _) = result.unwrap();
+        assert_eq!(avail, "https://schema.org/OutOfStock");
+    }
+
+    #[test]
+    fn test_extract_availability_from_item_list_without_match() {
+        let result = extract_availability_and_price(
+            &item_list(),
+            None,
+            None,
+            "https://example.com/search?q=sneaker",
+            ProductGroupStrategy::First,
+        );
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_extract_availability_from_item_list_in_graph() {
+        let json = serde_json::json!({
+            "@graph": [
+                {"@type": "WebPage", "name": "Sneakers"},
+                {
+                    "@type": "ItemList",
+                    "itemListElement": [
+                        {
+                            "@type": "Product",
+                            "@id": "https://example.com/p/green-sneaker-789",
+                            "offers": {
+                                "price": "99.00",
+                                "priceCurrency": "USD",
+                                "availability": "https://schema.org/LimitedAvailability"
+                            }
+                        }
+                    ]
+                }
+            ]
+        });
+
+        let result = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/p/green-sneaker-789",
+            ProductGroupStrategy::First,
+        );
+        let (avail, price) = result.unwrap();
+        assert_eq!(avail, "https://schema.org/LimitedAvailability");
+        assert_eq!(price.price_minor_units, Some(9900));
+    }
+
+    #[test]
+    fn test_extract_availability_graph_variant_references_as_strings() {
+        let json = serde_json::json!({
+            "@graph": [
+                {
+                    "@type": "ProductGroup",
+                    "hasVariant": ["/products/mug?variant=1", "/products/mug?variant=2"]
+                },
+                {
+                    "@type": "Product",
+                    "@id": "/products/mug?variant=1",
+                    "offers": {"availability": "https://schema.org/SoldOut"}
+                },
+                {
+                    "@type": "Product",
+                    "@id": "/products/mug?variant=2",
+                    "offers": {"availability": "https://schema.org/InStock"}
+                }
+            ]
+        });
+        let result = extract_availability_and_price(
+            &json,
+            Some("2"),
+            None,
+            "https://example.com/mug",
+            ProductGroupStrategy::First,
+        );
+        let (avail, _) = result.unwrap();
+        assert_eq!(avail, "https://schema.org/InStock");
+    }
+
+    #[test]
+    fn test_extract_availability_unresolved_variant_references() {
+        let json = serde_json::json!({
+            "@graph": [{
+                "@type": "ProductGroup",
+                "hasVariant": [{"@id": "https://example.com/products/shirt?variant=999"}]
+            }]
+        });
+        let result = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/shirt",
+            ProductGroupStrategy::First,
+        );
+        assert!(result.is_none());
+    }
+
     #[test]
     fn test_extract_availability_from_array() {
         let json = serde_json::json!([
@@ -330,7 +1333,13 @@ mod tests {
                 }
             }
         ]);
-        let result = extract_availability_and_price(&json, None, "https://example.com/product");
+        let result = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/product",
+            ProductGroupStrategy::First,
+        );
         assert!(result.is_some());
         let (avail, _) = result.unwrap();
         assert_eq!(avail, "http://schema.org/BackOrder");
@@ -345,11 +1354,172 @@ mod tests {
                 {"availability": "http://schema.org/InStock", "price": "99.99"}
             ]
         });
-        let result = extract_availability_and_price(&json, None, "https://example.com/product");
+        let result = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/product",
+            ProductGroupStrategy::First,
+        );
         assert!(result.is_some());
         let (avail, price) = result.unwrap();
         // Should use first offer's availability
         assert_eq!(avail, "http://schema.org/OutOfStock");
         assert_eq!(price.price_minor_units, Some(4999));
     }
+
+    #[test]
+    fn test_extract_availability_from_aggregate_offer() {
+        let json = serde_json::json!({
+            "@type": "Product",
+            "name": "Test",
+            "offers": {
+                "@type": "AggregateOffer",
+                "availability": "https://schema.org/InStock",
+                "lowPrice": "79.00",
+                "highPrice": "129.50",
+                "offerCount": 4,
+                "priceCurrency": "USD"
+            }
+        });
+        let (avail, price) = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://example.com/product",
+            ProductGroupStrategy::First,
+        )
+        .unwrap();
+        assert_eq!(avail, "https://schema.org/InStock");
+        assert_eq!(price.price_minor_units, Some(7900));
+        assert_eq!(price.raw_price, Some("79.00".to_string()));
+        assert_eq!(
+            price.price_range,
+            Some(PriceRange {
+                low_minor_units: 7900,
+                high_minor_units: 12950,
+                offer_count: Some(4),
+            })
+        );
+    }
+
+    #[test]
+    fn test_extract_price_without_availability_aggregate_offer() {
+        let json = serde_json::json!({
+            "@type": "Product",
+            "offers": {"@type": "AggregateOffer", "lowPrice": 15, "highPrice": 20}
+        });
+        let price =
+            extract_price_without_availability(&json, "https://example.com/product").unwrap();
+        assert_eq!(price.price_minor_units, Some(1500));
+        assert_eq!(price.price_range.map(|r| r.high_minor_units), Some(2000));
+    }
+
+    #[test]
+    fn test_extract_path_locale_overrides_offer_currency() {
+        let json = serde_json::json!({
+            "@type": "Product",
+            "offers": {
+                "availability": "http://schema.org/InStock",
+                "price": "120.00",
+                "priceCurrency": "USD"
+            }
+        });
+        let (_, price) = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://store.com/en-au/products/item",
+            ProductGroupStrategy::First,
+        )
+        .unwrap();
+        assert_eq!(price.price_currency, Some("AUD".to_string()));
+        assert_eq!(price.price_minor_units, Some(12000));
+    }
+
+    #[test]
+    fn test_extract_path_locale_overrides_variant_offer_currency() {
+        let json = serde_json::json!({
+            "@type": "ProductGroup",
+            "hasVariant": [{
+                "@id": "/en-au/products/item?variant=123#variant",
+                "@type": "Product",
+                "offers": {
+                    "availability": "http://schema.org/InStock",
+                    "price": 45,
+                    "priceCurrency": "USD"
+                }
+            }]
+        });
+        let (_, price) = extract_availability_and_price(
+            &json,
+            Some("123"),
+            None,
+            "https://store.com/en-au/products/item?variant=123",
+            ProductGroupStrategy::First,
+        )
+        .unwrap();
+        assert_eq!(price.price_currency, Some("AUD".to_string()));
+    }
+
+    #[test]
+    fn test_extract_keeps_offer_currency_without_path_locale() {
+        let json = serde_json::json!({
+            "@type": "Product",
+            "offers": {
+                "availability": "http://schema.org/InStock",
+                "price": "120.00",
+                "priceCurrency": "USD"
+            }
+        });
+        let (_, price) = extract_availability_and_price(
+            &json,
+            None,
+            None,
+            "https://store.com.au/products/item",
+            ProductGroupStrategy::First,
+        )
+        .unwrap();
+        assert_eq!(price.price_currency, Some("USD".to_string()));
+    }
+
+    #[test]
+    fn test_extract_price_without_availability_direct_product() {
+        let json = serde_json::json!({
+            "@type": "Product",
+            "offers": { "price": "19.95", "priceCurrency": "USD" }
+        });
+        let price =
+            extract_price_without_availability(&json, "https://store.com/products/item").unwrap();
+        assert_eq!(price.price_minor_units, Some(1995));
+        assert_eq!(price.price_currency, Some("USD".to_string()));
+    }
+
+    #[test]
+    fn test_extract_price_without_availability_in_graph_variant() {
+        let json = serde_json::json!({
+            "@graph": [
+                { "@type": "WebPage" },
+                {
+                    "@type": "ProductGroup",
+                    "hasVariant": [
+                        { "@type": "Product", "offers": [{ "priceCurrency": "USD" }] },
+                        { "@type": "Product", "offers": [{ "price": 42, "priceCurrency": "USD" }] }
+                    ]
+                }
+            ]
+        });
+        let price =
+            extract_price_without_availability(&json, "https://store.com/products/item").unwrap();
+        assert_eq!(price.price_minor_units, Some(4200));
+    }
+
+    #[test]
+    fn test_extract_price_without_availability_no_price() {
+        let json = serde_json::json!({
+            "@type": "Product",
+            "offers": { "priceCurrency": "USD" }
+        });
+        assert!(extract_price_without_availability(&json, "https://store.com/p").is_none());
+    }
 }
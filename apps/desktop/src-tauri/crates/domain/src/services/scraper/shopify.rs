@@ -8,24 +8,30 @@
 //! 1. Extract variant ID from URL (required for Shopify products)
 //! 2. Fetch product.json for price and product details
 //! 3. Use cart/add.js API to check availability
+//!
+//! When product.json reports `variants[].available`, [`fetch_products_json`] can
+//! answer without fetching the product page at all, so the scraper tries it first.
+//! Stores whose product.json turns out not to exist are remembered in
+//! [`NonShopifyOrigins`] so later checks skip that extra request.
 
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
 
+use sea_orm::DatabaseConnection;
 use serde::Deserialize;
+use tokio::time::Instant;
 use url::Url;
 
-use crate::entities::availability_check::AvailabilityStatus;
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
 use product_stalker_core::AppError;
 
+use super::http_client;
 use super::price_parser::{
-    infer_currency_from_domain, infer_currency_from_path, parse_price_to_minor_units, PriceInfo,
+    compare_at_above_price, infer_currency_from_domain, infer_currency_from_path,
+    parse_price_to_minor_units, PriceInfo,
 };
-use super::ScrapingResult;
-
-/// HTTP request timeout for Shopify API calls
-const TIMEOUT_SECS: u64 = 15;
-
-use super::USER_AGENT;
+use super::{FetchOptions, ScrapingResult};
 
 /// Error phrases from the Shopify cart API that indicate a product is out of stock
 const CART_ERROR_OUT_OF_STOCK_PHRASES: &[&str] = &[
@@ -37,6 +43,9 @@ const CART_ERROR_OUT_OF_STOCK_PHRASES: &[&str] = &[
     "insufficient inventory",
 ];
 
+/// How long a store without product.json is skipped by the products.json fast path
+const NON_SHOPIFY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// Stores that use /products/ URL pattern but are NOT Shopify stores
 /// These stores have their own specialized adapters
 const NON_SHOPIFY_STORES: &[&str] = &["chemistwarehouse"];
@@ -70,6 +79,9 @@ struct ShopifyVariant {
     available: Option<bool>,
     #[serde(default)]
     price_currency: Option<String>,
+    /// Pre-sale price; null when the variant is not on sale
+    #[serde(default)]
+    compare_at_price: Option<String>,
 }
 
 /// Shopify cart error response - product is out of stock or unavailable
@@ -122,6 +134,58 @@ impl ShopifyContext {
     }
 }
 
+/// Origins (scheme, host and port) whose product.json was missing or not JSON.
+///
+/// Such stores have `/products/` URLs without being Shopify stores, so trying
+/// product.json there only costs an extra request and rate-limit wait per check.
+#[derive(Default)]
+pub struct NonShopifyOrigins {
+    /// When each origin may be tried again
+    expires_at: Mutex<HashMap<String, Instant>>,
+}
+
+impl NonShopifyOrigins {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Origins shared by all fetches in the process
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<NonShopifyOrigins> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Whether `url`'s store recently had no product.json
+    pub fn contains(&self, url: &str) -> bool {
+        let Some(origin) = origin_of(url) else {
+            return false;
+        };
+        self.expires_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&origin)
+            .is_some_and(|expires_at| *expires_at > Instant::now())
+    }
+
+    /// Remember that `url`'s store has no product.json
+    pub fn insert(&self, url: &str) {
+        let Some(origin) = origin_of(url) else {
+            return;
+        };
+        self.expires_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(origin, Instant::now() + NON_SHOPIFY_TTL);
+    }
+}
+
+/// A URL's origin, or `None` for URLs without a host
+fn origin_of(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    parsed.host_str()?;
+    Some(parsed.origin().ascii_serialization())
+}
+
 /// Check if a URL has an exact "products" path segment (common in Shopify URLs)
 fn has_products_path_segment(url: &str) -> bool {
     Url::parse(url)
@@ -201,14 +265,6 @@ fn get_base_url(url: &str) -> Option<String> {
     }
 }
 
-/// Build a configured HTTP client for Shopify API requests
-fn build_http_client() -> Result<reqwest::Client, AppError> {
-    reqwest::Client::builder()
-        .timeout(Duration::from_secs(TIMEOUT_SECS))
-        .build()
-        .map_err(|e| AppError::External(e.to_string()))
-}
-
 /// Build a ScrapingResult from product.json availability data
 fn build_product_json_result(
     available: bool,
@@ -231,22 +287,107 @@ fn build_product_json_result(
         status,
         raw_availability: Some(raw_availability.to_string()),
         price: extract_price_from_variant(variant, url),
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
     }
 }
 
+/// Check availability from `<store>/products/<handle>.json` alone.
+///
+/// Matches the URL's `?variant=` id (or the first variant) and reads its
+/// `available` flag and price. Fails when the endpoint is missing, the variant
+/// isn't listed or the store omits `available`, so the caller can fall back to
+/// scraping the product page.
+///
+/// The request is sent like the product page's would be (see
+/// [`http_client::host_request`]), so the host's timeout, headers, cookies, TLS
+/// and redirect settings apply.
+pub async fn fetch_products_json(
+    url: &str,
+    conn: &DatabaseConnection,
+    options: &FetchOptions,
+) -> Result<ScrapingResult, AppError> {
+    let context = ShopifyContext::from_url(url)?;
+    let request = http_client::host_request(
+        reqwest::Method::GET,
+        &context.product_json_url(),
+        "application/json",
+        conn,
+        options,
+    )
+    .await?;
+    let product = fetch_product_json(request).await.map_err(|e| {
+        if e.missing {
+            NonShopifyOrigins::global().insert(url);
+        }
+        AppError::from(e)
+    })?;
+    let target_variant = find_target_variant(&product.variants, context.variant_id)?;
+
+    let available = target_variant.available.ok_or_else(|| {
+        AppError::External("product.json does not include variant availability".to_string())
+    })?;
+    Ok(build_product_json_result(
+        available,
+        target_variant,
+        &context.url,
+    ))
+}
+
 /// Check availability for a Shopify product
 ///
 /// This is an async function that makes HTTP requests to:
 /// 1. product.json - to get price and variant info
 /// 2. cart/add.js - to verify availability
-pub async fn check_shopify_availability(url: &str, html: &str) -> Result<ScrapingResult, AppError> {
+///
+/// If product.json can't be fetched, the cart API is still tried for the URL's
+/// `?variant=` id (without a price). Both are requested like the product page
+/// (see [`http_client::host_request`]).
+pub async fn check_shopify_availability(
+    url: &str,
+    html: &str,
+    conn: &DatabaseConnection,
+    options: &FetchOptions,
+) -> Result<ScrapingResult, AppError> {
     if !is_shopify_store(html) {
         return Err(AppError::External("Not a Shopify store".to_string()));
     }
 
-    let client = build_http_client()?;
     let context = ShopifyContext::from_url(url)?;
-    let product = fetch_product_json(&client, &context.product_json_url()).await?;
+    let request = http_client::host_request(
+        reqwest::Method::GET,
+        &context.product_json_url(),
+        "application/json",
+        conn,
+        options,
+    )
+    .await?;
+    let product = match fetch_product_json(request).await.map_err(AppError::from) {
+        Ok(product) => product,
+        Err(e) => {
+            let Some(variant_id) = context.variant_id else {
+                return Err(e);
+            };
+            log::debug!(
+                "Shopify product.json unavailable ({}), using cart API for variant {}",
+                e,
+                variant_id
+            );
+            let cart_result =
+                check_cart_availability(&context.base_url, variant_id, conn, options).await?;
+            return Ok(ScrapingResult {
+                status: cart_result.status,
+                raw_availability: Some(cart_result.raw_availability),
+                price: PriceInfo::default(),
+                region_mismatch: false,
+                fetch_metrics: None,
+                canonical_url: None,
+                confidence: Confidence::High,
+            });
+        }
+    };
     let target_variant = find_target_variant(&product.variants, context.variant_id)?;
 
     // Use product.json availability if present, otherwise fall back to cart API
@@ -264,43 +405,78 @@ pub async fn check_shopify_availability(url: &str, html: &str) -> Result<Scrapin
         target_variant.id
     );
     let cart_result =
-        check_cart_availability(&client, &context.base_url, target_variant.id).await?;
+        check_cart_availability(&context.base_url, target_variant.id, conn, options).await?;
 
     Ok(ScrapingResult {
         status: cart_result.status,
         raw_availability: Some(cart_result.raw_availability),
         price: extract_price_from_variant(target_variant, &context.url),
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
     })
 }
 
-/// Fetch and parse product.json from Shopify store
+/// Send a prepared product.json request and parse the product
 async fn fetch_product_json(
-    client: &reqwest::Client,
-    url: &str,
-) -> Result<ShopifyProduct, AppError> {
-    let response = client
-        .get(url)
-        .header("User-Agent", USER_AGENT)
-        .header("Accept", "application/json")
+    request: reqwest::RequestBuilder,
+) -> Result<ShopifyProduct, ProductJsonError> {
+    let response = request
         .send()
         .await
-        .map_err(|e| AppError::External(e.to_string()))?;
+        .map_err(|e| ProductJsonError::failed(e.to_string()))?;
 
-    if !response.status().is_success() {
-        return Err(AppError::External(format!(
-            "Failed to fetch product.json: HTTP {}",
-            response.status()
-        )));
+    let status = response.status();
+    if !status.is_success() {
+        let message = format!("Failed to fetch product.json: HTTP {}", status);
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(ProductJsonError::missing(message));
+        }
+        return Err(ProductJsonError::failed(message));
     }
 
-    let product_response: ShopifyProductResponse = response
-        .json()
+    let body = response
+        .bytes()
         .await
-        .map_err(|e| AppError::External(format!("Failed to parse product.json: {}", e)))?;
+        .map_err(|e| ProductJsonError::failed(e.to_string()))?;
+    let product_response: ShopifyProductResponse = serde_json::from_slice(&body)
+        .map_err(|e| ProductJsonError::missing(format!("Failed to parse product.json: {}", e)))?;
 
     Ok(product_response.product)
 }
 
+/// Why product.json couldn't be read
+#[derive(Debug)]
+struct ProductJsonError {
+    error: AppError,
+    /// The store answered with a 404 or a body that isn't product JSON, so it
+    /// most likely isn't a Shopify store
+    missing: bool,
+}
+
+impl ProductJsonError {
+    fn missing(message: String) -> Self {
+        Self {
+            error: AppError::External(message),
+            missing: true,
+        }
+    }
+
+    fn failed(message: String) -> Self {
+        Self {
+            error: AppError::External(message),
+            missing: false,
+        }
+    }
+}
+
+impl From<ProductJsonError> for AppError {
+    fn from(e: ProductJsonError) -> Self {
+        e.error
+    }
+}
+
 /// Find the target variant by ID, or return the first variant if no ID specified
 fn find_target_variant(
     variants: &[ShopifyVariant],
@@ -337,11 +513,12 @@ struct CartAvailabilityResult {
 /// If we can successfully add to cart, the product is in stock.
 /// If we get an error, check the error message to determine status.
 async fn check_cart_availability(
-    client: &reqwest::Client,
     base_url: &str,
     variant_id: i64,
+    conn: &DatabaseConnection,
+    options: &FetchOptions,
 ) -> Result<CartAvailabilityResult, AppError> {
-    let response = send_cart_add_request(client, base_url, variant_id).await?;
+    let response = send_cart_add_request(base_url, variant_id, conn, options).await?;
     let status_code = response.status();
     let body = response.text().await.unwrap_or_default();
 
@@ -354,7 +531,7 @@ async fn check_cart_availability(
     if status_code.is_success() {
         // Successfully added to cart - product is in stock
         // Try to clear the cart item we just added
-        clear_cart(client, base_url).await;
+        clear_cart(base_url, conn, options).await;
 
         return Ok(CartAvailabilityResult {
             status: AvailabilityStatus::InStock,
@@ -367,9 +544,10 @@ async fn check_cart_availability(
 
 /// Send a request to add an item to the Shopify cart
 async fn send_cart_add_request(
-    client: &reqwest::Client,
     base_url: &str,
     variant_id: i64,
+    conn: &DatabaseConnection,
+    options: &FetchOptions,
 ) -> Result<reqwest::Response, AppError> {
     let cart_url = format!("{}/cart/add.js", base_url);
     let payload = serde_json::json!({
@@ -379,15 +557,18 @@ async fn send_cart_add_request(
         }]
     });
 
-    client
-        .post(&cart_url)
-        .header("User-Agent", USER_AGENT)
-        .header("Content-Type", "application/json")
-        .header("Accept", "application/json")
-        .json(&payload)
-        .send()
-        .await
-        .map_err(|e| AppError::External(e.to_string()))
+    http_client::host_request(
+        reqwest::Method::POST,
+        &cart_url,
+        "application/json",
+        conn,
+        options,
+    )
+    .await?
+    .json(&payload)
+    .send()
+    .await
+    .map_err(|e| AppError::External(e.to_string()))
 }
 
 /// Parse an error body from the cart API to determine if out of stock
@@ -426,15 +607,22 @@ fn is_cart_error_out_of_stock(message: &str) -> bool {
 }
 
 /// Clear the cart after checking availability
-async fn clear_cart(client: &reqwest::Client, base_url: &str) {
+async fn clear_cart(base_url: &str, conn: &DatabaseConnection, options: &FetchOptions) {
     let clear_url = format!("{}/cart/clear.js", base_url);
-
-    if let Err(e) = client
-        .post(&clear_url)
-        .header("User-Agent", USER_AGENT)
-        .send()
-        .await
-    {
+    let request = http_client::host_request(
+        reqwest::Method::POST,
+        &clear_url,
+        "application/json",
+        conn,
+        options,
+    )
+    .await;
+
+    let result = match request {
+        Ok(request) => request.send().await.map(|_| ()).map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = result {
         log::debug!("Failed to clear Shopify cart (non-critical): {}", e);
     }
 }
@@ -466,10 +654,18 @@ fn extract_price_from_variant(variant: &ShopifyVariant, url: &str) -> PriceInfo
         .as_ref()
         .and_then(|p| parse_price_to_minor_units(p, price_currency.as_deref()));
 
+    let compare_at_minor_units = variant
+        .compare_at_price
+        .as_deref()
+        .and_then(|p| parse_price_to_minor_units(p, price_currency.as_deref()));
+
     PriceInfo {
         price_minor_units,
         price_currency,
         raw_price,
+        compare_at_minor_units: compare_at_above_price(price_minor_units, compare_at_minor_units),
+        price_valid_until: None,
+        price_range: None,
     }
 }
 
@@ -609,12 +805,14 @@ mod tests {
                 price: "10.00".to_string(),
                 available: Some(true),
                 price_currency: None,
+                compare_at_price: None,
             },
             ShopifyVariant {
                 id: 200,
                 price: "20.00".to_string(),
                 available: Some(false),
                 price_currency: None,
+                compare_at_price: None,
             },
         ];
 
@@ -642,6 +840,7 @@ mod tests {
             price: "330.00".to_string(),
             available: Some(true),
             price_currency: Some("AUD".to_string()),
+            compare_at_price: None,
         };
 
         let price = extract_price_from_variant(&variant, "https://store.com.au/products/test");
@@ -650,6 +849,34 @@ mod tests {
         assert_eq!(price.raw_price, Some("330.00".to_string()));
     }
 
+    #[test]
+    fn test_extract_price_from_variant_compare_at_price() {
+        let variant: ShopifyVariant = serde_json::from_value(serde_json::json!({
+            "id": 123,
+            "price": "70.00",
+            "available": true,
+            "compare_at_price": "100.00"
+        }))
+        .unwrap();
+
+        let price = extract_price_from_variant(&variant, "https://store.com.au/products/test");
+        assert_eq!(price.price_minor_units, Some(7000));
+        assert_eq!(price.compare_at_minor_units, Some(10000));
+    }
+
+    #[test]
+    fn test_extract_price_from_variant_null_compare_at_price() {
+        let variant: ShopifyVariant = serde_json::from_value(serde_json::json!({
+            "id": 123,
+            "price": "70.00",
+            "compare_at_price": null
+        }))
+        .unwrap();
+
+        let price = extract_price_from_variant(&variant, "https://store.com.au/products/test");
+        assert_eq!(price.compare_at_minor_units, None);
+    }
+
     #[test]
     fn test_extract_price_from_variant_empty_price() {
         let variant = ShopifyVariant {
@@ -657,6 +884,7 @@ mod tests {
             price: "".to_string(),
             available: None,
             price_currency: None,
+            compare_at_price: None,
         };
 
         let price = extract_price_from_variant(&variant, "https://store.com/products/test");
@@ -671,7 +899,8 @@ mod tests {
             id: 123,
             price: "50.00".to_string(),
             available: Some(true),
-            price_currency: None, // No currency in variant data
+            price_currency: None, // No currency in variant data,
+            compare_at_price: None,
         };
 
         // Australian domain
@@ -701,7 +930,8 @@ mod tests {
             id: 123,
             price: "50.00".to_string(),
             available: Some(true),
-            price_currency: Some("EUR".to_string()), // API currency
+            price_currency: Some("EUR".to_string()), // API currency,
+            compare_at_price: None,
         };
 
         // API currency (EUR) should take precedence over domain (.com.au → AUD)
@@ -781,7 +1011,8 @@ mod tests {
             id: 123,
             price: "50.00".to_string(),
             available: Some(true),
-            price_currency: Some("GBP".to_string()), // API returns GBP default
+            price_currency: Some("GBP".to_string()), // API returns GBP default,
+            compare_at_price: None,
         };
 
         // Path locale /en-au/ should override API's GBP and domain inference
@@ -796,6 +1027,7 @@ mod tests {
             price: "50.00".to_string(),
             available: Some(true),
             price_currency: Some("GBP".to_string()),
+            compare_at_price: None,
         };
 
         // No path locale; API currency (GBP) should take precedence over domain (.com.au → AUD)
@@ -809,7 +1041,8 @@ mod tests {
             id: 123,
             price: "50.00".to_string(),
             available: Some(true),
-            price_currency: None, // No API currency
+            price_currency: None, // No API currency,
+            compare_at_price: None,
         };
 
         // No path locale, no API currency; should fall back to domain (.com.au → AUD)
@@ -824,6 +1057,7 @@ mod tests {
             price: "50.00".to_string(),
             available: Some(true),
             price_currency: Some("EUR".to_string()),
+            compare_at_price: None,
         };
 
         // No path locale or recognizable domain, use API default
@@ -846,4 +1080,311 @@ mod tests {
         // "inventory" alone should not match - too generic
         assert!(!is_cart_error_out_of_stock("inventory updated"));
     }
+
+    mod products_json_tests {
+        use super::*;
+        use crate::services::scraper::http_client::ROBOTS_DISALLOWED_MESSAGE;
+        use crate::services::scraper::DEFAULT_MAX_JSON_LD_BLOCK_BYTES;
+        use crate::services::{DomainRequestOverrides, DomainSettingService};
+        use crate::services::{FetchOptions, FetchTimeouts, ProductGroupStrategy, ScraperService};
+        use crate::test_utils::{setup_fetch_db, MockResponse, MockServer};
+
+        const PRODUCTS_JSON: &str = r#"{"product": {"id": 1, "handle": "widget", "variants": [
+            {"id": 111, "price": "19.99", "available": true, "compare_at_price": null},
+            {"id": 222, "price": "24.99", "available": false, "compare_at_price": "29.99"}
+        ]}}"#;
+
+        /// Mock store answering `routes` (path -> status, body) and 404 otherwise
+        async fn mock_store(routes: Vec<(&'static str, u16, &'static str)>) -> MockServer {
+            MockServer::start(move |request| {
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, _, _)| *route == request.path())
+                    .map(|(_, status, body)| (*status, *body))
+                    .unwrap_or((404, "{}"));
+                MockResponse::json(status, body)
+            })
+            .await
+        }
+
+        #[tokio::test]
+        async fn test_fetch_products_json_matches_variant() {
+            let server = mock_store(vec![("/products/widget.json", 200, PRODUCTS_JSON)]).await;
+            let conn = setup_fetch_db().await;
+
+            let result = fetch_products_json(
+                &server.url("/products/widget?variant=222"),
+                &conn,
+                &fetch_options(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+            assert_eq!(
+                result.raw_availability.as_deref(),
+                Some(RAW_AVAILABILITY_PRODUCT_JSON_UNAVAILABLE)
+            );
+            assert_eq!(result.price.raw_price.as_deref(), Some("24.99"));
+            assert_eq!(result.price.price_minor_units, Some(2499));
+            assert_eq!(result.price.compare_at_minor_units, Some(2999));
+
+            // No ?variant= picks the first variant
+            let result =
+                fetch_products_json(&server.url("/products/widget"), &conn, &fetch_options())
+                    .await
+                    .unwrap();
+            assert_eq!(result.status, AvailabilityStatus::InStock);
+            assert_eq!(result.price.price_minor_units, Some(1999));
+        }
+
+        #[tokio::test]
+        async fn test_fetch_products_json_fails_without_availability() {
+            let server = mock_store(vec![(
+                "/products/widget.json",
+                200,
+                r#"{"product": {"variants": [{"id": 111, "price": "19.99"}]}}"#,
+            )])
+            .await;
+            let conn = setup_fetch_db().await;
+
+            let result =
+                fetch_products_json(&server.url("/products/widget"), &conn, &fetch_options()).await;
+            assert!(result.is_err());
+
+            // Unknown variant and missing endpoint fail too
+            let result = fetch_products_json(
+                &server.url("/products/widget?variant=999"),
+                &conn,
+                &fetch_options(),
+            )
+            .await;
+            assert!(result.is_err());
+            let result =
+                fetch_products_json(&server.url("/products/missing"), &conn, &fetch_options())
+                    .await;
+            assert!(result.is_err());
+        }
+
+        #[tokio::test]
+        async fn test_products_json_sent_with_host_overrides() {
+            let server = mock_store(vec![("/products/widget.json", 200, PRODUCTS_JSON)]).await;
+            let conn = setup_fetch_db().await;
+            let overrides = DomainRequestOverrides {
+                headers: [("User-Agent".to_string(), "MemberBot/1.0".to_string())].into(),
+                cookies: [("member".to_string(), "gold".to_string())].into(),
+            };
+            DomainSettingService::set_request_overrides(&conn, "127.0.0.1", overrides)
+                .await
+                .unwrap();
+
+            fetch_products_json(&server.url("/products/widget"), &conn, &fetch_options())
+                .await
+                .unwrap();
+
+            let requests = server.requests();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].header("user-agent"), Some("MemberBot/1.0"));
+            assert_eq!(requests[0].header("cookie"), Some("member=gold"));
+            assert_eq!(requests[0].header("accept"), Some("application/json"));
+        }
+
+        #[tokio::test]
+        async fn test_cart_api_fallback_when_products_json_fails() {
+            let server = mock_store(vec![
+                ("/products/widget.json", 500, "{}"),
+                ("/cart/add.js", 200, r#"{"items": []}"#),
+            ])
+            .await;
+            let html = r#"<link href="https://cdn.shopify.com/s/files/theme.css">"#;
+
+            let conn = setup_fetch_db().await;
+            let result = check_shopify_availability(
+                &server.url("/products/widget?variant=111"),
+                html,
+                &conn,
+                &fetch_options(),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result.status, AvailabilityStatus::InStock);
+            assert_eq!(
+                result.raw_availability.as_deref(),
+                Some(RAW_AVAILABILITY_CART_API_IN_STOCK)
+            );
+            assert_eq!(result.price.price_minor_units, None);
+            assert!(server.paths().contains(&"/cart/add.js".to_string()));
+
+            // Without a variant id there's nothing to add to the cart
+            let result = check_shopify_availability(
+                &server.url("/products/widget"),
+                html,
+                &conn,
+                &fetch_options(),
+            )
+            .await;
+            assert!(result.is_err());
+        }
+
+        #[tokio::test]
+        async fn test_cart_api_sent_with_host_overrides() {
+            let server = mock_store(vec![
+                ("/products/widget.json", 500, "{}"),
+                ("/cart/add.js", 200, r#"{"items": []}"#),
+                ("/cart/clear.js", 200, "{}"),
+            ])
+            .await;
+            let conn = setup_fetch_db().await;
+            let overrides = DomainRequestOverrides {
+                headers: [("User-Agent".to_string(), "MemberBot/1.0".to_string())].into(),
+                cookies: [("member".to_string(), "gold".to_string())].into(),
+            };
+            DomainSettingService::set_request_overrides(&conn, "127.0.0.1", overrides)
+                .await
+                .unwrap();
+            let html = r#"<link href="https://cdn.shopify.com/s/files/theme.css">"#;
+
+            check_shopify_availability(
+                &server.url("/products/widget?variant=111"),
+                html,
+                &conn,
+                &fetch_options(),
+            )
+            .await
+            .unwrap();
+
+            let requests = server.requests();
+            assert_eq!(
+                server.paths(),
+                vec!["/products/widget.json", "/cart/add.js", "/cart/clear.js"]
+            );
+            for request in &requests {
+                assert_eq!(request.header("user-agent"), Some("MemberBot/1.0"));
+                assert_eq!(request.header("cookie"), Some("member=gold"));
+                assert_eq!(request.header("accept"), Some("application/json"));
+            }
+        }
+
+        #[tokio::test]
+        async fn test_scraper_uses_products_json_before_fetching_page() {
+            let server = mock_store(vec![("/products/widget.json", 200, PRODUCTS_JSON)]).await;
+            let conn = setup_fetch_db().await;
+
+            let result = ScraperService::check_availability_with_headless(
+                &server.url("/products/widget?variant=111"),
+                &conn,
+                &fetch_options(),
+                true,
+                None,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result.status, AvailabilityStatus::InStock);
+            assert_eq!(server.paths(), vec!["/products/widget.json".to_string()]);
+        }
+
+        #[tokio::test]
+        async fn test_non_shopify_store_fetches_products_json_once() {
+            let server = MockServer::start(|request| {
+                if request.path().ends_with(".json") {
+                    return MockResponse::new(404, "Not Found");
+                }
+                MockResponse::html(
+                    r#"<script type="application/ld+json">
+                    {"@type": "Product", "name": "Widget",
+                     "offers": {"@type": "Offer", "price": "19.99", "priceCurrency": "USD",
+                                "availability": "https://schema.org/InStock"}}
+                    </script>"#,
+                )
+            })
+            .await;
+            let conn = setup_fetch_db().await;
+            let url = server.url("/products/widget");
+
+            for _ in 0..2 {
+                let result = ScraperService::check_availability_with_headless(
+                    &url,
+                    &conn,
+                    &fetch_options(),
+                    true,
+                    None,
+                )
+                .await
+                .unwrap();
+                assert_eq!(result.status, AvailabilityStatus::InStock);
+            }
+
+            assert!(NonShopifyOrigins::global().contains(&url));
+            assert_eq!(
+                server.paths(),
+                vec![
+                    "/products/widget.json".to_string(),
+                    "/products/widget".to_string(),
+                    "/products/widget".to_string(),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_failed_products_json_is_not_remembered() {
+            let server = mock_store(vec![("/products/widget.json", 500, "{}")]).await;
+            let conn = setup_fetch_db().await;
+            let url = server.url("/products/widget");
+
+            assert!(fetch_products_json(&url, &conn, &fetch_options())
+                .await
+                .is_err());
+            assert!(!NonShopifyOrigins::global().contains(&url));
+        }
+
+        #[tokio::test]
+        async fn test_products_json_respects_robots_txt() {
+            let server = mock_store(vec![
+                ("/robots.txt", 200, "User-agent: *\nDisallow: /products/"),
+                ("/products/widget.json", 200, PRODUCTS_JSON),
+            ])
+            .await;
+            let conn = setup_fetch_db().await;
+            let options = FetchOptions {
+                respect_robots_txt: true,
+                ..fetch_options()
+            };
+
+            let result = ScraperService::check_availability_with_headless(
+                &server.url("/products/widget?variant=111"),
+                &conn,
+                &options,
+                true,
+                None,
+            )
+            .await;
+
+            assert!(
+                matches!(result, Err(AppError::Validation(ref msg)) if msg == ROBOTS_DISALLOWED_MESSAGE)
+            );
+            assert_eq!(server.paths(), vec!["/robots.txt".to_string()]);
+        }
+
+        fn fetch_options() -> FetchOptions {
+            FetchOptions {
+                enable_headless: false,
+                allow_manual_verification: false,
+                headless_profile_reuse: true,
+                session_cache_duration_days: 14,
+                timeouts: FetchTimeouts {
+                    min_host_interval_ms: 0,
+                    ..FetchTimeouts::default()
+                },
+                force_headless_domains: Vec::new(),
+                respect_robots_txt: false,
+                rotate_user_agent: false,
+                product_group_strategy: ProductGroupStrategy::First,
+                headless_browser_path: None,
+                insecure_hosts: Vec::new(),
+                max_redirects: 10,
+                max_json_ld_block_bytes: DEFAULT_MAX_JSON_LD_BLOCK_BYTES,
+            }
+        }
+    }
 }
@@ -0,0 +1,398 @@
+//! Target adapter for parsing embedded RedSky API responses.
+//!
+//! Target product pages are rendered from RedSky API responses that are
+//! embedded in the page, either in `window.__TGT_DATA__` (usually wrapped in a
+//! `JSON.parse("...")` string) or in the Next.js `__NEXT_DATA__` payload. The
+//! product is identified by its TCIN (Target.com Item Number).
+
+use serde_json::Value;
+use url::Url;
+
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
+use product_stalker_core::AppError;
+
+use super::nextjs_data;
+use super::price_parser::{parse_price_to_minor_units, scalar_to_string, PriceInfo};
+use super::ScrapingResult;
+
+/// Target US prices are always in US dollars.
+const CURRENCY: &str = "USD";
+
+/// Global that holds Target's preloaded RedSky query responses.
+const TGT_DATA_MARKER: &str = "__TGT_DATA__";
+
+/// Check if the URL is for Target US (target.com)
+pub fn is_target_url(url: &str) -> bool {
+    Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .is_some_and(|host| host == "target.com" || host.ends_with(".target.com"))
+}
+
+/// Extract the TCIN from a Target product URL.
+///
+/// A `preselect` query parameter (the selected variant) takes precedence over
+/// the `A-<tcin>` path segment, e.g. `/p/some-product/-/A-12345678?preselect=87654321`.
+pub fn extract_tcin(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+
+    let preselect = parsed
+        .query_pairs()
+        .find(|(key, _)| key == "preselect")
+        .map(|(_, value)| value.to_string())
+        .filter(|value| is_tcin(value));
+
+    preselect.or_else(|| {
+        parsed
+            .path_segments()?
+            .filter_map(|segment| segment.strip_prefix("A-"))
+            .find(|tcin| is_tcin(tcin))
+            .map(str::to_string)
+    })
+}
+
+/// Parse product availability and price from a Target product page.
+///
+/// The RedSky product object is found anywhere in the embedded data by its
+/// `tcin`, preferring the TCIN from the URL:
+/// ```json
+/// {
+///   "tcin": "12345678",
+///   "price": { "current_retail": 19.99, "formatted_current_price": "$19.99" },
+///   "fulfillment": { "shipping_options": { "availability_status": "IN_STOCK" } }
+/// }
+/// ```
+///
+/// Price and fulfillment may come from separate query responses for the same
+/// TCIN, so both are looked up across all matching objects.
+pub fn parse_target_data(html: &str, url: &str) -> Result<ScrapingResult, AppError> {
+    let data = extract_embedded_data(html)?;
+
+    let mut candidates = Vec::new();
+    collect_products(&data, &mut candidates);
+    let products = select_products(candidates, extract_tcin(url).as_deref());
+
+    let raw_availability = products
+        .iter()
+        .find_map(|product| shipping_status(product))
+        .map(str::to_string);
+    let raw_price = products
+        .iter()
+        .find_map(|product| product.get("price").and_then(price_value));
+
+    if raw_availability.is_none() && raw_price.is_none() {
+        return Err(AppError::External(
+            "No product data found in Target page".to_string(),
+        ));
+    }
+
+    let status = raw_availability
+        .as_deref()
+        .map(map_availability_status)
+        .unwrap_or_default();
+    let price_minor_units = raw_price
+        .as_deref()
+        .and_then(|p| parse_price_to_minor_units(p, Some(CURRENCY)));
+
+    Ok(ScrapingResult {
+        status,
+        raw_availability,
+        price: PriceInfo {
+            price_minor_units,
+            price_currency: price_minor_units.map(|_| CURRENCY.to_string()),
+            raw_price,
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            price_range: None,
+        },
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
+    })
+}
+
+/// A TCIN is a purely numeric ID
+fn is_tcin(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Extract the embedded RedSky data, trying `__TGT_DATA__` then `__NEXT_DATA__`.
+fn extract_embedded_data(html: &str) -> Result<Value, AppError> {
+    if let Some(data) = extract_tgt_data(html) {
+        return Ok(data);
+    }
+    nextjs_data::extract_next_data(html).map_err(|_| {
+        AppError::External("No embedded product data found in Target page".to_string())
+    })
+}
+
+/// Parse the JSON assigned to `__TGT_DATA__`.
+///
+/// Handles a `JSON.parse("...")` string literal (possibly wrapped, e.g. in
+/// `deepFreeze(...)`) and falls back to a plain object literal. Only the first
+/// JSON value is parsed, so trailing script is ignored.
+fn extract_tgt_data(html: &str) -> Option<Value> {
+    let marker = html.find(TGT_DATA_MARKER)?;
+    let after_marker = &html[marker + TGT_DATA_MARKER.len()..];
+
+    let from_json_parse = after_marker.find("JSON.parse(").and_then(|parse_at| {
+        let literal = after_marker[parse_at + "JSON.parse(".len()..].trim_start();
+        let json: String = first_json_value(literal)?;
+        serde_json::from_str(&json).ok()
+    });
+
+    from_json_parse.or_else(|| {
+        let object_at = after_marker.find('{')?;
+        first_json_value(&after_marker[object_at..])
+    })
+}
+
+/// Deserialize the first JSON value at the start of `input`
+fn first_json_value<T: serde::de::DeserializeOwned>(input: &str) -> Option<T> {
+    serde_json::Deserializer::from_str(input)
+        .into_iter::<T>()
+        .next()?
+        .ok()
+}
+
+/// Depth-first collection of objects carrying a `tcin`
+fn collect_products<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(map) => {
+            if map.contains_key("tcin") {
+                out.push(value);
+            }
+            map.values().for_each(|v| collect_products(v, out));
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_products(v, out)),
+        _ => {}
+    }
+}
+
+/// Keep the objects matching `tcin`, or all of them if none match
+fn select_products<'a>(candidates: Vec<&'a Value>, tcin: Option<&str>) -> Vec<&'a Value> {
+    let matching: Vec<&Value> = match tcin {
+        Some(tcin) => candidates
+            .iter()
+            .copied()
+            .filter(|product| {
+                product.get("tcin").and_then(scalar_to_string).as_deref() == Some(tcin)
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    if matching.is_empty() {
+        candidates
+    } else {
+        matching
+    }
+}
+
+/// Read `fulfillment.shipping_options.availability_status`
+fn shipping_status(product: &Value) -> Option<&str> {
+    product
+        .get("fulfillment")?
+        .get("shipping_options")?
+        .get("availability_status")?
+        .as_str()
+}
+
+/// Read the current price, preferring the numeric `current_retail`
+fn price_value(price: &Value) -> Option<String> {
+    price
+        .get("current_retail")
+        .and_then(scalar_to_string)
+        .or_else(|| {
+            price
+                .get("formatted_current_price")
+                .and_then(scalar_to_string)
+        })
+}
+
+/// Map a RedSky shipping availability status to an AvailabilityStatus
+fn map_availability_status(status: &str) -> AvailabilityStatus {
+    match status.to_ascii_uppercase().as_str() {
+        "IN_STOCK" => AvailabilityStatus::InStock,
+        "LIMITED_STOCK" => AvailabilityStatus::LimitedAvailability,
+        "PRE_ORDER_SELLABLE" => AvailabilityStatus::PreOrder,
+        "OUT_OF_STOCK" | "UNAVAILABLE" | "PRE_ORDER_UNSELLABLE" => AvailabilityStatus::OutOfStock,
+        _ => AvailabilityStatus::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRODUCT_URL: &str = "https://www.target.com/p/some-blender/-/A-12345678";
+
+    /// Wrap RedSky data the way Target does: an escaped string passed to JSON.parse
+    fn target_html(data: &str) -> String {
+        let escaped = serde_json::to_string(data).unwrap();
+        format!(
+            r#"<html><head><script>Object.defineProperties(window, {{'__TGT_DATA__': {{ configurable: false, value: deepFreeze(JSON.parse({})) }}}});</script></head><body></body></html>"#,
+            escaped
+        )
+    }
+
+    fn product_json(tcin: &str, status: &str, price: &str) -> String {
+        format!(
+            r#"{{"tcin":"{}","price":{{"current_retail":{},"formatted_current_price":"${}"}},"fulfillment":{{"shipping_options":{{"availability_status":"{}"}}}}}}"#,
+            tcin, price, price, status
+        )
+    }
+
+    fn preloaded(product: &str) -> String {
+        format!(
+            r#"{{"__PRELOADED_QUERIES__":{{"queries":[[["@web/domain-product/get-pdp-v1",{{"tcin":"12345678"}}],{{"data":{{"product":{}}}}}]]}}}}"#,
+            product
+        )
+    }
+
+    #[test]
+    fn test_is_target_url() {
+        assert!(is_target_url(PRODUCT_URL));
+        assert!(is_target_url("https://target.com/p/-/A-12345678"));
+        assert!(!is_target_url("https://www.target.com.au/p/item/123"));
+        assert!(!is_target_url("https://www.nottarget.com/p/-/A-1"));
+        assert!(!is_target_url("https://example.com/target.com"));
+    }
+
+    #[test]
+    fn test_extract_tcin_from_path() {
+        assert_eq!(extract_tcin(PRODUCT_URL), Some("12345678".to_string()));
+    }
+
+    #[test]
+    fn test_extract_tcin_prefers_preselect() {
+        let url = format!("{}?preselect=87654321#lnk=sametab", PRODUCT_URL);
+        assert_eq!(extract_tcin(&url), Some("87654321".to_string()));
+    }
+
+    #[test]
+    fn test_extract_tcin_missing() {
+        assert_eq!(extract_tcin("https://www.target.com/c/kitchen"), None);
+        assert_eq!(extract_tcin("https://www.target.com/p/-/A-abc"), None);
+    }
+
+    #[test]
+    fn test_parse_in_stock() {
+        let html = target_html(&preloaded(&product_json("12345678", "IN_STOCK", "49.99")));
+
+        let result = parse_target_data(&html, PRODUCT_URL).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.raw_availability, Some("IN_STOCK".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(4999));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+        assert_eq!(result.price.raw_price, Some("49.99".to_string()));
+    }
+
+    #[test]
+    fn test_parse_out_of_stock() {
+        let html = target_html(&preloaded(&product_json(
+            "12345678",
+            "OUT_OF_STOCK",
+            "1299.00",
+        )));
+
+        let result = parse_target_data(&html, PRODUCT_URL).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(129900));
+    }
+
+    #[test]
+    fn test_parse_plain_object_assignment() {
+        let html = format!(
+            "<script>window.__TGT_DATA__ = {};</script>",
+            preloaded(&product_json("12345678", "LIMITED_STOCK", "5"))
+        );
+
+        let result = parse_target_data(&html, PRODUCT_URL).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::LimitedAvailability);
+        assert_eq!(result.price.price_minor_units, Some(500));
+    }
+
+    #[test]
+    fn test_parse_next_data() {
+        let html = format!(
+            r#"<script id="__NEXT_DATA__" type="application/json">{{"props":{{"pageProps":{}}}}}</script>"#,
+            preloaded(&product_json("12345678", "IN_STOCK", "19.99"))
+        );
+
+        let result = parse_target_data(&html, PRODUCT_URL).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.price.price_minor_units, Some(1999));
+    }
+
+    #[test]
+    fn test_parse_selects_preselected_variant() {
+        let parent = format!(
+            r#"{{"tcin":"12345678","children":[{},{}]}}"#,
+            product_json("11111111", "IN_STOCK", "10"),
+            product_json("22222222", "OUT_OF_STOCK", "12")
+        );
+        let html = target_html(&preloaded(&parent));
+        let url = format!("{}?preselect=22222222", PRODUCT_URL);
+
+        let result = parse_target_data(&html, &url).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(1200));
+    }
+
+    #[test]
+    fn test_parse_merges_separate_price_and_fulfillment() {
+        let data = r#"{"pdp":{"product":{"tcin":"12345678","price":{"formatted_current_price":"$24.99"}}},
+            "fulfillment":{"product":{"tcin":"12345678","fulfillment":{"shipping_options":{"availability_status":"IN_STOCK"}}}}}"#;
+        let html = target_html(data);
+
+        let result = parse_target_data(&html, PRODUCT_URL).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.price.price_minor_units, Some(2499));
+        assert_eq!(result.price.raw_price, Some("$24.99".to_string()));
+    }
+
+    #[test]
+    fn test_parse_price_without_fulfillment_is_unknown() {
+        let data = r#"{"product":{"tcin":"12345678","price":{"current_retail":9.99}}}"#;
+        let html = target_html(data);
+
+        let result = parse_target_data(&html, PRODUCT_URL).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::Unknown);
+        assert_eq!(result.raw_availability, None);
+        assert_eq!(result.price.price_minor_units, Some(999));
+    }
+
+    #[test]
+    fn test_parse_missing_data_errors() {
+        let html = "<html><body><p>Nothing embedded</p></body></html>";
+        assert!(parse_target_data(html, PRODUCT_URL).is_err());
+    }
+
+    #[test]
+    fn test_parse_data_without_product_errors() {
+        let html = target_html(r#"{"__PRELOADED_QUERIES__":{"queries":[]}}"#);
+        assert!(parse_target_data(&html, PRODUCT_URL).is_err());
+    }
+
+    #[test]
+    fn test_map_availability_status() {
+        assert_eq!(
+            map_availability_status("IN_STOCK"),
+            AvailabilityStatus::InStock
+        );
+        assert_eq!(
+            map_availability_status("OUT_OF_STOCK"),
+            AvailabilityStatus::OutOfStock
+        );
+        assert_eq!(
+            map_availability_status("PRE_ORDER_SELLABLE"),
+            AvailabilityStatus::PreOrder
+        );
+        assert_eq!(
+            map_availability_status("SOMETHING_NEW"),
+            AvailabilityStatus::Unknown
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Browser identities sent with page requests: a User-Agent plus the client hints
+//! that browser would send alongside it.
+//!
+//! Always sending the same User-Agent makes the scraper easy to fingerprint, so
+//! with rotation enabled each host is given one of a small pool of current desktop
+//! browsers. The choice is stable per host for the life of the process, so a site
+//! never sees one visitor switch browsers mid-session.
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::OnceLock;
+
+use reqwest::header::{HeaderMap, HeaderValue};
+
+use super::USER_AGENT;
+use crate::services::ManualVerificationService;
+
+/// `Sec-Ch-Ua*` client hints; only Chromium-based browsers send these
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ClientHints {
+    /// `Sec-Ch-Ua` brand list, whose versions must match the User-Agent's
+    pub brands: &'static str,
+    /// `Sec-Ch-Ua-Platform`, matching the User-Agent's operating system
+    pub platform: &'static str,
+}
+
+/// A User-Agent and the client hints that go with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BrowserProfile {
+    pub user_agent: &'static str,
+    /// `None` for browsers that don't send client hints (Firefox, Safari)
+    pub client_hints: Option<ClientHints>,
+}
+
+impl BrowserProfile {
+    /// Identity headers for a page request
+    pub(crate) fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("user-agent", HeaderValue::from_static(self.user_agent));
+        if let Some(hints) = self.client_hints {
+            headers.insert("sec-ch-ua", HeaderValue::from_static(hints.brands));
+            headers.insert("sec-ch-ua-mobile", HeaderValue::from_static("?0"));
+            headers.insert(
+                "sec-ch-ua-platform",
+                HeaderValue::from_static(hints.platform),
+            );
+        }
+        headers
+    }
+}
+
+/// Identity used when rotation is off, and for requests replaying a verified
+/// session (whose cookies were issued to this User-Agent)
+pub(crate) const DEFAULT_PROFILE: BrowserProfile = BrowserProfile {
+    user_agent: USER_AGENT,
+    client_hints: Some(ClientHints {
+        brands: r#""Not_A Brand";v="8", "Chromium";v="120", "Google Chrome";v="120""#,
+        platform: r#""Windows""#,
+    }),
+};
+
+/// Current desktop browsers to rotate between
+pub(crate) const ROTATION_POOL: &[BrowserProfile] = &[
+    BrowserProfile {
+        user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/141.0.0.0 Safari/537.36",
+        client_hints: Some(ClientHints {
+            brands: r#""Google Chrome";v="141", "Not?A_Brand";v="8", "Chromium";v="141""#,
+            platform: r#""Windows""#,
+        }),
+    },
+    BrowserProfile {
+        user_agent: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/141.0.0.0 Safari/537.36",
+        client_hints: Some(ClientHints {
+            brands: r#""Google Chrome";v="141", "Not?A_Brand";v="8", "Chromium";v="141""#,
+            platform: r#""macOS""#,
+        }),
+    },
+    BrowserProfile {
+        user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/141.0.0.0 Safari/537.36 Edg/141.0.0.0",
+        client_hints: Some(ClientHints {
+            brands: r#""Microsoft Edge";v="141", "Not?A_Brand";v="8", "Chromium";v="141""#,
+            platform: r#""Windows""#,
+        }),
+    },
+    BrowserProfile {
+        user_agent:
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:143.0) Gecko/20100101 Firefox/143.0",
+        client_hints: None,
+    },
+    BrowserProfile {
+        user_agent: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.6 Safari/605.1.15",
+        client_hints: None,
+    },
+];
+
+/// Pick the identity for a request to `url`.
+///
+/// With rotation off, or when the host can't be parsed, this is [`DEFAULT_PROFILE`].
+/// Otherwise the host is hashed with a per-process random seed, so each host keeps
+/// one identity until the app restarts.
+pub(crate) fn profile_for_url(url: &str, rotate: bool) -> &'static BrowserProfile {
+    if !rotate {
+        return &DEFAULT_PROFILE;
+    }
+    let Ok(host) = ManualVerificationService::extract_domain(url) else {
+        return &DEFAULT_PROFILE;
+    };
+
+    static SEED: OnceLock<RandomState> = OnceLock::new();
+    let hash = SEED
+        .get_or_init(RandomState::new)
+        .hash_one(host.to_lowercase());
+    &ROTATION_POOL[(hash % ROTATION_POOL.len() as u64) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Major version after `marker` in a User-Agent, e.g. "Chrome/" -> "141"
+    fn version_after<'a>(user_agent: &'a str, marker: &str) -> Option<&'a str> {
+        let rest = &user_agent[user_agent.find(marker)? + marker.len()..];
+        rest.split(['.', ' ']).next()
+    }
+
+    fn all_profiles() -> impl Iterator<Item = &'static BrowserProfile> {
+        std::iter::once(&DEFAULT_PROFILE).chain(ROTATION_POOL)
+    }
+
+    #[test]
+    fn test_client_hint_versions_match_user_agent() {
+        for profile in all_profiles() {
+            let Some(hints) = profile.client_hints else {
+                continue;
+            };
+            let chrome = version_after(profile.user_agent, "Chrome/").unwrap();
+            assert!(
+                hints
+                    .brands
+                    .contains(&format!(r#""Chromium";v="{}""#, chrome)),
+                "{:?}",
+                profile
+            );
+            let brand = match version_after(profile.user_agent, "Edg/") {
+                Some(edge) => format!(r#""Microsoft Edge";v="{}""#, edge),
+                None => format!(r#""Google Chrome";v="{}""#, chrome),
+            };
+            assert!(hints.brands.contains(&brand), "{:?}", profile);
+        }
+    }
+
+    #[test]
+    fn test_client_hint_platform_matches_user_agent() {
+        for profile in all_profiles() {
+            let Some(hints) = profile.client_hints else {
+                continue;
+            };
+            let expected = if profile.user_agent.contains("Windows NT") {
+                r#""Windows""#
+            } else if profile.user_agent.contains("Macintosh") {
+                r#""macOS""#
+            } else {
+                panic!("unexpected platform in {}", profile.user_agent);
+            };
+            assert_eq!(hints.platform, expected);
+        }
+    }
+
+    #[test]
+    fn test_only_chromium_browsers_send_client_hints() {
+        for profile in all_profiles() {
+            assert_eq!(
+                profile.client_hints.is_some(),
+                profile.user_agent.contains("Chrome/"),
+                "{}",
+                profile.user_agent
+            );
+        }
+    }
+
+    #[test]
+    fn test_headers_without_client_hints() {
+        let firefox = ROTATION_POOL
+            .iter()
+            .find(|profile| profile.user_agent.contains("Firefox/"))
+            .unwrap();
+
+        let headers = firefox.headers();
+
+        assert_eq!(headers["user-agent"], firefox.user_agent);
+        assert!(!headers.contains_key("sec-ch-ua"));
+        assert!(!headers.contains_key("sec-ch-ua-platform"));
+    }
+
+    #[test]
+    fn test_headers_with_client_hints() {
+        let headers = DEFAULT_PROFILE.headers();
+
+        assert_eq!(headers["user-agent"], USER_AGENT);
+        assert_eq!(
+            headers["sec-ch-ua"],
+            r#""Not_A Brand";v="8", "Chromium";v="120", "Google Chrome";v="120""#
+        );
+        assert_eq!(headers["sec-ch-ua-mobile"], "?0");
+        assert_eq!(headers["sec-ch-ua-platform"], r#""Windows""#);
+    }
+
+    #[test]
+    fn test_rotation_off_uses_default_profile() {
+        assert_eq!(
+            profile_for_url("https://shop.example.com/item", false),
+            &DEFAULT_PROFILE
+        );
+    }
+
+    #[test]
+    fn test_rotation_is_stable_per_host() {
+        let first = profile_for_url("https://shop.example.com/a", true);
+        let second = profile_for_url("https://SHOP.example.com/b?x=1", true);
+
+        assert_eq!(first, second);
+        assert!(ROTATION_POOL.contains(first));
+    }
+
+    #[test]
+    fn test_rotation_spreads_hosts_across_pool() {
+        let chosen: std::collections::HashSet<&str> = (0..50)
+            .map(|i| profile_for_url(&format!("https://shop{}.example.com/", i), true).user_agent)
+            .collect();
+
+        assert!(chosen.len() > 1);
+    }
+
+    #[test]
+    fn test_unparseable_url_uses_default_profile() {
+        assert_eq!(profile_for_url("not a url", true), &DEFAULT_PROFILE);
+    }
+}
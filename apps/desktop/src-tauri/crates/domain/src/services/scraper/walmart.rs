@@ -0,0 +1,239 @@
+//! Walmart adapter for parsing product data from the page's hydration JSON.
+//!
+//! Walmart product pages are rendered with Next.js and embed the product in the
+//! `__NEXT_DATA__` payload under `initialData.data.product`, with an
+//! `availabilityStatus` string and a `priceInfo.currentPrice` dollar price.
+
+use serde_json::Value;
+use url::Url;
+
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
+use product_stalker_core::AppError;
+
+use super::price_parser::{parse_price_to_minor_units, scalar_to_string, PriceInfo};
+use super::ScrapingResult;
+
+/// Check if the URL is for Walmart US (walmart.com) or Canada (walmart.ca)
+pub fn is_walmart_url(url: &str) -> bool {
+    Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .is_some_and(|host| {
+            ["walmart.com", "walmart.ca"]
+                .iter()
+                .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+        })
+}
+
+/// Parse product availability from Walmart hydration data.
+///
+/// The expected structure within pageProps is:
+/// ```json
+/// {
+///   "initialData": {
+///     "data": {
+///       "product": {
+///         "usItemId": "123456789",
+///         "name": "Product Name",
+///         "availabilityStatus": "IN_STOCK",
+///         "priceInfo": {
+///           "currentPrice": { "price": 12.97, "currencyUnit": "USD" },
+///           "wasPrice": { "price": 15.0, "currencyUnit": "USD" }
+///         }
+///       }
+///     }
+///   }
+/// }
+/// ```
+pub fn parse_walmart_data(page_props: &Value) -> Result<ScrapingResult, AppError> {
+    let product = page_props
+        .pointer("/initialData/data/product")
+        .or_else(|| page_props.get("product"))
+        .filter(|product| product.is_object())
+        .ok_or_else(|| {
+            AppError::External("No product data found in Walmart page props".to_string())
+        })?;
+
+    let raw_availability = product
+        .get("availabilityStatus")
+        .and_then(Value::as_str)
+        .ok_or_else(|| {
+            AppError::External("No availability found in Walmart product data".to_string())
+        })?;
+
+    Ok(ScrapingResult {
+        status: map_availability(raw_availability),
+        raw_availability: Some(raw_availability.to_string()),
+        price: extract_price_info(product),
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
+    })
+}
+
+/// Map Walmart's `availabilityStatus` (e.g. "IN_STOCK", "OUT_OF_STOCK") to an
+/// AvailabilityStatus
+fn map_availability(value: &str) -> AvailabilityStatus {
+    match value.to_ascii_uppercase().as_str() {
+        "IN_STOCK" | "AVAILABLE" => AvailabilityStatus::InStock,
+        "LIMITED_STOCK" => AvailabilityStatus::LimitedAvailability,
+        "OUT_OF_STOCK" | "NOT_AVAILABLE" | "UNAVAILABLE" | "RETIRED" => {
+            AvailabilityStatus::OutOfStock
+        }
+        "PRE_ORDER" | "PREORDER" => AvailabilityStatus::PreOrder,
+        "BACKORDER" | "BACK_ORDER" => AvailabilityStatus::BackOrder,
+        _ => AvailabilityStatus::Unknown,
+    }
+}
+
+/// Extract the current price, with the "was" price as compare-at when higher
+fn extract_price_info(product: &Value) -> PriceInfo {
+    let Some(current) = product.pointer("/priceInfo/currentPrice") else {
+        return PriceInfo::default();
+    };
+
+    let raw_price = current.get("price").and_then(scalar_to_string);
+    let currency = current
+        .get("currencyUnit")
+        .and_then(Value::as_str)
+        .map(str::to_uppercase)
+        .unwrap_or_else(|| "USD".to_string());
+
+    let price_minor_units = raw_price
+        .as_deref()
+        .and_then(|p| parse_price_to_minor_units(p, Some(&currency)));
+    let compare_at_minor_units = product
+        .pointer("/priceInfo/wasPrice/price")
+        .and_then(scalar_to_string)
+        .and_then(|p| parse_price_to_minor_units(&p, Some(&currency)))
+        .filter(|was| price_minor_units.is_some_and(|now| *was > now));
+
+    PriceInfo {
+        price_minor_units,
+        price_currency: price_minor_units.map(|_| currency),
+        raw_price,
+        compare_at_minor_units,
+        price_valid_until: None,
+        price_range: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn page_props(product: Value) -> Value {
+        json!({"initialData": {"data": {"product": product}}})
+    }
+
+    #[test]
+    fn test_is_walmart_url() {
+        assert!(is_walmart_url(
+            "https://www.walmart.com/ip/Onn-65-Class-4K-TV/123456789"
+        ));
+        assert!(is_walmart_url(
+            "https://www.walmart.ca/en/ip/kettle/6000201"
+        ));
+        assert!(!is_walmart_url("https://notwalmart.com/ip/123"));
+        assert!(!is_walmart_url("https://example.com/?ref=walmart.com"));
+    }
+
+    #[test]
+    fn test_parse_in_stock_with_was_price() {
+        let props = page_props(json!({
+            "usItemId": "123456789",
+            "name": "Onn 65\" 4K TV",
+            "availabilityStatus": "IN_STOCK",
+            "priceInfo": {
+                "currentPrice": {"price": 298.0, "priceString": "$298.00", "currencyUnit": "USD"},
+                "wasPrice": {"price": 348.0, "priceString": "$348.00", "currencyUnit": "USD"}
+            }
+        }));
+
+        let result = parse_walmart_data(&props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.raw_availability, Some("IN_STOCK".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(29800));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+        assert_eq!(result.price.compare_at_minor_units, Some(34800));
+    }
+
+    #[test]
+    fn test_parse_out_of_stock() {
+        let props = page_props(json!({
+            "availabilityStatus": "OUT_OF_STOCK",
+            "priceInfo": {"currentPrice": {"price": 12.97, "currencyUnit": "USD"}}
+        }));
+
+        let result = parse_walmart_data(&props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, Some(1297));
+        assert_eq!(result.price.compare_at_minor_units, None);
+    }
+
+    #[test]
+    fn test_parse_canadian_price() {
+        let props = page_props(json!({
+            "availabilityStatus": "IN_STOCK",
+            "priceInfo": {"currentPrice": {"price": "24.88", "currencyUnit": "CAD"}}
+        }));
+
+        let result = parse_walmart_data(&props).unwrap();
+        assert_eq!(result.price.price_minor_units, Some(2488));
+        assert_eq!(result.price.price_currency, Some("CAD".to_string()));
+    }
+
+    #[test]
+    fn test_parse_without_price() {
+        let props = page_props(json!({"availabilityStatus": "OUT_OF_STOCK", "priceInfo": {}}));
+
+        let result = parse_walmart_data(&props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.price.price_minor_units, None);
+        assert_eq!(result.price.price_currency, None);
+    }
+
+    #[test]
+    fn test_was_price_not_above_current_is_ignored() {
+        let props = page_props(json!({
+            "availabilityStatus": "IN_STOCK",
+            "priceInfo": {
+                "currentPrice": {"price": 10.0},
+                "wasPrice": {"price": 10.0}
+            }
+        }));
+
+        let result = parse_walmart_data(&props).unwrap();
+        assert_eq!(result.price.price_minor_units, Some(1000));
+        assert_eq!(result.price.price_currency, Some("USD".to_string()));
+        assert_eq!(result.price.compare_at_minor_units, None);
+    }
+
+    #[test]
+    fn test_map_availability_variants() {
+        assert_eq!(
+            map_availability("LIMITED_STOCK"),
+            AvailabilityStatus::LimitedAvailability
+        );
+        assert_eq!(
+            map_availability("NOT_AVAILABLE"),
+            AvailabilityStatus::OutOfStock
+        );
+        assert_eq!(map_availability("PRE_ORDER"), AvailabilityStatus::PreOrder);
+        assert_eq!(map_availability("in_stock"), AvailabilityStatus::InStock);
+        assert_eq!(map_availability("SOMETHING"), AvailabilityStatus::Unknown);
+    }
+
+    #[test]
+    fn test_parse_no_availability() {
+        let props = page_props(json!({"priceInfo": {"currentPrice": {"price": 1.0}}}));
+        assert!(parse_walmart_data(&props).is_err());
+    }
+
+    #[test]
+    fn test_parse_no_product() {
+        assert!(parse_walmart_data(&json!({"initialData": {"data": {}}})).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! Woolworths adapter for parsing product data from the page's hydration JSON.
+//!
+//! Woolworths product pages do not publish Schema.org offers and block headless
+//! browsers, but embed the product in the `__NEXT_DATA__` payload with boolean
+//! stock flags (`IsAvailable`, `IsInStock`) and a dollar `Price`.
+
+use serde_json::Value;
+
+use crate::entities::availability_check::{AvailabilityStatus, Confidence};
+use product_stalker_core::AppError;
+
+use super::price_parser::{parse_price_to_minor_units, scalar_to_string, PriceInfo};
+use super::ScrapingResult;
+
+/// Woolworths only sells in Australia
+const CURRENCY: &str = "AUD";
+
+/// Check if the URL is for Woolworths
+pub fn is_woolworths_url(url: &str) -> bool {
+    url.contains("woolworths.com.au")
+}
+
+/// Parse product availability from Woolworths hydration data.
+///
+/// The product is found under `product`, `productDetails.Product` or `productDetails`:
+/// ```json
+/// {
+///   "product": {
+///     "Stockcode": 123456,
+///     "Name": "Product Name",
+///     "Price": 4.5,
+///     "IsAvailable": true,
+///     "IsInStock": true
+///   }
+/// }
+/// ```
+///
+/// The product is out of stock if either flag is false.
+pub fn parse_woolworths_data(page_props: &Value) -> Result<ScrapingResult, AppError> {
+    let product = find_product_data(page_props).ok_or_else(|| {
+        AppError::External("No product data found in Woolworths page props".to_string())
+    })?;
+
+    let is_available = product.get("IsAvailable").and_then(Value::as_bool);
+    let is_in_stock = product.get("IsInStock").and_then(Value::as_bool);
+
+    let (status, raw_availability) = match (is_available, is_in_stock) {
+        (None, None) => {
+            return Err(AppError::External(
+                "No availability found in Woolworths product data".to_string(),
+            ))
+        }
+        (Some(false), _) => (AvailabilityStatus::OutOfStock, "unavailable"),
+        (_, Some(false)) => (AvailabilityStatus::OutOfStock, "out-of-stock"),
+        _ => (AvailabilityStatus::InStock, "in-stock"),
+    };
+
+    Ok(ScrapingResult {
+        status,
+        raw_availability: Some(raw_availability.to_string()),
+        price: extract_price_info(product),
+        region_mismatch: false,
+        fetch_metrics: None,
+        canonical_url: None,
+        confidence: Confidence::High,
+    })
+}
+
+/// Find the product object from the possible locations in pageProps
+fn find_product_data(page_props: &Value) -> Option<&Value> {
+    let candidates = [
+        page_props.get("product"),
+        page_props
+            .get("productDetails")
+            .and_then(|details| details.get("Product")),
+        page_props.get("productDetails"),
+    ];
+
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|product| product.get("IsAvailable").is_some() || product.get("IsInStock").is_some())
+}
+
+/// Extract the dollar price (falling back to the in-store price) in cents
+fn extract_price_info(product: &Value) -> PriceInfo {
+    let raw_price = ["Price", "InstorePrice"]
+        .iter()
+        .find_map(|key| product.get(*key).and_then(scalar_to_string));
+
+    let price_minor_units = raw_price
+        .as_deref()
+        .and_then(|p| parse_price_to_minor_units(p, Some(CURRENCY)));
+
+    PriceInfo {
+        price_minor_units,
+        price_currency: raw_price.as_ref().map(|_| CURRENCY.to_string()),
+        raw_price,
+        compare_at_minor_units: None,
+        price_valid_until: None,
+        price_range: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_woolworths_url() {
+        assert!(is_woolworths_url(
+            "https://www.woolworths.com.au/shop/productdetails/123456/milk"
+        ));
+        assert!(!is_woolworths_url(
+            "https://www.coles.com.au/product/milk-123"
+        ));
+        assert!(!is_woolworths_url("https://example.com"));
+    }
+
+    #[test]
+    fn test_parse_in_stock() {
+        let page_props = json!({
+            "product": {"Name": "Milk", "Price": 4.5, "IsAvailable": true, "IsInStock": true}
+        });
+
+        let result = parse_woolworths_data(&page_props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.raw_availability, Some("in-stock".to_string()));
+        assert_eq!(result.price.price_minor_units, Some(450));
+        assert_eq!(result.price.price_currency, Some("AUD".to_string()));
+    }
+
+    #[test]
+    fn test_parse_not_in_stock() {
+        let page_props = json!({
+            "product": {"Name": "Milk", "Price": 4.5, "IsAvailable": true, "IsInStock": false}
+        });
+
+        let result = parse_woolworths_data(&page_props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.raw_availability, Some("out-of-stock".to_string()));
+    }
+
+    #[test]
+    fn test_parse_unavailable() {
+        let page_props = json!({
+            "product": {"Name": "Milk", "IsAvailable": false}
+        });
+
+        let result = parse_woolworths_data(&page_props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::OutOfStock);
+        assert_eq!(result.raw_availability, Some("unavailable".to_string()));
+        assert!(result.price.price_minor_units.is_none());
+        assert!(result.price.price_currency.is_none());
+    }
+
+    #[test]
+    fn test_parse_product_details_path() {
+        let page_props = json!({
+            "productDetails": {
+                "Product": {"Name": "Bread", "InstorePrice": "3.80", "IsInStock": true}
+            }
+        });
+
+        let result = parse_woolworths_data(&page_props).unwrap();
+        assert_eq!(result.status, AvailabilityStatus::InStock);
+        assert_eq!(result.price.price_minor_units, Some(380));
+    }
+
+    #[test]
+    fn test_parse_no_product() {
+        let page_props = json!({"product": {"Name": "Milk", "Price": 4.5}});
+        assert!(parse_woolworths_data(&page_props).is_err());
+    }
+}
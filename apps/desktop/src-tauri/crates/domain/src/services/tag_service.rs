@@ -0,0 +1,246 @@
+//! Service layer for product tags.
+
+use std::collections::HashSet;
+
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::entities::prelude::TagModel;
+use crate::repositories::{ProductRepository, TagRepository};
+use product_stalker_core::AppError;
+
+/// Service layer for tag business logic
+pub struct TagService;
+
+impl TagService {
+    /// Get all tags
+    pub async fn get_all(conn: &DatabaseConnection) -> Result<Vec<TagModel>, AppError> {
+        TagRepository::find_all(conn).await
+    }
+
+    /// Get the tags attached to a product
+    pub async fn get_for_product(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+    ) -> Result<Vec<TagModel>, AppError> {
+        TagRepository::find_by_product_id(conn, product_id).await
+    }
+
+    /// Attach a tag to a product, creating the tag if it doesn't exist yet
+    pub async fn attach_to_product(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        name: &str,
+    ) -> Result<TagModel, AppError> {
+        Self::validate_name(name)?;
+        if ProductRepository::find_by_id(conn, product_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::NotFound(format!(
+                "Product not found: {}",
+                product_id
+            )));
+        }
+
+        let tag = TagRepository::find_or_create_by_name(conn, name).await?;
+        TagRepository::attach(conn, product_id, tag.id).await?;
+        Ok(tag)
+    }
+
+    /// Detach a tag from a product
+    pub async fn detach_from_product(
+        conn: &DatabaseConnection,
+        product_id: Uuid,
+        name: &str,
+    ) -> Result<(), AppError> {
+        let rows = match TagRepository::find_by_name(conn, name).await? {
+            Some(tag) => TagRepository::detach(conn, product_id, tag.id).await?,
+            None => 0,
+        };
+        if rows == 0 {
+            return Err(AppError::NotFound(format!(
+                "Tag '{}' is not attached to product {}",
+                TagRepository::normalize_name(name),
+                product_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get the IDs of all products carrying the named tag
+    pub async fn get_product_ids_by_tag(
+        conn: &DatabaseConnection,
+        name: &str,
+    ) -> Result<HashSet<Uuid>, AppError> {
+        let tag = TagRepository::find_by_name(conn, name)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Tag not found: {}",
+                    TagRepository::normalize_name(name)
+                ))
+            })?;
+        let ids = TagRepository::find_product_ids_by_tag_id(conn, tag.id).await?;
+        Ok(ids.into_iter().collect())
+    }
+
+    fn validate_name(name: &str) -> Result<(), AppError> {
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("Tag name cannot be empty".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(TagService::validate_name("kitchen").is_ok());
+        assert!(matches!(
+            TagService::validate_name("   "),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::repositories::ProductRetailerRepository;
+    use crate::services::{
+        AddRetailerParams, AvailabilityService, ProductRetailerService, ProductService,
+    };
+    use crate::test_utils::{create_test_product, setup_tag_db};
+
+    #[tokio::test]
+    async fn test_attach_to_product_dedups_tags() {
+        let conn = setup_tag_db().await;
+        let first = create_test_product(&conn, "https://a.com").await;
+        let second = create_test_product(&conn, "https://b.com").await;
+
+        let a = TagService::attach_to_product(&conn, first, "Kitchen")
+            .await
+            .unwrap();
+        let b = TagService::attach_to_product(&conn, second, "kitchen ")
+            .await
+            .unwrap();
+
+        assert_eq!(a.id, b.id);
+        assert_eq!(TagService::get_all(&conn).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_attach_to_missing_product_fails() {
+        let conn = setup_tag_db().await;
+
+        let result = TagService::attach_to_product(&conn, Uuid::new_v4(), "gpu").await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+        assert!(TagService::get_all(&conn).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_attach_empty_name_fails() {
+        let conn = setup_tag_db().await;
+        let product_id = create_test_product(&conn, "https://a.com").await;
+
+        let result = TagService::attach_to_product(&conn, product_id, "  ").await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_detach_from_product() {
+        let conn = setup_tag_db().await;
+        let product_id = create_test_product(&conn, "https://a.com").await;
+        TagService::attach_to_product(&conn, product_id, "gpu")
+            .await
+            .unwrap();
+
+        TagService::detach_from_product(&conn, product_id, "GPU")
+            .await
+            .unwrap();
+
+        assert!(TagService::get_for_product(&conn, product_id)
+            .await
+            .unwrap()
+            .is_empty());
+        let again = TagService::detach_from_product(&conn, product_id, "gpu").await;
+        assert!(matches!(again, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_product_ids_by_tag() {
+        let conn = setup_tag_db().await;
+        let tagged = create_test_product(&conn, "https://a.com").await;
+        let untagged = create_test_product(&conn, "https://b.com").await;
+        TagService::attach_to_product(&conn, tagged, "gpu")
+            .await
+            .unwrap();
+
+        let ids = TagService::get_product_ids_by_tag(&conn, "GPU")
+            .await
+            .unwrap();
+
+        assert!(ids.contains(&tagged));
+        assert!(!ids.contains(&untagged));
+    }
+
+    #[tokio::test]
+    async fn test_get_product_ids_by_unknown_tag_fails() {
+        let conn = setup_tag_db().await;
+
+        let result = TagService::get_product_ids_by_tag(&conn, "nope").await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_tag_filter_narrows_bulk_check_work_list() {
+        let conn = setup_tag_db().await;
+        let tagged = create_test_product(&conn, "https://a.com").await;
+        let untagged = create_test_product(&conn, "https://b.com").await;
+        let legacy_tagged = create_test_product(&conn, "https://c.com").await;
+        for (product_id, url) in [
+            (tagged, "https://a.com/item"),
+            (untagged, "https://b.com/item"),
+        ] {
+            ProductRetailerService::add_retailer(
+                &conn,
+                AddRetailerParams {
+                    product_id,
+                    url: url.to_string(),
+                    label: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+        TagService::attach_to_product(&conn, tagged, "gpu")
+            .await
+            .unwrap();
+        TagService::attach_to_product(&conn, legacy_tagged, "gpu")
+            .await
+            .unwrap();
+
+        let mut product_retailers = ProductRetailerRepository::find_all_with_product(&conn)
+            .await
+            .unwrap();
+        let mut legacy_products = ProductService::get_all_without_retailers(&conn)
+            .await
+            .unwrap();
+        let ids = TagService::get_product_ids_by_tag(&conn, "gpu")
+            .await
+            .unwrap();
+        AvailabilityService::retain_product_ids(&mut product_retailers, &mut legacy_products, &ids);
+
+        assert_eq!(product_retailers.len(), 1);
+        assert_eq!(product_retailers[0].0.product_id, tagged);
+        assert_eq!(legacy_products.len(), 1);
+        assert_eq!(legacy_products[0].id, legacy_tagged);
+    }
+}
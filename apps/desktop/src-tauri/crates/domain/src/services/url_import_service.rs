@@ -0,0 +1,357 @@
+//! Bulk import of product URLs from pasted text.
+//!
+//! Every http(s) URL in the text becomes a product with a single retailer link,
+//! named after the URL's path. Names are derived offline rather than by scraping
+//! each page, so pasting a long list doesn't fire a request per URL.
+
+use std::collections::HashSet;
+
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use url::Url;
+
+use crate::repositories::{ProductRepository, ProductRetailerRepository};
+use crate::services::{
+    AddRetailerParams, CreateProductParams, ProductRetailerService, ProductService,
+};
+use product_stalker_core::AppError;
+
+/// Characters that end a URL in free text
+const URL_TERMINATORS: &[char] = &['<', '>', '"', '\'', '`', '|', '^', '{', '}', '\\'];
+
+/// Punctuation trimmed from the end of a URL, as it usually belongs to the
+/// surrounding sentence (e.g. "see https://example.com/item.")
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '}', '*'];
+
+/// Outcome of a URL import
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportReport {
+    pub created: usize,
+    /// URLs already tracked, or repeated in the pasted text
+    pub skipped: usize,
+    /// URLs that couldn't be parsed or saved
+    pub failed: usize,
+}
+
+/// Service for importing products from a list of URLs
+pub struct UrlImportService;
+
+impl UrlImportService {
+    /// Create a product for every new http(s) URL found in `text`.
+    ///
+    /// URLs matching an existing product or retailer link (after normalization)
+    /// are skipped, as are repeats within the text.
+    pub async fn import_urls(
+        conn: &DatabaseConnection,
+        text: &str,
+    ) -> Result<ImportReport, AppError> {
+        let mut known: HashSet<String> = ProductRetailerRepository::find_all_urls(conn)
+            .await?
+            .into_iter()
+            .chain(
+                ProductRepository::find_all_including_archived(conn)
+                    .await?
+                    .into_iter()
+                    .filter_map(|product| product.url),
+            )
+            .map(|url| normalize_url(&url).unwrap_or(url))
+            .collect();
+
+        let mut report = ImportReport::default();
+        for candidate in extract_urls(text) {
+            let Some(url) = normalize_url(&candidate) else {
+                log::warn!("Skipping unparseable URL in import: {}", candidate);
+                report.failed += 1;
+                continue;
+            };
+            if !known.insert(url.clone()) {
+                report.skipped += 1;
+                continue;
+            }
+
+            match Self::create_product_for_url(conn, &url).await {
+                Ok(()) => report.created += 1,
+                Err(e) => {
+                    log::warn!("Failed to import {}: {}", url, e);
+                    report.failed += 1;
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    async fn create_product_for_url(conn: &DatabaseConnection, url: &str) -> Result<(), AppError> {
+        let product = ProductService::create(
+            conn,
+            CreateProductParams {
+                name: name_from_url(url),
+                description: None,
+                notes: None,
+                check_interval_minutes: None,
+                target_price_minor_units: None,
+                target_price_currency: None,
+            },
+        )
+        .await?;
+
+        let linked = ProductRetailerService::add_retailer(
+            conn,
+            AddRetailerParams {
+                product_id: product.id,
+                url: url.to_string(),
+                label: None,
+            },
+        )
+        .await;
+        if let Err(e) = linked {
+            // Don't leave a product behind that has nothing to check
+            ProductService::delete(conn, product.id).await?;
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+/// Find every `http://` or `https://` URL in free text, in order of appearance
+fn extract_urls(text: &str) -> Vec<String> {
+    let mut urls = Vec::new();
+    let mut rest = text;
+    while let Some(start) = find_scheme(rest) {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || URL_TERMINATORS.contains(&c))
+            .unwrap_or(candidate.len());
+        let url = candidate[..end].trim_end_matches(TRAILING_PUNCTUATION);
+        urls.push(url.to_string());
+        rest = &candidate[end.max(1)..];
+    }
+    urls
+}
+
+/// Byte offset of the next `http://` or `https://`, case-insensitively
+fn find_scheme(text: &str) -> Option<usize> {
+    let lower = text.to_ascii_lowercase();
+    ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| lower.find(scheme))
+        .min()
+}
+
+/// Parse a URL into its canonical form (lowercase scheme and host, default port
+/// dropped), or `None` if it isn't an http(s) URL with a host
+fn normalize_url(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    let is_http = matches!(parsed.scheme(), "http" | "https");
+    (is_http && parsed.host_str().is_some()).then(|| parsed.to_string())
+}
+
+/// Derive a readable product name from a URL's path.
+///
+/// Prefers the last slug-like segment (several words joined by `-` or `_`),
+/// skipping IDs such as Amazon ASINs or trailing numeric IDs, and falls back to
+/// the host when the path has nothing usable.
+fn name_from_url(url: &str) -> String {
+    let Ok(parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+    let host = parsed
+        .host_str()
+        .unwrap_or_default()
+        .trim_start_matches("www.")
+        .to_string();
+
+    let segments: Vec<String> = parsed
+        .path_segments()
+        .map(|segments| {
+            segments
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| {
+                    let segment = segment.rsplit_once('.').map_or(segment, |(stem, ext)| {
+                        if ext.chars().all(|c| c.is_ascii_alphabetic()) {
+                            stem
+                        } else {
+                            segment
+                        }
+                    });
+                    segment.replace("%20", " ")
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let words = |segment: &str| -> Vec<String> {
+        segment
+            .split(['-', '_', '+', ' '])
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let slug = segments
+        .iter()
+        .rev()
+        .map(|segment| words(segment))
+        .find(|words| words.len() >= 2 && words.iter().any(|w| w.chars().any(char::is_alphabetic)))
+        .or_else(|| {
+            segments
+                .iter()
+                .rev()
+                .find(|segment| segment.chars().all(|c| c.is_alphabetic()))
+                .map(|segment| words(segment))
+        });
+
+    let Some(mut words) = slug else {
+        return host;
+    };
+    // Drop a trailing product ID, e.g. "full-cream-milk-3l-8150288"
+    if words.len() > 1
+        && words
+            .last()
+            .is_some_and(|w| w.chars().all(|c| c.is_ascii_digit()))
+    {
+        words.pop();
+    }
+
+    words
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_product, setup_product_retailer_db};
+
+    const PASTED: &str = r#"
+        Wishlist for this week:
+        - https://www.walmart.com/ip/Great-Value-Whole-Milk-1-Gallon/5129928603
+        - Headphones (https://www.amazon.com/Sony-WH-1000XM5/dp/B09XS7JWHH), maybe later.
+        - not a link: ftp://files.example.com/catalog.pdf
+        - https://shop.example.com/products/linen-shirt?variant=42
+        - duplicate: HTTPS://WWW.WALMART.COM/ip/Great-Value-Whole-Milk-1-Gallon/5129928603
+        - broken: https://
+        - already tracked: https://tracked.example.com/item
+    "#;
+
+    #[test]
+    fn test_extract_urls_from_mixed_text() {
+        let urls = extract_urls(PASTED);
+        assert_eq!(
+            urls,
+            vec![
+                "https://www.walmart.com/ip/Great-Value-Whole-Milk-1-Gallon/5129928603",
+                "https://www.amazon.com/Sony-WH-1000XM5/dp/B09XS7JWHH",
+                "https://shop.example.com/products/linen-shirt?variant=42",
+                "HTTPS://WWW.WALMART.COM/ip/Great-Value-Whole-Milk-1-Gallon/5129928603",
+                "https://",
+                "https://tracked.example.com/item",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_urls_stops_at_markup() {
+        assert_eq!(
+            extract_urls(r#"<a href="https://example.com/a">x</a> 'https://example.com/b'"#),
+            vec!["https://example.com/a", "https://example.com/b"]
+        );
+        assert!(extract_urls("no links here").is_empty());
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url("HTTPS://WWW.Example.COM:443/Item").as_deref(),
+            Some("https://www.example.com/Item")
+        );
+        assert_eq!(normalize_url("https://"), None);
+        assert_eq!(normalize_url("mailto:me@example.com"), None);
+    }
+
+    #[test]
+    fn test_name_from_url() {
+        assert_eq!(
+            name_from_url("https://www.walmart.com/ip/Great-Value-Whole-Milk-1-Gallon/5129928603"),
+            "Great Value Whole Milk 1 Gallon"
+        );
+        assert_eq!(
+            name_from_url("https://www.amazon.com/Sony-WH-1000XM5/dp/B09XS7JWHH"),
+            "Sony WH 1000XM5"
+        );
+        assert_eq!(
+            name_from_url("https://www.coles.com.au/product/coles-full-cream-milk-3l-8150288"),
+            "Coles Full Cream Milk 3l"
+        );
+        assert_eq!(
+            name_from_url("https://shop.example.com/products/linen_shirt.html"),
+            "Linen Shirt"
+        );
+        assert_eq!(
+            name_from_url("https://shop.example.com/products/kettle"),
+            "Kettle"
+        );
+        assert_eq!(name_from_url("https://www.example.com/"), "example.com");
+        assert_eq!(name_from_url("https://www.example.com/p/123456"), "P");
+    }
+
+    #[tokio::test]
+    async fn test_import_creates_skips_and_fails() {
+        let conn = setup_product_retailer_db().await;
+        create_test_product(&conn, "https://tracked.example.com/item").await;
+
+        let report = UrlImportService::import_urls(&conn, PASTED).await.unwrap();
+
+        assert_eq!(
+            report,
+            ImportReport {
+                created: 3,
+                skipped: 2,
+                failed: 1,
+            }
+        );
+        let products = ProductRepository::find_all(&conn).await.unwrap();
+        let names: Vec<&str> = products.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Test Product",
+                "Great Value Whole Milk 1 Gallon",
+                "Sony WH 1000XM5",
+                "Linen Shirt",
+            ]
+        );
+        let links = ProductRetailerRepository::find_by_product_id(&conn, products[3].id)
+            .await
+            .unwrap();
+        assert_eq!(
+            links[0].url,
+            "https://shop.example.com/products/linen-shirt?variant=42"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_skips_existing_retailer_links() {
+        let conn = setup_product_retailer_db().await;
+        let text = "https://example.com/products/blue-mug";
+
+        let first = UrlImportService::import_urls(&conn, text).await.unwrap();
+        let second = UrlImportService::import_urls(&conn, text).await.unwrap();
+
+        assert_eq!(first.created, 1);
+        assert_eq!(
+            second,
+            ImportReport {
+                created: 0,
+                skipped: 1,
+                failed: 0,
+            }
+        );
+    }
+}
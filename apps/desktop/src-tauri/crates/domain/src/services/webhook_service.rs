@@ -0,0 +1,217 @@
+//! Webhook delivery for notifications (Discord, Slack, or any JSON endpoint).
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::entities::availability_check::AvailabilityStatus;
+use crate::entities::prelude::AvailabilityCheckModel;
+use product_stalker_core::services::notification_helpers::NotificationData;
+use product_stalker_core::AppError;
+
+/// Request timeout for webhook deliveries
+const TIMEOUT_SECS: u64 = 10;
+
+/// JSON body POSTed to the notification webhook
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WebhookPayload {
+    pub title: String,
+    pub body: String,
+    /// Product that triggered the notification (None for bulk summaries)
+    pub product_id: Option<String>,
+    pub status: Option<AvailabilityStatus>,
+    /// Price in minor units of the check's currency
+    pub price: Option<i64>,
+}
+
+impl WebhookPayload {
+    /// Payload for a notification that isn't tied to a single product (e.g. bulk summaries)
+    pub fn from_notification(notification: &NotificationData) -> Self {
+        Self {
+            title: notification.title.clone(),
+            body: notification.body.clone(),
+            product_id: None,
+            status: None,
+            price: None,
+        }
+    }
+
+    /// Payload for a notification raised by a single availability check
+    pub fn for_check(notification: &NotificationData, check: &AvailabilityCheckModel) -> Self {
+        Self {
+            product_id: Some(check.product_id.to_string()),
+            status: check.status.parse().ok(),
+            price: check.price_minor_units,
+            ..Self::from_notification(notification)
+        }
+    }
+}
+
+/// Service for pushing notifications to a user-configured webhook (Discord, Slack, ...)
+pub struct WebhookService;
+
+impl WebhookService {
+    /// POST the payload to the webhook URL.
+    ///
+    /// Returns `Ok(false)` without sending anything when the URL is empty.
+    pub async fn send(url: &str, payload: &WebhookPayload) -> Result<bool, AppError> {
+        let url = url.trim();
+        if url.is_empty() {
+            return Ok(false);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(TIMEOUT_SECS))
+            .build()
+            .map_err(|e| AppError::External(e.to_string()))?;
+
+        client
+            .post(url)
+            .json(payload)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            // The URL often embeds a secret token, so keep it out of the error
+            .map_err(|e| {
+                AppError::External(format!("Webhook request failed: {}", e.without_url()))
+            })?;
+
+        Ok(true)
+    }
+
+    /// Send the payload, logging failures instead of returning them.
+    ///
+    /// Webhooks are best-effort: a misconfigured or unreachable endpoint must not
+    /// fail the availability check that triggered the notification.
+    pub async fn notify(url: &str, payload: &WebhookPayload) {
+        match Self::send(url, payload).await {
+            Ok(true) => log::info!("Sent webhook notification: {}", payload.title),
+            Ok(false) => {}
+            Err(e) => log::warn!("Failed to send webhook notification: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockResponse, MockServer};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    /// Start a server that answers every request with `status`
+    async fn mock_webhook_server(status: u16) -> MockServer {
+        MockServer::serving(MockResponse::new(status, Vec::new())).await
+    }
+
+    /// The JSON body of the one request `server` received
+    fn received_json(server: &MockServer) -> serde_json::Value {
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        serde_json::from_slice(&requests[0].body).unwrap()
+    }
+
+    fn sample_check(product_id: Uuid) -> AvailabilityCheckModel {
+        AvailabilityCheckModel {
+            id: Uuid::new_v4(),
+            product_id,
+            product_retailer_id: None,
+            status: "in_stock".to_string(),
+            raw_availability: None,
+            error_message: None,
+            error_kind: None,
+            checked_at: Utc::now(),
+            price_minor_units: Some(4999),
+            price_currency: Some("AUD".to_string()),
+            raw_price: None,
+            normalized_price_minor_units: None,
+            normalized_currency: None,
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            fetch_duration_ms: None,
+            content_bytes: None,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_payload_for_check() {
+        let product_id = Uuid::new_v4();
+        let notification = NotificationData::new("Back in Stock!", "Widget is now in stock!");
+
+        let payload = WebhookPayload::for_check(&notification, &sample_check(product_id));
+        assert_eq!(payload.title, "Back in Stock!");
+        assert_eq!(payload.product_id, Some(product_id.to_string()));
+        assert_eq!(payload.status, Some(AvailabilityStatus::InStock));
+        assert_eq!(payload.price, Some(4999));
+    }
+
+    #[tokio::test]
+    async fn test_send_posts_payload() {
+        let server = mock_webhook_server(200).await;
+        let url = server.url("/webhook");
+        let product_id = Uuid::new_v4();
+        let notification = NotificationData::new("Back in Stock!", "Widget is now in stock!");
+        let payload = WebhookPayload::for_check(&notification, &sample_check(product_id));
+
+        let sent = WebhookService::send(&url, &payload).await.unwrap();
+        assert!(sent);
+
+        let body = received_json(&server);
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "title": "Back in Stock!",
+                "body": "Widget is now in stock!",
+                "product_id": product_id.to_string(),
+                "status": "in_stock",
+                "price": 4999
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_bulk_payload_has_null_product_fields() {
+        let server = mock_webhook_server(204).await;
+        let url = server.url("/webhook");
+        let payload =
+            WebhookPayload::from_notification(&NotificationData::new("2 Products Back", "A, B"));
+
+        assert!(WebhookService::send(&url, &payload).await.unwrap());
+
+        let body = received_json(&server);
+        assert_eq!(body["title"], "2 Products Back");
+        assert!(body["product_id"].is_null());
+        assert!(body["status"].is_null());
+        assert!(body["price"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_send_empty_url_is_noop() {
+        let payload = WebhookPayload::from_notification(&NotificationData::new("Title", "Body"));
+
+        assert!(!WebhookService::send("", &payload).await.unwrap());
+        assert!(!WebhookService::send("   ", &payload).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_send_error_status_fails() {
+        let server = mock_webhook_server(500).await;
+        let url = server.url("/webhook/secret-token");
+        let payload = WebhookPayload::from_notification(&NotificationData::new("Title", "Body"));
+
+        let result = WebhookService::send(&url, &payload).await;
+        assert!(
+            matches!(result, Err(AppError::External(ref msg)) if msg.contains("500") && !msg.contains("secret-token"))
+        );
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_notify_swallows_errors() {
+        let payload = WebhookPayload::from_notification(&NotificationData::new("Title", "Body"));
+
+        // Nothing is listening on port 9; must not panic or return an error
+        WebhookService::notify("http://127.0.0.1:9/webhook", &payload).await;
+    }
+}
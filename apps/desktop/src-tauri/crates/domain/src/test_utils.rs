@@ -1,14 +1,24 @@
-//! Shared test utilities for database setup and test data creation.
+//! Shared test utilities for database setup, test data creation and a mock
+//! HTTP server.
 //!
 //! This module is only compiled in test mode.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
 use sea_orm::{ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, Schema};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
 use uuid::Uuid;
 
 use crate::entities::availability_check::Entity as AvailabilityCheckEntity;
+use crate::entities::bulk_check_run::Entity as BulkCheckRunEntity;
 use crate::entities::product::Entity as ProductEntity;
 use crate::entities::product_retailer::Entity as ProductRetailerEntity;
+use crate::entities::product_tag::Entity as ProductTagEntity;
 use crate::entities::retailer::Entity as RetailerEntity;
+use crate::entities::tag::Entity as TagEntity;
 use crate::repositories::{CreateProductRepoParams, ProductRepository};
 
 /// Creates an in-memory SQLite test database with products table only
@@ -55,6 +65,17 @@ pub async fn setup_availability_db() -> DatabaseConnection {
     conn
 }
 
+/// Creates an in-memory SQLite test database with bulk_check_runs table only
+pub async fn setup_bulk_check_runs_db() -> DatabaseConnection {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+    let stmt = schema.create_table_from_entity(BulkCheckRunEntity);
+    conn.execute(conn.get_database_backend().build(&stmt))
+        .await
+        .unwrap();
+    conn
+}
+
 /// Creates an in-memory SQLite test database with retailers table only
 pub async fn setup_retailer_db() -> DatabaseConnection {
     let conn = Database::connect("sqlite::memory:").await.unwrap();
@@ -93,20 +114,63 @@ pub async fn setup_product_retailer_db() -> DatabaseConnection {
 }
 
 /// Creates an in-memory SQLite test database with products, retailers,
-/// product_retailers, availability_checks, AND exchange_rates tables.
+/// product_retailers, tags, and product_tags tables
+pub async fn setup_tag_db() -> DatabaseConnection {
+    let conn = setup_product_retailer_db().await;
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+
+    let stmt = schema.create_table_from_entity(TagEntity);
+    conn.execute(conn.get_database_backend().build(&stmt))
+        .await
+        .unwrap();
+
+    let stmt = schema.create_table_from_entity(ProductTagEntity);
+    conn.execute(conn.get_database_backend().build(&stmt))
+        .await
+        .unwrap();
+
+    conn
+}
+
+/// Creates an in-memory SQLite test database with products, retailers,
+/// product_retailers, availability_checks, tags, and product_tags tables
+pub async fn setup_availability_db_with_tags() -> DatabaseConnection {
+    let conn = setup_availability_db().await;
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+
+    let stmt = schema.create_table_from_entity(TagEntity);
+    conn.execute(conn.get_database_backend().build(&stmt))
+        .await
+        .unwrap();
+
+    let stmt = schema.create_table_from_entity(ProductTagEntity);
+    conn.execute(conn.get_database_backend().build(&stmt))
+        .await
+        .unwrap();
+
+    conn
+}
+
+/// Creates an in-memory SQLite test database with products, retailers,
+/// product_retailers, availability_checks, AND exchange_rates (+ history) tables.
 ///
 /// Used for tests that need both availability data and exchange rate lookups
 /// (e.g., re-normalization tests for daily price comparison).
 pub async fn setup_availability_db_with_exchange_rates() -> DatabaseConnection {
     use product_stalker_core::entities::exchange_rate::Entity as ExchangeRateEntity;
+    use product_stalker_core::entities::exchange_rate_history::Entity as ExchangeRateHistoryEntity;
 
     let conn = setup_availability_db().await;
     let schema = Schema::new(DatabaseBackend::Sqlite);
 
-    let stmt = schema.create_table_from_entity(ExchangeRateEntity);
-    conn.execute(conn.get_database_backend().build(&stmt))
-        .await
-        .unwrap();
+    for stmt in [
+        schema.create_table_from_entity(ExchangeRateEntity),
+        schema.create_table_from_entity(ExchangeRateHistoryEntity),
+    ] {
+        conn.execute(conn.get_database_backend().build(&stmt))
+            .await
+            .unwrap();
+    }
 
     // Create the unique index needed for upsert operations
     conn.execute_unprepared(
@@ -118,6 +182,25 @@ pub async fn setup_availability_db_with_exchange_rates() -> DatabaseConnection {
     conn
 }
 
+/// Creates an in-memory SQLite test database with the app_settings and
+/// verified_sessions tables a page fetch reads
+pub async fn setup_fetch_db() -> DatabaseConnection {
+    use product_stalker_core::entities::app_setting::Entity as AppSettingEntity;
+    use product_stalker_core::entities::verified_session::Entity as VerifiedSessionEntity;
+
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let schema = Schema::new(DatabaseBackend::Sqlite);
+    for stmt in [
+        schema.create_table_from_entity(AppSettingEntity),
+        schema.create_table_from_entity(VerifiedSessionEntity),
+    ] {
+        conn.execute(conn.get_database_backend().build(&stmt))
+            .await
+            .unwrap();
+    }
+    conn
+}
+
 /// Creates a test product with the given URL
 pub async fn create_test_product(conn: &DatabaseConnection, url: &str) -> Uuid {
     let id = Uuid::new_v4();
@@ -129,6 +212,9 @@ pub async fn create_test_product(conn: &DatabaseConnection, url: &str) -> Uuid {
             url: Some(url.to_string()),
             description: None,
             notes: None,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
         },
     )
     .await
@@ -140,3 +226,255 @@ pub async fn create_test_product(conn: &DatabaseConnection, url: &str) -> Uuid {
 pub async fn create_test_product_default(conn: &DatabaseConnection) -> Uuid {
     create_test_product(conn, "https://example.com/product").await
 }
+
+/// A request received by a [`MockServer`]
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub method: String,
+    /// Path and query string, e.g. `/products/widget?variant=1`
+    pub target: String,
+    /// Header names are lowercased; values are kept as sent
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    /// The request path without its query string
+    pub fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or_default()
+    }
+
+    /// The first value of the header named `name` (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.header_values(name).into_iter().next()
+    }
+
+    /// Values of every header named `name` (case-insensitive)
+    pub fn header_values(&self, name: &str) -> Vec<&str> {
+        let name = name.to_lowercase();
+        self.headers
+            .iter()
+            .filter(|(header, _)| *header == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+}
+
+/// A response for a [`MockServer`] to send. `Content-Length` is added automatically.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    delay: Duration,
+}
+
+impl MockResponse {
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// A 200 response with `body` and no `Content-Type`
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self::new(200, body)
+    }
+
+    /// A 200 `text/html` response
+    pub fn html(body: impl Into<Vec<u8>>) -> Self {
+        Self::ok(body).header("content-type", "text/html")
+    }
+
+    /// An `application/json` response with `status`
+    pub fn json(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self::new(status, body).header("content-type", "application/json")
+    }
+
+    /// A redirect with `status` (301, 302, ...) to `location`
+    pub fn redirect(status: u16, location: impl Into<String>) -> Self {
+        Self::new(status, Vec::new()).header("location", location)
+    }
+
+    /// Add a response header
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Wait `delay` before answering, e.g. to make a request time out
+    pub fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = reqwest::StatusCode::from_u16(self.status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or("Unknown");
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason);
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!("content-length: {}\r\n\r\n", self.body.len()));
+
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+/// Local HTTP/1.1 server answering each request with a handler's response and
+/// recording the requests it received.
+///
+/// Connections are kept alive, so pooled clients reuse them. The server stops
+/// when dropped.
+pub struct MockServer {
+    base_url: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+    connections: Arc<AtomicUsize>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Start a server on a free local port that answers with `respond`
+    pub async fn start<F>(respond: F) -> Self
+    where
+        F: Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let connections = Arc::new(AtomicUsize::new(0));
+        let respond = Arc::new(respond);
+
+        let task = tokio::spawn({
+            let requests = Arc::clone(&requests);
+            let connections = Arc::clone(&connections);
+            async move {
+                while let Ok((socket, _)) = listener.accept().await {
+                    connections.fetch_add(1, Ordering::SeqCst);
+                    tokio::spawn(serve_connection(
+                        socket,
+                        Arc::clone(&respond),
+                        Arc::clone(&requests),
+                    ));
+                }
+            }
+        });
+
+        Self {
+            base_url,
+            requests,
+            connections,
+            task,
+        }
+    }
+
+    /// Start a server that answers every request with `response`
+    pub async fn serving(response: MockResponse) -> Self {
+        Self::start(move |_| response.clone()).await
+    }
+
+    /// Scheme, host and port, e.g. `http://127.0.0.1:4321`
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Absolute URL for `target` (a path with optional query)
+    pub fn url(&self, target: &str) -> String {
+        format!("{}{}", self.base_url, target)
+    }
+
+    /// Requests received so far, in order
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Paths (without query strings) requested so far, in order
+    pub fn paths(&self) -> Vec<String> {
+        self.requests()
+            .iter()
+            .map(|request| request.path().to_string())
+            .collect()
+    }
+
+    /// Number of connections accepted so far
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Answer requests on one connection until the client closes it
+async fn serve_connection<F>(
+    mut socket: TcpStream,
+    respond: Arc<F>,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+) where
+    F: Fn(&MockRequest) -> MockResponse,
+{
+    let mut buffer = Vec::new();
+    while let Some(request) = read_request(&mut socket, &mut buffer).await {
+        let response = respond(&request);
+        requests.lock().unwrap().push(request);
+        tokio::time::sleep(response.delay).await;
+        if socket.write_all(&response.to_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Read the next request from `socket`, keeping any bytes past it in `buffer`.
+/// Returns `None` once the connection is closed.
+async fn read_request(socket: &mut TcpStream, buffer: &mut Vec<u8>) -> Option<MockRequest> {
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(at) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break at;
+        }
+        let n = socket.read(&mut chunk).await.ok().filter(|n| *n > 0)?;
+        buffer.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_lowercase(), value.trim().to_string()))
+        })
+        .collect();
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let body_start = head_end + 4;
+    while buffer.len() < body_start + content_length {
+        let n = socket.read(&mut chunk).await.ok().filter(|n| *n > 0)?;
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+    let body = buffer[body_start..body_start + content_length].to_vec();
+    buffer.drain(..body_start + content_length);
+
+    Some(MockRequest {
+        method,
+        target,
+        headers,
+        body,
+    })
+}
@@ -1,11 +1,18 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use sea_orm::DatabaseConnection;
 use tauri::AppHandle;
 
-use crate::domain::services::DomainSettingService;
-use crate::tauri_services::{send_desktop_notification, TauriAvailabilityService};
+use crate::core::services::{SettingService, Settings};
+use crate::domain::services::{
+    AvailabilityService, DomainSettingService, DomainSettings, NotificationData,
+    NotificationDigest, NotificationService, ProductService, WebhookPayload,
+};
+use crate::tauri_services::{
+    send_desktop_notification, send_webhook_notification, TauriAvailabilityService,
+};
 
 /// Delay in seconds before retrying after a settings fetch error.
 ///
@@ -13,12 +20,19 @@ use crate::tauri_services::{send_desktop_notification, TauriAvailabilityService}
 /// it waits this long before trying again to avoid tight error loops.
 const ERROR_RETRY_DELAY_SECS: u64 = 60;
 
-/// Polling interval in seconds when background checking is disabled.
+/// Polling interval in seconds when background checking is disabled, or outside
+/// its active hours and days.
 ///
 /// The checker periodically re-checks settings even when disabled,
-/// so it can start checking when the user enables the feature.
+/// so it can start checking when the user enables the feature or the window opens.
 const DISABLED_POLL_INTERVAL_SECS: u64 = 60;
 
+/// Minimum interval in seconds between check history prunes.
+///
+/// Pruning only runs when a data retention window is set, and a day of
+/// extra history is not worth re-scanning every product on each tick.
+const PRUNE_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
 /// State for managing the background checker task.
 ///
 /// Stores the `JoinHandle` so the task can be cancelled if needed (e.g., on app shutdown).
@@ -28,8 +42,14 @@ pub struct BackgroundCheckerState {
 
 /// Spawns the background availability checker task.
 ///
-/// The task periodically checks all products for availability based on settings.
-/// It sends desktop notifications when products come back in stock.
+/// The task periodically checks products for availability based on settings.
+/// Products with their own `check_interval_minutes` are checked on that schedule;
+/// the rest use the global interval. Paused products are skipped, and ticks outside
+/// the configured active hours and days (in the display timezone) are skipped
+/// entirely. It sends desktop (and webhook, if configured) notifications when
+/// products come back in stock, or one summary per digest interval in digest
+/// notification mode.
+/// When a data retention window is set, old check history is pruned once a day.
 pub fn spawn_background_checker(
     app: AppHandle,
     conn: Arc<DatabaseConnection>,
@@ -41,18 +61,38 @@ pub fn spawn_background_checker(
 
 async fn background_checker_loop(app: AppHandle, conn: Arc<DatabaseConnection>) {
     log::info!("Background availability checker started");
+    let mut last_pruned: Option<Instant> = None;
+    let mut digest = NotificationDigest::new(Utc::now());
 
     loop {
-        // Get current domain settings
-        let domain_settings = match DomainSettingService::get(&conn).await {
-            Ok(s) => s,
-            Err(e) => {
+        // Get current settings
+        let (settings, domain_settings) = match (
+            SettingService::get(&conn).await,
+            DomainSettingService::get(&conn).await,
+        ) {
+            (Ok(settings), Ok(domain_settings)) => (settings, domain_settings),
+            (Err(e), _) | (_, Err(e)) => {
                 log::error!("Failed to get settings in background checker: {}", e);
                 tokio::time::sleep(Duration::from_secs(ERROR_RETRY_DELAY_SECS)).await;
                 continue;
             }
         };
 
+        // Prune old check history, independent of background checking
+        if is_prune_due(domain_settings.data_retention_days, last_pruned) {
+            match AvailabilityService::prune_check_history(
+                &conn,
+                domain_settings.data_retention_days,
+                chrono::Utc::now(),
+            )
+            .await
+            {
+                Ok(deleted) => log::info!("Pruned {} old availability checks", deleted),
+                Err(e) => log::error!("Failed to prune check history: {}", e),
+            }
+            last_pruned = Some(Instant::now());
+        }
+
         // Check if background checking is enabled
         if !domain_settings.background_check_enabled {
             log::debug!(
@@ -63,9 +103,39 @@ async fn background_checker_loop(app: AppHandle, conn: Arc<DatabaseConnection>)
             continue;
         }
 
-        // Perform the check (includes notification logic)
+        // Stay idle outside the active hours and days (manual checks are unaffected)
+        if !is_check_window_open(&settings, &domain_settings, Utc::now()) {
+            log::debug!(
+                "Outside background check hours, sleeping for {} seconds",
+                DISABLED_POLL_INTERVAL_SECS
+            );
+            tokio::time::sleep(Duration::from_secs(DISABLED_POLL_INTERVAL_SECS)).await;
+            continue;
+        }
+
+        // Wake up often enough for the shortest per-product interval
+        let global_interval = domain_settings.background_check_interval_minutes;
+        let tick_minutes = match ProductService::get_all(&conn, false).await {
+            Ok(products) => {
+                AvailabilityService::background_tick_minutes(&products, global_interval)
+            }
+            Err(e) => {
+                log::warn!("Failed to load product check intervals: {}", e);
+                global_interval
+            }
+        };
+
+        // Perform the check on due products (includes notification logic)
         log::info!("Starting background availability check");
-        match TauriAvailabilityService::check_all_products_with_notification(&conn, &app).await {
+        let due_by = chrono::Utc::now() + chrono::Duration::minutes(tick_minutes as i64);
+        match TauriAvailabilityService::check_due_products_with_notification(
+            &conn,
+            &app,
+            global_interval,
+            due_by,
+        )
+        .await
+        {
             Ok(result) => {
                 log::info!(
                     "Background check complete: {}/{} successful, {} back in stock, {} price drops",
@@ -75,8 +145,10 @@ async fn background_checker_loop(app: AppHandle, conn: Arc<DatabaseConnection>)
                     result.summary.price_drop_count
                 );
 
-                if let Some(notification) = result.notification {
-                    send_desktop_notification(&app, &notification);
+                if settings.is_digest_mode() {
+                    digest.add_summary(&result.summary);
+                } else if let Some(notification) = result.notification {
+                    send_notification(&app, &conn, &notification).await;
                 }
             }
             Err(e) => {
@@ -84,18 +156,84 @@ async fn background_checker_loop(app: AppHandle, conn: Arc<DatabaseConnection>)
             }
         }
 
-        // Sleep for the configured interval
-        let interval_secs = (domain_settings.background_check_interval_minutes as u64) * 60;
-        log::debug!(
-            "Background checker sleeping for {} minutes",
-            domain_settings.background_check_interval_minutes
-        );
+        // Send the digest once its interval has passed, or start over outside digest mode
+        let now = Utc::now();
+        if should_send_digest(&settings, &digest, now) {
+            if let Some(notification) = NotificationService::build_digest_notification(
+                settings.enable_notifications,
+                &settings.digest_interval,
+                &digest,
+            ) {
+                send_notification(&app, &conn, &notification).await;
+            }
+            digest = NotificationDigest::new(now);
+        } else if !settings.is_digest_mode() {
+            digest = NotificationDigest::new(now);
+        }
+
+        // Sleep until the next product could be due
+        let interval_secs = (tick_minutes as u64) * 60;
+        log::debug!("Background checker sleeping for {} minutes", tick_minutes);
         tokio::time::sleep(Duration::from_secs(interval_secs)).await;
     }
 }
 
+/// Send a notification to the desktop and the webhook, if configured
+async fn send_notification(
+    app: &AppHandle,
+    conn: &DatabaseConnection,
+    notification: &NotificationData,
+) {
+    send_desktop_notification(app, notification);
+    let payload = WebhookPayload::from_notification(notification);
+    send_webhook_notification(conn, &payload).await;
+}
+
+/// Whether the digest is due on this tick
+///
+/// A due digest is held back during quiet hours and sent on the first tick after.
+fn should_send_digest(
+    settings: &Settings,
+    digest: &NotificationDigest,
+    now: DateTime<Utc>,
+) -> bool {
+    settings.is_digest_mode()
+        && digest.is_due(now, settings.digest_period())
+        && !settings.is_quiet_time(now)
+}
+
+/// Whether background checks may run at `now`, given the active hours and days
+/// interpreted in the display timezone
+fn is_check_window_open(
+    settings: &Settings,
+    domain_settings: &DomainSettings,
+    now: DateTime<Utc>,
+) -> bool {
+    domain_settings
+        .background_check_window()
+        .is_open_at(now, &settings.display_timezone)
+}
+
+/// Whether check history should be pruned on this tick
+fn is_prune_due(retention_days: i32, last_pruned: Option<Instant>) -> bool {
+    if retention_days <= 0 {
+        return false;
+    }
+    match last_pruned {
+        Some(at) => at.elapsed() >= Duration::from_secs(PRUNE_INTERVAL_SECS),
+        None => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Instant;
+
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::services::Settings;
+    use crate::domain::services::{DomainSettings, NotificationDigest};
+
     #[test]
     fn test_error_retry_delay_is_reasonable() {
         assert!(super::ERROR_RETRY_DELAY_SECS > 0);
@@ -107,4 +245,101 @@ mod tests {
         assert!(super::DISABLED_POLL_INTERVAL_SECS > 0);
         assert!(super::DISABLED_POLL_INTERVAL_SECS <= 300);
     }
+
+    #[test]
+    fn test_is_prune_due() {
+        // Retention disabled: never prune
+        assert!(!super::is_prune_due(0, None));
+        // First tick with retention set prunes right away
+        assert!(super::is_prune_due(30, None));
+        // Pruned just now: wait for the next interval
+        assert!(!super::is_prune_due(30, Some(Instant::now())));
+    }
+
+    #[test]
+    fn test_is_check_window_open() {
+        // Thursday 2026-01-15, 20:00 UTC
+        let thursday_evening = Utc.with_ymd_and_hms(2026, 1, 15, 20, 0, 0).unwrap();
+        let business_hours = DomainSettings {
+            background_check_active_hours_start: "09:00".to_string(),
+            background_check_active_hours_end: "18:00".to_string(),
+            background_check_days: "mon,tue,wed,thu,fri".to_string(),
+            ..DomainSettings::default()
+        };
+        let london = Settings {
+            display_timezone: "Europe/London".to_string(),
+            ..Settings::default()
+        };
+        let tokyo = Settings {
+            display_timezone: "Asia/Tokyo".to_string(),
+            ..Settings::default()
+        };
+
+        // No window configured: always open
+        assert!(super::is_check_window_open(
+            &london,
+            &DomainSettings::default(),
+            thursday_evening
+        ));
+        // 20:00 in London is after hours
+        assert!(!super::is_check_window_open(
+            &london,
+            &business_hours,
+            thursday_evening
+        ));
+        // ...and it's Friday 05:00 in Tokyo, still before hours
+        assert!(!super::is_check_window_open(
+            &tokyo,
+            &business_hours,
+            thursday_evening
+        ));
+        // Friday 10:00 in Tokyo is inside the window
+        assert!(super::is_check_window_open(
+            &tokyo,
+            &business_hours,
+            thursday_evening + chrono::Duration::hours(5)
+        ));
+    }
+
+    #[test]
+    fn test_should_send_digest() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap();
+        let digest = NotificationDigest::new(start);
+        let next_day = start + chrono::Duration::days(1);
+        let digest_settings = Settings {
+            notification_mode: "digest".to_string(),
+            ..Settings::default()
+        };
+
+        // Per-event mode never sends a digest
+        assert!(!super::should_send_digest(
+            &Settings::default(),
+            &digest,
+            next_day
+        ));
+        // Digest mode waits for the interval to pass
+        assert!(!super::should_send_digest(
+            &digest_settings,
+            &digest,
+            start + chrono::Duration::hours(12)
+        ));
+        assert!(super::should_send_digest(
+            &digest_settings,
+            &digest,
+            next_day
+        ));
+
+        // Held back during quiet hours
+        let quiet_settings = Settings {
+            quiet_hours_start: "11:00".to_string(),
+            quiet_hours_end: "13:00".to_string(),
+            display_timezone: "Europe/London".to_string(),
+            ..digest_settings
+        };
+        assert!(!super::should_send_digest(
+            &quiet_settings,
+            &digest,
+            next_day
+        ));
+    }
 }
@@ -1,14 +1,26 @@
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use tauri::State;
 
+use super::products::ProductResponse;
 use crate::core::services::SettingService;
 use crate::db::DbState;
-use crate::domain::entities::prelude::AvailabilityCheckModel;
+use crate::domain::entities::prelude::{AvailabilityCheckModel, BulkCheckRunModel};
+use crate::domain::repositories::{
+    Bucket, PriceDrop, PriceExtremes, PriceTrend, RepairReport, StatusTransition,
+};
 use crate::domain::services::currency;
-use crate::domain::services::{AvailabilityService, BulkCheckSummary, DailyPriceComparison};
+use crate::domain::services::scraper::ScrapeDiagnostics;
+use crate::domain::services::{
+    AvailabilityService, BulkCheckSummary, DailyPriceComparison, DomainHealth,
+    DomainSettingService, DomainSettings, PortfolioSummary, ProductComparison, RestockStats,
+    ScraperService, WebhookPayload,
+};
 use crate::tauri_error::CommandError;
-use crate::tauri_services::{send_desktop_notification, TauriAvailabilityService};
-use crate::utils::parse_uuid;
+use crate::tauri_services::{
+    send_desktop_notification, send_webhook_notification, TauriAvailabilityService,
+};
+use crate::utils::{parse_timestamp, parse_uuid};
 
 /// Response DTO for availability checks
 #[derive(Debug, Serialize)]
@@ -19,6 +31,10 @@ pub struct AvailabilityCheckResponse {
     pub status: String,
     pub raw_availability: Option<String>,
     pub error_message: Option<String>,
+    /// Category of the failure (bot_protection, http_status, network, scraping, validation, internal)
+    pub error_kind: Option<String>,
+    /// How the status was found (high = structured data, low = inferred from page heuristics)
+    pub confidence: Option<String>,
     pub checked_at: String,
     pub price_minor_units: Option<i64>,
     pub price_currency: Option<String>,
@@ -29,7 +45,8 @@ pub struct AvailabilityCheckResponse {
     pub today_average_price_minor_units: Option<i64>,
     /// Yesterday's average price in minor units for daily comparison
     pub yesterday_average_price_minor_units: Option<i64>,
-    /// True if today's average price is lower than yesterday's average
+    /// True if today's average price dropped below yesterday's average by more
+    /// than the price drop threshold
     pub is_price_drop: bool,
     /// Lowest current price across all retailers (minor units)
     pub lowest_price_minor_units: Option<i64>,
@@ -37,6 +54,15 @@ pub struct AvailabilityCheckResponse {
     pub lowest_price_currency: Option<String>,
     /// Currency exponent for the lowest price
     pub lowest_currency_exponent: Option<u32>,
+    /// Lowest price converted to the user's preferred currency (minor units)
+    pub lowest_normalized_price_minor_units: Option<i64>,
+    /// Currency code of the converted lowest price
+    pub lowest_normalized_currency: Option<String>,
+    /// Currency exponent for the converted lowest price
+    pub lowest_normalized_currency_exponent: Option<u32>,
+    /// True if retailers were compared in their native currencies because an
+    /// exchange rate was missing
+    pub lowest_price_normalization_skipped: bool,
     /// Price normalized to the user's preferred currency (minor units)
     pub normalized_price_minor_units: Option<i64>,
     /// Currency code of the normalized price
@@ -50,11 +76,14 @@ impl AvailabilityCheckResponse {
     pub fn from_model_with_daily_comparison(
         model: AvailabilityCheckModel,
         daily_comparison: DailyPriceComparison,
+        price_drop_threshold_percent: i32,
     ) -> Self {
-        let is_price_drop = AvailabilityService::is_price_drop(
-            daily_comparison.yesterday_average_minor_units,
-            daily_comparison.today_average_minor_units,
-        );
+        let is_price_drop = !model.is_offer_expired()
+            && AvailabilityService::is_price_drop(
+                daily_comparison.yesterday_average_minor_units,
+                daily_comparison.today_average_minor_units,
+                price_drop_threshold_percent,
+            );
         let currency_exponent = model
             .price_currency
             .as_deref()
@@ -70,6 +99,8 @@ impl AvailabilityCheckResponse {
             status: model.status,
             raw_availability: model.raw_availability,
             error_message: model.error_message,
+            error_kind: model.error_kind,
+            confidence: model.confidence,
             checked_at: model.checked_at.to_rfc3339(),
             price_minor_units: model.price_minor_units,
             price_currency: model.price_currency,
@@ -81,6 +112,10 @@ impl AvailabilityCheckResponse {
             lowest_price_minor_units: None,
             lowest_price_currency: None,
             lowest_currency_exponent: None,
+            lowest_normalized_price_minor_units: None,
+            lowest_normalized_currency: None,
+            lowest_normalized_currency_exponent: None,
+            lowest_price_normalization_skipped: false,
             normalized_price_minor_units: model.normalized_price_minor_units,
             normalized_currency: model.normalized_currency,
             normalized_currency_exponent,
@@ -97,6 +132,13 @@ impl AvailabilityCheckResponse {
             self.lowest_price_minor_units = Some(c.price_minor_units);
             self.lowest_price_currency = Some(c.price_currency);
             self.lowest_currency_exponent = Some(exponent);
+            self.lowest_normalized_currency_exponent = c
+                .normalized_currency
+                .as_deref()
+                .map(currency::currency_exponent);
+            self.lowest_normalized_price_minor_units = c.normalized_price_minor_units;
+            self.lowest_normalized_currency = c.normalized_currency;
+            self.lowest_price_normalization_skipped = c.normalization_skipped;
         }
         self
     }
@@ -104,14 +146,78 @@ impl AvailabilityCheckResponse {
 
 impl From<AvailabilityCheckModel> for AvailabilityCheckResponse {
     fn from(model: AvailabilityCheckModel) -> Self {
-        Self::from_model_with_daily_comparison(model, DailyPriceComparison::default())
+        Self::from_model_with_daily_comparison(
+            model,
+            DailyPriceComparison::default(),
+            DomainSettings::default().price_drop_threshold_percent,
+        )
+    }
+}
+
+/// Response DTO for a single bucket in a price history series
+#[derive(Debug, Serialize)]
+pub struct PricePointResponse {
+    /// Start of the bucket (RFC 3339, UTC)
+    pub bucket_start: String,
+    /// Average price in the bucket (normalized when available), None if no check had a price
+    pub average_price_minor_units: Option<i64>,
+}
+
+impl From<(DateTime<Utc>, Option<i64>)> for PricePointResponse {
+    fn from((bucket_start, average_price_minor_units): (DateTime<Utc>, Option<i64>)) -> Self {
+        Self {
+            bucket_start: bucket_start.to_rfc3339(),
+            average_price_minor_units,
+        }
     }
 }
 
+/// Response DTO for a product whose status changed since a given time
+#[derive(Debug, Serialize)]
+pub struct ProductChangeResponse {
+    pub product: ProductResponse,
+    /// Latest check, whose status differs from the one before the given time
+    pub latest_check: AvailabilityCheckResponse,
+}
+
+/// Response DTO for a recorded bulk check run
+#[derive(Debug, Serialize)]
+pub struct BulkCheckRunResponse {
+    pub id: String,
+    /// When the run finished (RFC 3339, UTC)
+    pub ran_at: String,
+    pub total: i32,
+    pub successful: i32,
+    pub failed: i32,
+    pub back_in_stock_count: i32,
+    pub price_drop_count: i32,
+}
+
+impl From<BulkCheckRunModel> for BulkCheckRunResponse {
+    fn from(model: BulkCheckRunModel) -> Self {
+        Self {
+            id: model.id.to_string(),
+            ran_at: model.ran_at.to_rfc3339(),
+            total: model.total,
+            successful: model.successful,
+            failed: model.failed,
+            back_in_stock_count: model.back_in_stock_count,
+            price_drop_count: model.price_drop_count,
+        }
+    }
+}
+
+/// Response DTO for a product whose price recently dropped
+#[derive(Debug, Serialize)]
+pub struct RecentDealResponse {
+    pub product: ProductResponse,
+    pub price_drop: PriceDrop,
+}
+
 /// Check availability for a product
 ///
 /// Fetches the product's URL and parses Schema.org data to determine availability.
-/// Sends a desktop notification if the product is back in stock.
+/// Sends a desktop notification (and webhook, if configured) if the product is back in stock.
 #[tauri::command]
 pub async fn check_availability(
     app: tauri::AppHandle,
@@ -121,14 +227,18 @@ pub async fn check_availability(
     let uuid = parse_uuid(&product_id)?;
 
     let result = TauriAvailabilityService::check_product_with_notification(db.conn(), uuid).await?;
+    let domain_settings = DomainSettingService::get(db.conn()).await?;
 
-    if let Some(notification) = result.notification {
-        send_desktop_notification(&app, &notification);
+    if let Some(notification) = &result.notification {
+        send_desktop_notification(&app, notification);
+        let payload = WebhookPayload::for_check(notification, &result.check);
+        send_webhook_notification(db.conn(), &payload).await;
     }
 
     Ok(AvailabilityCheckResponse::from_model_with_daily_comparison(
         result.check,
         result.daily_comparison,
+        domain_settings.price_drop_threshold_percent,
     ))
 }
 
@@ -146,6 +256,7 @@ pub async fn get_latest_availability(
         Some(model) => {
             // Get daily price comparison for today vs yesterday (re-normalized with today's rates)
             let settings = SettingService::get(db.conn()).await?;
+            let domain_settings = DomainSettingService::get(db.conn()).await?;
             let daily_comparison = AvailabilityService::get_daily_price_comparison(
                 db.conn(),
                 uuid,
@@ -153,11 +264,17 @@ pub async fn get_latest_availability(
             )
             .await?;
             // Get cheapest current price across all retailers
-            let cheapest = AvailabilityService::get_cheapest_current_price(db.conn(), uuid).await?;
+            let cheapest = AvailabilityService::get_cheapest_current_price(
+                db.conn(),
+                uuid,
+                &settings.preferred_currency,
+            )
+            .await?;
             Ok(Some(
                 AvailabilityCheckResponse::from_model_with_daily_comparison(
                     model,
                     daily_comparison,
+                    domain_settings.price_drop_threshold_percent,
                 )
                 .with_cheapest_price(cheapest),
             ))
@@ -167,26 +284,320 @@ pub async fn get_latest_availability(
 }
 
 /// Get availability check history for a product
+///
+/// `from` and `to` are optional RFC 3339 timestamps bounding the history to
+/// [from, to); `limit` keeps the most recent checks within that range.
 #[tauri::command]
 pub async fn get_availability_history(
     product_id: String,
     limit: Option<u64>,
+    from: Option<String>,
+    to: Option<String>,
     db: State<'_, DbState>,
 ) -> Result<Vec<AvailabilityCheckResponse>, CommandError> {
     let uuid = parse_uuid(&product_id)?;
+    let from = from.as_deref().map(parse_timestamp).transpose()?;
+    let to = to.as_deref().map(parse_timestamp).transpose()?;
 
-    let checks = AvailabilityService::get_history(db.conn(), uuid, limit).await?;
+    let checks = AvailabilityService::get_history(db.conn(), uuid, limit, from, to).await?;
     Ok(checks
         .into_iter()
         .map(AvailabilityCheckResponse::from)
         .collect())
 }
 
+/// Get bucketed average price history for a product
+///
+/// `from` and `to` are RFC 3339 timestamps; the range is [from, to).
+/// When `currency` is given, every price is converted into it at the exchange rate
+/// recorded nearest the check, and buckets with no convertible price are null.
+#[tauri::command]
+pub async fn get_price_history(
+    product_id: String,
+    bucket: Bucket,
+    from: String,
+    to: String,
+    currency: Option<String>,
+    db: State<'_, DbState>,
+) -> Result<Vec<PricePointResponse>, CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+    let from = parse_timestamp(&from)?;
+    let to = parse_timestamp(&to)?;
+
+    let series = match currency {
+        Some(currency) => {
+            AvailabilityService::get_price_series_in_currency(
+                db.conn(),
+                uuid,
+                bucket,
+                from,
+                to,
+                &currency,
+            )
+            .await?
+        }
+        None => AvailabilityService::get_price_series(db.conn(), uuid, bucket, from, to).await?,
+    };
+    Ok(series.into_iter().map(PricePointResponse::from).collect())
+}
+
+/// Get the lowest and highest prices ever recorded for a product
+#[tauri::command]
+pub async fn get_price_extremes(
+    product_id: String,
+    db: State<'_, DbState>,
+) -> Result<PriceExtremes, CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+
+    let extremes = AvailabilityService::get_price_extremes(db.conn(), uuid).await?;
+    Ok(extremes)
+}
+
+/// Format a price in minor units for display (e.g. "$12.34", "¥1,500", "KWD 1.250"),
+/// using the currency's number of decimal places
+#[tauri::command]
+pub fn format_price(minor_units: i64, currency: String) -> String {
+    currency::format_price(minor_units, &currency)
+}
+
+/// Get the availability status changes for a product (e.g. out of stock -> in stock),
+/// with how long each previous status lasted
+#[tauri::command]
+pub async fn get_status_transitions(
+    product_id: String,
+    db: State<'_, DbState>,
+) -> Result<Vec<StatusTransition>, CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+
+    let transitions = AvailabilityService::get_status_transitions(db.conn(), uuid).await?;
+    Ok(transitions)
+}
+
+/// Get how often a product came back in stock and the average time between restocks
+///
+/// `since` is an RFC 3339 timestamp; defaults to the last 90 days.
+#[tauri::command]
+pub async fn get_restock_stats(
+    product_id: String,
+    since: Option<String>,
+    db: State<'_, DbState>,
+) -> Result<RestockStats, CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+    let since = match since {
+        Some(since) => parse_timestamp(&since)?,
+        None => Utc::now() - chrono::Duration::days(AvailabilityService::RESTOCK_STATS_WINDOW_DAYS),
+    };
+
+    let stats = AvailabilityService::get_restock_stats(db.conn(), uuid, since).await?;
+    Ok(stats)
+}
+
+/// Get whether a product's price is rising, falling or stable over the last `days`
+///
+/// `days` defaults to 30; 7 and 30 are the windows the UI offers.
+#[tauri::command]
+pub async fn get_price_trend(
+    product_id: String,
+    days: Option<i64>,
+    db: State<'_, DbState>,
+) -> Result<PriceTrend, CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+    let days = days.unwrap_or(AvailabilityService::PRICE_TREND_WINDOW_DAYS);
+
+    let trend = AvailabilityService::get_price_trend(db.conn(), uuid, days).await?;
+    Ok(trend)
+}
+
+/// Default window for comparing two products when no `days` is given
+const DEFAULT_COMPARISON_WINDOW_DAYS: u32 = 30;
+
+/// Compare two products' daily average prices over the last `days` (default 30)
+///
+/// Both series are converted into the preferred currency and aligned on the same
+/// dates, e.g. to see which of two GPUs dropped more.
+#[tauri::command]
+pub async fn compare_products(
+    product_a_id: String,
+    product_b_id: String,
+    days: Option<u32>,
+    db: State<'_, DbState>,
+) -> Result<ProductComparison, CommandError> {
+    let product_a_id = parse_uuid(&product_a_id)?;
+    let product_b_id = parse_uuid(&product_b_id)?;
+    let settings = SettingService::get(db.conn()).await?;
+
+    let comparison = AvailabilityService::compare_products(
+        db.conn(),
+        product_a_id,
+        product_b_id,
+        days.unwrap_or(DEFAULT_COMPARISON_WINDOW_DAYS),
+        &settings.preferred_currency,
+        Utc::now(),
+    )
+    .await?;
+    Ok(comparison)
+}
+
+/// Get stock counts and the total value of in-stock products in the preferred
+/// currency, for the dashboard header
+#[tauri::command]
+pub async fn get_portfolio_summary(
+    db: State<'_, DbState>,
+) -> Result<PortfolioSummary, CommandError> {
+    let settings = SettingService::get(db.conn()).await?;
+
+    let summary =
+        AvailabilityService::get_portfolio_summary(db.conn(), &settings.preferred_currency).await?;
+    Ok(summary)
+}
+
+/// Get products whose availability status changed since a time, newest first
+///
+/// `since` is an RFC 3339 timestamp, e.g. when the user last looked, for a
+/// "what changed while I was away" digest.
+#[tauri::command]
+pub async fn get_changes_since(
+    since: String,
+    db: State<'_, DbState>,
+) -> Result<Vec<ProductChangeResponse>, CommandError> {
+    let since = parse_timestamp(&since)?;
+
+    let changes = AvailabilityService::get_changes_since(db.conn(), since).await?;
+    Ok(changes
+        .into_iter()
+        .map(|(product, check)| ProductChangeResponse {
+            product: product.into(),
+            latest_check: check.into(),
+        })
+        .collect())
+}
+
+/// Default window for recent deals when no `since` is given
+const DEFAULT_RECENT_DEALS_WINDOW_DAYS: i64 = 7;
+
+/// Default number of deals returned when no limit is given
+const DEFAULT_RECENT_DEALS_LIMIT: u64 = 20;
+
+/// Get products by largest recent price drop, for a deals dashboard
+///
+/// `since` is an RFC 3339 timestamp; defaults to the last 7 days. Prices are
+/// compared in the preferred currency.
+#[tauri::command]
+pub async fn get_recent_deals(
+    since: Option<String>,
+    limit: Option<u64>,
+    db: State<'_, DbState>,
+) -> Result<Vec<RecentDealResponse>, CommandError> {
+    let since = match since {
+        Some(since) => parse_timestamp(&since)?,
+        None => Utc::now() - chrono::Duration::days(DEFAULT_RECENT_DEALS_WINDOW_DAYS),
+    };
+    let settings = SettingService::get(db.conn()).await?;
+
+    let deals = AvailabilityService::get_recent_deals(
+        db.conn(),
+        since,
+        limit.unwrap_or(DEFAULT_RECENT_DEALS_LIMIT),
+        &settings.preferred_currency,
+    )
+    .await?;
+    Ok(deals
+        .into_iter()
+        .map(|(product, price_drop)| RecentDealResponse {
+            product: product.into(),
+            price_drop,
+        })
+        .collect())
+}
+
+/// Get scraper success rates per host over the most recent checks, least healthy first
+///
+/// Helps spot sites that stopped working, e.g. after adding bot protection.
+#[tauri::command]
+pub async fn get_scraper_health(db: State<'_, DbState>) -> Result<Vec<DomainHealth>, CommandError> {
+    let health = AvailabilityService::get_scraper_health(db.conn()).await?;
+    Ok(health)
+}
+
+/// Default number of bulk check runs returned when no limit is given
+const DEFAULT_BULK_CHECK_HISTORY_LIMIT: u64 = 20;
+
+/// Get summaries of recent "check all" runs, newest first
+#[tauri::command]
+pub async fn get_bulk_check_history(
+    limit: Option<u64>,
+    db: State<'_, DbState>,
+) -> Result<Vec<BulkCheckRunResponse>, CommandError> {
+    let runs = AvailabilityService::get_bulk_check_history(
+        db.conn(),
+        limit.unwrap_or(DEFAULT_BULK_CHECK_HISTORY_LIMIT),
+    )
+    .await?;
+    Ok(runs.into_iter().map(BulkCheckRunResponse::from).collect())
+}
+
+/// Delete checks older than the data retention window, returning how many were removed
+///
+/// Does nothing when retention is 0 (keep forever). The latest check per product is kept.
+#[tauri::command]
+pub async fn prune_check_history(db: State<'_, DbState>) -> Result<u64, CommandError> {
+    let domain_settings = DomainSettingService::get(db.conn()).await?;
+    let deleted = AvailabilityService::prune_check_history(
+        db.conn(),
+        domain_settings.data_retention_days,
+        Utc::now(),
+    )
+    .await?;
+    Ok(deleted)
+}
+
+/// Reassign or delete checks left behind by a deleted product or retailer link
+///
+/// Runs in one transaction and returns how many checks were reassigned and deleted.
+#[tauri::command]
+pub async fn repair_orphaned_checks(db: State<'_, DbState>) -> Result<RepairReport, CommandError> {
+    let report = AvailabilityService::repair_orphaned_checks(db.conn()).await?;
+    Ok(report)
+}
+
+/// Scrape a URL without saving a check and report how the result was extracted
+///
+/// Helps users understand why a URL returns Unknown before adding it as a product.
+#[tauri::command]
+pub async fn test_scrape_url(
+    url: String,
+    db: State<'_, DbState>,
+) -> Result<ScrapeDiagnostics, CommandError> {
+    let domain_settings = DomainSettingService::get(db.conn()).await?;
+
+    let diagnostics =
+        ScraperService::diagnose(&url, db.conn(), &domain_settings.fetch_options()).await?;
+    Ok(diagnostics)
+}
+
+/// Fetch the HTML the scraper sees for a product, for debugging a mis-reported check
+///
+/// Uses the same headless and manual verification settings as a check; the HTML
+/// is truncated to `ScraperService::MAX_RAW_HTML_BYTES`.
+#[tauri::command]
+pub async fn fetch_raw_html(
+    product_id: String,
+    db: State<'_, DbState>,
+) -> Result<String, CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+    let domain_settings = DomainSettingService::get(db.conn()).await?;
+
+    let html =
+        AvailabilityService::fetch_raw_html(db.conn(), uuid, &domain_settings.fetch_options())
+            .await?;
+    Ok(html)
+}
+
 /// Check availability for all products
 ///
 /// Performs a bulk availability check on all products with rate limiting.
-/// Sends desktop notifications for products that are back in stock.
-/// Emits progress events for each product checked.
+/// Sends desktop notifications (and webhook, if configured) for products that are
+/// back in stock. Emits progress events for each product checked.
 #[tauri::command]
 pub async fn check_all_availability(
     app: tauri::AppHandle,
@@ -197,6 +608,78 @@ pub async fn check_all_availability(
 
     if let Some(notification) = result.notification {
         send_desktop_notification(&app, &notification);
+        let payload = WebhookPayload::from_notification(&notification);
+        send_webhook_notification(db.conn(), &payload).await;
+    }
+
+    Ok(result.summary)
+}
+
+/// Check availability for all products carrying a tag
+///
+/// Same as `check_all_availability`, but only products tagged with `tag` are checked.
+#[tauri::command]
+pub async fn check_all_by_tag(
+    tag: String,
+    app: tauri::AppHandle,
+    db: State<'_, DbState>,
+) -> Result<BulkCheckSummary, CommandError> {
+    let result =
+        TauriAvailabilityService::check_tagged_products_with_notification(db.conn(), &app, &tag)
+            .await?;
+
+    if let Some(notification) = result.notification {
+        send_desktop_notification(&app, &notification);
+        let payload = WebhookPayload::from_notification(&notification);
+        send_webhook_notification(db.conn(), &payload).await;
+    }
+
+    Ok(result.summary)
+}
+
+/// Check availability for an explicit list of products
+///
+/// Same as `check_all_availability`, but only the given products are checked
+/// (including paused ones). IDs that are malformed or don't match a product
+/// appear as failed results instead of aborting the batch.
+#[tauri::command]
+pub async fn check_selected(
+    product_ids: Vec<String>,
+    app: tauri::AppHandle,
+    db: State<'_, DbState>,
+) -> Result<BulkCheckSummary, CommandError> {
+    let result = TauriAvailabilityService::check_selected_products_with_notification(
+        db.conn(),
+        &app,
+        &product_ids,
+    )
+    .await?;
+
+    if let Some(notification) = result.notification {
+        send_desktop_notification(&app, &notification);
+        let payload = WebhookPayload::from_notification(&notification);
+        send_webhook_notification(db.conn(), &payload).await;
+    }
+
+    Ok(result.summary)
+}
+
+/// Re-check only the products whose latest check failed
+///
+/// Uses the same rate-limited loop, progress events and notifications as a
+/// bulk check. Paused products are skipped.
+#[tauri::command]
+pub async fn recheck_failed(
+    app: tauri::AppHandle,
+    db: State<'_, DbState>,
+) -> Result<BulkCheckSummary, CommandError> {
+    let result =
+        TauriAvailabilityService::check_failed_products_with_notification(db.conn(), &app).await?;
+
+    if let Some(notification) = result.notification {
+        send_desktop_notification(&app, &notification);
+        let payload = WebhookPayload::from_notification(&notification);
+        send_webhook_notification(db.conn(), &payload).await;
     }
 
     Ok(result.summary)
@@ -216,12 +699,18 @@ mod tests {
             status: "in_stock".to_string(),
             raw_availability: Some("http://schema.org/InStock".to_string()),
             error_message: None,
+            error_kind: None,
             checked_at: Utc::now(),
             price_minor_units: Some(78900),
             price_currency: Some("USD".to_string()),
             raw_price: Some("789.00".to_string()),
             normalized_price_minor_units: None,
             normalized_currency: None,
+            compare_at_minor_units: None,
+            price_valid_until: None,
+            fetch_duration_ms: None,
+            content_bytes: None,
+            confidence: Some("high".to_string()),
         }
     }
 
@@ -241,6 +730,7 @@ mod tests {
             Some("http://schema.org/InStock".to_string())
         );
         assert!(response.error_message.is_none());
+        assert_eq!(response.confidence.as_deref(), Some("high"));
         assert!(!response.checked_at.is_empty());
         assert_eq!(response.price_minor_units, Some(78900));
         assert_eq!(response.price_currency, Some("USD".to_string()));
@@ -263,6 +753,7 @@ mod tests {
             status: "unknown".to_string(),
             raw_availability: None,
             error_message: Some("Failed to fetch page".to_string()),
+            error_kind: Some("network".to_string()),
             price_minor_units: None,
             price_currency: None,
             raw_price: None,
@@ -273,6 +764,7 @@ mod tests {
 
         assert_eq!(response.status, "unknown");
         assert!(response.raw_availability.is_none());
+        assert_eq!(response.error_kind.as_deref(), Some("network"));
         assert_eq!(
             response.error_message,
             Some("Failed to fetch page".to_string())
@@ -326,7 +818,7 @@ mod tests {
         };
 
         let response =
-            AvailabilityCheckResponse::from_model_with_daily_comparison(model, daily_comparison);
+            AvailabilityCheckResponse::from_model_with_daily_comparison(model, daily_comparison, 5);
 
         assert_eq!(response.price_minor_units, Some(78900));
         assert_eq!(response.currency_exponent, Some(2));
@@ -335,6 +827,24 @@ mod tests {
         assert!(response.is_price_drop);
     }
 
+    #[test]
+    fn test_availability_check_response_small_drop_below_threshold() {
+        let daily_comparison = DailyPriceComparison {
+            today_average_minor_units: Some(89800),
+            yesterday_average_minor_units: Some(89900),
+        };
+
+        let response = AvailabilityCheckResponse::from_model_with_daily_comparison(
+            test_model(),
+            daily_comparison,
+            5,
+        );
+
+        assert_eq!(response.today_average_price_minor_units, Some(89800));
+        assert_eq!(response.yesterday_average_price_minor_units, Some(89900));
+        assert!(!response.is_price_drop);
+    }
+
     #[test]
     fn test_availability_check_response_with_daily_comparison_price_increase() {
         let model = AvailabilityCheckModel {
@@ -350,7 +860,7 @@ mod tests {
         };
 
         let response =
-            AvailabilityCheckResponse::from_model_with_daily_comparison(model, daily_comparison);
+            AvailabilityCheckResponse::from_model_with_daily_comparison(model, daily_comparison, 5);
 
         assert_eq!(response.price_minor_units, Some(99900));
         assert_eq!(response.currency_exponent, Some(2));
@@ -373,7 +883,7 @@ mod tests {
         };
 
         let response =
-            AvailabilityCheckResponse::from_model_with_daily_comparison(model, daily_comparison);
+            AvailabilityCheckResponse::from_model_with_daily_comparison(model, daily_comparison, 5);
 
         assert_eq!(response.price_minor_units, Some(78900));
         assert_eq!(response.currency_exponent, Some(2));
@@ -390,6 +900,9 @@ mod tests {
         let cheapest = CheapestPriceResult {
             price_minor_units: 3000,
             price_currency: "AUD".to_string(),
+            normalized_price_minor_units: None,
+            normalized_currency: None,
+            normalization_skipped: false,
         };
 
         let response = response.with_cheapest_price(Some(cheapest));
@@ -399,6 +912,29 @@ mod tests {
         assert_eq!(response.lowest_currency_exponent, Some(2));
     }
 
+    #[test]
+    fn test_with_cheapest_price_sets_normalized_fields() {
+        use crate::domain::repositories::CheapestPriceResult;
+
+        let cheapest = CheapestPriceResult {
+            price_minor_units: 5000,
+            price_currency: "JPY".to_string(),
+            normalized_price_minor_units: Some(5150),
+            normalized_currency: Some("AUD".to_string()),
+            normalization_skipped: false,
+        };
+
+        let response =
+            AvailabilityCheckResponse::from(test_model()).with_cheapest_price(Some(cheapest));
+
+        assert_eq!(response.lowest_price_minor_units, Some(5000));
+        assert_eq!(response.lowest_currency_exponent, Some(0));
+        assert_eq!(response.lowest_normalized_price_minor_units, Some(5150));
+        assert_eq!(response.lowest_normalized_currency, Some("AUD".to_string()));
+        assert_eq!(response.lowest_normalized_currency_exponent, Some(2));
+        assert!(!response.lowest_price_normalization_skipped);
+    }
+
     #[test]
     fn test_with_cheapest_price_none_leaves_fields_null() {
         let response = AvailabilityCheckResponse::from(test_model()).with_cheapest_price(None);
@@ -416,6 +952,9 @@ mod tests {
             CheapestPriceResult {
                 price_minor_units: 5000,
                 price_currency: "JPY".to_string(),
+                normalized_price_minor_units: None,
+                normalized_currency: None,
+                normalization_skipped: true,
             },
         ));
 
@@ -423,5 +962,20 @@ mod tests {
         assert!(json.contains("\"lowest_price_minor_units\":5000"));
         assert!(json.contains("\"lowest_price_currency\":\"JPY\""));
         assert!(json.contains("\"lowest_currency_exponent\":0"));
+        assert!(json.contains("\"lowest_price_normalization_skipped\":true"));
+    }
+
+    #[test]
+    fn test_price_point_response_from_tuple() {
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        let response = PricePointResponse::from((start, Some(1500)));
+
+        assert_eq!(response.bucket_start, "2024-01-02T00:00:00+00:00");
+        assert_eq!(response.average_price_minor_units, Some(1500));
+
+        let json = serde_json::to_string(&PricePointResponse::from((start, None))).unwrap();
+        assert!(json.contains("\"average_price_minor_units\":null"));
     }
 }
@@ -0,0 +1,25 @@
+use tauri::State;
+
+use crate::db::DbState;
+use crate::domain::services::{BackupService, ImportMode, ImportSummary};
+use crate::tauri_error::CommandError;
+
+/// Export all products, retailers and retailer links as a versioned JSON backup
+///
+/// Check history is not included.
+#[tauri::command]
+pub async fn export_products_json(db: State<'_, DbState>) -> Result<String, CommandError> {
+    Ok(BackupService::export_json(db.conn()).await?)
+}
+
+/// Import a JSON backup created by `export_products_json`
+///
+/// `mode` is `"merge"` (keep existing products) or `"replace"` (delete them first).
+#[tauri::command]
+pub async fn import_products_json(
+    json: String,
+    mode: ImportMode,
+    db: State<'_, DbState>,
+) -> Result<ImportSummary, CommandError> {
+    Ok(BackupService::import_json(db.conn(), &json, mode).await?)
+}
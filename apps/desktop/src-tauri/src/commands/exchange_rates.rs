@@ -4,6 +4,7 @@ use tauri::State;
 use crate::core::entities::exchange_rate;
 use crate::core::services::{ExchangeRateService, SettingService};
 use crate::db::DbState;
+use crate::domain::services::AvailabilityService;
 use crate::tauri_error::CommandError;
 
 #[derive(Debug, Serialize)]
@@ -69,6 +70,19 @@ pub async fn delete_exchange_rate(
     Ok(())
 }
 
+/// Convert a batch of minor-unit prices into a target currency
+///
+/// Items whose currency has no exchange rate come back as `null`.
+#[tauri::command]
+pub async fn convert_prices(
+    items: Vec<(i64, String)>,
+    to: String,
+    db: State<'_, DbState>,
+) -> Result<Vec<Option<i64>>, CommandError> {
+    let converted = AvailabilityService::convert_prices(db.conn(), &items, &to).await?;
+    Ok(converted)
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::Utc;
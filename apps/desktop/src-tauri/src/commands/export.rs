@@ -0,0 +1,13 @@
+use tauri::State;
+
+use crate::db::DbState;
+use crate::domain::services::ExportService;
+use crate::tauri_error::CommandError;
+
+/// Export all products with their latest status as CSV text
+///
+/// Products tracked at several retailers get one row per retailer.
+#[tauri::command]
+pub async fn export_products_csv(db: State<'_, DbState>) -> Result<String, CommandError> {
+    Ok(ExportService::products_csv(db.conn()).await?)
+}
@@ -1,7 +1,10 @@
 // === DOMAIN ===
 mod availability;
+mod backup;
+mod export;
 mod product_retailers;
 mod products;
+mod tags;
 
 // === INFRASTRUCTURE ===
 mod exchange_rates;
@@ -12,8 +15,11 @@ mod window;
 
 // === DOMAIN ===
 pub use availability::*;
+pub use backup::*;
+pub use export::*;
 pub use product_retailers::*;
 pub use products::*;
+pub use tags::*;
 
 // === INFRASTRUCTURE ===
 pub use exchange_rates::*;
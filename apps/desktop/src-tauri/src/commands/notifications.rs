@@ -1,12 +1,15 @@
+use chrono::{DateTime, Utc};
 use sea_orm::DatabaseConnection;
 use serde::Deserialize;
 use tauri::State;
 use tauri_plugin_notification::NotificationExt;
 
-use crate::core::services::SettingService;
+use crate::core::services::{SettingService, Settings};
 use crate::core::AppError;
 use crate::db::DbState;
+use crate::domain::services::{NotificationData, WebhookPayload, WebhookService};
 use crate::tauri_error::CommandError;
+use crate::tauri_services::{send_desktop_notification, send_webhook_notification};
 
 /// Input for sending a notification
 #[derive(Debug, Clone, Deserialize)]
@@ -82,6 +85,60 @@ pub async fn send_notification(
     Ok(true)
 }
 
+/// Send a sample payload to the configured webhook (testable version)
+///
+/// Unlike notification-triggered webhooks, failures are returned so the user
+/// can see why their webhook isn't working.
+pub async fn deliver_test_webhook(conn: &DatabaseConnection) -> Result<(), AppError> {
+    let settings = SettingService::get(conn).await?;
+    let payload = WebhookPayload::from_notification(&NotificationData::new(
+        "Product Stalker",
+        "Test notification from Product Stalker",
+    ));
+
+    if !WebhookService::send(&settings.notification_webhook_url, &payload).await? {
+        return Err(AppError::Validation(
+            "No notification webhook URL configured".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Send a test notification to the configured webhook
+#[tauri::command]
+pub async fn send_test_webhook(db: State<'_, DbState>) -> Result<(), CommandError> {
+    Ok(deliver_test_webhook(db.conn()).await?)
+}
+
+/// Whether a test notification is held back: quiet hours apply unless `force` is set
+fn is_test_notification_suppressed(settings: &Settings, force: bool, now: DateTime<Utc>) -> bool {
+    !force && settings.is_quiet_time(now)
+}
+
+/// Send a sample notification through the same desktop and webhook path as a
+/// real restock, so the user can check notifications actually arrive
+///
+/// Quiet hours are respected unless `force` is set. Returns whether the OS
+/// reported the desktop notification as shown (`false` when held back).
+#[tauri::command]
+pub async fn send_test_notification(
+    app: tauri::AppHandle,
+    force: Option<bool>,
+    db: State<'_, DbState>,
+) -> Result<bool, CommandError> {
+    let settings = SettingService::get(db.conn()).await?;
+    if is_test_notification_suppressed(&settings, force.unwrap_or(false), Utc::now()) {
+        log::debug!("Test notification skipped (quiet hours)");
+        return Ok(false);
+    }
+
+    let notification =
+        NotificationData::new("Product Stalker", "Test notification from Product Stalker");
+    let shown = send_desktop_notification(&app, &notification);
+    send_webhook_notification(db.conn(), &WebhookPayload::from_notification(&notification)).await;
+    Ok(shown)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,6 +231,29 @@ mod tests {
         assert!(input.body.contains("🔔"));
     }
 
+    #[test]
+    fn test_test_notification_force_overrides_quiet_hours() {
+        use chrono::TimeZone;
+
+        let settings = Settings {
+            quiet_hours_start: "22:00".to_string(),
+            quiet_hours_end: "07:00".to_string(),
+            display_timezone: "UTC".to_string(),
+            ..Settings::default()
+        };
+        let night = Utc.with_ymd_and_hms(2026, 1, 15, 23, 30, 0).unwrap();
+        let day = Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap();
+
+        assert!(is_test_notification_suppressed(&settings, false, night));
+        assert!(!is_test_notification_suppressed(&settings, true, night));
+        assert!(!is_test_notification_suppressed(&settings, false, day));
+        assert!(!is_test_notification_suppressed(
+            &Settings::default(),
+            false,
+            night
+        ));
+    }
+
     #[test]
     fn test_deserialize_missing_field_fails() {
         let json = r#"{"title": "Test"}"#;
@@ -249,4 +329,12 @@ mod integration_tests {
 
         assert!(check_notifications_enabled(&conn).await.unwrap());
     }
+
+    #[tokio::test]
+    async fn test_deliver_test_webhook_without_url_fails() {
+        let conn = setup_app_settings_db().await;
+
+        let result = deliver_test_webhook(&conn).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
 }
@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
+use crate::core::services::SettingService;
 use crate::db::DbState;
 use crate::domain::entities::prelude::ProductRetailerModel;
-use crate::domain::services::{AddRetailerParams, ProductRetailerService, ReorderRetailersParams};
+use crate::domain::services::{
+    AddRetailerParams, AvailabilityService, CheckConfig, DomainSettingService,
+    ProductRetailerService, ReorderRetailersParams, RetailerComparison, UrlValidation,
+    VariantSelector,
+};
 use crate::tauri_error::CommandError;
 use crate::utils::parse_uuid;
 
@@ -24,6 +29,10 @@ pub struct ProductRetailerResponse {
     pub url: String,
     pub label: Option<String>,
     pub sort_order: i32,
+    /// Currency detected for this link, which may differ from the product's
+    pub currency: Option<String>,
+    /// Variant attributes checked on this link (e.g. size and color)
+    pub variant_selector: Option<VariantSelector>,
     pub created_at: String,
 }
 
@@ -36,11 +45,25 @@ impl From<ProductRetailerModel> for ProductRetailerResponse {
             url: model.url,
             label: model.label,
             sort_order: model.sort_order,
+            currency: model.currency,
+            variant_selector: model
+                .variant_selector
+                .as_deref()
+                .and_then(|json| VariantSelector::from_json(json).ok()),
             created_at: model.created_at.to_rfc3339(),
         }
     }
 }
 
+/// Response for a newly added retailer link
+#[derive(Debug, Serialize)]
+pub struct AddProductRetailerResponse {
+    #[serde(flatten)]
+    pub product_retailer: ProductRetailerResponse,
+    /// Dry-run scrape of the URL, present when `validate_url_on_create` is enabled
+    pub validation: Option<UrlValidation>,
+}
+
 /// A single reorder update entry
 #[derive(Debug, Deserialize)]
 pub struct ReorderRetailerUpdate {
@@ -55,11 +78,14 @@ pub struct ReorderRetailersInput {
 }
 
 /// Add a retailer URL to a product
+///
+/// With `validate_url_on_create` enabled, the URL is scraped once and its status
+/// stored as the first check; untrackable URLs return a warning but are still added.
 #[tauri::command]
 pub async fn add_product_retailer(
     input: AddRetailerInput,
     db: State<'_, DbState>,
-) -> Result<ProductRetailerResponse, CommandError> {
+) -> Result<AddProductRetailerResponse, CommandError> {
     let product_id = parse_uuid(&input.product_id)?;
 
     let product_retailer = ProductRetailerService::add_retailer(
@@ -72,7 +98,27 @@ pub async fn add_product_retailer(
     )
     .await?;
 
-    Ok(ProductRetailerResponse::from(product_retailer))
+    let domain_settings = DomainSettingService::get(db.conn()).await?;
+    let validation = if domain_settings.validate_url_on_create {
+        let settings = SettingService::get(db.conn()).await?;
+        let config = CheckConfig {
+            fetch: domain_settings.fetch_options(),
+            preferred_currency: &settings.preferred_currency,
+            price_drop_threshold_percent: domain_settings.price_drop_threshold_percent,
+            min_plausible_price_percent: domain_settings.min_plausible_price_percent,
+        };
+        Some(
+            AvailabilityService::validate_product_retailer(db.conn(), product_retailer.id, &config)
+                .await?,
+        )
+    } else {
+        None
+    };
+
+    Ok(AddProductRetailerResponse {
+        product_retailer: ProductRetailerResponse::from(product_retailer),
+        validation,
+    })
 }
 
 /// Get all retailer links for a product
@@ -90,6 +136,19 @@ pub async fn get_product_retailers(
         .collect())
 }
 
+/// Compare a product's retailers: each link's latest check plus how reliably
+/// its retailer has been checked recently
+#[tauri::command]
+pub async fn get_retailer_comparison(
+    product_id: String,
+    db: State<'_, DbState>,
+) -> Result<Vec<RetailerComparison>, CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+
+    let comparison = AvailabilityService::get_retailer_comparison(db.conn(), uuid).await?;
+    Ok(comparison)
+}
+
 /// Reorder retailers for a product
 #[tauri::command]
 pub async fn reorder_product_retailers(
@@ -106,6 +165,34 @@ pub async fn reorder_product_retailers(
     Ok(())
 }
 
+/// Set the variant attributes checked on a retailer link, or clear them with `null`
+#[tauri::command]
+pub async fn set_product_retailer_variant_selector(
+    id: String,
+    variant_selector: Option<VariantSelector>,
+    db: State<'_, DbState>,
+) -> Result<ProductRetailerResponse, CommandError> {
+    let uuid = parse_uuid(&id)?;
+
+    let pr =
+        ProductRetailerService::set_variant_selector(db.conn(), uuid, variant_selector).await?;
+    Ok(ProductRetailerResponse::from(pr))
+}
+
+/// Replace a retailer link's URL with the normalized address it redirects to
+/// (the `canonical_url` reported when the URL was scraped)
+#[tauri::command]
+pub async fn normalize_product_url(
+    id: String,
+    canonical_url: String,
+    db: State<'_, DbState>,
+) -> Result<ProductRetailerResponse, CommandError> {
+    let uuid = parse_uuid(&id)?;
+
+    let pr = ProductRetailerService::normalize_url(db.conn(), uuid, &canonical_url).await?;
+    Ok(ProductRetailerResponse::from(pr))
+}
+
 /// Remove a retailer link from a product
 #[tauri::command]
 pub async fn remove_product_retailer(
@@ -138,6 +225,8 @@ mod tests {
             url: "https://amazon.com/dp/B123".to_string(),
             label: Some("64GB version".to_string()),
             sort_order: 0,
+            currency: None,
+            variant_selector: None,
             created_at: now,
         };
 
@@ -160,6 +249,8 @@ mod tests {
             url: "https://walmart.com/item/456".to_string(),
             label: None,
             sort_order: 0,
+            currency: None,
+            variant_selector: None,
             created_at: Utc::now(),
         };
 
@@ -179,6 +270,8 @@ mod tests {
             url: "https://bestbuy.com/product/789".to_string(),
             label: Some("Blue".to_string()),
             sort_order: 0,
+            currency: None,
+            variant_selector: None,
             created_at: Utc::now(),
         };
 
@@ -190,6 +283,33 @@ mod tests {
         assert!(json.contains(&id.to_string()));
     }
 
+    #[test]
+    fn test_add_product_retailer_response_flattens_link() {
+        let model = ProductRetailerModel {
+            id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            retailer_id: Uuid::new_v4(),
+            url: "https://bestbuy.com/product/789".to_string(),
+            label: None,
+            sort_order: 0,
+            currency: None,
+            variant_selector: None,
+            created_at: Utc::now(),
+        };
+
+        let response = AddProductRetailerResponse {
+            product_retailer: ProductRetailerResponse::from(model),
+            validation: Some(UrlValidation {
+                diagnostics: None,
+                warning: Some("Couldn't load this URL".to_string()),
+            }),
+        };
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains("\"url\":\"https://bestbuy.com/product/789\""));
+        assert!(json.contains("\"warning\":\"Couldn't load this URL\""));
+    }
+
     #[test]
     fn test_add_retailer_input_deserializes() {
         let json = r#"{"product_id":"550e8400-e29b-41d4-a716-446655440000","url":"https://amazon.com/dp/B123","label":"64GB"}"#;
@@ -231,10 +351,32 @@ mod tests {
             url: "https://amazon.com/dp/B123".to_string(),
             label: None,
             sort_order: 5,
+            currency: None,
+            variant_selector: None,
             created_at: now,
         };
 
         let response = ProductRetailerResponse::from(model);
         assert_eq!(response.sort_order, 5);
     }
+
+    #[test]
+    fn test_product_retailer_response_includes_variant_selector() {
+        let model = ProductRetailerModel {
+            id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            retailer_id: Uuid::new_v4(),
+            url: "https://shop.example.com/tee".to_string(),
+            label: None,
+            sort_order: 0,
+            currency: None,
+            variant_selector: Some(r#"{"size":"M"}"#.to_string()),
+            created_at: Utc::now(),
+        };
+
+        let response = ProductRetailerResponse::from(model);
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains(r#""variant_selector":{"size":"M"}"#));
+    }
 }
@@ -1,13 +1,17 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use tauri::State;
 
 use crate::db::DbState;
+use crate::domain::entities::availability_check::AvailabilityStatus;
 use crate::domain::entities::prelude::ProductModel;
+use crate::domain::repositories::{PriceExtremes, PriceTrend};
 use crate::domain::services::{
-    CreateProductParams, ProductService, ReorderProductsParams, UpdateProductParams,
+    AvailabilityService, CreateProductParams, ImportReport, ProductService, ReorderProductsParams,
+    RestockStats, UpdateProductParams, UrlImportService,
 };
 use crate::tauri_error::CommandError;
-use crate::utils::parse_uuid;
+use crate::utils::{parse_timestamp, parse_uuid};
 
 /// Input for creating a product
 #[derive(Debug, Deserialize)]
@@ -15,6 +19,12 @@ pub struct CreateProductInput {
     pub name: String,
     pub description: Option<String>,
     pub notes: Option<String>,
+    #[serde(default)]
+    pub check_interval_minutes: Option<i32>,
+    #[serde(default)]
+    pub target_price_minor_units: Option<i64>,
+    #[serde(default)]
+    pub target_price_currency: Option<String>,
 }
 
 /// Input for updating a product
@@ -23,6 +33,30 @@ pub struct UpdateProductInput {
     pub name: Option<String>,
     pub description: Option<String>,
     pub notes: Option<String>,
+    /// Missing = keep, `null` = clear the override, number = set the override
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub check_interval_minutes: Option<Option<i32>>,
+    /// Missing = keep, `null` = clear the target price, number = set it
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub target_price_minor_units: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub target_price_currency: Option<Option<String>>,
+    pub is_paused: Option<bool>,
+    pub track_price: Option<bool>,
+    /// Missing = keep, `null` = clear and unlock, code = set and lock
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub currency: Option<Option<String>>,
+    pub currency_locked: Option<bool>,
+}
+
+/// Deserialize a present field (including `null`) as `Some`, so a missing field
+/// can be told apart from an explicit `null`.
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 /// Response DTO for products
@@ -34,8 +68,58 @@ pub struct ProductResponse {
     pub notes: Option<String>,
     pub currency: Option<String>,
     pub sort_order: i32,
+    pub check_interval_minutes: Option<i32>,
+    pub target_price_minor_units: Option<i64>,
+    pub target_price_currency: Option<String>,
+    pub is_paused: bool,
+    pub is_archived: bool,
+    /// Stock-only products are checked without recording prices
+    pub track_price: bool,
+    /// Locked currencies are never changed by auto-detection
+    pub currency_locked: bool,
+    /// RFC 3339 time until which notifications are snoozed
+    pub notifications_snoozed_until: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Lowest/highest recorded prices, only included in the product detail payload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_extremes: Option<PriceExtremes>,
+    /// Restock frequency over the default window, only included in the product detail payload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restock_stats: Option<RestockStats>,
+    /// RFC 3339 time of the first in-stock check, only included in the product
+    /// detail payload (and only once the product has been in stock)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_in_stock_at: Option<String>,
+    /// Price trend over the default window, only included in the product detail payload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_trend: Option<PriceTrend>,
+}
+
+impl ProductResponse {
+    /// Attach the lowest/highest recorded prices for the product detail view
+    pub fn with_price_extremes(mut self, extremes: PriceExtremes) -> Self {
+        self.price_extremes = Some(extremes);
+        self
+    }
+
+    /// Attach restock frequency stats for the product detail view
+    pub fn with_restock_stats(mut self, stats: RestockStats) -> Self {
+        self.restock_stats = Some(stats);
+        self
+    }
+
+    /// Attach when the product was first seen in stock for the product detail view
+    pub fn with_first_in_stock_at(mut self, first_in_stock_at: Option<DateTime<Utc>>) -> Self {
+        self.first_in_stock_at = first_in_stock_at.map(|t| t.to_rfc3339());
+        self
+    }
+
+    /// Attach the price trend for the product detail view
+    pub fn with_price_trend(mut self, trend: PriceTrend) -> Self {
+        self.price_trend = Some(trend);
+        self
+    }
 }
 
 impl From<ProductModel> for ProductResponse {
@@ -47,16 +131,31 @@ impl From<ProductModel> for ProductResponse {
             notes: model.notes,
             currency: model.currency,
             sort_order: model.sort_order,
+            check_interval_minutes: model.check_interval_minutes,
+            target_price_minor_units: model.target_price_minor_units,
+            target_price_currency: model.target_price_currency,
+            is_paused: model.is_paused,
+            is_archived: model.is_archived,
+            track_price: model.track_price,
+            currency_locked: model.currency_locked,
+            notifications_snoozed_until: model.notifications_snoozed_until.map(|t| t.to_rfc3339()),
             created_at: model.created_at.to_rfc3339(),
             updated_at: model.updated_at.to_rfc3339(),
+            price_extremes: None,
+            restock_stats: None,
+            first_in_stock_at: None,
+            price_trend: None,
         }
     }
 }
 
-/// Get all products
+/// Get all products, leaving out archived ones unless `include_archived` is set
 #[tauri::command]
-pub async fn get_products(db: State<'_, DbState>) -> Result<Vec<ProductResponse>, CommandError> {
-    let products = ProductService::get_all(db.conn()).await?;
+pub async fn get_products(
+    include_archived: Option<bool>,
+    db: State<'_, DbState>,
+) -> Result<Vec<ProductResponse>, CommandError> {
+    let products = ProductService::get_all(db.conn(), include_archived.unwrap_or(false)).await?;
     Ok(products.into_iter().map(ProductResponse::from).collect())
 }
 
@@ -69,7 +168,41 @@ pub async fn get_product(
     let uuid = parse_uuid(&id)?;
 
     let product = ProductService::get_by_id(db.conn(), uuid).await?;
-    Ok(ProductResponse::from(product))
+    let extremes = AvailabilityService::get_price_extremes(db.conn(), uuid).await?;
+    let since = Utc::now() - Duration::days(AvailabilityService::RESTOCK_STATS_WINDOW_DAYS);
+    let restock_stats = AvailabilityService::get_restock_stats(db.conn(), uuid, since).await?;
+    let first_in_stock_at = AvailabilityService::get_first_in_stock_at(db.conn(), uuid).await?;
+    let price_trend = AvailabilityService::get_price_trend(
+        db.conn(),
+        uuid,
+        AvailabilityService::PRICE_TREND_WINDOW_DAYS,
+    )
+    .await?;
+    Ok(ProductResponse::from(product)
+        .with_price_extremes(extremes)
+        .with_restock_stats(restock_stats)
+        .with_first_in_stock_at(first_in_stock_at)
+        .with_price_trend(price_trend))
+}
+
+/// Search products by name, description or notes (case-insensitive)
+#[tauri::command]
+pub async fn search_products(
+    query: String,
+    db: State<'_, DbState>,
+) -> Result<Vec<ProductResponse>, CommandError> {
+    let products = ProductService::search(db.conn(), &query).await?;
+    Ok(products.into_iter().map(ProductResponse::from).collect())
+}
+
+/// Get products whose latest availability check has the given status
+#[tauri::command]
+pub async fn get_products_by_status(
+    status: AvailabilityStatus,
+    db: State<'_, DbState>,
+) -> Result<Vec<ProductResponse>, CommandError> {
+    let products = ProductService::get_by_latest_status(db.conn(), status).await?;
+    Ok(products.into_iter().map(ProductResponse::from).collect())
 }
 
 /// Create a new product
@@ -84,6 +217,9 @@ pub async fn create_product(
             name: input.name,
             description: input.description,
             notes: input.notes,
+            check_interval_minutes: input.check_interval_minutes,
+            target_price_minor_units: input.target_price_minor_units,
+            target_price_currency: input.target_price_currency,
         },
     )
     .await?;
@@ -91,6 +227,19 @@ pub async fn create_product(
     Ok(ProductResponse::from(product))
 }
 
+/// Create a product for every new http(s) URL in pasted text
+///
+/// URLs that are already tracked or repeated in the text are skipped; each
+/// product is named after its URL path.
+#[tauri::command]
+pub async fn import_urls(
+    text: String,
+    db: State<'_, DbState>,
+) -> Result<ImportReport, CommandError> {
+    let report = UrlImportService::import_urls(db.conn(), &text).await?;
+    Ok(report)
+}
+
 /// Update an existing product
 #[tauri::command]
 pub async fn update_product(
@@ -107,6 +256,13 @@ pub async fn update_product(
             name: input.name,
             description: input.description,
             notes: input.notes,
+            check_interval_minutes: input.check_interval_minutes,
+            target_price_minor_units: input.target_price_minor_units,
+            target_price_currency: input.target_price_currency,
+            is_paused: input.is_paused,
+            track_price: input.track_price,
+            currency: input.currency,
+            currency_locked: input.currency_locked,
         },
     )
     .await?;
@@ -152,9 +308,82 @@ pub async fn delete_product(id: String, db: State<'_, DbState>) -> Result<(), Co
     Ok(())
 }
 
+/// Merge a duplicate product into another
+///
+/// Retailer links, check history and tags of `merge_id` move to `keep_id`, and
+/// `merge_id` is deleted. A link whose URL `keep_id` already tracks keeps the
+/// existing link.
+#[tauri::command]
+pub async fn merge_products(
+    keep_id: String,
+    merge_id: String,
+    db: State<'_, DbState>,
+) -> Result<ProductResponse, CommandError> {
+    let keep_id = parse_uuid(&keep_id)?;
+    let merge_id = parse_uuid(&merge_id)?;
+
+    let product = ProductService::merge(db.conn(), keep_id, merge_id).await?;
+    Ok(ProductResponse::from(product))
+}
+
+/// Clone a product as a template, named "<name> (copy)"
+///
+/// Settings and tags are copied but not check history. Retailer links are
+/// copied unless `new_url` is given, which becomes the clone's only link.
+#[tauri::command]
+pub async fn clone_product(
+    id: String,
+    new_url: Option<String>,
+    db: State<'_, DbState>,
+) -> Result<ProductResponse, CommandError> {
+    let uuid = parse_uuid(&id)?;
+
+    let product = ProductService::clone(db.conn(), uuid, new_url).await?;
+    Ok(ProductResponse::from(product))
+}
+
+/// Snooze a product's notifications until an RFC 3339 time, or resume them with `null`
+///
+/// Checks keep running while snoozed; only their notifications are suppressed.
+#[tauri::command]
+pub async fn snooze_product(
+    id: String,
+    until: Option<String>,
+    db: State<'_, DbState>,
+) -> Result<ProductResponse, CommandError> {
+    let uuid = parse_uuid(&id)?;
+    let until = until.as_deref().map(parse_timestamp).transpose()?;
+
+    let product = ProductService::snooze_notifications(db.conn(), uuid, until).await?;
+    Ok(ProductResponse::from(product))
+}
+
+/// Archive a product: hidden from the list and bulk checks, with its history kept
+#[tauri::command]
+pub async fn archive_product(
+    id: String,
+    db: State<'_, DbState>,
+) -> Result<ProductResponse, CommandError> {
+    let uuid = parse_uuid(&id)?;
+    let product = ProductService::archive(db.conn(), uuid).await?;
+    Ok(ProductResponse::from(product))
+}
+
+/// Restore an archived product
+#[tauri::command]
+pub async fn unarchive_product(
+    id: String,
+    db: State<'_, DbState>,
+) -> Result<ProductResponse, CommandError> {
+    let uuid = parse_uuid(&id)?;
+    let product = ProductService::unarchive(db.conn(), uuid).await?;
+    Ok(ProductResponse::from(product))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::repositories::TrendDirection;
     use chrono::Utc;
     use uuid::Uuid;
 
@@ -170,6 +399,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -194,6 +433,16 @@ mod tests {
             notes: Some("Some notes".to_string()),
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -220,6 +469,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -243,6 +502,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -252,6 +521,162 @@ mod tests {
 
         assert!(json.contains("JSON Test"));
         assert!(json.contains(&id.to_string()));
+        assert!(!json.contains("price_extremes"));
+        assert!(!json.contains("restock_stats"));
+    }
+
+    #[test]
+    fn test_product_response_with_price_extremes() {
+        use crate::domain::repositories::PricePoint;
+
+        let now = Utc::now();
+        let model = ProductModel {
+            id: Uuid::new_v4(),
+            name: "Extremes".to_string(),
+            url: None,
+            description: None,
+            notes: None,
+            currency: None,
+            sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let extremes = PriceExtremes {
+            lowest: Some(PricePoint {
+                price_minor_units: 999,
+                price_currency: Some("USD".to_string()),
+                checked_at: now,
+            }),
+            highest: None,
+            first_checked_at: Some(now),
+        };
+
+        let response = ProductResponse::from(model).with_price_extremes(extremes);
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains("\"price_extremes\":{"));
+        assert!(json.contains("\"price_minor_units\":999"));
+        assert!(json.contains("\"highest\":null"));
+    }
+
+    #[test]
+    fn test_product_response_with_restock_stats() {
+        let now = Utc::now();
+        let model = ProductModel {
+            id: Uuid::new_v4(),
+            name: "Restocks".to_string(),
+            url: None,
+            description: None,
+            notes: None,
+            currency: None,
+            sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let stats = RestockStats {
+            since: now,
+            restock_count: 3,
+            average_restock_interval_secs: Some(86400),
+        };
+
+        let response = ProductResponse::from(model).with_restock_stats(stats);
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains("\"restock_stats\":{"));
+        assert!(json.contains("\"restock_count\":3"));
+        assert!(json.contains("\"average_restock_interval_secs\":86400"));
+    }
+
+    #[test]
+    fn test_product_response_with_price_trend() {
+        let now = Utc::now();
+        let model = ProductModel {
+            id: Uuid::new_v4(),
+            name: "Trending".to_string(),
+            url: None,
+            description: None,
+            notes: None,
+            currency: None,
+            sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let trend = PriceTrend {
+            direction: TrendDirection::Falling,
+            change_percent: -12.5,
+            sample_count: 8,
+            days: 30,
+        };
+
+        let response = ProductResponse::from(model).with_price_trend(trend);
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains("\"price_trend\":{"));
+        assert!(json.contains("\"direction\":\"falling\""));
+        assert!(json.contains("\"change_percent\":-12.5"));
+    }
+
+    #[test]
+    fn test_product_response_with_first_in_stock_at() {
+        let now = Utc::now();
+        let model = ProductModel {
+            id: Uuid::new_v4(),
+            name: "Drop".to_string(),
+            url: None,
+            description: None,
+            notes: None,
+            currency: None,
+            sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let never = ProductResponse::from(model.clone()).with_first_in_stock_at(None);
+        let json = serde_json::to_string(&never).unwrap();
+        assert!(!json.contains("first_in_stock_at"));
+
+        let response = ProductResponse::from(model).with_first_in_stock_at(Some(now));
+        assert_eq!(response.first_in_stock_at, Some(now.to_rfc3339()));
     }
 
     #[test]
@@ -302,6 +727,68 @@ mod tests {
         assert!(input.name.is_none());
         assert!(input.description.is_none());
         assert!(input.notes.is_none());
+        assert!(input.check_interval_minutes.is_none());
+    }
+
+    #[test]
+    fn test_update_product_input_check_interval_set_and_clear() {
+        let input: UpdateProductInput =
+            serde_json::from_str(r#"{"check_interval_minutes":15}"#).unwrap();
+        assert_eq!(input.check_interval_minutes, Some(Some(15)));
+
+        let input: UpdateProductInput =
+            serde_json::from_str(r#"{"check_interval_minutes":null}"#).unwrap();
+        assert_eq!(input.check_interval_minutes, Some(None));
+    }
+
+    #[test]
+    fn test_update_product_input_target_price_set_and_clear() {
+        let input: UpdateProductInput = serde_json::from_str(
+            r#"{"target_price_minor_units":4999,"target_price_currency":"USD"}"#,
+        )
+        .unwrap();
+        assert_eq!(input.target_price_minor_units, Some(Some(4999)));
+        assert_eq!(input.target_price_currency, Some(Some("USD".to_string())));
+
+        let input: UpdateProductInput = serde_json::from_str(
+            r#"{"target_price_minor_units":null,"target_price_currency":null}"#,
+        )
+        .unwrap();
+        assert_eq!(input.target_price_minor_units, Some(None));
+        assert_eq!(input.target_price_currency, Some(None));
+    }
+
+    #[test]
+    fn test_update_product_input_is_paused() {
+        let input: UpdateProductInput = serde_json::from_str(r#"{"is_paused":true}"#).unwrap();
+        assert_eq!(input.is_paused, Some(true));
+
+        let input: UpdateProductInput = serde_json::from_str(r#"{"name":"Renamed"}"#).unwrap();
+        assert_eq!(input.is_paused, None);
+    }
+
+    #[test]
+    fn test_update_product_input_track_price() {
+        let input: UpdateProductInput = serde_json::from_str(r#"{"track_price":false}"#).unwrap();
+        assert_eq!(input.track_price, Some(false));
+
+        let input: UpdateProductInput = serde_json::from_str(r#"{"name":"Renamed"}"#).unwrap();
+        assert_eq!(input.track_price, None);
+    }
+
+    #[test]
+    fn test_update_product_input_currency() {
+        let input: UpdateProductInput = serde_json::from_str(r#"{"currency":"GBP"}"#).unwrap();
+        assert_eq!(input.currency, Some(Some("GBP".to_string())));
+        assert_eq!(input.currency_locked, None);
+
+        let input: UpdateProductInput = serde_json::from_str(r#"{"currency":null}"#).unwrap();
+        assert_eq!(input.currency, Some(None));
+
+        let input: UpdateProductInput =
+            serde_json::from_str(r#"{"currency_locked":false}"#).unwrap();
+        assert_eq!(input.currency, None);
+        assert_eq!(input.currency_locked, Some(false));
     }
 
     #[test]
@@ -316,6 +803,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 0,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -350,6 +847,16 @@ mod tests {
             notes: None,
             currency: None,
             sort_order: 5,
+            check_interval_minutes: None,
+            target_price_minor_units: None,
+            target_price_currency: None,
+            is_paused: false,
+            is_archived: false,
+            track_price: true,
+            currency_locked: false,
+            last_notified_check_id: None,
+            last_notified_at: None,
+            notifications_snoozed_until: None,
             created_at: now,
             updated_at: now,
         };
@@ -1,10 +1,13 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager, State};
 
 use crate::core::services::{ExchangeRateService, SettingService, Settings, UpdateSettingsParams};
 use crate::db::DbState;
 use crate::domain::services::{
-    AvailabilityService, DomainSettingService, DomainSettings, UpdateDomainSettingsParams,
+    AvailabilityService, DomainRequestOverrides, DomainSettingService, DomainSettings,
+    HeadlessBrowserStatus, HeadlessService, UpdateDomainSettingsParams,
 };
 use crate::tauri_error::CommandError;
 use crate::TrayState;
@@ -27,10 +30,35 @@ pub struct SettingsResponse {
     pub enable_headless_browser: bool,
     pub allow_manual_verification: bool,
     pub session_cache_duration_days: i32,
+    pub bulk_check_delay_ms: i32,
+    pub headless_profile_reuse: bool,
+    pub http_timeout_secs: i32,
+    pub headless_timeout_secs: i32,
+    pub price_drop_threshold_percent: i32,
+    pub bulk_check_concurrency: i32,
+    pub force_headless_domains: String,
+    pub data_retention_days: i32,
+    pub validate_url_on_create: bool,
+    pub respect_robots_txt: bool,
+    pub product_group_strategy: String,
+    pub headless_browser_path: String,
+    pub rotate_user_agent: bool,
+    pub background_check_active_hours_start: String,
+    pub background_check_active_hours_end: String,
+    pub background_check_days: String,
+    pub insecure_hosts: String,
+    pub max_redirects: i32,
+    pub max_json_ld_block_kb: i32,
+    pub min_plausible_price_percent: i32,
     pub color_palette: String,
     pub display_timezone: String,
     pub date_format: String,
     pub preferred_currency: String,
+    pub notification_webhook_url: String,
+    pub quiet_hours_start: String,
+    pub quiet_hours_end: String,
+    pub notification_mode: String,
+    pub digest_interval: String,
     pub updated_at: String,
 }
 
@@ -49,10 +77,35 @@ impl SettingsResponse {
             enable_headless_browser: domain.enable_headless_browser,
             allow_manual_verification: domain.allow_manual_verification,
             session_cache_duration_days: domain.session_cache_duration_days,
+            bulk_check_delay_ms: domain.bulk_check_delay_ms,
+            headless_profile_reuse: domain.headless_profile_reuse,
+            http_timeout_secs: domain.http_timeout_secs,
+            headless_timeout_secs: domain.headless_timeout_secs,
+            price_drop_threshold_percent: domain.price_drop_threshold_percent,
+            bulk_check_concurrency: domain.bulk_check_concurrency,
+            force_headless_domains: domain.force_headless_domains,
+            data_retention_days: domain.data_retention_days,
+            validate_url_on_create: domain.validate_url_on_create,
+            respect_robots_txt: domain.respect_robots_txt,
+            product_group_strategy: domain.product_group_strategy,
+            headless_browser_path: domain.headless_browser_path,
+            rotate_user_agent: domain.rotate_user_agent,
+            background_check_active_hours_start: domain.background_check_active_hours_start,
+            background_check_active_hours_end: domain.background_check_active_hours_end,
+            background_check_days: domain.background_check_days,
+            insecure_hosts: domain.insecure_hosts,
+            max_redirects: domain.max_redirects,
+            max_json_ld_block_kb: domain.max_json_ld_block_kb,
+            min_plausible_price_percent: domain.min_plausible_price_percent,
             color_palette: settings.color_palette,
             display_timezone: settings.display_timezone,
             date_format: settings.date_format,
             preferred_currency: settings.preferred_currency,
+            notification_webhook_url: settings.notification_webhook_url,
+            quiet_hours_start: settings.quiet_hours_start,
+            quiet_hours_end: settings.quiet_hours_end,
+            notification_mode: settings.notification_mode,
+            digest_interval: settings.digest_interval,
             updated_at: settings.updated_at.to_rfc3339(),
         }
     }
@@ -76,10 +129,35 @@ pub struct CombinedUpdateParams {
     pub enable_headless_browser: Option<bool>,
     pub allow_manual_verification: Option<bool>,
     pub session_cache_duration_days: Option<i32>,
+    pub bulk_check_delay_ms: Option<i32>,
+    pub headless_profile_reuse: Option<bool>,
+    pub http_timeout_secs: Option<i32>,
+    pub headless_timeout_secs: Option<i32>,
+    pub price_drop_threshold_percent: Option<i32>,
+    pub bulk_check_concurrency: Option<i32>,
+    pub force_headless_domains: Option<String>,
+    pub data_retention_days: Option<i32>,
+    pub validate_url_on_create: Option<bool>,
+    pub respect_robots_txt: Option<bool>,
+    pub product_group_strategy: Option<String>,
+    pub headless_browser_path: Option<String>,
+    pub rotate_user_agent: Option<bool>,
+    pub background_check_active_hours_start: Option<String>,
+    pub background_check_active_hours_end: Option<String>,
+    pub background_check_days: Option<String>,
+    pub insecure_hosts: Option<String>,
+    pub max_redirects: Option<i32>,
+    pub max_json_ld_block_kb: Option<i32>,
+    pub min_plausible_price_percent: Option<i32>,
     pub color_palette: Option<String>,
     pub display_timezone: Option<String>,
     pub date_format: Option<String>,
     pub preferred_currency: Option<String>,
+    pub notification_webhook_url: Option<String>,
+    pub quiet_hours_start: Option<String>,
+    pub quiet_hours_end: Option<String>,
+    pub notification_mode: Option<String>,
+    pub digest_interval: Option<String>,
 }
 
 /// Get current settings
@@ -139,6 +217,11 @@ pub async fn update_settings(
         display_timezone: input.display_timezone,
         date_format: input.date_format,
         preferred_currency: input.preferred_currency,
+        notification_webhook_url: input.notification_webhook_url,
+        quiet_hours_start: input.quiet_hours_start,
+        quiet_hours_end: input.quiet_hours_end,
+        notification_mode: input.notification_mode,
+        digest_interval: input.digest_interval,
     };
 
     let domain_params = UpdateDomainSettingsParams {
@@ -147,6 +230,26 @@ pub async fn update_settings(
         enable_headless_browser: input.enable_headless_browser,
         allow_manual_verification: input.allow_manual_verification,
         session_cache_duration_days: input.session_cache_duration_days,
+        bulk_check_delay_ms: input.bulk_check_delay_ms,
+        headless_profile_reuse: input.headless_profile_reuse,
+        http_timeout_secs: input.http_timeout_secs,
+        headless_timeout_secs: input.headless_timeout_secs,
+        price_drop_threshold_percent: input.price_drop_threshold_percent,
+        bulk_check_concurrency: input.bulk_check_concurrency,
+        force_headless_domains: input.force_headless_domains,
+        data_retention_days: input.data_retention_days,
+        validate_url_on_create: input.validate_url_on_create,
+        respect_robots_txt: input.respect_robots_txt,
+        product_group_strategy: input.product_group_strategy,
+        headless_browser_path: input.headless_browser_path,
+        rotate_user_agent: input.rotate_user_agent,
+        background_check_active_hours_start: input.background_check_active_hours_start,
+        background_check_active_hours_end: input.background_check_active_hours_end,
+        background_check_days: input.background_check_days,
+        insecure_hosts: input.insecure_hosts,
+        max_redirects: input.max_redirects,
+        max_json_ld_block_kb: input.max_json_ld_block_kb,
+        min_plausible_price_percent: input.min_plausible_price_percent,
     };
 
     let settings = SettingService::update(db.conn(), core_params).await?;
@@ -167,6 +270,95 @@ pub async fn update_settings(
     Ok(SettingsResponse::from_merged(settings, domain))
 }
 
+/// Export the app settings as JSON, for restoring after a reinstall
+#[tauri::command]
+pub async fn export_settings(db: State<'_, DbState>) -> Result<String, CommandError> {
+    let json = SettingService::export(db.conn()).await?;
+    Ok(json)
+}
+
+/// Import app settings from JSON produced by `export_settings`
+///
+/// Rejects unknown keys and invalid values without applying anything, then
+/// re-applies the tray and autostart settings to match the imported values.
+/// Historical checks are re-normalized if the preferred currency changed.
+#[tauri::command]
+pub async fn import_settings(
+    app: AppHandle,
+    json: String,
+    db: State<'_, DbState>,
+) -> Result<SettingsResponse, CommandError> {
+    let old_currency = SettingService::get(db.conn()).await?.preferred_currency;
+    let settings = SettingService::import(db.conn(), &json).await?;
+    let domain = DomainSettingService::get(db.conn()).await?;
+
+    update_tray_visibility(&app, settings.show_in_tray);
+    #[cfg(desktop)]
+    crate::configure_autostart(&app, settings.launch_at_login);
+
+    if settings.preferred_currency != old_currency {
+        ExchangeRateService::refresh_rates(db.conn(), &settings.preferred_currency).await?;
+        AvailabilityService::renormalize_all_checks(db.conn(), &settings.preferred_currency)
+            .await?;
+    }
+
+    Ok(SettingsResponse::from_merged(settings, domain))
+}
+
+/// Get custom request headers/cookies keyed by domain
+#[tauri::command]
+pub async fn get_domain_request_overrides(
+    db: State<'_, DbState>,
+) -> Result<BTreeMap<String, DomainRequestOverrides>, CommandError> {
+    let overrides = DomainSettingService::get_request_overrides(db.conn()).await?;
+    Ok(overrides)
+}
+
+/// Set (or clear, when empty) the request overrides for a domain
+#[tauri::command]
+pub async fn set_domain_request_overrides(
+    domain: String,
+    overrides: DomainRequestOverrides,
+    db: State<'_, DbState>,
+) -> Result<BTreeMap<String, DomainRequestOverrides>, CommandError> {
+    let all = DomainSettingService::set_request_overrides(db.conn(), &domain, overrides).await?;
+    Ok(all)
+}
+
+/// Get the CSS selector the headless browser waits for, keyed by domain
+#[tauri::command]
+pub async fn get_headless_wait_selectors(
+    db: State<'_, DbState>,
+) -> Result<BTreeMap<String, String>, CommandError> {
+    let selectors = DomainSettingService::get_headless_wait_selectors(db.conn()).await?;
+    Ok(selectors)
+}
+
+/// Set (or clear, when empty) the CSS selector the headless browser waits for
+/// on a domain before reading the page
+#[tauri::command]
+pub async fn set_headless_wait_selector(
+    domain: String,
+    selector: Option<String>,
+    db: State<'_, DbState>,
+) -> Result<BTreeMap<String, String>, CommandError> {
+    let all =
+        DomainSettingService::set_headless_wait_selector(db.conn(), &domain, selector).await?;
+    Ok(all)
+}
+
+/// Report whether Chrome/Chromium was found for the headless browser fallback,
+/// honoring the `headless_browser_path` setting
+#[tauri::command]
+pub async fn get_headless_browser_status(
+    db: State<'_, DbState>,
+) -> Result<HeadlessBrowserStatus, CommandError> {
+    let settings = DomainSettingService::get(db.conn()).await?;
+    Ok(HeadlessService::browser_status(
+        settings.headless_browser_path().as_deref(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,6 +377,11 @@ mod tests {
             display_timezone: "auto".to_string(),
             date_format: "system".to_string(),
             preferred_currency: "AUD".to_string(),
+            notification_webhook_url: String::new(),
+            quiet_hours_start: String::new(),
+            quiet_hours_end: String::new(),
+            notification_mode: "per_event".to_string(),
+            digest_interval: "daily".to_string(),
             updated_at: Utc::now(),
         }
     }
@@ -196,6 +393,26 @@ mod tests {
             enable_headless_browser: true,
             allow_manual_verification: false,
             session_cache_duration_days: 14,
+            bulk_check_delay_ms: 500,
+            headless_profile_reuse: true,
+            http_timeout_secs: 30,
+            headless_timeout_secs: 60,
+            price_drop_threshold_percent: 5,
+            bulk_check_concurrency: 1,
+            force_headless_domains: String::new(),
+            data_retention_days: 0,
+            validate_url_on_create: false,
+            respect_robots_txt: true,
+            product_group_strategy: "first".to_string(),
+            headless_browser_path: String::new(),
+            rotate_user_agent: true,
+            background_check_active_hours_start: String::new(),
+            background_check_active_hours_end: String::new(),
+            background_check_days: String::new(),
+            insecure_hosts: String::new(),
+            max_redirects: 10,
+            max_json_ld_block_kb: 2048,
+            min_plausible_price_percent: 10,
         }
     }
 
@@ -233,6 +450,11 @@ mod tests {
             display_timezone: "America/New_York".to_string(),
             date_format: "MM/DD/YYYY".to_string(),
             preferred_currency: "USD".to_string(),
+            notification_webhook_url: "https://hooks.example.com/abc".to_string(),
+            quiet_hours_start: "22:00".to_string(),
+            quiet_hours_end: "07:00".to_string(),
+            notification_mode: "digest".to_string(),
+            digest_interval: "weekly".to_string(),
             updated_at: Utc::now(),
         };
         let domain = DomainSettings {
@@ -241,6 +463,26 @@ mod tests {
             enable_headless_browser: false,
             allow_manual_verification: true,
             session_cache_duration_days: 30,
+            bulk_check_delay_ms: 1000,
+            headless_profile_reuse: false,
+            http_timeout_secs: 15,
+            headless_timeout_secs: 90,
+            price_drop_threshold_percent: 10,
+            bulk_check_concurrency: 3,
+            force_headless_domains: "shop.example.com".to_string(),
+            data_retention_days: 365,
+            validate_url_on_create: true,
+            respect_robots_txt: false,
+            product_group_strategy: "cheapest_in_stock".to_string(),
+            headless_browser_path: "/opt/chromium/chrome".to_string(),
+            rotate_user_agent: false,
+            background_check_active_hours_start: "09:00".to_string(),
+            background_check_active_hours_end: "17:00".to_string(),
+            background_check_days: "mon,fri".to_string(),
+            insecure_hosts: "nas.local".to_string(),
+            max_redirects: 5,
+            max_json_ld_block_kb: 512,
+            min_plausible_price_percent: 20,
         };
 
         let response = SettingsResponse::from_merged(settings, domain);
@@ -255,10 +497,38 @@ mod tests {
         assert!(response.background_check_enabled);
         assert_eq!(response.background_check_interval_minutes, 30);
         assert!(!response.enable_headless_browser);
+        assert_eq!(response.bulk_check_delay_ms, 1000);
+        assert!(!response.headless_profile_reuse);
+        assert_eq!(response.http_timeout_secs, 15);
+        assert_eq!(response.headless_timeout_secs, 90);
+        assert_eq!(response.price_drop_threshold_percent, 10);
+        assert_eq!(response.bulk_check_concurrency, 3);
+        assert_eq!(response.force_headless_domains, "shop.example.com");
+        assert_eq!(response.data_retention_days, 365);
+        assert!(response.validate_url_on_create);
+        assert!(!response.respect_robots_txt);
+        assert_eq!(response.product_group_strategy, "cheapest_in_stock");
+        assert_eq!(response.headless_browser_path, "/opt/chromium/chrome");
+        assert!(!response.rotate_user_agent);
+        assert_eq!(response.background_check_active_hours_start, "09:00");
+        assert_eq!(response.background_check_active_hours_end, "17:00");
+        assert_eq!(response.background_check_days, "mon,fri");
+        assert_eq!(response.insecure_hosts, "nas.local");
+        assert_eq!(response.max_redirects, 5);
+        assert_eq!(response.max_json_ld_block_kb, 512);
+        assert_eq!(response.min_plausible_price_percent, 20);
         assert_eq!(response.color_palette, "ocean");
         assert_eq!(response.display_timezone, "America/New_York");
         assert_eq!(response.date_format, "MM/DD/YYYY");
         assert_eq!(response.preferred_currency, "USD");
+        assert_eq!(
+            response.notification_webhook_url,
+            "https://hooks.example.com/abc"
+        );
+        assert_eq!(response.quiet_hours_start, "22:00");
+        assert_eq!(response.quiet_hours_end, "07:00");
+        assert_eq!(response.notification_mode, "digest");
+        assert_eq!(response.digest_interval, "weekly");
     }
 
     #[test]
@@ -288,10 +558,23 @@ mod tests {
         assert!(json.contains("\"background_check_enabled\":false"));
         assert!(json.contains("\"background_check_interval_minutes\":60"));
         assert!(json.contains("\"enable_headless_browser\":true"));
+        assert!(json.contains("\"bulk_check_delay_ms\":500"));
+        assert!(json.contains("\"headless_profile_reuse\":true"));
+        assert!(json.contains("\"http_timeout_secs\":30"));
+        assert!(json.contains("\"headless_timeout_secs\":60"));
+        assert!(json.contains("\"price_drop_threshold_percent\":5"));
+        assert!(json.contains("\"bulk_check_concurrency\":1"));
+        assert!(json.contains("\"force_headless_domains\":\"\""));
+        assert!(json.contains("\"data_retention_days\":0"));
+        assert!(json.contains("\"validate_url_on_create\":false"));
+        assert!(json.contains("\"respect_robots_txt\":true"));
+        assert!(json.contains("\"min_plausible_price_percent\":10"));
         assert!(json.contains("\"color_palette\":\"default\""));
         assert!(json.contains("\"display_timezone\":\"auto\""));
         assert!(json.contains("\"date_format\":\"system\""));
         assert!(json.contains("\"preferred_currency\":\"AUD\""));
+        assert!(json.contains("\"notification_mode\":\"per_event\""));
+        assert!(json.contains("\"digest_interval\":\"daily\""));
     }
 
     #[test]
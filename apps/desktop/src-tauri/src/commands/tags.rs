@@ -0,0 +1,106 @@
+use serde::Serialize;
+use tauri::State;
+
+use crate::db::DbState;
+use crate::domain::entities::prelude::TagModel;
+use crate::domain::services::TagService;
+use crate::tauri_error::CommandError;
+use crate::utils::parse_uuid;
+
+/// Response DTO for tags
+#[derive(Debug, Serialize)]
+pub struct TagResponse {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+}
+
+impl From<TagModel> for TagResponse {
+    fn from(model: TagModel) -> Self {
+        Self {
+            id: model.id.to_string(),
+            name: model.name,
+            created_at: model.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Get all tags
+#[tauri::command]
+pub async fn get_tags(db: State<'_, DbState>) -> Result<Vec<TagResponse>, CommandError> {
+    let tags = TagService::get_all(db.conn()).await?;
+    Ok(tags.into_iter().map(TagResponse::from).collect())
+}
+
+/// Get the tags attached to a product
+#[tauri::command]
+pub async fn get_product_tags(
+    product_id: String,
+    db: State<'_, DbState>,
+) -> Result<Vec<TagResponse>, CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+
+    let tags = TagService::get_for_product(db.conn(), uuid).await?;
+    Ok(tags.into_iter().map(TagResponse::from).collect())
+}
+
+/// Attach a tag to a product, creating the tag if needed
+#[tauri::command]
+pub async fn attach_product_tag(
+    product_id: String,
+    tag: String,
+    db: State<'_, DbState>,
+) -> Result<TagResponse, CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+
+    let tag = TagService::attach_to_product(db.conn(), uuid, &tag).await?;
+    Ok(TagResponse::from(tag))
+}
+
+/// Detach a tag from a product
+#[tauri::command]
+pub async fn detach_product_tag(
+    product_id: String,
+    tag: String,
+    db: State<'_, DbState>,
+) -> Result<(), CommandError> {
+    let uuid = parse_uuid(&product_id)?;
+
+    TagService::detach_from_product(db.conn(), uuid, &tag).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_tag_response_from_model() {
+        let id = Uuid::new_v4();
+        let model = TagModel {
+            id,
+            name: "kitchen".to_string(),
+            created_at: Utc::now(),
+        };
+
+        let response = TagResponse::from(model);
+
+        assert_eq!(response.id, id.to_string());
+        assert_eq!(response.name, "kitchen");
+        assert!(!response.created_at.is_empty());
+    }
+
+    #[test]
+    fn test_tag_response_serializes_to_json() {
+        let model = TagModel {
+            id: Uuid::new_v4(),
+            name: "gpu".to_string(),
+            created_at: Utc::now(),
+        };
+
+        let json = serde_json::to_string(&TagResponse::from(model)).unwrap();
+        assert!(json.contains("\"name\":\"gpu\""));
+    }
+}
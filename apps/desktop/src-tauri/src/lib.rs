@@ -23,7 +23,7 @@ pub struct TrayState(pub Mutex<Option<TrayIcon<tauri::Wry>>>);
 
 /// Sync the OS autostart setting with the user's preference
 #[cfg(desktop)]
-fn configure_autostart(app: &tauri::App, launch_at_login: bool) {
+pub(crate) fn configure_autostart(app: &tauri::AppHandle, launch_at_login: bool) {
     use tauri_plugin_autostart::ManagerExt;
     let autostart_manager = app.autolaunch();
     let is_enabled = autostart_manager.is_enabled().unwrap_or(false);
@@ -107,7 +107,7 @@ pub fn run() {
             }
 
             #[cfg(desktop)]
-            configure_autostart(app, settings.launch_at_login);
+            configure_autostart(app.handle(), settings.launch_at_login);
 
             // Refresh exchange rates if stale (>24h) or missing
             let preferred = settings.preferred_currency.clone();
@@ -145,27 +145,74 @@ pub fn run() {
             // === DOMAIN ===
             commands::get_products,
             commands::get_product,
+            commands::get_products_by_status,
+            commands::search_products,
             commands::create_product,
+            commands::import_urls,
             commands::update_product,
             commands::delete_product,
+            commands::merge_products,
+            commands::clone_product,
+            commands::snooze_product,
+            commands::archive_product,
+            commands::unarchive_product,
             commands::reorder_products,
             commands::add_product_retailer,
             commands::get_product_retailers,
+            commands::get_retailer_comparison,
             commands::reorder_product_retailers,
             commands::remove_product_retailer,
+            commands::set_product_retailer_variant_selector,
+            commands::normalize_product_url,
+            commands::get_tags,
+            commands::get_product_tags,
+            commands::attach_product_tag,
+            commands::detach_product_tag,
             commands::check_availability,
             commands::get_latest_availability,
             commands::get_availability_history,
+            commands::get_price_history,
+            commands::get_price_extremes,
+            commands::format_price,
+            commands::get_status_transitions,
+            commands::get_restock_stats,
+            commands::get_price_trend,
+            commands::compare_products,
+            commands::get_portfolio_summary,
+            commands::get_changes_since,
+            commands::get_recent_deals,
+            commands::get_scraper_health,
+            commands::prune_check_history,
+            commands::repair_orphaned_checks,
+            commands::test_scrape_url,
+            commands::fetch_raw_html,
             commands::check_all_availability,
+            commands::get_bulk_check_history,
+            commands::check_all_by_tag,
+            commands::check_selected,
+            commands::recheck_failed,
+            commands::export_products_csv,
+            commands::export_products_json,
+            commands::import_products_json,
             // === INFRASTRUCTURE ===
             commands::get_settings,
             commands::update_settings,
+            commands::export_settings,
+            commands::import_settings,
+            commands::get_domain_request_overrides,
+            commands::set_domain_request_overrides,
+            commands::get_headless_wait_selectors,
+            commands::set_headless_wait_selector,
+            commands::get_headless_browser_status,
             commands::refresh_exchange_rates,
             commands::get_exchange_rates,
             commands::set_manual_exchange_rate,
             commands::delete_exchange_rate,
+            commands::convert_prices,
             commands::are_notifications_enabled,
             commands::send_notification,
+            commands::send_test_webhook,
+            commands::send_test_notification,
             commands::close_splashscreen,
             commands::check_for_update,
             commands::download_and_install_update,
@@ -5,8 +5,7 @@
 //! - Desktop notification composition
 //! - Settings integration for headless browser toggle
 
-use std::time::Duration;
-
+use chrono::{DateTime, Utc};
 use sea_orm::DatabaseConnection;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
@@ -14,15 +13,13 @@ use uuid::Uuid;
 
 use crate::core::services::{ExchangeRateService, SettingService, SettingsCache};
 use crate::core::AppError;
-use crate::domain::repositories::ProductRetailerRepository;
+use crate::domain::entities::prelude::{AvailabilityStatus, ProductModel, ProductRetailerModel};
+use crate::domain::repositories::{AvailabilityCheckRepository, ProductRetailerRepository};
 use crate::domain::services::{
     AvailabilityService, BulkCheckSummary, CheckConfig, DomainSettingService, DomainSettingsCache,
-    NotificationData, ProductService,
+    NotificationData, ProductService, TagService,
 };
 
-/// Delay in milliseconds between consecutive product checks during bulk operations.
-const RATE_LIMIT_BETWEEN_CHECKS_MS: u64 = 500;
-
 /// Event emitted for each product check during bulk operations
 #[derive(Debug, Clone, Serialize)]
 pub struct BulkCheckProgressEvent {
@@ -41,6 +38,38 @@ pub struct TauriBulkCheckResult {
     pub notification: Option<NotificationData>,
 }
 
+/// Which products a bulk check covers
+#[derive(Clone, Copy)]
+enum BulkCheckScope<'a> {
+    /// Every unpaused product
+    All,
+    /// Unpaused products whose check interval has elapsed by the given time
+    Due(i32, DateTime<Utc>),
+    /// Unpaused products carrying the tag
+    Tag(&'a str),
+    /// An explicit list of product IDs, paused or not
+    Selected(&'a [String]),
+}
+
+/// A single product-retailer link or legacy product to check in a bulk operation
+#[derive(Clone, Copy)]
+enum BulkCheckTarget<'a> {
+    /// A product-retailer link with its product
+    Retailer(&'a ProductModel, &'a ProductRetailerModel),
+    /// A legacy product without retailer links (deprecated url fallback)
+    Legacy(&'a ProductModel),
+}
+
+impl BulkCheckTarget<'_> {
+    /// URL the check fetches, used to keep same-host checks apart
+    fn url(&self) -> Option<&str> {
+        match self {
+            Self::Retailer(_, pr) => Some(&pr.url),
+            Self::Legacy(product) => product.url.as_deref(),
+        }
+    }
+}
+
 /// Tauri-aware availability service
 pub struct TauriAvailabilityService;
 
@@ -56,15 +85,16 @@ impl TauriAvailabilityService {
         let settings = SettingService::get(conn).await?;
         let domain_settings = DomainSettingService::get(conn).await?;
         let config = CheckConfig {
-            enable_headless: domain_settings.enable_headless_browser,
-            allow_manual_verification: domain_settings.allow_manual_verification,
-            session_cache_duration_days: domain_settings.session_cache_duration_days,
+            fetch: domain_settings.fetch_options(),
             preferred_currency: &settings.preferred_currency,
+            price_drop_threshold_percent: domain_settings.price_drop_threshold_percent,
+            min_plausible_price_percent: domain_settings.min_plausible_price_percent,
         };
         AvailabilityService::check_product_with_notification(
             conn,
             product_id,
             settings.enable_notifications,
+            settings.is_quiet_time(Utc::now()),
             &config,
         )
         .await
@@ -74,18 +104,99 @@ impl TauriAvailabilityService {
     ///
     /// Iterates all product-retailer links and checks each one. Also handles
     /// legacy products that have no retailer links (using their deprecated url).
+    /// Paused products are skipped; they can still be checked individually.
     /// Emits "availability:check-progress" events for each check.
     /// Uses settings caching to avoid repeated database reads during bulk processing.
     pub async fn check_all_products_with_notification(
         conn: &DatabaseConnection,
         app: &AppHandle,
     ) -> Result<TauriBulkCheckResult, AppError> {
+        let result = Self::check_products_with_notification(conn, app, BulkCheckScope::All).await?;
+
+        // History is informational, so a failed write never fails the check itself
+        if let Err(e) =
+            AvailabilityService::record_bulk_check_run(conn, &result.summary, Utc::now()).await
+        {
+            log::warn!("Failed to record bulk check run: {}", e);
+        }
+
+        Ok(result)
+    }
+
+    /// Check only the products carrying the given tag, with progress events and
+    /// bulk notification. Paused products are skipped as in a full bulk check.
+    pub async fn check_tagged_products_with_notification(
+        conn: &DatabaseConnection,
+        app: &AppHandle,
+        tag: &str,
+    ) -> Result<TauriBulkCheckResult, AppError> {
+        Self::check_products_with_notification(conn, app, BulkCheckScope::Tag(tag)).await
+    }
+
+    /// Check only the products that are due, for the background checker.
+    ///
+    /// A product is due when its own `check_interval_minutes` (or the global
+    /// interval when unset) has elapsed by `due_by`. See
+    /// [`AvailabilityService::is_check_due`].
+    pub async fn check_due_products_with_notification(
+        conn: &DatabaseConnection,
+        app: &AppHandle,
+        global_interval_minutes: i32,
+        due_by: DateTime<Utc>,
+    ) -> Result<TauriBulkCheckResult, AppError> {
+        Self::check_products_with_notification(
+            conn,
+            app,
+            BulkCheckScope::Due(global_interval_minutes, due_by),
+        )
+        .await
+    }
+
+    /// Check an explicit selection of products, e.g. "recheck selected".
+    ///
+    /// Uses the same rate-limited loop and progress events as a full bulk check.
+    /// IDs that are malformed or don't match a product are reported as failed
+    /// results rather than aborting the batch.
+    pub async fn check_selected_products_with_notification(
+        conn: &DatabaseConnection,
+        app: &AppHandle,
+        product_ids: &[String],
+    ) -> Result<TauriBulkCheckResult, AppError> {
+        Self::check_products_with_notification(conn, app, BulkCheckScope::Selected(product_ids))
+            .await
+    }
+
+    /// Re-check only the products whose latest check failed, e.g. to retry
+    /// transient errors from the last bulk check. Paused products are left out.
+    pub async fn check_failed_products_with_notification(
+        conn: &DatabaseConnection,
+        app: &AppHandle,
+    ) -> Result<TauriBulkCheckResult, AppError> {
+        let product_ids: Vec<String> =
+            AvailabilityCheckRepository::find_products_with_failed_latest_check(conn)
+                .await?
+                .into_iter()
+                .map(|id| id.to_string())
+                .collect();
+        Self::check_selected_products_with_notification(conn, app, &product_ids).await
+    }
+
+    /// Shared bulk check loop over the products in `scope`.
+    async fn check_products_with_notification(
+        conn: &DatabaseConnection,
+        app: &AppHandle,
+        scope: BulkCheckScope<'_>,
+    ) -> Result<TauriBulkCheckResult, AppError> {
+        // Resolve the tag up front so an unknown tag fails before any setup work
+        let tagged_ids = match scope {
+            BulkCheckScope::Tag(tag) => Some(TagService::get_product_ids_by_tag(conn, tag).await?),
+            _ => None,
+        };
+
         // Load settings once and cache for the entire bulk operation
         let settings_cache = SettingsCache::load(conn).await?;
         let domain_cache = DomainSettingsCache::load(conn).await?;
-        let enable_headless = domain_cache.enable_headless_browser();
-        let allow_manual_verification = domain_cache.allow_manual_verification();
-        let session_cache_duration = domain_cache.session_cache_duration_days();
+        let bulk_check_concurrency = domain_cache.bulk_check_concurrency();
 
         // Refresh exchange rates if stale before bulk check
         let preferred = settings_cache.preferred_currency().to_string();
@@ -94,19 +205,72 @@ impl TauriAvailabilityService {
         }
 
         let config = CheckConfig {
-            enable_headless,
-            allow_manual_verification,
-            session_cache_duration_days: session_cache_duration,
+            fetch: domain_cache.fetch_options(),
             preferred_currency: &preferred,
+            price_drop_threshold_percent: domain_cache.price_drop_threshold_percent(),
+            min_plausible_price_percent: domain_cache.min_plausible_price_percent(),
         };
 
-        // Gather all product-retailer links (with their associated products)
-        let product_retailers = ProductRetailerRepository::find_all_with_product(conn).await?;
+        let (mut product_retailers, mut legacy_products, invalid) =
+            if let BulkCheckScope::Selected(product_ids) = scope {
+                let targets = AvailabilityService::find_selected_targets(conn, product_ids).await?;
+                (
+                    targets.product_retailers,
+                    targets.legacy_products,
+                    targets.invalid,
+                )
+            } else {
+                // Gather all product-retailer links (with their associated products),
+                // plus legacy products with no retailer links (deprecated url path)
+                (
+                    ProductRetailerRepository::find_all_with_product(conn).await?,
+                    ProductService::get_all_without_retailers(conn).await?,
+                    Vec::new(),
+                )
+            };
 
-        // Also find legacy products with no retailer links (deprecated url path)
-        let legacy_products = ProductService::get_all_without_retailers(conn).await?;
+        // Skip products whose check interval has not elapsed yet
+        if let BulkCheckScope::Due(global_interval_minutes, due_by) = scope {
+            let products = product_retailers
+                .iter()
+                .filter_map(|(_, p)| p.as_ref())
+                .chain(legacy_products.iter())
+                .collect::<Vec<_>>();
+            let due_ids = AvailabilityService::find_due_product_ids(
+                conn,
+                &products,
+                global_interval_minutes,
+                due_by,
+            )
+            .await?;
+
+            AvailabilityService::retain_product_ids(
+                &mut product_retailers,
+                &mut legacy_products,
+                &due_ids,
+            );
+        }
 
-        let total = product_retailers.len() + legacy_products.len();
+        if let Some(tagged_ids) = &tagged_ids {
+            AvailabilityService::retain_product_ids(
+                &mut product_retailers,
+                &mut legacy_products,
+                tagged_ids,
+            );
+        }
+
+        // Links whose product no longer exists have nothing to check
+        let targets: Vec<BulkCheckTarget> = product_retailers
+            .iter()
+            .filter_map(|(pr, product)| {
+                product
+                    .as_ref()
+                    .map(|product| BulkCheckTarget::Retailer(product, pr))
+            })
+            .chain(legacy_products.iter().map(BulkCheckTarget::Legacy))
+            .collect();
+
+        let total = invalid.len() + targets.len();
 
         if total == 0 {
             return Ok(TauriBulkCheckResult {
@@ -116,74 +280,73 @@ impl TauriAvailabilityService {
                     failed: 0,
                     back_in_stock_count: 0,
                     price_drop_count: 0,
+                    sold_out_count: 0,
                     results: vec![],
                 },
                 notification: None,
             });
         }
 
-        let mut paired_results = Vec::with_capacity(total);
-        let mut current = 0;
-
-        // Check each product-retailer link
-        for (pr, maybe_product) in &product_retailers {
-            if current > 0 {
-                tokio::time::sleep(Duration::from_millis(RATE_LIMIT_BETWEEN_CHECKS_MS)).await;
-            }
-
-            let product = match maybe_product {
-                Some(p) => p,
-                None => {
-                    current += 1;
-                    continue;
-                }
-            };
-
-            let (bulk_result, processing_result) =
-                AvailabilityService::check_single_product_retailer(conn, product, pr, &config)
-                    .await;
-
+        let emit_progress = |product_id: String, status: AvailabilityStatus, current: usize| {
             let _ = app.emit(
                 "availability:check-progress",
                 &BulkCheckProgressEvent {
-                    product_id: product.id.to_string(),
-                    status: bulk_result.status.as_str().to_string(),
-                    current: current + 1,
+                    product_id,
+                    status: status.as_str().to_string(),
+                    current,
                     total,
                 },
             );
+        };
 
-            paired_results.push((bulk_result, processing_result));
-            current += 1;
-        }
-
-        // Check legacy products without retailer links (deprecated url fallback)
-        for product in &legacy_products {
-            if current > 0 {
-                tokio::time::sleep(Duration::from_millis(RATE_LIMIT_BETWEEN_CHECKS_MS)).await;
-            }
-
-            let (bulk_result, processing_result) =
-                AvailabilityService::check_single_product(conn, product, &config).await;
-
-            let _ = app.emit(
-                "availability:check-progress",
-                &BulkCheckProgressEvent {
-                    product_id: product.id.to_string(),
-                    status: bulk_result.status.as_str().to_string(),
-                    current: current + 1,
-                    total,
-                },
+        // Check product-retailer links and legacy products, same-host checks serialized
+        let config = &config;
+        let mut paired_results = AvailabilityService::run_bulk_checks(
+            targets,
+            bulk_check_concurrency,
+            |target| target.url().and_then(AvailabilityService::bulk_check_host),
+            |target| async move {
+                match target {
+                    BulkCheckTarget::Retailer(product, pr) => {
+                        AvailabilityService::check_single_product_retailer(
+                            conn, product, pr, config,
+                        )
+                        .await
+                    }
+                    BulkCheckTarget::Legacy(product) => {
+                        AvailabilityService::check_single_product(conn, product, config).await
+                    }
+                }
+            },
+            |current, (bulk_result, _)| {
+                emit_progress(
+                    bulk_result.product_id.clone(),
+                    bulk_result.status.clone(),
+                    current,
+                )
+            },
+        )
+        .await;
+
+        // Report selected IDs that couldn't be resolved; they need no network request
+        for (product_id, error_message) in invalid {
+            emit_progress(
+                product_id.clone(),
+                AvailabilityStatus::Unknown,
+                paired_results.len() + 1,
             );
 
-            paired_results.push((bulk_result, processing_result));
-            current += 1;
+            paired_results.push(AvailabilityService::build_invalid_selection_result(
+                &product_id,
+                error_message,
+            ));
         }
 
         let summary = AvailabilityService::build_summary_from_results(total, paired_results);
 
         let notification = AvailabilityService::build_bulk_notification_with_settings(
             settings_cache.enable_notifications(),
+            settings_cache.is_quiet_time(Utc::now()),
             &summary,
         );
 
@@ -3,26 +3,51 @@
 //! This module provides Tauri-aware wrappers around the domain services,
 //! adding event emission and notification handling that requires Tauri's AppHandle.
 
+use sea_orm::DatabaseConnection;
 use tauri::AppHandle;
 use tauri_plugin_notification::NotificationExt;
 
-use crate::domain::services::NotificationData;
+use crate::core::services::SettingService;
+use crate::domain::services::{NotificationData, WebhookPayload, WebhookService};
 
 mod availability_service;
 
 pub use availability_service::TauriAvailabilityService;
 
 /// Send a desktop notification via the Tauri notification plugin.
-pub fn send_desktop_notification(app: &AppHandle, notification: &NotificationData) {
-    if let Err(e) = app
+///
+/// A product notification carries its `product_id` and `action_url` as extras,
+/// which the frontend reads from the plugin's action event to open the product.
+/// Returns whether the OS accepted the notification.
+pub fn send_desktop_notification(app: &AppHandle, notification: &NotificationData) -> bool {
+    let mut builder = app
         .notification()
         .builder()
         .title(&notification.title)
-        .body(&notification.body)
-        .show()
-    {
+        .body(&notification.body);
+    if let Some(product_id) = &notification.product_id {
+        builder = builder.extra("product_id", product_id);
+    }
+    if let Some(action_url) = &notification.action_url {
+        builder = builder.extra("action_url", action_url);
+    }
+
+    if let Err(e) = builder.show() {
         log::warn!("Failed to send notification: {}", e);
+        false
     } else {
         log::info!("Sent notification: {}", notification.title);
+        true
+    }
+}
+
+/// Push a notification to the configured webhook, if any.
+///
+/// Failures are logged rather than returned so they never fail the check that
+/// triggered the notification.
+pub async fn send_webhook_notification(conn: &DatabaseConnection, payload: &WebhookPayload) {
+    match SettingService::get(conn).await {
+        Ok(settings) => WebhookService::notify(&settings.notification_webhook_url, payload).await,
+        Err(e) => log::warn!("Failed to load webhook settings: {}", e),
     }
 }
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use product_stalker_core::AppError;
 use uuid::Uuid;
 
@@ -6,6 +7,13 @@ pub fn parse_uuid(id: &str) -> Result<Uuid, AppError> {
     Uuid::parse_str(id).map_err(|_| AppError::Validation(format!("Invalid UUID: {}", id)))
 }
 
+/// Parse an RFC 3339 timestamp into UTC, returning a validation error if invalid
+pub fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, AppError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| AppError::Validation(format!("Invalid timestamp: {}", value)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,4 +44,16 @@ mod tests {
         let result = parse_uuid("");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_timestamp_converts_to_utc() {
+        let result = parse_timestamp("2024-01-01T10:00:00+10:00").unwrap();
+        assert_eq!(result.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_timestamp_invalid() {
+        let result = parse_timestamp("yesterday");
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
 }
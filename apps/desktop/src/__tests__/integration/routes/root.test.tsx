@@ -48,6 +48,11 @@ vi.mock("@/modules/settings/hooks/useSettings", () => ({
 	}),
 }));
 
+// Mock the notification click listener (needs the router and Tauri runtime)
+vi.mock("@/modules/shared/hooks/useNotificationDeepLink", () => ({
+	useNotificationDeepLink: vi.fn(),
+}));
+
 // Mock sonner
 vi.mock("@/components/ui/sonner", () => ({
 	Toaster: () => <div data-testid="toaster" />,
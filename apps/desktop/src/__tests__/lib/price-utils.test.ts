@@ -33,6 +33,8 @@ function createCheck(
 		status: "in_stock",
 		raw_availability: null,
 		error_message: null,
+		error_kind: null,
+		confidence: null,
 		checked_at: new Date().toISOString(),
 		price_minor_units: 9999,
 		price_currency: "USD",
@@ -44,6 +46,10 @@ function createCheck(
 		lowest_price_minor_units: null,
 		lowest_price_currency: null,
 		lowest_currency_exponent: null,
+		lowest_normalized_price_minor_units: null,
+		lowest_normalized_currency: null,
+		lowest_normalized_currency_exponent: null,
+		lowest_price_normalization_skipped: false,
 		normalized_price_minor_units: null,
 		normalized_currency: null,
 		normalized_currency_exponent: null,
@@ -429,6 +435,8 @@ function createRetailer(
 		url: "https://www.amazon.com/dp/B123",
 		label: null,
 		sort_order: 0,
+		currency: null,
+		variant_selector: null,
 		created_at: new Date().toISOString(),
 		...overrides,
 	};
@@ -464,6 +472,26 @@ describe("getDisplayPrice", () => {
 		expect(result.exponent).toBe(2);
 	});
 
+	it("should prefer the lowest price converted to the preferred currency", () => {
+		const check = createCheck({
+			price_minor_units: 9999,
+			price_currency: "USD",
+			currency_exponent: 2,
+			lowest_price_minor_units: 5000,
+			lowest_price_currency: "JPY",
+			lowest_currency_exponent: 0,
+			lowest_normalized_price_minor_units: 5150,
+			lowest_normalized_currency: "AUD",
+			lowest_normalized_currency_exponent: 2,
+		});
+
+		const result = getDisplayPrice(check);
+
+		expect(result.price).toBe(5150);
+		expect(result.currency).toBe("AUD");
+		expect(result.exponent).toBe(2);
+	});
+
 	it("should fall back to single-check price fields", () => {
 		const check = createCheck({
 			price_minor_units: 9999,
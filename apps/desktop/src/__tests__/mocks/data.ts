@@ -23,6 +23,14 @@ export function createMockProduct(
 		notes: null,
 		currency: null,
 		sort_order: 0,
+		check_interval_minutes: null,
+		target_price_minor_units: null,
+		target_price_currency: null,
+		is_paused: false,
+		is_archived: false,
+		track_price: true,
+		currency_locked: false,
+		notifications_snoozed_until: null,
 		created_at: now,
 		updated_at: now,
 		...overrides,
@@ -55,11 +63,35 @@ export function createMockSettings(
 		sidebar_expanded: true,
 		background_check_enabled: false,
 		background_check_interval_minutes: 60,
+		background_check_active_hours_start: "",
+		background_check_active_hours_end: "",
+		background_check_days: "",
+		insecure_hosts: "",
+		max_redirects: 10,
+		max_json_ld_block_kb: 2048,
 		enable_headless_browser: true,
+		headless_profile_reuse: true,
+		http_timeout_secs: 30,
+		headless_timeout_secs: 60,
+		price_drop_threshold_percent: 5,
+		bulk_check_concurrency: 1,
+		force_headless_domains: "",
+		data_retention_days: 0,
+		validate_url_on_create: false,
+		respect_robots_txt: true,
+		product_group_strategy: "first",
+		headless_browser_path: "",
+		rotate_user_agent: true,
+		min_plausible_price_percent: 10,
 		color_palette: "default",
 		display_timezone: "auto",
 		date_format: "system",
 		preferred_currency: "AUD",
+		notification_webhook_url: "",
+		quiet_hours_start: "",
+		quiet_hours_end: "",
+		notification_mode: "per_event",
+		digest_interval: "daily",
 		updated_at: new Date().toISOString(),
 		...overrides,
 	};
@@ -113,6 +145,8 @@ export function createMockAvailabilityCheck(
 		status: "in_stock",
 		raw_availability: null,
 		error_message: null,
+		error_kind: null,
+		confidence: null,
 		checked_at: new Date().toISOString(),
 		price_minor_units: null,
 		price_currency: null,
@@ -124,6 +158,10 @@ export function createMockAvailabilityCheck(
 		lowest_price_minor_units: null,
 		lowest_price_currency: null,
 		lowest_currency_exponent: null,
+		lowest_normalized_price_minor_units: null,
+		lowest_normalized_currency: null,
+		lowest_normalized_currency_exponent: null,
+		lowest_price_normalization_skipped: false,
 		normalized_price_minor_units: null,
 		normalized_currency: null,
 		normalized_currency_exponent: null,
@@ -143,6 +181,7 @@ export function createMockBulkCheckSummary(
 		failed: 0,
 		back_in_stock_count: 0,
 		price_drop_count: 0,
+		sold_out_count: 0,
 		results: [],
 		...overrides,
 	};
@@ -17,6 +17,10 @@ function createMockProduct(
 		notes: null,
 		currency: null,
 		sort_order: 0,
+		check_interval_minutes: null,
+		target_price_minor_units: null,
+		target_price_currency: null,
+		is_paused: false,
 		created_at: "2024-01-01T00:00:00Z",
 		updated_at: "2024-01-15T00:00:00Z",
 		...overrides,
@@ -33,6 +37,8 @@ function createMockCheck(
 		status: "in_stock",
 		raw_availability: null,
 		error_message: null,
+		error_kind: null,
+		confidence: null,
 		checked_at: "2024-01-15T10:00:00Z",
 		price_minor_units: 9999,
 		price_currency: "USD",
@@ -44,6 +50,10 @@ function createMockCheck(
 		lowest_price_minor_units: null,
 		lowest_price_currency: null,
 		lowest_currency_exponent: null,
+		lowest_normalized_price_minor_units: null,
+		lowest_normalized_currency: null,
+		lowest_normalized_currency_exponent: null,
+		lowest_price_normalization_skipped: false,
 		normalized_price_minor_units: null,
 		normalized_currency: null,
 		normalized_currency_exponent: null,
@@ -18,6 +18,8 @@ function createRetailer(
 		url: "https://amazon.com/dp/B123",
 		label: null,
 		sort_order: 0,
+		currency: null,
+		variant_selector: null,
 		created_at: new Date().toISOString(),
 		...overrides,
 	};
@@ -564,6 +564,8 @@ describe("ProductsView", () => {
 				url: "https://example.com/product",
 				label: null,
 				sort_order: 0,
+				currency: null,
+				variant_selector: null,
 				created_at: new Date().toISOString(),
 			};
 			mockInvokeMultiple({
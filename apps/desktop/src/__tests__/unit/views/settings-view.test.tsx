@@ -470,6 +470,24 @@ describe("SettingsView", () => {
 				).toBeInTheDocument();
 			});
 		});
+
+		it("should render remember site sessions switch", async () => {
+			const settings = createMockSettings({ headless_profile_reuse: false });
+			mockInvokeMultiple({
+				[COMMANDS.GET_SETTINGS]: settings,
+				[COMMANDS.GET_CURRENT_VERSION]: "1.0.0",
+				[COMMANDS.UPDATE_SETTINGS]: settings,
+			});
+
+			render(<SettingsView />);
+
+			await waitFor(() => {
+				expect(screen.getByText("Remember site sessions")).toBeInTheDocument();
+			});
+
+			const switchEl = document.getElementById("headless-profile-reuse");
+			expect(switchEl).toHaveAttribute("aria-checked", "false");
+		});
 	});
 
 	describe("interface settings", () => {
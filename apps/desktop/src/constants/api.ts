@@ -2,26 +2,73 @@ export const COMMANDS = {
 	// === DOMAIN ===
 	GET_PRODUCTS: "get_products",
 	GET_PRODUCT: "get_product",
+	GET_PRODUCTS_BY_STATUS: "get_products_by_status",
+	SEARCH_PRODUCTS: "search_products",
 	CREATE_PRODUCT: "create_product",
 	UPDATE_PRODUCT: "update_product",
 	DELETE_PRODUCT: "delete_product",
+	MERGE_PRODUCTS: "merge_products",
+	CLONE_PRODUCT: "clone_product",
+	IMPORT_URLS: "import_urls",
+	SNOOZE_PRODUCT: "snooze_product",
+	ARCHIVE_PRODUCT: "archive_product",
+	UNARCHIVE_PRODUCT: "unarchive_product",
 	REORDER_PRODUCTS: "reorder_products",
 	ADD_PRODUCT_RETAILER: "add_product_retailer",
 	GET_PRODUCT_RETAILERS: "get_product_retailers",
+	GET_RETAILER_COMPARISON: "get_retailer_comparison",
 	REORDER_PRODUCT_RETAILERS: "reorder_product_retailers",
 	REMOVE_PRODUCT_RETAILER: "remove_product_retailer",
+	SET_PRODUCT_RETAILER_VARIANT_SELECTOR: "set_product_retailer_variant_selector",
+	NORMALIZE_PRODUCT_URL: "normalize_product_url",
+	GET_TAGS: "get_tags",
+	GET_PRODUCT_TAGS: "get_product_tags",
+	ATTACH_PRODUCT_TAG: "attach_product_tag",
+	DETACH_PRODUCT_TAG: "detach_product_tag",
 	CHECK_AVAILABILITY: "check_availability",
 	GET_LATEST_AVAILABILITY: "get_latest_availability",
 	GET_AVAILABILITY_HISTORY: "get_availability_history",
+	GET_PRICE_HISTORY: "get_price_history",
+	GET_PRICE_EXTREMES: "get_price_extremes",
+	FORMAT_PRICE: "format_price",
+	GET_STATUS_TRANSITIONS: "get_status_transitions",
+	GET_RESTOCK_STATS: "get_restock_stats",
+	GET_PRICE_TREND: "get_price_trend",
+	COMPARE_PRODUCTS: "compare_products",
+	GET_PORTFOLIO_SUMMARY: "get_portfolio_summary",
+	GET_CHANGES_SINCE: "get_changes_since",
+	GET_RECENT_DEALS: "get_recent_deals",
+	GET_SCRAPER_HEALTH: "get_scraper_health",
 	CHECK_ALL_AVAILABILITY: "check_all_availability",
+	GET_BULK_CHECK_HISTORY: "get_bulk_check_history",
+	CHECK_ALL_BY_TAG: "check_all_by_tag",
+	CHECK_SELECTED: "check_selected",
+	RECHECK_FAILED: "recheck_failed",
+	PRUNE_CHECK_HISTORY: "prune_check_history",
+	REPAIR_ORPHANED_CHECKS: "repair_orphaned_checks",
+	TEST_SCRAPE_URL: "test_scrape_url",
+	FETCH_RAW_HTML: "fetch_raw_html",
+	EXPORT_PRODUCTS_CSV: "export_products_csv",
+	EXPORT_PRODUCTS_JSON: "export_products_json",
+	IMPORT_PRODUCTS_JSON: "import_products_json",
 	// === INFRASTRUCTURE ===
 	GET_SETTINGS: "get_settings",
 	UPDATE_SETTINGS: "update_settings",
+	EXPORT_SETTINGS: "export_settings",
+	IMPORT_SETTINGS: "import_settings",
+	GET_DOMAIN_REQUEST_OVERRIDES: "get_domain_request_overrides",
+	SET_DOMAIN_REQUEST_OVERRIDES: "set_domain_request_overrides",
+	GET_HEADLESS_WAIT_SELECTORS: "get_headless_wait_selectors",
+	SET_HEADLESS_WAIT_SELECTOR: "set_headless_wait_selector",
+	GET_HEADLESS_BROWSER_STATUS: "get_headless_browser_status",
 	REFRESH_EXCHANGE_RATES: "refresh_exchange_rates",
 	GET_EXCHANGE_RATES: "get_exchange_rates",
 	SET_MANUAL_EXCHANGE_RATE: "set_manual_exchange_rate",
 	DELETE_EXCHANGE_RATE: "delete_exchange_rate",
+	CONVERT_PRICES: "convert_prices",
 	SEND_NOTIFICATION: "send_notification",
+	SEND_TEST_WEBHOOK: "send_test_webhook",
+	SEND_TEST_NOTIFICATION: "send_test_notification",
 	CLOSE_SPLASHSCREEN: "close_splashscreen",
 	CHECK_FOR_UPDATE: "check_for_update",
 	DOWNLOAD_AND_INSTALL_UPDATE: "download_and_install_update",
@@ -17,6 +17,8 @@ export const MESSAGES = {
 		IN_STOCK: "In Stock",
 		OUT_OF_STOCK: "Out of Stock",
 		BACK_ORDER: "Back Order",
+		LIMITED_AVAILABILITY: "Limited Stock",
+		PRE_ORDER: "Pre-order",
 		UNKNOWN: "Unknown",
 		BOT_PROTECTION:
 			"This site has bot protection. Enable headless browser in settings to check this site.",
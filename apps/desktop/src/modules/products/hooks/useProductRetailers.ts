@@ -2,7 +2,10 @@ import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
 
 import { COMMANDS, QUERY_KEYS } from "@/constants";
-import type { ProductRetailerResponse } from "@/modules/products/types";
+import type {
+	AddProductRetailerResponse,
+	ProductRetailerResponse,
+} from "@/modules/products/types";
 
 export interface AddRetailerInput {
 	product_id: string;
@@ -36,7 +39,7 @@ export function useProductRetailers(productId: string) {
 
 	const addMutation = useMutation({
 		mutationFn: (input: AddRetailerInput) =>
-			invoke<ProductRetailerResponse>(COMMANDS.ADD_PRODUCT_RETAILER, {
+			invoke<AddProductRetailerResponse>(COMMANDS.ADD_PRODUCT_RETAILER, {
 				input,
 			}),
 		onSuccess: () => {
@@ -264,11 +264,19 @@ export interface DisplayPrice {
 
 /**
  * Resolve the best display price from an availability check.
- * Prefers `lowest_price_*` fields (multi-retailer cheapest) over the single-check `price_*` fields.
+ * Prefers the multi-retailer cheapest price (converted to the preferred currency when
+ * available, then `lowest_price_*`) over the single-check `price_*` fields.
  */
 export function getDisplayPrice(
 	check: AvailabilityCheckResponse | null | undefined,
 ): DisplayPrice {
+	if (check?.lowest_normalized_price_minor_units != null) {
+		return {
+			price: check.lowest_normalized_price_minor_units,
+			currency: check.lowest_normalized_currency,
+			exponent: check.lowest_normalized_currency_exponent ?? 2,
+		};
+	}
 	return {
 		price: check?.lowest_price_minor_units ?? check?.price_minor_units ?? null,
 		currency: check?.lowest_price_currency ?? check?.price_currency ?? null,
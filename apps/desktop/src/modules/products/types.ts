@@ -14,10 +14,132 @@ export interface ProductResponse {
 	currency: string | null;
 	/** User-defined display order (0 = first) */
 	sort_order: number;
+	/** Background check interval override in minutes (null = global interval) */
+	check_interval_minutes: number | null;
+	/** Notify when the price drops to or below this amount (minor units) */
+	target_price_minor_units: number | null;
+	/** ISO 4217 currency of the target price (null = same as the scraped price) */
+	target_price_currency: string | null;
+	/** Paused products are skipped by background and bulk checks */
+	is_paused: boolean;
+	/** Archived products are hidden from the list and never checked, but keep their history */
+	is_archived: boolean;
+	/** Stock-only products are checked without recording prices */
+	track_price: boolean;
+	/** Locked currencies are never changed by auto-detection */
+	currency_locked: boolean;
+	/** ISO 8601 timestamp until which notifications are snoozed (checks still run) */
+	notifications_snoozed_until: string | null;
 	/** ISO 8601 timestamp when the product was added */
 	created_at: string;
 	/** ISO 8601 timestamp of the last update */
 	updated_at: string;
+	/** Lowest/highest recorded prices (only included by `get_product`) */
+	price_extremes?: PriceExtremes;
+	/** Restock frequency over the last 90 days (only included by `get_product`) */
+	restock_stats?: RestockStats;
+	/** ISO 8601 timestamp of the first in-stock check (only included by `get_product`, once it has been in stock) */
+	first_in_stock_at?: string;
+	/** Price trend over the last 30 days (only included by `get_product`) */
+	price_trend?: PriceTrend;
+}
+
+/**
+ * A price recorded at a point in time.
+ */
+export interface PricePoint {
+	/** Price in minor units (e.g., cents) */
+	price_minor_units: number;
+	/** ISO 4217 currency code */
+	price_currency: string | null;
+	/** ISO 8601 timestamp of the check that recorded this price */
+	checked_at: string;
+}
+
+/**
+ * Lowest and highest prices ever recorded for a product.
+ */
+export interface PriceExtremes {
+	lowest: PricePoint | null;
+	highest: PricePoint | null;
+	/** ISO 8601 timestamp of the product's first check */
+	first_checked_at: string | null;
+}
+
+/**
+ * How often a product came back in stock within a time window.
+ */
+export interface RestockStats {
+	/** ISO 8601 timestamp of the start of the window */
+	since: string;
+	/** Number of out of stock -> in stock transitions in the window */
+	restock_count: number;
+	/** Average seconds between consecutive restocks (null with fewer than two) */
+	average_restock_interval_secs: number | null;
+}
+
+/**
+ * Whether a product's price is rising, falling or stable over a window.
+ */
+export interface PriceTrend {
+	direction: "rising" | "falling" | "stable";
+	/** Change along the fitted trend line, as a percentage of the starting price */
+	change_percent: number;
+	/** Number of priced checks in the window */
+	sample_count: number;
+	/** Window length in days */
+	days: number;
+}
+
+/**
+ * Two products' daily average prices aligned on the same dates.
+ */
+export interface ProductComparison {
+	product_a_id: string;
+	product_b_id: string;
+	/** ISO 4217 currency both series are converted into */
+	currency: string;
+	/** One point per UTC day, oldest first */
+	points: ComparisonPoint[];
+	/** Change from first to last priced day, in percent (null with fewer than two) */
+	a_change_percent: number | null;
+	b_change_percent: number | null;
+}
+
+export interface ComparisonPoint {
+	/** ISO 8601 start of the day (UTC) */
+	date: string;
+	/** Average price in minor units, or null when there is no price that day */
+	a_minor_units: number | null;
+	b_minor_units: number | null;
+}
+
+/**
+ * Stock counts and in-stock value across all unarchived products.
+ */
+export interface PortfolioSummary {
+	/** ISO 4217 currency of the total (the preferred currency) */
+	currency: string;
+	/** Sum of each in-stock product's cheapest in-stock price, in minor units */
+	total_value_minor_units: number;
+	in_stock_count: number;
+	/** Out of stock, back-ordered or pre-order only */
+	out_of_stock_count: number;
+	/** Never checked, or the latest checks couldn't tell */
+	unknown_count: number;
+	/** In-stock products left out of the total for lacking a convertible price */
+	unpriced_in_stock_count: number;
+}
+
+/**
+ * Outcome of importing products from pasted URLs.
+ */
+export interface ImportReport {
+	created: number;
+	/** URLs already tracked, or repeated in the pasted text */
+	skipped: number;
+	/** URLs that couldn't be parsed or saved */
+	failed: number;
 }
 
 /**
@@ -36,23 +158,118 @@ export interface ProductRetailerResponse {
 	label: string | null;
 	/** User-defined display order (0 = first) */
 	sort_order: number;
+	/** Currency detected for this link (ISO 4217), may differ from the product's */
+	currency: string | null;
+	/** Variant attributes checked on this link (e.g. `{ size: "M", color: "black" }`) */
+	variant_selector: Record<string, string> | null;
 	/** ISO 8601 timestamp when the link was created */
 	created_at: string;
 }
 
+/**
+ * A user-defined tag for grouping products (e.g., "kitchen", "gpu").
+ * Names are stored trimmed and lowercase.
+ */
+export interface TagResponse {
+	/** Unique identifier (UUID) */
+	id: string;
+	/** Normalized tag name */
+	name: string;
+	/** ISO 8601 timestamp when the tag was created */
+	created_at: string;
+}
+
 /**
  * Availability status parsed from Schema.org data.
  * - `in_stock`: Product is available for purchase
+ * - `limited_availability`: Product is available but stock is running low
  * - `out_of_stock`: Product is not available
  * - `back_order`: Product can be ordered but ships later
+ * - `pre_order`: Product has not launched yet but can be pre-ordered
  * - `unknown`: Status could not be determined
  */
 export type AvailabilityStatus =
 	| "in_stock"
+	| "limited_availability"
 	| "out_of_stock"
 	| "back_order"
+	| "pre_order"
 	| "unknown";
 
+/**
+ * Category of a failed availability check.
+ * - `bot_protection`: Blocked by a bot challenge
+ * - `http_status`: The site responded with an error status
+ * - `network`: The request failed before a response arrived (e.g. TLS error)
+ * - `unreachable`: The site couldn't be reached (DNS lookup failed or connection refused)
+ * - `timeout`: The site didn't respond in time
+ * - `scraping`: The page loaded but no availability data was found
+ * - `validation`: The product can't be checked (e.g. missing or unsupported URL)
+ * - `internal`: Unexpected failure inside the app
+ * - `implausible_price`: The scraped price was far below the recent average
+ *   (likely a site glitch) and was ignored
+ *
+ * `http_status`, `network`, `unreachable` and `timeout` are transient and worth retrying.
+ */
+export type CheckErrorKind =
+	| "bot_protection"
+	| "http_status"
+	| "network"
+	| "unreachable"
+	| "timeout"
+	| "scraping"
+	| "validation"
+	| "internal"
+	| "implausible_price";
+
+/**
+ * How a check's status was found: "high" from structured data (Schema.org,
+ * site APIs), "low" when inferred from page heuristics (add-to-cart buttons,
+ * meta tags).
+ */
+export type CheckConfidence = "high" | "low";
+
+/**
+ * Scraper success over the most recent checks against one host.
+ */
+export interface DomainHealth {
+	host: string;
+	total_checks: number;
+	success_count: number;
+	failure_count: number;
+	bot_protection_count: number;
+	/** Share of checks that succeeded, from 0 to 1 */
+	success_rate: number;
+	/** Most frequent error kind among the failures (null without any) */
+	most_common_error_kind: CheckErrorKind | null;
+}
+
+/**
+ * How consistently checks against a retailer succeed.
+ */
+export type ReliabilityLabel = "reliable" | "mixed" | "unreliable";
+
+/**
+ * One retailer link of a product with its latest check, for comparing retailers.
+ */
+export interface RetailerComparison {
+	product_retailer_id: string;
+	retailer_id: string;
+	domain: string;
+	url: string;
+	label: string | null;
+	/** Latest check at this link (null if never checked) */
+	status: AvailabilityStatus | null;
+	price_minor_units: number | null;
+	price_currency: string | null;
+	normalized_price_minor_units: number | null;
+	normalized_currency: string | null;
+	checked_at: string | null;
+	/** Share of the retailer's recent checks that succeeded, from 0 to 1 (null until enough checks) */
+	reliability_score: number | null;
+	reliability_label: ReliabilityLabel | null;
+}
+
 /**
  * Result of a single availability check for a product.
  */
@@ -69,6 +286,10 @@ export interface AvailabilityCheckResponse {
 	raw_availability: string | null;
 	/** Error message if the check failed */
 	error_message: string | null;
+	/** Category of the failure, null for successful checks */
+	error_kind: CheckErrorKind | null;
+	/** How the status was found, null for failed checks */
+	confidence: CheckConfidence | null;
 	/** ISO 8601 timestamp when the check was performed */
 	checked_at: string;
 	/** Price in minor units (smallest currency unit) */
@@ -91,6 +312,14 @@ export interface AvailabilityCheckResponse {
 	lowest_price_currency: string | null;
 	/** Currency exponent for the lowest price */
 	lowest_currency_exponent: number | null;
+	/** Lowest price converted to the user's preferred currency (minor units) */
+	lowest_normalized_price_minor_units: number | null;
+	/** Currency code of the converted lowest price */
+	lowest_normalized_currency: string | null;
+	/** Currency exponent for the converted lowest price */
+	lowest_normalized_currency_exponent: number | null;
+	/** True if retailers were compared in native currencies because an exchange rate was missing */
+	lowest_price_normalization_skipped: boolean;
 	/** Price normalized to the user's preferred currency (minor units) */
 	normalized_price_minor_units: number | null;
 	/** Currency code of the normalized price */
@@ -99,6 +328,119 @@ export interface AvailabilityCheckResponse {
 	normalized_currency_exponent: number | null;
 }
 
+/**
+ * A product whose status changed since a given time, returned by `get_changes_since`.
+ */
+export interface ProductChangeResponse {
+	product: ProductResponse;
+	/** Latest check, whose status differs from the one before the given time */
+	latest_check: AvailabilityCheckResponse;
+}
+
+/**
+ * A product's largest price drop within a time window.
+ * Field names match the Rust `PriceDrop` struct.
+ */
+export interface PriceDrop {
+	product_id: string;
+	/** Retailer link the drop was seen at (null for legacy checks) */
+	product_retailer_id: string | null;
+	/** Highest price in the window before the latest check */
+	previous_price_minor_units: number;
+	current_price_minor_units: number;
+	/** Preferred currency, or the retailer's own when no exchange rate was available */
+	currency: string;
+	drop_percent: number;
+	/** ISO 8601 timestamp of the latest check */
+	current_checked_at: string;
+}
+
+/**
+ * A product with a recent price drop, returned by `get_recent_deals`.
+ */
+export interface RecentDealResponse {
+	product: ProductResponse;
+	price_drop: PriceDrop;
+}
+
+/**
+ * Bucket size for server-side price history aggregation.
+ */
+export type PriceBucket = "hourly" | "daily" | "weekly";
+
+/**
+ * A single bucket of averaged price history returned by `get_price_history`.
+ */
+export interface PricePointResponse {
+	/** ISO 8601 timestamp of the bucket start (UTC) */
+	bucket_start: string;
+	/** Average price in minor units, normalized when available; null if no check had a price */
+	average_price_minor_units: number | null;
+}
+
+/**
+ * Extraction strategy that produced a scraping result.
+ */
+export type ExtractionStrategy =
+	| "shopify_products_json"
+	| "schema_org"
+	| "gtm"
+	| "shopify"
+	| "meta"
+	| "site_specific";
+
+/**
+ * Lowest and highest price of a Schema.org AggregateOffer, in minor units.
+ */
+export interface PriceRange {
+	low_minor_units: number;
+	high_minor_units: number;
+	/** Number of offers the range covers, when given */
+	offer_count: number | null;
+}
+
+/**
+ * Dry-run scrape result returned by `test_scrape_url`.
+ */
+export interface ScrapeDiagnostics {
+	url: string;
+	/** Strategy that produced the result, or null if every strategy failed */
+	strategy: ExtractionStrategy | null;
+	/** Number of JSON-LD blocks found in the page */
+	json_ld_block_count: number;
+	/** True if the plain HTTP request hit bot protection and a fallback was used */
+	bot_protection_detected: boolean;
+	status: AvailabilityStatus;
+	raw_availability: string | null;
+	price_minor_units: number | null;
+	price_currency: string | null;
+	raw_price: string | null;
+	/** Low-to-high range when the page lists an AggregateOffer */
+	price_range: PriceRange | null;
+	/** Why extraction failed, when no strategy matched */
+	error: string | null;
+	/** Normalized address the URL redirected to (https, www or trailing slash), offered as a replacement */
+	canonical_url: string | null;
+}
+
+/**
+ * Dry-run scrape of a retailer URL done when it is added
+ * (only with `validate_url_on_create` enabled).
+ */
+export interface UrlValidation {
+	/** Scrape result, or null if the page couldn't be fetched */
+	diagnostics: ScrapeDiagnostics | null;
+	/** Why the URL may not be trackable; the link is added either way */
+	warning: string | null;
+}
+
+/**
+ * Retailer link returned by `add_product_retailer`.
+ */
+export interface AddProductRetailerResponse extends ProductRetailerResponse {
+	validation: UrlValidation | null;
+}
+
 /**
  * Result of checking a single product during bulk check operation.
  */
@@ -123,14 +465,26 @@ export interface BulkCheckResult {
 	price_currency: string | null;
 	/** Currency exponent (number of decimal places: 0 for JPY, 2 for USD, 3 for KWD) */
 	currency_exponent: number | null;
+	/** Strikethrough ("was") price in minor units, same currency as the price */
+	compare_at_minor_units: number | null;
+	/** Percentage off the compare-at price (e.g. 30 for "30% off") */
+	discount_percent: number | null;
 	/** Today's average price in minor units for daily comparison */
 	today_average_price_minor_units: number | null;
 	/** Yesterday's average price in minor units for daily comparison */
 	yesterday_average_price_minor_units: number | null;
 	/** True if today's average price is lower than yesterday's average */
 	is_price_drop: boolean;
+	/** True if product changed from limited_availability to out_of_stock */
+	is_sold_out: boolean;
 	/** Error message if this product's check failed */
 	error: string | null;
+	/** Category of the failure, null if the check succeeded */
+	error_kind: CheckErrorKind | null;
+	/** "low" when the status was inferred from page heuristics, null if the check failed */
+	confidence: CheckConfidence | null;
+	/** True if the product's notifications are snoozed, so its events don't notify */
+	notifications_snoozed: boolean;
 }
 
 /**
@@ -147,10 +501,27 @@ export interface BulkCheckSummary {
 	back_in_stock_count: number;
 	/** Count of products with price drops */
 	price_drop_count: number;
+	/** Count of products that sold out after limited availability */
+	sold_out_count: number;
 	/** Individual results for each product */
 	results: BulkCheckResult[];
 }
 
+/**
+ * Recorded summary of a "check all" run.
+ * Field names match the Rust `BulkCheckRunResponse` struct.
+ */
+export interface BulkCheckRun {
+	id: string;
+	/** When the run finished (RFC 3339, UTC) */
+	ran_at: string;
+	total: number;
+	successful: number;
+	failed: number;
+	back_in_stock_count: number;
+	price_drop_count: number;
+}
+
 /**
  * Event emitted during bulk check for progress updates.
  * Field names match the Rust `BulkCheckProgressEvent` struct.
@@ -46,8 +46,10 @@ const STATUS_DOT_CONFIG: Record<
 	{ color: string; label: string }
 > = {
 	in_stock: { color: "bg-green-500", label: "In Stock" },
+	limited_availability: { color: "bg-orange-500", label: "Limited Stock" },
 	out_of_stock: { color: "bg-red-500", label: "Out of Stock" },
 	back_order: { color: "bg-yellow-500", label: "Back Order" },
+	pre_order: { color: "bg-blue-500", label: "Pre-order" },
 	unknown: { color: "bg-gray-400", label: "Unknown" },
 };
 
@@ -10,6 +10,11 @@ export const STATUS_BADGE_CONFIG: Record<
 		className:
 			"bg-green-100 text-green-800 dark:bg-green-900 dark:text-green-200",
 	},
+	limited_availability: {
+		label: MESSAGES.AVAILABILITY.LIMITED_AVAILABILITY,
+		className:
+			"bg-orange-100 text-orange-800 dark:bg-orange-900 dark:text-orange-200",
+	},
 	out_of_stock: {
 		label: MESSAGES.AVAILABILITY.OUT_OF_STOCK,
 		className: "bg-red-100 text-red-800 dark:bg-red-900 dark:text-red-200",
@@ -19,6 +24,10 @@ export const STATUS_BADGE_CONFIG: Record<
 		className:
 			"bg-yellow-100 text-yellow-800 dark:bg-yellow-900 dark:text-yellow-200",
 	},
+	pre_order: {
+		label: MESSAGES.AVAILABILITY.PRE_ORDER,
+		className: "bg-blue-100 text-blue-800 dark:bg-blue-900 dark:text-blue-200",
+	},
 	unknown: {
 		label: MESSAGES.AVAILABILITY.UNKNOWN,
 		className: "bg-gray-100 text-gray-800 dark:bg-gray-800 dark:text-gray-200",
@@ -20,8 +20,8 @@ import {
 import { useProductDialogs } from "@/modules/products/hooks/useProductDialogs";
 import { useProducts } from "@/modules/products/hooks/useProducts";
 import type {
+	AddProductRetailerResponse,
 	BulkCheckSummary,
-	ProductRetailerResponse,
 } from "@/modules/products/types";
 import { DeleteConfirmDialog } from "@/modules/products/ui/components/delete-confirm-dialog";
 import { ProductFormDialog } from "@/modules/products/ui/components/product-form-dialog";
@@ -144,13 +144,19 @@ export function ProductsView() {
 
 			for (const entry of retailerEntries) {
 				try {
-					await invoke<ProductRetailerResponse>(COMMANDS.ADD_PRODUCT_RETAILER, {
-						input: {
-							product_id: product.id,
-							url: entry.url.trim(),
-							label: entry.label.trim() || null,
+					const added = await invoke<AddProductRetailerResponse>(
+						COMMANDS.ADD_PRODUCT_RETAILER,
+						{
+							input: {
+								product_id: product.id,
+								url: entry.url.trim(),
+								label: entry.label.trim() || null,
+							},
 						},
-					});
+					);
+					if (added.validation?.warning) {
+						toast.warning(`${entry.url}: ${added.validation.warning}`);
+					}
 				} catch {
 					toast.error(`Failed to add retailer: ${entry.url}`);
 				}
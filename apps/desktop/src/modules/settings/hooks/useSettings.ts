@@ -20,16 +20,82 @@ export interface Settings {
 	sidebar_expanded: boolean;
 	background_check_enabled: boolean;
 	background_check_interval_minutes: number;
+	/** Start of the daily hours background checks run in, "HH:MM" in display_timezone (empty = all day) */
+	background_check_active_hours_start: string;
+	/** End of the active hours, "HH:MM" (may be earlier than the start to cross midnight) */
+	background_check_active_hours_end: string;
+	/** Comma-separated days background checks run on, e.g. "mon,tue,wed,thu,fri" (empty = every day) */
+	background_check_days: string;
+	/** Comma-separated hosts whose TLS certificates aren't verified (self-signed stores; exact hosts only) */
+	insecure_hosts: string;
+	/** Redirects a page may follow before a check fails (0-20) */
+	max_redirects: number;
+	/** JSON-LD blocks larger than this many KB are skipped (64-65536) */
+	max_json_ld_block_kb: number;
 	enable_headless_browser: boolean;
+	/** Keep a persistent headless browser profile per site so passed challenges are remembered */
+	headless_profile_reuse: boolean;
+	/** Timeout for plain HTTP page fetches (5-120 seconds) */
+	http_timeout_secs: number;
+	/** Page load timeout for the headless browser (10-300 seconds) */
+	headless_timeout_secs: number;
+	/** Minimum drop vs yesterday's average price that counts as a price drop (0-100 percent) */
+	price_drop_threshold_percent: number;
+	/** Maximum checks running at once during a bulk check; same-store checks never overlap (1-10) */
+	bulk_check_concurrency: number;
+	/** Comma-separated hosts that always block plain HTTP and go straight to the headless browser */
+	force_headless_domains: string;
+	/** Days of check history to keep; the latest check per product is always kept (0 = forever) */
+	data_retention_days: number;
+	/** Scrape retailer URLs once when added and warn when no product data is found */
+	validate_url_on_create: boolean;
+	respect_robots_txt: boolean;
+	/** Which variant a multi-variant product reports when the URL doesn't pick one */
+	product_group_strategy: "first" | "cheapest_in_stock";
+	/** Chrome/Chromium executable for the headless browser (empty = search the usual install locations) */
+	headless_browser_path: string;
+	/** Send each store a User-Agent from a pool of current desktop browsers */
+	rotate_user_agent: boolean;
+	/** Scraped prices below this percent of the recent average are ignored as glitches (0 = off) */
+	min_plausible_price_percent: number;
 	color_palette: string;
 	display_timezone: string;
 	date_format: string;
 	preferred_currency: string;
+	/** Discord/Slack webhook that receives notifications (empty = disabled) */
+	notification_webhook_url: string;
+	/** Start of daily quiet hours, "HH:MM" in display_timezone (empty = disabled) */
+	quiet_hours_start: string;
+	/** End of daily quiet hours, "HH:MM" (may be earlier than the start to cross midnight) */
+	quiet_hours_end: string;
+	/** "per_event" notifies on each background check, "digest" sends one summary per digest_interval */
+	notification_mode: "per_event" | "digest";
+	/** How often the digest is sent in digest mode */
+	digest_interval: "daily" | "weekly";
 	updated_at: string;
 }
 
 export type UpdateSettingsInput = Partial<Omit<Settings, "updated_at">>;
 
+/**
+ * Custom request headers/cookies sent to a single domain (e.g. member pricing).
+ */
+export interface DomainRequestOverrides {
+	headers: Record<string, string>;
+	cookies: Record<string, string>;
+}
+
+/**
+ * Whether a Chrome/Chromium binary was found for the headless browser.
+ */
+export interface HeadlessBrowserStatus {
+	available: boolean;
+	/** Binary that will be launched, when one was found */
+	path: string | null;
+	/** Why no browser is available */
+	error: string | null;
+}
+
 /**
  * Return type for the useSettings hook.
  * Provides explicit typing for better API discoverability and IDE support.
@@ -1,3 +1,11 @@
+import { Label } from "@/components/ui/label";
+import {
+	Select,
+	SelectContent,
+	SelectItem,
+	SelectTrigger,
+	SelectValue,
+} from "@/components/ui/select";
 import type {
 	Settings,
 	UpdateSettingsInput,
@@ -26,6 +34,69 @@ export function AdvancedCard({ settings, onUpdate }: AdvancedCardProps) {
 					onUpdate({ enable_headless_browser: checked })
 				}
 			/>
+			<SettingsSwitchRow
+				id="headless-profile-reuse"
+				label="Remember site sessions"
+				description="Keep a browser profile per site so passed bot checks are reused"
+				checked={settings.headless_profile_reuse}
+				onCheckedChange={(checked) =>
+					onUpdate({ headless_profile_reuse: checked })
+				}
+			/>
+			<SettingsSwitchRow
+				id="validate-url-on-create"
+				label="Verify new URLs"
+				description="Check retailer URLs once when added and warn if no product data is found"
+				checked={settings.validate_url_on_create}
+				onCheckedChange={(checked) =>
+					onUpdate({ validate_url_on_create: checked })
+				}
+			/>
+			<SettingsSwitchRow
+				id="respect-robots-txt"
+				label="Respect robots.txt"
+				description="Skip pages a site's robots.txt disallows and honor its crawl delay"
+				checked={settings.respect_robots_txt}
+				onCheckedChange={(checked) =>
+					onUpdate({ respect_robots_txt: checked })
+				}
+			/>
+			<SettingsSwitchRow
+				id="rotate-user-agent"
+				label="Rotate browser identity"
+				description="Vary the browser each store sees to reduce blocking"
+				checked={settings.rotate_user_agent}
+				onCheckedChange={(checked) =>
+					onUpdate({ rotate_user_agent: checked })
+				}
+			/>
+			<div className="flex items-center justify-between">
+				<div className="space-y-0.5">
+					<Label htmlFor="product-group-strategy">Variant price</Label>
+					<p className="text-muted-foreground text-xs">
+						Which variant to track when the URL doesn&apos;t select one
+					</p>
+				</div>
+				<Select
+					value={settings.product_group_strategy}
+					onValueChange={(value) =>
+						onUpdate({
+							product_group_strategy:
+								value as Settings["product_group_strategy"],
+						})
+					}
+				>
+					<SelectTrigger id="product-group-strategy" className="w-44">
+						<SelectValue />
+					</SelectTrigger>
+					<SelectContent>
+						<SelectItem value="first">First variant</SelectItem>
+						<SelectItem value="cheapest_in_stock">
+							Cheapest in stock
+						</SelectItem>
+					</SelectContent>
+				</Select>
+			</div>
 		</SettingsCard>
 	);
 }
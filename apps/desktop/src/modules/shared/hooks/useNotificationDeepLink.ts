@@ -0,0 +1,60 @@
+import { useNavigate } from "@tanstack/react-router";
+import {
+	addPluginListener,
+	isTauri,
+	type PluginListener,
+} from "@tauri-apps/api/core";
+import { useEffect } from "react";
+
+/** Payload of the notification plugin's `actionPerformed` event */
+interface NotificationActionEvent {
+	notification: {
+		extra?: Record<string, unknown>;
+	};
+}
+
+/**
+ * Hook that opens a product when its notification is clicked
+ *
+ * Product notifications carry a `product_id` extra (set by the backend). Outside
+ * the desktop app, or on platforms whose notifications don't report clicks,
+ * this does nothing.
+ */
+export function useNotificationDeepLink() {
+	const navigate = useNavigate();
+
+	useEffect(() => {
+		if (!isTauri()) {
+			return;
+		}
+
+		let listener: PluginListener | undefined;
+		let cancelled = false;
+
+		addPluginListener<NotificationActionEvent>(
+			"notification",
+			"actionPerformed",
+			({ notification }) => {
+				const productId = notification.extra?.product_id;
+				if (typeof productId === "string") {
+					navigate({ to: "/products/$id", params: { id: productId } });
+				}
+			},
+		)
+			.then((registered) => {
+				if (cancelled) {
+					registered.unregister();
+				} else {
+					listener = registered;
+				}
+			})
+			.catch(() => {
+				// Click actions aren't supported on this platform
+			});
+
+		return () => {
+			cancelled = true;
+			listener?.unregister();
+		};
+	}, [navigate]);
+}
@@ -8,6 +8,7 @@ import type { ReactNode } from "react";
 import { useCallback } from "react";
 import { Toaster } from "@/components/ui/sonner";
 import { useSettings } from "@/modules/settings/hooks/useSettings";
+import { useNotificationDeepLink } from "@/modules/shared/hooks/useNotificationDeepLink";
 import { PaletteProvider } from "@/modules/shared/providers/palette-provider";
 import { ThemeProvider } from "@/modules/shared/providers/theme-provider";
 import { ThemeSync } from "@/modules/shared/providers/theme-sync";
@@ -59,6 +60,8 @@ function PaletteProviderWithSettings({ children }: { children: ReactNode }) {
 }
 
 export function RootComponent() {
+	useNotificationDeepLink();
+
 	return (
 		<>
 			<HeadContent />